@@ -0,0 +1,407 @@
+//! Adoption of models downloaded by other tools (`adopt` command)
+//!
+//! Scans a directory for model folders - huggingface-cli cache entries
+//! (`models--org--name/snapshots/<commit>`), `--local-dir` copies and this
+//! tool's own `author/model` layout - works out which Hub repository each
+//! one is a copy of, and adds the files that match the repository's tree by
+//! path and size (and hash, on request) to the registry as complete
+//! downloads. They then show as downloaded and can be verified, or diffed
+//! against the Hub, like files this tool fetched.
+
+use crate::api;
+use crate::models::{
+    AccessFilter, ContentFilter, DownloadMetadata, DownloadRegistry, DownloadStatus, ExpectedHash,
+    ModelFile, ParamRange, SearchLimits, SortDirection, SortField,
+};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Directory levels below the scanned directory searched for model folders
+const MAX_DEPTH: usize = 4;
+
+/// Files that make a directory a model folder even without weights
+const MARKER_FILES: &[&str] = &[
+    "config.json",
+    "model_index.json",
+    "modules.json",
+    "adapter_config.json",
+];
+
+/// Search results tried for a folder whose location names no repository
+const SEARCH_CANDIDATES: usize = 5;
+
+/// A directory holding (part of) a copy of a repository
+#[derive(Debug, Clone)]
+pub struct LocalFolder {
+    pub dir: PathBuf,
+    /// Repository IDs the folder's location suggests, most likely first
+    pub candidates: Vec<String>,
+    /// Commit the copy was made from, when the tool that made it recorded one
+    pub commit: Option<String>,
+}
+
+/// What adopting one folder found
+#[derive(Debug, Clone, Serialize)]
+pub struct AdoptedFolder {
+    pub dir: String,
+    /// Repository the files matched; `None` if no candidate did
+    pub model_id: Option<String>,
+    pub commit: Option<String>,
+    /// Registry entries of the files that matched
+    pub files: Vec<DownloadMetadata>,
+    /// Matched files the registry already listed
+    pub already_known: usize,
+    /// Local files not in the repository at that size (or with that hash)
+    pub unmatched: Vec<String>,
+}
+
+/// Model folders under `root`; with `model_id` the whole of `root` is
+/// taken as a copy of that repository
+pub fn find_folders(root: &Path, model_id: Option<&str>) -> Vec<LocalFolder> {
+    if let Some(model_id) = model_id {
+        let dir = cache_snapshot(root).map_or_else(|| root.to_path_buf(), |(dir, _)| dir);
+        return vec![LocalFolder {
+            commit: folder_commit(root, &dir),
+            dir,
+            candidates: vec![model_id.to_string()],
+        }];
+    }
+
+    let mut folders = Vec::new();
+    collect_folders(root, 0, &mut folders);
+    folders
+}
+
+fn collect_folders(dir: &Path, depth: usize, folders: &mut Vec<LocalFolder>) {
+    if let Some(folder) = cache_folder(dir) {
+        folders.push(folder);
+        return;
+    }
+    if is_model_folder(dir) {
+        folders.push(LocalFolder {
+            dir: dir.to_path_buf(),
+            candidates: guess_model_ids(dir),
+            commit: folder_commit(dir, dir),
+        });
+        return;
+    }
+    if depth >= MAX_DEPTH {
+        return;
+    }
+
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let mut subdirs: Vec<PathBuf> = entries
+        .flatten()
+        .filter(|e| e.file_type().is_ok_and(|t| t.is_dir()))
+        .filter(|e| !e.file_name().to_string_lossy().starts_with('.'))
+        .map(|e| e.path())
+        .collect();
+    subdirs.sort();
+    for subdir in subdirs {
+        collect_folders(&subdir, depth + 1, folders);
+    }
+}
+
+/// A huggingface-cli cache entry (`models--org--name`), read from the
+/// snapshot `refs/main` points at
+fn cache_folder(dir: &Path) -> Option<LocalFolder> {
+    let name = dir.file_name()?.to_string_lossy();
+    let (author, model) = name.strip_prefix("models--")?.split_once("--")?;
+    let (snapshot, commit) = cache_snapshot(dir)?;
+    Some(LocalFolder {
+        dir: snapshot,
+        candidates: vec![format!("{}/{}", author, model)],
+        commit,
+    })
+}
+
+/// Snapshot directory of a cache entry and its commit: the one `refs/main`
+/// names, or else the most recently written
+fn cache_snapshot(dir: &Path) -> Option<(PathBuf, Option<String>)> {
+    let snapshots = dir.join("snapshots");
+    if let Ok(commit) = fs::read_to_string(dir.join("refs").join("main")) {
+        let commit = commit.trim().to_string();
+        if snapshots.join(&commit).is_dir() {
+            return Some((snapshots.join(&commit), Some(commit)));
+        }
+    }
+
+    let latest = fs::read_dir(&snapshots)
+        .ok()?
+        .flatten()
+        .filter(|e| e.file_type().is_ok_and(|t| t.is_dir()))
+        .max_by_key(|e| e.metadata().and_then(|m| m.modified()).ok())?;
+    let commit = latest.file_name().to_string_lossy().to_string();
+    Some((latest.path(), Some(commit)))
+}
+
+/// Commit of a snapshot, or the one huggingface-cli `--local-dir` records
+/// on the first line of each `.cache/huggingface/download/*.metadata` file
+fn folder_commit(root: &Path, dir: &Path) -> Option<String> {
+    if let Some((snapshot, commit)) = cache_snapshot(root) {
+        if snapshot == dir {
+            return commit;
+        }
+    }
+
+    fs::read_dir(dir.join(".cache").join("huggingface").join("download"))
+        .ok()?
+        .flatten()
+        .filter(|e| e.file_name().to_string_lossy().ends_with(".metadata"))
+        .find_map(|e| {
+            let content = fs::read_to_string(e.path()).ok()?;
+            let commit = content.lines().next()?.trim();
+            (commit.len() == 40 && commit.chars().all(|c| c.is_ascii_hexdigit()))
+                .then(|| commit.to_string())
+        })
+}
+
+/// Whether a directory directly holds model weights or a model config
+fn is_model_folder(dir: &Path) -> bool {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return false;
+    };
+    entries.flatten().any(|e| {
+        let name = e.file_name().to_string_lossy().to_string();
+        e.path().is_file()
+            && (MARKER_FILES.contains(&name.as_str()) || api::is_model_weight_file(&name))
+    })
+}
+
+/// Repository IDs a folder's path suggests: `author/model` from its parent
+/// and name (this tool's and the usual `--local-dir` layout), and
+/// `author--model` folder names
+fn guess_model_ids(dir: &Path) -> Vec<String> {
+    let component = |path: Option<&Path>| {
+        path.and_then(|p| p.file_name())
+            .map(|name| name.to_string_lossy().to_string())
+            .and_then(|name| crate::download::sanitize_path_component(&name))
+    };
+    let mut ids = Vec::new();
+    let Some(name) = component(Some(dir)) else {
+        return ids;
+    };
+    if let Some((author, model)) = name.split_once("--") {
+        ids.push(format!("{}/{}", author, model));
+    }
+    if let Some(author) = component(dir.parent()) {
+        ids.push(format!("{}/{}", author, name));
+    }
+    ids
+}
+
+/// Files under a folder as (path relative to it, full path, size), leaving
+/// out hidden entries and unfinished downloads
+fn local_files(dir: &Path) -> Vec<(String, PathBuf, u64)> {
+    let mut files = Vec::new();
+    let mut pending = vec![(dir.to_path_buf(), String::new())];
+    while let Some((current, prefix)) = pending.pop() {
+        let Ok(entries) = fs::read_dir(&current) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name.starts_with('.') || name.ends_with(".incomplete") {
+                continue;
+            }
+            let path = entry.path();
+            let relative = format!("{}{}", prefix, name);
+            // Follows symlinks, which is what cache snapshots are made of
+            match fs::metadata(&path) {
+                Ok(meta) if meta.is_dir() => pending.push((path, format!("{}/", relative))),
+                Ok(meta) if meta.is_file() => files.push((relative, path, meta.len())),
+                _ => {}
+            }
+        }
+    }
+    files.sort();
+    files
+}
+
+/// Pair local files with repository files: the same path and size, or else
+/// the only repository file with that name and size
+fn match_files<'a>(
+    local: &'a [(String, PathBuf, u64)],
+    repo: &'a [ModelFile],
+) -> Vec<(&'a (String, PathBuf, u64), &'a ModelFile)> {
+    let by_path: HashMap<&str, &ModelFile> = repo
+        .iter()
+        .filter(|f| f.file_type == "file")
+        .map(|f| (f.path.as_str(), f))
+        .collect();
+    let mut by_name: HashMap<(&str, u64), Vec<&ModelFile>> = HashMap::new();
+    for file in by_path.values() {
+        let name = file.path.rsplit('/').next().unwrap_or(&file.path);
+        by_name.entry((name, file.size)).or_default().push(file);
+    }
+
+    local
+        .iter()
+        .filter_map(|entry| {
+            let (relative, _, size) = entry;
+            if let Some(file) = by_path.get(relative.as_str()).filter(|f| f.size == *size) {
+                return Some((entry, *file));
+            }
+            let name = relative.rsplit('/').next().unwrap_or(relative);
+            match by_name.get(&(name, *size)).map(Vec::as_slice) {
+                Some([file]) => Some((entry, *file)),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// Repositories named like the folder, for folders whose location doesn't
+/// say which one they are
+async fn search_model_ids(dir: &Path, token: Option<&String>) -> Vec<String> {
+    let Some(name) = dir.file_name().map(|n| n.to_string_lossy().to_string()) else {
+        return Vec::new();
+    };
+    let limits = SearchLimits {
+        page_size: 20,
+        max_results: 20,
+    };
+    let Ok(models) = api::fetch_models_filtered(
+        &name,
+        SortField::Downloads,
+        SortDirection::Descending,
+        0,
+        0,
+        ParamRange::default(),
+        AccessFilter::default(),
+        ContentFilter::default(),
+        limits,
+        token,
+    )
+    .await
+    else {
+        return Vec::new();
+    };
+    models
+        .into_iter()
+        .filter(|m| {
+            m.id.rsplit('/')
+                .next()
+                .is_some_and(|model| model.eq_ignore_ascii_case(&name))
+        })
+        .map(|m| m.id)
+        .take(SEARCH_CANDIDATES)
+        .collect()
+}
+
+/// Work out which repository `folder` is a copy of - the candidate whose
+/// tree matches the most files, falling back to a Hub search by folder
+/// name - and build registry entries for the matched files. With
+/// `check_hash`, files whose hash is known are only matched if their
+/// content hashes to it.
+pub async fn resolve(
+    folder: &LocalFolder,
+    check_hash: bool,
+    token: Option<&String>,
+) -> AdoptedFolder {
+    let local = local_files(&folder.dir);
+    let mut result = AdoptedFolder {
+        dir: folder.dir.display().to_string(),
+        model_id: None,
+        commit: folder.commit.clone(),
+        files: Vec::new(),
+        already_known: 0,
+        unmatched: Vec::new(),
+    };
+
+    let mut candidates = folder.candidates.clone();
+    let mut searched = false;
+    let mut best: Option<(String, Vec<ModelFile>, usize)> = None;
+    loop {
+        for model_id in candidates.drain(..) {
+            let tree = match &folder.commit {
+                Some(commit) => api::fetch_repo_files_at(&model_id, commit, token).await,
+                None => api::fetch_repo_files(&model_id, token).await,
+            };
+            let Ok(tree) = tree else {
+                continue;
+            };
+            let matched = match_files(&local, &tree).len();
+            if matched > best.as_ref().map_or(0, |(_, _, n)| *n) {
+                best = Some((model_id, tree, matched));
+            }
+        }
+        if best.is_some() || searched {
+            break;
+        }
+        searched = true;
+        candidates = search_model_ids(&folder.dir, token).await;
+    }
+
+    let Some((model_id, tree, _)) = best else {
+        result.unmatched = local.into_iter().map(|(relative, _, _)| relative).collect();
+        return result;
+    };
+
+    let matches = match_files(&local, &tree);
+    let mut matched_paths = HashSet::new();
+    for ((relative, path, size), file) in matches {
+        let expected = ExpectedHash::of(file.lfs.as_ref(), file.oid.as_deref());
+        if check_hash {
+            if let Some(expected) = expected.clone() {
+                let path = path.clone();
+                let matches = tokio::task::spawn_blocking(move || {
+                    crate::verification::file_matches_hash(&path, &expected)
+                })
+                .await;
+                if !matches!(matches, Ok(Ok(true))) {
+                    continue;
+                }
+            }
+        }
+
+        matched_paths.insert(relative.as_str());
+        let (expected_sha256, expected_git_blob) = ExpectedHash::fields(expected.as_ref());
+        result.files.push(DownloadMetadata {
+            model_id: model_id.clone(),
+            filename: file.path.clone(),
+            url: crate::download::file_url(&model_id, None, &file.path),
+            local_path: path.to_string_lossy().to_string(),
+            total_size: *size,
+            downloaded_size: *size,
+            status: DownloadStatus::Complete,
+            expected_sha256,
+            expected_git_blob,
+            commit: folder.commit.clone(),
+        });
+    }
+    result.unmatched = local
+        .iter()
+        .filter(|(relative, _, _)| !matched_paths.contains(relative.as_str()))
+        .map(|(relative, _, _)| relative.clone())
+        .collect();
+    result.model_id = Some(model_id);
+    result
+}
+
+/// Add the matched files of `folders` to the registry as complete
+/// downloads, leaving files it already lists (by local path) as they are;
+/// returns the number of entries added
+pub fn register(registry: &mut DownloadRegistry, folders: &mut [AdoptedFolder]) -> usize {
+    let mut known: HashSet<String> = registry
+        .downloads
+        .iter()
+        .map(|d| d.local_path.clone())
+        .collect();
+    let mut added = 0;
+    for folder in folders.iter_mut() {
+        for file in &folder.files {
+            if known.insert(file.local_path.clone()) {
+                registry.downloads.push(file.clone());
+                added += 1;
+            } else {
+                folder.already_known += 1;
+            }
+        }
+    }
+    added
+}
@@ -1,23 +1,715 @@
+use crate::gguf::{self, GgufError, GgufMetadata};
 use crate::models::{
-    FileTreeNode, ModelFile, ModelInfo, ModelMetadata, QuantizationGroup, QuantizationInfo,
-    RepoFile,
+    AuthorProfile, ExpectedHash, FileTreeNode, GgufHeaderSummary, ModelCardData,
+    ModelConfigSummary, ModelFile, ModelInfo, ModelMetadata, ModelReference, PromptFormat,
+    QuantizationGroup, QuantizationInfo, RepoFile, RepoLayout, RepoPreset, Runtime,
+    TokenizerConfig, WeightShards, WeightVariant,
 };
-use std::collections::HashMap;
+use futures::StreamExt;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use reqwest::StatusCode;
+use serde::de::DeserializeOwned;
+use std::collections::{HashMap, HashSet};
+
+/// Error type for HuggingFace API calls
+///
+/// HTTP error statuses are mapped to dedicated variants so callers can show
+/// targeted messages (e.g. prompt for a token on `Unauthorized`) instead of a
+/// generic "request failed" string.
+#[derive(Debug)]
+pub enum ApiError {
+    /// 404 - model, revision or path does not exist
+    NotFound(String),
+    /// 401 - missing or invalid token
+    Unauthorized(String),
+    /// 403 - gated model whose terms have not been accepted for this token
+    Gated(String),
+    /// 429 - too many requests; `retry_after` is taken from the `Retry-After` header
+    RateLimited { retry_after: Option<u64> },
+    /// Response body could not be parsed into the expected type
+    Deserialize(String),
+    /// Connection, TLS, timeout or any other HTTP failure
+    Network(reqwest::Error),
+    /// Offline mode (or network failure) and no cached response is available
+    Offline(String),
+    /// Any other status that is not a success, e.g. a redirect that was not
+    /// followed or a 304
+    UnexpectedStatus { status: u16, url: String },
+}
+
+impl ApiError {
+    /// True if the error can be fixed by providing (or changing) an HF token
+    pub fn is_auth_error(&self) -> bool {
+        matches!(self, ApiError::Unauthorized(_) | ApiError::Gated(_))
+    }
+}
+
+impl std::fmt::Display for ApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ApiError::NotFound(url) => write!(f, "Not found: {}", url),
+            ApiError::Unauthorized(url) => write!(
+                f,
+                "Unauthorized: a valid HuggingFace token is required for {}",
+                url
+            ),
+            ApiError::Gated(url) => write!(
+                f,
+                "Access denied: model is gated, accept its terms on huggingface.co ({})",
+                url
+            ),
+            ApiError::RateLimited {
+                retry_after: Some(secs),
+            } => write!(f, "Rate limited by HuggingFace, retry after {}s", secs),
+            ApiError::RateLimited { retry_after: None } => {
+                write!(f, "Rate limited by HuggingFace, try again later")
+            }
+            ApiError::Deserialize(msg) => write!(f, "Unexpected API response: {}", msg),
+            ApiError::Network(err) => write!(f, "Network error: {}", err),
+            ApiError::Offline(url) => write!(f, "Offline: no cached response for {}", url),
+            ApiError::UnexpectedStatus { status, url } => {
+                write!(f, "Unexpected HTTP status {} for {}", status, url)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ApiError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ApiError::Network(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for ApiError {
+    fn from(err: reqwest::Error) -> Self {
+        if err.is_decode() {
+            ApiError::Deserialize(err.to_string())
+        } else {
+            ApiError::Network(err)
+        }
+    }
+}
+
+/// Map an HTTP response to an `ApiError` based on its status code
+fn check_status(response: reqwest::Response) -> Result<reqwest::Response, ApiError> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+
+    let url = response.url().to_string();
+    match status {
+        StatusCode::NOT_FOUND => Err(ApiError::NotFound(url)),
+        StatusCode::UNAUTHORIZED => Err(ApiError::Unauthorized(url)),
+        StatusCode::FORBIDDEN => Err(ApiError::Gated(url)),
+        StatusCode::TOO_MANY_REQUESTS => {
+            let retry_after = response
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.trim().parse::<u64>().ok());
+            Err(ApiError::RateLimited { retry_after })
+        }
+        _ => match response.error_for_status() {
+            Err(err) => Err(err.into()),
+            // Not an error status (1xx or 3xx) but not a success either
+            Ok(_) => Err(ApiError::UnexpectedStatus {
+                status: status.as_u16(),
+                url,
+            }),
+        },
+    }
+}
+
+/// GET a JSON endpoint, mapping HTTP and decoding failures to `ApiError`
+///
+/// Responses within the cache TTL are served from disk; successful responses
+/// are written back. In offline mode the cache is used exclusively; on network
+/// errors it is used as a fallback.
+async fn get_json<T: DeserializeOwned>(url: &str, token: Option<&String>) -> Result<T, ApiError> {
+    get_json_with_cache(url, token, true).await
+}
+
+/// Like `get_json`, but `use_fresh_cache = false` always queries the API
+/// (the response is still written to the cache)
+async fn get_json_with_cache<T: DeserializeOwned>(
+    url: &str,
+    token: Option<&String>,
+    use_fresh_cache: bool,
+) -> Result<T, ApiError> {
+    get_cached(url, token, use_fresh_cache, fetch_body(url, token)).await
+}
+
+/// Key a response fetched with `token` is cached under: the URL, with a
+/// fingerprint of the token when there is one, so a private or gated
+/// response is only ever served to the token that fetched it
+fn cache_key(url: &str, token: Option<&String>) -> String {
+    use sha2::{Digest, Sha256};
+
+    match token.filter(|token| !token.is_empty()) {
+        Some(token) => {
+            let fingerprint = format!("{:x}", Sha256::digest(token.as_bytes()));
+            format!("{}#token={}", url, &fingerprint[..16])
+        }
+        None => url.to_string(),
+    }
+}
+
+/// Cache policy shared by all JSON endpoints; `fetch` is only awaited when
+/// the cache cannot answer, and its body is cached under `url` (see
+/// [`cache_key`])
+async fn get_cached<T, F>(
+    url: &str,
+    token: Option<&String>,
+    use_fresh_cache: bool,
+    fetch: F,
+) -> Result<T, ApiError>
+where
+    T: DeserializeOwned,
+    F: std::future::Future<Output = Result<String, ApiError>>,
+{
+    let url = &cache_key(url, token);
+    if crate::cache::is_offline() {
+        return parse_cached(url);
+    }
+
+    // Fresh cache hit: skip the network entirely
+    if use_fresh_cache {
+        if let Some(entry) = crate::cache::load_fresh(url) {
+            if let Ok(parsed) = serde_json::from_str(&entry.body) {
+                return Ok(parsed);
+            }
+        }
+    }
+
+    match fetch.await {
+        Ok(body) => {
+            crate::cache::set_network_unavailable(false);
+            let parsed = serde_json::from_str(&body)
+                .map_err(|e| ApiError::Deserialize(e.to_string()))?;
+            crate::cache::store(url, &body);
+            Ok(parsed)
+        }
+        Err(ApiError::Network(err)) => {
+            // Network is down: serve the last known response if we have one
+            if crate::cache::is_enabled() && crate::cache::load(url).is_some() {
+                crate::cache::set_network_unavailable(true);
+                parse_cached(url)
+            } else {
+                Err(ApiError::Network(err))
+            }
+        }
+        Err(e) => Err(e),
+    }
+}
+
+async fn fetch_body(url: &str, token: Option<&String>) -> Result<String, ApiError> {
+    let response = crate::http_client::get_with_optional_token(url, token).await?;
+    let response = check_status(response)?;
+    Ok(response.text().await?)
+}
+
+/// Fetch every page of a paginated list endpoint and merge them into one JSON array
+///
+/// The Hub paginates large listings and points at the next page with a
+/// `Link: <url>; rel="next"` header (carrying a `cursor` parameter).
+async fn fetch_all_pages(url: &str, token: Option<&String>) -> Result<String, ApiError> {
+    fetch_pages_up_to(url, usize::MAX, token).await
+}
+
+/// Like [`fetch_all_pages`], but stops once `max_items` items are collected
+async fn fetch_pages_up_to(
+    url: &str,
+    max_items: usize,
+    token: Option<&String>,
+) -> Result<String, ApiError> {
+    let mut items: Vec<serde_json::Value> = Vec::new();
+    let mut next_url = Some(url.to_string());
+
+    while let Some(page_url) = next_url {
+        let response = crate::http_client::get_with_optional_token(&page_url, token).await?;
+        let response = check_status(response)?;
+        next_url = next_page_url(response.headers());
+
+        let body = response.text().await?;
+        let page: Vec<serde_json::Value> =
+            serde_json::from_str(&body).map_err(|e| ApiError::Deserialize(e.to_string()))?;
+        items.extend(page);
+        if items.len() >= max_items {
+            items.truncate(max_items);
+            break;
+        }
+    }
+
+    serde_json::to_string(&items).map_err(|e| ApiError::Deserialize(e.to_string()))
+}
+
+/// Extract the `rel="next"` target from a `Link` header
+fn next_page_url(headers: &reqwest::header::HeaderMap) -> Option<String> {
+    let link = headers.get(reqwest::header::LINK)?.to_str().ok()?;
+    link.split(',').find_map(|part| {
+        let (target, params) = part.split_once(';')?;
+        let is_next = params
+            .split(';')
+            .any(|p| p.trim().trim_start_matches("rel=").trim_matches('"') == "next");
+        is_next.then(|| {
+            target
+                .trim()
+                .trim_start_matches('<')
+                .trim_end_matches('>')
+                .to_string()
+        })
+    })
+}
+
+fn parse_cached<T: DeserializeOwned>(url: &str) -> Result<T, ApiError> {
+    let entry = crate::cache::load(url).ok_or_else(|| ApiError::Offline(url.to_string()))?;
+    serde_json::from_str(&entry.body).map_err(|e| ApiError::Deserialize(e.to_string()))
+}
 
 /// Fetch models with sorting and filtering parameters
+#[allow(clippy::too_many_arguments)]
 pub async fn fetch_models_filtered(
     query: &str,
     sort_field: crate::models::SortField,
     sort_direction: crate::models::SortDirection,
     min_downloads: u64,
     min_likes: u64,
+    params: crate::models::ParamRange,
+    access: crate::models::AccessFilter,
+    content: crate::models::ContentFilter,
+    limits: crate::models::SearchLimits,
     token: Option<&String>,
-) -> Result<Vec<ModelInfo>, reqwest::Error> {
-    use crate::models::{SortDirection, SortField};
+) -> Result<Vec<ModelInfo>, ApiError> {
+    search_models_with_cache(
+        query,
+        sort_field,
+        sort_direction,
+        min_downloads,
+        min_likes,
+        params,
+        access,
+        content,
+        limits,
+        token,
+        true,
+    )
+    .await
+}
 
-    // Determine if we need client-side sorting
-    let needs_client_side_sort =
-        matches!(sort_field, SortField::Name) || matches!(sort_direction, SortDirection::Ascending);
+/// Same as `fetch_models_filtered`, but always hits the API so newly
+/// published models show up (used by search refresh)
+#[allow(clippy::too_many_arguments)]
+pub async fn refresh_models_filtered(
+    query: &str,
+    sort_field: crate::models::SortField,
+    sort_direction: crate::models::SortDirection,
+    min_downloads: u64,
+    min_likes: u64,
+    params: crate::models::ParamRange,
+    access: crate::models::AccessFilter,
+    content: crate::models::ContentFilter,
+    limits: crate::models::SearchLimits,
+    token: Option<&String>,
+) -> Result<Vec<ModelInfo>, ApiError> {
+    search_models_with_cache(
+        query,
+        sort_field,
+        sort_direction,
+        min_downloads,
+        min_likes,
+        params,
+        access,
+        content,
+        limits,
+        token,
+        false,
+    )
+    .await
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn search_models_with_cache(
+    query: &str,
+    sort_field: crate::models::SortField,
+    sort_direction: crate::models::SortDirection,
+    min_downloads: u64,
+    min_likes: u64,
+    params: crate::models::ParamRange,
+    access: crate::models::AccessFilter,
+    content: crate::models::ContentFilter,
+    limits: crate::models::SearchLimits,
+    token: Option<&String>,
+    use_fresh_cache: bool,
+) -> Result<Vec<ModelInfo>, ApiError> {
+    let filter = SearchFilter {
+        min_downloads,
+        min_likes,
+        params,
+        access,
+        content,
+    };
+    let api_sort = filter.api_sort(sort_field);
+    let url = search_url(query, api_sort, limits.page_size.min(limits.max_results));
+    let models: Vec<ModelInfo> = if filter.is_active() {
+        // Which rows get fetched depends on the filters, so they are part of the key
+        let key = format!(
+            "{}#max_results={}&min_downloads={}&min_likes={}&params={:?}-{:?}&access={:?}&content={:?}",
+            url,
+            limits.max_results,
+            min_downloads,
+            min_likes,
+            params.min,
+            params.max,
+            access,
+            content
+        );
+        let max_scanned = (limits.max_results * FILTERED_SCAN_FACTOR)
+            .min(crate::models::SearchLimits::MAX_RESULTS)
+            .max(limits.max_results);
+        let fetch = fetch_filtered_pages(
+            &url,
+            api_sort,
+            filter,
+            limits.max_results,
+            max_scanned,
+            token,
+        );
+        get_cached(&key, token, use_fresh_cache, fetch).await?
+    } else if limits.max_results > limits.page_size {
+        // Several pages are cached as one response, keyed apart from the first page
+        let key = format!("{}#max_results={}", url, limits.max_results);
+        let fetch = fetch_pages_up_to(&url, limits.max_results, token);
+        get_cached(&key, token, use_fresh_cache, fetch).await?
+    } else {
+        get_json_with_cache(&url, token, use_fresh_cache).await?
+    };
+    Ok(filter_and_sort_models(
+        models,
+        sort_field,
+        sort_direction,
+        min_downloads,
+        min_likes,
+        params,
+        access,
+        content,
+    ))
+}
+
+/// Filtered searches check at most this many times `max_results` rows
+const FILTERED_SCAN_FACTOR: usize = 10;
+
+/// The client-side filters of a search (the API has no parameters for them)
+#[derive(Debug, Clone, Copy)]
+struct SearchFilter {
+    min_downloads: u64,
+    min_likes: u64,
+    params: crate::models::ParamRange,
+    access: crate::models::AccessFilter,
+    content: crate::models::ContentFilter,
+}
+
+impl SearchFilter {
+    fn is_active(&self) -> bool {
+        self.min_downloads > 0
+            || self.min_likes > 0
+            || !self.params.is_any()
+            || !self.access.is_any()
+            || !self.content.is_any()
+    }
+
+    fn matches(&self, model: &ModelInfo) -> bool {
+        model.downloads >= self.min_downloads
+            && model.likes >= self.min_likes
+            && self.params.contains(model.parameter_count())
+            && self.access.allows(model)
+            && self.content.allows(model)
+    }
+
+    /// Sort to request from the API
+    ///
+    /// Name order is applied client-side, so the API can sort by the count a
+    /// minimum applies to instead, letting paging stop at the first model
+    /// below it (see [`Self::exhausted_by`]).
+    fn api_sort(&self, sort_field: crate::models::SortField) -> crate::models::SortField {
+        use crate::models::SortField;
+
+        match sort_field {
+            SortField::Name if self.min_downloads == 0 && self.min_likes > 0 => SortField::Likes,
+            other => other,
+        }
+    }
+
+    /// Whether no model after `model` in the API's descending `api_sort`
+    /// order can pass the filters
+    fn exhausted_by(&self, api_sort: crate::models::SortField, model: &ModelInfo) -> bool {
+        use crate::models::SortField;
+
+        match api_sort {
+            SortField::Downloads | SortField::Name => model.downloads < self.min_downloads,
+            SortField::Likes => model.likes < self.min_likes,
+            SortField::Modified => false,
+        }
+    }
+}
+
+/// Follow search result pages until `wanted` models pass `filter`, the sort
+/// order shows that no later model can, or `max_scanned` models were checked
+///
+/// All fetched rows are kept (merged into one JSON array), so the cached
+/// response can be filtered again like a single page.
+async fn fetch_filtered_pages(
+    url: &str,
+    api_sort: crate::models::SortField,
+    filter: SearchFilter,
+    wanted: usize,
+    max_scanned: usize,
+    token: Option<&String>,
+) -> Result<String, ApiError> {
+    let mut items: Vec<serde_json::Value> = Vec::new();
+    let mut matched = 0;
+    let mut next_url = Some(url.to_string());
+
+    while let Some(page_url) = next_url.take() {
+        let response = crate::http_client::get_with_optional_token(&page_url, token).await?;
+        let response = check_status(response)?;
+        next_url = next_page_url(response.headers());
+
+        let body = response.text().await?;
+        let page: Vec<serde_json::Value> =
+            serde_json::from_str(&body).map_err(|e| ApiError::Deserialize(e.to_string()))?;
+        let mut exhausted = false;
+        for item in &page {
+            let Ok(model) = serde_json::from_value::<ModelInfo>(item.clone()) else {
+                continue;
+            };
+            if filter.matches(&model) {
+                matched += 1;
+            }
+            exhausted |= filter.exhausted_by(api_sort, &model);
+        }
+        items.extend(page);
+        if exhausted || matched >= wanted || items.len() >= max_scanned {
+            break;
+        }
+    }
+
+    serde_json::to_string(&items).map_err(|e| ApiError::Deserialize(e.to_string()))
+}
+
+/// Models whose card (README) text matches `query`, e.g. "function calling"
+///
+/// Uses the Hub's full-text search, which returns repository IDs in relevance
+/// order; each is then resolved to its model info (downloads, likes, tags),
+/// so the usual filters apply. Relevance order is kept unless sorting by
+/// name or ascending.
+#[allow(clippy::too_many_arguments)]
+pub async fn fetch_models_full_text(
+    query: &str,
+    sort_field: crate::models::SortField,
+    sort_direction: crate::models::SortDirection,
+    min_downloads: u64,
+    min_likes: u64,
+    params: crate::models::ParamRange,
+    access: crate::models::AccessFilter,
+    content: crate::models::ContentFilter,
+    token: Option<&String>,
+    use_fresh_cache: bool,
+) -> Result<Vec<ModelInfo>, ApiError> {
+    #[derive(serde::Deserialize)]
+    struct FullTextResults {
+        #[serde(default)]
+        hits: Vec<FullTextHit>,
+    }
+    #[derive(serde::Deserialize)]
+    struct FullTextHit {
+        #[serde(rename = "repoId")]
+        repo_id: String,
+    }
+
+    let url = format!(
+        "https://huggingface.co/api/search/full-text?q={}&type=model&limit={}",
+        urlencoding::encode(query),
+        FULL_TEXT_LIMIT
+    );
+    let results: FullTextResults = get_json_with_cache(&url, token, use_fresh_cache).await?;
+
+    // A repository can match in several README sections
+    let mut ids: Vec<String> = Vec::new();
+    for hit in results.hits {
+        if !ids.contains(&hit.repo_id) {
+            ids.push(hit.repo_id);
+        }
+    }
+
+    let models: Vec<ModelInfo> = futures::stream::iter(ids)
+        .map(|id| async move {
+            let url = format!("https://huggingface.co/api/models/{}", id);
+            get_json::<ModelInfo>(&url, token).await.ok()
+        })
+        .buffered(SUBDIR_FETCH_CONCURRENCY)
+        .filter_map(|model| async move { model })
+        .collect()
+        .await;
+
+    Ok(filter_and_sort_models(
+        models,
+        sort_field,
+        sort_direction,
+        min_downloads,
+        min_likes,
+        params,
+        access,
+        content,
+    ))
+}
+
+/// Total size of a repository's files (`usedStorage`), which search
+/// listings don't include
+pub async fn fetch_repo_size(
+    model_id: &str,
+    token: Option<&String>,
+) -> Result<Option<u64>, ApiError> {
+    #[derive(serde::Deserialize)]
+    struct UsedStorage {
+        #[serde(rename = "usedStorage", default)]
+        used_storage: Option<u64>,
+    }
+
+    let url = format!(
+        "https://huggingface.co/api/models/{}?expand%5B%5D=usedStorage",
+        model_id
+    );
+    let info: UsedStorage = get_json(&url, token).await?;
+    Ok(info.used_storage)
+}
+
+/// [`fetch_repo_size`] of each of `ids`, a few at a time and in order; a
+/// failed lookup yields `None`
+pub fn repo_sizes<'a>(
+    ids: Vec<String>,
+    token: Option<&'a String>,
+) -> impl futures::Stream<Item = (String, Option<u64>)> + 'a {
+    futures::stream::iter(ids)
+        .map(move |id| async move {
+            let size = fetch_repo_size(&id, token).await.ok().flatten();
+            (id, size)
+        })
+        .buffered(SUBDIR_FETCH_CONCURRENCY)
+}
+
+/// Fill in [`ModelInfo::used_storage`] where it is unknown
+pub async fn fill_repo_sizes(models: &mut [ModelInfo], token: Option<&String>) {
+    let ids = models
+        .iter()
+        .filter(|m| m.used_storage.is_none())
+        .map(|m| m.id.clone())
+        .collect();
+    let sizes: Vec<(String, Option<u64>)> = repo_sizes(ids, token).collect().await;
+    for (id, size) in sizes {
+        if let Some(model) = models.iter_mut().find(|m| m.id == id) {
+            model.used_storage = size;
+        }
+    }
+}
+
+/// Full-text hits requested per search; each costs a model info request
+const FULL_TEXT_LIMIT: usize = 50;
+
+/// One page of search results and the cursor of the next page, if there is one
+///
+/// Pages are always fetched from the API (the cursor lives in the `Link`
+/// header, which the cache doesn't keep); offline, a page seen before is
+/// served from the cache without a next cursor. Filters and client-side sorts
+/// apply per page; there is no next cursor once the sort order shows that
+/// later pages can't pass the filters.
+#[allow(clippy::too_many_arguments)]
+pub async fn search_models_page(
+    query: &str,
+    sort_field: crate::models::SortField,
+    sort_direction: crate::models::SortDirection,
+    min_downloads: u64,
+    min_likes: u64,
+    params: crate::models::ParamRange,
+    access: crate::models::AccessFilter,
+    content: crate::models::ContentFilter,
+    page_size: usize,
+    cursor: Option<&str>,
+    token: Option<&String>,
+) -> Result<(Vec<ModelInfo>, Option<String>), ApiError> {
+    let filter = SearchFilter {
+        min_downloads,
+        min_likes,
+        params,
+        access,
+        content,
+    };
+    let api_sort = filter.api_sort(sort_field);
+    let mut url = search_url(query, api_sort, page_size);
+    if let Some(cursor) = cursor {
+        url.push_str(&format!("&cursor={}", urlencoding::encode(cursor)));
+    }
+    if crate::cache::is_offline() {
+        let models = parse_cached(&cache_key(&url, token))?;
+        return Ok((
+            filter_and_sort_models(
+                models,
+                sort_field,
+                sort_direction,
+                min_downloads,
+                min_likes,
+                params,
+                access,
+                content,
+            ),
+            None,
+        ));
+    }
+
+    let response = crate::http_client::get_with_optional_token(&url, token).await?;
+    let response = check_status(response)?;
+    let next_cursor = next_page_url(response.headers()).and_then(|next| url_cursor(&next));
+    let body = response.text().await?;
+    let models: Vec<ModelInfo> =
+        serde_json::from_str(&body).map_err(|e| ApiError::Deserialize(e.to_string()))?;
+    crate::cache::store(&cache_key(&url, token), &body);
+
+    // Later pages sort below this one, so none of them can pass the filters
+    let next_cursor = next_cursor.filter(|_| {
+        !filter.is_active() || !models.iter().any(|m| filter.exhausted_by(api_sort, m))
+    });
+
+    Ok((
+        filter_and_sort_models(
+            models,
+            sort_field,
+            sort_direction,
+            min_downloads,
+            min_likes,
+            params,
+            access,
+            content,
+        ),
+        next_cursor,
+    ))
+}
+
+/// The decoded `cursor` query parameter of a pagination URL
+fn url_cursor(url: &str) -> Option<String> {
+    let (_, query) = url.split_once('?')?;
+    query
+        .split('&')
+        .find_map(|param| param.strip_prefix("cursor="))
+        .and_then(|cursor| urlencoding::decode(cursor).ok())
+        .map(|cursor| cursor.into_owned())
+}
+
+fn search_url(query: &str, sort_field: crate::models::SortField, limit: usize) -> String {
+    use crate::models::SortField;
 
     // API only reliably supports descending sort (direction=-1)
     // For name or ascending, we'll fetch descending and sort client-side
@@ -31,20 +723,45 @@ pub async fn fetch_models_filtered(
     // Always use descending for API call
     let direction = "-1";
 
-    // Request more results (100) since we'll filter client-side
+    // Request a large page (100 by default) since we'll filter client-side
     // Use full=true to get complete metadata including lastModified
-    let url = format!(
-        "https://huggingface.co/api/models?search={}&limit=100&sort={}&direction={}&full=true",
+    format!(
+        "https://huggingface.co/api/models?search={}&limit={}&sort={}&direction={}&full=true",
         urlencoding::encode(query),
+        limit,
         sort,
         direction
-    );
+    )
+}
 
-    let response = crate::http_client::get_with_optional_token(&url, token).await?;
-    let mut models: Vec<ModelInfo> = response.json().await?;
+#[allow(clippy::too_many_arguments)]
+fn filter_and_sort_models(
+    mut models: Vec<ModelInfo>,
+    sort_field: crate::models::SortField,
+    sort_direction: crate::models::SortDirection,
+    min_downloads: u64,
+    min_likes: u64,
+    params: crate::models::ParamRange,
+    access: crate::models::AccessFilter,
+    content: crate::models::ContentFilter,
+) -> Vec<ModelInfo> {
+    use crate::models::{SortDirection, SortField};
+
+    // Determine if we need client-side sorting
+    let needs_client_side_sort =
+        matches!(sort_field, SortField::Name) || matches!(sort_direction, SortDirection::Ascending);
 
     // Client-side filtering (API doesn't support these filters)
-    models.retain(|m| m.downloads >= min_downloads && m.likes >= min_likes);
+    let filter = SearchFilter {
+        min_downloads,
+        min_likes,
+        params,
+        access,
+        content,
+    };
+    // Models and authors the user hid never show up
+    let ignored = crate::ignorelist::load_ignore_list();
+    models.retain(|m| filter.matches(m) && !ignored.hides(&m.id));
 
     // Client-side sorting when needed
     if needs_client_side_sort {
@@ -63,21 +780,212 @@ pub async fn fetch_models_filtered(
         });
     }
 
-    Ok(models)
+    models
+}
+
+#[derive(serde::Deserialize)]
+struct WhoAmI {
+    name: String,
 }
 
+/// User name the token belongs to (`/api/whoami-v2`)
+pub async fn fetch_username(token: Option<&String>) -> Result<String, ApiError> {
+    let url = "https://huggingface.co/api/whoami-v2";
+    let whoami: WhoAmI = get_json_with_cache(url, token, false).await?;
+    Ok(whoami.name)
+}
+
+/// Every model of `author`, most recently modified first, including the
+/// private ones the token can see (always queried from the API)
+pub async fn fetch_author_models(
+    author: &str,
+    token: Option<&String>,
+) -> Result<Vec<ModelInfo>, ApiError> {
+    let url = format!(
+        "https://huggingface.co/api/models?author={}&limit=100&sort=lastModified&direction=-1&full=true",
+        urlencoding::encode(author)
+    );
+    get_cached(&url, token, false, fetch_all_pages(&url, token)).await
+}
+
+/// Profile of `author` (`/api/organizations|users/{author}/overview`, when
+/// available) with every model they publish
+pub async fn fetch_author_profile(
+    author: &str,
+    token: Option<&String>,
+) -> Result<AuthorProfile, ApiError> {
+    #[derive(serde::Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Overview {
+        #[serde(default)]
+        fullname: Option<String>,
+        #[serde(default)]
+        num_followers: Option<u64>,
+    }
+
+    let models = fetch_author_models(author, token).await?;
+
+    // Organizations and users have separate overview endpoints
+    let mut profile = AuthorProfile {
+        name: author.to_string(),
+        models,
+        ..AuthorProfile::default()
+    };
+    for (kind, is_org) in [("organizations", true), ("users", false)] {
+        let url = format!(
+            "https://huggingface.co/api/{}/{}/overview",
+            kind,
+            urlencoding::encode(author)
+        );
+        if let Ok(overview) = get_json::<Overview>(&url, token).await {
+            profile.fullname = overview.fullname.filter(|name| name != author);
+            profile.is_org = Some(is_org);
+            profile.followers = overview.num_followers;
+            break;
+        }
+    }
+    Ok(profile)
+}
+
+/// GGUF repositories created in the last `days` days, newest first (always
+/// queried from the API, up to [`RECENT_GGUF_MAX_PAGES`] pages)
+pub async fn fetch_recent_gguf_models(
+    days: u64,
+    token: Option<&String>,
+) -> Result<Vec<ModelInfo>, ApiError> {
+    let url = "https://huggingface.co/api/models?filter=gguf&sort=createdAt&direction=-1&limit=100&full=true";
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let since = crate::utils::format_unix_date(now.saturating_sub(days * 86_400));
+
+    let models: Vec<ModelInfo> =
+        get_cached(url, token, false, fetch_pages_since(url, &since, token)).await?;
+    let ignored = crate::ignorelist::load_ignore_list();
+    Ok(models
+        .into_iter()
+        .filter(|m| created_since(m.created_at.as_deref(), &since) && !ignored.hides(&m.id))
+        .collect())
+}
+
+/// Pages fetched by [`fetch_recent_gguf_models`] at most
+pub const RECENT_GGUF_MAX_PAGES: usize = 10;
+
+/// Whether a `createdAt` timestamp is on or after the `since` date (YYYY-MM-DD)
+fn created_since(created_at: Option<&str>, since: &str) -> bool {
+    match created_at {
+        Some(created) => created.get(..10).unwrap_or(created) >= since,
+        None => false,
+    }
+}
+
+/// Like [`fetch_all_pages`] for a listing sorted by creation date, stopping
+/// at the first page that reaches models created before `since`
+async fn fetch_pages_since(
+    url: &str,
+    since: &str,
+    token: Option<&String>,
+) -> Result<String, ApiError> {
+    let mut items: Vec<serde_json::Value> = Vec::new();
+    let mut next_url = Some(url.to_string());
+
+    for _ in 0..RECENT_GGUF_MAX_PAGES {
+        let Some(page_url) = next_url.take() else {
+            break;
+        };
+        let response = crate::http_client::get_with_optional_token(&page_url, token).await?;
+        let response = check_status(response)?;
+        next_url = next_page_url(response.headers());
+
+        let body = response.text().await?;
+        let page: Vec<serde_json::Value> =
+            serde_json::from_str(&body).map_err(|e| ApiError::Deserialize(e.to_string()))?;
+        let reached_older = page
+            .iter()
+            .any(|m| !created_since(m.get("createdAt").and_then(|c| c.as_str()), since));
+        items.extend(page);
+        if reached_older {
+            break;
+        }
+    }
+
+    serde_json::to_string(&items).map_err(|e| ApiError::Deserialize(e.to_string()))
+}
+
+/// Group models by [`ModelInfo::base_model`] (a model without one is its own
+/// group), keeping the order of each group's first model
+pub fn group_by_base_model(models: Vec<ModelInfo>) -> Vec<(String, Vec<ModelInfo>)> {
+    let mut groups: Vec<(String, Vec<ModelInfo>)> = Vec::new();
+    for model in models {
+        let base = model.base_model().unwrap_or(&model.id).to_string();
+        match groups.iter_mut().find(|(key, _)| *key == base) {
+            Some((_, members)) => members.push(model),
+            None => groups.push((base, vec![model])),
+        }
+    }
+    groups
+}
+
+/// Models related to `metadata`: the author's other finetunes of the same
+/// base model first, then the most downloaded models sharing its base model
+/// (or derived from it, for a model without one)
+pub async fn fetch_related_models(
+    metadata: &ModelMetadata,
+    token: Option<&String>,
+) -> Result<Vec<ModelInfo>, ApiError> {
+    let model_id = &metadata.model_id;
+    let base = metadata.base_model();
+    let related_url = format!(
+        "https://huggingface.co/api/models?filter={}&sort=downloads&direction=-1&limit={}",
+        urlencoding::encode(&format!(
+            "base_model:{}",
+            base.as_deref().unwrap_or(model_id)
+        )),
+        RELATED_FETCH_LIMIT
+    );
+    let mut candidates: Vec<ModelInfo> = get_json(&related_url, token).await?;
+
+    if let (Some(base), Some((author, _))) = (base.as_deref(), model_id.split_once('/')) {
+        let author_url = format!(
+            "https://huggingface.co/api/models?author={}&sort=downloads&direction=-1&limit={}",
+            urlencoding::encode(author),
+            RELATED_FETCH_LIMIT
+        );
+        let siblings: Vec<ModelInfo> = get_json(&author_url, token).await?;
+        let siblings = siblings
+            .into_iter()
+            .filter(|m| m.base_model() == Some(base));
+        candidates = siblings.chain(candidates).collect();
+    }
+
+    let mut related: Vec<ModelInfo> = Vec::new();
+    for model in candidates {
+        if model.id != *model_id && !related.iter().any(|r| r.id == model.id) {
+            related.push(model);
+        }
+    }
+    related.truncate(RELATED_LIMIT);
+    Ok(related)
+}
+
+/// Models requested per related-models query
+const RELATED_FETCH_LIMIT: usize = 20;
+
+/// Related models shown for a model
+pub const RELATED_LIMIT: usize = 8;
+
 /// Fetch detailed model metadata from /api/models/{model_id}
 pub async fn fetch_model_metadata(
     model_id: &str,
     token: Option<&String>,
-) -> Result<ModelMetadata, reqwest::Error> {
+) -> Result<ModelMetadata, ApiError> {
     let url = format!("https://huggingface.co/api/models/{}", model_id);
 
-    let response = crate::http_client::get_with_optional_token(&url, token).await?;
-    let mut metadata: ModelMetadata = response.json().await?;
+    let mut metadata: ModelMetadata = get_json(&url, token).await?;
 
     // Fetch the complete file tree recursively
-    let all_files = fetch_recursive_tree(model_id, "", token).await?;
+    let all_files = fetch_recursive_tree(model_id, token).await?;
 
     // Convert ModelFile to RepoFile with proper size information
     metadata.siblings = all_files
@@ -86,49 +994,369 @@ pub async fn fetch_model_metadata(
             rfilename: f.path,
             size: Some(f.size),
             lfs: f.lfs,
+            oid: f.oid,
         })
         .collect();
 
+    fill_card_from_readme(&mut metadata, token).await;
+    metadata.weight_shards = fetch_weight_shards(&metadata, token).await;
+    metadata.model_config = fetch_model_config(&metadata, token).await;
+
     Ok(metadata)
 }
 
-/// Recursively fetch all files from a repository, including subdirectories
-fn fetch_recursive_tree<'a>(
-    model_id: &'a str,
-    path: &'a str,
-    token: Option<&'a String>,
-) -> std::pin::Pin<
-    Box<dyn std::future::Future<Output = Result<Vec<ModelFile>, reqwest::Error>> + Send + 'a>,
-> {
-    Box::pin(async move {
-        let tree_url = if path.is_empty() {
-            format!("https://huggingface.co/api/models/{}/tree/main", model_id)
-        } else {
-            format!(
-                "https://huggingface.co/api/models/{}/tree/main/{}",
-                model_id, path
-            )
+/// Architecture summary from the `config.json` of a Standard (non-GGUF)
+/// repository
+///
+/// Best-effort: `None` when there is no config or it cannot be fetched.
+async fn fetch_model_config(
+    metadata: &ModelMetadata,
+    token: Option<&String>,
+) -> Option<ModelConfigSummary> {
+    let has_config = metadata
+        .siblings
+        .iter()
+        .any(|f| f.rfilename == "config.json");
+    if !has_config || has_gguf_files(metadata) {
+        return None;
+    }
+    let url = crate::download::file_url(&metadata.model_id, None, "config.json");
+    let config: serde_json::Value = get_json(&url, token).await.ok()?;
+    let summary = ModelConfigSummary::from_config(&config);
+    (!summary.is_empty()).then_some(summary)
+}
+
+/// Shard index of a sharded checkpoint (`*.safetensors.index.json`,
+/// `pytorch_model.bin.index.json`, ...); only the tensor → file map is needed
+#[derive(serde::Deserialize)]
+struct WeightIndex {
+    weight_map: HashMap<String, String>,
+}
+
+/// Weight format named by a shard index file, e.g. "safetensors" for
+/// `model.safetensors.index.json`
+fn weight_index_format(path: &str) -> Option<&'static str> {
+    let stem = path.strip_suffix(".index.json")?;
+    ["safetensors", "bin", "pth", "pt"]
+        .into_iter()
+        .find(|ext| stem.ends_with(&format!(".{}", ext)))
+}
+
+/// Read every shard index in the repository and list the shards it points at
+///
+/// Best-effort: an index that cannot be fetched or parsed is left out, and
+/// shards missing from the listing are ignored.
+async fn fetch_weight_shards(
+    metadata: &ModelMetadata,
+    token: Option<&String>,
+) -> Vec<WeightShards> {
+    let mut sets = Vec::new();
+    for index in &metadata.siblings {
+        let Some(format) = weight_index_format(&index.rfilename) else {
+            continue;
+        };
+        let url = format!(
+            "https://huggingface.co/{}/resolve/main/{}",
+            metadata.model_id, index.rfilename
+        );
+        let Ok(parsed) = get_json::<WeightIndex>(&url, token).await else {
+            continue;
         };
 
-        let response = crate::http_client::get_with_optional_token(&tree_url, token).await?;
-        let items: Vec<ModelFile> = response.json().await?;
+        // Shard names in the weight map are relative to the index's directory
+        let dir = index
+            .rfilename
+            .rsplit_once('/')
+            .map(|(dir, _)| format!("{}/", dir))
+            .unwrap_or_default();
+        let mut shards: Vec<String> = parsed
+            .weight_map
+            .into_values()
+            .map(|shard| format!("{}{}", dir, shard))
+            .filter(|shard| metadata.siblings.iter().any(|f| &f.rfilename == shard))
+            .collect();
+        shards.sort();
+        shards.dedup();
+
+        if !shards.is_empty() {
+            sets.push(WeightShards {
+                index_file: index.rfilename.clone(),
+                format: format.to_string(),
+                shards,
+            });
+        }
+    }
+    sets
+}
+
+/// Fill in card fields the API left out from the README's YAML front matter
+///
+/// The Hub drops `cardData` when it cannot parse a card, and many cards set
+/// only some fields. Best-effort: the card stays as it is when the README
+/// cannot be fetched or has no front matter. The parsed fields are cached
+/// under the README URL with a `#front-matter` suffix.
+async fn fill_card_from_readme(metadata: &mut ModelMetadata, token: Option<&String>) {
+    let sparse = metadata
+        .card_data
+        .as_ref()
+        .map_or(true, ModelCardData::is_sparse);
+    let has_readme = metadata.siblings.iter().any(|f| f.rfilename == "README.md");
+    if !sparse || !has_readme {
+        return;
+    }
+
+    let url = crate::download::file_url(&metadata.model_id, None, "README.md");
+    let fetch = async {
+        let readme = fetch_body(&url, token).await?;
+        let card = ModelCardData::from_front_matter(&readme).unwrap_or_default();
+        serde_json::to_string(&card).map_err(|e| ApiError::Deserialize(e.to_string()))
+    };
+    let key = format!("{}#front-matter", url);
+    let Ok(front_matter) = get_cached::<ModelCardData, _>(&key, token, true, fetch).await else {
+        return;
+    };
+    metadata
+        .card_data
+        .get_or_insert_with(ModelCardData::default)
+        .fill_missing(front_matter);
+}
+
+/// Maximum number of `base_model` links followed when building the lineage
+const MAX_BASE_MODEL_DEPTH: usize = 5;
+
+/// Follow `base_model` links in the model cards to build the finetune lineage,
+/// nearest ancestor first
+///
+/// Best-effort: the chain simply ends at a model whose card cannot be fetched.
+/// Not part of [`fetch_model_metadata`], since each link costs a request;
+/// the views that show the lineage load it.
+pub async fn fetch_base_model_chain(
+    metadata: &ModelMetadata,
+    token: Option<&String>,
+) -> Vec<String> {
+    let mut chain: Vec<String> = Vec::new();
+    let mut next = metadata
+        .card_data
+        .as_ref()
+        .and_then(|card| card.base_model.clone());
+
+    while let Some(base) = next.take() {
+        // Guard against self-references and cycles in user-written cards
+        if chain.len() >= MAX_BASE_MODEL_DEPTH || base == metadata.model_id || chain.contains(&base)
+        {
+            break;
+        }
+
+        let url = format!("https://huggingface.co/api/models/{}", base);
+        next = get_json::<ModelMetadata>(&url, token)
+            .await
+            .ok()
+            .and_then(|m| m.card_data)
+            .and_then(|card| card.base_model);
+        chain.push(base);
+    }
 
-        let mut all_files = Vec::new();
+    chain
+}
+
+/// Fetch all files from a repository, including subdirectories
+///
+/// Uses the recursive tree listing, so the whole repository costs one request
+/// per page of results instead of one request per directory.
+async fn fetch_recursive_tree(
+    model_id: &str,
+    token: Option<&String>,
+) -> Result<Vec<ModelFile>, ApiError> {
+    fetch_recursive_tree_with_cache(model_id, "main", token, true).await
+}
+
+/// Current file listing of a repository, always queried from the API
+/// (used by the watchlist to detect upstream changes)
+pub async fn fetch_repo_files(
+    model_id: &str,
+    token: Option<&String>,
+) -> Result<Vec<ModelFile>, ApiError> {
+    fetch_recursive_tree_with_cache(model_id, "main", token, false).await
+}
+
+/// File listing of a repository at a commit or branch (cached like any
+/// listing; a commit's files don't change)
+pub async fn fetch_repo_files_at(
+    model_id: &str,
+    revision: &str,
+    token: Option<&String>,
+) -> Result<Vec<ModelFile>, ApiError> {
+    fetch_recursive_tree_with_cache(model_id, revision, token, true).await
+}
+
+/// Current `lastModified` and basic info of a repository, always queried from the API
+pub async fn fetch_model_info(model_id: &str, token: Option<&String>) -> Result<ModelInfo, ApiError> {
+    let url = format!("https://huggingface.co/api/models/{}", model_id);
+    get_json_with_cache(&url, token, false).await
+}
 
-        for item in items {
-            if item.file_type == "directory" {
-                // Recursively fetch contents of this directory
-                if let Ok(subdir_files) = fetch_recursive_tree(model_id, &item.path, token).await {
-                    all_files.extend(subdir_files);
+async fn fetch_recursive_tree_with_cache(
+    model_id: &str,
+    revision: &str,
+    token: Option<&String>,
+    use_fresh_cache: bool,
+) -> Result<Vec<ModelFile>, ApiError> {
+    let tree_url = format!(
+        "https://huggingface.co/api/models/{}/tree/{}?recursive=true&expand=true",
+        model_id,
+        crate::download::encode_revision(revision)
+    );
+
+    let items: Vec<ModelFile> =
+        get_cached(&tree_url, token, use_fresh_cache, fetch_all_pages(&tree_url, token)).await?;
+
+    Ok(items
+        .into_iter()
+        .filter(|item| item.file_type != "directory")
+        .collect())
+}
+
+/// Largest GGUF prefix read looking for the end of the metadata; the
+/// vocabulary and merges of current models take a few megabytes
+const MAX_GGUF_METADATA_BYTES: usize = 64 * 1024 * 1024;
+
+/// Chat template and special tokens stored in a GGUF file's metadata
+///
+/// Only the start of the file is downloaded: the response is read until the
+/// metadata parses, then dropped. The result is cached under the file's URL.
+pub async fn fetch_gguf_prompt_format(
+    model_id: &str,
+    revision: Option<&str>,
+    filename: &str,
+    token: Option<&String>,
+) -> Result<PromptFormat, ApiError> {
+    let url = crate::download::file_url(model_id, revision, filename);
+    let fetch = async {
+        let metadata = fetch_gguf_metadata(&url, token).await?;
+        serde_json::to_string(&metadata.prompt_format(filename))
+            .map_err(|e| ApiError::Deserialize(e.to_string()))
+    };
+    get_cached(&url, token, true, fetch).await
+}
+
+/// Architecture, context length and RoPE scaling of a GGUF file
+///
+/// Only the first key/value pairs are needed, so the response is dropped as
+/// soon as the tokenizer keys start. The result is cached under the file's
+/// URL with a `#header` suffix.
+pub async fn fetch_gguf_header_summary(
+    model_id: &str,
+    revision: Option<&str>,
+    filename: &str,
+    token: Option<&String>,
+) -> Result<GgufHeaderSummary, ApiError> {
+    let url = crate::download::file_url(model_id, revision, filename);
+    let fetch = async {
+        let mut response = open_gguf_range(&url, token).await?;
+
+        // Retry the parse each time the prefix doubles, from 256 KB
+        let mut data = Vec::new();
+        let mut next_attempt = 256 * 1024;
+        loop {
+            let finished = match response.chunk().await? {
+                Some(chunk) => {
+                    data.extend_from_slice(&chunk);
+                    false
                 }
-            } else {
-                // It's a file, add it to the list
-                all_files.push(item);
+                None => true,
+            };
+            if !finished && data.len() < next_attempt && data.len() < MAX_GGUF_METADATA_BYTES {
+                continue;
+            }
+            // Settle for what has been read once no more is coming
+            let last_attempt = finished || data.len() >= MAX_GGUF_METADATA_BYTES;
+            match gguf::parse_metadata_prefix(&data) {
+                Ok((metadata, complete))
+                    if complete || last_attempt || metadata.has_architecture_keys() =>
+                {
+                    return serde_json::to_string(&metadata.header_summary())
+                        .map_err(|e| ApiError::Deserialize(e.to_string()));
+                }
+                Err(e) if last_attempt || e != GgufError::Truncated => {
+                    return Err(ApiError::Deserialize(e.to_string()));
+                }
+                _ => next_attempt = data.len() * 2,
             }
         }
+    };
+    get_cached(&format!("{}#header", url), token, true, fetch).await
+}
 
-        Ok(all_files)
-    })
+/// Whether `group` was made with a different context length or RoPE scaling
+/// than most quants of the repository
+pub fn has_unusual_context(groups: &[QuantizationGroup], group: &QuantizationGroup) -> bool {
+    let Some(header) = &group.header else {
+        return false;
+    };
+    let context_of =
+        |header: &GgufHeaderSummary| (header.context_length, header.rope_scaling.clone());
+    let mut counts: HashMap<(Option<u64>, Option<String>), usize> = HashMap::new();
+    for header in groups.iter().filter_map(|g| g.header.as_ref()) {
+        *counts.entry(context_of(header)).or_default() += 1;
+    }
+    if counts.len() < 2 {
+        return false;
+    }
+    let most_common = counts.values().copied().max().unwrap_or(0);
+    counts.get(&context_of(header)).copied().unwrap_or(0) < most_common
+}
+
+/// Start a `Range` request for the header of the GGUF file at `url`
+async fn open_gguf_range(url: &str, token: Option<&String>) -> Result<reqwest::Response, ApiError> {
+    let response = crate::http_client::get(url, token)
+        .header(
+            reqwest::header::RANGE,
+            format!("bytes=0-{}", MAX_GGUF_METADATA_BYTES - 1),
+        )
+        .send()
+        .await?;
+    check_status(response)
+}
+
+/// Read the header metadata of the GGUF file at `url` with a `Range` request
+async fn fetch_gguf_metadata(url: &str, token: Option<&String>) -> Result<GgufMetadata, ApiError> {
+    let mut response = open_gguf_range(url, token).await?;
+
+    // Retry the parse each time the prefix doubles, from 1 MB
+    let mut data = Vec::new();
+    let mut next_attempt = 1024 * 1024;
+    while let Some(chunk) = response.chunk().await? {
+        data.extend_from_slice(&chunk);
+        if data.len() < next_attempt && data.len() < MAX_GGUF_METADATA_BYTES {
+            continue;
+        }
+        match gguf::parse_metadata(&data) {
+            Err(GgufError::Truncated) if data.len() < MAX_GGUF_METADATA_BYTES => {
+                next_attempt = data.len() * 2;
+            }
+            result => return result.map_err(|e| ApiError::Deserialize(e.to_string())),
+        }
+    }
+    gguf::parse_metadata(&data).map_err(|e| ApiError::Deserialize(e.to_string()))
+}
+
+/// Chat template and special tokens from a Transformers repository's
+/// `tokenizer_config.json` (or its separate `chat_template.jinja`)
+pub async fn fetch_tokenizer_prompt_format(
+    model_id: &str,
+    token: Option<&String>,
+) -> Result<PromptFormat, ApiError> {
+    let url = crate::download::file_url(model_id, None, "tokenizer_config.json");
+    let config: TokenizerConfig = get_json(&url, token).await?;
+
+    // Newer repositories keep the template in its own file
+    let chat_template = if config.chat_template.is_some() || crate::cache::is_offline() {
+        None
+    } else {
+        let url = crate::download::file_url(model_id, None, "chat_template.jinja");
+        fetch_body(&url, token).await.ok()
+    };
+    Ok(config.prompt_format("tokenizer_config.json", chat_template))
 }
 
 /// Check if model has GGUF files
@@ -139,8 +1367,328 @@ pub fn has_gguf_files(metadata: &ModelMetadata) -> bool {
         .any(|file| file.rfilename.ends_with(".gguf") || file.rfilename.contains(".gguf.part"))
 }
 
-/// Build tree structure from flat file list
-pub fn build_file_tree(files: Vec<RepoFile>) -> FileTreeNode {
+/// Weight formats that are optional extras when the repository also ships
+/// safetensors (or that only serve other runtimes)
+const OPTIONAL_WEIGHT_EXTENSIONS: &[&str] = &[
+    "h5",
+    "msgpack",
+    "onnx",
+    "onnx_data",
+    "ot",
+    "tflite",
+    "mlmodel",
+];
+
+/// Runtime of the export directory (`onnx/`, `coreml/`, ...) at the top of
+/// a repository path
+pub fn runtime_export(path: &str) -> Option<Runtime> {
+    Runtime::from_dir(path.split('/').next()?)
+}
+
+/// Files to download for "weights only": the preferred weights (safetensors
+/// over PyTorch pickles) plus configs and tokenizers, without duplicate
+/// weights in other formats or exports for other runtimes
+pub fn weights_only_files(metadata: &ModelMetadata) -> Vec<&RepoFile> {
+    let has_safetensors = metadata
+        .siblings
+        .iter()
+        .any(|f| f.rfilename.ends_with(".safetensors"));
+
+    metadata
+        .siblings
+        .iter()
+        .filter(|f| {
+            let path = f.rfilename.as_str();
+            let ext = path.rsplit_once('.').map(|(_, ext)| ext).unwrap_or("");
+            let pickled = matches!(ext, "bin" | "pth" | "pt")
+                || weight_index_format(path).is_some_and(|format| format != "safetensors");
+
+            !(OPTIONAL_WEIGHT_EXTENSIONS.contains(&ext)
+                || (path.contains('/') && runtime_export(path).is_some())
+                || (has_safetensors && pickled))
+        })
+        .collect()
+}
+
+/// Tokenizer and processor files kept by the "essential" preset, besides
+/// every `*config.json`
+const ESSENTIAL_FILE_NAMES: &[&str] = &[
+    "tokenizer.json",
+    "tokenizer.model",
+    "special_tokens_map.json",
+    "added_tokens.json",
+    "vocab.json",
+    "vocab.txt",
+    "merges.txt",
+    "spiece.model",
+    "chat_template.jinja",
+    "chat_template.json",
+];
+
+/// Training leftovers that share the weights' extensions
+const TRAINING_ARTIFACTS: &[&str] = &[
+    "training_args.bin",
+    "optimizer.pt",
+    "optimizer.bin",
+    "scheduler.pt",
+    "scaler.pt",
+];
+
+/// Files to download for "essential": of the weights-only files, just the
+/// configs, tokenizer files, weights with their shard index and remote code
+/// (`*.py`), so READMEs, images, checkpoints and training state are skipped
+pub fn essential_files(metadata: &ModelMetadata) -> Vec<&RepoFile> {
+    weights_only_files(metadata)
+        .into_iter()
+        .filter(|f| {
+            let path = f.rfilename.as_str();
+            let (dirs, name) = path.rsplit_once('/').unwrap_or(("", path));
+            let ext = name.rsplit_once('.').map(|(_, ext)| ext).unwrap_or("");
+            let weights = matches!(ext, "safetensors" | "bin" | "pth" | "pt")
+                || weight_index_format(path).is_some();
+            let training = TRAINING_ARTIFACTS.contains(&name)
+                || name.starts_with("rng_state")
+                || dirs.split('/').any(|dir| dir.starts_with("checkpoint-"));
+
+            !training && (weights || is_config_or_tokenizer(name) || ext == "py")
+        })
+        .collect()
+}
+
+fn is_config_or_tokenizer(name: &str) -> bool {
+    name.ends_with("config.json")
+        || ESSENTIAL_FILE_NAMES.contains(&name)
+        || name.ends_with(".tiktoken")
+}
+
+/// Files to download for one runtime: its export directories plus the
+/// configs and tokenizer files at the repository root, which exports
+/// usually load from there
+pub fn runtime_files(metadata: &ModelMetadata, runtime: Runtime) -> Vec<&RepoFile> {
+    metadata
+        .siblings
+        .iter()
+        .filter(|f| {
+            let path = f.rfilename.as_str();
+            match path.split_once('/') {
+                Some(_) => runtime_export(path) == Some(runtime),
+                None => is_config_or_tokenizer(path),
+            }
+        })
+        .collect()
+}
+
+/// Runtimes the repository has an export directory for
+pub fn runtime_exports(metadata: &ModelMetadata) -> Vec<Runtime> {
+    Runtime::ALL
+        .into_iter()
+        .filter(|runtime| {
+            metadata.siblings.iter().any(|f| {
+                f.rfilename.contains('/') && runtime_export(&f.rfilename) == Some(*runtime)
+            })
+        })
+        .collect()
+}
+
+/// Component folders of a diffusers pipeline (the keys of `model_index.json`)
+const DIFFUSERS_COMPONENTS: &[&str] = &[
+    "unet",
+    "transformer",
+    "vae",
+    "text_encoder",
+    "text_encoder_2",
+    "text_encoder_3",
+    "tokenizer",
+    "tokenizer_2",
+    "tokenizer_3",
+    "scheduler",
+    "feature_extractor",
+    "image_encoder",
+    "safety_checker",
+    "prior",
+    "decoder",
+    "movq",
+    "controlnet",
+];
+
+/// Format and precision variant of a weight file in a diffusers component,
+/// e.g. ("safetensors", Some("fp16")) for `diffusion_pytorch_model.fp16.safetensors`
+/// and for its shard index `diffusion_pytorch_model.safetensors.index.fp16.json`
+fn component_weight(name: &str) -> Option<(&str, Option<&str>)> {
+    let (format, variant) = match name.split_once(".index.") {
+        Some((stem, rest)) => {
+            let variant = match rest {
+                "json" => None,
+                _ => Some(rest.strip_suffix(".json")?),
+            };
+            (stem.rsplit_once('.')?.1, variant)
+        }
+        None => {
+            let (stem, ext) = name.rsplit_once('.')?;
+            // Shards carry the variant before their number: model.fp16-00001-of-00002
+            let variant = stem
+                .split_once('.')
+                .map(|(_, variant)| variant.split('-').next().unwrap_or(variant));
+            (ext, variant)
+        }
+    };
+    matches!(format, "safetensors" | "bin").then_some((format, variant))
+}
+
+/// Files of a diffusers pipeline: `model_index.json` and the configs,
+/// tokenizer files and weights of each component folder, in `variant` where
+/// a component has it (else its default weights, as diffusers falls back
+/// to) and as safetensors where both formats are there; single-file
+/// checkpoints at the root are skipped
+fn diffusers_files(metadata: &ModelMetadata, variant: WeightVariant) -> Vec<&RepoFile> {
+    // Per component: the variant to take and whether it has safetensors
+    let mut chosen: HashMap<&str, (Option<&str>, bool)> = HashMap::new();
+    for component in DIFFUSERS_COMPONENTS {
+        let weights: Vec<(&str, Option<&str>)> = metadata
+            .siblings
+            .iter()
+            .filter_map(|f| match f.rfilename.split_once('/') {
+                Some((dir, name)) if dir == *component => component_weight(name),
+                _ => None,
+            })
+            .collect();
+        let wanted = variant
+            .suffix()
+            .filter(|suffix| weights.iter().any(|(_, v)| *v == Some(*suffix)));
+        let safetensors = weights
+            .iter()
+            .any(|(format, v)| *v == wanted && *format == "safetensors");
+        chosen.insert(component, (wanted, safetensors));
+    }
+
+    metadata
+        .siblings
+        .iter()
+        .filter(|f| match f.rfilename.split_once('/') {
+            None => f.rfilename == "model_index.json",
+            Some((dir, name)) if !name.contains('/') => {
+                match (chosen.get(dir), component_weight(name)) {
+                    (None, _) => false,
+                    (Some(&(wanted, safetensors)), Some((format, v))) => {
+                        v == wanted && (!safetensors || format == "safetensors")
+                    }
+                    (Some(_), None) => is_config_or_tokenizer(name),
+                }
+            }
+            _ => false,
+        })
+        .collect()
+}
+
+/// Precision variants the component weights of a diffusers pipeline come in
+fn diffusers_variants(metadata: &ModelMetadata) -> Vec<WeightVariant> {
+    WeightVariant::ALL
+        .into_iter()
+        .filter(|variant| match variant.suffix() {
+            None => true,
+            Some(suffix) => metadata.siblings.iter().any(|f| {
+                f.rfilename.split_once('/').is_some_and(|(dir, name)| {
+                    DIFFUSERS_COMPONENTS.contains(&dir)
+                        && component_weight(name).is_some_and(|(_, v)| v == Some(suffix))
+                })
+            }),
+        })
+        .collect()
+}
+
+/// Files sentence-transformers reads besides the configs and tokenizer files
+const SENTENCE_TRANSFORMERS_FILES: &[&str] = &["modules.json", "config_sentence_transformers.json"];
+
+/// Files of a sentence-transformers model: the essential files at the root
+/// (the transformer module), `modules.json` and the numbered module folders
+/// (`1_Pooling/`, `2_Dense/`, ...), which only hold small configs and weights
+fn sentence_transformers_files(metadata: &ModelMetadata) -> Vec<&RepoFile> {
+    let essential: HashSet<&str> = essential_files(metadata)
+        .into_iter()
+        .map(|f| f.rfilename.as_str())
+        .collect();
+    metadata
+        .siblings
+        .iter()
+        .filter(|f| {
+            let path = f.rfilename.as_str();
+            match path.split_once('/') {
+                None => essential.contains(path) || SENTENCE_TRANSFORMERS_FILES.contains(&path),
+                Some((dir, _)) => dir.split_once('_').is_some_and(|(index, _)| {
+                    !index.is_empty() && index.bytes().all(|b| b.is_ascii_digit())
+                }),
+            }
+        })
+        .collect()
+}
+
+/// Folders of speaker / voice embeddings that TTS models load
+const SPEECH_VOICE_DIRS: &[&str] = &["voices", "speaker_embeddings"];
+
+/// Files of a speech model: the essential files plus Whisper's text
+/// normalizer and the voice embeddings of TTS models
+fn speech_files(metadata: &ModelMetadata) -> Vec<&RepoFile> {
+    let essential: HashSet<&str> = essential_files(metadata)
+        .into_iter()
+        .map(|f| f.rfilename.as_str())
+        .collect();
+    metadata
+        .siblings
+        .iter()
+        .filter(|f| {
+            let path = f.rfilename.as_str();
+            essential.contains(path)
+                || path == "normalizer.json"
+                || path
+                    .split_once('/')
+                    .is_some_and(|(dir, _)| SPEECH_VOICE_DIRS.contains(&dir))
+        })
+        .collect()
+}
+
+/// Presets that apply to a repository, in `Tab` order: all, weights and
+/// essential (when they skip anything), its pipeline presets and its
+/// runtime exports
+pub fn repo_presets(metadata: &ModelMetadata) -> Vec<RepoPreset> {
+    let mut presets = vec![RepoPreset::All];
+    if essential_files(metadata).len() < metadata.siblings.len() {
+        presets.extend([RepoPreset::Weights, RepoPreset::Essential]);
+    }
+    match metadata.layout() {
+        Some(RepoLayout::Diffusers) => presets.extend(
+            diffusers_variants(metadata)
+                .into_iter()
+                .map(|variant| RepoPreset::Pipeline(RepoLayout::Diffusers, variant)),
+        ),
+        Some(layout) => presets.push(RepoPreset::Pipeline(layout, WeightVariant::Default)),
+        None => {}
+    }
+    presets.extend(
+        runtime_exports(metadata)
+            .into_iter()
+            .map(RepoPreset::Runtime),
+    );
+    presets
+}
+
+/// The files of a repository a preset downloads
+pub fn preset_files(metadata: &ModelMetadata, preset: RepoPreset) -> Vec<&RepoFile> {
+    match preset {
+        RepoPreset::All => metadata.siblings.iter().collect(),
+        RepoPreset::Weights => weights_only_files(metadata),
+        RepoPreset::Essential => essential_files(metadata),
+        RepoPreset::Runtime(runtime) => runtime_files(metadata, runtime),
+        RepoPreset::Pipeline(RepoLayout::Diffusers, variant) => diffusers_files(metadata, variant),
+        RepoPreset::Pipeline(RepoLayout::SentenceTransformers, _) => {
+            sentence_transformers_files(metadata)
+        }
+        RepoPreset::Pipeline(RepoLayout::Speech, _) => speech_files(metadata),
+    }
+}
+
+/// Build tree structure from flat file list, with the shards of each sharded
+/// checkpoint grouped under one "weights" node
+pub fn build_file_tree(files: Vec<RepoFile>, weight_shards: &[WeightShards]) -> FileTreeNode {
     let mut root = FileTreeNode {
         name: String::new(),
         path: String::new(),
@@ -149,6 +1697,7 @@ pub fn build_file_tree(files: Vec<RepoFile>) -> FileTreeNode {
         children: Vec::new(),
         expanded: true, // Root is always expanded
         depth: 0,
+        shard_count: None,
     };
 
     for file in files {
@@ -156,6 +1705,24 @@ pub fn build_file_tree(files: Vec<RepoFile>) -> FileTreeNode {
         insert_into_tree(&mut root, &parts, 0, &file);
     }
 
+    for set in weight_shards.iter().filter(|set| set.shards.len() > 1) {
+        // Several checkpoints in one directory (safetensors + bin) are told apart by format
+        let dir = set.index_file.rsplit_once('/').map(|(dir, _)| dir);
+        let shared_dir = weight_shards
+            .iter()
+            .filter(|other| other.shards.len() > 1)
+            .any(|other| {
+                other.index_file != set.index_file
+                    && other.index_file.rsplit_once('/').map(|(dir, _)| dir) == dir
+            });
+        let name = if shared_dir {
+            format!("weights ({})", set.format)
+        } else {
+            "weights".to_string()
+        };
+        group_shards(&mut root, set, name);
+    }
+
     // Sort children at each level (directories first, then alphabetically)
     sort_tree_recursive(&mut root);
 
@@ -209,6 +1776,7 @@ fn insert_into_tree(node: &mut FileTreeNode, parts: &[&str], depth: usize, file:
             children: Vec::new(),
             expanded: false,
             depth: depth + 1,
+            shard_count: None,
         };
         node.children.push(new_node);
         node.children.last_mut().unwrap()
@@ -219,6 +1787,42 @@ fn insert_into_tree(node: &mut FileTreeNode, parts: &[&str], depth: usize, file:
     }
 }
 
+/// Move the shards of `set` under a virtual node in the directory of its index
+fn group_shards(root: &mut FileTreeNode, set: &WeightShards, name: String) {
+    let mut dir = root;
+    if let Some((dir_path, _)) = set.index_file.rsplit_once('/') {
+        for part in dir_path.split('/') {
+            match dir.children.iter().position(|child| child.name == part) {
+                Some(pos) => dir = &mut dir.children[pos],
+                None => return,
+            }
+        }
+    }
+
+    let (mut shards, rest): (Vec<FileTreeNode>, Vec<FileTreeNode>) =
+        std::mem::take(&mut dir.children)
+            .into_iter()
+            .partition(|child| !child.is_dir && set.shards.contains(&child.path));
+    dir.children = rest;
+    if shards.is_empty() {
+        return;
+    }
+
+    for shard in &mut shards {
+        shard.depth += 1;
+    }
+    dir.children.push(FileTreeNode {
+        path: format!("{}#{}", set.index_file, name),
+        name,
+        is_dir: true,
+        size: None,
+        expanded: false,
+        depth: dir.depth + 1,
+        shard_count: Some(shards.len()),
+        children: shards,
+    });
+}
+
 fn sort_tree_recursive(node: &mut FileTreeNode) {
     node.children.sort_by(|a, b| {
         // Directories before files
@@ -234,17 +1838,20 @@ fn sort_tree_recursive(node: &mut FileTreeNode) {
     }
 }
 
+/// Maximum number of quantization subdirectories listed at once
+const SUBDIR_FETCH_CONCURRENCY: usize = 4;
+
 pub async fn fetch_model_files(
     model_id: &str,
     token: Option<&String>,
-) -> Result<Vec<QuantizationGroup>, reqwest::Error> {
+) -> Result<Vec<QuantizationGroup>, ApiError> {
     let url = format!("https://huggingface.co/api/models/{}/tree/main", model_id);
 
-    let response = crate::http_client::get_with_optional_token(&url, token).await?;
-    let files: Vec<ModelFile> = response.json().await?;
+    let files: Vec<ModelFile> = get_json(&url, token).await?;
 
     let mut quantizations = Vec::new();
     let mut multi_part_groups: HashMap<String, Vec<ModelFile>> = HashMap::new();
+    let mut quant_dirs: Vec<String> = Vec::new();
 
     for file in &files {
         // Handle GGUF files in root directory
@@ -254,7 +1861,10 @@ pub async fn fetch_model_files(
 
         if is_gguf_file {
             // Extract SHA256 from lfs.oid (available for all files)
-            let sha256 = file.lfs.as_ref().map(|lfs| lfs.oid.clone());
+            let expected_hash = file
+                .lfs
+                .as_ref()
+                .map(|lfs| ExpectedHash::Sha256(lfs.oid.clone()));
 
             // Check if this is a multi-part file
             if let Some((_, _)) = parse_multipart_filename(&file.path) {
@@ -271,42 +1881,57 @@ pub async fn fetch_model_files(
                         quant_type,
                         filename: file.path.clone(),
                         size: file.size,
-                        sha256,
+                        expected_hash,
+                        revision: None,
                     });
                 }
             }
         }
-        // Handle subdirectories named by quantization type (e.g., Q4_K_M/, Q8_0/)
+        // Remember subdirectories named by quantization type (e.g., Q4_K_M/, Q8_0/)
         else if file.file_type == "directory" && is_quantization_directory(&file.path) {
-            // Fetch files from this subdirectory
-            let subdir_url = format!(
-                "https://huggingface.co/api/models/{}/tree/main/{}",
-                model_id, file.path
-            );
+            quant_dirs.push(file.path.clone());
+        }
+    }
+
+    // Fetch quantization subdirectories concurrently on the shared client;
+    // repos with 20+ quant folders would otherwise pay for each round trip in turn
+    let subdir_listings: Vec<(String, Result<Vec<ModelFile>, ApiError>)> =
+        futures::stream::iter(quant_dirs)
+            .map(|dir| async move {
+                let subdir_url = format!(
+                    "https://huggingface.co/api/models/{}/tree/main/{}",
+                    model_id, dir
+                );
+                let listing = get_json::<Vec<ModelFile>>(&subdir_url, token).await;
+                (dir, listing)
+            })
+            .buffer_unordered(SUBDIR_FETCH_CONCURRENCY)
+            .collect()
+            .await;
+
+    for (dir, listing) in subdir_listings {
+        let Ok(subdir_files) = listing else {
+            continue;
+        };
+        let quant_type = extract_quantization_type_from_dirname(&dir);
 
-            if let Ok(subdir_response) =
-                crate::http_client::get_with_optional_token(&subdir_url, token).await
+        // Add each individual file in the directory as a separate QuantizationInfo
+        for subdir_file in subdir_files {
+            if subdir_file.file_type == "file"
+                && (subdir_file.path.ends_with(".gguf") || subdir_file.path.contains(".gguf.part"))
             {
-                if let Ok(subdir_files) = subdir_response.json::<Vec<ModelFile>>().await {
-                    let quant_type = extract_quantization_type_from_dirname(&file.path);
-
-                    // Add each individual file in the directory as a separate QuantizationInfo
-                    for subdir_file in subdir_files {
-                        if subdir_file.file_type == "file"
-                            && (subdir_file.path.ends_with(".gguf")
-                                || subdir_file.path.contains(".gguf.part"))
-                        {
-                            let sha256 = subdir_file.lfs.as_ref().map(|lfs| lfs.oid.clone());
-
-                            quantizations.push(QuantizationInfo {
-                                quant_type: quant_type.clone(),
-                                filename: subdir_file.path.clone(),
-                                size: subdir_file.size,
-                                sha256,
-                            });
-                        }
-                    }
-                }
+                let expected_hash = subdir_file
+                    .lfs
+                    .as_ref()
+                    .map(|lfs| ExpectedHash::Sha256(lfs.oid.clone()));
+
+                quantizations.push(QuantizationInfo {
+                    quant_type: quant_type.clone(),
+                    filename: subdir_file.path.clone(),
+                    size: subdir_file.size,
+                    expected_hash,
+                    revision: None,
+                });
             }
         }
     }
@@ -318,12 +1943,16 @@ pub async fn fetch_model_files(
         if let Some(quant_type) = extract_quantization_type(&base_name) {
             // Add each individual part as a separate QuantizationInfo
             for part in parts {
-                let sha256 = part.lfs.as_ref().map(|lfs| lfs.oid.clone());
+                let expected_hash = part
+                    .lfs
+                    .as_ref()
+                    .map(|lfs| ExpectedHash::Sha256(lfs.oid.clone()));
                 quantizations.push(QuantizationInfo {
                     quant_type: quant_type.clone(),
                     filename: part.path.clone(),
                     size: part.size,
-                    sha256,
+                    expected_hash,
+                    revision: None,
                 });
             }
         }
@@ -348,27 +1977,141 @@ pub async fn fetch_model_files(
                 quant_type,
                 files,
                 total_size,
+                header: None,
             }
         })
         .collect();
 
-    quantization_groups.sort_by(|a, b| b.total_size.cmp(&a.total_size));
+    quantization_groups.sort_by_key(|g| std::cmp::Reverse(g.total_size));
+
+    // Quants published on other branches (e.g. one branch per EXL2 bpw) follow
+    quantization_groups.extend(fetch_branch_groups(model_id, token).await);
 
     Ok(quantization_groups)
 }
 
+/// Branches of a repository, from `/api/models/{id}/refs`
+#[derive(serde::Deserialize)]
+struct RepoRefs {
+    #[serde(default)]
+    branches: Vec<GitRef>,
+}
+
+#[derive(serde::Deserialize)]
+struct GitRef {
+    name: String,
+}
+
+/// Maximum number of non-main branches listed for one repository
+const MAX_BRANCHES: usize = 16;
+
+/// Model weights in any format (as opposed to configs, tokenizers and docs)
+pub fn is_model_weight_file(path: &str) -> bool {
+    [".safetensors", ".gguf", ".bin", ".pth", ".pt"]
+        .iter()
+        .any(|ext| path.ends_with(ext))
+        || path.contains(".gguf.part")
+}
+
+/// One selectable group per non-main branch that holds model files, named
+/// after the branch and containing every file on it
+///
+/// Best-effort: repositories whose refs or branch listings cannot be fetched
+/// simply have no branch groups.
+pub async fn fetch_branch_groups(model_id: &str, token: Option<&String>) -> Vec<QuantizationGroup> {
+    let url = format!("https://huggingface.co/api/models/{}/refs", model_id);
+    let Ok(refs) = get_json::<RepoRefs>(&url, token).await else {
+        return Vec::new();
+    };
+
+    let branches: Vec<String> = refs
+        .branches
+        .into_iter()
+        .map(|branch| branch.name)
+        .filter(|name| name != "main")
+        .take(MAX_BRANCHES)
+        .collect();
+
+    let listings: Vec<(String, Result<Vec<ModelFile>, ApiError>)> = futures::stream::iter(branches)
+        .map(|branch| async move {
+            let tree_url = format!(
+                "https://huggingface.co/api/models/{}/tree/{}?recursive=true&expand=true",
+                model_id,
+                crate::download::encode_revision(&branch)
+            );
+            let listing =
+                get_cached(&tree_url, token, true, fetch_all_pages(&tree_url, token)).await;
+            (branch, listing)
+        })
+        .buffer_unordered(SUBDIR_FETCH_CONCURRENCY)
+        .collect()
+        .await;
+
+    let mut groups: Vec<QuantizationGroup> = listings
+        .into_iter()
+        .filter_map(|(branch, listing)| {
+            let files: Vec<ModelFile> = listing
+                .ok()?
+                .into_iter()
+                .filter(|f| f.file_type != "directory")
+                .collect();
+            if !files.iter().any(|f| is_model_weight_file(&f.path)) {
+                return None;
+            }
+            Some(branch_group(&branch, Some(&branch), files))
+        })
+        .collect();
+    groups.sort_by(|a, b| a.quant_type.cmp(&b.quant_type));
+    groups
+}
+
+/// Every file of the main branch as one group, shown next to the branch
+/// groups of repositories that keep their quants on branches
+pub fn main_branch_group(metadata: &ModelMetadata) -> QuantizationGroup {
+    let files = metadata
+        .siblings
+        .iter()
+        .map(|f| ModelFile {
+            file_type: "file".to_string(),
+            path: f.rfilename.clone(),
+            size: f.size.unwrap_or(0),
+            oid: f.oid.clone(),
+            lfs: f.lfs.clone(),
+        })
+        .collect();
+    branch_group("main", None, files)
+}
+
+fn branch_group(name: &str, revision: Option<&str>, files: Vec<ModelFile>) -> QuantizationGroup {
+    let files: Vec<QuantizationInfo> = files
+        .into_iter()
+        .map(|f| QuantizationInfo {
+            quant_type: name.to_string(),
+            expected_hash: ExpectedHash::of(f.lfs.as_ref(), f.oid.as_deref()),
+            filename: f.path,
+            size: f.size,
+            revision: revision.map(str::to_string),
+        })
+        .collect();
+    QuantizationGroup {
+        quant_type: name.to_string(),
+        total_size: files.iter().map(|f| f.size).sum(),
+        files,
+        header: None,
+    }
+}
+
 /// Fetch SHA256 hashes for multiple files in a single API call
 /// Returns a HashMap mapping filename to its SHA256 hash (if available)
 pub async fn fetch_multipart_sha256s(
     model_id: &str,
     filenames: &[String],
     token: Option<&String>,
-) -> Result<HashMap<String, Option<String>>, reqwest::Error> {
+) -> Result<HashMap<String, Option<String>>, ApiError> {
     // Single API call to get all files
     let url = format!("https://huggingface.co/api/models/{}/tree/main", model_id);
 
-    let response = crate::http_client::get_with_optional_token(&url, token).await?;
-    let files: Vec<ModelFile> = response.json().await?;
+    let files: Vec<ModelFile> = get_json(&url, token).await?;
 
     // Create lookup map for fast matching
     let mut sha256_map = HashMap::new();
@@ -637,12 +2380,40 @@ pub fn extract_quantization_type(filename: &str) -> Option<String> {
     None
 }
 
+/// Rough quality tier of a quantization, estimated from its name
+/// (bits per weight: Q4_K_M -> 4, IQ3_XXS -> 3, UD-Q6_K_XL -> 6, BF16 -> lossless)
+pub fn estimate_quality_tier(quant_type: &str) -> &'static str {
+    static BITS: Lazy<Regex> = Lazy::new(|| Regex::new(r"I?Q(\d)").unwrap());
+
+    let upper = quant_type.to_uppercase();
+    if upper.contains("F32") || upper.contains("F16") {
+        return "Lossless";
+    }
+    if upper.contains("MXFP4") {
+        return "Good";
+    }
+
+    let bits = BITS
+        .captures(&upper)
+        .and_then(|caps| caps.get(1))
+        .and_then(|m| m.as_str().parse::<u32>().ok());
+
+    match bits {
+        Some(8..) => "Near-lossless",
+        Some(6 | 7) => "Very high",
+        Some(5) => "High",
+        Some(4) => "Good",
+        Some(3) => "Medium",
+        Some(0..=2) => "Low",
+        None => "Unknown",
+    }
+}
+
 pub fn parse_multipart_filename(filename: &str) -> Option<(u32, u32)> {
     // Parse filenames like:
     // "Q2_K/MiniMax-M2-Q2_K-00001-of-00002.gguf" (5-digit format)
     // "MiniMax-M2-REAP-162B-A10B.Q4_K_M.gguf.part1of2" (partNofM format)
     // Returns (current_part, total_parts) if this is a multi-part file
-    use regex::Regex;
 
     // Try 5-digit format first: 00001-of-00002
     if let Ok(re) = Regex::new(r"(\d{5})-of-(\d{5})") {
@@ -670,3 +2441,217 @@ pub fn parse_multipart_filename(filename: &str) -> Option<(u32, u32)> {
 
     None
 }
+
+/// Parse a model ID or huggingface.co URL pasted by the user
+///
+/// Accepts:
+/// - "author/model"
+/// - "https://huggingface.co/author/model" (model page, any sub-page)
+/// - "https://huggingface.co/author/model/tree/main"
+/// - "https://huggingface.co/author/model/blob/main/dir/file.gguf"
+/// - "https://huggingface.co/author/model/resolve/main/file.gguf?download=true"
+///
+/// Returns None for anything that does not name a model repository
+/// (datasets, spaces, malformed IDs).
+pub fn parse_model_reference(input: &str) -> Option<ModelReference> {
+    let input = input.trim();
+    // Drop query string and fragment (e.g. "?download=true")
+    let input = input.split(['?', '#']).next()?;
+
+    let without_scheme = input
+        .strip_prefix("https://")
+        .or_else(|| input.strip_prefix("http://"));
+    let is_url = without_scheme.is_some();
+    let host_and_path = without_scheme.unwrap_or(input);
+    let host_and_path = host_and_path.strip_prefix("www.").unwrap_or(host_and_path);
+
+    let hub_path = host_and_path
+        .strip_prefix("huggingface.co/")
+        .or_else(|| host_and_path.strip_prefix("hf.co/"));
+    let (path, has_host) = match hub_path {
+        Some(path) => (path, true),
+        None if is_url => return None, // Some other host
+        None => (host_and_path, false),
+    };
+
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    if segments.len() < 2 {
+        return None;
+    }
+
+    let (author, model) = (segments[0], segments[1]);
+    if matches!(author, "datasets" | "spaces" | "api" | "docs" | "settings") {
+        return None;
+    }
+
+    let is_valid_part = |part: &str| {
+        part.chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    };
+    if !is_valid_part(author) || !is_valid_part(model) {
+        return None;
+    }
+
+    let model_id = format!("{}/{}", author, model);
+    let rest = &segments[2..];
+
+    match rest {
+        [] => Some(ModelReference {
+            model_id,
+            revision: None,
+            file_path: None,
+        }),
+        // Bare IDs must be exactly author/model
+        _ if !has_host => None,
+        ["blob" | "resolve", revision, file @ ..] if !file.is_empty() => Some(ModelReference {
+            model_id,
+            revision: Some(revision.to_string()),
+            file_path: Some(file.join("/")),
+        }),
+        ["tree", revision, ..] => Some(ModelReference {
+            model_id,
+            revision: Some(revision.to_string()),
+            file_path: None,
+        }),
+        // Other model sub-pages (discussions, commits, ...) open the model
+        _ => Some(ModelReference {
+            model_id,
+            revision: None,
+            file_path: None,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Model ID, revision and file path of a reference
+    type Reference = (&'static str, Option<&'static str>, Option<&'static str>);
+
+    #[test]
+    fn test_parse_model_reference() {
+        let cases: &[(&str, Option<Reference>)] = &[
+            // Bare IDs
+            ("author/model", Some(("author/model", None, None))),
+            ("  author/model  ", Some(("author/model", None, None))),
+            ("author/model/", Some(("author/model", None, None))),
+            ("author/model.v2_x-y", Some(("author/model.v2_x-y", None, None))),
+            // Model pages
+            ("https://huggingface.co/author/model", Some(("author/model", None, None))),
+            ("https://huggingface.co/author/model/", Some(("author/model", None, None))),
+            ("http://www.huggingface.co/author/model", Some(("author/model", None, None))),
+            ("hf.co/author/model", Some(("author/model", None, None))),
+            ("https://huggingface.co/author/model#readme", Some(("author/model", None, None))),
+            (
+                "https://huggingface.co/author/model/discussions/3",
+                Some(("author/model", None, None)),
+            ),
+            // File URLs
+            (
+                "https://huggingface.co/author/model/blob/main/model.gguf",
+                Some(("author/model", Some("main"), Some("model.gguf"))),
+            ),
+            (
+                "https://huggingface.co/author/model/blob/main/Q4_K_M/model.gguf",
+                Some(("author/model", Some("main"), Some("Q4_K_M/model.gguf"))),
+            ),
+            (
+                "https://huggingface.co/author/model/resolve/v1.0/model.gguf?download=true",
+                Some(("author/model", Some("v1.0"), Some("model.gguf"))),
+            ),
+            (
+                "https://huggingface.co/author/model/resolve/0123456789abcdef0123456789abcdef01234567/config.json",
+                Some((
+                    "author/model",
+                    Some("0123456789abcdef0123456789abcdef01234567"),
+                    Some("config.json"),
+                )),
+            ),
+            (
+                "https://huggingface.co/author/model/blob/main",
+                Some(("author/model", None, None)),
+            ),
+            // Tree URLs
+            (
+                "https://huggingface.co/author/model/tree/dev",
+                Some(("author/model", Some("dev"), None)),
+            ),
+            (
+                "https://huggingface.co/author/model/tree/main/Q4_K_M/",
+                Some(("author/model", Some("main"), None)),
+            ),
+            // Not a model repository
+            ("", None),
+            ("author", None),
+            ("author/model/extra", None),
+            ("author/mo del", None),
+            ("https://huggingface.co/author", None),
+            ("https://huggingface.co/datasets/author/data", None),
+            ("https://huggingface.co/spaces/author/space", None),
+            ("https://github.com/author/model", None),
+        ];
+
+        for (input, expected) in cases {
+            let expected = expected.map(|(model_id, revision, file_path)| ModelReference {
+                model_id: model_id.to_string(),
+                revision: revision.map(str::to_string),
+                file_path: file_path.map(str::to_string),
+            });
+            assert_eq!(parse_model_reference(input), expected, "{}", input);
+        }
+    }
+
+    fn link_headers(link: &[u8]) -> reqwest::header::HeaderMap {
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert(
+            reqwest::header::LINK,
+            reqwest::header::HeaderValue::from_bytes(link).unwrap(),
+        );
+        headers
+    }
+
+    #[test]
+    fn test_next_page_url() {
+        let next = "https://huggingface.co/api/models?cursor=abc&limit=100";
+        let cases: &[(&[u8], Option<&str>)] = &[
+            (
+                b"<https://huggingface.co/api/models?cursor=abc&limit=100>; rel=\"next\"",
+                Some(next),
+            ),
+            // Among several links, in any order
+            (
+                b"<https://huggingface.co/api/models?cursor=old>; rel=\"prev\", \
+                  <https://huggingface.co/api/models?cursor=abc&limit=100>; rel=\"next\"",
+                Some(next),
+            ),
+            (
+                b"<https://huggingface.co/api/models?cursor=abc&limit=100>; rel=next, \
+                  <https://huggingface.co/api/models>; rel=\"first\"",
+                Some(next),
+            ),
+            (
+                b"<https://huggingface.co/api/models?cursor=abc&limit=100>; type=\"json\"; rel=\"next\"",
+                Some(next),
+            ),
+            // No next link
+            (b"<https://huggingface.co/api/models?cursor=old>; rel=\"prev\"", None),
+            (b"<https://huggingface.co/api/models?cursor=abc>", None),
+            (b"", None),
+            // Malformed
+            (b"rel=\"next\"", None),
+            (b"; ; ,", None),
+            (b"<https://huggingface.co/api/models?cursor=\xff>; rel=\"next\"", None),
+        ];
+
+        for (link, expected) in cases {
+            assert_eq!(
+                next_page_url(&link_headers(link)).as_deref(),
+                *expected,
+                "{}",
+                String::from_utf8_lossy(link)
+            );
+        }
+        assert_eq!(next_page_url(&reqwest::header::HeaderMap::new()), None);
+    }
+}
@@ -0,0 +1,363 @@
+//! Extraction of downloaded archives
+//!
+//! Some repositories ship assets (voices, datasets) as `.zip` or `.tar.gz`.
+//! With the `extract_archives` option on, recognized archives are unpacked
+//! into the directory they were downloaded to once they are verified (or,
+//! without a hash to check, once the download completes).
+//!
+//! Every entry path is checked before anything is written: an archive with
+//! an absolute path or a `..` component is refused as a whole, and links and
+//! special files are skipped, so nothing lands outside that directory.
+//! Files that already exist are left alone.
+
+use crate::download::DOWNLOAD_CONFIG;
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::Ordering;
+use tokio::sync::mpsc;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveKind {
+    Zip,
+    Tar,
+    TarGz,
+}
+
+/// The archive format of `path`, judged by its extension
+pub fn archive_kind(path: &Path) -> Option<ArchiveKind> {
+    let name = path.file_name()?.to_str()?.to_ascii_lowercase();
+    if name.ends_with(".zip") {
+        Some(ArchiveKind::Zip)
+    } else if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+        Some(ArchiveKind::TarGz)
+    } else if name.ends_with(".tar") {
+        Some(ArchiveKind::Tar)
+    } else {
+        None
+    }
+}
+
+/// What an extraction did
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ExtractSummary {
+    pub extracted: usize,
+    /// Files that already existed, and links or special files
+    pub skipped: usize,
+}
+
+/// `name` as a path below the destination, or `None` if it is absolute or
+/// climbs out with `..`
+fn safe_relative_path(name: &Path) -> Option<PathBuf> {
+    let mut relative = PathBuf::new();
+    for component in name.components() {
+        match component {
+            Component::Normal(part) => relative.push(part),
+            Component::CurDir => {}
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => return None,
+        }
+    }
+    Some(relative)
+}
+
+fn unsafe_entry(name: &Path) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("unsafe path in archive: {}", name.display()),
+    )
+}
+
+/// Write one regular file below `dest`, unless it exists already
+fn write_entry(
+    dest: &Path,
+    relative: &Path,
+    reader: &mut impl Read,
+    summary: &mut ExtractSummary,
+) -> io::Result<()> {
+    let target = dest.join(relative);
+    if target.exists() {
+        summary.skipped += 1;
+        return Ok(());
+    }
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = File::create(&target)?;
+    io::copy(reader, &mut file)?;
+    summary.extracted += 1;
+    Ok(())
+}
+
+fn extract_zip(archive: &Path, dest: &Path) -> io::Result<ExtractSummary> {
+    let mut zip = zip::ZipArchive::new(File::open(archive)?)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    // Check every path before writing anything
+    for index in 0..zip.len() {
+        let entry = zip
+            .by_index_raw(index)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        if entry.enclosed_name().is_none() {
+            return Err(unsafe_entry(Path::new(entry.name())));
+        }
+    }
+
+    let mut summary = ExtractSummary::default();
+    for index in 0..zip.len() {
+        let mut entry = zip
+            .by_index(index)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        let Some(relative) = entry.enclosed_name().and_then(safe_relative_path) else {
+            return Err(unsafe_entry(Path::new(entry.name())));
+        };
+        let is_symlink = entry
+            .unix_mode()
+            .is_some_and(|mode| mode & 0o170000 == 0o120000);
+        if entry.is_dir() {
+            fs::create_dir_all(dest.join(relative))?;
+        } else if is_symlink {
+            summary.skipped += 1;
+        } else {
+            write_entry(dest, &relative, &mut entry, &mut summary)?;
+        }
+    }
+    Ok(summary)
+}
+
+fn tar_reader(archive: &Path, kind: ArchiveKind) -> io::Result<tar::Archive<Box<dyn Read>>> {
+    let file = File::open(archive)?;
+    let reader: Box<dyn Read> = match kind {
+        ArchiveKind::TarGz => Box::new(flate2::read::GzDecoder::new(file)),
+        _ => Box::new(file),
+    };
+    Ok(tar::Archive::new(reader))
+}
+
+fn extract_tar(archive: &Path, kind: ArchiveKind, dest: &Path) -> io::Result<ExtractSummary> {
+    // Check every path before writing anything
+    for entry in tar_reader(archive, kind)?.entries()? {
+        let entry = entry?;
+        let name = entry.path()?;
+        if safe_relative_path(&name).is_none() {
+            return Err(unsafe_entry(&name));
+        }
+    }
+
+    let mut summary = ExtractSummary::default();
+    for entry in tar_reader(archive, kind)?.entries()? {
+        let mut entry = entry?;
+        let name = entry.path()?.into_owned();
+        let relative = safe_relative_path(&name).ok_or_else(|| unsafe_entry(&name))?;
+        let entry_type = entry.header().entry_type();
+        if entry_type.is_dir() {
+            fs::create_dir_all(dest.join(relative))?;
+        } else if entry_type.is_file() {
+            write_entry(dest, &relative, &mut entry, &mut summary)?;
+        } else if !entry_type.is_pax_global_extensions()
+            && !entry_type.is_pax_local_extensions()
+            && !entry_type.is_gnu_longname()
+            && !entry_type.is_gnu_longlink()
+        {
+            // Links and special files
+            summary.skipped += 1;
+        }
+    }
+    Ok(summary)
+}
+
+/// Extract `archive` into `dest`
+pub fn extract(archive: &Path, dest: &Path) -> io::Result<ExtractSummary> {
+    match archive_kind(archive) {
+        Some(ArchiveKind::Zip) => extract_zip(archive, dest),
+        Some(kind) => extract_tar(archive, kind, dest),
+        None => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "not a recognized archive",
+        )),
+    }
+}
+
+/// Extract a finished download next to itself if it is an archive and the
+/// `extract_archives` option is on, reporting the outcome on `status_tx`
+pub async fn extract_if_enabled(local_path: &str, status_tx: &mpsc::UnboundedSender<String>) {
+    let archive = PathBuf::from(local_path);
+    if !DOWNLOAD_CONFIG.extract_archives.load(Ordering::Relaxed) || archive_kind(&archive).is_none()
+    {
+        return;
+    }
+    let Some(dest) = archive.parent().map(Path::to_path_buf) else {
+        return;
+    };
+    let filename = archive
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    let _ = status_tx.send(format!("Extracting {}...", filename));
+    let result = tokio::task::spawn_blocking(move || extract(&archive, &dest)).await;
+    let status = match result {
+        Ok(Ok(summary)) if summary.skipped > 0 => format!(
+            "Extracted {} file(s) from {} ({} skipped: already present, or links)",
+            summary.extracted, filename, summary.skipped
+        ),
+        Ok(Ok(summary)) => format!("Extracted {} file(s) from {}", summary.extracted, filename),
+        Ok(Err(e)) => format!("Error: Could not extract {}: {}", filename, e),
+        Err(e) => format!("Error: Could not extract {}: {}", filename, e),
+    };
+    let _ = status_tx.send(status);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    enum Entry<'a> {
+        File(&'a str, &'a [u8]),
+        Symlink(&'a str, &'a str),
+    }
+
+    /// An empty scratch directory for one test
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "hf-downloader-archive-{}-{}",
+            std::process::id(),
+            name
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("dest")).unwrap();
+        dir
+    }
+
+    fn write_zip(path: &Path, entries: &[Entry]) {
+        let mut zip = zip::ZipWriter::new(File::create(path).unwrap());
+        let options = zip::write::FileOptions::default();
+        for entry in entries {
+            match entry {
+                Entry::File(name, data) => {
+                    zip.start_file(*name, options).unwrap();
+                    zip.write_all(data).unwrap();
+                }
+                Entry::Symlink(name, target) => zip.add_symlink(*name, *target, options).unwrap(),
+            }
+        }
+        zip.finish().unwrap();
+    }
+
+    /// Names are written into the header as they are, since the builder
+    /// refuses the unsafe ones
+    fn write_tar(path: &Path, kind: ArchiveKind, entries: &[Entry]) {
+        let file = File::create(path).unwrap();
+        let writer: Box<dyn Write> = match kind {
+            ArchiveKind::TarGz => Box::new(flate2::write::GzEncoder::new(
+                file,
+                flate2::Compression::default(),
+            )),
+            _ => Box::new(file),
+        };
+        let mut builder = tar::Builder::new(writer);
+        for entry in entries {
+            let (name, link, data, entry_type) = match entry {
+                Entry::File(name, data) => (*name, "", *data, tar::EntryType::Regular),
+                Entry::Symlink(name, target) => (*name, *target, &[][..], tar::EntryType::Symlink),
+            };
+            let mut header = tar::Header::new_old();
+            header.as_old_mut().name[..name.len()].copy_from_slice(name.as_bytes());
+            header.as_old_mut().linkname[..link.len()].copy_from_slice(link.as_bytes());
+            header.set_entry_type(entry_type);
+            header.set_size(data.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder.append(&header, data).unwrap();
+        }
+        builder.into_inner().unwrap().flush().unwrap();
+    }
+
+    fn write_archive(path: &Path, entries: &[Entry]) {
+        match archive_kind(path).unwrap() {
+            ArchiveKind::Zip => write_zip(path, entries),
+            kind => write_tar(path, kind, entries),
+        }
+    }
+
+    const ARCHIVES: [&str; 3] = ["assets.zip", "assets.tar", "assets.tar.gz"];
+
+    #[test]
+    fn test_safe_relative_path() {
+        let cases = [
+            ("voices/en.bin", Some("voices/en.bin")),
+            ("./voices/./en.bin", Some("voices/en.bin")),
+            ("voices/", Some("voices")),
+            ("../en.bin", None),
+            ("voices/../../en.bin", None),
+            ("voices/../en.bin", None),
+            ("/etc/passwd", None),
+        ];
+        for (name, expected) in cases {
+            assert_eq!(
+                safe_relative_path(Path::new(name)),
+                expected.map(PathBuf::from),
+                "{}",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn test_extract() {
+        for archive_name in ARCHIVES {
+            let dir = scratch_dir(&format!("extract-{}", archive_name));
+            let dest = dir.join("dest");
+            let archive = dir.join(archive_name);
+            write_archive(
+                &archive,
+                &[
+                    Entry::File("voices/en.bin", b"english"),
+                    Entry::File("readme.txt", b"new"),
+                    Entry::Symlink("voices/link", "../../outside.txt"),
+                ],
+            );
+            fs::write(dest.join("readme.txt"), "old").unwrap();
+
+            let summary = extract(&archive, &dest).unwrap();
+            assert_eq!(summary.extracted, 1, "{}", archive_name);
+            assert_eq!(summary.skipped, 2, "{}", archive_name);
+            assert_eq!(fs::read(dest.join("voices/en.bin")).unwrap(), b"english");
+            assert_eq!(fs::read_to_string(dest.join("readme.txt")).unwrap(), "old");
+            assert!(fs::symlink_metadata(dest.join("voices/link")).is_err());
+            fs::remove_dir_all(&dir).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_extract_refuses_unsafe_paths() {
+        for archive_name in ARCHIVES {
+            let dir = scratch_dir(&format!("unsafe-{}", archive_name));
+            let dest = dir.join("dest");
+            let absolute = dir.join("absolute.txt");
+            let unsafe_names = [
+                "../climbed.txt",
+                "voices/../../climbed.txt",
+                absolute.to_str().unwrap(),
+            ];
+            for name in unsafe_names {
+                let archive = dir.join(archive_name);
+                write_archive(
+                    &archive,
+                    &[
+                        Entry::File("safe.txt", b"safe"),
+                        Entry::File(name, b"escaped"),
+                    ],
+                );
+
+                let error = extract(&archive, &dest).unwrap_err();
+                assert_eq!(error.kind(), io::ErrorKind::InvalidData, "{}", name);
+                // Refused as a whole: not even the safe entry is written
+                assert_eq!(fs::read_dir(&dest).unwrap().count(), 0, "{}", name);
+                assert!(!dir.join("climbed.txt").exists(), "{}", name);
+                assert!(!absolute.exists(), "{}", name);
+            }
+            fs::remove_dir_all(&dir).unwrap();
+        }
+    }
+}
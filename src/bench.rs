@@ -0,0 +1,173 @@
+//! Connection benchmark (`bench` command)
+//!
+//! Reads the same amount of a large repository file with several
+//! combinations of parallel connections and chunk size, in memory (nothing
+//! is written to disk), and measures the throughput of each, so
+//! `concurrent_threads` and the chunk size can be picked for the link
+//! instead of guessed. Every run reads a different part of the file, so a
+//! CDN edge that cached one run's bytes doesn't favour the next.
+
+use crate::download::DOWNLOAD_CONFIG;
+use serde::Serialize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+type BenchError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Repository read when no `--model` is given (large, ungated files)
+pub const DEFAULT_MODEL: &str = "Qwen/Qwen2.5-7B-Instruct-GGUF";
+
+/// Bytes read by each run
+pub const RUN_BYTES: u64 = 64 * 1024 * 1024;
+
+const MB: u64 = 1024 * 1024;
+
+/// Connection count and chunk size of each run, so that every run has at
+/// least one chunk per connection
+pub const COMBINATIONS: [(usize, u64); 7] = [
+    (1, 8 * MB),
+    (2, 8 * MB),
+    (4, 8 * MB),
+    (4, 16 * MB),
+    (8, 4 * MB),
+    (8, 8 * MB),
+    (16, 4 * MB),
+];
+
+/// Throughput within this fraction of the fastest run counts as a tie,
+/// which the run with fewer connections wins
+const TIE_MARGIN: f64 = 0.05;
+
+/// The file a benchmark reads
+pub struct BenchTarget {
+    pub model_id: String,
+    pub filename: String,
+    pub size: u64,
+    /// Signed CDN URL the Hub's redirects end at
+    url: String,
+}
+
+/// Throughput of one connection count / chunk size combination
+#[derive(Debug, Clone, Serialize)]
+pub struct BenchResult {
+    pub threads: usize,
+    pub chunk_size: u64,
+    pub bytes: u64,
+    pub secs: f64,
+    pub speed_mbps: f64,
+}
+
+/// The largest file of `model_id`, which has to hold at least one run
+pub async fn find_target(
+    model_id: &str,
+    token: Option<&String>,
+) -> Result<BenchTarget, BenchError> {
+    let files = crate::api::fetch_repo_files(model_id, token).await?;
+    let file = files
+        .into_iter()
+        .filter(|f| f.file_type == "file")
+        .max_by_key(|f| f.size)
+        .filter(|f| f.size >= RUN_BYTES)
+        .ok_or_else(|| {
+            format!(
+                "{} has no file of at least {} MB to read",
+                model_id,
+                RUN_BYTES / MB
+            )
+        })?;
+
+    let url = crate::download::file_url(model_id, None, &file.path);
+    let url = crate::download::resolve_download_url(&url, token, timeout()).await?;
+    Ok(BenchTarget {
+        model_id: model_id.to_string(),
+        filename: file.path,
+        size: file.size,
+        url,
+    })
+}
+
+fn timeout() -> std::time::Duration {
+    std::time::Duration::from_secs(
+        DOWNLOAD_CONFIG
+            .download_timeout_secs
+            .load(Ordering::Relaxed),
+    )
+}
+
+/// Read [`RUN_BYTES`] of the target with `threads` connections fetching
+/// `chunk_size` ranges; `run` picks the part of the file that is read
+pub async fn run(
+    target: &BenchTarget,
+    threads: usize,
+    chunk_size: u64,
+    run: usize,
+) -> Result<BenchResult, BenchError> {
+    let regions = (target.size / RUN_BYTES).max(1);
+    let start = (run as u64 % regions) * RUN_BYTES;
+    let end = (start + RUN_BYTES).min(target.size);
+    let next = Arc::new(AtomicU64::new(start));
+    let read = Arc::new(AtomicU64::new(0));
+
+    let started = std::time::Instant::now();
+    let workers: Vec<_> = (0..threads)
+        .map(|_| {
+            let url = target.url.clone();
+            let next = next.clone();
+            let read = read.clone();
+            tokio::spawn(async move {
+                loop {
+                    let offset = next.fetch_add(chunk_size, Ordering::Relaxed);
+                    if offset >= end {
+                        return Ok::<_, BenchError>(());
+                    }
+                    let stop = (offset + chunk_size).min(end) - 1;
+                    read_range(&url, offset, stop, &read).await?;
+                }
+            })
+        })
+        .collect();
+    for worker in workers {
+        worker.await??;
+    }
+
+    let secs = started.elapsed().as_secs_f64().max(0.001);
+    let bytes = read.load(Ordering::Relaxed);
+    Ok(BenchResult {
+        threads,
+        chunk_size,
+        bytes,
+        secs,
+        speed_mbps: bytes as f64 / secs / MB as f64,
+    })
+}
+
+/// GET `offset..=stop` of the (CDN, so token-less) URL and count the bytes
+async fn read_range(url: &str, offset: u64, stop: u64, read: &AtomicU64) -> Result<(), BenchError> {
+    use futures::StreamExt;
+
+    let response = crate::http_client::get(url, None)
+        .timeout(timeout())
+        .header("Range", format!("bytes={}-{}", offset, stop))
+        .send()
+        .await?
+        .error_for_status()?;
+    let mut stream = response.bytes_stream();
+    while let Some(bytes) = stream.next().await {
+        read.fetch_add(bytes?.len() as u64, Ordering::Relaxed);
+    }
+    Ok(())
+}
+
+/// The run to keep: the fewest connections within [`TIE_MARGIN`] of the
+/// fastest, since extra connections that add nothing only load the server
+pub fn best(results: &[BenchResult]) -> Option<&BenchResult> {
+    let fastest = results.iter().map(|r| r.speed_mbps).fold(0.0, f64::max);
+    results
+        .iter()
+        .filter(|r| r.speed_mbps >= fastest * (1.0 - TIE_MARGIN))
+        .min_by(|a, b| {
+            a.threads
+                .cmp(&b.threads)
+                .then(b.speed_mbps.total_cmp(&a.speed_mbps))
+        })
+}
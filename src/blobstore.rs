@@ -0,0 +1,406 @@
+//! Content-addressable blob store (`blob_store` option)
+//!
+//! With the option on, a downloaded file whose LFS SHA-256 is known is moved
+//! to `.blobs/<sha256>` in its download root (the directory the
+//! `author/model` directories are in, e.g. `~/models`) and its place in the
+//! model directory becomes a symlink to it, as in the hf-hub cache. Each root
+//! has its own store, so a blob stays on the disk placement picked for the
+//! download. A file whose blob is already stored in its root is only linked,
+//! so models that share files share the bytes, and model directories can be
+//! moved or re-laid-out by moving links. Blobs are reference-counted through
+//! the registry: [`gc`] only deletes a blob once no registered download links
+//! to it any more.
+
+use crate::download::DOWNLOAD_CONFIG;
+use crate::models::{DownloadRegistry, ExpectedHash};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::Ordering;
+
+const BLOBS_DIR: &str = ".blobs";
+
+/// Whether new downloads go to the blob store
+pub fn enabled() -> bool {
+    DOWNLOAD_CONFIG.blob_store.load(Ordering::Relaxed)
+}
+
+/// The store of the download root `root`
+pub fn blobs_dir(root: &Path) -> PathBuf {
+    root.join(BLOBS_DIR)
+}
+
+/// Every store there may be blobs in: those of the configured download
+/// roots, of `~/models` next to the registry (where all blobs went before
+/// stores were per root), and of the roots registered or trashed downloads
+/// are in
+pub fn blobs_dirs(registry: &DownloadRegistry) -> Vec<PathBuf> {
+    let mut roots = vec![crate::utils::expand_home(
+        &crate::config::load_config().default_directory,
+    )];
+    roots.extend(crate::placement::roots().into_iter().map(PathBuf::from));
+    if let Some(dir) = crate::registry::get_registry_path().parent() {
+        roots.push(dir.to_path_buf());
+    }
+    let mut dirs: Vec<PathBuf> = Vec::new();
+    for dir in roots.iter().map(|root| blobs_dir(root)) {
+        if !dirs.contains(&dir) {
+            dirs.push(dir);
+        }
+    }
+
+    // A download's store is in one of the directories above it
+    let trashed = crate::trash::entries();
+    let paths = registry
+        .downloads
+        .iter()
+        .map(|d| PathBuf::from(&d.local_path))
+        .chain(trashed.iter().map(|e| e.path.clone()));
+    let mut searched = HashSet::new();
+    for path in paths {
+        for ancestor in path.ancestors().skip(1) {
+            if !searched.insert(ancestor.to_path_buf()) {
+                break;
+            }
+            let dir = blobs_dir(ancestor);
+            if dir.is_dir() && !dirs.contains(&dir) {
+                dirs.push(dir);
+            }
+        }
+    }
+    // Canonical like the blob paths links resolve to, so they compare equal
+    let mut canonical: Vec<PathBuf> = Vec::new();
+    for dir in dirs.iter().filter_map(|dir| dir.canonicalize().ok()) {
+        if !canonical.contains(&dir) {
+            canonical.push(dir);
+        }
+    }
+    canonical
+}
+
+/// Whether a file name in the store names a blob: a SHA-256 (64 hex digits)
+pub fn is_blob_hash(hash: &str) -> bool {
+    hash.len() == 64 && hash.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// SHA-256 a download's blob is stored under. The store is keyed by LFS
+/// SHA-256 only: a git blob id of a small non-LFS file is another kind of
+/// hash and never names a blob.
+fn blob_key(hash: &ExpectedHash) -> Option<&str> {
+    hash.sha256().filter(|sha256| is_blob_hash(sha256))
+}
+
+pub fn blob_path(root: &Path, sha256: &str) -> PathBuf {
+    blobs_dir(root).join(sha256.to_ascii_lowercase())
+}
+
+#[cfg(unix)]
+pub(crate) fn symlink(blob: &Path, link: &Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(blob, link)
+}
+
+#[cfg(windows)]
+pub(crate) fn symlink(blob: &Path, link: &Path) -> io::Result<()> {
+    std::os::windows::fs::symlink_file(blob, link)
+}
+
+/// Link `path` to the blob of `hash` stored in `root` if there is one of
+/// `total_size` bytes (any size when 0); returns whether it was linked
+pub fn link_stored(
+    root: &Path,
+    hash: &ExpectedHash,
+    total_size: u64,
+    path: &Path,
+) -> io::Result<bool> {
+    let Some(sha256) = blob_key(hash) else {
+        return Ok(false);
+    };
+    let blob = blob_path(root, sha256);
+    match fs::metadata(&blob) {
+        Ok(meta) if meta.is_file() && (total_size == 0 || meta.len() == total_size) => {
+            // A dangling link left by a discarded blob
+            if fs::symlink_metadata(path).is_ok_and(|m| m.file_type().is_symlink()) {
+                fs::remove_file(path)?;
+            }
+            symlink(&blob, path)?;
+            Ok(true)
+        }
+        _ => Ok(false),
+    }
+}
+
+/// Existing blobs up to this size are hashed before a new download is
+/// dropped in their favour; larger ones only have their size checked
+const REHASH_MAX_SIZE: u64 = 16 * 1024 * 1024;
+
+/// Move a downloaded file into the store of `root` and leave a link in its
+/// place. A blob already stored under the hash is kept and the file dropped
+/// when it is intact; a truncated or corrupted one is replaced by the file
+pub fn store(root: &Path, path: &Path, hash: &ExpectedHash) -> io::Result<()> {
+    let Some(sha256) = blob_key(hash).filter(|_| !is_stored_link(path)) else {
+        return Ok(());
+    };
+    let blob = blob_path(root, sha256);
+    fs::create_dir_all(blobs_dir(root))?;
+    if blob_intact(&blob, fs::metadata(path)?.len(), sha256) {
+        fs::remove_file(path)?;
+    } else if fs::rename(path, &blob).is_err() {
+        // The store is on another filesystem
+        fs::copy(path, &blob)?;
+        fs::remove_file(path)?;
+    }
+    symlink(&blob, path)
+}
+
+/// Whether a stored blob holds `len` bytes hashing to `sha256` (hashed
+/// only up to [`REHASH_MAX_SIZE`])
+fn blob_intact(blob: &Path, len: u64, sha256: &str) -> bool {
+    match fs::metadata(blob) {
+        Ok(meta) if meta.is_file() && meta.len() == len => {
+            len > REHASH_MAX_SIZE
+                || crate::verification::file_matches_hash(
+                    blob,
+                    &ExpectedHash::Sha256(sha256.to_ascii_lowercase()),
+                )
+                .unwrap_or(false)
+        }
+        _ => false,
+    }
+}
+
+/// Whether `path` is a link into the store
+pub fn is_stored_link(path: &Path) -> bool {
+    stored_blob(path).is_some()
+}
+
+/// The blob a link in a model directory points at, in whichever store
+/// (canonical, and only while it exists)
+pub(crate) fn stored_blob(path: &Path) -> Option<PathBuf> {
+    let target = fs::read_link(path).ok()?;
+    let in_store = target.parent()?.file_name()? == BLOBS_DIR
+        && is_blob_hash(&target.file_name()?.to_string_lossy());
+    if !in_store {
+        return None;
+    }
+    path.parent()?.join(target).canonicalize().ok()
+}
+
+/// Remove the blob behind a link whose content failed verification, so it
+/// is not linked again; the dangling link counts as a missing file and the
+/// download starts over
+pub fn discard_blob(path: &Path) {
+    if let Some(blob) = stored_blob(path) {
+        let _ = fs::remove_file(blob);
+    }
+}
+
+/// Size and sharing of the stores
+#[derive(Debug, Default, Serialize)]
+pub struct StoreStats {
+    /// The stores, one per download root
+    pub directories: Vec<PathBuf>,
+    pub blobs: usize,
+    pub bytes: u64,
+    /// Registered (or trashed) downloads linking to a blob
+    pub links: usize,
+    /// Bytes the links would take as separate files
+    pub linked_bytes: u64,
+    /// Blobs no registered download links to
+    pub unreferenced: usize,
+    pub unreferenced_bytes: u64,
+}
+
+/// Every blob stored in `dirs` with its size
+fn stored_blobs(dirs: &[PathBuf]) -> Vec<(PathBuf, u64)> {
+    dirs.iter()
+        .filter_map(|dir| fs::read_dir(dir).ok())
+        .flat_map(|entries| entries.flatten())
+        .filter_map(|e| {
+            let meta = e.metadata().ok()?;
+            meta.is_file().then(|| (e.path(), meta.len()))
+        })
+        .collect()
+}
+
+/// Links to each blob among the registry's downloads (its refcount); links
+/// waiting in the trash count too, so a restore finds its blob
+pub(crate) fn reference_counts(registry: &DownloadRegistry) -> HashMap<PathBuf, usize> {
+    let mut counts = HashMap::new();
+    let trashed = crate::trash::entries();
+    let links = registry
+        .downloads
+        .iter()
+        .map(|d| PathBuf::from(&d.local_path))
+        .chain(trashed.iter().map(|e| e.file_path()));
+    for link in links {
+        if let Some(blob) = stored_blob(&link) {
+            *counts.entry(blob).or_insert(0) += 1;
+        }
+    }
+    counts
+}
+
+pub fn stats(registry: &DownloadRegistry) -> StoreStats {
+    let counts = reference_counts(registry);
+    let mut stats = StoreStats {
+        directories: blobs_dirs(registry),
+        ..StoreStats::default()
+    };
+    for (blob, size) in stored_blobs(&stats.directories) {
+        let links = counts.get(&blob).copied().unwrap_or(0);
+        stats.blobs += 1;
+        stats.bytes += size;
+        stats.links += links;
+        stats.linked_bytes += size * links as u64;
+        if links == 0 {
+            stats.unreferenced += 1;
+            stats.unreferenced_bytes += size;
+        }
+    }
+    stats
+}
+
+/// Delete the blobs no registered download links to, in every store (or
+/// only list them with `dry_run`); returns their number and total size
+pub fn gc(registry: &DownloadRegistry, dry_run: bool) -> (usize, u64) {
+    let counts = reference_counts(registry);
+    let mut removed = (0, 0);
+    for (blob, size) in stored_blobs(&blobs_dirs(registry)) {
+        if counts.contains_key(&blob) {
+            continue;
+        }
+        if dry_run || fs::remove_file(&blob).is_ok() {
+            removed.0 += 1;
+            removed.1 += size;
+        }
+    }
+    removed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{DownloadMetadata, DownloadStatus};
+    use sha2::{Digest, Sha256};
+
+    /// Store `content` in `root` as a blob, returning its hash and path
+    fn write_blob(root: &Path, content: &[u8]) -> (String, PathBuf) {
+        let sha256 = hex::encode(Sha256::digest(content));
+        let blob = blob_path(root, &sha256);
+        fs::create_dir_all(blob.parent().unwrap()).unwrap();
+        fs::write(&blob, content).unwrap();
+        (sha256, blob)
+    }
+
+    /// A registered download of author/`model` linking to `blob`
+    fn linked_download(root: &Path, model: &str, blob: &Path) -> DownloadMetadata {
+        let path = root.join("author").join(model).join("model.gguf");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        symlink(blob, &path).unwrap();
+        DownloadMetadata {
+            model_id: format!("author/{}", model),
+            filename: "model.gguf".to_string(),
+            url: format!(
+                "https://huggingface.co/author/{}/resolve/main/model.gguf",
+                model
+            ),
+            local_path: path.to_string_lossy().into_owned(),
+            total_size: 0,
+            downloaded_size: 0,
+            status: DownloadStatus::Complete,
+            expected_sha256: None,
+            expected_git_blob: None,
+            commit: None,
+        }
+    }
+
+    #[test]
+    fn test_gc() {
+        let (_home, home) = crate::registry::scratch_home("blobs-gc");
+        let root = home.join("models");
+        let (_, linked) = write_blob(&root, b"linked");
+        let (_, trashed) = write_blob(&root, b"trashed");
+        let (_, orphan) = write_blob(&root, b"orphan");
+
+        // A link whose blob is gone counts for nothing
+        let missing = blob_path(&root, &"f".repeat(64));
+        let mut registry = DownloadRegistry {
+            downloads: vec![
+                linked_download(&root, "linked", &linked),
+                linked_download(&root, "trashed", &trashed),
+                linked_download(&root, "dangling", &missing),
+            ],
+            ..Default::default()
+        };
+        let deleted = crate::trash::delete_downloads(
+            &mut registry,
+            |d| d.model_id == "author/trashed",
+            crate::trash::DeleteMode::Trash,
+        );
+        assert!(deleted.errors.is_empty(), "{:?}", deleted.errors);
+        assert_eq!(crate::trash::entries().len(), 1);
+
+        let stats = stats(&registry);
+        assert_eq!((stats.blobs, stats.links, stats.unreferenced), (3, 2, 1));
+        assert_eq!(gc(&registry, true), (1, 6));
+        assert!(orphan.exists());
+        assert_eq!(gc(&registry, false), (1, 6));
+        assert!(!orphan.exists());
+        // The trashed link still needs its blob, the dangling one none
+        assert!(linked.exists() && trashed.exists());
+        assert_eq!(gc(&registry, false), (0, 0));
+
+        // Once the trash is purged its blob goes too
+        assert_eq!(crate::trash::purge(true, false), (1, 7));
+        assert!(!trashed.exists());
+        assert!(linked.exists());
+    }
+
+    #[test]
+    fn test_store() {
+        let (_home, home) = crate::registry::scratch_home("blobs-store");
+        let root = home.join("models");
+        let content = b"weights";
+        let sha256 = hex::encode(Sha256::digest(content));
+        let hash = ExpectedHash::Sha256(sha256.clone());
+        let download = |model: &str| {
+            let path = root.join("author").join(model).join("model.gguf");
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(&path, content).unwrap();
+            path
+        };
+
+        let first = download("first");
+        store(&root, &first, &hash).unwrap();
+        let blob = blob_path(&root, &sha256);
+        assert_eq!(stored_blob(&first), Some(blob.canonicalize().unwrap()));
+        assert_eq!(fs::read(&first).unwrap(), content);
+
+        // An intact blob is kept and the new file only linked
+        let second = download("second");
+        store(&root, &second, &hash).unwrap();
+        assert!(is_stored_link(&second));
+
+        // A truncated or corrupted blob is replaced by the new download
+        for bad in [&b"weig"[..], b"WEIGHTS"] {
+            fs::write(&blob, bad).unwrap();
+            let again = download("again");
+            store(&root, &again, &hash).unwrap();
+            assert!(is_stored_link(&again));
+            assert_eq!(fs::read(&blob).unwrap(), content);
+            assert_eq!(fs::read(&first).unwrap(), content);
+            fs::remove_file(&again).unwrap();
+        }
+
+        // A git blob id, even one spelled like a SHA-256, names no blob
+        let small = download("small");
+        let git_blob = ExpectedHash::GitBlob(sha256.clone());
+        store(&root, &small, &git_blob).unwrap();
+        assert!(!is_stored_link(&small));
+        let linked = root.join("author/small/linked.json");
+        assert!(!link_stored(&root, &git_blob, 0, &linked).unwrap());
+        assert!(link_stored(&root, &hash, 0, &linked).unwrap());
+    }
+}
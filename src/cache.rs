@@ -0,0 +1,233 @@
+//! On-disk cache of HuggingFace API responses
+//!
+//! Successful JSON responses from the API layer (searches, model metadata and
+//! tree listings) are written to `~/.config/jreb/cache/<sha256(url)>.json`.
+//! Entries younger than the configured TTL are served without a network
+//! request. Older entries are still used when offline mode is enabled
+//! (`--offline`) or when a request fails with a network error, so search
+//! results, metadata and file listings stay browsable without connectivity.
+//! The directory is pruned oldest-first to stay under the configured size.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Global cache configuration (thread-safe, runtime-modifiable)
+pub struct CacheConfig {
+    pub enabled: AtomicBool,
+    pub ttl_secs: AtomicU64,
+    pub max_size_bytes: AtomicU64,
+}
+
+impl CacheConfig {
+    pub const fn new() -> Self {
+        Self {
+            enabled: AtomicBool::new(true),
+            ttl_secs: AtomicU64::new(30 * 60),                 // 30 minutes
+            max_size_bytes: AtomicU64::new(200 * 1024 * 1024), // 200 MB
+        }
+    }
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub static CACHE_CONFIG: CacheConfig = CacheConfig::new();
+
+/// Apply cache settings (from `AppOptions`) to the global config
+pub fn configure(enabled: bool, ttl_minutes: u64, max_size_mb: u64) {
+    CACHE_CONFIG.enabled.store(enabled, Ordering::Relaxed);
+    CACHE_CONFIG
+        .ttl_secs
+        .store(ttl_minutes * 60, Ordering::Relaxed);
+    CACHE_CONFIG
+        .max_size_bytes
+        .store(max_size_mb * 1024 * 1024, Ordering::Relaxed);
+}
+
+pub fn is_enabled() -> bool {
+    CACHE_CONFIG.enabled.load(Ordering::Relaxed)
+}
+
+/// Explicit offline mode: never touch the network, serve everything from cache
+static OFFLINE: AtomicBool = AtomicBool::new(false);
+
+/// Set when the last API request fell back to cache because the network failed
+static NETWORK_UNAVAILABLE: AtomicBool = AtomicBool::new(false);
+
+/// A cached API response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheEntry {
+    pub url: String,
+    /// Unix timestamp (seconds) when the response was fetched
+    pub fetched_at: u64,
+    pub body: String,
+}
+
+impl CacheEntry {
+    /// Age of the entry in seconds
+    pub fn age_secs(&self) -> u64 {
+        now_secs().saturating_sub(self.fetched_at)
+    }
+
+    /// True if the entry is younger than the configured TTL
+    pub fn is_fresh(&self) -> bool {
+        self.age_secs() < CACHE_CONFIG.ttl_secs.load(Ordering::Relaxed)
+    }
+}
+
+pub fn set_offline(offline: bool) {
+    OFFLINE.store(offline, Ordering::Relaxed);
+}
+
+pub fn is_offline() -> bool {
+    OFFLINE.load(Ordering::Relaxed)
+}
+
+pub fn set_network_unavailable(unavailable: bool) {
+    NETWORK_UNAVAILABLE.store(unavailable, Ordering::Relaxed);
+}
+
+/// True if responses are currently served from cache (explicitly or after a network failure)
+pub fn serving_from_cache() -> bool {
+    is_offline() || NETWORK_UNAVAILABLE.load(Ordering::Relaxed)
+}
+
+/// Get the cache directory path
+pub fn get_cache_dir() -> PathBuf {
+    let home = std::env::var("HOME").unwrap_or_else(|_| "/tmp".to_string());
+    PathBuf::from(format!("{}/.config/jreb/cache", home))
+}
+
+fn entry_path(url: &str) -> PathBuf {
+    let hash = Sha256::digest(url.as_bytes());
+    get_cache_dir().join(format!("{:x}.json", hash))
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Load a cached response for `url`, regardless of age
+pub fn load(url: &str) -> Option<CacheEntry> {
+    let contents = fs::read_to_string(entry_path(url)).ok()?;
+    let entry: CacheEntry = serde_json::from_str(&contents).ok()?;
+    // Guard against (astronomically unlikely) hash collisions
+    (entry.url == url).then_some(entry)
+}
+
+/// Load a cached response for `url` only if it is within the TTL
+pub fn load_fresh(url: &str) -> Option<CacheEntry> {
+    if !is_enabled() {
+        return None;
+    }
+    load(url).filter(|entry| entry.is_fresh())
+}
+
+/// Store a response body for `url`
+/// Failures are ignored: the cache is best-effort
+pub fn store(url: &str, body: &str) {
+    if !is_enabled() {
+        return;
+    }
+
+    let dir = get_cache_dir();
+    if fs::create_dir_all(&dir).is_err() {
+        return;
+    }
+
+    let entry = CacheEntry {
+        url: url.to_string(),
+        fetched_at: now_secs(),
+        body: body.to_string(),
+    };
+
+    if let Ok(json) = serde_json::to_string(&entry) {
+        // Write to a temp file and rename so readers never see a partial entry
+        let path = entry_path(url);
+        let tmp_path = path.with_extension("json.tmp");
+        if fs::write(&tmp_path, json).is_ok() {
+            let _ = fs::rename(&tmp_path, &path);
+        }
+    }
+
+    prune(CACHE_CONFIG.max_size_bytes.load(Ordering::Relaxed));
+}
+
+/// List cache files with their size and modification time
+fn list_entries() -> Vec<(PathBuf, u64, SystemTime)> {
+    let Ok(read_dir) = fs::read_dir(get_cache_dir()) else {
+        return Vec::new();
+    };
+
+    read_dir
+        .flatten()
+        .filter_map(|e| {
+            let meta = e.metadata().ok()?;
+            if !meta.is_file() {
+                return None;
+            }
+            let modified = meta.modified().unwrap_or(UNIX_EPOCH);
+            Some((e.path(), meta.len(), modified))
+        })
+        .collect()
+}
+
+/// Delete the oldest entries until the cache is at most `max_bytes`
+fn prune(max_bytes: u64) {
+    let mut entries = list_entries();
+    let mut total: u64 = entries.iter().map(|(_, size, _)| size).sum();
+    if total <= max_bytes {
+        return;
+    }
+
+    entries.sort_by_key(|(_, _, modified)| *modified);
+    for (path, size, _) in entries {
+        if total <= max_bytes {
+            break;
+        }
+        if fs::remove_file(&path).is_ok() {
+            total = total.saturating_sub(size);
+        }
+    }
+}
+
+/// Every cached response, regardless of age (unreadable entries are skipped)
+pub fn entries() -> Vec<CacheEntry> {
+    list_entries()
+        .into_iter()
+        .filter_map(|(path, _, _)| {
+            let contents = fs::read_to_string(path).ok()?;
+            serde_json::from_str(&contents).ok()
+        })
+        .collect()
+}
+
+/// Total number of entries and bytes currently in the cache
+pub fn stats() -> (usize, u64) {
+    let entries = list_entries();
+    let bytes = entries.iter().map(|(_, size, _)| size).sum();
+    (entries.len(), bytes)
+}
+
+/// Remove all cached responses
+/// Returns the number of entries and bytes removed
+pub fn clear() -> std::io::Result<(usize, u64)> {
+    let mut removed = 0;
+    let mut bytes = 0;
+    for (path, size, _) in list_entries() {
+        fs::remove_file(&path)?;
+        removed += 1;
+        bytes += size;
+    }
+    Ok((removed, bytes))
+}
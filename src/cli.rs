@@ -1,10 +1,11 @@
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 
 /// TUI and CLI for searching and downloading HuggingFace models
 #[derive(Parser, Debug, Clone)]
 #[command(name = "rust-hf-downloader")]
 #[command(about = "TUI and CLI for searching and downloading HuggingFace models", long_about = None)]
 #[command(version = "1.3.2")]
+#[command(after_long_help = GENERAL_EXAMPLES)]
 pub struct Cli {
     /// Run in CLI mode (no TUI)
     #[arg(long, global = true)]
@@ -22,13 +23,99 @@ pub struct Cli {
     #[arg(long, global = true)]
     pub dry_run: bool,
 
+    /// Offline mode - serve search results and file listings from the local cache
+    #[arg(long, global = true)]
+    pub offline: bool,
+
+    /// Download/verification progress output (default: bar, or json with --json)
+    #[arg(long, global = true, value_enum)]
+    pub progress: Option<ProgressMode>,
+
+    /// Don't report download/verification progress (same as --progress=none)
+    #[arg(long, global = true, conflicts_with = "progress")]
+    pub no_progress: bool,
+
+    /// Download models without a permissive license when the config sets
+    /// require_license_confirmation
+    #[arg(long, global = true)]
+    pub accept_license: bool,
+
+    /// Don't verify SHA256 hashes of downloaded files
+    #[arg(long, global = true)]
+    pub skip_verify: bool,
+
+    /// User-Agent header for headless requests (overrides the user_agent
+    /// config option; an empty string sends none)
+    #[arg(long, global = true)]
+    pub user_agent: Option<String>,
+
+    /// PEM file of root CAs to trust in addition to the built-in ones, e.g. a
+    /// corporate proxy's (overrides the ca_bundle config option)
+    #[arg(long, global = true)]
+    pub ca_bundle: Option<std::path::PathBuf>,
+
+    /// Serve Prometheus metrics on ADDR (e.g. 127.0.0.1:9184) at /metrics
+    /// while headless downloads or the daemon run
+    #[arg(long, global = true, value_name = "ADDR")]
+    pub metrics_addr: Option<String>,
+
+    /// Don't verify TLS certificates at all. Dangerous: anyone on the network
+    /// path can read and change traffic, token included. Prefer --ca-bundle
+    #[arg(long, global = true)]
+    pub insecure: bool,
+
+    /// Don't write the download registry or config, e.g. next to another
+    /// running instance (which is otherwise refused)
+    #[arg(long, global = true)]
+    pub read_only: bool,
+
+    /// Lock downloads: ask before any download is queued, and refuse them
+    /// when nobody can answer (same as the no_download config option)
+    #[arg(long, global = true)]
+    pub no_download: bool,
+
+    /// Screen-reader friendly TUI: no box-drawing characters or color-only
+    /// cues, and the focused item described on the top line (same as the
+    /// screen_reader config option)
+    #[arg(long, global = true)]
+    pub screen_reader: bool,
+
+    /// Append TUI focus and status changes to FILE as plain text lines, e.g.
+    /// for `tail -f` into a speech synthesizer (overrides screen_reader_log)
+    #[arg(long, global = true, value_name = "FILE")]
+    pub screen_reader_log: Option<std::path::PathBuf>,
+
+    /// With --progress=plain, print a line every N percent
+    #[arg(long, global = true, default_value_t = 10, value_parser = clap::value_parser!(u32).range(1..=100))]
+    pub progress_step: u32,
+
+    /// Model ID or huggingface.co URL to open (model page, blob or resolve URL)
+    ///
+    /// In the TUI this jumps straight to the model (and file). With --headless,
+    /// file URLs are downloaded and model references are listed.
+    pub target: Option<String>,
+
     #[command(subcommand)]
     pub command: Option<Commands>,
 }
 
+/// How headless downloads report progress
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProgressMode {
+    /// Redrawn in-place progress bar (carriage returns)
+    Bar,
+    /// One line per file every --progress-step percent, for logs and cron jobs
+    Plain,
+    /// One JSON object per progress update
+    Json,
+    /// No progress output
+    None,
+}
+
 #[derive(Subcommand, Debug, Clone)]
 pub enum Commands {
     /// Search for models
+    #[command(after_long_help = SEARCH_EXAMPLES)]
     Search {
         /// Search query
         query: String,
@@ -44,11 +131,56 @@ pub enum Commands {
         /// Minimum likes filter
         #[arg(long)]
         min_likes: Option<u64>,
+
+        /// Only models with at least this many parameters (e.g., "8B", "500M")
+        #[arg(long, value_parser = parse_param_count)]
+        min_params: Option<u64>,
+
+        /// Only models with at most this many parameters (e.g., "34B")
+        #[arg(long, value_parser = parse_param_count)]
+        max_params: Option<u64>,
+
+        /// Leave out gated models (which need an accepted license and a token)
+        #[arg(long)]
+        no_gated: bool,
+
+        /// Leave out private models (visible with a token that can access them)
+        #[arg(long)]
+        no_private: bool,
+
+        /// Leave out models tagged as adult content (not-for-all-audiences)
+        #[arg(long)]
+        no_nsfw: bool,
+
+        /// Match the query against model card (README) text instead of repository names
+        #[arg(long, conflicts_with = "cursor")]
+        full_text: bool,
+
+        /// Look up each result's total repository size (one extra request per model)
+        #[arg(long)]
+        repo_size: bool,
+
+        /// Results requested per API call (default: the configured page size, 100)
+        #[arg(long, value_parser = clap::value_parser!(u64).range(1..=1000))]
+        page_size: Option<u64>,
+
+        /// Results fetched before filtering, following further pages (default: configured, 100)
+        #[arg(long, value_parser = clap::value_parser!(u64).range(1..=5000))]
+        max_results: Option<u64>,
+
+        /// Continue from the `next_cursor` of a previous page (fetches one page)
+        #[arg(long)]
+        cursor: Option<String>,
+
+        /// Print one record per model: csv, tsv, json or a template like '{id}\t{downloads}'
+        #[arg(long)]
+        format: Option<String>,
     },
 
     /// Download a model
+    #[command(after_long_help = DOWNLOAD_EXAMPLES)]
     Download {
-        /// Model ID (e.g., "meta-llama/Llama-3.1-8B")
+        /// Model ID (e.g., "meta-llama/Llama-3.1-8B") or huggingface.co model/file URL
         model_id: String,
 
         /// Filter by quantization type (e.g., "Q4_K_M", "Q8_0")
@@ -59,17 +191,569 @@ pub enum Commands {
         #[arg(long)]
         all: bool,
 
+        /// For LoRA/PEFT adapters, also download the base model (into <output>/<author>/<model>)
+        #[arg(long)]
+        with_base: bool,
+
+        /// Files of a repository without GGUF quants (instead of --all): all,
+        /// weights (no duplicate formats), essential (configs, tokenizer,
+        /// weights and remote code only), a library layout (diffusers,
+        /// diffusers-fp16, diffusers-bf16, sentence-transformers, speech) or a
+        /// runtime export (onnx, openvino, coreml, tensorrt, tflite: that
+        /// directory plus root configs)
+        #[arg(long, value_parser = parse_repo_preset)]
+        preset: Option<crate::models::RepoPreset>,
+
+        /// Output directory (default: picked by the [[placement]] rules, else the
+        /// default directory)
+        #[arg(short, long)]
+        output: Option<String>,
+
+        /// Don't download; verify the SHA256 of files already downloaded
+        #[arg(long)]
+        verify_only: bool,
+    },
+
+    /// Download matching files from every model of an organization or user
+    #[command(after_long_help = DOWNLOAD_ORG_EXAMPLES)]
+    DownloadOrg {
+        /// Organization or user name (e.g., "bartowski")
+        org: String,
+
+        /// Only files whose path matches this glob (e.g., "*.gguf"); repeatable
+        #[arg(long)]
+        include: Vec<String>,
+
+        /// Only files of this quantization type (e.g., "Q4_K_M")
+        #[arg(long)]
+        quant: Option<String>,
+
         /// Output directory
         #[arg(short, long)]
         output: Option<String>,
+
+        /// Queue the downloads without asking for confirmation
+        #[arg(short, long)]
+        yes: bool,
+    },
+
+    /// Print models or quantizations as lines for fzf and other pickers,
+    /// then download the line that is picked
+    #[command(after_long_help = PICK_EXAMPLES)]
+    Pick {
+        /// Search query, model ID, or "-" to read a picked line from stdin
+        query: String,
+
+        /// Picker command to run on the candidates (e.g., "fzf") instead of printing them
+        #[arg(long)]
+        picker: Option<String>,
+
+        /// Output directory (default: picked by the [[placement]] rules, else the
+        /// default directory)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+
+    /// List your own models, including private ones (requires --token)
+    MyModels,
+
+    /// List GGUF repositories created in the last few days, grouped by base model
+    #[command(after_long_help = RECENT_EXAMPLES)]
+    Recent {
+        /// Number of days to look back (default: recent_gguf_days from the config, 7)
+        #[arg(long, value_parser = clap::value_parser!(u64).range(1..=30))]
+        days: Option<u64>,
+
+        /// Print one record per model: csv, tsv, json or a template like '{id}\t{downloads}'
+        #[arg(long)]
+        format: Option<String>,
+    },
+
+    /// Print a shell completion script, e.g. `source <(rust-hf-downloader completions bash)`
+    ///
+    /// Besides commands and flags, model IDs and quantization names known
+    /// locally (downloads, notes, watchlist, cached API responses) complete
+    /// after `download`, `list`, `pick` and `--quantization`.
+    Completions { shell: clap_complete::Shell },
+
+    /// Print the man page, or write one page per command with --out-dir
+    Man {
+        /// Command to print the page of (e.g., "download")
+        command: Option<String>,
+
+        /// Write rust-hf-downloader.1 and a page per command into this directory
+        #[arg(long)]
+        out_dir: Option<std::path::PathBuf>,
     },
 
     /// List available files for a model
+    #[command(after_long_help = LIST_EXAMPLES)]
     List {
-        /// Model ID (e.g., "meta-llama/Llama-3.1-8B")
+        /// Model ID (e.g., "meta-llama/Llama-3.1-8B") or huggingface.co model URL
         model_id: String,
+
+        /// Print one record per file: csv, tsv, json or a template like '{quant}\t{size}\t{path}'
+        #[arg(long)]
+        format: Option<String>,
     },
 
     /// Resume incomplete downloads
     Resume,
+
+    /// Add models downloaded by other tools (huggingface-cli cache or
+    /// --local-dir copies, older downloads) to the registry
+    #[command(after_long_help = ADOPT_EXAMPLES)]
+    Adopt {
+        /// Directory to scan for model folders
+        dir: std::path::PathBuf,
+
+        /// Repository the whole directory is a copy of, instead of working it out
+        #[arg(long)]
+        model: Option<String>,
+
+        /// Only adopt files whose content hashes to the Hub's checksum (reads every file)
+        #[arg(long)]
+        hash: bool,
+    },
+
+    /// Manage the on-disk API response cache
+    Cache {
+        #[command(subcommand)]
+        action: CacheAction,
+    },
+
+    /// Inspect or clean up the content-addressable blob store (`blob_store` option)
+    Blobs {
+        #[command(subcommand)]
+        action: BlobsAction,
+    },
+
+    /// Delete a model's downloaded files; they go to the trash for
+    /// `trash_retention_days` (see `restore`)
+    #[command(after_long_help = TRASH_EXAMPLES)]
+    Delete {
+        /// Model ID or huggingface.co model URL
+        model_id: String,
+
+        /// Only this file (path within the repository)
+        #[arg(long)]
+        file: Option<String>,
+    },
+
+    /// Put a deleted file back where it was and register it again
+    #[command(after_long_help = TRASH_EXAMPLES)]
+    Restore {
+        /// Trash entry to restore (see `trash list`); the last deleted file if omitted
+        id: Option<String>,
+    },
+
+    /// List or purge deleted files
+    #[command(after_long_help = TRASH_EXAMPLES)]
+    Trash {
+        #[command(subcommand)]
+        action: TrashAction,
+    },
+
+    /// Delete the least recently used models until enough disk space is free
+    #[command(after_long_help = EVICT_EXAMPLES)]
+    Evict {
+        /// Free space to reach, e.g. 200GB or 1.5TB
+        #[arg(long, value_parser = parse_byte_size)]
+        keep_free: u64,
+
+        /// Directory whose models may be deleted (default: the download directory)
+        #[arg(long)]
+        dir: Option<String>,
+
+        /// Delete without asking for confirmation
+        #[arg(short, long)]
+        yes: bool,
+    },
+
+    /// Print the local path of a downloaded model (exit code 1 if it isn't),
+    /// e.g. for `llama-server -m $(rust-hf-downloader path MODEL --quant Q4_K_M)`
+    #[command(after_long_help = PATH_EXAMPLES)]
+    Path {
+        /// Model ID, or huggingface.co model/file URL
+        model_id: String,
+
+        /// The file of this quantization type (e.g., "Q4_K_M"); needed when
+        /// several are downloaded
+        #[arg(long)]
+        quant: Option<String>,
+    },
+
+    /// Show download history statistics (total bytes, throughput per day, biggest models)
+    Stats {
+        /// Number of most recent days to list
+        #[arg(long, default_value_t = 14)]
+        days: usize,
+    },
+
+    /// Measure download throughput with several connection counts and chunk
+    /// sizes, then offer to save the fastest in the config
+    #[command(after_long_help = BENCH_EXAMPLES)]
+    Bench {
+        /// Repository whose largest file is read (default: Qwen/Qwen2.5-7B-Instruct-GGUF)
+        #[arg(long)]
+        model: Option<String>,
+
+        /// Save the best settings without asking
+        #[arg(short, long)]
+        yes: bool,
+    },
+
+    /// Watch repositories for upstream changes
+    #[command(after_long_help = WATCH_EXAMPLES)]
+    Watch {
+        #[command(subcommand)]
+        action: WatchAction,
+    },
+
+    /// Hide models or whole authors from search results
+    #[command(after_long_help = IGNORE_EXAMPLES)]
+    Ignore {
+        #[command(subcommand)]
+        action: IgnoreAction,
+    },
+
+    /// Run downloads in a background daemon that outlives the terminal
+    #[command(after_long_help = DAEMON_EXAMPLES)]
+    Daemon {
+        #[command(subcommand)]
+        action: DaemonAction,
+    },
+}
+
+impl Commands {
+    /// Whether the command can change the download registry, the config or
+    /// the other files an instance owns (downloading included), so it needs
+    /// the instance lock; the others run read-only next to another instance
+    pub fn writes_state(&self, dry_run: bool) -> bool {
+        match self {
+            Commands::Search { .. }
+            | Commands::MyModels
+            | Commands::Recent { .. }
+            | Commands::Completions { .. }
+            | Commands::Man { .. }
+            | Commands::List { .. }
+            | Commands::Cache { .. }
+            | Commands::Path { .. }
+            | Commands::Stats { .. } => false,
+            Commands::Blobs { action } => matches!(action, BlobsAction::Gc) && !dry_run,
+            Commands::Trash { action } => matches!(action, TrashAction::Gc { .. }) && !dry_run,
+            Commands::Watch { action } => !matches!(action, WatchAction::List),
+            Commands::Ignore { action } => !matches!(action, IgnoreAction::List),
+            Commands::Download { .. }
+            | Commands::DownloadOrg { .. }
+            | Commands::Adopt { .. }
+            | Commands::Delete { .. }
+            | Commands::Evict { .. } => !dry_run,
+            _ => true,
+        }
+    }
+
+    /// Whether downloading is all the command writes, so a client of the
+    /// download daemon can run it by handing the downloads over; the daemon
+    /// owns the registry, so other writing commands wait until it stops
+    pub fn only_downloads(&self) -> bool {
+        matches!(
+            self,
+            Commands::Download {
+                verify_only: false,
+                ..
+            } | Commands::DownloadOrg { .. }
+                | Commands::Pick { .. }
+                | Commands::Resume
+        )
+    }
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum CacheAction {
+    /// Remove all cached API responses
+    Clear,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum BlobsAction {
+    /// Show the store's size, how many downloads link to it and what it saves
+    Status,
+    /// Delete blobs no registered download links to any more (see --dry-run)
+    Gc,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum TrashAction {
+    /// Show deleted files waiting in the trash
+    List,
+    /// Permanently delete files past the retention period (see --dry-run)
+    Gc {
+        /// Empty the whole trash, not only expired files
+        #[arg(long)]
+        all: bool,
+    },
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum WatchAction {
+    /// Add a model to the watchlist (snapshots its current files)
+    Add {
+        /// Model ID or huggingface.co model URL
+        model_id: String,
+    },
+    /// Remove a model from the watchlist
+    Remove {
+        /// Model ID or huggingface.co model URL
+        model_id: String,
+    },
+    /// Show watched models
+    List,
+    /// Check watched models for updates and list changed files
+    Check,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum IgnoreAction {
+    /// Hide a model, or every model of an author
+    Add {
+        /// Model ID or huggingface.co model URL, or `<author>/*` for an author
+        target: String,
+    },
+    /// Show a hidden model or author in searches again
+    Remove {
+        /// Model ID or huggingface.co model URL, or `<author>/*` for an author
+        target: String,
+    },
+    /// Show hidden models and authors
+    List,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum DaemonAction {
+    /// Start the daemon; it resumes incomplete and queued downloads, and
+    /// downloads started by the TUI or other commands are handed to it
+    Start {
+        /// Run in the background, logging to ~/models/hf-downloader-daemon.log
+        #[arg(long)]
+        detach: bool,
+    },
+    /// Follow the daemon's progress until its queue is empty (Ctrl+C detaches)
+    Attach,
+    /// Show the daemon's queue and current download
+    Status,
+    /// Save the daemon's pending queue and stop it
+    Stop,
+}
+
+/// `--min-params`/`--max-params` value: "8B", "1.5B", "500M", ...
+fn parse_param_count(value: &str) -> Result<u64, String> {
+    crate::utils::parse_params(value)
+        .ok_or_else(|| format!("'{}' is not a parameter count like 8B or 500M", value))
+}
+
+/// `--keep-free` value: "200GB", "1.5TB", "500M", ...
+fn parse_byte_size(value: &str) -> Result<u64, String> {
+    crate::utils::parse_size(value)
+        .ok_or_else(|| format!("'{}' is not a size like 200GB or 1.5TB", value))
 }
+
+/// `--preset` value: all, weights, essential, a layout or a runtime name
+fn parse_repo_preset(value: &str) -> Result<crate::models::RepoPreset, String> {
+    crate::models::RepoPreset::from_name(value).ok_or_else(|| {
+        let names: Vec<&str> = crate::models::RepoPreset::all()
+            .iter()
+            .map(|preset| preset.name())
+            .collect();
+        format!("'{}' is not a preset ({})", value, names.join(", "))
+    })
+}
+
+// Recipes shown by `help <command>` / `--help` (not `-h`) and in the man
+// page's EXAMPLES section. Each starts with the "Examples:" heading.
+
+const GENERAL_EXAMPLES: &str = "\
+Examples:
+  # Open the TUI
+  rust-hf-downloader
+
+  # Open the TUI on a model (a huggingface.co URL works too)
+  rust-hf-downloader unsloth/Qwen3-8B-GGUF
+
+  # Long-form help with recipes for a command
+  rust-hf-downloader help download
+
+  # Install the man pages
+  rust-hf-downloader man --out-dir ~/.local/share/man/man1";
+
+const SEARCH_EXAMPLES: &str = "\
+Examples:
+  # Most downloaded GGUF builds of a model
+  rust-hf-downloader --headless search \"llama 8b gguf\" --sort downloads
+
+  # Only well-known repositories
+  rust-hf-downloader --headless search qwen --min-downloads 10000 --min-likes 50
+
+  # Models between 8B and 34B parameters
+  rust-hf-downloader --headless search \"instruct gguf\" --min-params 8B --max-params 34B
+
+  # Models whose card mentions a feature
+  rust-hf-downloader --headless search \"function calling\" --full-text
+
+  # Page through every result as JSON, following \"next_cursor\"
+  rust-hf-downloader --headless --json search mistral
+  rust-hf-downloader --headless --json search mistral --cursor '<next_cursor>'
+
+  # Tab-separated ID, downloads and parameters, most downloaded first
+  rust-hf-downloader --headless search \"qwen gguf\" --format '{id}\\t{downloads}\\t{params}' \\
+    | sort -t$'\\t' -k2 -nr";
+
+const DOWNLOAD_EXAMPLES: &str = "\
+Examples:
+  # One quantization of a GGUF model (all of its parts)
+  rust-hf-downloader --headless download bartowski/Llama-3.2-3B-Instruct-GGUF \\
+    --quantization Q4_K_M --output ~/models
+
+  # Every file of a safetensors model, gated models need a token
+  rust-hf-downloader --headless --token hf_... download meta-llama/Llama-3.1-8B --all
+
+  # A single file from a pasted blob/resolve URL
+  rust-hf-downloader --headless download \\
+    https://huggingface.co/unsloth/Qwen3-8B-GGUF/blob/main/Qwen3-8B-Q4_K_M.gguf
+
+  # A quant published on its own branch (EXL2 bpw branches)
+  rust-hf-downloader --headless download turboderp/Llama-3.1-8B-Instruct-exl2 --quantization 4.0bpw
+
+  # Only what transformers needs: configs, tokenizer and safetensors shards
+  rust-hf-downloader --headless download Qwen/Qwen2.5-7B-Instruct --preset essential
+
+  # A diffusers pipeline: its component folders with fp16 weights
+  rust-hf-downloader --headless download stabilityai/stable-diffusion-xl-base-1.0 --preset diffusers-fp16
+
+  # Only the ONNX export (onnx/) with the configs and tokenizer it loads
+  rust-hf-downloader --headless download Xenova/all-MiniLM-L6-v2 --preset onnx
+
+  # A LoRA adapter together with its base model
+  rust-hf-downloader --headless download some-user/my-lora --all --with-base
+
+  # See what would be downloaded, then re-check hashes of what is on disk
+  rust-hf-downloader --headless --dry-run download unsloth/Qwen3-8B-GGUF --quantization Q8_0
+  rust-hf-downloader --headless download unsloth/Qwen3-8B-GGUF --quantization Q8_0 --verify-only
+
+  # Quiet, log-friendly progress for cron jobs
+  rust-hf-downloader --headless --progress plain --progress-step 25 \\
+    download unsloth/Qwen3-8B-GGUF --quantization Q4_K_M";
+
+const DOWNLOAD_ORG_EXAMPLES: &str = "\
+Examples:
+  # The Q4_K_M GGUFs of every model an organization publishes
+  rust-hf-downloader --headless download-org bartowski --quant Q4_K_M --output ~/models
+
+  # Only the plan: per-repository file counts and sizes
+  rust-hf-downloader --headless --dry-run download-org unsloth --include '*.gguf'
+
+  # Unattended (scripts, CI): skip the confirmation
+  rust-hf-downloader --headless download-org my-org --include '*.safetensors' --include '*.json' --yes";
+
+const PICK_EXAMPLES: &str = "\
+Examples:
+  # Search, pick a model, then a quantization, with fzf
+  rust-hf-downloader --headless pick \"llama 8b gguf\" --picker fzf --output ~/models
+
+  # The same rounds chained by hand
+  rust-hf-downloader --headless pick \"llama 8b gguf\" | fzf \\
+    | rust-hf-downloader --headless pick - | fzf | rust-hf-downloader --headless pick -
+
+  # Pick a quantization of a known model
+  rust-hf-downloader --headless pick unsloth/Qwen3-8B-GGUF --picker fzf";
+
+const RECENT_EXAMPLES: &str = "\
+Examples:
+  # GGUF builds published this week, grouped by the model they quantize
+  rust-hf-downloader --headless recent
+
+  # Just today's repository IDs
+  rust-hf-downloader --headless recent --days 1 --format '{id}'
+
+  # Groups as JSON
+  rust-hf-downloader --headless --json recent --days 3 | jq '.groups[].base_model'";
+
+const LIST_EXAMPLES: &str = "\
+Examples:
+  # Quantizations of a GGUF model with sizes
+  rust-hf-downloader --headless list bartowski/Llama-3.2-3B-Instruct-GGUF
+
+  # Every file as CSV for a spreadsheet
+  rust-hf-downloader --headless list bartowski/Llama-3.2-3B-Instruct-GGUF --format csv > files.csv
+
+  # From the cache only, without network access
+  rust-hf-downloader --headless --offline list unsloth/Qwen3-8B-GGUF";
+
+const DAEMON_EXAMPLES: &str = "\
+Examples:
+  # Start the daemon in the background, then queue a model through it
+  rust-hf-downloader --headless daemon start --detach
+  rust-hf-downloader --headless download unsloth/Qwen3-8B-GGUF --quantization Q4_K_M
+
+  # Watch its progress from another terminal (Ctrl+C leaves it running)
+  rust-hf-downloader --headless daemon attach";
+
+const BENCH_EXAMPLES: &str = "\
+Examples:
+  # Try 7 combinations (64 MB each, nothing is written to disk), then pick one
+  rust-hf-downloader --headless bench
+
+  # Read a repository closer to what you download, and save the result unasked
+  rust-hf-downloader --headless bench --model unsloth/Qwen3-8B-GGUF --yes";
+
+const ADOPT_EXAMPLES: &str = "\
+Examples:
+  # Register everything huggingface-cli downloaded
+  rust-hf-downloader --headless adopt ~/.cache/huggingface/hub
+
+  # A folder the tool can't name, checked byte for byte
+  rust-hf-downloader --headless adopt ~/llms/qwen --model Qwen/Qwen3-8B --hash --dry-run";
+
+const TRASH_EXAMPLES: &str = "\
+Examples:
+  # Delete one file of a model (kept in the trash for a week by default)
+  rust-hf-downloader --headless delete unsloth/Qwen3-8B-GGUF --file Qwen3-8B-Q8_0.gguf
+
+  # Undo it
+  rust-hf-downloader --headless restore
+
+  # See what is in the trash, then empty it
+  rust-hf-downloader --headless trash list
+  rust-hf-downloader --headless trash gc --all";
+
+const EVICT_EXAMPLES: &str = "\
+Examples:
+  # See which models would go to get 200 GB free
+  rust-hf-downloader --headless evict --keep-free 200GB --dry-run
+
+  # Delete them (asks first; --yes for cron)
+  rust-hf-downloader --headless evict --keep-free 200GB";
+
+const PATH_EXAMPLES: &str = "\
+Examples:
+  # Serve a downloaded quantization (prints the first part of a split GGUF)
+  llama-server -m \"$(rust-hf-downloader --headless path unsloth/Qwen3-8B-GGUF --quant Q4_K_M)\"
+
+  # Directory of a safetensors repository
+  rust-hf-downloader --headless path Qwen/Qwen3-8B";
+
+const WATCH_EXAMPLES: &str = "\
+Examples:
+  # Start watching a repository (snapshots its files)
+  rust-hf-downloader --headless watch add unsloth/Qwen3-8B-GGUF
+
+  # From cron: report added, removed and modified files as JSON
+  rust-hf-downloader --headless --json watch check";
+
+const IGNORE_EXAMPLES: &str = "\
+Examples:
+  # Never list this re-upload again
+  rust-hf-downloader --headless ignore add someone/Llama-3-8B-reupload
+
+  # Hide everything an account publishes
+  rust-hf-downloader --headless ignore add 'spam-quants/*'";
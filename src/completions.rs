@@ -0,0 +1,233 @@
+//! Shell completion scripts with dynamic model ID and quantization candidates
+//!
+//! The static part (subcommands and flags) is generated by `clap_complete`.
+//! Each script then wraps it: after `download`, `list`, `pick`, `watch add`
+//! or `watch remove` it asks the `__complete model` hook for model IDs,
+//! and after `--quantization`/`--quant` it asks `__complete quant` for the
+//! quantization names of the model on the command line. Candidates come from
+//! local state only (download registry, notes, watchlist and the API cache),
+//! so completing never waits on the network.
+
+use crate::{api, cache, registry, watchlist};
+use clap::CommandFactory;
+use clap_complete::Shell;
+use std::collections::BTreeSet;
+
+/// First argument that turns the binary into the completion hook:
+/// `__complete model|quant <current word> [command line words...]`
+pub const HOOK: &str = "__complete";
+
+/// Print the completion script for `shell`
+pub fn print_script(shell: Shell) {
+    let mut command = crate::cli::Cli::command();
+    let bin = command.get_name().to_string();
+    clap_complete::generate(shell, &mut command, &bin, &mut std::io::stdout());
+
+    let hook = match shell {
+        Shell::Bash => BASH_HOOK,
+        Shell::Zsh => ZSH_HOOK,
+        Shell::Fish => FISH_HOOK,
+        // No dynamic candidates for the other shells
+        _ => return,
+    };
+    print!(
+        "{}",
+        hook.replace("{fn}", &bin.replace('-', "_"))
+            .replace("{bin}", &bin)
+            .replace("{hook}", HOOK)
+    );
+}
+
+/// Print the candidates for the word being completed, one per line; `args`
+/// are the hook's arguments. Anything unexpected prints nothing, so the
+/// shell just offers no candidates.
+pub fn run_hook(args: &[String]) {
+    let (Some(kind), current) = (args.first(), args.get(1).map_or("", String::as_str)) else {
+        return;
+    };
+    let words = args.get(2..).unwrap_or_default();
+    let candidates = match kind.as_str() {
+        "model" => complete_model_ids(current),
+        "quant" => complete_quants(current, words),
+        _ => return,
+    };
+    for candidate in candidates {
+        println!("{}", candidate);
+    }
+}
+
+/// Known model IDs starting with `current`, or whose name (after the author)
+/// does, ignoring case
+fn complete_model_ids(current: &str) -> Vec<String> {
+    let current = current.to_lowercase();
+    let mut ids = BTreeSet::new();
+
+    let registry = registry::load_registry();
+    ids.extend(registry.downloads.into_iter().map(|d| d.model_id));
+    ids.extend(registry.model_notes.into_keys());
+    ids.extend(
+        watchlist::load_watchlist()
+            .models
+            .into_iter()
+            .map(|m| m.model_id),
+    );
+    for entry in cache::entries() {
+        ids.extend(cached_model_ids(&entry));
+    }
+
+    ids.into_iter()
+        .filter(|id| {
+            let id = id.to_lowercase();
+            let name = id.split_once('/').map_or(id.as_str(), |(_, name)| name);
+            id.starts_with(&current) || name.starts_with(&current)
+        })
+        .collect()
+}
+
+/// Quantization names starting with `current` (ignoring case) known for the
+/// model on the command line, or for any model when there is none
+fn complete_quants(current: &str, words: &[String]) -> Vec<String> {
+    let model_id = words
+        .iter()
+        .filter(|word| !word.starts_with('-'))
+        .find_map(|word| api::parse_model_reference(word))
+        .map(|reference| reference.model_id);
+    let matches_model = |id: &str| match model_id.as_deref() {
+        Some(model_id) => model_id == id,
+        None => true,
+    };
+
+    let mut paths: Vec<String> = registry::load_registry()
+        .downloads
+        .into_iter()
+        .filter(|d| matches_model(&d.model_id))
+        .map(|d| d.filename)
+        .collect();
+    for entry in cache::entries() {
+        if let Some((id, files)) = cached_file_listing(&entry) {
+            if matches_model(&id) {
+                paths.extend(files);
+            }
+        }
+    }
+
+    let current = current.to_lowercase();
+    paths
+        .iter()
+        .filter_map(|path| {
+            let name = path.rsplit('/').next().unwrap_or(path);
+            api::extract_quantization_type(name)
+        })
+        .filter(|quant| quant.to_lowercase().starts_with(&current))
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+const MODELS_API: &str = "https://huggingface.co/api/models";
+
+/// Model IDs in a cached response: the repository a metadata or tree URL
+/// belongs to, or the results of a search
+fn cached_model_ids(entry: &cache::CacheEntry) -> Vec<String> {
+    #[derive(serde::Deserialize)]
+    struct Listed {
+        id: String,
+    }
+
+    if let Some(id) = cached_repo_id(&entry.url) {
+        return vec![id];
+    }
+    if entry.url.starts_with(&format!("{}?", MODELS_API)) {
+        if let Ok(models) = serde_json::from_str::<Vec<Listed>>(&entry.body) {
+            return models.into_iter().map(|m| m.id).collect();
+        }
+    }
+    Vec::new()
+}
+
+/// Repository of a cached `/api/models/{author}/{name}[/...]` URL
+fn cached_repo_id(url: &str) -> Option<String> {
+    let rest = url.strip_prefix(MODELS_API)?.strip_prefix('/')?;
+    let mut segments = rest.split(['/', '?']);
+    let (author, name) = (segments.next()?, segments.next()?);
+    (!author.is_empty() && !name.is_empty()).then(|| format!("{}/{}", author, name))
+}
+
+/// File paths in a cached metadata or tree listing, with the repository
+fn cached_file_listing(entry: &cache::CacheEntry) -> Option<(String, Vec<String>)> {
+    #[derive(serde::Deserialize)]
+    struct Metadata {
+        #[serde(default)]
+        siblings: Vec<Sibling>,
+    }
+    #[derive(serde::Deserialize)]
+    struct Sibling {
+        rfilename: String,
+    }
+    #[derive(serde::Deserialize)]
+    struct TreeItem {
+        path: String,
+    }
+
+    let id = cached_repo_id(&entry.url)?;
+    let files = if entry.url.contains("/tree/") {
+        serde_json::from_str::<Vec<TreeItem>>(&entry.body)
+            .ok()?
+            .into_iter()
+            .map(|item| item.path)
+            .collect()
+    } else {
+        serde_json::from_str::<Metadata>(&entry.body)
+            .ok()?
+            .siblings
+            .into_iter()
+            .map(|sibling| sibling.rfilename)
+            .collect()
+    };
+    Some((id, files))
+}
+
+const BASH_HOOK: &str = r#"
+_{fn}_dynamic() {
+    local cur="${COMP_WORDS[COMP_CWORD]}" prev="${COMP_WORDS[COMP_CWORD-1]}"
+    case "$prev" in
+        download|list|pick|add|remove)
+            local IFS=$'\n'
+            COMPREPLY=($({bin} {hook} model "$cur" 2>/dev/null))
+            return 0
+            ;;
+        --quantization|--quant)
+            local IFS=$'\n'
+            COMPREPLY=($({bin} {hook} quant "$cur" "${COMP_WORDS[@]}" 2>/dev/null))
+            return 0
+            ;;
+    esac
+    _{bin} "$@"
+}
+complete -F _{fn}_dynamic -o bashdefault -o default {bin}
+"#;
+
+const ZSH_HOOK: &str = r#"
+_{fn}_dynamic() {
+    local -a candidates
+    case "${words[CURRENT-1]}" in
+        download|list|pick|add|remove)
+            candidates=(${(f)"$({bin} {hook} model "${words[CURRENT]}" 2>/dev/null)"})
+            compadd -U -- $candidates
+            ;;
+        --quantization|--quant)
+            candidates=(${(f)"$({bin} {hook} quant "${words[CURRENT]}" "${words[@]}" 2>/dev/null)"})
+            compadd -U -- $candidates
+            ;;
+        *)
+            _{bin} "$@"
+            ;;
+    esac
+}
+compdef _{fn}_dynamic {bin}
+"#;
+
+const FISH_HOOK: &str = r#"
+complete -c {bin} -n "__fish_seen_subcommand_from download list pick add remove; and not string match -q -- '-*' (commandline -ct)" -f -a "({bin} {hook} model (commandline -ct) 2>/dev/null)"
+complete -c {bin} -l quantization -l quant -x -a "({bin} {hook} quant (commandline -ct) (commandline -opc) 2>/dev/null)"
+"#;
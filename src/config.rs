@@ -46,12 +46,16 @@ pub fn load_config() -> AppOptions {
     }
 }
 
-/// Save configuration to disk
+/// Save configuration to disk (a no-op in read-only mode)
 pub fn save_config(options: &AppOptions) -> Result<(), Box<dyn std::error::Error>> {
+    if crate::instance::is_read_only() {
+        return Ok(());
+    }
     ensure_config_dir()?;
 
     let toml_string = toml::to_string_pretty(options)?;
-    fs::write(get_config_path(), toml_string)?;
+    let path = get_config_path();
+    crate::instance::with_file_lock(&path, || fs::write(&path, toml_string))?;
 
     Ok(())
 }
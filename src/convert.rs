@@ -0,0 +1,123 @@
+//! GGUF conversion of downloaded repositories with llama.cpp
+//!
+//! Repositories without GGUF files (safetensors/PyTorch checkpoints) can be
+//! converted after downloading by shelling out to llama.cpp's
+//! `convert_hf_to_gguf.py`, which writes an F16 GGUF next to the model, and
+//! then to `llama-quantize` for the configured quantization. The tool paths
+//! come from the options; nothing here downloads llama.cpp.
+
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use tokio::process::Command;
+
+/// Quantization types offered for converted models
+pub const QUANT_TYPES: &[&str] = &["Q4_K_M", "Q5_K_M", "Q6_K", "Q8_0"];
+
+/// Paths of the llama.cpp tools
+#[derive(Debug, Clone)]
+pub struct ConvertTools {
+    /// Interpreter that runs the conversion script
+    pub python: String,
+    pub convert_script: PathBuf,
+    /// Without it, the F16 GGUF is the result
+    pub quantize_binary: Option<PathBuf>,
+    pub quant_type: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConversionState {
+    /// Waiting for the repository's downloads to finish
+    Waiting,
+    Converting,
+    Quantizing,
+    Done(PathBuf),
+    Failed(String),
+}
+
+impl ConversionState {
+    pub fn is_finished(&self) -> bool {
+        matches!(self, ConversionState::Done(_) | ConversionState::Failed(_))
+    }
+}
+
+/// A repository to convert once its downloads finish
+#[derive(Debug, Clone)]
+pub struct ConversionJob {
+    pub model_id: String,
+    /// Directory the repository was downloaded to
+    pub model_root: PathBuf,
+    pub state: ConversionState,
+}
+
+/// `<model root>/<model name>-<suffix>.gguf`
+pub fn output_path(model_root: &Path, model_id: &str, suffix: &str) -> PathBuf {
+    let name = model_id.rsplit('/').next().unwrap_or(model_id);
+    model_root.join(format!("{}-{}.gguf", name, suffix))
+}
+
+/// Run one tool, failing with the last line it wrote to stderr
+async fn run_tool(command: &mut Command, what: &str) -> Result<(), String> {
+    let output = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .output()
+        .await
+        .map_err(|e| format!("could not run {}: {}", what, e))?;
+    if output.status.success() {
+        return Ok(());
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let last_line = stderr
+        .lines()
+        .rev()
+        .find(|line| !line.trim().is_empty())
+        .unwrap_or("no output");
+    Err(format!(
+        "{} failed ({}): {}",
+        what,
+        output.status,
+        last_line.trim()
+    ))
+}
+
+/// Convert the repository at `model_root` to GGUF and quantize it, calling
+/// `on_state` as each step starts. Returns the path of the final GGUF.
+pub async fn convert_to_gguf(
+    tools: &ConvertTools,
+    model_id: &str,
+    model_root: &Path,
+    on_state: impl Fn(ConversionState),
+) -> Result<PathBuf, String> {
+    let f16_path = output_path(model_root, model_id, "F16");
+
+    on_state(ConversionState::Converting);
+    run_tool(
+        Command::new(&tools.python)
+            .arg(&tools.convert_script)
+            .arg(model_root)
+            .arg("--outfile")
+            .arg(&f16_path)
+            .arg("--outtype")
+            .arg("f16"),
+        "convert_hf_to_gguf.py",
+    )
+    .await?;
+
+    let Some(quantize_binary) = &tools.quantize_binary else {
+        return Ok(f16_path);
+    };
+
+    on_state(ConversionState::Quantizing);
+    let quant_path = output_path(model_root, model_id, &tools.quant_type);
+    run_tool(
+        Command::new(quantize_binary)
+            .arg(&f16_path)
+            .arg(&quant_path)
+            .arg(&tools.quant_type),
+        "llama-quantize",
+    )
+    .await?;
+    Ok(quant_path)
+}
@@ -0,0 +1,420 @@
+//! Background daemon that owns the download queue
+//!
+//! `rust-hf-downloader --headless daemon start` runs the download engine
+//! without a terminal and listens on `~/models/.hf-downloader.sock`. While
+//! it runs, the TUI and headless download commands hand their downloads to
+//! it instead of fetching them, and `daemon attach` (or the TUI) follows its
+//! progress, so closing a terminal does not stop a long download.
+//!
+//! Each connection carries one JSON request line and one JSON response.
+
+use crate::models::{
+    DownloadProgress, ExpectedHash, QueueState, QueuedDownload, VerificationProgress,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::io;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokio::sync::{mpsc, Mutex};
+
+/// How often clients poll the daemon's status
+pub const STATUS_INTERVAL: std::time::Duration = std::time::Duration::from_millis(500);
+
+/// How long a client waits before offering the daemon the downloads it did
+/// not take again
+const RETRY_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Status log lines the daemon keeps for clients that attach later
+const LOG_CAPACITY: usize = 200;
+
+/// The front-ends' download message: model ID, filename, base path,
+/// expected hash, token, size and revision
+type DownloadMessage = (
+    String,
+    String,
+    PathBuf,
+    Option<ExpectedHash>,
+    Option<String>,
+    u64,
+    Option<String>,
+);
+
+pub fn get_socket_path() -> PathBuf {
+    let home = std::env::var("HOME").unwrap_or_else(|_| "/tmp".to_string());
+    PathBuf::from(format!("{}/models/.hf-downloader.sock", home))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "request", rename_all = "snake_case")]
+pub enum DaemonRequest {
+    /// Queue downloads (ones the daemon already has are skipped)
+    Enqueue {
+        downloads: Vec<QueuedDownload>,
+        #[serde(default)]
+        hf_token: Option<String>,
+    },
+    /// Queue and progress, plus log lines numbered `log_since` and later
+    Status { log_since: u64 },
+    /// Save the pending queue and exit
+    Stop,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "response", rename_all = "snake_case")]
+pub enum DaemonResponse {
+    Queued { count: usize },
+    Status(DaemonStatus),
+    Stopping,
+    Error { message: String },
+}
+
+impl DaemonResponse {
+    /// Describe a response the request did not expect
+    pub fn unexpected(&self) -> String {
+        match self {
+            DaemonResponse::Error { message } => message.clone(),
+            _ => "unexpected response from the daemon".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DaemonStatus {
+    pub pid: u32,
+    /// Downloads waiting to start
+    pub queued: usize,
+    pub queued_bytes: u64,
+    pub progress: Option<DownloadProgress>,
+    pub verifying: Vec<DaemonVerification>,
+    /// Files queued for or being verified
+    pub verification_backlog: usize,
+    pub log: Vec<String>,
+    /// Number of the next log line, for the following `Status` request
+    pub log_next: u64,
+}
+
+impl DaemonStatus {
+    /// Nothing queued, downloading or verifying
+    pub fn is_idle(&self) -> bool {
+        self.queued == 0 && self.progress.is_none() && self.verification_backlog == 0
+    }
+}
+
+/// A file being verified, as reported to clients
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DaemonVerification {
+    /// Missing from daemons older than the model ID in progress titles
+    #[serde(default)]
+    pub model_id: String,
+    pub filename: String,
+    pub local_path: String,
+    pub verified_bytes: u64,
+    pub total_bytes: u64,
+    pub speed_mbps: f64,
+}
+
+impl From<&VerificationProgress> for DaemonVerification {
+    fn from(progress: &VerificationProgress) -> Self {
+        Self {
+            model_id: progress.model_id.clone(),
+            filename: progress.filename.clone(),
+            local_path: progress.local_path.clone(),
+            verified_bytes: progress.verified_bytes.load(Ordering::Relaxed),
+            total_bytes: progress.total_bytes,
+            speed_mbps: progress.speed_mbps,
+        }
+    }
+}
+
+impl From<DaemonVerification> for VerificationProgress {
+    fn from(verification: DaemonVerification) -> Self {
+        Self {
+            model_id: verification.model_id,
+            filename: verification.filename,
+            local_path: verification.local_path,
+            verified_bytes: Arc::new(AtomicU64::new(verification.verified_bytes)),
+            total_bytes: verification.total_bytes,
+            speed_mbps: verification.speed_mbps,
+            skipped: Arc::new(AtomicBool::new(false)),
+        }
+    }
+}
+
+/// The daemon's recent status messages, numbered so clients only fetch new ones
+#[derive(Debug, Default)]
+pub struct DaemonLog {
+    lines: VecDeque<String>,
+    next: u64,
+}
+
+impl DaemonLog {
+    pub fn push(&mut self, line: String) {
+        if self.lines.len() == LOG_CAPACITY {
+            self.lines.pop_front();
+        }
+        self.lines.push_back(line);
+        self.next += 1;
+    }
+
+    /// Kept lines numbered `since` and later, and the number of the next line
+    pub fn since(&self, since: u64) -> (Vec<String>, u64) {
+        let first = self.next - self.lines.len() as u64;
+        let skip = usize::try_from(since.saturating_sub(first)).unwrap_or(usize::MAX);
+        (self.lines.iter().skip(skip).cloned().collect(), self.next)
+    }
+}
+
+/// Send one request to the running daemon
+#[cfg(unix)]
+pub async fn request(request: &DaemonRequest) -> io::Result<DaemonResponse> {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let mut stream = tokio::net::UnixStream::connect(get_socket_path()).await?;
+    let mut line = serde_json::to_string(request)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    line.push('\n');
+    stream.write_all(line.as_bytes()).await?;
+    stream.shutdown().await?;
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await?;
+    serde_json::from_str(&response).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+#[cfg(not(unix))]
+pub async fn request(_request: &DaemonRequest) -> io::Result<DaemonResponse> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "the download daemon needs Unix domain sockets",
+    ))
+}
+
+/// Whether a daemon answers on the socket
+pub async fn is_running() -> bool {
+    matches!(
+        request(&DaemonRequest::Status {
+            log_since: u64::MAX
+        })
+        .await,
+        Ok(DaemonResponse::Status(_))
+    )
+}
+
+/// Listen on the daemon socket, readable by the current user only. A socket
+/// file left by a killed daemon is replaced; the caller holds the instance
+/// lock, so no live daemon owns it.
+#[cfg(unix)]
+pub fn bind() -> io::Result<tokio::net::UnixListener> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let path = get_socket_path();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let _ = std::fs::remove_file(&path);
+    let listener = tokio::net::UnixListener::bind(&path)?;
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600))?;
+    Ok(listener)
+}
+
+/// Answer one client connection with `handle`
+#[cfg(unix)]
+pub async fn serve_connection<F, Fut>(mut stream: tokio::net::UnixStream, handle: F)
+where
+    F: FnOnce(DaemonRequest) -> Fut,
+    Fut: std::future::Future<Output = DaemonResponse>,
+{
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let mut line = String::new();
+    let read = tokio::time::timeout(
+        std::time::Duration::from_secs(5),
+        stream.read_to_string(&mut line),
+    )
+    .await;
+    let response = match read {
+        Ok(Ok(_)) => match serde_json::from_str(line.trim()) {
+            Ok(request) => handle(request).await,
+            Err(e) => DaemonResponse::Error {
+                message: format!("invalid request: {}", e),
+            },
+        },
+        _ => return,
+    };
+    if let Ok(json) = serde_json::to_string(&response) {
+        let _ = stream.write_all(json.as_bytes()).await;
+    }
+}
+
+/// A download message as it is sent to (or saved by) the daemon
+pub fn queued_download(message: &DownloadMessage) -> QueuedDownload {
+    let (expected_sha256, expected_git_blob) = ExpectedHash::fields(message.3.as_ref());
+    QueuedDownload {
+        model_id: message.0.clone(),
+        filename: message.1.clone(),
+        base_path: message.2.to_string_lossy().into_owned(),
+        expected_sha256,
+        expected_git_blob,
+        total_size: message.5,
+        revision: message.6.clone(),
+        priority: Default::default(),
+    }
+}
+
+/// The download message for a queued download
+pub fn download_message(queued: QueuedDownload, hf_token: Option<String>) -> DownloadMessage {
+    let expected_hash = queued.expected_hash();
+    (
+        queued.model_id,
+        queued.filename,
+        PathBuf::from(queued.base_path),
+        expected_hash,
+        hf_token,
+        queued.total_size,
+        queued.revision,
+    )
+}
+
+/// Download manager of a daemon client: hands every queued download to the
+/// daemon and takes it off the local queue once the daemon has queued it.
+/// Downloads the daemon does not take stay queued. When it has stopped and
+/// the instance lock is free, this instance takes the lock over and puts
+/// them back in the channel; the function then returns so the caller runs
+/// its own download manager. Otherwise they are offered again every
+/// [`RETRY_INTERVAL`].
+pub async fn forward_downloads(
+    download_rx: Arc<Mutex<mpsc::UnboundedReceiver<DownloadMessage>>>,
+    download_tx: mpsc::UnboundedSender<DownloadMessage>,
+    download_queue: Arc<Mutex<QueueState>>,
+    status_tx: mpsc::UnboundedSender<String>,
+) {
+    let mut unsent: Vec<DownloadMessage> = Vec::new();
+    loop {
+        let mut messages = std::mem::take(&mut unsent);
+        {
+            let mut rx = download_rx.lock().await;
+            if messages.is_empty() {
+                let Some(message) = rx.recv().await else {
+                    break; // Channel closed
+                };
+                messages.push(message);
+            }
+            while let Ok(message) = rx.try_recv() {
+                messages.push(message);
+            }
+        }
+
+        // One request per run of downloads sharing a token
+        let mut failure = None;
+        let mut start = 0;
+        while start < messages.len() {
+            let hf_token = messages[start].4.clone();
+            let end = messages[start..]
+                .iter()
+                .position(|message| message.4 != hf_token)
+                .map_or(messages.len(), |offset| start + offset);
+            let run = &messages[start..end];
+            let downloads = run.iter().map(queued_download).collect();
+            match request(&DaemonRequest::Enqueue {
+                downloads,
+                hf_token,
+            })
+            .await
+            {
+                Ok(DaemonResponse::Queued { count }) => {
+                    let bytes = run.iter().map(|message| message.5).sum();
+                    download_queue.lock().await.remove(run.len(), bytes);
+                    let _ = status_tx.send(format!("Queued {} download(s) in the daemon", count));
+                }
+                Ok(response) => {
+                    failure = Some(format!(
+                        "the daemon refused them: {}",
+                        response.unexpected()
+                    ));
+                    unsent.extend_from_slice(run);
+                }
+                Err(e) => {
+                    failure = Some(format!("could not reach the daemon: {}", e));
+                    unsent.extend_from_slice(run);
+                }
+            }
+            start = end;
+        }
+
+        let Some(failure) = failure else {
+            continue;
+        };
+        if take_over_from_daemon() {
+            let _ = status_tx.send(format!(
+                "The download daemon has stopped; downloading {} file(s) here instead",
+                unsent.len()
+            ));
+            for message in unsent {
+                let _ = download_tx.send(message);
+            }
+            return;
+        }
+        let _ = status_tx.send(format!(
+            "{} download(s) still queued, {}; retrying",
+            unsent.len(),
+            failure
+        ));
+        tokio::time::sleep(RETRY_INTERVAL).await;
+    }
+}
+
+/// Become a regular instance once the daemon has stopped: claim the
+/// instance lock it released (held until the process exits) and drop the
+/// registry copy read while the daemon owned it
+fn take_over_from_daemon() -> bool {
+    match crate::instance::acquire() {
+        Ok(lock) => {
+            std::mem::forget(lock);
+            crate::instance::set_daemon_client(false);
+            crate::registry::forget_cached_registry();
+            true
+        }
+        Err(_) => false,
+    }
+}
+
+/// Keep a client's progress state in step with the daemon's, and pass its
+/// new log lines on to `status_tx`
+pub async fn mirror_status(
+    download_progress: Arc<Mutex<Option<DownloadProgress>>>,
+    download_queue: Arc<Mutex<QueueState>>,
+    verification_progress: Arc<Mutex<Vec<VerificationProgress>>>,
+    verification_queue_size: Arc<AtomicUsize>,
+    status_tx: mpsc::UnboundedSender<String>,
+) {
+    let mut interval = tokio::time::interval(STATUS_INTERVAL);
+    let mut log_since = u64::MAX;
+    let mut connected = true;
+
+    // Until forward_downloads has taken over from a stopped daemon
+    while crate::instance::is_daemon_client() {
+        interval.tick().await;
+        match request(&DaemonRequest::Status { log_since }).await {
+            Ok(DaemonResponse::Status(status)) => {
+                connected = true;
+                for line in status.log {
+                    let _ = status_tx.send(line);
+                }
+                log_since = status.log_next;
+                *download_queue.lock().await = QueueState::new(status.queued, status.queued_bytes);
+                *download_progress.lock().await = status.progress;
+                *verification_progress.lock().await =
+                    status.verifying.into_iter().map(Into::into).collect();
+                verification_queue_size.store(status.verification_backlog, Ordering::Relaxed);
+            }
+            _ if connected => {
+                connected = false;
+                let _ = status_tx.send("Lost connection to the download daemon".to_string());
+                *download_progress.lock().await = None;
+            }
+            _ => {}
+        }
+    }
+}
@@ -1,13 +1,14 @@
 use crate::models::{
     ChunkProgress, CompleteDownloads, DownloadMetadata, DownloadProgress, DownloadStatus,
-    VerificationQueueItem,
+    ExpectedHash, IdenticalFiles, QuantizationInfo, VerificationQueueItem,
 };
 use crate::rate_limiter::RateLimiter;
 use crate::registry;
 use once_cell::sync::Lazy;
+use std::collections::VecDeque;
 use std::io::SeekFrom;
-use std::path::PathBuf;
-use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicUsize, Ordering};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicU8, AtomicUsize, Ordering};
 use std::sync::Arc;
 use tokio::io::{AsyncSeekExt, AsyncWriteExt};
 use tokio::sync::{mpsc, Mutex, Semaphore};
@@ -20,10 +21,38 @@ pub struct DownloadParams {
     pub progress: Arc<Mutex<Option<DownloadProgress>>>,
     pub status_tx: mpsc::UnboundedSender<String>,
     pub complete_downloads: Arc<Mutex<CompleteDownloads>>,
-    pub expected_sha256: Option<String>,
+    pub expected_hash: Option<ExpectedHash>,
     pub verification_queue: Arc<Mutex<Vec<VerificationQueueItem>>>,
     pub verification_queue_size: Arc<AtomicUsize>,
     pub hf_token: Option<String>,
+    /// Branch to download from; `None` for main
+    pub revision: Option<String>,
+    /// Size from the file listing, 0 when unknown
+    pub total_size: u64,
+}
+
+/// Revision as a single URL path segment (branch names may contain `/`)
+pub fn encode_revision(revision: &str) -> String {
+    revision.replace('/', "%2F")
+}
+
+/// Download URL of `filename` on `revision` (main when `None`)
+pub fn file_url(model_id: &str, revision: Option<&str>, filename: &str) -> String {
+    format!(
+        "https://huggingface.co/{}/resolve/{}/{}",
+        model_id,
+        encode_revision(revision.unwrap_or("main")),
+        filename
+    )
+}
+
+/// Branch a download URL built by [`file_url`] points at; `None` for main
+pub fn url_revision(url: &str) -> Option<String> {
+    let (_, rest) = url
+        .split_once("/resolve/")
+        .or_else(|| url.split_once("/raw/"))?;
+    let revision = rest.split('/').next()?.replace("%2F", "/");
+    (revision != "main").then_some(revision)
 }
 
 /// Parameters for chunked download
@@ -35,8 +64,10 @@ struct ChunkedDownloadParams<'a> {
     status_tx: &'a mpsc::UnboundedSender<String>,
     complete_downloads: &'a Arc<Mutex<CompleteDownloads>>,
     filename: &'a str,
-    expected_sha256: &'a Option<String>,
+    expected_hash: &'a Option<ExpectedHash>,
     hf_token: &'a Option<String>,
+    /// Size from the file listing, 0 when unknown
+    listed_size: u64,
 }
 
 pub fn sanitize_path_component(component: &str) -> Option<String> {
@@ -62,6 +93,28 @@ pub fn sanitize_path_component(component: &str) -> Option<String> {
     Some(trimmed.to_string())
 }
 
+/// Subfolder (under the model directory) a quantization file is stored in
+///
+/// Files in a remote subfolder (e.g. "Q4_K_M/model-00001-of-00002.gguf") keep
+/// that layout either way. Files at the repository root are stored flat unless
+/// `per_quant_subdir` is set, in which case they go into `<quant_type>/`.
+pub fn quant_subdir(quant_type: &str, filename: &str, per_quant_subdir: bool) -> Option<String> {
+    if !per_quant_subdir || filename.contains('/') {
+        return None;
+    }
+    sanitize_path_component(quant_type)
+}
+
+/// Subfolder a file from the quantization views is stored in: files published
+/// on a branch go into `<branch>/` (branches usually reuse the same file
+/// names), all others follow [`quant_subdir`]
+pub fn quant_file_subdir(file: &QuantizationInfo, per_quant_subdir: bool) -> Option<String> {
+    match &file.revision {
+        Some(revision) => sanitize_path_component(&revision.replace('/', "_")),
+        None => quant_subdir(&file.quant_type, &file.filename, per_quant_subdir),
+    }
+}
+
 pub fn validate_and_sanitize_path(
     base_path: &str,
     model_id: &str,
@@ -139,23 +192,35 @@ pub fn validate_and_sanitize_path(
     Ok(final_path)
 }
 
-pub async fn start_download(params: DownloadParams) {
-    let DownloadParams {
-        model_id,
-        filename,
-        base_path,
-        progress,
-        status_tx,
-        complete_downloads,
-        expected_sha256,
-        verification_queue,
-        verification_queue_size,
-        hf_token,
-    } = params;
+/// Where a download is written: its registry URL, final path and the
+/// `.incomplete` path it is streamed into
+struct DownloadPaths {
+    url: String,
+    final_path: PathBuf,
+    incomplete_path: PathBuf,
+    /// Download root the model directory is in, for its blob store
+    root: PathBuf,
+}
 
-    // Notify user that download is starting
-    let _ = status_tx.send(format!("Starting download: {}", filename));
+/// The download root a model directory is in: `model_dir` without the
+/// `author/model` it ends with
+fn download_root(model_dir: &Path, model_id: &str) -> PathBuf {
+    let depth = model_id.split('/').count();
+    match model_dir.ancestors().nth(depth) {
+        Some(root) if model_dir.ends_with(model_id) => root.to_path_buf(),
+        _ => model_dir.to_path_buf(),
+    }
+}
 
+/// Validate the filename, create the directories and remove a leftover
+/// incomplete file; errors are reported on `status_tx`
+async fn prepare_download_paths(
+    model_id: &str,
+    filename: &str,
+    base_path: &PathBuf,
+    revision: Option<&str>,
+    status_tx: &mpsc::UnboundedSender<String>,
+) -> Option<DownloadPaths> {
     // Validate filename to prevent path traversal
     let sanitized_filename = {
         let parts: Vec<&str> = filename.split('/').collect();
@@ -165,22 +230,19 @@ pub async fn start_download(params: DownloadParams) {
                 Some(p) => sanitized_parts.push(p),
                 None => {
                     let _ = status_tx.send(format!("Error: Invalid filename component: {}", part));
-                    return;
+                    return None;
                 }
             }
         }
         sanitized_parts.join("/")
     };
 
-    let url = format!(
-        "https://huggingface.co/{}/resolve/main/{}",
-        model_id, sanitized_filename
-    );
+    let url = file_url(model_id, revision, &sanitized_filename);
 
     // Create directory if it doesn't exist
-    if let Err(e) = tokio::fs::create_dir_all(&base_path).await {
+    if let Err(e) = tokio::fs::create_dir_all(base_path).await {
         let _ = status_tx.send(format!("Error: Failed to create directory: {}", e));
-        return;
+        return None;
     }
 
     // Canonicalize base path for safety checks
@@ -188,7 +250,7 @@ pub async fn start_download(params: DownloadParams) {
         Ok(path) => path,
         Err(e) => {
             let _ = status_tx.send(format!("Error: Cannot canonicalize base path: {}", e));
-            return;
+            return None;
         }
     };
 
@@ -201,7 +263,7 @@ pub async fn start_download(params: DownloadParams) {
         if let Ok(canonical_final_parent) = parent.canonicalize() {
             if !canonical_final_parent.starts_with(&canonical_base) {
                 let _ = status_tx.send("Error: Path traversal detected".to_string());
-                return;
+                return None;
             }
         }
     }
@@ -216,7 +278,7 @@ pub async fn start_download(params: DownloadParams) {
     if let Some(parent) = final_path.parent() {
         if let Err(e) = tokio::fs::create_dir_all(parent).await {
             let _ = status_tx.send(format!("Error: Failed to create parent directory: {}", e));
-            return;
+            return None;
         }
     }
     if let Some(parent) = incomplete_path.parent() {
@@ -225,7 +287,7 @@ pub async fn start_download(params: DownloadParams) {
                 "Error: Failed to create parent directory for incomplete file: {}",
                 e
             ));
-            return;
+            return None;
         }
     }
 
@@ -240,62 +302,283 @@ pub async fn start_download(params: DownloadParams) {
         }
     }
 
-    // Also check for the complete file - if it exists, queue for verification if enabled
-    if final_path.exists() {
-        let _ = status_tx.send(format!(
-            "File {} already exists, skipping download",
-            filename
-        ));
+    Some(DownloadPaths {
+        url,
+        final_path,
+        incomplete_path,
+        root: download_root(&canonical_base, model_id),
+    })
+}
 
-        // Update registry as complete
-        let mut registry = registry::load_registry();
-        if let Some(entry) = registry.downloads.iter_mut().find(|d| d.url == url) {
-            entry.status = DownloadStatus::Complete;
-            let mut complete = complete_downloads.lock().await;
-            complete.insert(filename.clone(), entry.clone());
-        }
-        registry::save_registry(&registry);
+/// A file that is already on disk: mark it complete in the registry and
+/// queue it for verification if enabled and its hash is known
+#[allow(clippy::too_many_arguments)]
+async fn skip_existing_file(
+    model_id: &str,
+    filename: &str,
+    paths: &DownloadPaths,
+    expected_hash: &Option<ExpectedHash>,
+    status_tx: &mpsc::UnboundedSender<String>,
+    complete_downloads: &Arc<Mutex<CompleteDownloads>>,
+    verification_queue: Arc<Mutex<Vec<VerificationQueueItem>>>,
+    verification_queue_size: Arc<AtomicUsize>,
+) {
+    // Update registry as complete
+    let mut registry = registry::load_registry();
+    if let Some(entry) = registry.downloads.iter_mut().find(|d| d.url == paths.url) {
+        entry.status = DownloadStatus::Complete;
+        let mut complete = complete_downloads.lock().await;
+        complete.insert(filename.to_string(), entry.clone());
+    }
+    registry::save_registry(&registry);
 
-        // Queue verification if enabled AND hash is available
-        let verification_enabled = DOWNLOAD_CONFIG.enable_verification.load(Ordering::Relaxed);
-        if verification_enabled {
-            if let Some(expected_hash) = &expected_sha256 {
-                // Get file size for progress tracking
-                let file_size = tokio::fs::metadata(&final_path)
-                    .await
-                    .map(|m| m.len())
-                    .unwrap_or(0);
-
-                let item = VerificationQueueItem {
-                    filename: filename.clone(),
-                    local_path: final_path.to_string_lossy().to_string(),
-                    expected_sha256: expected_hash.clone(),
-                    total_size: file_size,
-                    is_manual: false,
-                };
+    // Queue verification if enabled AND hash is available
+    let verification_enabled = DOWNLOAD_CONFIG.enable_verification.load(Ordering::Relaxed);
+    if verification_enabled {
+        if let Some(expected_hash) = expected_hash {
+            // Get file size for progress tracking
+            let file_size = tokio::fs::metadata(&paths.final_path)
+                .await
+                .map(|m| m.len())
+                .unwrap_or(0);
+
+            let item = VerificationQueueItem {
+                model_id: model_id.to_string(),
+                filename: filename.to_string(),
+                local_path: paths.final_path.to_string_lossy().to_string(),
+                expected_hash: expected_hash.clone(),
+                total_size: file_size,
+                is_manual: false,
+            };
 
-                crate::verification::queue_verification(
-                    verification_queue,
-                    verification_queue_size,
-                    item,
-                )
+            crate::verification::queue_verification(verification_queue, verification_queue_size, item)
                 .await;
 
-                let _ = status_tx.send(format!("Queued {} for verification", filename));
-            } else {
-                let _ = status_tx.send(format!(
-                    "File {} exists but no hash available for verification",
-                    filename
-                ));
-            }
+            let _ = status_tx.send(format!("Queued {} for verification", filename));
+        } else {
+            let _ = status_tx.send(format!(
+                "File {} exists but no hash available for verification",
+                filename
+            ));
+        }
+    }
+}
+
+/// Complete downloads of other files recorded with the same content hash,
+/// still on disk at their recorded size. Hashes only match the same kind of
+/// hash. A `Complete` entry need not have been verified, so callers re-hash
+/// a candidate before reusing it.
+fn find_identical_files(
+    expected_hash: &ExpectedHash,
+    total_size: u64,
+    final_path: &Path,
+) -> Vec<DownloadMetadata> {
+    registry::load_registry()
+        .downloads
+        .into_iter()
+        .filter(|d| {
+            d.status == DownloadStatus::Complete
+                && d.expected_hash().as_ref() == Some(expected_hash)
+                && (total_size == 0 || d.total_size == total_size)
+                && Path::new(&d.local_path) != final_path
+                && std::fs::metadata(&d.local_path)
+                    .is_ok_and(|meta| meta.is_file() && meta.len() == d.total_size)
+        })
+        .collect()
+}
+
+/// First identical file whose content actually hashes to `expected_hash`
+async fn find_verified_identical_file(
+    expected_hash: &ExpectedHash,
+    total_size: u64,
+    final_path: &Path,
+) -> Option<DownloadMetadata> {
+    for candidate in find_identical_files(expected_hash, total_size, final_path) {
+        let path = PathBuf::from(&candidate.local_path);
+        let expected = expected_hash.clone();
+        let matches = tokio::task::spawn_blocking(move || {
+            crate::verification::file_matches_hash(&path, &expected)
+        })
+        .await;
+        if matches!(matches, Ok(Ok(true))) {
+            return Some(candidate);
         }
+    }
+    None
+}
+
+/// With the blob store on, link a file whose content is already stored
+/// instead of fetching it; returns the status message when it was linked
+fn link_stored_blob(
+    filename: &str,
+    expected_hash: Option<&ExpectedHash>,
+    total_size: u64,
+    paths: &DownloadPaths,
+) -> Option<String> {
+    if !crate::blobstore::enabled() {
+        return None;
+    }
+    match crate::blobstore::link_stored(&paths.root, expected_hash?, total_size, &paths.final_path)
+    {
+        Ok(true) => Some(format!(
+            "Linked {} to its blob in the store, skipping download",
+            filename
+        )),
+        _ => None,
+    }
+}
+
+/// With the blob store on, move a finished download into the store of
+/// its download root and leave a link in its place
+fn store_blob(
+    filename: &str,
+    root: &Path,
+    final_path: &Path,
+    expected_hash: Option<&ExpectedHash>,
+    status_tx: &mpsc::UnboundedSender<String>,
+) {
+    let Some(hash) = expected_hash.filter(|_| crate::blobstore::enabled()) else {
+        return;
+    };
+    if let Err(e) = crate::blobstore::store(root, final_path, hash) {
+        let _ = status_tx.send(format!(
+            "Warning: could not move {} to the blob store: {}",
+            filename, e
+        ));
+    }
+}
+
+/// Hardlink or copy a file the registry already holds with the same content
+/// hash (e.g. from a mirror of the same repository) to the download's final
+/// path instead of fetching it, once its content is re-hashed and matches,
+/// as the `identical_files` option says; a hardlink falls back to a copy
+/// across filesystems. Returns the status message when the file was put in
+/// place.
+async fn reuse_identical_file(
+    filename: &str,
+    expected_hash: Option<&ExpectedHash>,
+    total_size: u64,
+    paths: &DownloadPaths,
+    status_tx: &mpsc::UnboundedSender<String>,
+) -> Option<String> {
+    let mode = DOWNLOAD_CONFIG.identical_files();
+    if mode == IdenticalFiles::Download {
+        return None;
+    }
+    let source =
+        find_verified_identical_file(expected_hash?, total_size, &paths.final_path).await?;
+
+    // Built at the incomplete path and renamed, like a download
+    let linked = mode == IdenticalFiles::Link
+        && tokio::fs::hard_link(&source.local_path, &paths.incomplete_path)
+            .await
+            .is_ok();
+    let placed = if linked {
+        Ok(())
+    } else {
+        tokio::fs::copy(&source.local_path, &paths.incomplete_path)
+            .await
+            .map(|_| ())
+    };
+    let placed = match placed {
+        Ok(()) => tokio::fs::rename(&paths.incomplete_path, &paths.final_path).await,
+        Err(e) => Err(e),
+    };
+    if let Err(e) = placed {
+        let _ = tokio::fs::remove_file(&paths.incomplete_path).await;
+        let _ = status_tx.send(format!(
+            "Warning: could not reuse identical {} ({}), downloading it",
+            source.local_path, e
+        ));
+        return None;
+    }
+
+    Some(format!(
+        "{} {} from {}/{} (identical content), skipping download",
+        if linked { "Linked" } else { "Copied" },
+        filename,
+        source.model_id,
+        source.filename
+    ))
+}
+
+pub async fn start_download(params: DownloadParams) {
+    let DownloadParams {
+        model_id,
+        filename,
+        base_path,
+        progress,
+        status_tx,
+        complete_downloads,
+        expected_hash,
+        verification_queue,
+        verification_queue_size,
+        hf_token,
+        revision,
+        total_size,
+    } = params;
+
+    // Notify user that download is starting
+    let _ = status_tx.send(format!("Starting download: {}", filename));
+
+    let Some(paths) =
+        prepare_download_paths(&model_id, &filename, &base_path, revision.as_deref(), &status_tx)
+            .await
+    else {
+        return;
+    };
+
+    // Also check for the complete file - if it exists, queue for verification
+    // if enabled. A file the registry already holds with the same content is
+    // put in place first, unless the `identical_files` option is off.
+    let existing = if paths.final_path.exists() {
+        Some(format!(
+            "File {} already exists, skipping download",
+            filename
+        ))
+    } else if let Some(message) =
+        link_stored_blob(&filename, expected_hash.as_ref(), total_size, &paths)
+    {
+        Some(message)
+    } else {
+        reuse_identical_file(
+            &filename,
+            expected_hash.as_ref(),
+            total_size,
+            &paths,
+            &status_tx,
+        )
+        .await
+    };
+    if let Some(message) = existing {
+        let _ = status_tx.send(message);
+        skip_existing_file(
+            &model_id,
+            &filename,
+            &paths,
+            &expected_hash,
+            &status_tx,
+            &complete_downloads,
+            verification_queue,
+            verification_queue_size,
+        )
+        .await;
 
         let mut prog = progress.lock().await;
         *prog = None;
         return;
     }
 
+    let DownloadPaths {
+        url,
+        final_path,
+        incomplete_path,
+        root,
+    } = paths;
+
     let mut retries = DOWNLOAD_CONFIG.max_retries.load(Ordering::Relaxed);
+    let started = std::time::Instant::now();
+    let commit = repo_commit(&model_id, revision.as_deref(), &url, hf_token.as_ref()).await;
 
     loop {
         let chunked_params = ChunkedDownloadParams {
@@ -306,14 +589,34 @@ pub async fn start_download(params: DownloadParams) {
             status_tx: &status_tx,
             complete_downloads: &complete_downloads,
             filename: &filename,
-            expected_sha256: &expected_sha256,
+            expected_hash: &expected_hash,
             hf_token: &hf_token,
+            listed_size: total_size,
         };
 
-        match download_chunked(chunked_params, &model_id).await {
+        // Small files (config, tokenizer, ...) take one plain GET: no size
+        // probe and no range requests
+        let single_request_max = DOWNLOAD_CONFIG
+            .single_request_max_size
+            .load(Ordering::Relaxed);
+        let attempt = if total_size > 0 && total_size <= single_request_max {
+            download_single(chunked_params, &model_id).await
+        } else {
+            download_chunked(chunked_params, &model_id).await
+        };
+
+        match attempt {
             Ok((final_size, expected_size, verification_item, successful_url)) => {
                 // Verify the download is complete
                 if final_size == expected_size && expected_size > 0 {
+                    store_blob(
+                        &filename,
+                        &root,
+                        &final_path,
+                        expected_hash.as_ref(),
+                        &status_tx,
+                    );
+
                     // Update registry: mark as complete and update URL if it changed (raw fallback)
                     let mut registry = registry::load_registry();
                     if let Some(entry) = registry
@@ -324,6 +627,7 @@ pub async fn start_download(params: DownloadParams) {
                         entry.status = DownloadStatus::Complete;
                         entry.downloaded_size = final_size;
                         entry.url = successful_url.clone(); // Update with successful URL
+                        entry.commit = commit.clone();
 
                         // Update in-memory complete downloads map
                         let mut complete = complete_downloads.lock().await;
@@ -331,6 +635,23 @@ pub async fn start_download(params: DownloadParams) {
                     }
                     registry::save_registry(&registry);
 
+                    crate::metrics::COMPLETED_FILES.fetch_add(1, Ordering::Relaxed);
+                    crate::webhook::download_complete(
+                        &model_id,
+                        &filename,
+                        &final_path.to_string_lossy(),
+                        final_size,
+                        &status_tx,
+                    );
+                    // Log for `stats` (duration includes retries)
+                    crate::history::record(&crate::history::HistoryEntry::new(
+                        &model_id,
+                        &filename,
+                        final_size,
+                        started.elapsed(),
+                        &successful_url,
+                    ));
+
                     // Queue verification if enabled AND hash is available
                     let verification_enabled =
                         DOWNLOAD_CONFIG.enable_verification.load(Ordering::Relaxed);
@@ -351,9 +672,19 @@ pub async fn start_download(params: DownloadParams) {
                                 "Download complete: {} (no hash available)",
                                 filename
                             ));
+                            crate::archive::extract_if_enabled(
+                                &final_path.to_string_lossy(),
+                                &status_tx,
+                            )
+                            .await;
                         }
                     } else {
                         let _ = status_tx.send(format!("Download complete: {}", filename));
+                        crate::archive::extract_if_enabled(
+                            &final_path.to_string_lossy(),
+                            &status_tx,
+                        )
+                        .await;
                     }
                 } else {
                     let _ = status_tx.send(format!(
@@ -365,6 +696,7 @@ pub async fn start_download(params: DownloadParams) {
             }
             Err(e) if retries > 0 && is_transient_error(&e) => {
                 retries -= 1;
+                crate::metrics::record_error(crate::metrics::ErrorKind::of(&*e));
                 let _ = status_tx.send(format!(
                     "Download interrupted: {}. Retrying ({} left)...",
                     e, retries
@@ -379,6 +711,7 @@ pub async fn start_download(params: DownloadParams) {
                 continue;
             }
             Err(e) => {
+                crate::metrics::record_error(crate::metrics::ErrorKind::of(&*e));
                 // Check for 401 Unauthorized errors
                 if let Some(reqwest_err) = e.downcast_ref::<reqwest::Error>() {
                     if reqwest_err.status() == Some(reqwest::StatusCode::UNAUTHORIZED) {
@@ -430,159 +763,986 @@ pub async fn start_download(params: DownloadParams) {
     *prog = None;
 }
 
-#[allow(clippy::borrowed_box)]
-fn is_transient_error(e: &Box<dyn std::error::Error + Send + Sync>) -> bool {
-    // Check if error is a reqwest error and if it's a timeout or connection error
-    if let Some(reqwest_err) = e.downcast_ref::<reqwest::Error>() {
-        return reqwest_err.is_timeout() || reqwest_err.is_connect();
+/// Move what is left at the path of a download changed outside the tool
+/// (`DownloadStatus::Changed`) to the trash before it is downloaded again,
+/// since a file already at the final path is otherwise taken as done
+pub fn discard_changed_file(metadata: &DownloadMetadata) {
+    if metadata.status == DownloadStatus::Changed {
+        let _ = crate::trash::move_to_trash(Path::new(&metadata.local_path), None);
     }
-    false
 }
 
-// Global download configuration (thread-safe, runtime-modifiable)
-pub struct DownloadConfig {
-    pub concurrent_threads: AtomicUsize,
-    pub target_chunks: AtomicUsize,
-    pub min_chunk_size: AtomicU64,
-    pub max_chunk_size: AtomicU64,
-    pub enable_verification: AtomicBool,
-    pub max_retries: AtomicU32,
-    pub download_timeout_secs: AtomicU64,
-    pub retry_delay_secs: AtomicU64,
-    pub progress_update_interval_ms: AtomicU64,
-    pub rate_limit_enabled: AtomicBool,
-    pub rate_limit_bytes_per_sec: AtomicU64,
+/// Whether a queued file is small enough for one plain GET and the
+/// small-file batch
+pub fn is_small_file(total_size: u64) -> bool {
+    total_size > 0
+        && total_size
+            <= DOWNLOAD_CONFIG
+                .single_request_max_size
+                .load(Ordering::Relaxed)
 }
 
-impl DownloadConfig {
-    pub const fn new() -> Self {
-        Self {
-            concurrent_threads: AtomicUsize::new(8),
-            target_chunks: AtomicUsize::new(20),
-            min_chunk_size: AtomicU64::new(5 * 1024 * 1024),
-            max_chunk_size: AtomicU64::new(100 * 1024 * 1024),
-            enable_verification: AtomicBool::new(true),
-            max_retries: AtomicU32::new(5),
-            download_timeout_secs: AtomicU64::new(300),
-            retry_delay_secs: AtomicU64::new(1),
-            progress_update_interval_ms: AtomicU64::new(200),
-            rate_limit_enabled: AtomicBool::new(false),
-            rate_limit_bytes_per_sec: AtomicU64::new(50 * 1024 * 1024), // 50 MB/s
-        }
+/// Wait for the next queued download and take the small files queued right
+/// behind a small one with it (a repository download), so they share one
+/// pass. The download that ends the run is kept in `held`, the manager's own
+/// buffer, and comes next, so nothing in the channel changes order. `None`
+/// once the channel is closed and nothing is held.
+pub async fn next_download_batch<T>(
+    download_rx: &Mutex<mpsc::UnboundedReceiver<T>>,
+    held: &mut VecDeque<T>,
+    size_of: impl Fn(&T) -> u64,
+) -> Option<Vec<T>> {
+    let first = match held.pop_front() {
+        Some(message) => message,
+        None => download_rx.lock().await.recv().await?,
+    };
+    if !is_small_file(size_of(&first)) {
+        return Some(vec![first]);
     }
-}
-
-// Global static configuration
-pub static DOWNLOAD_CONFIG: DownloadConfig = DownloadConfig::new();
-
-// Global rate limiter instance (initialized lazily)
-pub static RATE_LIMITER: Lazy<RateLimiter> = Lazy::new(|| {
-    let rate = DOWNLOAD_CONFIG
-        .rate_limit_bytes_per_sec
-        .load(Ordering::Relaxed);
-    RateLimiter::new(rate, 2.0) // 2 second burst window (fixed)
-});
 
-fn calculate_chunk_size(file_size: u64) -> usize {
-    let target_chunks = DOWNLOAD_CONFIG.target_chunks.load(Ordering::Relaxed) as u64;
-    let min_size = DOWNLOAD_CONFIG.min_chunk_size.load(Ordering::Relaxed);
-    let max_size = DOWNLOAD_CONFIG.max_chunk_size.load(Ordering::Relaxed);
-    let ideal_size = file_size / target_chunks;
-    ideal_size.clamp(min_size, max_size) as usize
+    let mut batch = vec![first];
+    let mut rx = download_rx.lock().await;
+    while let Some(message) = held.pop_front().or_else(|| rx.try_recv().ok()) {
+        if !is_small_file(size_of(&message)) {
+            held.push_front(message);
+            break;
+        }
+        batch.push(message);
+    }
+    Some(batch)
 }
 
-async fn download_chunked(
-    params: ChunkedDownloadParams<'_>,
-    model_id: &str,
-) -> Result<
-    (u64, u64, Option<VerificationQueueItem>, String),
-    Box<dyn std::error::Error + Send + Sync>,
-> {
-    let ChunkedDownloadParams {
-        url,
-        incomplete_path,
-        final_path,
-        progress,
-        status_tx,
-        complete_downloads: _complete_downloads,
-        filename,
-        expected_sha256,
-        hf_token,
-    } = params;
-
-    let local_path_str = final_path.to_string_lossy().to_string();
-    let timeout_secs = DOWNLOAD_CONFIG
-        .download_timeout_secs
-        .load(Ordering::Relaxed);
-    let client = crate::http_client::build_client_with_token(
-        hf_token.as_ref(),
-        Some(std::time::Duration::from_secs(timeout_secs)),
-    )?;
-
-    // Step 1: Get file size using a range request
-    // Try the primary URL first, fallback to raw endpoint on 404
-    let (response, final_url) = match client.get(url).header("Range", "bytes=0-0").send().await {
-        Ok(resp) => match resp.error_for_status() {
-            Ok(r) => (r, url.to_string()),
-            Err(e) if e.status() == Some(reqwest::StatusCode::NOT_FOUND) => {
-                // Try raw endpoint as fallback
-                let raw_url = url.replace("/resolve/main/", "/raw/main/");
-                let _ = status_tx.send(format!("404 error, trying raw endpoint for: {}", filename));
-
-                let raw_response = client
-                    .get(&raw_url)
-                    .header("Range", "bytes=0-0")
-                    .send()
-                    .await?
-                    .error_for_status()?;
-
-                (raw_response, raw_url)
-            }
-            Err(e) => return Err(Box::new(e)),
-        },
-        Err(e) => return Err(Box::new(e)),
-    };
-
-    let total_size = if let Some(content_range) = response.headers().get("content-range") {
-        // Parse "bytes 0-0/TOTAL" to get TOTAL
-        if let Ok(range_str) = content_range.to_str() {
-            if let Some(total_str) = range_str.split('/').nth(1) {
-                total_str.parse::<u64>().unwrap_or(0)
-            } else {
-                return Err("Invalid Content-Range header".into());
-            }
-        } else {
-            return Err("Invalid Content-Range header encoding".into());
-        }
-    } else {
-        // Fallback: try Content-Length
-        response.content_length().unwrap_or(0)
+/// Download a batch of small files one after another with one plain GET
+/// each, under a single "12/40 small files" progress row and with one
+/// registry write at the end
+pub async fn start_small_downloads(batch: Vec<DownloadParams>) {
+    let Some(first) = batch.first() else {
+        return;
     };
-
-    if total_size == 0 {
-        return Err("Could not determine file size".into());
+    let progress = first.progress.clone();
+    let status_tx = first.status_tx.clone();
+    let complete_downloads = first.complete_downloads.clone();
+    let model_id = first.model_id.clone();
+
+    let count = batch.len();
+    let total_bytes: u64 = batch.iter().map(|p| p.total_size).sum();
+    let _ = status_tx.send(format!("Downloading {} small files", count));
+    {
+        let mut prog = progress.lock().await;
+        *prog = Some(DownloadProgress {
+            model_id: model_id.clone(),
+            filename: format!("0/{} small files", count),
+            downloaded: 0,
+            total: total_bytes,
+            speed_mbps: 0.0,
+            avg_speed_mbps: 0.0,
+            chunks: Vec::new(),
+            verifying: false,
+        });
     }
 
-    // Update metadata entry in registry
-    let mut registry = registry::load_registry();
-
-    if let Some(entry) = registry.downloads.iter_mut().find(|d| d.url == url) {
-        entry.total_size = total_size;
-        entry.downloaded_size = 0;
-    } else {
-        registry.downloads.push(DownloadMetadata {
-            model_id: model_id.to_string(),
-            filename: filename.to_string(),
-            url: url.to_string(),
-            local_path: local_path_str.clone(),
+    let started = std::time::Instant::now();
+    let mut done_bytes = 0u64;
+    let mut failed = 0usize;
+    // (registry URL, URL that served it, completed entry)
+    let mut completed: Vec<(String, String, DownloadMetadata)> = Vec::new();
+    let mut verification_items = Vec::new();
+    // Completed files without a hash to check
+    let mut unverified_paths = Vec::new();
+
+    for (idx, params) in batch.into_iter().enumerate() {
+        let DownloadParams {
+            model_id,
+            filename,
+            base_path,
+            expected_hash,
+            verification_queue,
+            verification_queue_size,
+            hf_token,
+            revision,
             total_size,
-            downloaded_size: 0,
-            status: DownloadStatus::Incomplete,
-            expected_sha256: expected_sha256.clone(),
-        });
-    }
+            ..
+        } = params;
+
+        if let Some(paths) =
+            prepare_download_paths(&model_id, &filename, &base_path, revision.as_deref(), &status_tx)
+                .await
+        {
+            // A stored blob or a file the registry already holds with the
+            // same content is put in place instead, as in `start_download`
+            let existing = if paths.final_path.exists() {
+                Some(format!(
+                    "File {} already exists, skipping download",
+                    filename
+                ))
+            } else if let Some(message) =
+                link_stored_blob(&filename, expected_hash.as_ref(), total_size, &paths)
+            {
+                Some(message)
+            } else {
+                reuse_identical_file(
+                    &filename,
+                    expected_hash.as_ref(),
+                    total_size,
+                    &paths,
+                    &status_tx,
+                )
+                .await
+            };
+            if let Some(message) = existing {
+                let _ = status_tx.send(message);
+                skip_existing_file(
+                    &model_id,
+                    &filename,
+                    &paths,
+                    &expected_hash,
+                    &status_tx,
+                    &complete_downloads,
+                    verification_queue,
+                    verification_queue_size,
+                )
+                .await;
+            } else {
+                let file_started = std::time::Instant::now();
+                let commit = repo_commit(
+                    &model_id,
+                    revision.as_deref(),
+                    &paths.url,
+                    hf_token.as_ref(),
+                )
+                .await;
+                let mut retries = DOWNLOAD_CONFIG.max_retries.load(Ordering::Relaxed);
+                let fetched = loop {
+                    match fetch_whole_file(
+                        &paths.url,
+                        hf_token.as_ref(),
+                        &paths.incomplete_path,
+                        &status_tx,
+                        &filename,
+                        &progress,
+                        done_bytes,
+                        started,
+                    )
+                    .await
+                    {
+                        Err(e) if retries > 0 && is_transient_error(&e) => {
+                            retries -= 1;
+                            crate::metrics::record_error(crate::metrics::ErrorKind::of(&*e));
+                            let retry_delay =
+                                DOWNLOAD_CONFIG.retry_delay_secs.load(Ordering::Relaxed);
+                            tokio::time::sleep(tokio::time::Duration::from_secs(retry_delay))
+                                .await;
+                        }
+                        result => break result,
+                    }
+                };
+
+                match fetched {
+                    Ok(fetched) if fetched.downloaded == fetched.expected_size => {
+                        match tokio::fs::rename(&paths.incomplete_path, &paths.final_path).await {
+                            Ok(()) => {
+                                store_blob(
+                                    &filename,
+                                    &paths.root,
+                                    &paths.final_path,
+                                    expected_hash.as_ref(),
+                                    &status_tx,
+                                );
+                                let local_path =
+                                    paths.final_path.to_string_lossy().to_string();
+                                crate::metrics::COMPLETED_FILES.fetch_add(1, Ordering::Relaxed);
+                                crate::webhook::download_complete(
+                                    &model_id,
+                                    &filename,
+                                    &local_path,
+                                    fetched.downloaded,
+                                    &status_tx,
+                                );
+                                crate::history::record(&crate::history::HistoryEntry::new(
+                                    &model_id,
+                                    &filename,
+                                    fetched.downloaded,
+                                    file_started.elapsed(),
+                                    &fetched.final_url,
+                                ));
+                                if let Some(expected_hash) = &expected_hash {
+                                    verification_items.push((
+                                        verification_queue,
+                                        verification_queue_size,
+                                        VerificationQueueItem {
+                                            model_id: model_id.clone(),
+                                            filename: filename.clone(),
+                                            local_path: local_path.clone(),
+                                            expected_hash: expected_hash.clone(),
+                                            total_size: fetched.downloaded,
+                                            is_manual: false,
+                                        },
+                                    ));
+                                } else {
+                                    unverified_paths.push(local_path.clone());
+                                }
+                                let (expected_sha256, expected_git_blob) =
+                                    ExpectedHash::fields(expected_hash.as_ref());
+                                completed.push((
+                                    paths.url.clone(),
+                                    fetched.final_url.clone(),
+                                    DownloadMetadata {
+                                        model_id: model_id.clone(),
+                                        filename: filename.clone(),
+                                        url: fetched.final_url,
+                                        local_path,
+                                        total_size: fetched.downloaded,
+                                        downloaded_size: fetched.downloaded,
+                                        status: DownloadStatus::Complete,
+                                        expected_sha256,
+                                        expected_git_blob,
+                                        commit,
+                                    },
+                                ));
+                            }
+                            Err(e) => {
+                                failed += 1;
+                                let _ = status_tx.send(format!(
+                                    "Error: Failed to finish {}: {}",
+                                    filename, e
+                                ));
+                            }
+                        }
+                    }
+                    Ok(fetched) => {
+                        failed += 1;
+                        let _ = status_tx.send(format!(
+                            "Warning: Download may be incomplete: {} (got {} bytes, expected {})",
+                            filename, fetched.downloaded, fetched.expected_size
+                        ));
+                    }
+                    Err(e) => {
+                        failed += 1;
+                        crate::metrics::record_error(crate::metrics::ErrorKind::of(&*e));
+                        if e.downcast_ref::<reqwest::Error>()
+                            .and_then(|e| e.status())
+                            == Some(reqwest::StatusCode::UNAUTHORIZED)
+                        {
+                            let _ = status_tx.send(format!("AUTH_ERROR:{}", model_id));
+                        } else {
+                            let _ = status_tx.send(format!(
+                                "Error: Download of {} failed: {}",
+                                filename, e
+                            ));
+                        }
+                        if paths.incomplete_path.exists() {
+                            let _ = tokio::fs::remove_file(&paths.incomplete_path).await;
+                        }
+                    }
+                }
+            }
+        } else {
+            failed += 1;
+        }
+
+        done_bytes += total_size;
+        let mut prog = progress.lock().await;
+        if let Some(p) = prog.as_mut() {
+            p.filename = format!("{}/{} small files", idx + 1, count);
+            p.downloaded = done_bytes;
+        }
+    }
+
+    // One registry write for the whole batch
+    if !completed.is_empty() {
+        let mut registry = registry::load_registry();
+        let mut complete = complete_downloads.lock().await;
+        for (url, final_url, metadata) in completed {
+            let filename = metadata.filename.clone();
+            match registry
+                .downloads
+                .iter_mut()
+                .find(|d| d.url == url || d.url == final_url)
+            {
+                Some(entry) => {
+                    entry.status = DownloadStatus::Complete;
+                    entry.total_size = metadata.total_size;
+                    entry.downloaded_size = metadata.downloaded_size;
+                    entry.url = metadata.url;
+                    entry.commit = metadata.commit;
+                    complete.insert(filename, entry.clone());
+                }
+                None => {
+                    complete.insert(filename, metadata.clone());
+                    registry.downloads.push(metadata);
+                }
+            }
+        }
+        registry::save_registry(&registry);
+    }
+
+    let verification_enabled = DOWNLOAD_CONFIG.enable_verification.load(Ordering::Relaxed);
+    if verification_enabled {
+        for (queue, queue_size, item) in verification_items {
+            crate::verification::queue_verification(queue, queue_size, item).await;
+        }
+    } else {
+        unverified_paths.extend(
+            verification_items
+                .into_iter()
+                .map(|(_, _, item)| item.local_path),
+        );
+    }
+    for local_path in unverified_paths {
+        crate::archive::extract_if_enabled(&local_path, &status_tx).await;
+    }
+
+    let _ = status_tx.send(if failed == 0 {
+        format!("Downloaded {} small files", count)
+    } else {
+        format!("Downloaded {} of {} small files", count - failed, count)
+    });
+
+    let mut prog = progress.lock().await;
+    *prog = None;
+}
+
+#[allow(clippy::borrowed_box)]
+fn is_transient_error(e: &Box<dyn std::error::Error + Send + Sync>) -> bool {
+    // Check if error is a reqwest error and if it's a timeout or connection error
+    if let Some(reqwest_err) = e.downcast_ref::<reqwest::Error>() {
+        return reqwest_err.is_timeout() || reqwest_err.is_connect();
+    }
+    false
+}
+
+/// Failed attempts on one CDN host before a chunk re-resolves its URL
+const CHUNK_ATTEMPTS_PER_HOST: u32 = 3;
+
+/// Expiry refreshes in a row before a chunk gives up on a refused URL
+const CHUNK_EXPIRY_REFRESHES: u32 = 2;
+
+/// Signed URLs this close to their expiry are refreshed before a request
+const SIGNED_URL_EXPIRY_MARGIN_SECS: u64 = 30;
+
+/// Why a chunk request failed, when trying again might help
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ChunkFailure {
+    /// Timeout, dropped connection or server error: retry the same host first
+    Transient,
+    /// 403 or 410 from the CDN: the signed URL has expired, re-resolve
+    /// right away
+    Expired,
+}
+
+#[allow(clippy::borrowed_box)]
+fn chunk_failure(e: &Box<dyn std::error::Error + Send + Sync>) -> Option<ChunkFailure> {
+    let reqwest_err = e.downcast_ref::<reqwest::Error>()?;
+    match reqwest_err.status() {
+        Some(reqwest::StatusCode::FORBIDDEN | reqwest::StatusCode::GONE) => {
+            Some(ChunkFailure::Expired)
+        }
+        Some(status) if status.is_server_error() => Some(ChunkFailure::Transient),
+        Some(_) => None,
+        None if reqwest_err.is_timeout()
+            || reqwest_err.is_connect()
+            || reqwest_err.is_request()
+            || reqwest_err.is_body() =>
+        {
+            Some(ChunkFailure::Transient)
+        }
+        None => None,
+    }
+}
+
+/// Follow the redirects of a download URL and return the signed URL they end at
+pub async fn resolve_download_url(
+    url: &str,
+    hf_token: Option<&String>,
+    timeout: std::time::Duration,
+) -> Result<String, reqwest::Error> {
+    let response = crate::http_client::get(url, hf_token)
+        .timeout(timeout)
+        .header("Range", "bytes=0-0")
+        .send()
+        .await?
+        .error_for_status()?;
+    Ok(response.url().to_string())
+}
+
+/// How long a looked-up commit is used for the other files of its
+/// revision; a branch can move on in the meantime
+const REPO_COMMIT_TTL: std::time::Duration = std::time::Duration::from_secs(10 * 60);
+
+/// Commits looked up per (model, revision), with when
+type RepoCommits =
+    std::collections::HashMap<(String, Option<String>), (std::time::Instant, Option<String>)>;
+
+static REPO_COMMITS: Lazy<std::sync::Mutex<RepoCommits>> = Lazy::new(Default::default);
+
+/// Commit the revision of a download URL points at, from the
+/// `X-Repo-Commit` header of the Hub's resolve response (read before its
+/// redirect to the CDN); `None` when it can't be read. The files of a
+/// revision share one lookup, and a revision that is a commit needs none.
+pub async fn repo_commit(
+    model_id: &str,
+    revision: Option<&str>,
+    url: &str,
+    hf_token: Option<&String>,
+) -> Option<String> {
+    if let Some(commit) =
+        revision.filter(|r| r.len() == 40 && r.chars().all(|c| c.is_ascii_hexdigit()))
+    {
+        return Some(commit.to_string());
+    }
+    let key = (model_id.to_string(), revision.map(str::to_string));
+    if let Ok(commits) = REPO_COMMITS.lock() {
+        if let Some((looked_up, commit)) = commits.get(&key) {
+            if looked_up.elapsed() < REPO_COMMIT_TTL {
+                return commit.clone();
+            }
+        }
+    }
+
+    let timeout = DOWNLOAD_CONFIG
+        .download_timeout_secs
+        .load(Ordering::Relaxed);
+    let commit = async {
+        let response = crate::http_client::head_no_redirect(url, hf_token)
+            .timeout(std::time::Duration::from_secs(timeout))
+            .send()
+            .await
+            .ok()?;
+        let commit = response.headers().get("x-repo-commit")?.to_str().ok()?;
+        Some(commit.to_string())
+    }
+    .await;
+    if let Ok(mut commits) = REPO_COMMITS.lock() {
+        commits.insert(key, (std::time::Instant::now(), commit.clone()));
+    }
+    commit
+}
+
+/// Replace `stale` in `chunk_url` with a freshly resolved URL, unless another
+/// chunk already did; returns the URL to use and whether it was resolved here
+async fn refresh_chunk_url(
+    chunk_url: &Mutex<String>,
+    stale: &str,
+    download_url: &str,
+    hf_token: Option<&String>,
+    timeout: std::time::Duration,
+) -> Result<(String, bool), reqwest::Error> {
+    // Held across the request so concurrent chunks resolve only once
+    let mut current = chunk_url.lock().await;
+    if *current != stale {
+        return Ok((current.clone(), false));
+    }
+    let fresh = resolve_download_url(download_url, hf_token, timeout).await?;
+    *current = fresh.clone();
+    Ok((fresh, true))
+}
+
+/// Whether a signed URL's `Expires` parameter (Unix seconds, as on the Hub's
+/// CloudFront CDNs) is within [`SIGNED_URL_EXPIRY_MARGIN_SECS`] of now
+fn signed_url_expires_soon(url: &str) -> bool {
+    let Some(expires) = reqwest::Url::parse(url).ok().and_then(|url| {
+        url.query_pairs()
+            .find(|(key, _)| key == "Expires")
+            .and_then(|(_, value)| value.parse::<u64>().ok())
+    }) else {
+        return false;
+    };
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    expires <= now + SIGNED_URL_EXPIRY_MARGIN_SECS
+}
+
+fn url_host(url: &str) -> Option<String> {
+    reqwest::Url::parse(url)
+        .ok()?
+        .host_str()
+        .map(str::to_string)
+}
+
+// Global download configuration (thread-safe, runtime-modifiable)
+pub struct DownloadConfig {
+    pub concurrent_threads: AtomicUsize,
+    pub target_chunks: AtomicUsize,
+    pub min_chunk_size: AtomicU64,
+    pub max_chunk_size: AtomicU64,
+    pub enable_verification: AtomicBool,
+    pub max_retries: AtomicU32,
+    pub download_timeout_secs: AtomicU64,
+    pub retry_delay_secs: AtomicU64,
+    pub progress_update_interval_ms: AtomicU64,
+    /// Files up to this size (as listed) are fetched with one plain GET
+    pub single_request_max_size: AtomicU64,
+    /// Set by [`DownloadConfig::apply_conservative_networking`]
+    pub conservative_networking: AtomicBool,
+    /// Tune the connections of each chunked download between 1 and
+    /// [`ADAPTIVE_MAX_THREADS`] by the throughput they bring, starting from
+    /// `concurrent_threads` (see [`tune_chunk_slots`])
+    pub adaptive_threads: AtomicBool,
+    /// Most download connections open at once across all files, 0 for no
+    /// ceiling (see [`CONNECTION_BUDGET`])
+    pub max_connections: AtomicUsize,
+    /// Unpack downloaded archives (see [`crate::archive`])
+    pub extract_archives: AtomicBool,
+    /// [`IdenticalFiles`] as u8 (see [`reuse_identical_file`])
+    identical_files: AtomicU8,
+    /// Keep downloads in the content-addressable store (see [`crate::blobstore`])
+    pub blob_store: AtomicBool,
+    pub rate_limit_enabled: AtomicBool,
+    pub rate_limit_bytes_per_sec: AtomicU64,
+}
+
+impl DownloadConfig {
+    pub const fn new() -> Self {
+        Self {
+            concurrent_threads: AtomicUsize::new(8),
+            target_chunks: AtomicUsize::new(20),
+            min_chunk_size: AtomicU64::new(5 * 1024 * 1024),
+            max_chunk_size: AtomicU64::new(100 * 1024 * 1024),
+            enable_verification: AtomicBool::new(true),
+            max_retries: AtomicU32::new(5),
+            download_timeout_secs: AtomicU64::new(300),
+            retry_delay_secs: AtomicU64::new(1),
+            progress_update_interval_ms: AtomicU64::new(200),
+            single_request_max_size: AtomicU64::new(10 * 1024 * 1024),
+            conservative_networking: AtomicBool::new(false),
+            adaptive_threads: AtomicBool::new(false),
+            max_connections: AtomicUsize::new(0),
+            extract_archives: AtomicBool::new(false),
+            identical_files: AtomicU8::new(IdenticalFiles::Link as u8),
+            blob_store: AtomicBool::new(false),
+            rate_limit_enabled: AtomicBool::new(false),
+            rate_limit_bytes_per_sec: AtomicU64::new(50 * 1024 * 1024), // 50 MB/s
+        }
+    }
+}
+
+impl DownloadConfig {
+    pub fn identical_files(&self) -> IdenticalFiles {
+        IdenticalFiles::from_u8(self.identical_files.load(Ordering::Relaxed))
+    }
+
+    pub fn set_identical_files(&self, mode: IdenticalFiles) {
+        self.identical_files.store(mode as u8, Ordering::Relaxed);
+    }
+}
+
+/// Chunk size of the conservative networking profile
+const CONSERVATIVE_CHUNK_SIZE: u64 = 4 * 1024 * 1024;
+/// Lower bounds on the request timeout and retry delay of the profile
+const CONSERVATIVE_TIMEOUT_SECS: u64 = 900;
+const CONSERVATIVE_RETRY_DELAY_SECS: u64 = 10;
+
+impl DownloadConfig {
+    /// Switch to the conservative networking profile for Tor and VPN
+    /// tunnels, which drop the default burst of parallel range requests: one
+    /// connection, small chunks, long timeouts and no size probe when the
+    /// listing has the size. Overrides the thread (including adaptive
+    /// threads), chunk, timeout and retry delay settings stored before, so
+    /// call it after syncing those.
+    pub fn apply_conservative_networking(&self) {
+        self.conservative_networking.store(true, Ordering::Relaxed);
+        self.concurrent_threads.store(1, Ordering::Relaxed);
+        self.adaptive_threads.store(false, Ordering::Relaxed);
+        self.min_chunk_size
+            .store(CONSERVATIVE_CHUNK_SIZE, Ordering::Relaxed);
+        self.max_chunk_size
+            .store(CONSERVATIVE_CHUNK_SIZE, Ordering::Relaxed);
+        self.download_timeout_secs
+            .fetch_max(CONSERVATIVE_TIMEOUT_SECS, Ordering::Relaxed);
+        self.retry_delay_secs
+            .fetch_max(CONSERVATIVE_RETRY_DELAY_SECS, Ordering::Relaxed);
+    }
+}
+
+impl Default for DownloadConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Global static configuration
+pub static DOWNLOAD_CONFIG: DownloadConfig = DownloadConfig::new();
+
+static DOWNLOADS_LOCKED: AtomicBool = AtomicBool::new(false);
+
+/// Download lock (`--no-download`, or the `no_download` option in headless
+/// mode): every download needs an explicit confirmation, so browsing on a
+/// shared or metered machine can't start a large download by accident
+pub fn set_downloads_locked(locked: bool) {
+    DOWNLOADS_LOCKED.store(locked, Ordering::Relaxed);
+}
+
+pub fn downloads_locked() -> bool {
+    DOWNLOADS_LOCKED.load(Ordering::Relaxed)
+}
+
+// Global rate limiter instance (initialized lazily)
+pub static RATE_LIMITER: Lazy<RateLimiter> = Lazy::new(|| {
+    let rate = DOWNLOAD_CONFIG
+        .rate_limit_bytes_per_sec
+        .load(Ordering::Relaxed);
+    RateLimiter::new(rate, 2.0) // 2 second burst window (fixed)
+});
+
+/// Connections shared by every active download, so the total stays under
+/// `max_connections` however many files download at once and however many
+/// connections each of them opens
+pub static CONNECTION_BUDGET: Lazy<ConnectionBudget> = Lazy::new(ConnectionBudget::new);
+
+pub struct ConnectionBudget {
+    semaphore: Semaphore,
+    /// Permits handed to the semaphore, held ones included
+    size: std::sync::Mutex<usize>,
+}
+
+impl ConnectionBudget {
+    fn new() -> Self {
+        Self {
+            semaphore: Semaphore::new(0),
+            size: std::sync::Mutex::new(0),
+        }
+    }
+
+    /// Wait for a free connection, held until the permit drops; `None`
+    /// right away when there is no ceiling
+    pub async fn acquire(&self) -> Option<tokio::sync::SemaphorePermit<'_>> {
+        let max = DOWNLOAD_CONFIG.max_connections.load(Ordering::Relaxed);
+        if max == 0 {
+            return None;
+        }
+        self.resize(max);
+        // Never closed
+        Some(self.semaphore.acquire().await.unwrap())
+    }
+
+    /// Follow a changed ceiling; when it drops below the open connections,
+    /// the rest is given up by later calls as they close
+    fn resize(&self, max: usize) {
+        let mut size = self.size.lock().unwrap();
+        if max > *size {
+            self.semaphore.add_permits(max - *size);
+            *size = max;
+        } else if max < *size {
+            *size -= self.semaphore.forget_permits(*size - max);
+        }
+    }
+}
+
+fn calculate_chunk_size(file_size: u64) -> usize {
+    let target_chunks = DOWNLOAD_CONFIG.target_chunks.load(Ordering::Relaxed) as u64;
+    let min_size = DOWNLOAD_CONFIG.min_chunk_size.load(Ordering::Relaxed);
+    let max_size = DOWNLOAD_CONFIG.max_chunk_size.load(Ordering::Relaxed);
+    let ideal_size = file_size / target_chunks;
+    ideal_size.clamp(min_size, max_size) as usize
+}
+
+/// GET a download URL, falling back to the raw endpoint on 404
+/// Returns the response and the URL that served it
+async fn get_with_raw_fallback(
+    url: &str,
+    hf_token: Option<&String>,
+    timeout: std::time::Duration,
+    range: Option<&str>,
+    status_tx: &mpsc::UnboundedSender<String>,
+    filename: &str,
+) -> Result<(reqwest::Response, String), Box<dyn std::error::Error + Send + Sync>> {
+    let request = |url: &str| {
+        let request = crate::http_client::get(url, hf_token).timeout(timeout);
+        match range {
+            Some(range) => request.header("Range", range),
+            None => request,
+        }
+    };
+
+    match request(url).send().await?.error_for_status() {
+        Ok(response) => Ok((response, url.to_string())),
+        Err(e) if e.status() == Some(reqwest::StatusCode::NOT_FOUND) => {
+            // Try raw endpoint as fallback
+            let raw_url = url.replacen("/resolve/", "/raw/", 1);
+            let _ = status_tx.send(format!("404 error, trying raw endpoint for: {}", filename));
+
+            let raw_response = request(&raw_url).send().await?.error_for_status()?;
+            Ok((raw_response, raw_url))
+        }
+        Err(e) => Err(Box::new(e)),
+    }
+}
+
+/// Record the size of a download that is starting in its registry entry
+fn record_download_start(
+    url: &str,
+    model_id: &str,
+    filename: &str,
+    local_path: &str,
+    total_size: u64,
+    expected_hash: &Option<ExpectedHash>,
+) {
+    let mut registry = registry::load_registry();
+
+    if let Some(entry) = registry.downloads.iter_mut().find(|d| d.url == url) {
+        entry.total_size = total_size;
+        entry.downloaded_size = 0;
+    } else {
+        let (expected_sha256, expected_git_blob) = ExpectedHash::fields(expected_hash.as_ref());
+        registry.downloads.push(DownloadMetadata {
+            model_id: model_id.to_string(),
+            filename: filename.to_string(),
+            url: url.to_string(),
+            local_path: local_path.to_string(),
+            total_size,
+            downloaded_size: 0,
+            status: DownloadStatus::Incomplete,
+            expected_sha256,
+            expected_git_blob,
+            commit: None,
+        });
+    }
 
     registry::save_registry(&registry);
+}
+
+/// Download a small file with one plain GET, streamed into the incomplete file
+async fn download_single(
+    params: ChunkedDownloadParams<'_>,
+    model_id: &str,
+) -> Result<
+    (u64, u64, Option<VerificationQueueItem>, String),
+    Box<dyn std::error::Error + Send + Sync>,
+> {
+    let ChunkedDownloadParams {
+        url,
+        incomplete_path,
+        final_path,
+        progress,
+        status_tx,
+        complete_downloads: _complete_downloads,
+        filename,
+        expected_hash,
+        hf_token,
+        listed_size,
+    } = params;
+
+    // Registered before the GET, so an interrupted download shows as
+    // incomplete, as in `download_chunked`
+    let local_path_str = final_path.to_string_lossy().to_string();
+    record_download_start(
+        url,
+        model_id,
+        filename,
+        &local_path_str,
+        listed_size,
+        expected_hash,
+    );
+
+    {
+        let mut prog = progress.lock().await;
+        *prog = Some(DownloadProgress {
+            model_id: model_id.to_string(),
+            filename: filename.to_string(),
+            downloaded: 0,
+            total: 0,
+            speed_mbps: 0.0,
+            avg_speed_mbps: 0.0,
+            chunks: Vec::new(),
+            verifying: false,
+        });
+    }
+
+    let fetched = fetch_whole_file(
+        url,
+        hf_token.as_ref(),
+        incomplete_path,
+        status_tx,
+        filename,
+        progress,
+        0,
+        std::time::Instant::now(),
+    )
+    .await?;
+
+    if fetched.expected_size != listed_size {
+        record_download_start(
+            url,
+            model_id,
+            filename,
+            &local_path_str,
+            fetched.expected_size,
+            expected_hash,
+        );
+    }
+    if fetched.downloaded == fetched.expected_size {
+        tokio::fs::rename(incomplete_path, final_path).await?;
+    }
+
+    let verification_item = expected_hash
+        .as_ref()
+        .map(|expected_hash| VerificationQueueItem {
+            model_id: model_id.to_string(),
+            filename: filename.to_string(),
+            local_path: local_path_str,
+            expected_hash: expected_hash.clone(),
+            total_size: fetched.downloaded,
+            is_manual: false,
+        });
+
+    Ok((
+        fetched.downloaded,
+        fetched.expected_size,
+        verification_item,
+        fetched.final_url,
+    ))
+}
+
+/// Result of [`fetch_whole_file`]
+struct FetchedFile {
+    downloaded: u64,
+    /// Content-Length, or what arrived when the server sent none
+    expected_size: u64,
+    /// URL that served the file (the raw endpoint after a 404)
+    final_url: String,
+}
+
+/// Stream a whole file (one plain GET) into `incomplete_path`, reporting
+/// `progress_base` plus its bytes as the progress row's downloaded total
+#[allow(clippy::too_many_arguments)]
+async fn fetch_whole_file(
+    url: &str,
+    hf_token: Option<&String>,
+    incomplete_path: &PathBuf,
+    status_tx: &mpsc::UnboundedSender<String>,
+    filename: &str,
+    progress: &Arc<Mutex<Option<DownloadProgress>>>,
+    progress_base: u64,
+    started: std::time::Instant,
+) -> Result<FetchedFile, Box<dyn std::error::Error + Send + Sync>> {
+    let timeout = std::time::Duration::from_secs(
+        DOWNLOAD_CONFIG
+            .download_timeout_secs
+            .load(Ordering::Relaxed),
+    );
+
+    let _connection = CONNECTION_BUDGET.acquire().await;
+    let (response, final_url) =
+        get_with_raw_fallback(url, hf_token, timeout, None, status_tx, filename).await?;
+    let content_length = response.content_length().unwrap_or(0);
+    {
+        let mut prog = progress.lock().await;
+        if let Some(p) = prog.as_mut() {
+            p.total = p.total.max(progress_base + content_length);
+        }
+    }
+
+    let mut file = tokio::fs::File::create(incomplete_path).await?;
+
+    use futures::StreamExt;
+    let mut stream = response.bytes_stream();
+    let mut downloaded = 0u64;
+
+    while let Some(item) = stream.next().await {
+        let bytes = item?;
+
+        if DOWNLOAD_CONFIG.rate_limit_enabled.load(Ordering::Relaxed) {
+            RATE_LIMITER.acquire(bytes.len()).await?;
+        }
+
+        file.write_all(&bytes).await?;
+        downloaded += bytes.len() as u64;
+        crate::metrics::DOWNLOADED_BYTES.fetch_add(bytes.len() as u64, Ordering::Relaxed);
+
+        let mut prog = progress.lock().await;
+        if let Some(p) = prog.as_mut() {
+            p.downloaded = progress_base + downloaded;
+            p.record_speed(
+                p.downloaded as f64 / started.elapsed().as_secs_f64().max(0.001) / 1_048_576.0,
+            );
+        }
+    }
+
+    file.flush().await?;
+
+    Ok(FetchedFile {
+        downloaded,
+        expected_size: if content_length > 0 {
+            content_length
+        } else {
+            downloaded
+        },
+        final_url,
+    })
+}
+
+async fn download_chunked(
+    params: ChunkedDownloadParams<'_>,
+    model_id: &str,
+) -> Result<
+    (u64, u64, Option<VerificationQueueItem>, String),
+    Box<dyn std::error::Error + Send + Sync>,
+> {
+    let ChunkedDownloadParams {
+        url,
+        incomplete_path,
+        final_path,
+        progress,
+        status_tx,
+        complete_downloads: _complete_downloads,
+        filename,
+        expected_hash,
+        hf_token,
+        listed_size,
+    } = params;
+
+    let local_path_str = final_path.to_string_lossy().to_string();
+    let timeout_secs = DOWNLOAD_CONFIG
+        .download_timeout_secs
+        .load(Ordering::Relaxed);
+    let timeout = std::time::Duration::from_secs(timeout_secs);
+
+    let (total_size, final_url, chunk_url) = if listed_size > 0
+        && DOWNLOAD_CONFIG.conservative_networking.load(Ordering::Relaxed)
+    {
+        // Conservative networking trusts the listed size: no probe request,
+        // and every chunk follows the redirects itself
+        (listed_size, url.to_string(), url.to_string())
+    } else {
+        // Step 1: Get file size using a range request
+        let (response, final_url) = get_with_raw_fallback(
+            url,
+            hf_token.as_ref(),
+            timeout,
+            Some("bytes=0-0"),
+            status_tx,
+            filename,
+        )
+        .await?;
+
+        let total_size = if let Some(content_range) = response.headers().get("content-range") {
+            // Parse "bytes 0-0/TOTAL" to get TOTAL
+            if let Ok(range_str) = content_range.to_str() {
+                if let Some(total_str) = range_str.split('/').nth(1) {
+                    total_str.parse::<u64>().unwrap_or(0)
+                } else {
+                    return Err("Invalid Content-Range header".into());
+                }
+            } else {
+                return Err("Invalid Content-Range header encoding".into());
+            }
+        } else {
+            // Fallback: try Content-Length
+            response.content_length().unwrap_or(0)
+        };
+        (total_size, final_url, response.url().to_string())
+    };
+
+    if total_size == 0 {
+        return Err("Could not determine file size".into());
+    }
+
+    // Chunks go straight to the signed CDN URL the redirects ended at; a chunk
+    // that keeps failing there resolves `final_url` again for a fresh one
+    let chunk_url = Arc::new(Mutex::new(chunk_url));
+
+    record_download_start(url, model_id, filename, &local_path_str, total_size, expected_hash);
 
     // Calculate dynamic chunk size based on file size
     let chunk_size = calculate_chunk_size(total_size);
@@ -598,6 +1758,7 @@ async fn download_chunked(
             downloaded: 0,
             total: total_size,
             speed_mbps: 0.0,
+            avg_speed_mbps: 0.0,
             chunks: Vec::new(), // Chunks will be added dynamically as they start
             verifying: false,
         });
@@ -616,12 +1777,18 @@ async fn download_chunked(
     drop(file); // Close to allow multiple handles
 
     // Step 3: Download chunks in parallel
-    let max_concurrent = DOWNLOAD_CONFIG.concurrent_threads.load(Ordering::Relaxed);
-    let semaphore = Arc::new(Semaphore::new(max_concurrent));
+    let adaptive = DOWNLOAD_CONFIG.adaptive_threads.load(Ordering::Relaxed);
+    let max_concurrent = match ADAPTIVE_LEVEL.load(Ordering::Relaxed) {
+        level if adaptive && level > 0 => level,
+        _ => DOWNLOAD_CONFIG.concurrent_threads.load(Ordering::Relaxed),
+    };
+    let slots = Arc::new(ChunkSlots::new(max_concurrent));
     let mut handles = Vec::new();
 
     // Shared progress tracking
     let progress_downloaded = Arc::new(Mutex::new(0u64));
+    let tuner = adaptive
+        .then(|| tokio::spawn(tune_chunk_slots(slots.clone(), progress_downloaded.clone())));
     let start_time = std::time::Instant::now();
     let last_update_time = Arc::new(Mutex::new(start_time));
     let last_downloaded_bytes = Arc::new(Mutex::new(0u64));
@@ -629,17 +1796,20 @@ async fn download_chunked(
     for chunk_id in 0..num_chunks {
         let start = chunk_id as u64 * chunk_size as u64;
         let stop = std::cmp::min(start + chunk_size as u64 - 1, total_size - 1);
-        let client = client.clone();
+        let hf_token = hf_token.clone();
         let download_url = final_url.clone();
+        let chunk_url = chunk_url.clone();
+        let status_tx = status_tx.clone();
+        let filename = filename.to_string();
         let incomplete_path = incomplete_path.clone();
-        let semaphore = semaphore.clone();
+        let slots = slots.clone();
         let progress_downloaded = progress_downloaded.clone();
         let progress = progress.clone();
         let last_update_time = last_update_time.clone();
         let last_downloaded_bytes = last_downloaded_bytes.clone();
 
         let handle = tokio::spawn(async move {
-            let _permit = semaphore.acquire().await.unwrap();
+            let permit = slots.acquire().await;
 
             let chunk_total = stop - start + 1;
 
@@ -662,25 +1832,176 @@ async fn download_chunked(
             let chunk_start_time = std::time::Instant::now();
             let mut chunk_last_update = chunk_start_time;
             let mut chunk_last_bytes = 0u64;
+            let mut chunk_downloaded = 0u64;
+            let mut host_failures = 0u32;
+            let mut re_resolves_left = DOWNLOAD_CONFIG.max_retries.load(Ordering::Relaxed);
+            let mut expired_in_a_row = 0u32;
+            let mut check_expiry = true;
+
+            // Download this chunk with progress tracking, moving to a freshly
+            // resolved URL when the signed one expires or the current CDN host
+            // keeps failing
+            let result = loop {
+                let url = chunk_url.lock().await.clone();
+                // The token is for the Hub only, never for the CDN
+                let on_cdn = url_host(&url) != url_host(&download_url);
+                let token = if on_cdn { None } else { hf_token.as_ref() };
+
+                // Chunks can wait in the queue for hours on a big file: swap a
+                // signed URL that is about to expire before using it
+                if on_cdn && check_expiry && signed_url_expires_soon(&url) {
+                    check_expiry = false;
+                    if let Ok((_, true)) = refresh_chunk_url(
+                        &chunk_url,
+                        &url,
+                        &download_url,
+                        hf_token.as_ref(),
+                        timeout,
+                    )
+                    .await
+                    {
+                        let _ = status_tx.send(format!(
+                            "Chunk {} of {}: signed URL about to expire, fetched a fresh one",
+                            chunk_id, filename
+                        ));
+                    }
+                    continue;
+                }
+                check_expiry = true;
+
+                let attempt = download_chunk_with_progress(
+                    &url,
+                    token,
+                    timeout,
+                    &incomplete_path,
+                    start,
+                    stop,
+                    chunk_id,
+                    &mut chunk_downloaded,
+                    &progress,
+                    &mut chunk_last_update,
+                    &mut chunk_last_bytes,
+                    &progress_downloaded,
+                    &last_update_time,
+                    &last_downloaded_bytes,
+                )
+                .await;
 
-            // Download this chunk with progress tracking
-            let result = download_chunk_with_progress(
-                &client,
-                &download_url,
-                &incomplete_path,
-                start,
-                stop,
-                chunk_id,
-                &progress,
-                &mut chunk_last_update,
-                &mut chunk_last_bytes,
-                &progress_downloaded,
-                &last_update_time,
-                &last_downloaded_bytes,
-            )
-            .await;
+                let (e, failure) = match attempt {
+                    Ok(()) => break Ok(()),
+                    Err(e) => match chunk_failure(&e) {
+                        // A 403 from the Hub itself means no access, not expiry
+                        Some(ChunkFailure::Expired) if !on_cdn => break Err(e),
+                        Some(failure) => (e, failure),
+                        None => break Err(e),
+                    },
+                };
+                crate::metrics::record_error(crate::metrics::ErrorKind::of(&*e));
+                let host = url_host(&url).unwrap_or_default();
+                // reqwest's message repeats the (long, signed) URL
+                let e_text = match e.downcast_ref::<reqwest::Error>() {
+                    Some(reqwest_err) => match reqwest_err.status() {
+                        Some(status) => status.to_string(),
+                        None if reqwest_err.is_timeout() => "timed out".to_string(),
+                        None if reqwest_err.is_body() => "connection dropped".to_string(),
+                        None => "connection failed".to_string(),
+                    },
+                    None => e.to_string(),
+                };
+
+                if failure == ChunkFailure::Expired {
+                    // A fresh URL that is refused too was not an expiry
+                    expired_in_a_row += 1;
+                    if expired_in_a_row > CHUNK_EXPIRY_REFRESHES {
+                        break Err(e);
+                    }
+                    match refresh_chunk_url(
+                        &chunk_url,
+                        &url,
+                        &download_url,
+                        hf_token.as_ref(),
+                        timeout,
+                    )
+                    .await
+                    {
+                        Ok((_, true)) => {
+                            let _ = status_tx.send(format!(
+                                "Chunk {} of {}: signed URL expired ({}), resuming at byte {} with a fresh one",
+                                chunk_id,
+                                filename,
+                                e_text,
+                                start + chunk_downloaded
+                            ));
+                        }
+                        Ok((_, false)) => {}
+                        Err(resolve_err) => {
+                            let _ = status_tx.send(format!(
+                                "Chunk {} of {}: signed URL expired ({}), refreshing it failed: {}",
+                                chunk_id,
+                                filename,
+                                e_text,
+                                resolve_err.without_url()
+                            ));
+                            break Err(e);
+                        }
+                    }
+                    continue;
+                }
+                expired_in_a_row = 0;
+
+                host_failures += 1;
+                if host_failures < CHUNK_ATTEMPTS_PER_HOST {
+                    let _ = status_tx.send(format!(
+                        "Chunk {} of {}: {} failed ({}), retrying ({}/{})",
+                        chunk_id, filename, host, e_text, host_failures, CHUNK_ATTEMPTS_PER_HOST
+                    ));
+                    let retry_delay = DOWNLOAD_CONFIG.retry_delay_secs.load(Ordering::Relaxed);
+                    tokio::time::sleep(tokio::time::Duration::from_secs(retry_delay)).await;
+                    continue;
+                }
+                if re_resolves_left == 0 {
+                    break Err(e);
+                }
+                re_resolves_left -= 1;
+                host_failures = 0;
+
+                // One chunk re-resolves, the others pick up its URL
+                match refresh_chunk_url(&chunk_url, &url, &download_url, hf_token.as_ref(), timeout)
+                    .await
+                {
+                    Ok((fresh, resolved)) => {
+                        let action = if resolved {
+                            "re-resolved to"
+                        } else {
+                            "switching to"
+                        };
+                        let _ = status_tx.send(format!(
+                            "Chunk {} of {}: {} keeps failing ({}), {} {}",
+                            chunk_id,
+                            filename,
+                            host,
+                            e_text,
+                            action,
+                            url_host(&fresh).unwrap_or_default()
+                        ));
+                    }
+                    Err(resolve_err) => {
+                        let _ = status_tx.send(format!(
+                            "Chunk {} of {}: {} keeps failing ({}), re-resolving failed: {}",
+                            chunk_id,
+                            filename,
+                            host,
+                            e_text,
+                            resolve_err.without_url()
+                        ));
+                        let retry_delay = DOWNLOAD_CONFIG.retry_delay_secs.load(Ordering::Relaxed);
+                        tokio::time::sleep(tokio::time::Duration::from_secs(retry_delay)).await;
+                    }
+                }
+            };
 
             let chunk_size = stop - start + 1;
+            slots.release(permit);
 
             // Remove this chunk from active list (mark as inactive)
             {
@@ -709,9 +2030,19 @@ async fn download_chunked(
     }
 
     // Wait for all chunks to complete
-    for handle in handles {
-        handle.await??;
+    let chunks_result = async {
+        for handle in handles {
+            handle.await??;
+        }
+        Ok::<_, Box<dyn std::error::Error + Send + Sync>>(())
+    }
+    .await;
+    if let Some(tuner) = tuner {
+        tuner.abort();
+        // The next file starts where this one settled
+        ADAPTIVE_LEVEL.store(slots.limit(), Ordering::Relaxed);
     }
+    chunks_result?;
 
     // Final progress update
     {
@@ -725,12 +2056,13 @@ async fn download_chunked(
     tokio::fs::rename(incomplete_path, final_path).await?;
 
     // Prepare verification data if hash is available
-    let verification_item = expected_sha256
+    let verification_item = expected_hash
         .as_ref()
         .map(|expected_hash| VerificationQueueItem {
+            model_id: model_id.to_string(),
             filename: filename.to_string(),
             local_path: final_path.to_string_lossy().to_string(),
-            expected_sha256: expected_hash.clone(),
+            expected_hash: expected_hash.clone(),
             total_size,
             is_manual: false,
         });
@@ -738,14 +2070,154 @@ async fn download_chunked(
     Ok((total_size, total_size, verification_item, final_url))
 }
 
+/// Most connections per file the adaptive mode ramps up to (the Options maximum)
+pub const ADAPTIVE_MAX_THREADS: usize = 32;
+/// Throughput window each adaptive step is judged on
+const ADAPTIVE_WINDOW: std::time::Duration = std::time::Duration::from_secs(3);
+/// Throughput gain one more connection has to bring to be kept
+const ADAPTIVE_MIN_GAIN: f64 = 0.1;
+/// Throughput loss at an unchanged level that gives up one connection
+const ADAPTIVE_MAX_LOSS: f64 = 0.25;
+/// Windows to stay at a level before probing one more connection again
+const ADAPTIVE_SETTLE_WINDOWS: u32 = 5;
+
+/// Connections the adaptive mode settled on for the last file, 0 before one
+static ADAPTIVE_LEVEL: AtomicUsize = AtomicUsize::new(0);
+
+/// Limit on the chunks of one file downloading at once, which the adaptive
+/// mode raises and lowers while they run
+struct ChunkSlots {
+    semaphore: Semaphore,
+    limit: AtomicUsize,
+    /// Permits to drop as running chunks finish, lowering the limit
+    /// without interrupting a chunk
+    retiring: AtomicUsize,
+    /// Chunks not started yet
+    waiting: AtomicUsize,
+}
+
+impl ChunkSlots {
+    fn new(limit: usize) -> Self {
+        Self {
+            semaphore: Semaphore::new(limit),
+            limit: AtomicUsize::new(limit),
+            retiring: AtomicUsize::new(0),
+            waiting: AtomicUsize::new(0),
+        }
+    }
+
+    fn limit(&self) -> usize {
+        self.limit.load(Ordering::Relaxed)
+    }
+
+    async fn acquire(&self) -> tokio::sync::SemaphorePermit<'_> {
+        self.waiting.fetch_add(1, Ordering::Relaxed);
+        // Never closed
+        let permit = self.semaphore.acquire().await.unwrap();
+        self.waiting.fetch_sub(1, Ordering::Relaxed);
+        permit
+    }
+
+    /// Hand a finished chunk's permit back, or drop it to lower the limit
+    fn release(&self, permit: tokio::sync::SemaphorePermit<'_>) {
+        let take_one = |n: usize| n.checked_sub(1);
+        if self
+            .retiring
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, take_one)
+            .is_ok()
+        {
+            permit.forget();
+        }
+    }
+
+    fn raise(&self) {
+        self.limit.fetch_add(1, Ordering::Relaxed);
+        let take_one = |n: usize| n.checked_sub(1);
+        if self
+            .retiring
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, take_one)
+            .is_err()
+        {
+            self.semaphore.add_permits(1);
+        }
+    }
+
+    fn lower(&self) {
+        self.limit.fetch_sub(1, Ordering::Relaxed);
+        self.retiring.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Adaptive threads: hill-climb the connection count of one file on its
+/// aggregate throughput (`downloaded` bytes). Every [`ADAPTIVE_WINDOW`] one
+/// more connection is probed; it stays (and the next is probed) while it
+/// brings [`ADAPTIVE_MIN_GAIN`] more throughput, otherwise it is dropped
+/// again and the level holds for [`ADAPTIVE_SETTLE_WINDOWS`]. A throughput
+/// drop at a held level gives up a connection, since more connections than
+/// the link or server can serve only compete with each other. Once no chunk
+/// waits for a connection, the file's tail can't tell anything and the
+/// level is left alone. Runs until aborted.
+async fn tune_chunk_slots(slots: Arc<ChunkSlots>, downloaded: Arc<Mutex<u64>>) {
+    let mut interval = tokio::time::interval(ADAPTIVE_WINDOW);
+    interval.tick().await; // The first tick completes immediately
+    let mut last_bytes = 0u64;
+    // Throughput at the held level, bytes per window
+    let mut baseline: Option<f64> = None;
+    let mut probing = false;
+    let mut hold = 0u32;
+
+    loop {
+        interval.tick().await;
+        let bytes = *downloaded.lock().await;
+        let rate = bytes.saturating_sub(last_bytes) as f64;
+        last_bytes = bytes;
+        if slots.waiting.load(Ordering::Relaxed) == 0 {
+            probing = false;
+            baseline = Some(rate);
+            continue;
+        }
+
+        let Some(before) = baseline else {
+            baseline = Some(rate);
+            continue;
+        };
+        if probing {
+            probing = false;
+            if rate >= before * (1.0 + ADAPTIVE_MIN_GAIN) {
+                baseline = Some(rate);
+            } else {
+                slots.lower();
+                hold = ADAPTIVE_SETTLE_WINDOWS;
+                continue;
+            }
+        } else if rate < before * (1.0 - ADAPTIVE_MAX_LOSS) && slots.limit() > 1 {
+            slots.lower();
+            baseline = Some(rate);
+            hold = ADAPTIVE_SETTLE_WINDOWS;
+            continue;
+        } else {
+            baseline = Some(rate);
+        }
+
+        if hold > 0 {
+            hold -= 1;
+        } else if slots.limit() < ADAPTIVE_MAX_THREADS {
+            slots.raise();
+            probing = true;
+        }
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 async fn download_chunk_with_progress(
-    client: &reqwest::Client,
     url: &str,
+    hf_token: Option<&String>,
+    timeout: std::time::Duration,
     file_path: &PathBuf,
     start: u64,
     stop: u64,
     chunk_id: usize,
+    chunk_downloaded: &mut u64,
     progress: &Arc<Mutex<Option<DownloadProgress>>>,
     last_update: &mut std::time::Instant,
     last_bytes: &mut u64,
@@ -753,24 +2225,25 @@ async fn download_chunk_with_progress(
     last_update_time: &Arc<Mutex<std::time::Instant>>,
     last_downloaded_bytes: &Arc<Mutex<u64>>,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let range = format!("bytes={}-{}", start, stop);
+    // Resume after the bytes an earlier attempt already wrote
+    let offset = start + *chunk_downloaded;
+    let range = format!("bytes={}-{}", offset, stop);
 
-    let response = client
-        .get(url)
+    let _connection = CONNECTION_BUDGET.acquire().await;
+    let response = crate::http_client::get(url, hf_token)
+        .timeout(timeout)
         .header("Range", range)
         .send()
         .await?
         .error_for_status()?;
 
-    let mut chunk_downloaded = 0u64;
-
     // Open file for writing at offset
     let mut file = tokio::fs::OpenOptions::new()
         .write(true)
         .open(file_path)
         .await?;
 
-    file.seek(SeekFrom::Start(start)).await?;
+    file.seek(SeekFrom::Start(offset)).await?;
 
     // Stream the response and update progress
     use futures::StreamExt;
@@ -787,7 +2260,8 @@ async fn download_chunk_with_progress(
         file.write_all(&bytes).await?;
 
         let bytes_len = bytes.len() as u64;
-        chunk_downloaded += bytes_len;
+        crate::metrics::DOWNLOADED_BYTES.fetch_add(bytes_len, Ordering::Relaxed);
+        *chunk_downloaded += bytes_len;
 
         // Update total downloaded bytes immediately
         {
@@ -804,7 +2278,7 @@ async fn download_chunk_with_progress(
             / 1000.0;
 
         if elapsed >= interval_secs {
-            let bytes_since_last = chunk_downloaded - *last_bytes;
+            let bytes_since_last = *chunk_downloaded - *last_bytes;
             let chunk_speed_mbps = (bytes_since_last as f64 / elapsed) / 1_048_576.0;
 
             // Calculate total download speed
@@ -832,19 +2306,19 @@ async fn download_chunk_with_progress(
             let mut prog = progress.lock().await;
             if let Some(p) = prog.as_mut() {
                 if let Some(chunk) = p.chunks.iter_mut().find(|c| c.chunk_id == chunk_id) {
-                    chunk.downloaded = chunk_downloaded;
+                    chunk.downloaded = *chunk_downloaded;
                     chunk.speed_mbps = chunk_speed_mbps;
                 }
 
                 // Update total speed and downloaded if calculated
                 if let Some((speed, total)) = total_speed_mbps {
-                    p.speed_mbps = speed;
+                    p.record_speed(speed);
                     p.downloaded = total;
                 }
             }
 
             *last_update = now;
-            *last_bytes = chunk_downloaded;
+            *last_bytes = *chunk_downloaded;
         }
     }
 
@@ -852,3 +2326,109 @@ async fn download_chunk_with_progress(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::DownloadRegistry;
+
+    // sha256 of "hello"
+    const HELLO_SHA256: &str = "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824";
+
+    fn complete(path: &Path, content: &[u8]) -> DownloadMetadata {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+        DownloadMetadata {
+            model_id: "author/model".to_string(),
+            filename: path.file_name().unwrap().to_string_lossy().into_owned(),
+            url: String::new(),
+            local_path: path.to_string_lossy().into_owned(),
+            total_size: content.len() as u64,
+            downloaded_size: content.len() as u64,
+            status: DownloadStatus::Complete,
+            expected_sha256: Some(HELLO_SHA256.to_string()),
+            expected_git_blob: None,
+            commit: None,
+        }
+    }
+
+    #[test]
+    fn test_identical_file_is_rehashed() {
+        // Synchronous so the HOME lock is not held across an await
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        let (_home, home) = registry::scratch_home("identical");
+        let models = home.join("models/author/model");
+        // Recorded with the right hash but damaged on disk, never verified
+        let corrupt = complete(&models.join("corrupt.bin"), b"hellx");
+        let intact = complete(&models.join("intact.bin"), b"hello");
+        // The same digits recorded as another kind of hash
+        let mut other_kind = complete(&models.join("other.bin"), b"hello");
+        other_kind.expected_sha256 = None;
+        other_kind.expected_git_blob = Some(HELLO_SHA256.to_string());
+        registry::save_registry(&DownloadRegistry {
+            downloads: vec![corrupt, intact.clone(), other_kind.clone()],
+            ..Default::default()
+        });
+
+        let target = models.join("new.bin");
+        let hash = ExpectedHash::Sha256(HELLO_SHA256.to_string());
+        assert_eq!(find_identical_files(&hash, 5, &target).len(), 2);
+        let found = runtime.block_on(find_verified_identical_file(&hash, 5, &target));
+        assert_eq!(found.map(|d| d.local_path), Some(intact.local_path.clone()));
+
+        // Only a hash of the same kind matches
+        let git_blob = ExpectedHash::GitBlob(HELLO_SHA256.to_string());
+        let found = find_identical_files(&git_blob, 5, &target);
+        assert_eq!(
+            found.iter().map(|d| &d.local_path).collect::<Vec<_>>(),
+            [&other_kind.local_path]
+        );
+
+        std::fs::write(&intact.local_path, b"hellx").unwrap();
+        let found = runtime.block_on(find_verified_identical_file(&hash, 5, &target));
+        assert!(found.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_next_download_batch() {
+        const LARGE: u64 = 1 << 40;
+        let (tx, rx) = mpsc::unbounded_channel();
+        let rx = Mutex::new(rx);
+        let mut held = VecDeque::new();
+        for message in [
+            ("a", 1),
+            ("b", 1),
+            ("c", LARGE),
+            ("d", 1),
+            ("e", 0),
+            ("f", 1),
+        ] {
+            tx.send(message).unwrap();
+        }
+        let size = |message: &(&str, u64)| message.1;
+        let names = |batch: Option<Vec<(&'static str, u64)>>| {
+            batch.map(|batch| batch.into_iter().map(|m| m.0).collect::<Vec<_>>())
+        };
+
+        // Small files run together; the large file ending the run is held
+        let batch = next_download_batch(&rx, &mut held, size).await;
+        assert_eq!(names(batch), Some(vec!["a", "b"]));
+        assert_eq!(held.len(), 1);
+        // Queued later, so it stays behind everything already queued
+        tx.send(("g", 1)).unwrap();
+        let batch = next_download_batch(&rx, &mut held, size).await;
+        assert_eq!(names(batch), Some(vec!["c"]));
+        // An unknown size is not small
+        let batch = next_download_batch(&rx, &mut held, size).await;
+        assert_eq!(names(batch), Some(vec!["d"]));
+        let batch = next_download_batch(&rx, &mut held, size).await;
+        assert_eq!(names(batch), Some(vec!["e"]));
+        let batch = next_download_batch(&rx, &mut held, size).await;
+        assert_eq!(names(batch), Some(vec!["f", "g"]));
+
+        drop(tx);
+        assert_eq!(names(next_download_batch(&rx, &mut held, size).await), None);
+    }
+}
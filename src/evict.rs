@@ -0,0 +1,196 @@
+//! Least-recently-used eviction (`evict --keep-free`)
+//!
+//! Each downloaded model's last use is the latest of its files' access
+//! times (when the filesystem records them), its last completed download in
+//! the history log and its files' modification times. [`plan`] picks the
+//! least recently used models until deleting them would leave the requested
+//! free space; `evict` shows the plan and deletes it once confirmed. The
+//! files are removed for good rather than moved to the trash, which would
+//! keep the space in use.
+
+use crate::history::HistoryEntry;
+use crate::models::{DownloadRegistry, DownloadStatus};
+use crate::trash::Released;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// What a model's last use is based on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LastUse {
+    /// A file was read (its access time)
+    Accessed,
+    /// The last download of a file
+    Downloaded,
+}
+
+impl LastUse {
+    pub fn label(&self) -> &'static str {
+        match self {
+            LastUse::Accessed => "accessed",
+            LastUse::Downloaded => "downloaded",
+        }
+    }
+}
+
+/// A downloaded model and when it was last used
+#[derive(Debug, Clone, Serialize)]
+pub struct ModelUsage {
+    pub model_id: String,
+    pub files: usize,
+    /// Space deleting the model releases
+    pub bytes: u64,
+    /// Unix time of the last use
+    pub last_used: u64,
+    pub last_use: LastUse,
+}
+
+impl ModelUsage {
+    /// Whole days since the last use
+    pub fn idle_days(&self) -> u64 {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        now.saturating_sub(self.last_used) / (24 * 60 * 60)
+    }
+}
+
+fn unix_secs(time: std::io::Result<SystemTime>) -> u64 {
+    time.ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |d| d.as_secs())
+}
+
+/// Models with complete downloads under `directory`, least recently used
+/// first
+pub fn model_usage(
+    registry: &DownloadRegistry,
+    history: &[HistoryEntry],
+    directory: &Path,
+) -> Vec<ModelUsage> {
+    let mut downloaded: HashMap<&str, u64> = HashMap::new();
+    for entry in history {
+        let last = downloaded.entry(&entry.model_id).or_insert(0);
+        *last = (*last).max(entry.completed_at);
+    }
+
+    let unreleased = Released::new(registry);
+    let mut released: HashMap<&str, Released> = HashMap::new();
+    let mut models: HashMap<&str, ModelUsage> = HashMap::new();
+    for download in &registry.downloads {
+        let path = Path::new(&download.local_path);
+        if download.status != DownloadStatus::Complete || !path.starts_with(directory) {
+            continue;
+        }
+        // Follows a blob-store link to the blob, for its access time
+        let Ok(meta) = fs::metadata(path) else {
+            continue;
+        };
+        let accessed = unix_secs(meta.accessed());
+        let modified = unix_secs(meta.modified());
+        let last_download = downloaded
+            .get(download.model_id.as_str())
+            .copied()
+            .unwrap_or(0)
+            .max(modified);
+
+        let usage = models
+            .entry(&download.model_id)
+            .or_insert_with(|| ModelUsage {
+                model_id: download.model_id.clone(),
+                files: 0,
+                bytes: 0,
+                last_used: 0,
+                last_use: LastUse::Downloaded,
+            });
+        usage.files += 1;
+        released
+            .entry(&download.model_id)
+            .or_insert_with(|| unreleased.clone())
+            .add(path);
+        // Without atime (noatime mounts) the access time stays at or before
+        // the download, so the download counts
+        let (used, how) = if accessed > last_download {
+            (accessed, LastUse::Accessed)
+        } else {
+            (last_download, LastUse::Downloaded)
+        };
+        if used > usage.last_used {
+            usage.last_used = used;
+            usage.last_use = how;
+        }
+    }
+
+    // Only what deleting the model alone releases: blobs and hard-linked
+    // files it shares with other models stay
+    for (model_id, usage) in models.iter_mut() {
+        usage.bytes = released.get(model_id).map_or(0, |r| r.bytes);
+    }
+    let mut models: Vec<ModelUsage> = models.into_values().collect();
+    models.sort_by_key(|m| m.last_used);
+    models
+}
+
+/// The least recently used models to delete so that at least `keep_free`
+/// bytes are free, given `free` now; every model when even that is not
+/// enough
+pub fn plan(models: Vec<ModelUsage>, free: u64, keep_free: u64) -> Vec<ModelUsage> {
+    let mut freed = 0;
+    models
+        .into_iter()
+        .take_while(|m| {
+            let needed = free + freed < keep_free;
+            freed += m.bytes;
+            needed
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GB: u64 = 1024 * 1024 * 1024;
+
+    fn usage(model_id: &str, gb: u64, last_used: u64) -> ModelUsage {
+        ModelUsage {
+            model_id: model_id.to_string(),
+            files: 1,
+            bytes: gb * GB,
+            last_used,
+            last_use: LastUse::Downloaded,
+        }
+    }
+
+    fn models() -> Vec<ModelUsage> {
+        vec![usage("a", 4, 100), usage("b", 8, 200), usage("c", 2, 300)]
+    }
+
+    fn ids(plan: &[ModelUsage]) -> Vec<&str> {
+        plan.iter().map(|m| m.model_id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_plan_enough_free() {
+        assert!(plan(models(), 10 * GB, 10 * GB).is_empty());
+        assert!(plan(models(), 20 * GB, 10 * GB).is_empty());
+    }
+
+    #[test]
+    fn test_plan_least_recently_used_first() {
+        assert_eq!(ids(&plan(models(), 5 * GB, 8 * GB)), ["a"]);
+        assert_eq!(ids(&plan(models(), 5 * GB, 10 * GB)), ["a", "b"]);
+        assert_eq!(ids(&plan(models(), 0, 12 * GB)), ["a", "b"]);
+        assert_eq!(ids(&plan(models(), 0, 12 * GB + 1)), ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn test_plan_not_enough() {
+        assert_eq!(ids(&plan(models(), 0, 100 * GB)), ["a", "b", "c"]);
+        assert!(plan(Vec::new(), 0, GB).is_empty());
+    }
+}
@@ -0,0 +1,439 @@
+//! GGUF header metadata
+//!
+//! A GGUF file starts with its metadata: a magic number, a version, the tensor
+//! and key/value counts, then the key/value pairs (architecture, tokenizer
+//! vocabulary, chat template, ...). [`parse_metadata`] reads just that part,
+//! so callers can fetch the first few megabytes of a multi-gigabyte file with
+//! a `Range` request and stop once it parses.
+
+use crate::models::{GgufHeaderSummary, PromptFormat};
+use std::collections::BTreeMap;
+
+const MAGIC: &[u8; 4] = b"GGUF";
+
+/// How deeply arrays may nest; llama.cpp reads no arrays of arrays, and
+/// without a limit a crafted header could recurse until the stack overflows
+const MAX_ARRAY_DEPTH: usize = 1;
+
+/// A metadata value
+#[derive(Debug, Clone, PartialEq)]
+pub enum GgufValue {
+    UInt(u64),
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+    String(String),
+    Array(Vec<GgufValue>),
+}
+
+impl GgufValue {
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            GgufValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            GgufValue::UInt(n) => Some(*n),
+            GgufValue::Int(n) => u64::try_from(*n).ok(),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            GgufValue::Float(n) => Some(*n),
+            GgufValue::UInt(n) => Some(*n as f64),
+            GgufValue::Int(n) => Some(*n as f64),
+            _ => None,
+        }
+    }
+}
+
+/// Why the metadata could not be read
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GgufError {
+    /// The data ends before the metadata does; fetch more and retry
+    Truncated,
+    /// Not a GGUF file, or a malformed one
+    Invalid(String),
+}
+
+impl std::fmt::Display for GgufError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GgufError::Truncated => write!(f, "GGUF metadata is incomplete"),
+            GgufError::Invalid(msg) => write!(f, "Invalid GGUF file: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for GgufError {}
+
+/// Header metadata of a GGUF file
+#[derive(Debug, Clone, Default)]
+pub struct GgufMetadata {
+    pub version: u32,
+    pub tensor_count: u64,
+    pub values: BTreeMap<String, GgufValue>,
+}
+
+impl GgufMetadata {
+    pub fn get_str(&self, key: &str) -> Option<&str> {
+        self.values.get(key).and_then(GgufValue::as_str)
+    }
+
+    /// Text of token `id` in the tokenizer vocabulary
+    pub fn token(&self, id: u64) -> Option<&str> {
+        match self.values.get("tokenizer.ggml.tokens")? {
+            GgufValue::Array(tokens) => tokens.get(usize::try_from(id).ok()?)?.as_str(),
+            _ => None,
+        }
+    }
+
+    /// Chat templates and special tokens stored by llama.cpp's converter
+    pub fn prompt_format(&self, source: &str) -> PromptFormat {
+        let mut chat_templates = Vec::new();
+        if let Some(template) = self.get_str("tokenizer.chat_template") {
+            chat_templates.push(("default".to_string(), template.to_string()));
+        }
+        // Additional named templates (tool_use, rag, ...)
+        for (key, value) in &self.values {
+            if let (Some(name), Some(template)) =
+                (key.strip_prefix("tokenizer.chat_template."), value.as_str())
+            {
+                chat_templates.push((name.to_string(), template.to_string()));
+            }
+        }
+
+        let special_tokens = SPECIAL_TOKEN_KEYS
+            .iter()
+            .filter_map(|(role, key)| {
+                let id = self.values.get(*key)?.as_u64()?;
+                let text = self
+                    .token(id)
+                    .map_or_else(|| format!("#{}", id), str::to_string);
+                Some((role.to_string(), text))
+            })
+            .collect();
+
+        PromptFormat {
+            source: source.to_string(),
+            chat_templates,
+            special_tokens,
+        }
+    }
+
+    /// Architecture, context length and RoPE scaling, from the
+    /// `<architecture>.*` keys llama.cpp writes after `general.*`
+    pub fn header_summary(&self) -> GgufHeaderSummary {
+        let architecture = self.get_str("general.architecture");
+        let arch_value =
+            |key: &str| architecture.and_then(|arch| self.values.get(&format!("{}.{}", arch, key)));
+
+        let scaling_type = arch_value("rope.scaling.type").and_then(GgufValue::as_str);
+        let rope_scaling = match scaling_type {
+            None | Some("none") => None,
+            Some(kind) => {
+                let mut parts = vec![kind.to_string()];
+                if let Some(factor) = arch_value("rope.scaling.factor").and_then(GgufValue::as_f64)
+                {
+                    // Stored as f32: round away the float noise (1.1 → 1.100000023...)
+                    parts.push(format!("×{}", (factor * 1000.0).round() / 1000.0));
+                }
+                if let Some(original) =
+                    arch_value("rope.scaling.original_context_length").and_then(GgufValue::as_u64)
+                {
+                    parts.push(format!("(from {})", original));
+                }
+                Some(parts.join(" "))
+            }
+        };
+
+        GgufHeaderSummary {
+            architecture: architecture.map(str::to_string),
+            context_length: arch_value("context_length").and_then(GgufValue::as_u64),
+            rope_scaling,
+        }
+    }
+
+    /// Whether the architecture keys have all been read: the tokenizer keys
+    /// come after them
+    pub fn has_architecture_keys(&self) -> bool {
+        self.values.keys().any(|key| key.starts_with("tokenizer."))
+    }
+}
+
+/// Special token roles and the metadata keys holding their token ids
+const SPECIAL_TOKEN_KEYS: &[(&str, &str)] = &[
+    ("bos", "tokenizer.ggml.bos_token_id"),
+    ("eos", "tokenizer.ggml.eos_token_id"),
+    ("eot", "tokenizer.ggml.eot_token_id"),
+    ("eom", "tokenizer.ggml.eom_token_id"),
+    ("unk", "tokenizer.ggml.unknown_token_id"),
+    ("sep", "tokenizer.ggml.seperator_token_id"),
+    ("pad", "tokenizer.ggml.padding_token_id"),
+    ("mask", "tokenizer.ggml.mask_token_id"),
+];
+
+/// Parse the header metadata at the start of a GGUF file
+///
+/// `data` may be just a prefix of the file; [`GgufError::Truncated`] means
+/// the metadata continues past its end.
+pub fn parse_metadata(data: &[u8]) -> Result<GgufMetadata, GgufError> {
+    match parse_metadata_prefix(data)? {
+        (metadata, true) => Ok(metadata),
+        (_, false) => Err(GgufError::Truncated),
+    }
+}
+
+/// Parse as many key/value pairs as `data` holds, and whether that was all
+/// of them
+///
+/// [`GgufError::Truncated`] means `data` ends inside the fixed header.
+pub fn parse_metadata_prefix(data: &[u8]) -> Result<(GgufMetadata, bool), GgufError> {
+    let mut reader = Reader { data, pos: 0 };
+    if reader.take(4)? != MAGIC {
+        return Err(GgufError::Invalid("missing GGUF magic".to_string()));
+    }
+    let version = reader.u32()?;
+    if !(2..=3).contains(&version) {
+        return Err(GgufError::Invalid(format!(
+            "unsupported version {}",
+            version
+        )));
+    }
+    let tensor_count = reader.u64()?;
+    let kv_count = reader.u64()?;
+
+    let mut values = BTreeMap::new();
+    let mut complete = true;
+    for _ in 0..kv_count {
+        match reader.key_value() {
+            Ok((key, value)) => {
+                values.insert(key, value);
+            }
+            Err(GgufError::Truncated) => {
+                complete = false;
+                break;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+
+    let metadata = GgufMetadata {
+        version,
+        tensor_count,
+        values,
+    };
+    Ok((metadata, complete))
+}
+
+/// Little-endian cursor over a (possibly truncated) GGUF prefix
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], GgufError> {
+        let end = self.pos.checked_add(len).ok_or(GgufError::Truncated)?;
+        let bytes = self.data.get(self.pos..end).ok_or(GgufError::Truncated)?;
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], GgufError> {
+        let mut out = [0; N];
+        out.copy_from_slice(self.take(N)?);
+        Ok(out)
+    }
+
+    fn u32(&mut self) -> Result<u32, GgufError> {
+        Ok(u32::from_le_bytes(self.array()?))
+    }
+
+    fn u64(&mut self) -> Result<u64, GgufError> {
+        Ok(u64::from_le_bytes(self.array()?))
+    }
+
+    fn len(&mut self) -> Result<usize, GgufError> {
+        let len = self.u64()?;
+        usize::try_from(len).map_err(|_| GgufError::Invalid(format!("length {} too large", len)))
+    }
+
+    fn string(&mut self) -> Result<String, GgufError> {
+        let len = self.len()?;
+        Ok(String::from_utf8_lossy(self.take(len)?).into_owned())
+    }
+
+    fn key_value(&mut self) -> Result<(String, GgufValue), GgufError> {
+        let key = self.string()?;
+        let value_type = self.u32()?;
+        Ok((key, self.value(value_type, 0)?))
+    }
+
+    /// Read a value of `value_type`, inside `depth` arrays
+    fn value(&mut self, value_type: u32, depth: usize) -> Result<GgufValue, GgufError> {
+        Ok(match value_type {
+            0 => GgufValue::UInt(u8::from_le_bytes(self.array()?).into()),
+            1 => GgufValue::Int(i8::from_le_bytes(self.array()?).into()),
+            2 => GgufValue::UInt(u16::from_le_bytes(self.array()?).into()),
+            3 => GgufValue::Int(i16::from_le_bytes(self.array()?).into()),
+            4 => GgufValue::UInt(self.u32()?.into()),
+            5 => GgufValue::Int(i32::from_le_bytes(self.array()?).into()),
+            6 => GgufValue::Float(f32::from_le_bytes(self.array()?).into()),
+            7 => GgufValue::Bool(self.take(1)?[0] != 0),
+            8 => GgufValue::String(self.string()?),
+            9 if depth >= MAX_ARRAY_DEPTH => {
+                return Err(GgufError::Invalid("arrays nested too deeply".to_string()));
+            }
+            9 => {
+                let item_type = self.u32()?;
+                let count = self.len()?;
+                // Don't trust the count for the allocation: a truncated prefix
+                // runs out long before a bogus count would
+                let mut items = Vec::with_capacity(count.min(1 << 16));
+                for _ in 0..count {
+                    items.push(self.value(item_type, depth + 1)?);
+                }
+                GgufValue::Array(items)
+            }
+            10 => GgufValue::UInt(self.u64()?),
+            11 => GgufValue::Int(i64::from_le_bytes(self.array()?)),
+            12 => GgufValue::Float(f64::from_le_bytes(self.array()?)),
+            other => return Err(GgufError::Invalid(format!("unknown value type {}", other))),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A GGUF header of `version` with the given encoded key/value pairs
+    fn header(version: u32, kv_count: u64, pairs: &[u8]) -> Vec<u8> {
+        let mut data = MAGIC.to_vec();
+        data.extend_from_slice(&version.to_le_bytes());
+        data.extend_from_slice(&0u64.to_le_bytes());
+        data.extend_from_slice(&kv_count.to_le_bytes());
+        data.extend_from_slice(pairs);
+        data
+    }
+
+    fn string(s: &str) -> Vec<u8> {
+        let mut data = (s.len() as u64).to_le_bytes().to_vec();
+        data.extend_from_slice(s.as_bytes());
+        data
+    }
+
+    fn string_pair(key: &str, value: &str) -> Vec<u8> {
+        let mut data = string(key);
+        data.extend_from_slice(&8u32.to_le_bytes());
+        data.extend_from_slice(&string(value));
+        data
+    }
+
+    /// An array of `depth` nested arrays around a single u8
+    fn nested_array_pair(depth: usize) -> Vec<u8> {
+        let mut data = string("nested");
+        data.extend_from_slice(&9u32.to_le_bytes());
+        for level in 1..=depth {
+            let item_type: u32 = if level == depth { 0 } else { 9 };
+            data.extend_from_slice(&item_type.to_le_bytes());
+            data.extend_from_slice(&1u64.to_le_bytes());
+        }
+        data.push(7);
+        data
+    }
+
+    #[test]
+    fn test_parse_metadata() {
+        let mut pairs = string_pair("general.architecture", "llama");
+        pairs.extend_from_slice(&nested_array_pair(1));
+        let metadata = parse_metadata(&header(3, 2, &pairs)).unwrap();
+        assert_eq!(metadata.version, 3);
+        assert_eq!(metadata.get_str("general.architecture"), Some("llama"));
+        assert_eq!(
+            metadata.values.get("nested"),
+            Some(&GgufValue::Array(vec![GgufValue::UInt(7)]))
+        );
+    }
+
+    #[test]
+    fn test_truncated_input() {
+        let mut pairs = string_pair("general.architecture", "llama");
+        pairs.extend_from_slice(&string_pair("general.name", "test"));
+        let data = header(3, 2, &pairs);
+
+        assert_eq!(
+            parse_metadata(&data[..10]).err(),
+            Some(GgufError::Truncated)
+        );
+        assert_eq!(
+            parse_metadata(&data[..data.len() - 1]).err(),
+            Some(GgufError::Truncated)
+        );
+
+        let (metadata, complete) = parse_metadata_prefix(&data[..data.len() - 1]).unwrap();
+        assert!(!complete);
+        assert_eq!(metadata.get_str("general.architecture"), Some("llama"));
+        assert_eq!(metadata.get_str("general.name"), None);
+    }
+
+    #[test]
+    fn test_bad_magic() {
+        let mut data = header(3, 0, &[]);
+        data[..4].copy_from_slice(b"GGML");
+        assert!(matches!(parse_metadata(&data), Err(GgufError::Invalid(_))));
+    }
+
+    #[test]
+    fn test_bad_version() {
+        for version in [0, 1, 4, u32::MAX] {
+            assert!(matches!(
+                parse_metadata(&header(version, 0, &[])),
+                Err(GgufError::Invalid(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_nested_arrays() {
+        for depth in [2, 100_000] {
+            let data = header(3, 1, &nested_array_pair(depth));
+            assert!(matches!(parse_metadata(&data), Err(GgufError::Invalid(_))));
+        }
+    }
+
+    #[test]
+    fn test_oversized_lengths() {
+        // A string far longer than the data
+        let mut pairs = string("key");
+        pairs.extend_from_slice(&8u32.to_le_bytes());
+        pairs.extend_from_slice(&u64::MAX.to_le_bytes());
+        assert_eq!(
+            parse_metadata(&header(3, 1, &pairs)).err(),
+            Some(GgufError::Truncated)
+        );
+
+        // An array claiming more items than the data holds
+        let mut pairs = string("key");
+        pairs.extend_from_slice(&9u32.to_le_bytes());
+        pairs.extend_from_slice(&0u32.to_le_bytes());
+        pairs.extend_from_slice(&u64::MAX.to_le_bytes());
+        pairs.extend_from_slice(&[1, 2, 3]);
+        assert_eq!(
+            parse_metadata(&header(3, 1, &pairs)).err(),
+            Some(GgufError::Truncated)
+        );
+
+        // More key/value pairs than the data holds
+        let data = header(3, u64::MAX, &string_pair("key", "value"));
+        let (metadata, complete) = parse_metadata_prefix(&data).unwrap();
+        assert!(!complete);
+        assert_eq!(metadata.get_str("key"), Some("value"));
+    }
+}
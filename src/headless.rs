@@ -3,10 +3,24 @@
 //! This module provides functions for running the application without a TUI,
 //! suitable for CI/CD automation and scripting.
 
+use crate::adopt;
 use crate::api;
+use crate::bench;
+use crate::blobstore;
+use crate::cli::{DaemonAction, ProgressMode};
 use crate::config;
+use crate::daemon;
+use crate::evict;
+use crate::history;
+use crate::ignorelist;
 use crate::models::*;
+use crate::placement;
+use crate::quota;
+use crate::records::{Record, RecordFormat, LIST_FIELDS, SEARCH_FIELDS};
 use crate::registry;
+use crate::trash;
+use crate::watchlist;
+use std::collections::{HashMap, VecDeque};
 use std::io::Write;
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicUsize, Ordering};
@@ -45,6 +59,16 @@ impl From<reqwest::Error> for HeadlessError {
     }
 }
 
+impl From<api::ApiError> for HeadlessError {
+    fn from(err: api::ApiError) -> Self {
+        if err.is_auth_error() {
+            HeadlessError::AuthError(err.to_string())
+        } else {
+            HeadlessError::ApiError(err.to_string())
+        }
+    }
+}
+
 impl From<std::io::Error> for HeadlessError {
     fn from(err: std::io::Error) -> Self {
         HeadlessError::IoError(err)
@@ -53,12 +77,13 @@ impl From<std::io::Error> for HeadlessError {
 
 /// Type for download messages sent to the download manager
 pub type DownloadMessage = (
-    String,         // model_id
-    String,         // filename
-    PathBuf,        // output path
-    Option<String>, // sha256
-    Option<String>, // hf_token
-    u64,            // total_size
+    String,               // model_id
+    String,               // filename
+    PathBuf,              // output path
+    Option<ExpectedHash>, // sha256 or git blob id
+    Option<String>,       // hf_token
+    u64,                  // total_size
+    Option<String>,       // revision (None for main)
 );
 
 /// Exit code constants
@@ -67,6 +92,9 @@ pub const EXIT_ERROR: i32 = 1;
 pub const EXIT_AUTH_ERROR: i32 = 2;
 pub const EXIT_INVALID_ARGS: i32 = 3;
 
+/// Characters of the model ID kept in text progress lines
+const PROGRESS_MODEL_CHARS: usize = 40;
+
 impl HeadlessError {
     pub fn exit_code(&self) -> i32 {
         match self {
@@ -127,13 +155,60 @@ pub fn validate_model_id(model_id: &str) -> Result<(), HeadlessError> {
     Ok(())
 }
 
+/// Resolve a model ID or huggingface.co URL given on the command line
+pub fn resolve_model_reference(
+    input: &str,
+    reporter: &ProgressReporter,
+) -> Result<ModelReference, HeadlessError> {
+    let reference = api::parse_model_reference(input).ok_or_else(|| {
+        HeadlessError::DownloadError(format!(
+            "Invalid model reference '{}'. Expected: 'author/model-name' or a huggingface.co model or file URL",
+            input
+        ))
+    })?;
+    validate_model_id(&reference.model_id)?;
+
+    if let Some(revision) = reference.revision.as_deref() {
+        if revision != "main" {
+            reporter.report_info(&format!(
+                "Note: revision '{}' is not supported, using 'main'",
+                revision
+            ));
+        }
+    }
+
+    Ok(reference)
+}
+
+/// Find a single file in the repository listing
+fn find_repo_file<'a>(
+    metadata: &'a ModelMetadata,
+    file_path: &str,
+) -> Result<&'a RepoFile, HeadlessError> {
+    metadata
+        .siblings
+        .iter()
+        .find(|f| f.rfilename == file_path)
+        .ok_or_else(|| {
+            HeadlessError::DownloadError(format!(
+                "File '{}' not found in '{}'",
+                file_path, metadata.model_id
+            ))
+        })
+}
+
 /// Search for models with optional filters
+#[allow(clippy::too_many_arguments)]
 pub async fn search_models(
     query: &str,
     sort_field: Option<SortField>,
     sort_direction: Option<SortDirection>,
     min_downloads: Option<u64>,
     min_likes: Option<u64>,
+    params: ParamRange,
+    access: AccessFilter,
+    content: ContentFilter,
+    limits: SearchLimits,
     token: Option<&String>,
 ) -> Result<Vec<ModelInfo>, HeadlessError> {
     let sort = sort_field.unwrap_or(SortField::Downloads);
@@ -141,28 +216,709 @@ pub async fn search_models(
     let min_dl = min_downloads.unwrap_or(0);
     let min_likes_val = min_likes.unwrap_or(0);
 
-    api::fetch_models_filtered(query, sort, direction, min_dl, min_likes_val, token)
-        .await
-        .map_err(|e| HeadlessError::ApiError(e.to_string()))
+    api::fetch_models_filtered(
+        query,
+        sort,
+        direction,
+        min_dl,
+        min_likes_val,
+        params,
+        access,
+        content,
+        limits,
+        token,
+    )
+    .await
+    .map_err(HeadlessError::from)
 }
 
 /// Run search command with formatted output
+///
+/// JSON output and `--cursor` fetch a single page and report the cursor of
+/// the next one, so scripts can enumerate every result page by page.
+/// `full_text` searches model card text, which has no pages. Otherwise up
+/// to `limits.max_results` models are fetched, `limits.page_size` per request.
+/// `repo_sizes` looks up each result's total repository size.
+#[allow(clippy::too_many_arguments)]
 pub async fn run_search(
     query: &str,
     sort_field: Option<SortField>,
     min_downloads: Option<u64>,
     min_likes: Option<u64>,
+    params: ParamRange,
+    access: AccessFilter,
+    content: ContentFilter,
+    limits: SearchLimits,
+    full_text: bool,
+    repo_sizes: bool,
+    cursor: Option<&str>,
+    format: Option<&RecordFormat>,
     token: Option<&String>,
     reporter: &ProgressReporter,
 ) -> Result<(), HeadlessError> {
     let start = std::time::Instant::now();
 
-    let models = search_models(query, sort_field, None, min_downloads, min_likes, token).await?;
+    let (mut models, next_cursor) = if full_text {
+        let models = api::fetch_models_full_text(
+            query,
+            sort_field.unwrap_or(SortField::Downloads),
+            SortDirection::Descending,
+            min_downloads.unwrap_or(0),
+            min_likes.unwrap_or(0),
+            params,
+            access,
+            content,
+            token,
+            true,
+        )
+        .await?;
+        (models, None)
+    } else if reporter.is_json() || cursor.is_some() {
+        api::search_models_page(
+            query,
+            sort_field.unwrap_or(SortField::Downloads),
+            SortDirection::Descending,
+            min_downloads.unwrap_or(0),
+            min_likes.unwrap_or(0),
+            params,
+            access,
+            content,
+            limits.page_size,
+            cursor,
+            token,
+        )
+        .await?
+    } else {
+        let models = search_models(
+            query,
+            sort_field,
+            None,
+            min_downloads,
+            min_likes,
+            params,
+            access,
+            content,
+            limits,
+            token,
+        )
+        .await?;
+        (models, None)
+    };
+
+    if repo_sizes {
+        api::fill_repo_sizes(&mut models, token).await;
+    }
+
+    if let Some(format) = format {
+        reporter.report_records(format, SEARCH_FIELDS, &search_records(&models));
+        // Keep stdout to the records
+        if let Some(cursor) = next_cursor {
+            eprintln!("More results: --cursor '{}'", cursor);
+        }
+        return Ok(());
+    }
 
     let elapsed = start.elapsed();
 
-    reporter.report_search_with_timing(&models, elapsed);
+    reporter.report_search_with_timing(&models, elapsed, next_cursor.as_deref());
+
+    Ok(())
+}
+
+/// One `search --format` record per model
+fn search_records(models: &[ModelInfo]) -> Vec<Record> {
+    models
+        .iter()
+        .map(|model| {
+            let format = model.quant_format();
+            vec![
+                ("id", model.id.clone()),
+                ("author", model.author.clone().unwrap_or_default()),
+                ("downloads", model.downloads.to_string()),
+                ("likes", model.likes.to_string()),
+                ("modified", model.last_modified.clone().unwrap_or_default()),
+                ("gated", model.is_gated().to_string()),
+                ("private", model.private.to_string()),
+                ("gguf", (format == Some(QuantFormat::Gguf)).to_string()),
+                ("format", format.map(|f| f.label()).unwrap_or_default()),
+                (
+                    "params",
+                    model
+                        .parameter_count()
+                        .map(crate::utils::format_params)
+                        .unwrap_or_default(),
+                ),
+                (
+                    "size_class",
+                    model.size_class().unwrap_or_default().to_string(),
+                ),
+                (
+                    "repo_size",
+                    model
+                        .used_storage
+                        .map(crate::utils::format_size)
+                        .unwrap_or_default(),
+                ),
+                (
+                    "repo_size_bytes",
+                    model
+                        .used_storage
+                        .map(|bytes| bytes.to_string())
+                        .unwrap_or_default(),
+                ),
+            ]
+        })
+        .collect()
+}
+
+/// One `list --format` record per file: the quantization groups' files when
+/// the model has any, otherwise every file of the repository
+fn list_records(quantizations: &[QuantizationGroup], metadata: &ModelMetadata) -> Vec<Record> {
+    if api::has_gguf_files(metadata) || !quantizations.is_empty() {
+        return quantizations
+            .iter()
+            .flat_map(|group| {
+                group.files.iter().map(move |file| {
+                    let revision = file.revision.as_deref().unwrap_or("main");
+                    vec![
+                        ("id", metadata.model_id.clone()),
+                        ("quant", group.quant_type.clone()),
+                        ("revision", revision.to_string()),
+                        ("path", file.filename.clone()),
+                        ("size", format_file_size(file.size)),
+                        ("size_bytes", file.size.to_string()),
+                        (
+                            "sha256",
+                            file.expected_hash
+                                .as_ref()
+                                .and_then(ExpectedHash::sha256)
+                                .unwrap_or_default()
+                                .to_string(),
+                        ),
+                    ]
+                })
+            })
+            .collect();
+    }
+
+    metadata
+        .siblings
+        .iter()
+        .map(|file| {
+            let size = file.size.unwrap_or(0);
+            vec![
+                ("id", metadata.model_id.clone()),
+                (
+                    "quant",
+                    api::extract_quantization_type(&file.rfilename).unwrap_or_default(),
+                ),
+                ("revision", "main".to_string()),
+                ("path", file.rfilename.clone()),
+                ("size", format_file_size(size)),
+                ("size_bytes", size.to_string()),
+                (
+                    "sha256",
+                    file.lfs.as_ref().map(|l| l.oid.clone()).unwrap_or_default(),
+                ),
+            ]
+        })
+        .collect()
+}
+
+/// Files of one repository picked by `download-org`
+pub struct OrgRepoPlan {
+    pub model_id: String,
+    pub files: Vec<ModelFile>,
+}
+
+impl OrgRepoPlan {
+    pub fn total_size(&self) -> u64 {
+        self.files.iter().map(|f| f.size).sum()
+    }
+}
+
+/// Whether a repository file passes the `download-org` filters: any of the
+/// `--include` globs, and `--quant` read from the file name or a quant folder
+fn org_file_matches(path: &str, include: &[String], quant: Option<&str>) -> bool {
+    if !include.is_empty() && !include.iter().any(|p| crate::utils::glob_match(p, path)) {
+        return false;
+    }
+    let Some(quant) = quant else {
+        return true;
+    };
+
+    let mut components = path.rsplit('/');
+    let name = components.next().unwrap_or(path);
+    api::extract_quantization_type(name).is_some_and(|q| q.eq_ignore_ascii_case(quant))
+        || components.any(|dir| {
+            api::is_quantization_directory(dir)
+                && api::extract_quantization_type_from_dirname(dir).eq_ignore_ascii_case(quant)
+        })
+}
+
+/// Ask on the terminal whether to go ahead; needs `--yes` when nobody can answer
+fn confirm_org_download(
+    plans: &[OrgRepoPlan],
+    yes: bool,
+    reporter: &ProgressReporter,
+) -> Result<bool, HeadlessError> {
+    use std::io::IsTerminal;
+
+    if yes {
+        return Ok(true);
+    }
+    if reporter.is_json() || !std::io::stdin().is_terminal() {
+        return Err(HeadlessError::ConfigError(
+            "download-org asks for confirmation; pass --yes to run non-interactively".to_string(),
+        ));
+    }
+
+    let file_count: usize = plans.iter().map(|p| p.files.len()).sum();
+    let total_size: u64 = plans.iter().map(OrgRepoPlan::total_size).sum();
+    print!(
+        "Download {} file{} ({}) from {} repositor{}? [y/N] ",
+        file_count,
+        if file_count == 1 { "" } else { "s" },
+        format_file_size(total_size),
+        plans.len(),
+        if plans.len() == 1 { "y" } else { "ies" }
+    );
+    std::io::stdout().flush()?;
+
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer)?;
+    Ok(matches!(answer.trim(), "y" | "Y" | "yes" | "Yes"))
+}
+
+/// With downloads locked (`--no-download` / `no_download`), ask on the
+/// terminal before queueing; without a terminal (or with --json) nobody can
+/// confirm, so the download is refused
+fn confirm_locked_download(
+    file_count: usize,
+    total_size: u64,
+    reporter: &ProgressReporter,
+) -> Result<(), HeadlessError> {
+    use std::io::IsTerminal;
+
+    if !crate::download::downloads_locked() {
+        return Ok(());
+    }
+    if reporter.is_json() || !std::io::stdin().is_terminal() {
+        return Err(HeadlessError::ConfigError(
+            "downloads are locked (--no-download or no_download in the config)".to_string(),
+        ));
+    }
+
+    print!(
+        "Downloads are locked. Download {} file{} ({}) anyway? [y/N] ",
+        file_count,
+        if file_count == 1 { "" } else { "s" },
+        format_file_size(total_size)
+    );
+    std::io::stdout().flush()?;
+
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer)?;
+    if matches!(answer.trim(), "y" | "Y" | "yes" | "Yes") {
+        Ok(())
+    } else {
+        Err(HeadlessError::ConfigError(
+            "download cancelled (downloads are locked)".to_string(),
+        ))
+    }
+}
+
+/// Models suggested for deletion when a download goes over a quota
+const QUOTA_SUGGESTIONS: usize = 5;
+
+/// The `--output` directory when given, otherwise the download root the
+/// placement rules pick for `total_size` bytes of `model_id` (the default
+/// directory when none matches)
+fn placed_output_dir(
+    output: Option<&str>,
+    model_id: &str,
+    total_size: u64,
+    reporter: &ProgressReporter,
+) -> String {
+    if let Some(output) = output {
+        return output.to_string();
+    }
+    match placement::place(model_id, total_size) {
+        Some(placed) => {
+            reporter.report_placement(&placed);
+            placed.directory
+        }
+        None => config::load_config().default_directory,
+    }
+}
+
+/// Warn when `total_size` more bytes would put `output_dir` over its quota
+/// and suggest models to delete; the download still goes ahead
+async fn warn_over_quota(output_dir: &str, total_size: u64, reporter: &ProgressReporter) {
+    let Some(usage) = quota::usage(std::path::Path::new(output_dir)).await else {
+        return;
+    };
+    if usage.excess(total_size) == 0 {
+        return;
+    }
+    let mut candidates = quota::cleanup_candidates(&registry::load_registry(), &usage.directory);
+    candidates.truncate(QUOTA_SUGGESTIONS);
+    reporter.report_over_quota(&usage, total_size, &candidates);
+}
+
+/// Run `download-org` command: queue the matching files of every model an
+/// organization (or user) publishes, after confirming the per-repository plan
+#[allow(clippy::too_many_arguments)]
+pub async fn run_download_org(
+    org: &str,
+    include: &[String],
+    quant: Option<&str>,
+    output_dir: &str,
+    yes: bool,
+    dry_run: bool,
+    hf_token: Option<String>,
+    reporter: &ProgressReporter,
+    download_tx: mpsc::UnboundedSender<DownloadMessage>,
+    download_queue: Arc<tokio::sync::Mutex<QueueState>>,
+    download_progress: Arc<tokio::sync::Mutex<Option<DownloadProgress>>>,
+    verification_queue_size: Arc<AtomicUsize>,
+    verification_progress: Arc<tokio::sync::Mutex<Vec<VerificationProgress>>>,
+    shutdown_signal: Arc<tokio::sync::Mutex<bool>>,
+) -> Result<(), HeadlessError> {
+    use futures::StreamExt;
+
+    let token = hf_token.or(config::load_config().hf_token);
+    let models = api::fetch_author_models(org, token.as_ref()).await?;
+    if models.is_empty() {
+        return Err(HeadlessError::ApiError(format!(
+            "No models found for '{}'",
+            org
+        )));
+    }
+
+    // Gated repositories can't be downloaded without a token
+    let (models, gated): (Vec<ModelInfo>, Vec<ModelInfo>) = models
+        .into_iter()
+        .partition(|m| token.is_some() || !m.is_gated());
+    for model in &gated {
+        eprintln!("Skipping gated model {} (pass --token)", model.id);
+    }
+
+    let token_ref = token.as_ref();
+    let listings: Vec<(String, Result<Vec<ModelFile>, api::ApiError>)> =
+        futures::stream::iter(models)
+            .map(|model| async move {
+                let files = api::fetch_repo_files(&model.id, token_ref).await;
+                (model.id, files)
+            })
+            .buffer_unordered(4)
+            .collect()
+            .await;
+
+    let mut plans: Vec<OrgRepoPlan> = Vec::new();
+    for (model_id, listing) in listings {
+        match listing {
+            Ok(files) => {
+                let files: Vec<ModelFile> = files
+                    .into_iter()
+                    .filter(|f| org_file_matches(&f.path, include, quant))
+                    .collect();
+                if !files.is_empty() {
+                    plans.push(OrgRepoPlan { model_id, files });
+                }
+            }
+            Err(e) => eprintln!("Skipping {}: {}", model_id, e),
+        }
+    }
+    plans.sort_by(|a, b| a.model_id.cmp(&b.model_id));
+
+    reporter.report_org_plan(org, &plans, history::recent_speed());
+    if plans.is_empty() || dry_run || !confirm_org_download(&plans, yes, reporter)? {
+        return Ok(());
+    }
+
+    {
+        let file_count = plans.iter().map(|p| p.files.len()).sum();
+        let total_size = plans.iter().map(OrgRepoPlan::total_size).sum();
+        confirm_locked_download(file_count, total_size, reporter)?;
+        warn_over_quota(output_dir, total_size, reporter).await;
+        download_queue.lock().await.add(file_count, total_size);
+    }
+    for plan in &plans {
+        for file in &plan.files {
+            download_tx
+                .send((
+                    plan.model_id.clone(),
+                    file.path.clone(),
+                    PathBuf::from(output_dir),
+                    ExpectedHash::of(file.lfs.as_ref(), file.oid.as_deref()),
+                    token.clone(),
+                    file.size,
+                    None,
+                ))
+                .map_err(|e| HeadlessError::DownloadError(e.to_string()))?;
+        }
+    }
+
+    wait_for_downloads(
+        download_queue,
+        download_progress,
+        reporter,
+        shutdown_signal.clone(),
+    )
+    .await?;
+
+    // Check that split GGUFs arrived complete
+    let downloaded_paths: Vec<PathBuf> = registry::load_registry()
+        .downloads
+        .into_iter()
+        .filter(|d| {
+            plans
+                .iter()
+                .any(|p| p.model_id == d.model_id && p.files.iter().any(|f| f.path == d.filename))
+        })
+        .map(|d| PathBuf::from(d.local_path))
+        .collect();
+    reporter.report_part_sets(&crate::multipart::part_sets(&downloaded_paths));
+
+    wait_for_verification(
+        verification_queue_size,
+        verification_progress,
+        reporter,
+        shutdown_signal,
+    )
+    .await
+}
+
+/// First column of a `pick` line: `author/model` or `author/model:QUANT`
+fn parse_pick_key(line: &str) -> Option<(String, Option<String>)> {
+    let key = line.split('\t').next()?.trim();
+    if key.is_empty() {
+        return None;
+    }
+    Some(match key.split_once(':') {
+        Some((model_id, quant)) => (model_id.to_string(), Some(quant.to_string())),
+        None => (key.to_string(), None),
+    })
+}
+
+/// One `pick` line per search result
+async fn pick_model_candidates(
+    query: &str,
+    token: Option<&String>,
+) -> Result<Vec<String>, HeadlessError> {
+    let models = search_models(
+        query,
+        None,
+        None,
+        None,
+        None,
+        ParamRange::default(),
+        AccessFilter::default(),
+        ContentFilter::default(),
+        SearchLimits::default(),
+        token,
+    )
+    .await?;
+    Ok(models
+        .iter()
+        .map(|model| {
+            format!(
+                "{}\t↓{}\t♥{}\t{}\t{}",
+                model.id,
+                model.downloads,
+                model.likes,
+                model.quant_format().map(|f| f.label()).unwrap_or_default(),
+                model
+                    .parameter_count()
+                    .map(crate::utils::format_params)
+                    .unwrap_or_default()
+            )
+        })
+        .collect())
+}
+
+/// One `pick` line per quantization of `model_id`; `None` when the model has
+/// no quantizations to choose from (the whole repository is downloaded)
+async fn pick_quant_candidates(
+    model_id: &str,
+    token: Option<&String>,
+) -> Result<Option<Vec<String>>, HeadlessError> {
+    let (quantizations, metadata) = list_quantizations(model_id, token).await?;
+    if !api::has_gguf_files(&metadata) && quantizations.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(
+        quantizations
+            .iter()
+            .map(|group| {
+                let branch = group.files.iter().any(|f| f.revision.is_some());
+                format!(
+                    "{}:{}\t{}\t{} file{}{}",
+                    model_id,
+                    group.quant_type,
+                    format_file_size(group.total_size),
+                    group.files.len(),
+                    if group.files.len() == 1 { "" } else { "s" },
+                    if branch { "\t[branch]" } else { "" }
+                )
+            })
+            .collect(),
+    ))
+}
+
+/// Feed `lines` to a picker command (run through the shell, its UI on the
+/// terminal) and return the line it prints; `None` if nothing was picked
+fn run_picker(picker: &str, lines: &[String]) -> Result<Option<String>, HeadlessError> {
+    use std::process::{Command, Stdio};
+
+    let mut child = Command::new("sh")
+        .arg("-c")
+        .arg(picker)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // The picker may exit before reading everything
+        let _ = stdin.write_all(lines.join("\n").as_bytes());
+    }
+    let output = child.wait_with_output()?;
+    let picked = String::from_utf8_lossy(&output.stdout);
+    Ok(picked
+        .lines()
+        .next()
+        .filter(|line| !line.trim().is_empty())
+        .map(str::to_string))
+}
+
+/// Run `pick` command: print candidates for an external picker, or (with
+/// `--picker`) run it, and download the picked line
+///
+/// Candidates are search results for a query, or the quantizations of a model
+/// ID or URL. `-` reads a picked line from stdin: a quantization (or a model without
+/// quantizations) is downloaded, a model prints its quantizations, so
+/// `pick QUERY | fzf | pick - | fzf | pick -` works without `--picker`.
+#[allow(clippy::too_many_arguments)]
+pub async fn run_pick(
+    query: &str,
+    picker: Option<&str>,
+    output: Option<&str>,
+    hf_token: Option<String>,
+    accept_license: bool,
+    reporter: &ProgressReporter,
+    download_tx: mpsc::UnboundedSender<DownloadMessage>,
+    progress_tx: mpsc::UnboundedSender<String>,
+    download_queue: Arc<tokio::sync::Mutex<QueueState>>,
+    download_progress: Arc<tokio::sync::Mutex<Option<DownloadProgress>>>,
+    verification_queue_size: Arc<AtomicUsize>,
+    verification_progress: Arc<tokio::sync::Mutex<Vec<VerificationProgress>>>,
+    shutdown_signal: Arc<tokio::sync::Mutex<bool>>,
+) -> Result<(), HeadlessError> {
+    let token = hf_token.clone().or(config::load_config().hf_token);
+
+    // Choose with the picker, or print the candidates and stop
+    let choose = |lines: Vec<String>| -> Result<Option<String>, HeadlessError> {
+        match picker {
+            Some(picker) => run_picker(picker, &lines),
+            None => {
+                reporter.report_pick_candidates(&lines);
+                Ok(None)
+            }
+        }
+    };
+
+    let picked = if query == "-" {
+        let mut line = String::new();
+        std::io::stdin().read_line(&mut line)?;
+        Some(line)
+    } else if query.contains('/') {
+        Some(resolve_model_reference(query, reporter)?.model_id)
+    } else {
+        choose(pick_model_candidates(query, token.as_ref()).await?)?
+    };
+    let Some((model_id, mut quant)) = picked.as_deref().and_then(parse_pick_key) else {
+        if picker.is_some() || query == "-" {
+            eprintln!("Nothing picked");
+        }
+        return Ok(());
+    };
+    validate_model_id(&model_id)?;
+
+    if quant.is_none() {
+        if let Some(lines) = pick_quant_candidates(&model_id, token.as_ref()).await? {
+            let Some(line) = choose(lines)? else {
+                if picker.is_some() {
+                    eprintln!("Nothing picked");
+                }
+                return Ok(());
+            };
+            quant = parse_pick_key(&line).and_then(|(_, quant)| quant);
+            if quant.is_none() {
+                return Err(HeadlessError::DownloadError(format!(
+                    "Picked line is not a quantization of {}: {}",
+                    model_id, line
+                )));
+            }
+        }
+    }
+
+    run_download(
+        &model_id,
+        quant.as_deref(),
+        quant.is_none(),
+        None,
+        false,
+        output,
+        hf_token,
+        accept_license,
+        reporter,
+        download_tx,
+        progress_tx,
+        download_queue,
+        download_progress,
+        verification_queue_size,
+        verification_progress,
+        shutdown_signal,
+    )
+    .await
+}
+
+/// Run `my-models` command: every model of the token's owner, private ones included
+pub async fn run_my_models(
+    token: Option<&String>,
+    reporter: &ProgressReporter,
+) -> Result<(), HeadlessError> {
+    let Some(token) = token.filter(|t| !t.is_empty()) else {
+        return Err(HeadlessError::AuthError(
+            "my-models lists the models of the token's owner; pass --token".to_string(),
+        ));
+    };
+
+    let start = std::time::Instant::now();
+    let user = api::fetch_username(Some(token)).await?;
+    let models = api::fetch_author_models(&user, Some(token)).await?;
+
+    reporter.report_search_with_timing(&models, start.elapsed(), None);
+    Ok(())
+}
+
+/// Run `recent` command: GGUF repositories created in the last `days` days,
+/// grouped by base model
+pub async fn run_recent(
+    days: u64,
+    format: Option<&RecordFormat>,
+    token: Option<&String>,
+    reporter: &ProgressReporter,
+) -> Result<(), HeadlessError> {
+    let models = api::fetch_recent_gguf_models(days, token).await?;
+    let groups = api::group_by_base_model(models);
 
+    if let Some(format) = format {
+        let models: Vec<ModelInfo> = groups.into_iter().flat_map(|(_, models)| models).collect();
+        reporter.report_records(format, SEARCH_FIELDS, &search_records(&models));
+        return Ok(());
+    }
+    reporter.report_recent_gguf(days, &groups);
     Ok(())
 }
 
@@ -171,24 +927,46 @@ pub async fn list_quantizations(
     model_id: &str,
     token: Option<&String>,
 ) -> Result<(Vec<QuantizationGroup>, ModelMetadata), HeadlessError> {
-    // Try to fetch GGUF quantizations first
-    let quantizations = api::fetch_model_files(model_id, token)
-        .await
-        .map_err(|e| HeadlessError::ApiError(e.to_string()))?;
-
     // Always fetch full metadata for file tree
-    let metadata = api::fetch_model_metadata(model_id, token)
-        .await
-        .map_err(|e| HeadlessError::ApiError(e.to_string()))?;
+    let metadata = api::fetch_model_metadata(model_id, token).await?;
+    let quantizations = quant_groups(&metadata, token).await?;
 
     Ok((quantizations, metadata))
 }
 
-/// Download a model with optional quantization filter
+/// GGUF quantizations plus quants published on other branches; repositories
+/// without GGUF files that keep their quants on branches (typical for EXL2)
+/// get the files of main as a "main" group
+async fn quant_groups(
+    metadata: &ModelMetadata,
+    token: Option<&String>,
+) -> Result<Vec<QuantizationGroup>, HeadlessError> {
+    let mut quantizations = api::fetch_model_files(&metadata.model_id, token).await?;
+    if !api::has_gguf_files(metadata) && !quantizations.is_empty() {
+        quantizations.insert(0, api::main_branch_group(metadata));
+    }
+    Ok(quantizations)
+}
+
+/// Whether the download is picked from the quantization groups: always for
+/// GGUF repositories, and with `--quantization` for repositories whose quants
+/// live on branches
+fn selects_quant_group(
+    metadata: &ModelMetadata,
+    quantizations: &[QuantizationGroup],
+    quantization: Option<&str>,
+) -> bool {
+    api::has_gguf_files(metadata) || (quantization.is_some() && !quantizations.is_empty())
+}
+
+/// Download a model with optional quantization or single-file filter
+#[allow(clippy::too_many_arguments)]
 pub async fn download_model(
     model_id: &str,
     quantization_filter: Option<&str>,
+    file_filter: Option<&str>,
     download_all: bool,
+    preset: Option<RepoPreset>,
     output_dir: &str,
     hf_token: Option<String>,
     progress_tx: mpsc::UnboundedSender<String>,
@@ -198,18 +976,35 @@ pub async fn download_model(
     let token = hf_token.or(options.hf_token);
 
     // Fetch model metadata
-    let metadata = api::fetch_model_metadata(model_id, token.as_ref())
-        .await
-        .map_err(|e| HeadlessError::ApiError(e.to_string()))?;
+    let metadata = api::fetch_model_metadata(model_id, token.as_ref()).await?;
 
-    // Check if model has GGUF files
-    let has_gguf = api::has_gguf_files(&metadata);
+    // A file URL downloads exactly that file, whatever the model type
+    if let Some(file_path) = file_filter {
+        let file = find_repo_file(&metadata, file_path)?;
+        download_tx
+            .send((
+                model_id.to_string(),
+                file.rfilename.clone(),
+                PathBuf::from(output_dir),
+                ExpectedHash::of(file.lfs.as_ref(), None),
+                token.clone(),
+                file.size.unwrap_or(0),
+                None,
+            ))
+            .map_err(|e| HeadlessError::DownloadError(e.to_string()))?;
 
-    if has_gguf {
-        let quantizations = api::fetch_model_files(model_id, token.as_ref())
-            .await
-            .map_err(|e| HeadlessError::ApiError(e.to_string()))?;
+        let _ = progress_tx.send(format!("Queued: {}", file.rfilename));
+        return Ok(());
+    }
+
+    // GGUF quants, or quants kept on branches when one is asked for
+    let quantizations = if api::has_gguf_files(&metadata) || quantization_filter.is_some() {
+        quant_groups(&metadata, token.as_ref()).await?
+    } else {
+        Vec::new()
+    };
 
+    if selects_quant_group(&metadata, &quantizations, quantization_filter) {
         // Filter by quantization type if specified
         let files_to_download: Vec<_> = if let Some(q_filter) = quantization_filter {
             quantizations
@@ -227,42 +1022,50 @@ pub async fn download_model(
 
         // Queue downloads
         for quant_file in files_to_download {
-            let path = PathBuf::from(output_dir);
+            // Root-level quant files optionally go into a per-quant subfolder,
+            // files from other branches into a folder named after the branch
+            let path =
+                match crate::download::quant_file_subdir(quant_file, options.quant_subdirectory) {
+                    Some(subdir) => PathBuf::from(output_dir).join(subdir),
+                    None => PathBuf::from(output_dir),
+                };
             let total_size = quant_file.size;
             download_tx
                 .send((
                     model_id.to_string(),
                     quant_file.filename.clone(),
                     path,
-                    quant_file.sha256.clone(),
+                    quant_file.expected_hash.clone(),
                     token.clone(),
                     total_size,
+                    quant_file.revision.clone(),
                 ))
                 .map_err(|e| HeadlessError::DownloadError(e.to_string()))?;
 
             let _ = progress_tx.send(format!("Queued: {}", quant_file.filename));
         }
     } else {
-        // Non-GGUF model: download all files from metadata
-        if !download_all {
+        // Non-GGUF model: download the files of the preset (all by default)
+        if !download_all && preset.is_none() {
             return Err(HeadlessError::DownloadError(
-                "Non-GGUF models require --all flag".to_string(),
+                "Non-GGUF models require --all flag (or --preset)".to_string(),
             ));
         }
 
-        for file in &metadata.siblings {
+        for file in api::preset_files(&metadata, preset.unwrap_or_default()) {
             let path = PathBuf::from(output_dir);
             let size = file.size.unwrap_or(0);
-            let sha256 = file.lfs.as_ref().map(|l| l.oid.clone());
+            let expected_hash = file.expected_hash();
 
             download_tx
                 .send((
                     model_id.to_string(),
                     file.rfilename.clone(),
                     path,
-                    sha256,
+                    expected_hash,
                     token.clone(),
                     size,
+                    None,
                 ))
                 .map_err(|e| HeadlessError::DownloadError(e.to_string()))?;
 
@@ -339,35 +1142,91 @@ fn calculate_gguf_download_summary(
 fn calculate_non_gguf_download_summary(
     metadata: &ModelMetadata,
     download_all: bool,
+    preset: Option<RepoPreset>,
 ) -> Result<(Vec<String>, u64), HeadlessError> {
-    if !download_all {
+    if !download_all && preset.is_none() {
         return Err(HeadlessError::DownloadError(
-            "Non-GGUF model requires --all flag".to_string(),
+            "Non-GGUF model requires --all flag (or --preset)".to_string(),
         ));
     }
 
-    let files: Vec<String> = metadata
-        .siblings
+    // Pipeline and runtime presets only fit repositories with that layout
+    let presets = api::repo_presets(metadata);
+    if let Some(preset) = preset.filter(|preset| {
+        matches!(preset, RepoPreset::Runtime(_) | RepoPreset::Pipeline(..))
+            && !presets.contains(preset)
+    }) {
+        return Err(HeadlessError::DownloadError(format!(
+            "Preset '{}' does not fit {} (its presets: {})",
+            preset.name(),
+            metadata.model_id,
+            presets
+                .iter()
+                .map(|preset| preset.name())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )));
+    }
+
+    let selected = api::preset_files(metadata, preset.unwrap_or_default());
+    let files: Vec<String> = selected
         .iter()
         .filter_map(|f| f.size.map(|_| f.rfilename.clone()))
         .collect();
 
-    let total_size: u64 = metadata.siblings.iter().filter_map(|f| f.size).sum();
+    let total_size: u64 = selected.iter().filter_map(|f| f.size).sum();
 
     Ok((files, total_size))
 }
 
-/// Run download command in dry-run mode (show what would be downloaded)
+/// Resolve the base model to download alongside an adapter (`--with-base`)
+/// Returns the base model ID and its output directory (<output>/<author>/<model>)
+fn adapter_base_target(
+    metadata: &ModelMetadata,
+    with_base: bool,
+    output_dir: &str,
+) -> Result<Option<(String, String)>, HeadlessError> {
+    if !with_base {
+        return Ok(None);
+    }
+
+    let base = metadata.adapter_base_model().ok_or_else(|| {
+        HeadlessError::DownloadError(format!(
+            "--with-base requires a LoRA/PEFT adapter repository with a base model, '{}' is not one",
+            metadata.model_id
+        ))
+    })?;
+    validate_model_id(&base)?;
+
+    let base_dir = PathBuf::from(output_dir).join(&base);
+    Ok(Some((base, base_dir.to_string_lossy().to_string())))
+}
+
+/// Calculate download summary for a single file (from a blob/resolve URL)
+fn calculate_single_file_summary(
+    metadata: &ModelMetadata,
+    file_path: &str,
+) -> Result<(Vec<String>, u64), HeadlessError> {
+    let file = find_repo_file(metadata, file_path)?;
+    Ok((vec![file.rfilename.clone()], file.size.unwrap_or(0)))
+}
+
+/// Run download command in dry-run mode (show what would be downloaded)
+#[allow(clippy::too_many_arguments)]
 pub async fn run_download_dry_run(
     model_id: &str,
     quantization: Option<&str>,
     download_all: bool,
-    output_dir: &str,
+    preset: Option<RepoPreset>,
+    with_base: bool,
+    output: Option<&str>,
     hf_token: Option<String>,
+    accept_license: bool,
     reporter: &ProgressReporter,
 ) -> Result<(), HeadlessError> {
-    // Validate model ID first
-    validate_model_id(model_id)?;
+    // Accept a model ID or a huggingface.co model/file URL
+    let reference = resolve_model_reference(model_id, reporter)?;
+    let model_id = reference.model_id.as_str();
 
     reporter.report_info("Dry run mode - no files will be downloaded\n");
 
@@ -379,31 +1238,150 @@ pub async fn run_download_dry_run(
 
     let has_gguf = api::has_gguf_files(&metadata);
 
-    let (files_to_download, total_size) = if has_gguf {
+    let (files_to_download, total_size) = if let Some(file_path) = &reference.file_path {
+        calculate_single_file_summary(&metadata, file_path)?
+    } else if selects_quant_group(&metadata, &quantizations, quantization) {
         calculate_gguf_download_summary(&quantizations, quantization, download_all)?
     } else {
-        calculate_non_gguf_download_summary(&metadata, download_all)?
+        calculate_non_gguf_download_summary(&metadata, download_all, preset)?
     };
-
-    // Report what would be downloaded
-    reporter.report_dry_run_summary(&files_to_download, total_size, output_dir, has_gguf);
+    let output_dir = &placed_output_dir(output, model_id, total_size, reporter);
+
+    // Report what would be downloaded, with an ETA at the recent average speed
+    let speed_mbps = history::recent_speed();
+    let license = metadata.license_notice();
+    reporter.report_dry_run_summary(
+        &files_to_download,
+        total_size,
+        output_dir,
+        has_gguf,
+        &license,
+        speed_mbps,
+    );
+    report_license_requirement(&license, accept_license, reporter);
+
+    if let Some((base, base_dir)) = adapter_base_target(&metadata, with_base, output_dir)? {
+        let base_metadata = api::fetch_model_metadata(&base, hf_token.as_ref()).await?;
+        check_gated_model(&base_metadata, &hf_token)?;
+        let (base_files, base_size) =
+            calculate_non_gguf_download_summary(&base_metadata, true, preset)?;
+
+        reporter.report_info(&format!("\nBase model {}:", base));
+        let base_license = base_metadata.license_notice();
+        reporter.report_dry_run_summary(
+            &base_files,
+            base_size,
+            &base_dir,
+            false,
+            &base_license,
+            speed_mbps,
+        );
+        report_license_requirement(&base_license, accept_license, reporter);
+    }
 
     Ok(())
 }
 
+/// Verify already downloaded files of a model instead of downloading them
+///
+/// Uses the same file selection as `download`; every selected file that the
+/// registry lists as complete with a known SHA256 is queued for verification.
+#[allow(clippy::too_many_arguments)]
+pub async fn run_verify_only(
+    model_id: &str,
+    quantization: Option<&str>,
+    download_all: bool,
+    preset: Option<RepoPreset>,
+    with_base: bool,
+    output_dir: &str,
+    hf_token: Option<String>,
+    reporter: &ProgressReporter,
+    verification_queue: Arc<tokio::sync::Mutex<Vec<VerificationQueueItem>>>,
+    verification_queue_size: Arc<AtomicUsize>,
+    verification_progress: Arc<tokio::sync::Mutex<Vec<VerificationProgress>>>,
+    shutdown_signal: Arc<tokio::sync::Mutex<bool>>,
+) -> Result<(), HeadlessError> {
+    // Accept a model ID or a huggingface.co model/file URL
+    let reference = resolve_model_reference(model_id, reporter)?;
+    let model_id = reference.model_id.as_str();
+
+    let (quantizations, metadata) = list_quantizations(model_id, hf_token.as_ref()).await?;
+
+    let (files, _) = if let Some(file_path) = &reference.file_path {
+        calculate_single_file_summary(&metadata, file_path)?
+    } else if selects_quant_group(&metadata, &quantizations, quantization) {
+        calculate_gguf_download_summary(&quantizations, quantization, download_all)?
+    } else {
+        calculate_non_gguf_download_summary(&metadata, download_all, preset)?
+    };
+
+    let mut targets = vec![(model_id.to_string(), files)];
+    if let Some((base, _)) = adapter_base_target(&metadata, with_base, output_dir)? {
+        let base_metadata = api::fetch_model_metadata(&base, hf_token.as_ref()).await?;
+        let (base_files, _) = calculate_non_gguf_download_summary(&base_metadata, true, preset)?;
+        targets.push((base, base_files));
+    }
+
+    let registry = registry::load_registry();
+    let (mut queued, mut missing, mut no_hash) = (0, 0, 0);
+    for (repo, files) in &targets {
+        for filename in files {
+            let entry = registry.downloads.iter().find(|d| {
+                d.model_id == *repo
+                    && d.filename == *filename
+                    && d.status == DownloadStatus::Complete
+            });
+            let Some(entry) = entry.filter(|d| std::path::Path::new(&d.local_path).exists())
+            else {
+                missing += 1;
+                continue;
+            };
+            let Some(expected_hash) = entry.expected_hash() else {
+                no_hash += 1;
+                continue;
+            };
+
+            crate::verification::queue_verification(
+                verification_queue.clone(),
+                verification_queue_size.clone(),
+                VerificationQueueItem {
+                    model_id: entry.model_id.clone(),
+                    filename: entry.filename.clone(),
+                    local_path: entry.local_path.clone(),
+                    expected_hash,
+                    total_size: entry.total_size,
+                    is_manual: true,
+                },
+            )
+            .await;
+            queued += 1;
+        }
+    }
+
+    reporter.report_info(&format!(
+        "Verifying {} file{} ({} not downloaded, {} without a SHA256 hash)",
+        queued,
+        if queued == 1 { "" } else { "s" },
+        missing,
+        no_hash
+    ));
+
+    wait_for_verification(
+        verification_queue_size,
+        verification_progress,
+        reporter,
+        shutdown_signal,
+    )
+    .await
+}
+
 /// Check if a model is gated and requires authentication
 fn check_gated_model(
     metadata: &ModelMetadata,
     hf_token: &Option<String>,
 ) -> Result<(), HeadlessError> {
     // Check if model is gated
-    let is_gated = match &metadata.gated {
-        serde_json::Value::String(s) if s == "auto" || s == "manual" => true,
-        serde_json::Value::Bool(true) => true,
-        _ => false,
-    };
-
-    if is_gated {
+    if metadata.is_gated() {
         // Check if token is provided
         if hf_token.is_none() || hf_token.as_ref().map(|t| t.is_empty()).unwrap_or(true) {
             return Err(HeadlessError::AuthError(format!(
@@ -425,14 +1403,53 @@ fn check_gated_model(
     Ok(())
 }
 
+/// Whether `require_license_confirmation` holds back a model with this license
+fn needs_license_confirmation(license: &LicenseNotice, accept_license: bool) -> bool {
+    !accept_license
+        && !license.is_permissive()
+        && config::load_config().require_license_confirmation
+}
+
+/// Dry run: say when the real download would stop at the license check
+fn report_license_requirement(
+    license: &LicenseNotice,
+    accept_license: bool,
+    reporter: &ProgressReporter,
+) {
+    if needs_license_confirmation(license, accept_license) {
+        reporter.report_info(
+            "Not a permissive license: downloading requires --accept-license \
+             (require_license_confirmation is set)",
+        );
+    }
+}
+
+/// Refuse models without a permissive license when the config sets
+/// `require_license_confirmation` and `--accept-license` wasn't passed
+fn check_license(metadata: &ModelMetadata, accept_license: bool) -> Result<(), HeadlessError> {
+    let license = metadata.license_notice();
+    if needs_license_confirmation(&license, accept_license) {
+        return Err(HeadlessError::DownloadError(format!(
+            "Model '{}' has license: {}.\n\n\
+            Review the terms at https://huggingface.co/{} and pass --accept-license\n\
+            to download it (require_license_confirmation is set in the config).",
+            metadata.model_id, license, metadata.model_id
+        )));
+    }
+    Ok(())
+}
+
 /// Run download command with summary and progress tracking
 #[allow(clippy::too_many_arguments)]
 pub async fn run_download(
     model_id: &str,
     quantization: Option<&str>,
     download_all: bool,
-    output_dir: &str,
+    preset: Option<RepoPreset>,
+    with_base: bool,
+    output: Option<&str>,
     hf_token: Option<String>,
+    accept_license: bool,
     reporter: &ProgressReporter,
     download_tx: mpsc::UnboundedSender<DownloadMessage>,
     progress_tx: mpsc::UnboundedSender<String>,
@@ -442,24 +1459,43 @@ pub async fn run_download(
     verification_progress: Arc<tokio::sync::Mutex<Vec<VerificationProgress>>>,
     shutdown_signal: Arc<tokio::sync::Mutex<bool>>,
 ) -> Result<(), HeadlessError> {
-    // Validate model ID first
-    validate_model_id(model_id)?;
+    // Accept a model ID or a huggingface.co model/file URL
+    let reference = resolve_model_reference(model_id, reporter)?;
+    let model_id = reference.model_id.as_str();
+    let file_filter = reference.file_path.as_deref();
 
     // Get download summary
     let (quantizations, metadata) = list_quantizations(model_id, hf_token.as_ref()).await?;
 
     // Check if model is gated and token is provided
     check_gated_model(&metadata, &hf_token)?;
-    let has_gguf = api::has_gguf_files(&metadata);
+    check_license(&metadata, accept_license)?;
 
-    let (files_to_download, total_size) = if has_gguf {
+    let (mut files_to_download, mut total_size) = if let Some(file_path) = file_filter {
+        calculate_single_file_summary(&metadata, file_path)?
+    } else if selects_quant_group(&metadata, &quantizations, quantization) {
         calculate_gguf_download_summary(&quantizations, quantization, download_all)?
     } else {
-        calculate_non_gguf_download_summary(&metadata, download_all)?
+        calculate_non_gguf_download_summary(&metadata, download_all, preset)?
     };
+    let output_dir = &placed_output_dir(output, model_id, total_size, reporter);
+
+    // Adapter + base model: fold the base repository into the same summary
+    let base_target = adapter_base_target(&metadata, with_base, output_dir)?;
+    if let Some((base, _)) = &base_target {
+        let base_metadata = api::fetch_model_metadata(base, hf_token.as_ref()).await?;
+        check_gated_model(&base_metadata, &hf_token)?;
+        check_license(&base_metadata, accept_license)?;
+        let (base_files, base_size) =
+            calculate_non_gguf_download_summary(&base_metadata, true, preset)?;
+        files_to_download.extend(base_files.into_iter().map(|f| format!("{}/{}", base, f)));
+        total_size += base_size;
+    }
 
     // Report what will be downloaded
-    reporter.report_download_summary(&files_to_download, total_size);
+    reporter.report_download_summary(&files_to_download, total_size, &metadata.license_notice());
+    confirm_locked_download(files_to_download.len(), total_size, reporter)?;
+    warn_over_quota(output_dir, total_size, reporter).await;
 
     // Update queue state before enqueueing downloads
     {
@@ -471,14 +1507,31 @@ pub async fn run_download(
     download_model(
         model_id,
         quantization,
+        file_filter,
         download_all,
+        preset,
         output_dir,
-        hf_token,
-        progress_tx,
-        download_tx,
+        hf_token.clone(),
+        progress_tx.clone(),
+        download_tx.clone(),
     )
     .await?;
 
+    if let Some((base, base_dir)) = &base_target {
+        download_model(
+            base,
+            None,
+            None,
+            true,
+            preset,
+            base_dir,
+            hf_token,
+            progress_tx,
+            download_tx,
+        )
+        .await?;
+    }
+
     // Wait for downloads to complete
     wait_for_downloads(
         download_queue,
@@ -488,6 +1541,15 @@ pub async fn run_download(
     )
     .await?;
 
+    // Check that split GGUFs arrived complete
+    let downloaded_paths: Vec<PathBuf> = registry::load_registry()
+        .downloads
+        .into_iter()
+        .filter(|d| d.model_id == model_id && files_to_download.contains(&d.filename))
+        .map(|d| PathBuf::from(d.local_path))
+        .collect();
+    reporter.report_part_sets(&crate::multipart::part_sets(&downloaded_paths));
+
     // Wait for verification to complete
     wait_for_verification(
         verification_queue_size,
@@ -509,7 +1571,12 @@ pub async fn resume_downloads(
     let incomplete: Vec<_> = registry
         .downloads
         .iter()
-        .filter(|d| d.status == DownloadStatus::Incomplete)
+        .filter(|d| {
+            matches!(
+                d.status,
+                DownloadStatus::Incomplete | DownloadStatus::Changed
+            )
+        })
         .cloned()
         .collect();
 
@@ -519,34 +1586,70 @@ pub async fn resume_downloads(
     }
 
     for download in &incomplete {
-        let local_path = PathBuf::from(&download.local_path);
-        let filename_path = std::path::Path::new(&download.filename);
-        let mut base_path = local_path.clone();
-        let strip_count = filename_path.components().count();
-        for _ in 0..strip_count {
-            if let Some(parent) = base_path.parent() {
-                base_path = parent.to_path_buf();
-            } else {
-                break;
-            }
-        }
+        crate::download::discard_changed_file(download);
         download_tx
-            .send((
-                download.model_id.clone(),
-                download.filename.clone(),
-                base_path,
-                download.expected_sha256.clone(),
-                None, // Use token from config
-                download.total_size,
-            ))
+            .send(resume_message(download))
             .map_err(|e| HeadlessError::DownloadError(e.to_string()))?;
 
-        let _ = progress_tx.send(format!("Resumed: {}", download.filename));
+        let _ = progress_tx.send(if download.status == DownloadStatus::Changed {
+            format!("Downloading again (changed on disk): {}", download.filename)
+        } else {
+            format!("Resumed: {}", download.filename)
+        });
     }
 
     Ok(incomplete)
 }
 
+/// Download message that resumes a registry entry into its original directory
+fn resume_message(download: &DownloadMetadata) -> DownloadMessage {
+    let local_path = PathBuf::from(&download.local_path);
+    let filename_path = std::path::Path::new(&download.filename);
+    let mut base_path = local_path.clone();
+    let strip_count = filename_path.components().count();
+    for _ in 0..strip_count {
+        if let Some(parent) = base_path.parent() {
+            base_path = parent.to_path_buf();
+        } else {
+            break;
+        }
+    }
+    (
+        download.model_id.clone(),
+        download.filename.clone(),
+        base_path,
+        download.expected_hash(),
+        None, // Use token from config
+        download.total_size,
+        crate::download::url_revision(&download.url),
+    )
+}
+
+/// Queue the registry's incomplete downloads ahead of a download command
+/// (`auto_resume` option), so the command's wait covers them too
+pub async fn auto_resume_downloads(
+    download_tx: mpsc::UnboundedSender<DownloadMessage>,
+    progress_tx: mpsc::UnboundedSender<String>,
+    download_queue: Arc<tokio::sync::Mutex<QueueState>>,
+) -> Result<(), HeadlessError> {
+    let registry = registry::load_registry();
+    if !registry
+        .downloads
+        .iter()
+        .any(|d| d.status == DownloadStatus::Incomplete)
+    {
+        return Ok(());
+    }
+
+    let incomplete = resume_downloads(download_tx, progress_tx).await?;
+    let total_size: u64 = incomplete.iter().map(|d| d.total_size).sum();
+    download_queue
+        .lock()
+        .await
+        .add(incomplete.len(), total_size);
+    Ok(())
+}
+
 /// Wait for all downloads to complete and report progress
 pub async fn wait_for_downloads(
     download_queue: Arc<tokio::sync::Mutex<QueueState>>,
@@ -568,7 +1671,8 @@ pub async fn wait_for_downloads(
             return Ok(());
         }
 
-        // Check download progress
+        // Check download progress (queue first, never while holding the progress lock)
+        let queued_bytes = download_queue.lock().await.bytes;
         let progress_guard = download_progress.try_lock();
         if let Ok(ref progress_opt) = progress_guard {
             if let Some(progress) = progress_opt.as_ref() {
@@ -596,12 +1700,7 @@ pub async fn wait_for_downloads(
                         0.0
                     };
 
-                    reporter.report_download_progress(
-                        &progress.filename,
-                        progress.downloaded,
-                        progress.total,
-                        speed_mbps,
-                    );
+                    reporter.report_download_progress(progress, speed_mbps, queued_bytes);
                     last_progress = Some(progress.clone());
                     last_report_time = now;
                 }
@@ -618,7 +1717,7 @@ pub async fn wait_for_downloads(
 
         if queue_size == 0 && !has_progress {
             // Print newline to clear the progress bar line if we had an active download
-            if had_active_download && !reporter.is_json() {
+            if had_active_download && reporter.is_progress_bar() {
                 println!();
             }
             break;
@@ -636,7 +1735,8 @@ pub async fn wait_for_verification(
     shutdown_signal: Arc<tokio::sync::Mutex<bool>>,
 ) -> Result<(), HeadlessError> {
     let mut interval = tokio::time::interval(tokio::time::Duration::from_millis(200));
-    let mut last_progress: Option<VerificationProgress> = None;
+    // Verified bytes per file at the last report
+    let mut last_reported: HashMap<String, u64> = HashMap::new();
     let mut consecutive_idle_checks = 0;
     let mut shown_initial = false;
     let mut seen_verification_activity = false;
@@ -648,7 +1748,7 @@ pub async fn wait_for_verification(
     // Show initial 0% progress bar if there's work queued
     {
         let queue_size = verification_queue_size.load(Ordering::Relaxed);
-        if queue_size > 0 && !reporter.is_json() {
+        if queue_size > 0 && reporter.is_progress_bar() {
             print!("\r[{}] 0% verifying...", " ".repeat(40));
             let _ = std::io::stdout().flush();
             shown_initial = true;
@@ -672,40 +1772,39 @@ pub async fn wait_for_verification(
             return Ok(());
         }
 
-        // Check verification progress
-        let progress_guard = verification_progress.try_lock();
-        if let Ok(ref progress_vec) = progress_guard {
-            if let Some(progress) = progress_vec.first() {
+        // Check verification progress (one entry per file being hashed)
+        let active = verification_progress.try_lock().map(|p| p.clone());
+        if let Ok(active) = active {
+            if !active.is_empty() {
                 consecutive_idle_checks = 0; // Reset idle counter when we see activity
                 seen_verification_activity = true; // Mark that we've seen verification start
 
-                // Always show progress when we first see it (even at 0 bytes)
-                let should_report = last_progress.is_none()
-                    || progress.filename
-                        != last_progress
-                            .as_ref()
-                            .map(|p| p.filename.as_str())
-                            .unwrap_or("")
-                    || (progress.verified_bytes.load(std::sync::atomic::Ordering::Relaxed) as f64
-                        - last_progress
-                            .as_ref()
-                            .map(|l| l.verified_bytes.load(std::sync::atomic::Ordering::Relaxed))
-                            .unwrap_or(0) as f64)
-                        > progress.total_bytes as f64 * 0.01;
+                // Always show progress when a file starts (even at 0 bytes),
+                // then whenever any file moved by more than 1%
+                let should_report = active.len() != last_reported.len()
+                    || active.iter().any(|progress| {
+                        let verified = progress.verified_bytes.load(Ordering::Relaxed);
+                        match last_reported.get(&progress.filename) {
+                            Some(last) => {
+                                verified.saturating_sub(*last) as f64
+                                    > progress.total_bytes as f64 * 0.01
+                            }
+                            None => true,
+                        }
+                    });
 
                 if should_report {
-                    reporter.report_verification_progress(
-                        &progress.filename,
-                        progress.verified_bytes.load(std::sync::atomic::Ordering::Relaxed),
-                        progress.total_bytes,
-                        progress.speed_mbps,
-                    );
-                    last_progress = Some(progress.clone());
+                    reporter.report_verifications(&active);
+                    last_reported = active
+                        .iter()
+                        .map(|p| {
+                            (p.filename.clone(), p.verified_bytes.load(Ordering::Relaxed))
+                        })
+                        .collect();
                     shown_initial = true;
                 }
             }
         }
-        drop(progress_guard);
 
         // Check if queue is empty and no active verifications
         let queue_size = verification_queue_size.load(Ordering::Relaxed);
@@ -722,7 +1821,7 @@ pub async fn wait_for_verification(
                 consecutive_idle_checks += 1;
                 if consecutive_idle_checks >= 3 {
                     // Print newline to clear progress line
-                    if shown_initial && !reporter.is_json() {
+                    if shown_initial && reporter.is_progress_bar() {
                         println!();
                     }
                     break;
@@ -740,19 +1839,32 @@ pub async fn wait_for_verification(
 /// Run list command with formatted output
 pub async fn run_list(
     model_id: &str,
+    format: Option<&RecordFormat>,
     token: Option<&String>,
     reporter: &ProgressReporter,
 ) -> Result<(), HeadlessError> {
-    // Validate model ID first
-    validate_model_id(model_id)?;
+    // Accept a model ID or a huggingface.co model URL
+    let reference = resolve_model_reference(model_id, reporter)?;
+    let model_id = reference.model_id.as_str();
 
-    let (quantizations, metadata) = list_quantizations(model_id, token).await?;
+    let (quantizations, mut metadata) = list_quantizations(model_id, token).await?;
+
+    if let Some(format) = format {
+        let records = list_records(&quantizations, &metadata);
+        reporter.report_records(format, LIST_FIELDS, &records);
+        return Ok(());
+    }
+    metadata.base_model_chain = api::fetch_base_model_chain(&metadata, token).await;
 
     let has_gguf = api::has_gguf_files(&metadata);
 
     if reporter.is_json() {
         reporter.report_list_json(&quantizations, &metadata, has_gguf);
-    } else if has_gguf {
+        return Ok(());
+    }
+
+    reporter.report_model_overview(&metadata);
+    if has_gguf || !quantizations.is_empty() {
         reporter.report_quantizations_table(&quantizations);
     } else {
         reporter.report_file_tree(&metadata);
@@ -761,424 +1873,2260 @@ pub async fn run_list(
     Ok(())
 }
 
-/// Run resume command with formatted output
-#[allow(clippy::too_many_arguments)]
-pub async fn run_resume(
+/// Run `cache clear` command
+pub fn run_cache_clear(reporter: &ProgressReporter) -> Result<(), HeadlessError> {
+    let (entries, bytes) = crate::cache::clear()?;
+    reporter.report_cache_cleared(entries, bytes);
+    Ok(())
+}
+
+/// Run `blobs status` command
+pub fn run_blobs_status(reporter: &ProgressReporter) -> Result<(), HeadlessError> {
+    let stats = blobstore::stats(&registry::load_registry());
+    reporter.report_blob_stats(&stats);
+    Ok(())
+}
+
+/// Run `blobs gc` command: delete the blobs no registered download links to
+pub fn run_blobs_gc(dry_run: bool, reporter: &ProgressReporter) -> Result<(), HeadlessError> {
+    let (blobs, bytes) = blobstore::gc(&registry::load_registry(), dry_run);
+    reporter.report_blobs_removed(blobs, bytes, dry_run);
+    Ok(())
+}
+
+/// Run `delete` command: move a model's downloaded files (or one of them)
+/// to the trash and drop them from the registry
+pub fn run_delete(
+    model_id: &str,
+    file: Option<&str>,
+    dry_run: bool,
     reporter: &ProgressReporter,
-    download_tx: mpsc::UnboundedSender<DownloadMessage>,
-    progress_tx: mpsc::UnboundedSender<String>,
-    download_queue: Arc<tokio::sync::Mutex<QueueState>>,
-    download_progress: Arc<tokio::sync::Mutex<Option<DownloadProgress>>>,
-    verification_queue_size: Arc<AtomicUsize>,
-    verification_progress: Arc<tokio::sync::Mutex<Vec<VerificationProgress>>>,
-    shutdown_signal: Arc<tokio::sync::Mutex<bool>>,
 ) -> Result<(), HeadlessError> {
-    let incomplete = resume_downloads(download_tx, progress_tx).await?;
+    let reference = resolve_model_reference(model_id, reporter)?;
+    let file = file.map(str::to_string).or(reference.file_path);
+    let mut registry = registry::load_registry();
+    let deleted = trash::delete_downloads(
+        &mut registry,
+        |d| d.model_id == reference.model_id && file.as_ref().map_or(true, |f| &d.filename == f),
+        if dry_run {
+            trash::DeleteMode::DryRun
+        } else {
+            trash::DeleteMode::Trash
+        },
+    );
+    for error in &deleted.errors {
+        reporter.report_error(error);
+    }
+    if deleted.downloads.is_empty() && deleted.errors.is_empty() {
+        return Err(HeadlessError::DownloadError(format!(
+            "No downloads of {}{} in the registry",
+            reference.model_id,
+            file.map(|f| format!(" matching {}", f)).unwrap_or_default()
+        )));
+    }
 
-    if incomplete.is_empty() {
-        reporter.report_no_incomplete();
-        return Ok(());
+    if !dry_run {
+        registry::save_registry(&registry);
     }
+    let files: Vec<String> = deleted.downloads.into_iter().map(|d| d.filename).collect();
+    reporter.report_deleted(&reference.model_id, &files, deleted.bytes, dry_run);
+    Ok(())
+}
 
-    reporter.report_resume_summary(&incomplete);
+/// Run `restore` command
+pub fn run_restore(id: Option<&str>, reporter: &ProgressReporter) -> Result<(), HeadlessError> {
+    let entry = trash::restore(id)?;
+    reporter.report_restored(&entry);
+    Ok(())
+}
 
-    // Update queue state before downloads begin
-    {
-        let total_size: u64 = incomplete.iter().map(|d| d.total_size).sum();
-        let mut queue = download_queue.lock().await;
-        queue.add(incomplete.len(), total_size);
-    }
+/// Run `trash list` command
+pub fn run_trash_list(reporter: &ProgressReporter) -> Result<(), HeadlessError> {
+    reporter.report_trash(&trash::entries());
+    Ok(())
+}
 
-    // Wait for downloads to complete
-    wait_for_downloads(
-        download_queue,
-        download_progress,
-        reporter,
-        shutdown_signal.clone(),
-    )
-    .await?;
+/// Run `trash gc` command: permanently delete expired (or all) trash entries
+pub fn run_trash_gc(
+    all: bool,
+    dry_run: bool,
+    reporter: &ProgressReporter,
+) -> Result<(), HeadlessError> {
+    let (files, bytes) = trash::purge(all, dry_run);
+    reporter.report_trash_purged(files, bytes, dry_run);
+    Ok(())
+}
 
-    // Wait for verification to complete
-    wait_for_verification(
-        verification_queue_size,
-        verification_progress,
-        reporter,
-        shutdown_signal,
-    )
-    .await?;
+/// Run `evict` command: delete the least recently used models in the
+/// download directory until `keep_free` bytes are free, once confirmed
+pub fn run_evict(
+    keep_free: u64,
+    dir: Option<&str>,
+    yes: bool,
+    dry_run: bool,
+    reporter: &ProgressReporter,
+) -> Result<(), HeadlessError> {
+    let dir =
+        PathBuf::from(dir.map_or_else(|| config::load_config().default_directory, str::to_string));
+    let free = crate::utils::available_space(&dir).ok_or_else(|| {
+        HeadlessError::IoError(std::io::Error::other(format!(
+            "cannot read the free space of {}",
+            dir.display()
+        )))
+    })?;
+
+    let mut registry = registry::load_registry();
+    let models = evict::model_usage(&registry, &history::load_history(), &dir);
+    let plan = evict::plan(models, free, keep_free);
+    reporter.report_evict_plan(&dir, free, keep_free, &plan);
+    if plan.is_empty() || dry_run || !confirm_evict(&plan, yes, reporter)? {
+        return Ok(());
+    }
 
+    let evicted: std::collections::HashSet<&str> =
+        plan.iter().map(|m| m.model_id.as_str()).collect();
+    let deleted = trash::delete_downloads(
+        &mut registry,
+        |d| {
+            evicted.contains(d.model_id.as_str())
+                && std::path::Path::new(&d.local_path).starts_with(&dir)
+        },
+        trash::DeleteMode::Permanent,
+    );
+    for error in &deleted.errors {
+        reporter.report_error(error);
+    }
+    registry::save_registry(&registry);
+    reporter.report_evicted(
+        plan.len(),
+        deleted.bytes,
+        crate::utils::available_space(&dir).unwrap_or(free + deleted.bytes),
+    );
     Ok(())
 }
 
-/// Progress reporter for console output (text and JSON modes)
-pub struct ProgressReporter {
-    json_mode: bool,
+/// Ask on the terminal whether to delete the evicted models; needs `--yes`
+/// when nobody can answer
+fn confirm_evict(
+    plan: &[evict::ModelUsage],
+    yes: bool,
+    reporter: &ProgressReporter,
+) -> Result<bool, HeadlessError> {
+    use std::io::IsTerminal;
+
+    if yes {
+        return Ok(true);
+    }
+    if reporter.is_json() || !std::io::stdin().is_terminal() {
+        return Err(HeadlessError::ConfigError(
+            "evict asks for confirmation; pass --yes to run non-interactively".to_string(),
+        ));
+    }
+
+    print!(
+        "Permanently delete {} model{} ({})? [y/N] ",
+        plan.len(),
+        if plan.len() == 1 { "" } else { "s" },
+        format_file_size(plan.iter().map(|m| m.bytes).sum())
+    );
+    std::io::stdout().flush()?;
+
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer)?;
+    Ok(matches!(answer.trim(), "y" | "Y" | "yes" | "Yes"))
 }
 
-impl ProgressReporter {
-    pub fn new(json_mode: bool) -> Self {
-        Self { json_mode }
+/// Run `path` command: print where a downloaded model is, for use in shell
+/// substitutions like `llama-server -m $(rust-hf-downloader path MODEL --quant Q4_K_M)`
+pub fn run_path(
+    model_id: &str,
+    quant: Option<&str>,
+    reporter: &ProgressReporter,
+) -> Result<(), HeadlessError> {
+    let reference = resolve_model_reference(model_id, reporter)?;
+    let registry = registry::load_registry();
+    let downloads: Vec<&DownloadMetadata> = registry
+        .downloads
+        .iter()
+        .filter(|d| {
+            d.model_id == reference.model_id
+                && d.status == DownloadStatus::Complete
+                && reference
+                    .file_path
+                    .as_ref()
+                    .map_or(true, |f| &d.filename == f)
+                && (quant.is_none() || org_file_matches(&d.filename, &[], quant))
+                && std::path::Path::new(&d.local_path).exists()
+        })
+        .collect();
+
+    let path = local_model_path(&downloads).map_err(|reason| {
+        HeadlessError::DownloadError(format!(
+            "{}{}: {}",
+            reference.model_id,
+            quant.map(|q| format!(" ({})", q)).unwrap_or_default(),
+            reason
+        ))
+    })?;
+    reporter.report_path(&reference.model_id, &path);
+    Ok(())
+}
+
+/// The path `path` prints for a model's downloaded files: a lone file, the
+/// GGUF of the only quantization (its first part when split; mmproj files
+/// only when there is nothing else), or the model directory when there are
+/// no GGUFs
+fn local_model_path(downloads: &[&DownloadMetadata]) -> Result<PathBuf, String> {
+    if let [download] = downloads {
+        return Ok(PathBuf::from(&download.local_path));
+    }
+    if downloads.is_empty() {
+        return Err("not downloaded (no complete files in the registry)".to_string());
     }
 
-    #[allow(dead_code)]
-    pub fn report_search(&self, models: &[ModelInfo]) {
-        if self.json_mode {
-            println!("{}", serde_json::to_string(models).unwrap());
-        } else {
-            println!("Found {} models:", models.len());
-            for model in models {
-                println!(
-                    "  - {} ({} downloads, {} likes)",
-                    model.id, model.downloads, model.likes
-                );
+    let ggufs: Vec<&DownloadMetadata> = downloads
+        .iter()
+        .copied()
+        .filter(|d| d.filename.contains(".gguf"))
+        .collect();
+    if ggufs.is_empty() {
+        // Safetensors and other repositories are loaded by directory
+        let mut dir = PathBuf::from(&downloads[0].local_path);
+        dir.pop();
+        for download in downloads {
+            while !std::path::Path::new(&download.local_path).starts_with(&dir) {
+                dir.pop();
             }
         }
+        return Ok(dir);
     }
 
-    pub fn report_search_with_timing(&self, models: &[ModelInfo], elapsed: std::time::Duration) {
-        if self.json_mode {
-            let json = serde_json::json!({
-                "count": models.len(),
-                "query_time_seconds": elapsed.as_secs_f64(),
-                "results": models
-            });
-            println!("{}", serde_json::to_string_pretty(&json).unwrap());
-        } else {
-            println!(
-                "Found {} models in {:.2}s:",
-                models.len(),
-                elapsed.as_secs_f64()
-            );
-            println!();
+    let models: Vec<&DownloadMetadata> = match ggufs
+        .iter()
+        .copied()
+        .filter(|d| !file_name(&d.filename).to_lowercase().starts_with("mmproj"))
+        .collect::<Vec<_>>()
+    {
+        models if models.is_empty() => ggufs,
+        models => models,
+    };
+    let mut quants: Vec<String> = models
+        .iter()
+        .map(|d| file_quantization(&d.filename).unwrap_or_else(|| "unknown".to_string()))
+        .collect();
+    quants.sort();
+    quants.dedup();
+    if quants.len() > 1 {
+        return Err(format!(
+            "{} quantizations are downloaded ({}), pick one with --quant",
+            quants.len(),
+            quants.join(", ")
+        ));
+    }
 
-            if models.is_empty() {
-                println!("No models found matching your criteria.");
-                return;
-            }
+    // llama.cpp loads a split GGUF from its first part
+    models
+        .iter()
+        .filter(|d| api::parse_multipart_filename(&d.filename).map_or(true, |(part, _)| part == 1))
+        .min_by(|a, b| a.filename.cmp(&b.filename))
+        .map(|d| PathBuf::from(&d.local_path))
+        .ok_or_else(|| "the first part of the split file is not downloaded".to_string())
+}
 
-            // Calculate column widths
-            let max_id_width = models
-                .iter()
-                .map(|m| m.id.len())
-                .max()
-                .unwrap_or(40)
+/// Last component of a repository path
+fn file_name(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+/// Quantization of a repository file, from its name or else its folder
+fn file_quantization(path: &str) -> Option<String> {
+    api::extract_quantization_type(file_name(path)).or_else(|| {
+        path.rsplit('/')
+            .skip(1)
+            .find(|dir| api::is_quantization_directory(dir))
+            .map(api::extract_quantization_type_from_dirname)
+    })
+}
+
+/// Run `stats` command
+pub fn run_stats(days: usize, reporter: &ProgressReporter) -> Result<(), HeadlessError> {
+    let mut stats = history::compute_stats(&history::load_history(), 10);
+    stats.days.truncate(days);
+    reporter.report_stats(&stats);
+    Ok(())
+}
+
+/// Run `bench` command: time every [`bench::COMBINATIONS`] entry on the
+/// largest file of `model` and offer to save the best one in the config
+pub async fn run_bench(
+    model: Option<&str>,
+    yes: bool,
+    hf_token: Option<String>,
+    reporter: &ProgressReporter,
+) -> Result<(), HeadlessError> {
+    let model_id = model.unwrap_or(bench::DEFAULT_MODEL);
+    let token = hf_token.or(config::load_config().hf_token);
+    let target = bench::find_target(model_id, token.as_ref())
+        .await
+        .map_err(|e| HeadlessError::DownloadError(e.to_string()))?;
+    reporter.report_bench_start(&target);
+
+    let mut results = Vec::new();
+    for (run, (threads, chunk_size)) in bench::COMBINATIONS.into_iter().enumerate() {
+        let result = bench::run(&target, threads, chunk_size, run)
+            .await
+            .map_err(|e| HeadlessError::DownloadError(e.to_string()))?;
+        reporter.report_bench_result(&result);
+        results.push(result);
+    }
+
+    let Some(best) = bench::best(&results) else {
+        return Ok(());
+    };
+    let save = yes || confirm_bench_save(best, reporter)?;
+    if save {
+        let mut options = config::load_config();
+        options.concurrent_threads = best.threads;
+        options.min_chunk_size = best.chunk_size;
+        options.max_chunk_size = options.max_chunk_size.max(best.chunk_size);
+        config::save_config(&options).map_err(|e| HeadlessError::ConfigError(e.to_string()))?;
+    }
+    reporter.report_bench_summary(&target, &results, best, save);
+    Ok(())
+}
+
+/// Ask on the terminal whether to save the best settings; without a
+/// terminal (or with --json) they are only reported
+fn confirm_bench_save(
+    best: &bench::BenchResult,
+    reporter: &ProgressReporter,
+) -> Result<bool, HeadlessError> {
+    use std::io::IsTerminal;
+
+    if reporter.is_json() || !std::io::stdin().is_terminal() {
+        return Ok(false);
+    }
+    print!(
+        "Save concurrent_threads = {} and min_chunk_size = {} MB in the config? [y/N] ",
+        best.threads,
+        best.chunk_size / 1_048_576
+    );
+    std::io::stdout().flush()?;
+
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer)?;
+    Ok(matches!(answer.trim(), "y" | "Y" | "yes" | "Yes"))
+}
+
+/// Run `watch add` command
+pub async fn run_watch_add(
+    model_id: &str,
+    token: Option<&String>,
+    reporter: &ProgressReporter,
+) -> Result<(), HeadlessError> {
+    let reference = resolve_model_reference(model_id, reporter)?;
+    let watched = watchlist::snapshot(&reference.model_id, token).await?;
+    let (model_id, files) = (watched.model_id.clone(), watched.files.len());
+    watchlist::watch(watched)?;
+    reporter.report_watch_change(&model_id, true, files);
+    Ok(())
+}
+
+/// Run `watch remove` command
+pub fn run_watch_remove(model_id: &str, reporter: &ProgressReporter) -> Result<(), HeadlessError> {
+    let reference = resolve_model_reference(model_id, reporter)?;
+    if !watchlist::unwatch(&reference.model_id)? {
+        return Err(HeadlessError::ConfigError(format!(
+            "{} is not on the watchlist",
+            reference.model_id
+        )));
+    }
+    reporter.report_watch_change(&reference.model_id, false, 0);
+    Ok(())
+}
+
+/// Run `watch list` command
+pub fn run_watch_list(reporter: &ProgressReporter) -> Result<(), HeadlessError> {
+    reporter.report_watchlist(&watchlist::load_watchlist());
+    Ok(())
+}
+
+/// `<author>/*` (or a bare author) as an author entry, anything else as the
+/// model it refers to
+fn parse_ignore_target(
+    target: &str,
+    reporter: &ProgressReporter,
+) -> Result<ignorelist::IgnoredEntry, HeadlessError> {
+    let author = target.strip_suffix("/*").unwrap_or(target);
+    if !author.is_empty() && !author.contains('/') && !author.contains(':') {
+        return Ok(ignorelist::IgnoredEntry::Author(author.to_string()));
+    }
+    let reference = resolve_model_reference(target, reporter)?;
+    Ok(ignorelist::IgnoredEntry::Model(reference.model_id))
+}
+
+/// Run `ignore add` command
+pub fn run_ignore_add(target: &str, reporter: &ProgressReporter) -> Result<(), HeadlessError> {
+    let entry = parse_ignore_target(target, reporter)?;
+    ignorelist::ignore(entry.clone())?;
+    reporter.report_ignore_change(&entry, true);
+    Ok(())
+}
+
+/// Run `ignore remove` command
+pub fn run_ignore_remove(target: &str, reporter: &ProgressReporter) -> Result<(), HeadlessError> {
+    let entry = parse_ignore_target(target, reporter)?;
+    if !ignorelist::unignore(&entry)? {
+        return Err(HeadlessError::ConfigError(format!(
+            "{} is not hidden",
+            entry.label()
+        )));
+    }
+    reporter.report_ignore_change(&entry, false);
+    Ok(())
+}
+
+/// Run `ignore list` command
+pub fn run_ignore_list(reporter: &ProgressReporter) -> Result<(), HeadlessError> {
+    reporter.report_ignore_list(&ignorelist::load_ignore_list());
+    Ok(())
+}
+
+/// Run `watch check` command
+pub async fn run_watch_check(
+    token: Option<&String>,
+    reporter: &ProgressReporter,
+) -> Result<(), HeadlessError> {
+    let result = watchlist::check(token).await;
+    reporter.report_watch_check(&result);
+    Ok(())
+}
+
+/// Run `adopt` command: match the model folders under `dir` to Hub
+/// repositories and add their files to the registry as complete downloads
+pub async fn run_adopt(
+    dir: &std::path::Path,
+    model: Option<&str>,
+    check_hash: bool,
+    dry_run: bool,
+    hf_token: Option<String>,
+    reporter: &ProgressReporter,
+) -> Result<(), HeadlessError> {
+    if !dir.is_dir() {
+        return Err(HeadlessError::IoError(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("{} is not a directory", dir.display()),
+        )));
+    }
+    let token = hf_token.or(config::load_config().hf_token);
+
+    let mut folders = Vec::new();
+    for folder in adopt::find_folders(dir, model) {
+        folders.push(adopt::resolve(&folder, check_hash, token.as_ref()).await);
+    }
+    let mut registry = registry::load_registry();
+    let added = adopt::register(&mut registry, &mut folders);
+    if !dry_run && added > 0 {
+        registry::save_registry(&registry);
+    }
+    reporter.report_adopt(&folders, added, dry_run);
+    Ok(())
+}
+
+/// Run resume command with formatted output
+#[allow(clippy::too_many_arguments)]
+pub async fn run_resume(
+    reporter: &ProgressReporter,
+    download_tx: mpsc::UnboundedSender<DownloadMessage>,
+    progress_tx: mpsc::UnboundedSender<String>,
+    download_queue: Arc<tokio::sync::Mutex<QueueState>>,
+    download_progress: Arc<tokio::sync::Mutex<Option<DownloadProgress>>>,
+    verification_queue_size: Arc<AtomicUsize>,
+    verification_progress: Arc<tokio::sync::Mutex<Vec<VerificationProgress>>>,
+    shutdown_signal: Arc<tokio::sync::Mutex<bool>>,
+) -> Result<(), HeadlessError> {
+    // Files moved or resized since they completed are downloaded again
+    let mut registry = registry::load_registry();
+    registry::check_complete_files(&mut registry);
+    let pending = registry::get_incomplete_downloads(&registry);
+    if !pending.is_empty() {
+        let total_size = pending.iter().map(|d| d.total_size).sum();
+        confirm_locked_download(pending.len(), total_size, reporter)?;
+    }
+    let incomplete = resume_downloads(download_tx, progress_tx).await?;
+
+    if incomplete.is_empty() {
+        reporter.report_no_incomplete();
+        return Ok(());
+    }
+
+    reporter.report_resume_summary(&incomplete);
+
+    // Update queue state before downloads begin
+    {
+        let total_size: u64 = incomplete.iter().map(|d| d.total_size).sum();
+        let mut queue = download_queue.lock().await;
+        queue.add(incomplete.len(), total_size);
+    }
+
+    // Wait for downloads to complete
+    wait_for_downloads(
+        download_queue,
+        download_progress,
+        reporter,
+        shutdown_signal.clone(),
+    )
+    .await?;
+
+    // Wait for verification to complete
+    wait_for_verification(
+        verification_queue_size,
+        verification_progress,
+        reporter,
+        shutdown_signal,
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Download state the daemon works on and reports to its clients
+#[derive(Clone)]
+pub struct DaemonEngine {
+    pub download_tx: mpsc::UnboundedSender<DownloadMessage>,
+    pub download_rx: Arc<tokio::sync::Mutex<mpsc::UnboundedReceiver<DownloadMessage>>>,
+    pub download_queue: Arc<tokio::sync::Mutex<QueueState>>,
+    pub download_progress: Arc<tokio::sync::Mutex<Option<DownloadProgress>>>,
+    pub complete_downloads: Arc<tokio::sync::Mutex<CompleteDownloads>>,
+    pub verification_queue: Arc<tokio::sync::Mutex<Vec<VerificationQueueItem>>>,
+    pub verification_queue_size: Arc<AtomicUsize>,
+    pub verification_progress: Arc<tokio::sync::Mutex<Vec<VerificationProgress>>>,
+    pub progress_tx: mpsc::UnboundedSender<String>,
+    pub log: Arc<std::sync::Mutex<daemon::DaemonLog>>,
+    pub shutdown_signal: Arc<tokio::sync::Mutex<bool>>,
+}
+
+/// The daemon's engine plus what its request handler tracks
+#[cfg(unix)]
+#[derive(Clone)]
+struct DaemonState {
+    engine: DaemonEngine,
+    /// Downloads queued or in progress (model ID, filename), so a client
+    /// queueing one again (e.g. `resume`) does not fetch it twice
+    known: Arc<std::sync::Mutex<std::collections::HashSet<(String, String)>>>,
+    /// The batch being downloaded, then the download held for the next one,
+    /// saved with the queue if the daemon stops
+    active: Arc<std::sync::Mutex<Vec<DownloadMessage>>>,
+    /// Set by a `Stop` request
+    stop: Arc<std::sync::atomic::AtomicBool>,
+}
+
+#[cfg(unix)]
+impl DaemonState {
+    /// Queue the downloads the daemon does not have yet, returning how many
+    async fn enqueue(&self, messages: Vec<DownloadMessage>) -> usize {
+        let messages: Vec<_> = {
+            let Ok(mut known) = self.known.lock() else {
+                return 0;
+            };
+            messages
+                .into_iter()
+                .filter(|message| known.insert((message.0.clone(), message.1.clone())))
+                .collect()
+        };
+
+        let bytes = messages.iter().map(|message| message.5).sum();
+        self.engine
+            .download_queue
+            .lock()
+            .await
+            .add(messages.len(), bytes);
+        let count = messages.len();
+        for message in messages {
+            let _ = self.engine.download_tx.send(message);
+        }
+        count
+    }
+
+    async fn handle(self, request: daemon::DaemonRequest) -> daemon::DaemonResponse {
+        match request {
+            daemon::DaemonRequest::Enqueue {
+                downloads,
+                hf_token,
+            } => {
+                let messages = downloads
+                    .into_iter()
+                    .map(|queued| daemon::download_message(queued, hf_token.clone()))
+                    .collect();
+                let count = self.enqueue(messages).await;
+                if count > 0 {
+                    let _ = self
+                        .engine
+                        .progress_tx
+                        .send(format!("Queued {} download(s) from a client", count));
+                }
+                daemon::DaemonResponse::Queued { count }
+            }
+            daemon::DaemonRequest::Status { log_since } => {
+                let queue = self.engine.download_queue.lock().await.clone();
+                let (log, log_next) = match self.engine.log.lock() {
+                    Ok(log) => log.since(log_since),
+                    Err(_) => (Vec::new(), log_since),
+                };
+                daemon::DaemonResponse::Status(daemon::DaemonStatus {
+                    pid: std::process::id(),
+                    queued: queue.size,
+                    queued_bytes: queue.bytes,
+                    progress: self.engine.download_progress.lock().await.clone(),
+                    verifying: self
+                        .engine
+                        .verification_progress
+                        .lock()
+                        .await
+                        .iter()
+                        .map(Into::into)
+                        .collect(),
+                    verification_backlog: self
+                        .engine
+                        .verification_queue_size
+                        .load(Ordering::Relaxed),
+                    log,
+                    log_next,
+                })
+            }
+            daemon::DaemonRequest::Stop => {
+                self.stop.store(true, Ordering::Relaxed);
+                daemon::DaemonResponse::Stopping
+            }
+        }
+    }
+}
+
+/// Run the download daemon until `daemon stop` or a signal. It resumes the
+/// registry's incomplete downloads and the queue saved by its last run,
+/// downloads its queue one batch at a time and serves clients on the daemon
+/// socket. Downloads that have not started when it stops are saved for the
+/// next start.
+#[cfg(unix)]
+pub async fn run_daemon(
+    reporter: &ProgressReporter,
+    engine: DaemonEngine,
+) -> Result<(), HeadlessError> {
+    let listener = daemon::bind()?;
+    let state = DaemonState {
+        engine: engine.clone(),
+        known: Default::default(),
+        active: Default::default(),
+        stop: Default::default(),
+    };
+    reporter.report_info(&format!(
+        "Daemon running (PID {}), listening on {}",
+        std::process::id(),
+        daemon::get_socket_path().display()
+    ));
+
+    // Pick up where the last run stopped: incomplete files first, then the
+    // downloads that never started
+    let mut registry = registry::load_registry();
+    let saved_queue = std::mem::take(&mut registry.queue);
+    let mut messages: Vec<_> = registry
+        .downloads
+        .iter()
+        .filter(|d| d.status == DownloadStatus::Incomplete)
+        .map(resume_message)
+        .collect();
+    messages.extend(
+        saved_queue
+            .into_iter()
+            .map(|queued| daemon::download_message(queued, None)),
+    );
+    registry::save_registry(&registry);
+    let resumed = state.enqueue(messages).await;
+    if resumed > 0 {
+        let _ = engine
+            .progress_tx
+            .send(format!("Resumed {} download(s)", resumed));
+    }
+
+    // Download manager: one batch at a time, like the TUI's
+    let manager = {
+        let engine = engine.clone();
+        let known = state.known.clone();
+        let active = state.active.clone();
+        tokio::spawn(async move {
+            // The download that ended a small-file batch, next in line
+            let mut held = VecDeque::new();
+            loop {
+                let Some(batch) = crate::download::next_download_batch(
+                    &engine.download_rx,
+                    &mut held,
+                    |message| message.5,
+                )
+                .await
+                else {
+                    break; // Channel closed
+                };
+
+                {
+                    let mut queue = engine.download_queue.lock().await;
+                    queue.remove(batch.len(), batch.iter().map(|message| message.5).sum());
+                }
+                let count = batch.len();
+                if let Ok(mut active) = active.lock() {
+                    *active = batch.iter().chain(&held).cloned().collect();
+                }
+                let mut params: Vec<_> = batch
+                    .into_iter()
+                    .map(
+                        |(model_id, filename, path, hash, hf_token, total_size, revision)| {
+                            crate::download::DownloadParams {
+                                model_id,
+                                filename,
+                                base_path: path,
+                                progress: engine.download_progress.clone(),
+                                status_tx: engine.progress_tx.clone(),
+                                complete_downloads: engine.complete_downloads.clone(),
+                                expected_hash: hash,
+                                verification_queue: engine.verification_queue.clone(),
+                                verification_queue_size: engine.verification_queue_size.clone(),
+                                hf_token,
+                                revision,
+                                total_size,
+                            }
+                        },
+                    )
+                    .collect();
+                if params.len() == 1 {
+                    crate::download::start_download(params.remove(0)).await;
+                } else {
+                    crate::download::start_small_downloads(params).await;
+                }
+                if engine.download_queue.lock().await.is_empty() {
+                    crate::webhook::queue_drained(&engine.progress_tx);
+                }
+
+                let finished = active
+                    .lock()
+                    .map(|mut active| active.drain(..count).collect::<Vec<_>>());
+                if let (Ok(mut known), Ok(finished)) = (known.lock(), finished) {
+                    for message in finished {
+                        known.remove(&(message.0, message.1));
+                    }
+                }
+            }
+        })
+    };
+
+    let mut interval = tokio::time::interval(tokio::time::Duration::from_millis(200));
+    loop {
+        tokio::select! {
+            accepted = listener.accept() => {
+                if let Ok((stream, _)) = accepted {
+                    let state = state.clone();
+                    tokio::spawn(daemon::serve_connection(stream, |request| state.handle(request)));
+                }
+            }
+            _ = interval.tick() => {
+                if *engine.shutdown_signal.lock().await || state.stop.load(Ordering::Relaxed) {
+                    break;
+                }
+            }
+        }
+    }
+
+    // An interrupted download resumes from its .incomplete file next time
+    manager.abort();
+    let _ = manager.await;
+    let mut pending: Vec<_> = match state.active.lock() {
+        Ok(active) => active.iter().map(daemon::queued_download).collect(),
+        Err(_) => Vec::new(),
+    };
+    {
+        let mut rx = engine.download_rx.lock().await;
+        while let Ok(message) = rx.try_recv() {
+            pending.push(daemon::queued_download(&message));
+        }
+    }
+    let saved = pending.len();
+    let mut registry = registry::load_registry();
+    registry.queue = pending;
+    registry::save_registry(&registry);
+    let _ = std::fs::remove_file(daemon::get_socket_path());
+
+    reporter.report_info(&format!(
+        "Daemon stopped; {} queued download(s) saved for the next start",
+        saved
+    ));
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub async fn run_daemon(
+    _reporter: &ProgressReporter,
+    _engine: DaemonEngine,
+) -> Result<(), HeadlessError> {
+    Err(HeadlessError::ConfigError(
+        "the download daemon needs Unix domain sockets".to_string(),
+    ))
+}
+
+/// Log file of a daemon started with `--detach`
+fn daemon_log_path() -> PathBuf {
+    let home = std::env::var("HOME").unwrap_or_else(|_| "/tmp".to_string());
+    PathBuf::from(format!("{}/models/hf-downloader-daemon.log", home))
+}
+
+/// Status of the running daemon, or an error naming the missing daemon
+async fn daemon_status(log_since: u64) -> Result<daemon::DaemonStatus, HeadlessError> {
+    match daemon::request(&daemon::DaemonRequest::Status { log_since }).await {
+        Ok(daemon::DaemonResponse::Status(status)) => Ok(status),
+        Ok(response) => Err(HeadlessError::DownloadError(response.unexpected())),
+        Err(e) => Err(HeadlessError::DownloadError(format!(
+            "no download daemon is running ({})",
+            e
+        ))),
+    }
+}
+
+/// `daemon start --detach`, `attach`, `status` and `stop`: they talk to the
+/// daemon over its socket
+pub async fn run_daemon_client(
+    action: &DaemonAction,
+    reporter: &ProgressReporter,
+) -> Result<(), HeadlessError> {
+    match action {
+        DaemonAction::Start { .. } => start_detached_daemon(reporter).await,
+        DaemonAction::Attach => attach_daemon(reporter).await,
+        DaemonAction::Status => {
+            let status = daemon_status(u64::MAX).await?;
+            if reporter.is_json() {
+                println!("{}", serde_json::to_string(&status).unwrap_or_default());
+                return Ok(());
+            }
+            println!("Daemon PID {}", status.pid);
+            println!(
+                "Queued: {} file(s), {}",
+                status.queued,
+                format_file_size(status.queued_bytes)
+            );
+            match &status.progress {
+                Some(progress) => println!(
+                    "Downloading: {} ({} / {}, {:.2} MB/s)",
+                    progress.filename,
+                    format_file_size(progress.downloaded),
+                    format_file_size(progress.total),
+                    progress.speed_mbps
+                ),
+                None => println!("Downloading: nothing"),
+            }
+            println!("Verification backlog: {}", status.verification_backlog);
+            Ok(())
+        }
+        DaemonAction::Stop => match daemon::request(&daemon::DaemonRequest::Stop).await {
+            Ok(daemon::DaemonResponse::Stopping) => {
+                reporter.report_info("Daemon stopping; pending downloads are saved");
+                Ok(())
+            }
+            Ok(response) => Err(HeadlessError::DownloadError(response.unexpected())),
+            Err(e) => Err(HeadlessError::DownloadError(format!(
+                "no download daemon is running ({})",
+                e
+            ))),
+        },
+    }
+}
+
+/// Start the daemon as a background process of its own (outside the
+/// terminal's process group) and wait until it listens
+async fn start_detached_daemon(reporter: &ProgressReporter) -> Result<(), HeadlessError> {
+    if daemon::is_running().await {
+        reporter.report_info("The download daemon is already running");
+        return Ok(());
+    }
+
+    let log_path = daemon_log_path();
+    if let Some(parent) = log_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let log = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&log_path)?;
+
+    // Same global flags (token, TLS, user agent...), minus --detach
+    let args: Vec<String> = std::env::args()
+        .skip(1)
+        .filter(|arg| arg != "--detach")
+        .collect();
+    let mut command = std::process::Command::new(std::env::current_exe()?);
+    command
+        .args(args)
+        .stdin(std::process::Stdio::null())
+        .stdout(log.try_clone()?)
+        .stderr(log);
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        command.process_group(0);
+    }
+    let child = command.spawn()?;
+
+    for _ in 0..50 {
+        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+        if daemon::is_running().await {
+            reporter.report_info(&format!(
+                "Daemon started (PID {}), logging to {}",
+                child.id(),
+                log_path.display()
+            ));
+            return Ok(());
+        }
+    }
+    Err(HeadlessError::DownloadError(format!(
+        "the daemon did not start; see {}",
+        log_path.display()
+    )))
+}
+
+/// Print the daemon's log and progress until its queue is empty; Ctrl+C
+/// only detaches
+async fn attach_daemon(reporter: &ProgressReporter) -> Result<(), HeadlessError> {
+    let mut status = daemon_status(0).await?;
+    reporter.report_info(&format!("Attached to the daemon (PID {})", status.pid));
+    let mut interval = tokio::time::interval(daemon::STATUS_INTERVAL);
+
+    loop {
+        for line in &status.log {
+            if reporter.is_progress_bar() {
+                print!("\r\x1b[2K");
+            }
+            reporter.report_info(line);
+        }
+        if let Some(progress) = status.progress.as_ref().filter(|p| p.total > 0) {
+            reporter.report_download_progress(progress, progress.speed_mbps, status.queued_bytes);
+        }
+        if status.is_idle() {
+            if reporter.is_progress_bar() {
+                print!("\r\x1b[2K");
+            }
+            reporter.report_info("The daemon's queue is empty");
+            return Ok(());
+        }
+
+        tokio::select! {
+            _ = interval.tick() => {}
+            _ = tokio::signal::ctrl_c() => {
+                println!();
+                reporter.report_info("Detached; the daemon keeps downloading");
+                return Ok(());
+            }
+        }
+        status = daemon_status(status.log_next).await?;
+    }
+}
+
+/// Progress reporter for console output (text and JSON modes)
+pub struct ProgressReporter {
+    json_mode: bool,
+    progress_mode: ProgressMode,
+    /// Percent step between lines in plain progress mode
+    progress_step: u32,
+    /// Percent bucket of the last plain progress line, per kind + filename
+    last_plain: std::sync::Mutex<HashMap<String, u32>>,
+}
+
+impl ProgressReporter {
+    pub fn new(json_mode: bool) -> Self {
+        Self {
+            json_mode,
+            progress_mode: if json_mode {
+                ProgressMode::Json
+            } else {
+                ProgressMode::Bar
+            },
+            progress_step: 10,
+            last_plain: std::sync::Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Override how download and verification progress is reported
+    pub fn with_progress(mut self, mode: ProgressMode, step: u32) -> Self {
+        self.progress_mode = mode;
+        self.progress_step = step.max(1);
+        self
+    }
+
+    /// Whether progress is drawn as an in-place bar (needs a trailing newline)
+    pub fn is_progress_bar(&self) -> bool {
+        self.progress_mode == ProgressMode::Bar
+    }
+
+    /// In plain mode, whether `percent` reached a new step for this file
+    fn plain_step_reached(&self, kind: &str, filename: &str, percent: u32) -> bool {
+        let key = format!("{}:{}", kind, filename);
+        let bucket = percent / self.progress_step;
+        let mut last = self.last_plain.lock().unwrap_or_else(|e| e.into_inner());
+        match last.get(&key) {
+            Some(last_bucket) if *last_bucket >= bucket => false,
+            _ => {
+                last.insert(key, bucket);
+                true
+            }
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn report_search(&self, models: &[ModelInfo]) {
+        if self.json_mode {
+            println!("{}", serde_json::to_string(models).unwrap());
+        } else {
+            println!("Found {} models:", models.len());
+            for model in models {
+                println!(
+                    "  - {} ({} downloads, {} likes)",
+                    model.id, model.downloads, model.likes
+                );
+            }
+        }
+    }
+
+    pub fn report_search_with_timing(
+        &self,
+        models: &[ModelInfo],
+        elapsed: std::time::Duration,
+        next_cursor: Option<&str>,
+    ) {
+        if self.json_mode {
+            let json = serde_json::json!({
+                "count": models.len(),
+                "query_time_seconds": elapsed.as_secs_f64(),
+                "next_cursor": next_cursor,
+                "results": models
+            });
+            println!("{}", serde_json::to_string_pretty(&json).unwrap());
+        } else {
+            println!(
+                "Found {} models in {:.2}s:",
+                models.len(),
+                elapsed.as_secs_f64()
+            );
+            println!();
+
+            if models.is_empty() {
+                println!("No models found matching your criteria.");
+                return;
+            }
+
+            // Calculate column widths
+            let max_id_width = models
+                .iter()
+                .map(|m| m.id.len())
+                .max()
+                .unwrap_or(40)
                 .min(60);
 
-            // Print header
+            // Print header
+            println!(
+                "{:<width$} | {:>12} | {:>10} | Last Modified",
+                "Model",
+                "Downloads",
+                "Likes",
+                width = max_id_width
+            );
+            println!(
+                "{:-<width$}-+-{:-<12}-+-{:-<10}-+---------------",
+                "----",
+                "------------",
+                "----------",
+                width = max_id_width
+            );
+
+            // Print each model
+            for model in models {
+                let last_mod = model.last_modified.as_deref().unwrap_or("N/A");
+                println!(
+                    "{:<width$} | {:>12} | {:>10} | {}{}{}",
+                    model.id,
+                    model.downloads,
+                    model.likes,
+                    last_mod,
+                    if model.private { " (private)" } else { "" },
+                    model.used_storage.map_or_else(String::new, |bytes| format!(
+                        " [{}]",
+                        crate::utils::format_size(bytes)
+                    )),
+                    width = max_id_width
+                );
+            }
+
+            if let Some(cursor) = next_cursor {
+                println!();
+                println!("More results: --cursor '{}'", cursor);
+            }
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn report_download_start(&self, filename: &str, total_size: u64) {
+        if self.json_mode {
+            let json = serde_json::json!({
+                "status": "starting",
+                "filename": filename,
+                "size": total_size
+            });
+            println!("{}", json);
+        } else {
+            println!("Downloading: {} ({} MB)", filename, total_size / 1_048_576);
+        }
+    }
+
+    /// `queued_bytes` is the size of the downloads waiting behind this one,
+    /// for the queue's ETA
+    pub fn report_download_progress(
+        &self,
+        progress: &DownloadProgress,
+        speed_mbps: f64,
+        queued_bytes: u64,
+    ) {
+        let (downloaded, total) = (progress.downloaded, progress.total);
+        let name = crate::utils::progress_name(
+            &progress.model_id,
+            &progress.filename,
+            PROGRESS_MODEL_CHARS,
+        );
+        match self.progress_mode {
+            ProgressMode::None => return,
+            ProgressMode::Plain => {
+                let percent = if total > 0 {
+                    (downloaded as f64 / total as f64 * 100.0) as u32
+                } else {
+                    0
+                };
+                if self.plain_step_reached("download", &name, percent) {
+                    println!(
+                        "Downloading {}: {}% ({:.2} MB/s)",
+                        name, percent, speed_mbps
+                    );
+                }
+                return;
+            }
+            ProgressMode::Json | ProgressMode::Bar => {}
+        }
+
+        if self.progress_mode == ProgressMode::Json {
+            let json = serde_json::json!({
+                "status": "downloading",
+                "model_id": progress.model_id,
+                "filename": progress.filename,
+                "progress": (downloaded as f64 / total as f64 * 100.0),
+                "speed_mbps": speed_mbps,
+                "eta_seconds": progress.eta_secs(0),
+                "queue_eta_seconds": progress.eta_secs(queued_bytes)
+            });
+            println!("{}", json);
+        } else {
+            let percent = (downloaded as f64 / total as f64 * 100.0) as u32;
+            let bar_width = 40;
+            let filled = (percent as f32 / 100.0 * bar_width as f32) as usize;
+            let bar: String = "=".repeat(filled) + &" ".repeat(bar_width - filled);
+            let eta_str = progress
+                .eta_secs(0)
+                .map(|secs| format!(" ETA {}", crate::utils::format_duration(secs)))
+                .unwrap_or_default();
+            print!(
+                "\r[{}] {}% ({:.2} MB/s){} - {}",
+                bar, percent, speed_mbps, eta_str, name
+            );
+            let _ = std::io::stdout().flush();
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn report_download_complete(&self, filename: &str) {
+        if self.json_mode {
+            let json = serde_json::json!({
+                "status": "complete",
+                "filename": filename
+            });
+            println!("{}", json);
+        } else {
+            println!("\n✓ Complete: {}", filename);
+        }
+    }
+
+    /// Report every file currently being verified
+    ///
+    /// The progress bar shows one combined line for parallel verifications;
+    /// plain and JSON output get a row per file.
+    pub fn report_verifications(&self, active: &[VerificationProgress]) {
+        if self.progress_mode == ProgressMode::Bar && active.len() > 1 {
+            let verified = active
+                .iter()
+                .map(|p| p.verified_bytes.load(Ordering::Relaxed))
+                .sum();
+            let total = active.iter().map(|p| p.total_bytes).sum();
+            let speed_mbps = active.iter().map(|p| p.speed_mbps).sum();
+            let label = format!("{} files", active.len());
+            self.report_verification_progress("", &label, verified, total, speed_mbps);
+            return;
+        }
+
+        for progress in active {
+            self.report_verification_progress(
+                &progress.model_id,
+                &progress.filename,
+                progress.verified_bytes.load(Ordering::Relaxed),
+                progress.total_bytes,
+                progress.speed_mbps,
+            );
+        }
+    }
+
+    /// `model_id` is empty for the combined line of parallel verifications
+    pub fn report_verification_progress(
+        &self,
+        model_id: &str,
+        filename: &str,
+        verified: u64,
+        total: u64,
+        speed_mbps: f64,
+    ) {
+        let name = crate::utils::progress_name(model_id, filename, PROGRESS_MODEL_CHARS);
+        match self.progress_mode {
+            ProgressMode::None => return,
+            ProgressMode::Plain => {
+                let percent = if total > 0 {
+                    (verified as f64 / total as f64 * 100.0) as u32
+                } else {
+                    0
+                };
+                if self.plain_step_reached("verify", &name, percent) {
+                    println!("Verifying {}: {}% ({:.2} MB/s)", name, percent, speed_mbps);
+                }
+                return;
+            }
+            ProgressMode::Json | ProgressMode::Bar => {}
+        }
+
+        if self.progress_mode == ProgressMode::Json {
+            let eta_seconds = if speed_mbps > 0.0 && total > verified {
+                Some((total - verified) as f64 / (speed_mbps * 1_048_576.0))
+            } else {
+                None
+            };
+            let json = serde_json::json!({
+                "status": "verifying",
+                "model_id": model_id,
+                "filename": filename,
+                "progress": (verified as f64 / total as f64 * 100.0),
+                "speed_mbps": speed_mbps,
+                "eta_seconds": eta_seconds,
+            });
+            println!("{}", json);
+        } else {
+            let percent = if total > 0 {
+                (verified as f64 / total as f64 * 100.0) as u32
+            } else {
+                0
+            };
+            let bar_width = 40;
+            let filled = (percent as f32 / 100.0 * bar_width as f32) as usize;
+            let bar: String = "=".repeat(filled) + &" ".repeat(bar_width - filled);
+
+            // Calculate ETA
+            let eta_str = if speed_mbps > 0.0 && total > verified {
+                let remaining_bytes = total - verified;
+                let eta_secs = (remaining_bytes as f64 / (speed_mbps * 1_048_576.0)) as u64;
+                if eta_secs >= 3600 {
+                    format!(" ETA {}h {}m", eta_secs / 3600, (eta_secs % 3600) / 60)
+                } else if eta_secs >= 60 {
+                    format!(" ETA {}m {}s", eta_secs / 60, eta_secs % 60)
+                } else {
+                    format!(" ETA {}s", eta_secs)
+                }
+            } else {
+                String::new()
+            };
+
+            print!(
+                "\r[{}] {}% ({:.2} MB/s){} verifying - {}",
+                bar, percent, speed_mbps, eta_str, name
+            );
+            let _ = std::io::stdout().flush();
+        }
+    }
+
+    /// Report whether each split model file has all of its parts on disk
+    pub fn report_part_sets(&self, sets: &[crate::multipart::PartSet]) {
+        for set in sets {
+            if self.json_mode {
+                let json = serde_json::json!({
+                    "status": if set.is_complete() { "parts_complete" } else { "parts_missing" },
+                    "file": set.dir.join(&set.base_name),
+                    "parts_present": set.present(),
+                    "parts_total": set.total(),
+                    "missing_parts": set.missing(),
+                    "merge_command": set.merge_command(),
+                });
+                println!("{}", json);
+            } else if let Some(command) = set.merge_command() {
+                println!(
+                    "✓ All {} parts of {} present. To merge into a single file:",
+                    set.total(),
+                    set.base_name
+                );
+                println!("  {}", command);
+            } else {
+                let missing: Vec<String> = set.missing().iter().map(|n| n.to_string()).collect();
+                eprintln!(
+                    "Warning: {} is missing part{} {} of {} (in {})",
+                    set.base_name,
+                    if missing.len() == 1 { "" } else { "s" },
+                    missing.join(", "),
+                    set.total(),
+                    set.dir.display()
+                );
+            }
+        }
+    }
+
+    pub fn report_error(&self, error: &str) {
+        if self.json_mode {
+            let json = serde_json::json!({
+                "status": "error",
+                "error": error
+            });
+            eprintln!("{}", json);
+        } else {
+            eprintln!("Error: {}", error);
+        }
+    }
+
+    pub fn report_info(&self, message: &str) {
+        if self.json_mode {
+            let json = serde_json::json!({
+                "status": "info",
+                "message": message
+            });
+            println!("{}", json);
+        } else {
+            println!("{}", message);
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn report_list_quantizations(
+        &self,
+        quantizations: &[QuantizationGroup],
+        metadata: &ModelMetadata,
+    ) {
+        if self.json_mode {
+            // Simplified JSON output without full serialization
+            println!("{{");
+            println!("  \"model_id\": \"{}\",", metadata.model_id);
+            println!("  \"quantizations\": [");
+            for (i, quant) in quantizations.iter().enumerate() {
+                if i > 0 {
+                    println!(",");
+                }
+                println!("    {{");
+                println!("      \"quant_type\": \"{}\",", quant.quant_type);
+                if let Some(revision) = quant.files.first().and_then(|f| f.revision.as_ref()) {
+                    println!("      \"revision\": \"{}\",", revision);
+                }
+                println!("      \"total_size\": {},", quant.total_size);
+                println!("      \"file_count\": {}", quant.files.len());
+                print!("      \"files\": [");
+                for (j, file) in quant.files.iter().enumerate() {
+                    if j > 0 {
+                        print!(", ");
+                    }
+                    print!("\"{}\"", file.filename);
+                }
+                println!("]");
+                print!("    }}");
+            }
+            println!();
+            println!("  ]");
+            println!("}}");
+        } else {
+            println!("Model: {}", metadata.model_id);
+            println!("\nQuantizations:");
+            for quant in quantizations {
+                println!(
+                    "  - {} ({} files, {} MB total)",
+                    quant.quant_type,
+                    quant.files.len(),
+                    quant.total_size / 1_048_576
+                );
+                for file in &quant.files {
+                    println!("      - {} ({} MB)", file.filename, file.size / 1_048_576);
+                }
+            }
+
+            // Show file tree for non-GGUF models
+            if !quantizations.is_empty() {
+                println!("\nFile Tree:");
+                let file_tree = api::build_file_tree(metadata.siblings.clone(), &metadata.weight_shards);
+                print_tree_node(&file_tree, 0);
+            }
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn report_resume(&self, resumed: &[DownloadMetadata]) {
+        if self.json_mode {
+            let json = serde_json::json!({
+                "status": "resumed",
+                "count": resumed.len(),
+                "downloads": resumed
+            });
+            println!("{}", json);
+        } else if resumed.is_empty() {
+            self.report_info("No incomplete downloads to resume");
+        } else {
+            println!("Resumed {} downloads:", resumed.len());
+            for download in resumed {
+                println!("  - {}", download.filename);
+            }
+        }
+    }
+
+    /// Print `pick` candidates, one per line (never JSON: pickers read lines)
+    pub fn report_pick_candidates(&self, lines: &[String]) {
+        for line in lines {
+            println!("{}", line);
+        }
+    }
+
+    /// Print `--format` records; the format decides the layout, not `--json`
+    pub fn report_records(&self, format: &RecordFormat, fields: &[&str], records: &[Record]) {
+        print!("{}", format.render(fields, records));
+    }
+
+    pub fn report_org_plan(&self, org: &str, plans: &[OrgRepoPlan], speed_mbps: Option<f64>) {
+        let file_count: usize = plans.iter().map(|p| p.files.len()).sum();
+        let total_size: u64 = plans.iter().map(OrgRepoPlan::total_size).sum();
+
+        if self.json_mode {
+            let repos: Vec<serde_json::Value> = plans
+                .iter()
+                .map(|plan| {
+                    serde_json::json!({
+                        "model_id": plan.model_id,
+                        "file_count": plan.files.len(),
+                        "total_size_bytes": plan.total_size(),
+                        "files": plan.files.iter().map(|f| &f.path).collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+            let json = serde_json::json!({
+                "status": "planned",
+                "org": org,
+                "repo_count": plans.len(),
+                "file_count": file_count,
+                "total_size_bytes": total_size,
+                "recent_speed_mbps": speed_mbps,
+                "eta_seconds": speed_mbps.map(|speed| history::eta_secs(total_size, speed)),
+                "repos": repos
+            });
+            println!("{}", serde_json::to_string_pretty(&json).unwrap());
+            return;
+        }
+
+        if plans.is_empty() {
+            println!("No files of {}'s models match the filters.", org);
+            return;
+        }
+
+        let width = plans
+            .iter()
+            .map(|p| p.model_id.len())
+            .max()
+            .unwrap_or(0)
+            .min(60);
+        println!("Matching files in {}'s models:", org);
+        println!();
+        for plan in plans {
             println!(
-                "{:<width$} | {:>12} | {:>10} | Last Modified",
-                "Model",
-                "Downloads",
-                "Likes",
-                width = max_id_width
+                "  {:<width$}  {:>4} file{}  {:>10}",
+                plan.model_id,
+                plan.files.len(),
+                if plan.files.len() == 1 { " " } else { "s" },
+                format_file_size(plan.total_size()),
+                width = width
             );
+        }
+        println!();
+        println!(
+            "  Total: {} files in {} repositories, {}",
+            file_count,
+            plans.len(),
+            format_file_size(total_size)
+        );
+        if let Some(eta) = speed_mbps.map(|speed| history::format_eta(total_size, speed)) {
+            println!("  Estimated time: {}", eta);
+        }
+        println!();
+    }
+
+    pub fn report_download_summary(
+        &self,
+        files: &[String],
+        total_size: u64,
+        license: &LicenseNotice,
+    ) {
+        if self.json_mode {
+            let json = serde_json::json!({
+                "status": "queued",
+                "file_count": files.len(),
+                "total_size_bytes": total_size,
+                "license": license.license,
+                "license_terms": license.terms(),
+                "files": files
+            });
+            println!("{}", serde_json::to_string_pretty(&json).unwrap());
+        } else {
+            println!("Download Summary:");
+            println!("  Files: {}", files.len());
+            println!("  Total Size: {}", format_file_size(total_size));
+            println!("  License: {}", license);
+            println!();
+
+            if files.len() <= 10 {
+                for file in files {
+                    println!("  - {}", file);
+                }
+            } else {
+                for file in files.iter().take(5) {
+                    println!("  - {}", file);
+                }
+                println!("  ... and {} more", files.len() - 5);
+            }
+            println!();
+        }
+    }
+
+    pub fn report_dry_run_summary(
+        &self,
+        files: &[String],
+        total_size: u64,
+        output_dir: &str,
+        is_gguf: bool,
+        license: &LicenseNotice,
+        speed_mbps: Option<f64>,
+    ) {
+        if self.json_mode {
+            let json = serde_json::json!({
+                "status": "dry_run",
+                "model_type": if is_gguf { "GGUF" } else { "Non-GGUF" },
+                "file_count": files.len(),
+                "total_size_bytes": total_size,
+                "recent_speed_mbps": speed_mbps,
+                "eta_seconds": speed_mbps.map(|speed| history::eta_secs(total_size, speed)),
+                "output_directory": output_dir,
+                "license": license.license,
+                "license_terms": license.terms(),
+                "license_permissive": license.is_permissive(),
+                "files": files
+            });
+            println!("{}", serde_json::to_string_pretty(&json).unwrap());
+        } else {
+            println!("Download Plan:");
             println!(
-                "{:-<width$}-+-{:-<12}-+-{:-<10}-+---------------",
-                "----",
-                "------------",
-                "----------",
-                width = max_id_width
+                "  Model type: {}",
+                if is_gguf { "GGUF" } else { "Non-GGUF" }
             );
+            println!("  License: {}", license);
+            println!("  Files to download: {}", files.len());
+            println!("  Total size: {}", format_file_size(total_size));
+            let eta = match speed_mbps {
+                Some(speed) => history::format_eta(total_size, speed),
+                None => "unknown (no download history yet)".to_string(),
+            };
+            println!("  Estimated time: {}", eta);
+            println!("  Output directory: {}", output_dir);
+            println!();
 
-            // Print each model
-            for model in models {
-                let last_mod = model.last_modified.as_deref().unwrap_or("N/A");
+            println!("Files:");
+            for (i, file) in files.iter().enumerate() {
+                println!("  {}. {}", i + 1, file);
+            }
+            println!();
+
+            println!("Run without --dry-run to execute the download.");
+        }
+    }
+
+    pub fn report_no_incomplete(&self) {
+        if self.json_mode {
+            let json = serde_json::json!({
+                "status": "no_incomplete",
+                "message": "No incomplete downloads found"
+            });
+            println!("{}", serde_json::to_string_pretty(&json).unwrap());
+        } else {
+            println!("No incomplete downloads found.");
+        }
+    }
+
+    pub fn report_adopt(&self, folders: &[adopt::AdoptedFolder], added: usize, dry_run: bool) {
+        if self.json_mode {
+            let json = serde_json::json!({
+                "status": if dry_run { "dry_run" } else { "adopted" },
+                "added": added,
+                "folders": folders
+            });
+            println!("{}", serde_json::to_string_pretty(&json).unwrap());
+            return;
+        }
+
+        if folders.is_empty() {
+            println!("No model folders found.");
+            return;
+        }
+        for folder in folders {
+            let Some(model_id) = &folder.model_id else {
                 println!(
-                    "{:<width$} | {:>12} | {:>10} | {}",
-                    model.id,
-                    model.downloads,
-                    model.likes,
-                    last_mod,
-                    width = max_id_width
+                    "{}: no repository matches its {} file(s)",
+                    folder.dir,
+                    folder.unmatched.len()
                 );
+                continue;
+            };
+            let commit = folder
+                .commit
+                .as_deref()
+                .map(|commit| format!(" (commit {})", crate::utils::short_commit(commit)))
+                .unwrap_or_default();
+            println!(
+                "{}: {}{}, {} new file(s), {} already registered",
+                folder.dir,
+                model_id,
+                commit,
+                folder.files.len() - folder.already_known,
+                folder.already_known
+            );
+            for file in &folder.unmatched {
+                println!("  No match in the repository: {}", file);
             }
         }
+        println!();
+        if dry_run {
+            println!("Dry run: {} file(s) would be added to the registry", added);
+        } else {
+            println!("Added {} file(s) to the registry", added);
+        }
     }
 
-    #[allow(dead_code)]
-    pub fn report_download_start(&self, filename: &str, total_size: u64) {
+    pub fn report_cache_cleared(&self, entries: usize, bytes: u64) {
         if self.json_mode {
             let json = serde_json::json!({
-                "status": "starting",
-                "filename": filename,
-                "size": total_size
+                "status": "cache_cleared",
+                "entries": entries,
+                "bytes": bytes
             });
-            println!("{}", json);
+            println!("{}", serde_json::to_string_pretty(&json).unwrap());
         } else {
-            println!("Downloading: {} ({} MB)", filename, total_size / 1_048_576);
+            println!(
+                "Cleared {} cached API response(s) ({})",
+                entries,
+                format_file_size(bytes)
+            );
         }
     }
 
-    pub fn report_download_progress(
+    pub fn report_blob_stats(&self, stats: &blobstore::StoreStats) {
+        if self.json_mode {
+            println!("{}", serde_json::to_string_pretty(stats).unwrap());
+            return;
+        }
+
+        match stats.directories.as_slice() {
+            [] => println!("Blob store: empty"),
+            [dir] => println!("Blob store: {}", dir.display()),
+            dirs => {
+                println!("Blob stores:");
+                for dir in dirs {
+                    println!("  {}", dir.display());
+                }
+            }
+        }
+        println!(
+            "  {} blob(s), {}",
+            stats.blobs,
+            format_file_size(stats.bytes)
+        );
+        println!(
+            "  {} download(s) link to them ({} as separate files, {} saved)",
+            stats.links,
+            format_file_size(stats.linked_bytes),
+            format_file_size(
+                stats
+                    .linked_bytes
+                    .saturating_sub(stats.bytes - stats.unreferenced_bytes)
+            )
+        );
+        if stats.unreferenced > 0 {
+            println!(
+                "  {} blob(s) ({}) no longer linked, removed by `blobs gc`",
+                stats.unreferenced,
+                format_file_size(stats.unreferenced_bytes)
+            );
+        }
+    }
+
+    pub fn report_blobs_removed(&self, blobs: usize, bytes: u64, dry_run: bool) {
+        if self.json_mode {
+            let json = serde_json::json!({
+                "status": if dry_run { "dry_run" } else { "blobs_removed" },
+                "blobs": blobs,
+                "bytes": bytes
+            });
+            println!("{}", serde_json::to_string_pretty(&json).unwrap());
+        } else if dry_run {
+            println!(
+                "Dry run: {} unlinked blob(s) ({}) would be removed",
+                blobs,
+                format_file_size(bytes)
+            );
+        } else {
+            println!(
+                "Removed {} unlinked blob(s) ({})",
+                blobs,
+                format_file_size(bytes)
+            );
+        }
+    }
+
+    pub fn report_evict_plan(
         &self,
-        filename: &str,
-        downloaded: u64,
-        total: u64,
-        speed_mbps: f64,
+        dir: &std::path::Path,
+        free: u64,
+        keep_free: u64,
+        plan: &[evict::ModelUsage],
     ) {
+        let freed: u64 = plan.iter().map(|m| m.bytes).sum();
         if self.json_mode {
             let json = serde_json::json!({
-                "status": "downloading",
-                "filename": filename,
-                "progress": (downloaded as f64 / total as f64 * 100.0),
-                "speed_mbps": speed_mbps
+                "status": "evict_plan",
+                "directory": dir,
+                "free_bytes": free,
+                "keep_free_bytes": keep_free,
+                "freed_bytes": freed,
+                "models": plan
             });
-            println!("{}", json);
-        } else {
-            let percent = (downloaded as f64 / total as f64 * 100.0) as u32;
-            let bar_width = 40;
-            let filled = (percent as f32 / 100.0 * bar_width as f32) as usize;
-            let bar: String = "=".repeat(filled) + &" ".repeat(bar_width - filled);
-            print!(
-                "\r[{}] {}% ({:.2} MB/s) - {}",
-                bar, percent, speed_mbps, filename
+            println!("{}", serde_json::to_string_pretty(&json).unwrap());
+            return;
+        }
+
+        println!(
+            "{} free on the disk of {}, {} wanted",
+            format_file_size(free),
+            dir.display(),
+            format_file_size(keep_free)
+        );
+        if plan.is_empty() {
+            println!("Nothing to evict");
+            return;
+        }
+        println!("Least recently used models to delete:");
+        for m in plan {
+            println!(
+                "  {:>10}  {}  ({} file(s), {} {} day(s) ago)",
+                format_file_size(m.bytes),
+                m.model_id,
+                m.files,
+                m.last_use.label(),
+                m.idle_days()
             );
-            let _ = std::io::stdout().flush();
+        }
+        if free + freed < keep_free {
+            println!(
+                "Deleting all of them frees {}, still short of {} free",
+                format_file_size(freed),
+                format_file_size(keep_free)
+            );
+        } else {
+            println!("Deleting them frees {}", format_file_size(freed));
         }
     }
 
-    #[allow(dead_code)]
-    pub fn report_download_complete(&self, filename: &str) {
+    pub fn report_evicted(&self, models: usize, bytes: u64, free: u64) {
         if self.json_mode {
             let json = serde_json::json!({
-                "status": "complete",
-                "filename": filename
+                "status": "evicted",
+                "models": models,
+                "bytes": bytes,
+                "free_bytes": free
             });
-            println!("{}", json);
+            println!("{}", serde_json::to_string_pretty(&json).unwrap());
         } else {
-            println!("\n✓ Complete: {}", filename);
+            println!(
+                "Deleted {} model(s) ({}), {} free now",
+                models,
+                format_file_size(bytes),
+                format_file_size(free)
+            );
         }
     }
 
-    pub fn report_verification_progress(
+    pub fn report_over_quota(
         &self,
-        filename: &str,
-        verified: u64,
-        total: u64,
-        speed_mbps: f64,
+        usage: &quota::QuotaUsage,
+        download_bytes: u64,
+        candidates: &[quota::CleanupCandidate],
     ) {
         if self.json_mode {
-            let eta_seconds = if speed_mbps > 0.0 && total > verified {
-                Some((total - verified) as f64 / (speed_mbps * 1_048_576.0))
-            } else {
-                None
-            };
             let json = serde_json::json!({
-                "status": "verifying",
-                "filename": filename,
-                "progress": (verified as f64 / total as f64 * 100.0),
-                "speed_mbps": speed_mbps,
-                "eta_seconds": eta_seconds,
+                "status": "over_quota",
+                "quota": usage,
+                "download_bytes": download_bytes,
+                "excess_bytes": usage.excess(download_bytes),
+                "candidates": candidates
             });
-            println!("{}", json);
+            println!("{}", serde_json::to_string_pretty(&json).unwrap());
+            return;
+        }
+
+        println!(
+            "Warning: this download puts {} {} over its {} quota ({} used)",
+            usage.directory.display(),
+            format_file_size(usage.excess(download_bytes)),
+            format_file_size(usage.max_bytes),
+            format_file_size(usage.used_bytes)
+        );
+        if candidates.is_empty() {
+            return;
+        }
+        println!("Downloaded models that would make room (delete <MODEL_ID>):");
+        for c in candidates {
+            println!(
+                "  {:>10}  {}  ({} file(s), {} day(s) old{})",
+                format_file_size(c.bytes),
+                c.model_id,
+                c.files,
+                c.age_days(),
+                if c.unverified { ", unverified" } else { "" }
+            );
+        }
+    }
+
+    /// Report the local path of a downloaded model: the bare path, so shells
+    /// can substitute it
+    pub fn report_path(&self, model_id: &str, path: &std::path::Path) {
+        if self.json_mode {
+            let json = serde_json::json!({
+                "model_id": model_id,
+                "path": path
+            });
+            println!("{}", serde_json::to_string_pretty(&json).unwrap());
         } else {
-            let percent = if total > 0 {
-                (verified as f64 / total as f64 * 100.0) as u32
-            } else {
-                0
-            };
-            let bar_width = 40;
-            let filled = (percent as f32 / 100.0 * bar_width as f32) as usize;
-            let bar: String = "=".repeat(filled) + &" ".repeat(bar_width - filled);
+            println!("{}", path.display());
+        }
+    }
+
+    /// Report the download root the placement rules picked
+    pub fn report_placement(&self, placed: &placement::Placement) {
+        if self.json_mode {
+            let json = serde_json::json!({
+                "status": "placed",
+                "placement": placed
+            });
+            println!("{}", serde_json::to_string_pretty(&json).unwrap());
+            return;
+        }
+
+        println!(
+            "Downloading to {} (placement rule {}: {})",
+            placed.directory, placed.rule, placed.reason
+        );
+    }
+
+    pub fn report_deleted(&self, model_id: &str, files: &[String], bytes: u64, dry_run: bool) {
+        let trashed = trash::retention_days() > 0;
+        if self.json_mode {
+            let json = serde_json::json!({
+                "status": if dry_run { "dry_run" } else if trashed { "trashed" } else { "deleted" },
+                "model_id": model_id,
+                "files": files,
+                "bytes": bytes
+            });
+            println!("{}", serde_json::to_string_pretty(&json).unwrap());
+            return;
+        }
+
+        let verb = match (dry_run, trashed) {
+            (true, _) => "Dry run: would delete",
+            (false, true) => "Moved to the trash:",
+            (false, false) => "Deleted",
+        };
+        println!(
+            "{} {} file(s) of {} ({})",
+            verb,
+            files.len(),
+            model_id,
+            format_file_size(bytes)
+        );
+        for file in files {
+            println!("  {}", file);
+        }
+        if trashed && !dry_run {
+            println!(
+                "Kept for {} day(s); undo with: restore (see trash list)",
+                trash::retention_days()
+            );
+        }
+    }
 
-            // Calculate ETA
-            let eta_str = if speed_mbps > 0.0 && total > verified {
-                let remaining_bytes = total - verified;
-                let eta_secs = (remaining_bytes as f64 / (speed_mbps * 1_048_576.0)) as u64;
-                if eta_secs >= 3600 {
-                    format!(" ETA {}h {}m", eta_secs / 3600, (eta_secs % 3600) / 60)
-                } else if eta_secs >= 60 {
-                    format!(" ETA {}m {}s", eta_secs / 60, eta_secs % 60)
+    pub fn report_restored(&self, entry: &trash::TrashEntry) {
+        if self.json_mode {
+            let json = serde_json::json!({
+                "status": "restored",
+                "entry": entry
+            });
+            println!("{}", serde_json::to_string_pretty(&json).unwrap());
+        } else {
+            println!(
+                "Restored {} ({}){}",
+                entry.path.display(),
+                format_file_size(entry.size),
+                if entry.download.is_some() {
+                    ", registered again"
                 } else {
-                    format!(" ETA {}s", eta_secs)
+                    ""
                 }
-            } else {
-                String::new()
-            };
-
-            print!(
-                "\r[{}] {}% ({:.2} MB/s){} verifying - {}",
-                bar, percent, speed_mbps, eta_str, filename
             );
-            let _ = std::io::stdout().flush();
         }
     }
 
-    pub fn report_error(&self, error: &str) {
+    pub fn report_trash(&self, entries: &[trash::TrashEntry]) {
         if self.json_mode {
             let json = serde_json::json!({
-                "status": "error",
-                "error": error
+                "retention_days": trash::retention_days(),
+                "entries": entries
             });
-            eprintln!("{}", json);
+            println!("{}", serde_json::to_string_pretty(&json).unwrap());
+        } else if entries.is_empty() {
+            println!("Trash is empty");
         } else {
-            eprintln!("Error: {}", error);
+            for entry in entries {
+                println!(
+                    "{}  {}  ({}, deleted {} day(s) ago)",
+                    entry.id,
+                    entry.path.display(),
+                    format_file_size(entry.size),
+                    entry.age_days()
+                );
+            }
+            println!(
+                "Files are kept for {} day(s); restore one with: restore <ID>",
+                trash::retention_days()
+            );
         }
     }
 
-    pub fn report_info(&self, message: &str) {
+    pub fn report_trash_purged(&self, files: usize, bytes: u64, dry_run: bool) {
         if self.json_mode {
             let json = serde_json::json!({
-                "status": "info",
-                "message": message
+                "status": if dry_run { "dry_run" } else { "trash_purged" },
+                "files": files,
+                "bytes": bytes
             });
-            println!("{}", json);
+            println!("{}", serde_json::to_string_pretty(&json).unwrap());
+        } else if dry_run {
+            println!(
+                "Dry run: {} file(s) ({}) would be deleted for good",
+                files,
+                format_file_size(bytes)
+            );
         } else {
-            println!("{}", message);
+            println!(
+                "Deleted {} file(s) ({}) from the trash",
+                files,
+                format_file_size(bytes)
+            );
         }
     }
 
-    #[allow(dead_code)]
-    pub fn report_list_quantizations(
-        &self,
-        quantizations: &[QuantizationGroup],
-        metadata: &ModelMetadata,
-    ) {
+    pub fn report_recent_gguf(&self, days: u64, groups: &[(String, Vec<ModelInfo>)]) {
+        let count: usize = groups.iter().map(|(_, models)| models.len()).sum();
         if self.json_mode {
-            // Simplified JSON output without full serialization
-            println!("{{");
-            println!("  \"model_id\": \"{}\",", metadata.model_id);
-            println!("  \"quantizations\": [");
-            for (i, quant) in quantizations.iter().enumerate() {
-                if i > 0 {
-                    println!(",");
-                }
-                println!("    {{");
-                println!("      \"quant_type\": \"{}\",", quant.quant_type);
-                println!("      \"total_size\": {},", quant.total_size);
-                println!("      \"file_count\": {}", quant.files.len());
-                print!("      \"files\": [");
-                for (j, file) in quant.files.iter().enumerate() {
-                    if j > 0 {
-                        print!(", ");
-                    }
-                    print!("\"{}\"", file.filename);
-                }
-                println!("]");
-                print!("    }}");
-            }
-            println!();
-            println!("  ]");
-            println!("}}");
-        } else {
-            println!("Model: {}", metadata.model_id);
-            println!("\nQuantizations:");
-            for quant in quantizations {
+            let groups: Vec<serde_json::Value> = groups
+                .iter()
+                .map(|(base, models)| {
+                    serde_json::json!({
+                        "base_model": base,
+                        "models": models,
+                    })
+                })
+                .collect();
+            let json = serde_json::json!({
+                "days": days,
+                "count": count,
+                "groups": groups,
+            });
+            println!("{}", serde_json::to_string_pretty(&json).unwrap());
+            return;
+        }
+
+        println!(
+            "{} new GGUF repo(s) in the last {} day(s), {} base model(s):",
+            count,
+            days,
+            groups.len()
+        );
+        for (base, models) in groups {
+            println!("\n{}", base);
+            for model in models {
+                let created = model.created_at.as_deref().unwrap_or("N/A");
                 println!(
-                    "  - {} ({} files, {} MB total)",
-                    quant.quant_type,
-                    quant.files.len(),
-                    quant.total_size / 1_048_576
+                    "  {}  {}  ↓{} ♥{}",
+                    created.get(..10).unwrap_or(created),
+                    model.id,
+                    model.downloads,
+                    model.likes
                 );
-                for file in &quant.files {
-                    println!("      - {} ({} MB)", file.filename, file.size / 1_048_576);
-                }
             }
+        }
+    }
 
-            // Show file tree for non-GGUF models
-            if !quantizations.is_empty() {
-                println!("\nFile Tree:");
-                let file_tree = api::build_file_tree(metadata.siblings.clone());
-                print_tree_node(&file_tree, 0);
-            }
+    pub fn report_stats(&self, stats: &history::HistoryStats) {
+        if self.json_mode {
+            println!("{}", serde_json::to_string_pretty(stats).unwrap());
+            return;
+        }
+
+        if stats.downloads == 0 {
+            println!("No downloads recorded yet.");
+            return;
+        }
+
+        println!(
+            "Downloaded:        {} file(s), {}",
+            stats.downloads,
+            format_file_size(stats.total_bytes)
+        );
+        println!("Average speed:     {:.1} MB/s", stats.avg_speed_mbps);
+        println!(
+            "Per active day:    {}",
+            format_file_size(stats.avg_bytes_per_active_day)
+        );
+
+        println!("\nRecent days:");
+        for day in &stats.days {
+            println!(
+                "  {}  {:>4} file(s)  {:>10}  {:>7.1} MB/s",
+                day.date,
+                day.downloads,
+                format_file_size(day.bytes),
+                day.avg_speed_mbps
+            );
+        }
+
+        println!("\nBiggest models:");
+        for model in &stats.biggest_models {
+            println!("  {:>10}  {}", format_file_size(model.bytes), model.model_id);
         }
     }
 
-    #[allow(dead_code)]
-    pub fn report_resume(&self, resumed: &[DownloadMetadata]) {
+    pub fn report_bench_start(&self, target: &bench::BenchTarget) {
+        if self.json_mode {
+            return;
+        }
+        println!(
+            "Reading {} MB per run of {}/{} ({}); nothing is written to disk",
+            bench::RUN_BYTES / 1_048_576,
+            target.model_id,
+            target.filename,
+            format_file_size(target.size)
+        );
+        println!();
+    }
+
+    pub fn report_bench_result(&self, result: &bench::BenchResult) {
+        if self.json_mode {
+            return;
+        }
+        println!(
+            "  {:>2} connection{} x {:>2} MB chunks: {:>8.1} MB/s",
+            result.threads,
+            if result.threads == 1 { " " } else { "s" },
+            result.chunk_size / 1_048_576,
+            result.speed_mbps
+        );
+    }
+
+    pub fn report_bench_summary(
+        &self,
+        target: &bench::BenchTarget,
+        results: &[bench::BenchResult],
+        best: &bench::BenchResult,
+        saved: bool,
+    ) {
         if self.json_mode {
             let json = serde_json::json!({
-                "status": "resumed",
-                "count": resumed.len(),
-                "downloads": resumed
+                "model_id": target.model_id,
+                "filename": target.filename,
+                "run_bytes": bench::RUN_BYTES,
+                "results": results,
+                "best": best,
+                "saved": saved
             });
-            println!("{}", json);
-        } else if resumed.is_empty() {
-            self.report_info("No incomplete downloads to resume");
+            println!("{}", serde_json::to_string_pretty(&json).unwrap());
+            return;
+        }
+
+        println!();
+        println!(
+            "Best: {} connection{} with {} MB chunks ({:.1} MB/s)",
+            best.threads,
+            if best.threads == 1 { "" } else { "s" },
+            best.chunk_size / 1_048_576,
+            best.speed_mbps
+        );
+        if !saved {
+            println!("Not saved; run with --yes to save it in the config");
+        } else if crate::instance::is_read_only() {
+            println!("Not saved (--read-only)");
+        } else if config::load_config().conservative_networking {
+            println!("Saved, but conservative networking is on and overrides it");
         } else {
-            println!("Resumed {} downloads:", resumed.len());
-            for download in resumed {
-                println!("  - {}", download.filename);
-            }
+            println!("Saved in the config");
         }
     }
 
-    pub fn report_download_summary(&self, files: &[String], total_size: u64) {
+    pub fn report_watch_change(&self, model_id: &str, added: bool, files: usize) {
         if self.json_mode {
             let json = serde_json::json!({
-                "status": "queued",
-                "file_count": files.len(),
-                "total_size_bytes": total_size,
+                "status": if added { "watching" } else { "unwatched" },
+                "model_id": model_id,
                 "files": files
             });
             println!("{}", serde_json::to_string_pretty(&json).unwrap());
+        } else if added {
+            println!("Watching {} ({} files)", model_id, files);
         } else {
-            println!("Download Summary:");
-            println!("  Files: {}", files.len());
-            println!("  Total Size: {}", format_file_size(total_size));
-            println!();
+            println!("Stopped watching {}", model_id);
+        }
+    }
 
-            if files.len() <= 10 {
-                for file in files {
-                    println!("  - {}", file);
-                }
-            } else {
-                for file in files.iter().take(5) {
-                    println!("  - {}", file);
-                }
-                println!("  ... and {} more", files.len() - 5);
+    pub fn report_watchlist(&self, watchlist: &watchlist::Watchlist) {
+        if self.json_mode {
+            let models: Vec<_> = watchlist
+                .models
+                .iter()
+                .map(|m| {
+                    serde_json::json!({
+                        "model_id": m.model_id,
+                        "last_modified": m.last_modified,
+                        "files": m.files.len()
+                    })
+                })
+                .collect();
+            let json = serde_json::json!({ "models": models });
+            println!("{}", serde_json::to_string_pretty(&json).unwrap());
+        } else if watchlist.models.is_empty() {
+            println!("Watchlist is empty. Add a model with: watch add <model_id>");
+        } else {
+            for m in &watchlist.models {
+                println!(
+                    "{}  (last modified {}, {} files)",
+                    m.model_id,
+                    m.last_modified.as_deref().unwrap_or("unknown"),
+                    m.files.len()
+                );
             }
-            println!();
         }
     }
 
-    pub fn report_dry_run_summary(
-        &self,
-        files: &[String],
-        total_size: u64,
-        output_dir: &str,
-        is_gguf: bool,
-    ) {
+    pub fn report_ignore_change(&self, entry: &ignorelist::IgnoredEntry, hidden: bool) {
         if self.json_mode {
             let json = serde_json::json!({
-                "status": "dry_run",
-                "model_type": if is_gguf { "GGUF" } else { "Non-GGUF" },
-                "file_count": files.len(),
-                "total_size_bytes": total_size,
-                "output_directory": output_dir,
-                "files": files
+                "status": if hidden { "hidden" } else { "unhidden" },
+                "entry": entry.label(),
             });
             println!("{}", serde_json::to_string_pretty(&json).unwrap());
+        } else if hidden {
+            println!("Hiding {} from search results", entry.label());
         } else {
-            println!("Download Plan:");
-            println!(
-                "  Model type: {}",
-                if is_gguf { "GGUF" } else { "Non-GGUF" }
-            );
-            println!("  Files to download: {}", files.len());
-            println!("  Total size: {}", format_file_size(total_size));
-            println!("  Output directory: {}", output_dir);
-            println!();
+            println!("{} shows up in search results again", entry.label());
+        }
+    }
 
-            println!("Files:");
-            for (i, file) in files.iter().enumerate() {
-                println!("  {}. {}", i + 1, file);
+    pub fn report_ignore_list(&self, list: &ignorelist::IgnoreList) {
+        if self.json_mode {
+            let json = serde_json::json!({
+                "models": list.models,
+                "authors": list.authors,
+            });
+            println!("{}", serde_json::to_string_pretty(&json).unwrap());
+        } else if list.is_empty() {
+            println!("Nothing is hidden. Hide a model with: ignore add <model_id>");
+        } else {
+            for entry in list.entries() {
+                println!("{}", entry.label());
             }
-            println!();
-
-            println!("Run without --dry-run to execute the download.");
         }
     }
 
-    pub fn report_no_incomplete(&self) {
+    pub fn report_watch_check(&self, result: &watchlist::WatchCheck) {
         if self.json_mode {
+            let errors: Vec<_> = result
+                .errors
+                .iter()
+                .map(|(model_id, error)| serde_json::json!({ "model_id": model_id, "error": error }))
+                .collect();
             let json = serde_json::json!({
-                "status": "no_incomplete",
-                "message": "No incomplete downloads found"
+                "checked": result.checked,
+                "updated": result.updates,
+                "errors": errors
             });
             println!("{}", serde_json::to_string_pretty(&json).unwrap());
-        } else {
-            println!("No incomplete downloads found.");
+            return;
+        }
+
+        println!(
+            "{} of {} watched model(s) updated",
+            result.updates.len(),
+            result.checked
+        );
+        for update in &result.updates {
+            println!(
+                "\n{}  ({} -> {})",
+                update.model_id,
+                update.previous_modified.as_deref().unwrap_or("unknown"),
+                update.last_modified.as_deref().unwrap_or("unknown")
+            );
+            if update.changes.is_empty() {
+                println!("  (no file changes)");
+            }
+            for change in &update.changes {
+                let marker = match change.kind {
+                    watchlist::FileChangeKind::Added => '+',
+                    watchlist::FileChangeKind::Removed => '-',
+                    watchlist::FileChangeKind::Modified => '~',
+                };
+                println!("  {} {}", marker, change.path);
+            }
+            if let Some(commit) = &update.downloaded_commit {
+                println!(
+                    "  Your download (commit {}): {} file(s) changed since",
+                    crate::utils::short_commit(commit),
+                    update.since_download.len()
+                );
+                for change in &update.since_download {
+                    let marker = match change.kind {
+                        watchlist::FileChangeKind::Added => '+',
+                        watchlist::FileChangeKind::Removed => '-',
+                        watchlist::FileChangeKind::Modified => '~',
+                    };
+                    println!("    {} {}", marker, change.path);
+                }
+            }
+        }
+        for (model_id, error) in &result.errors {
+            eprintln!("Failed to check {}: {}", model_id, error);
         }
     }
 
@@ -1186,15 +4134,67 @@ impl ProgressReporter {
         self.json_mode
     }
 
+    /// Print weight format, parameter count, config summary and finetune lineage, when known
+    pub fn report_model_overview(&self, metadata: &ModelMetadata) {
+        let mut printed = false;
+        if let Some(format) = metadata.quant_format() {
+            println!("Format: {} (needs {})", format.label(), format.guidance());
+            printed = true;
+        }
+        if let Some(base) = metadata.adapter_base_model() {
+            println!("Adapter for: {} (use --with-base to download both)", base);
+            printed = true;
+        }
+        if let Some(params) = metadata.parameter_count() {
+            println!("Parameters: {}", crate::utils::format_params(params));
+            printed = true;
+        }
+        if let Some(ref config) = metadata.model_config {
+            let details: Vec<String> = [
+                config.architecture.clone(),
+                config.hidden_size.map(|size| format!("hidden {}", size)),
+                config.num_layers.map(|layers| format!("{} layers", layers)),
+                config
+                    .context_length
+                    .map(|context| format!("context {}", context)),
+                config
+                    .rope_scaling
+                    .as_ref()
+                    .map(|scaling| format!("RoPE {}", scaling)),
+            ]
+            .into_iter()
+            .flatten()
+            .collect();
+            println!("Config: {}", details.join(", "));
+            printed = true;
+        }
+        if !metadata.base_model_chain.is_empty() {
+            println!(
+                "Base model: {}",
+                metadata.base_model_chain.join(" -> ")
+            );
+            printed = true;
+        }
+        if printed {
+            println!();
+        }
+    }
+
     pub fn report_quantizations_table(&self, quantizations: &[QuantizationGroup]) {
         println!("Available Quantizations:");
         println!();
 
         for group in quantizations {
             let total_size_str = format_file_size(group.total_size);
+            let branch = if group.files.iter().any(|f| f.revision.is_some()) {
+                " [branch]"
+            } else {
+                ""
+            };
             println!(
-                "  {} ({} total, {} file{})",
+                "  {}{} ({} total, {} file{})",
                 group.quant_type,
+                branch,
                 total_size_str,
                 group.files.len(),
                 if group.files.len() == 1 { "" } else { "s" }
@@ -1219,7 +4219,7 @@ impl ProgressReporter {
         println!("  Files: {}", metadata.siblings.len());
         println!();
 
-        let tree = api::build_file_tree(metadata.siblings.clone());
+        let tree = api::build_file_tree(metadata.siblings.clone(), &metadata.weight_shards);
         print_tree_node(&tree, 0);
     }
 
@@ -1236,8 +4236,24 @@ impl ProgressReporter {
             metadata.pipeline_tag.as_deref().unwrap_or("N/A")
         );
         println!("  \"has_gguf\": {},", has_gguf);
+        match metadata.quant_format() {
+            Some(format) => println!("  \"format\": \"{}\",", format.label()),
+            None => println!("  \"format\": null,"),
+        }
+        match metadata.adapter_base_model() {
+            Some(base) => println!("  \"adapter_base_model\": \"{}\",", base),
+            None => println!("  \"adapter_base_model\": null,"),
+        }
+        match metadata.parameter_count() {
+            Some(params) => println!("  \"parameters\": {},", params),
+            None => println!("  \"parameters\": null,"),
+        }
+        println!(
+            "  \"base_model_chain\": {},",
+            serde_json::to_string(&metadata.base_model_chain).unwrap_or_else(|_| "[]".to_string())
+        );
 
-        if has_gguf {
+        if has_gguf || !quantizations.is_empty() {
             println!("  \"quantizations\": [");
             for (i, quant) in quantizations.iter().enumerate() {
                 if i > 0 {
@@ -1245,6 +4261,9 @@ impl ProgressReporter {
                 }
                 println!("    {{");
                 println!("      \"quant_type\": \"{}\",", quant.quant_type);
+                if let Some(revision) = quant.files.first().and_then(|f| f.revision.as_ref()) {
+                    println!("      \"revision\": \"{}\",", revision);
+                }
                 println!("      \"total_size\": {},", quant.total_size);
                 println!("      \"file_count\": {}", quant.files.len());
                 print!("      \"files\": [");
@@ -1314,7 +4333,9 @@ impl ProgressReporter {
 
 fn print_tree_node(node: &FileTreeNode, depth: usize) {
     let indent = "  ".repeat(depth);
-    let size_str = if let Some(size) = node.size {
+    let size_str = if let (Some(shards), Some(size)) = (node.shard_count, node.size) {
+        format!(" ({} shards, {} MB)", shards, size / 1_048_576)
+    } else if let Some(size) = node.size {
         format!(" ({} MB)", size / 1_048_576)
     } else {
         String::new()
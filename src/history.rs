@@ -0,0 +1,207 @@
+//! Log of completed downloads and usage statistics
+//!
+//! Every finished download appends one JSON line to
+//! `~/.config/jreb/download-history.jsonl` (timestamp, duration, size and the
+//! URL it came from). [`compute_stats`] summarizes the log for the `stats`
+//! command and the TUI statistics popup, e.g. to keep an eye on metered
+//! bandwidth.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// One completed download
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    /// Unix timestamp (seconds) when the download finished
+    pub completed_at: u64,
+    pub model_id: String,
+    pub filename: String,
+    pub size: u64,
+    pub duration_secs: f64,
+    /// URL the file was downloaded from
+    pub source: String,
+}
+
+impl HistoryEntry {
+    pub fn new(
+        model_id: &str,
+        filename: &str,
+        size: u64,
+        duration: std::time::Duration,
+        source: &str,
+    ) -> Self {
+        Self {
+            completed_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            model_id: model_id.to_string(),
+            filename: filename.to_string(),
+            size,
+            duration_secs: duration.as_secs_f64(),
+            source: source.to_string(),
+        }
+    }
+
+    /// Average speed in MB/s
+    pub fn speed_mbps(&self) -> f64 {
+        speed_mbps(self.size, self.duration_secs)
+    }
+}
+
+fn speed_mbps(bytes: u64, secs: f64) -> f64 {
+    if secs > 0.0 {
+        bytes as f64 / secs / 1_048_576.0
+    } else {
+        0.0
+    }
+}
+
+pub fn get_history_path() -> PathBuf {
+    let home = std::env::var("HOME").unwrap_or_else(|_| "/tmp".to_string());
+    PathBuf::from(format!("{}/.config/jreb/download-history.jsonl", home))
+}
+
+/// Append a completed download to the log
+/// Failures are ignored: the history is best-effort and must never fail a download
+pub fn record(entry: &HistoryEntry) {
+    let path = get_history_path();
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    let Ok(line) = serde_json::to_string(entry) else {
+        return;
+    };
+    if let Ok(mut file) = OpenOptions::new().create(true).append(true).open(&path) {
+        let _ = writeln!(file, "{}", line);
+    }
+}
+
+/// Load the whole log, oldest first (unreadable lines are skipped)
+pub fn load_history() -> Vec<HistoryEntry> {
+    fs::read_to_string(get_history_path())
+        .map(|contents| {
+            contents
+                .lines()
+                .filter_map(|line| serde_json::from_str(line).ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Average speed in MB/s over the last `count` completed downloads, if any
+pub fn recent_speed_mbps(entries: &[HistoryEntry], count: usize) -> Option<f64> {
+    let recent = &entries[entries.len().saturating_sub(count)..];
+    let bytes: u64 = recent.iter().map(|e| e.size).sum();
+    let secs: f64 = recent.iter().map(|e| e.duration_secs).sum();
+    (secs > 0.0).then(|| speed_mbps(bytes, secs))
+}
+
+/// Completed downloads averaged for ETAs before a download starts
+pub const ETA_RECENT_DOWNLOADS: usize = 10;
+
+/// Recent average speed from the history file, for ETAs before a download
+/// starts; `None` without usable history
+pub fn recent_speed() -> Option<f64> {
+    recent_speed_mbps(&load_history(), ETA_RECENT_DOWNLOADS).filter(|s| *s > 0.0)
+}
+
+/// Seconds `bytes` take at `speed_mbps`
+pub fn eta_secs(bytes: u64, speed_mbps: f64) -> u64 {
+    (bytes as f64 / (speed_mbps * 1_048_576.0)).round() as u64
+}
+
+/// "~42m 10s at your recent 55.0 MB/s"
+pub fn format_eta(bytes: u64, speed_mbps: f64) -> String {
+    format!(
+        "~{} at your recent {:.1} MB/s",
+        crate::utils::format_duration(eta_secs(bytes, speed_mbps)),
+        speed_mbps
+    )
+}
+
+/// Downloads finished on one (UTC) day
+#[derive(Debug, Clone, Serialize)]
+pub struct DayStats {
+    pub date: String,
+    pub downloads: usize,
+    pub bytes: u64,
+    pub avg_speed_mbps: f64,
+}
+
+/// Total downloaded for one model
+#[derive(Debug, Clone, Serialize)]
+pub struct ModelTotal {
+    pub model_id: String,
+    pub bytes: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct HistoryStats {
+    pub downloads: usize,
+    pub total_bytes: u64,
+    pub avg_speed_mbps: f64,
+    /// Average bytes downloaded per day that saw at least one download
+    pub avg_bytes_per_active_day: u64,
+    /// Per-day totals, most recent first
+    pub days: Vec<DayStats>,
+    /// Models by total bytes downloaded, largest first
+    pub biggest_models: Vec<ModelTotal>,
+}
+
+/// Summarize the log; `top_models` limits the biggest-models list
+pub fn compute_stats(entries: &[HistoryEntry], top_models: usize) -> HistoryStats {
+    if entries.is_empty() {
+        return HistoryStats::default();
+    }
+
+    let total_bytes: u64 = entries.iter().map(|e| e.size).sum();
+    let total_secs: f64 = entries.iter().map(|e| e.duration_secs).sum();
+
+    // date -> (downloads, bytes, seconds)
+    let mut by_day: BTreeMap<String, (usize, u64, f64)> = BTreeMap::new();
+    let mut by_model: HashMap<&str, u64> = HashMap::new();
+    for entry in entries {
+        let day = by_day
+            .entry(crate::utils::format_unix_date(entry.completed_at))
+            .or_default();
+        day.0 += 1;
+        day.1 += entry.size;
+        day.2 += entry.duration_secs;
+        *by_model.entry(entry.model_id.as_str()).or_default() += entry.size;
+    }
+
+    let days: Vec<DayStats> = by_day
+        .into_iter()
+        .rev()
+        .map(|(date, (downloads, bytes, secs))| DayStats {
+            date,
+            downloads,
+            bytes,
+            avg_speed_mbps: speed_mbps(bytes, secs),
+        })
+        .collect();
+
+    let mut biggest_models: Vec<ModelTotal> = by_model
+        .into_iter()
+        .map(|(model_id, bytes)| ModelTotal {
+            model_id: model_id.to_string(),
+            bytes,
+        })
+        .collect();
+    biggest_models.sort_by(|a, b| b.bytes.cmp(&a.bytes).then_with(|| a.model_id.cmp(&b.model_id)));
+    biggest_models.truncate(top_models);
+
+    HistoryStats {
+        downloads: entries.len(),
+        total_bytes,
+        avg_speed_mbps: speed_mbps(total_bytes, total_secs),
+        avg_bytes_per_active_day: total_bytes / days.len() as u64,
+        days,
+        biggest_models,
+    }
+}
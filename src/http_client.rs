@@ -1,30 +1,190 @@
-use reqwest::{header, Client};
+//! HTTP client shared by the API, download and verification layers
+//!
+//! Requests carry only what they need: `User-Agent` (see [`user_agent`]),
+//! `Authorization` when a token is set, `Range` for chunked downloads, and
+//! reqwest's `Accept: */*`. There are no analytics, session or client-id
+//! headers. reqwest drops `Authorization` when a redirect leaves the host,
+//! so the token isn't sent to the CDN.
+//!
+//! TLS settings ([`configure_tls`]) apply to every client built here, so
+//! they must be set before the first request.
+
+use once_cell::sync::{Lazy, OnceCell};
+use reqwest::{header, Certificate, Client, ClientBuilder, RequestBuilder};
+use std::path::Path;
+use std::sync::RwLock;
 use std::time::Duration;
 
-/// Build an HTTP client with optional token
+/// Default User-Agent: crate name and version
+pub const USER_AGENT: &str = concat!("rust-hf-downloader/", env!("CARGO_PKG_VERSION"));
+
+/// Configured User-Agent (`user_agent` option or `--user-agent`), if any
+static USER_AGENT_SETTING: RwLock<Option<String>> = RwLock::new(None);
+
+/// Use `user_agent` instead of [`USER_AGENT`] for the following requests; an
+/// empty string sends no User-Agent header at all, `None` restores the default
+pub fn set_user_agent(user_agent: Option<String>) {
+    *USER_AGENT_SETTING
+        .write()
+        .unwrap_or_else(|e| e.into_inner()) = user_agent;
+}
+
+/// User-Agent sent with requests (empty when none is sent); a configured
+/// value that can't be a header (e.g. with a line break) falls back to the default
+pub fn user_agent() -> String {
+    let setting = USER_AGENT_SETTING.read().unwrap_or_else(|e| e.into_inner());
+    match setting.as_deref() {
+        Some(user_agent) if header::HeaderValue::from_str(user_agent).is_ok() => {
+            user_agent.to_string()
+        }
+        _ => USER_AGENT.to_string(),
+    }
+}
+
+/// Extra root certificates and certificate checking, see [`configure_tls`]
+#[derive(Default)]
+struct TlsSettings {
+    root_certificates: Vec<Certificate>,
+    insecure: bool,
+}
+
+static TLS_SETTINGS: OnceCell<TlsSettings> = OnceCell::new();
+
+/// Trust the certificates in the PEM bundle at `ca_bundle` (e.g. the root CA
+/// of a corporate TLS-inspecting proxy) in addition to the built-in roots,
+/// and with `insecure` accept any certificate at all
+///
+/// Must be called before the first request; later calls are ignored.
+/// Returns the number of certificates loaded.
+pub fn configure_tls(ca_bundle: Option<&Path>, insecure: bool) -> Result<usize, String> {
+    let root_certificates = match ca_bundle {
+        Some(path) => {
+            let pem = std::fs::read(path)
+                .map_err(|e| format!("Cannot read CA bundle {}: {}", path.display(), e))?;
+            let certificates = Certificate::from_pem_bundle(&pem)
+                .map_err(|e| format!("Invalid CA bundle {}: {}", path.display(), e))?;
+            if certificates.is_empty() {
+                return Err(format!(
+                    "CA bundle {} has no PEM certificates",
+                    path.display()
+                ));
+            }
+            certificates
+        }
+        None => Vec::new(),
+    };
+    let count = root_certificates.len();
+    let _ = TLS_SETTINGS.set(TlsSettings {
+        root_certificates,
+        insecure,
+    });
+    Ok(count)
+}
+
+/// Whether certificate verification is turned off (`--insecure`)
+pub fn tls_insecure() -> bool {
+    TLS_SETTINGS.get().is_some_and(|tls| tls.insecure)
+}
+
+/// Shared HTTP client used by the API, download and verification layers
+///
+/// A single client keeps one connection pool, so whole-repo downloads reuse
+/// established TLS sessions (and HTTP/2 connections negotiated via ALPN)
+/// instead of handshaking again for every file. Tokens and timeouts are
+/// applied per request, see [`get`].
+pub static SHARED_CLIENT: Lazy<Client> = Lazy::new(|| {
+    client_builder()
+        .build()
+        .expect("failed to build shared HTTP client")
+});
+
+/// Client that doesn't follow redirects, for headers of the Hub's own
+/// response (`X-Repo-Commit` of a resolve URL) rather than the CDN's
+static NO_REDIRECT_CLIENT: Lazy<Client> = Lazy::new(|| {
+    client_builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .expect("failed to build HTTP client")
+});
+
+/// Client builder with the pooling and keep-alive settings used by `SHARED_CLIENT`
+///
+/// The User-Agent is added per request (see [`get`]), so changing it in the
+/// options applies without rebuilding the client.
+fn client_builder() -> ClientBuilder {
+    let mut builder = Client::builder()
+        .pool_max_idle_per_host(16)
+        .pool_idle_timeout(Duration::from_secs(90))
+        .tcp_keepalive(Duration::from_secs(60))
+        .tcp_nodelay(true)
+        .http2_adaptive_window(true);
+
+    let tls = TLS_SETTINGS.get_or_init(TlsSettings::default);
+    for certificate in &tls.root_certificates {
+        builder = builder.add_root_certificate(certificate.clone());
+    }
+    if tls.insecure {
+        builder = builder.danger_accept_invalid_certs(true);
+    }
+    builder
+}
+
+/// Build a dedicated HTTP client with optional token
+///
+/// Prefer [`get`] with the shared client; this is only needed when a caller
+/// requires its own connection pool.
 pub fn build_client_with_token(
     token: Option<&String>,
     timeout: Option<Duration>,
 ) -> Result<Client, reqwest::Error> {
-    let mut builder = Client::builder();
+    let mut builder = client_builder();
 
     if let Some(timeout) = timeout {
         builder = builder.timeout(timeout);
     }
 
+    let mut headers = header::HeaderMap::new();
+    let user_agent = user_agent();
+    if !user_agent.is_empty() {
+        if let Ok(value) = header::HeaderValue::from_str(&user_agent) {
+            headers.insert(header::USER_AGENT, value);
+        }
+    }
+
     // ONLY add authorization header if token is provided and non-empty
     if let Some(token) = token {
         if !token.is_empty() {
-            let mut headers = header::HeaderMap::new();
             let auth_value = format!("Bearer {}", token);
             if let Ok(header_val) = header::HeaderValue::from_str(&auth_value) {
                 headers.insert(header::AUTHORIZATION, header_val);
             }
-            builder = builder.default_headers(headers);
         }
     }
 
-    builder.build()
+    builder.default_headers(headers).build()
+}
+
+/// Start a GET request on the shared client, adding the User-Agent and the
+/// token if provided and non-empty
+pub fn get(url: &str, token: Option<&String>) -> RequestBuilder {
+    with_headers(SHARED_CLIENT.get(url), token)
+}
+
+/// Start a HEAD request that stops at the first response instead of
+/// following redirects, with the same headers as [`get`]
+pub fn head_no_redirect(url: &str, token: Option<&String>) -> RequestBuilder {
+    with_headers(NO_REDIRECT_CLIENT.head(url), token)
+}
+
+fn with_headers(mut request: RequestBuilder, token: Option<&String>) -> RequestBuilder {
+    let user_agent = user_agent();
+    if !user_agent.is_empty() {
+        request = request.header(header::USER_AGENT, user_agent);
+    }
+    match token {
+        Some(token) if !token.is_empty() => request.bearer_auth(token),
+        _ => request,
+    }
 }
 
 /// Make a GET request with optional token
@@ -33,15 +193,5 @@ pub async fn get_with_optional_token(
     url: &str,
     token: Option<&String>,
 ) -> Result<reqwest::Response, reqwest::Error> {
-    // Check if token is provided AND non-empty
-    let has_token = token.is_some_and(|t| !t.is_empty());
-
-    if has_token {
-        // Build client with token
-        let client = build_client_with_token(token, None)?;
-        client.get(url).send().await
-    } else {
-        // Use simple reqwest::get (no client needed)
-        reqwest::get(url).await
-    }
+    get(url, token).send().await
 }
@@ -0,0 +1,133 @@
+//! Models and authors hidden from search results
+//!
+//! The ignore list is stored in `~/.config/jreb/ignored.toml`, so spam
+//! re-uploads or quantizers a user never wants to see stay out of every
+//! later search, in the TUI and in headless mode alike. Entries are matched
+//! case-insensitively, like Hub repository IDs.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IgnoreList {
+    /// Hidden repositories (`author/model`)
+    #[serde(default)]
+    pub models: Vec<String>,
+    /// Authors (users or organizations) whose every repository is hidden
+    #[serde(default)]
+    pub authors: Vec<String>,
+}
+
+/// One entry of the ignore list
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IgnoredEntry {
+    Model(String),
+    Author(String),
+}
+
+impl IgnoredEntry {
+    /// The entry as listed to the user; authors read `author/*`
+    pub fn label(&self) -> String {
+        match self {
+            IgnoredEntry::Model(model_id) => model_id.clone(),
+            IgnoredEntry::Author(author) => format!("{}/*", author),
+        }
+    }
+}
+
+/// Author part of a repository ID
+pub fn author_of(model_id: &str) -> &str {
+    model_id.split('/').next().unwrap_or(model_id)
+}
+
+impl IgnoreList {
+    pub fn is_empty(&self) -> bool {
+        self.models.is_empty() && self.authors.is_empty()
+    }
+
+    /// Whether `model_id` is hidden, itself or through its author
+    pub fn hides(&self, model_id: &str) -> bool {
+        let author = author_of(model_id);
+        self.models.iter().any(|m| m.eq_ignore_ascii_case(model_id))
+            || self.authors.iter().any(|a| a.eq_ignore_ascii_case(author))
+    }
+
+    /// Every entry, models first, each sorted
+    pub fn entries(&self) -> Vec<IgnoredEntry> {
+        let mut models = self.models.clone();
+        models.sort_by_key(|m| m.to_lowercase());
+        let mut authors = self.authors.clone();
+        authors.sort_by_key(|a| a.to_lowercase());
+        models
+            .into_iter()
+            .map(IgnoredEntry::Model)
+            .chain(authors.into_iter().map(IgnoredEntry::Author))
+            .collect()
+    }
+
+    /// Add an entry; returns false if it was already listed
+    pub fn add(&mut self, entry: IgnoredEntry) -> bool {
+        let (list, name) = match entry {
+            IgnoredEntry::Model(model_id) => (&mut self.models, model_id),
+            IgnoredEntry::Author(author) => (&mut self.authors, author),
+        };
+        if list.iter().any(|n| n.eq_ignore_ascii_case(&name)) {
+            return false;
+        }
+        list.push(name);
+        true
+    }
+
+    /// Remove an entry; returns false if it was not listed
+    pub fn remove(&mut self, entry: &IgnoredEntry) -> bool {
+        let (list, name) = match entry {
+            IgnoredEntry::Model(model_id) => (&mut self.models, model_id),
+            IgnoredEntry::Author(author) => (&mut self.authors, author),
+        };
+        let before = list.len();
+        list.retain(|n| !n.eq_ignore_ascii_case(name));
+        list.len() != before
+    }
+}
+
+pub fn get_ignore_list_path() -> PathBuf {
+    let home = std::env::var("HOME").unwrap_or_else(|_| "/tmp".to_string());
+    PathBuf::from(format!("{}/.config/jreb/ignored.toml", home))
+}
+
+pub fn load_ignore_list() -> IgnoreList {
+    fs::read_to_string(get_ignore_list_path())
+        .ok()
+        .and_then(|contents| toml::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_ignore_list(list: &IgnoreList) -> std::io::Result<()> {
+    let path = get_ignore_list_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let toml_string = toml::to_string_pretty(list).map_err(std::io::Error::other)?;
+    fs::write(path, toml_string)
+}
+
+/// Hide a model or author; returns false if it was already hidden
+pub fn ignore(entry: IgnoredEntry) -> std::io::Result<bool> {
+    let mut list = load_ignore_list();
+    let added = list.add(entry);
+    if added {
+        save_ignore_list(&list)?;
+    }
+    Ok(added)
+}
+
+/// Show a model or author again; returns false if it was not hidden
+pub fn unignore(entry: &IgnoredEntry) -> std::io::Result<bool> {
+    let mut list = load_ignore_list();
+    let removed = list.remove(entry);
+    if removed {
+        save_ignore_list(&list)?;
+    }
+    Ok(removed)
+}
@@ -0,0 +1,123 @@
+//! Coordination between concurrently running instances
+//!
+//! The registry and config are shared by every instance on the machine. A
+//! running instance holds an exclusive advisory lock on
+//! `~/models/.hf-downloader.lock` (with its PID written inside) for its whole
+//! lifetime, so a second one can tell it is not alone and either refuse to
+//! start or run in read-only mode, where registry and config saves are
+//! skipped. Each write additionally takes a lock on a `<file>.lock` sibling
+//! so two writers never interleave.
+
+use fs2::FileExt;
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static READ_ONLY: AtomicBool = AtomicBool::new(false);
+
+/// Skip registry and config saves (set when another instance owns them)
+pub fn set_read_only(read_only: bool) {
+    READ_ONLY.store(read_only, Ordering::Relaxed);
+}
+
+pub fn is_read_only() -> bool {
+    READ_ONLY.load(Ordering::Relaxed)
+}
+
+static DAEMON_CLIENT: AtomicBool = AtomicBool::new(false);
+
+/// The instance lock belongs to a running download daemon: downloads are
+/// handed to it and it alone writes the registry (see [`crate::daemon`])
+pub fn set_daemon_client(daemon_client: bool) {
+    DAEMON_CLIENT.store(daemon_client, Ordering::Relaxed);
+}
+
+pub fn is_daemon_client() -> bool {
+    DAEMON_CLIENT.load(Ordering::Relaxed)
+}
+
+pub fn get_lock_path() -> PathBuf {
+    let home = std::env::var("HOME").unwrap_or_else(|_| "/tmp".to_string());
+    PathBuf::from(format!("{}/models/.hf-downloader.lock", home))
+}
+
+/// Held by the running instance; the lock is released when dropped
+pub struct InstanceLock {
+    _file: File,
+}
+
+/// Another live instance holds the instance lock
+#[derive(Debug, Clone)]
+pub struct OtherInstance {
+    /// Its PID, when the lock file could be read
+    pub pid: Option<u32>,
+}
+
+impl fmt::Display for OtherInstance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.pid {
+            Some(pid) => write!(
+                f,
+                "another rust-hf-downloader instance (PID {}) is running",
+                pid
+            ),
+            None => write!(f, "another rust-hf-downloader instance is running"),
+        }
+    }
+}
+
+/// Claim the instance lock, failing if another instance holds it. Lock
+/// files that cannot be created (e.g. a read-only home) are not an error:
+/// there is nothing to coordinate then.
+pub fn acquire() -> Result<Option<InstanceLock>, OtherInstance> {
+    let path = get_lock_path();
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    let Ok(mut file) = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(&path)
+    else {
+        return Ok(None);
+    };
+
+    if file.try_lock_exclusive().is_err() {
+        let mut content = String::new();
+        let pid = file
+            .read_to_string(&mut content)
+            .ok()
+            .and_then(|_| content.trim().parse().ok());
+        return Err(OtherInstance { pid });
+    }
+
+    let _ = file.set_len(0);
+    let _ = file.seek(SeekFrom::Start(0));
+    let _ = write!(file, "{}", std::process::id());
+    let _ = file.flush();
+    Ok(Some(InstanceLock { _file: file }))
+}
+
+/// Run `write` while holding an exclusive lock on `<path>.lock`. When the
+/// lock file cannot be opened, `write` runs unlocked.
+pub fn with_file_lock<T>(path: &Path, write: impl FnOnce() -> T) -> T {
+    let mut lock_name = path.as_os_str().to_os_string();
+    lock_name.push(".lock");
+    let lock_file = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(PathBuf::from(lock_name))
+        .ok()
+        .filter(|file| file.lock_exclusive().is_ok());
+
+    let result = write();
+    if let Some(file) = lock_file {
+        let _ = FileExt::unlock(&file);
+    }
+    result
+}
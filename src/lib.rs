@@ -0,0 +1,75 @@
+//! Core library for searching and downloading HuggingFace models
+//!
+//! This crate contains everything the `rust-hf-downloader` binary uses that is
+//! not tied to the terminal UI or the command-line front-end, so other Rust
+//! projects can embed the chunked downloader directly:
+//!
+//! - [`adopt`] - Registry import of models downloaded by other tools
+//! - [`api`] - HuggingFace API client (search, metadata, quantization discovery)
+//! - [`download`] - Chunked, resumable, rate-limited downloads with path sanitization
+//! - [`blobstore`] - Content-addressable storage of downloads, linked into model directories
+//! - [`bench`] - Throughput benchmark of connection counts and chunk sizes
+//! - [`verification`] - SHA256 verification worker
+//! - [`archive`] - Extraction of downloaded .zip/.tar(.gz) archives
+//! - [`convert`] - GGUF conversion of downloaded repositories with llama.cpp
+//! - [`registry`] - Download metadata persistence
+//! - [`trash`] - Deleted files kept for a retention period, and their restore
+//! - [`quota`] - Size quotas of download directories and cleanup suggestions
+//! - [`evict`] - Least-recently-used models to delete for free space
+//! - [`placement`] - Rules picking the download root of each download
+//! - [`history`] - Completed-download log and bandwidth statistics
+//! - [`cache`] - On-disk API response cache (offline mode)
+//! - [`watchlist`] - Watched repositories and upstream change detection
+//! - [`ignorelist`] - Models and authors hidden from search results
+//! - [`multipart`] - Completeness checks and merge commands for split GGUFs
+//! - [`gguf`] - GGUF header metadata (chat template, special tokens)
+//! - [`instance`] - Locking between concurrently running instances
+//! - [`daemon`] - Background download daemon and its local socket protocol
+//! - [`metrics`] - Prometheus metrics endpoint
+//! - [`webhook`] - Webhook notifications of download events
+//!
+//! Depend on it with `default-features = false` to skip the TUI/CLI dependencies:
+//!
+//! ```toml
+//! [dependencies]
+//! rust-hf-downloader = { version = "1.4", default-features = false }
+//! ```
+//!
+//! ```no_run
+//! # async fn example() -> Result<(), hf_downloader::api::ApiError> {
+//! let groups = hf_downloader::api::fetch_model_files("TheBloke/Llama-2-7B-GGUF", None).await?;
+//! for group in groups {
+//!     println!("{} ({} files)", group.quant_type, group.files.len());
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod adopt;
+pub mod api;
+pub mod archive;
+pub mod bench;
+pub mod blobstore;
+pub mod cache;
+pub mod config;
+pub mod convert;
+pub mod daemon;
+pub mod download;
+pub mod evict;
+pub mod gguf;
+pub mod history;
+pub mod http_client;
+pub mod ignorelist;
+pub mod instance;
+pub mod metrics;
+pub mod models;
+pub mod multipart;
+pub mod placement;
+pub mod quota;
+pub mod rate_limiter;
+pub mod registry;
+pub mod trash;
+pub mod utils;
+pub mod verification;
+pub mod watchlist;
+pub mod webhook;
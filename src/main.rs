@@ -1,33 +1,157 @@
-mod api;
 mod cli;
-mod config;
-mod download;
+mod completions;
 mod headless;
-mod http_client;
-mod models;
-mod rate_limiter;
-mod registry;
+mod manpage;
+mod records;
 mod ui;
-mod utils;
-mod verification;
 
-use std::sync::atomic::AtomicUsize;
+// Core modules live in the library crate; re-import them so the front-end
+// modules can keep addressing them as `crate::api`, `crate::models`, etc.
+use hf_downloader::{
+    adopt, api, bench, blobstore, cache, config, convert, daemon, download, evict, history,
+    http_client, ignorelist, instance, metrics, models, multipart, placement, quota, registry,
+    trash, utils, verification, watchlist, webhook,
+};
+
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 #[tokio::main]
 async fn main() -> color_eyre::Result<()> {
     color_eyre::install()?;
 
+    // The completion scripts' hook, kept out of clap so the generated
+    // scripts don't offer it as a subcommand
+    let args: Vec<String> = std::env::args().collect();
+    if args.get(1).map(String::as_str) == Some(completions::HOOK) {
+        completions::run_hook(&args[2..]);
+        return Ok(());
+    }
+
     // Parse CLI arguments
     use clap::Parser;
     let cli_args = cli::Cli::parse();
 
+    if cli_args.offline {
+        cache::set_offline(true);
+    }
+    if cli_args.no_download {
+        download::set_downloads_locked(true);
+    }
+    configure_tls(&cli_args);
+
+    // Completion scripts print straight to stdout, with or without --headless
+    match &cli_args.command {
+        Some(cli::Commands::Completions { shell }) => {
+            completions::print_script(*shell);
+            return Ok(());
+        }
+        Some(cli::Commands::Man { command, out_dir }) => {
+            run_man(command.as_deref(), out_dir.as_deref());
+            return Ok(());
+        }
+        _ => {}
+    }
+
+    // Daemon clients only talk to the daemon's socket, so they run before
+    // the instance lock (which the daemon holds) is claimed
+    if cli_args.headless {
+        if let Some(cli::Commands::Daemon { action }) = &cli_args.command {
+            if !matches!(action, cli::DaemonAction::Start { detach: false }) {
+                let reporter = headless_reporter(&cli_args);
+                match headless::run_daemon_client(action, &reporter).await {
+                    Ok(_) => std::process::exit(headless::EXIT_SUCCESS),
+                    Err(e) => {
+                        reporter.report_error(&e.to_string());
+                        std::process::exit(e.exit_code());
+                    }
+                }
+            }
+        }
+    }
+
+    // `path` only reads the registry and runs inside `$(...)`, often while a
+    // TUI or the daemon holds the instance lock, so it never claims it
+    if cli_args.headless {
+        if let Some(cli::Commands::Path { model_id, quant }) = &cli_args.command {
+            instance::set_read_only(true);
+            let reporter = headless_reporter(&cli_args);
+            match headless::run_path(model_id, quant.as_deref(), &reporter) {
+                Ok(_) => std::process::exit(headless::EXIT_SUCCESS),
+                Err(e) => {
+                    reporter.report_error(&e.to_string());
+                    std::process::exit(e.exit_code());
+                }
+            }
+        }
+    }
+
+    // Held until exit; without it this instance runs read-only or as a
+    // client of the download daemon
+    let _instance_lock = claim_instance(&cli_args).await;
+
     // If --headless flag is present, run in CLI mode
     if cli_args.headless {
-        let json_mode = cli_args.json;
-        let reporter = headless::ProgressReporter::new(json_mode);
+        let reporter = headless_reporter(&cli_args);
+
+        // Apply persisted API cache settings (the TUI does this via sync_options_to_config)
+        let options = config::load_config();
+        if options.no_download {
+            download::set_downloads_locked(true);
+        }
+        registry::start_deferred_writes();
+        trash::configure(options.trash_retention_days);
+        if !instance::is_read_only() && !instance::is_daemon_client() {
+            trash::purge_expired();
+        }
+        webhook::configure(options.webhooks.clone());
+        quota::configure(options.quotas.clone());
+        placement::configure(options.placement.clone());
+        cache::configure(
+            options.api_cache_enabled,
+            options.api_cache_ttl_minutes,
+            options.api_cache_max_size_mb,
+        );
+        http_client::set_user_agent(cli_args.user_agent.clone().or(options.user_agent));
+        verification::VERIFICATION_CONFIG
+            .concurrent_verifications
+            .store(options.concurrent_verifications, Ordering::Relaxed);
+        verification::VERIFICATION_CONFIG
+            .buffer_size
+            .store(options.verification_buffer_size, Ordering::Relaxed);
+        verification::VERIFICATION_CONFIG
+            .update_interval_iterations
+            .store(options.verification_update_interval, Ordering::Relaxed);
+        verification::VERIFICATION_CONFIG
+            .low_priority
+            .store(options.low_priority_verification, Ordering::Relaxed);
+        download::DOWNLOAD_CONFIG
+            .single_request_max_size
+            .store(options.single_request_max_size, Ordering::Relaxed);
+        download::DOWNLOAD_CONFIG
+            .adaptive_threads
+            .store(options.adaptive_threads, Ordering::Relaxed);
+        download::DOWNLOAD_CONFIG
+            .max_connections
+            .store(options.max_connections, Ordering::Relaxed);
+        if options.conservative_networking {
+            download::DOWNLOAD_CONFIG.apply_conservative_networking();
+        }
+        download::DOWNLOAD_CONFIG
+            .extract_archives
+            .store(options.extract_archives, Ordering::Relaxed);
+        download::DOWNLOAD_CONFIG.set_identical_files(options.identical_files);
+        download::DOWNLOAD_CONFIG
+            .blob_store
+            .store(options.blob_store, Ordering::Relaxed);
+        if cli_args.skip_verify {
+            download::DOWNLOAD_CONFIG
+                .enable_verification
+                .store(false, Ordering::Relaxed);
+        }
 
         // Create channels for download manager
-        let (download_tx, download_rx) = tokio::sync::mpsc::unbounded_channel();
+        let (download_tx, download_rx) =
+            tokio::sync::mpsc::unbounded_channel::<headless::DownloadMessage>();
         let (progress_tx, mut progress_rx) = tokio::sync::mpsc::unbounded_channel();
         let download_rx = std::sync::Arc::new(tokio::sync::Mutex::new(download_rx));
 
@@ -51,6 +175,21 @@ async fn main() -> color_eyre::Result<()> {
         // Create verification progress tracking
         let verification_progress = std::sync::Arc::new(tokio::sync::Mutex::new(Vec::new()));
 
+        // Daemon clients download nothing themselves; the daemon serves the metrics
+        if let Some(addr) = cli_args.metrics_addr.as_deref() {
+            if !instance::is_daemon_client() {
+                let sources = metrics::MetricsSources {
+                    download_queue: download_queue.clone(),
+                    download_progress: download_progress.clone(),
+                    verification_queue_size: verification_queue_size.clone(),
+                };
+                if let Err(e) = metrics::serve(addr, sources).await {
+                    reporter.report_error(&format!("Cannot serve metrics on {}: {}", addr, e));
+                    std::process::exit(headless::EXIT_INVALID_ARGS);
+                }
+            }
+        }
+
         // Clone Arcs for the download manager task
         let download_progress_clone = download_progress.clone();
         let complete_downloads_clone = complete_downloads.clone();
@@ -66,6 +205,7 @@ async fn main() -> color_eyre::Result<()> {
         let verification_queue_size_worker = verification_queue_size.clone();
         let progress_tx_verify = progress_tx.clone();
         let download_registry_verify = download_registry.clone();
+        let download_queue_verify = download_queue.clone();
         tokio::spawn(async move {
             verification::verification_worker(
                 verification_queue_worker,
@@ -73,53 +213,106 @@ async fn main() -> color_eyre::Result<()> {
                 verification_queue_size_worker,
                 progress_tx_verify,
                 download_registry_verify,
+                download_queue_verify,
             )
             .await;
         });
 
-        tokio::spawn(async move {
-            use crate::download::DownloadParams;
-
-            loop {
-                // Lock only when receiving, release immediately after
-                // This prevents deadlock by not holding download_rx while acquiring other locks
-                let (model_id, filename, path, sha256, hf_token, total_size) = {
-                    let mut rx = download_rx.lock().await;
-                    match rx.recv().await {
-                        Some(msg) => msg,
-                        None => break, // Channel closed
-                    }
-                };
+        // The daemon runs its own download manager, and its clients hand
+        // their downloads over to it
+        let starts_daemon = matches!(
+            cli_args.command,
+            Some(cli::Commands::Daemon {
+                action: cli::DaemonAction::Start { .. }
+            })
+        );
+        let download_rx_manager = download_rx.clone();
+        let download_tx_manager = download_tx.clone();
+        let daemon_client = instance::is_daemon_client();
+        if !starts_daemon {
+            tokio::spawn(async move {
+                use crate::download::DownloadParams;
 
-                // Spawn download task
-                let params = DownloadParams {
-                    model_id,
-                    filename,
-                    base_path: path,
-                    progress: download_progress_clone.clone(),
-                    status_tx: progress_tx_clone.clone(),
-                    complete_downloads: complete_downloads_clone.clone(),
-                    expected_sha256: sha256,
-                    verification_queue: verification_queue_clone.clone(),
-                    verification_queue_size: verification_queue_size_clone.clone(),
-                    hf_token,
-                };
+                // Returns only when the daemon has stopped and this instance
+                // downloads the rest itself
+                if daemon_client {
+                    daemon::forward_downloads(
+                        download_rx_manager.clone(),
+                        download_tx_manager.clone(),
+                        download_queue_clone.clone(),
+                        progress_tx_clone.clone(),
+                    )
+                    .await;
+                }
 
-                let queue = download_queue_clone.clone();
-                tokio::spawn(async move {
-                    download::start_download(params).await;
-                    let mut queue = queue.lock().await;
-                    queue.remove(1, total_size);
-                });
-            }
-        });
+                // The download that ended a small-file batch, next in line
+                let mut held = std::collections::VecDeque::new();
+                loop {
+                    // Lock only when receiving, release immediately after
+                    // This prevents deadlock by not holding download_rx while acquiring other locks
+                    let Some(batch) =
+                        download::next_download_batch(&download_rx_manager, &mut held, |message| {
+                            message.5
+                        })
+                        .await
+                    else {
+                        break; // Channel closed
+                    };
+
+                    // Spawn download task (small files queued together share one)
+                    let total_size: u64 = batch.iter().map(|message| message.5).sum();
+                    let count = batch.len();
+                    let mut params: Vec<_> = batch
+                        .into_iter()
+                        .map(
+                            |(model_id, filename, path, hash, hf_token, total_size, revision)| {
+                                DownloadParams {
+                                    model_id,
+                                    filename,
+                                    base_path: path,
+                                    progress: download_progress_clone.clone(),
+                                    status_tx: progress_tx_clone.clone(),
+                                    complete_downloads: complete_downloads_clone.clone(),
+                                    expected_hash: hash,
+                                    verification_queue: verification_queue_clone.clone(),
+                                    verification_queue_size: verification_queue_size_clone.clone(),
+                                    hf_token,
+                                    revision,
+                                    total_size,
+                                }
+                            },
+                        )
+                        .collect();
+
+                    let queue = download_queue_clone.clone();
+                    let status_tx = progress_tx_clone.clone();
+                    tokio::spawn(async move {
+                        if count == 1 {
+                            download::start_download(params.remove(0)).await;
+                        } else {
+                            download::start_small_downloads(params).await;
+                        }
+                        let mut queue = queue.lock().await;
+                        queue.remove(count, total_size);
+                        if queue.is_empty() {
+                            webhook::queue_drained(&status_tx);
+                        }
+                    });
+                }
+            });
+        }
 
-        // Spawn progress reporter task
+        // Spawn progress reporter task (also the daemon's log for clients)
         let json_mode = cli_args.json;
+        let daemon_log = std::sync::Arc::new(std::sync::Mutex::new(daemon::DaemonLog::default()));
+        let daemon_log_reporter = daemon_log.clone();
         tokio::spawn(async move {
             use std::io::Write;
 
             while let Some(msg) = progress_rx.recv().await {
+                if let Ok(mut log) = daemon_log_reporter.lock() {
+                    log.push(msg.clone());
+                }
                 if !json_mode {
                     print!("\r\x1b[2K");
                     let _ = std::io::stdout().flush();
@@ -160,19 +353,106 @@ async fn main() -> color_eyre::Result<()> {
             });
         }
 
+        // A bare model ID or URL without a subcommand: download a linked
+        // file, otherwise list what the model offers
+        let command = cli_args.command.clone().or_else(|| {
+            cli_args.target.clone().map(|target| {
+                let is_file = api::parse_model_reference(&target)
+                    .is_some_and(|r| r.file_path.is_some());
+                if is_file {
+                    cli::Commands::Download {
+                        model_id: target,
+                        quantization: None,
+                        all: false,
+                        with_base: false,
+                        preset: None,
+                        output: None,
+                        verify_only: false,
+                    }
+                } else {
+                    cli::Commands::List {
+                        model_id: target,
+                        format: None,
+                    }
+                }
+            })
+        });
+
+        // auto_resume: commands that download also finish what earlier runs left incomplete
+        let downloads_files = matches!(
+            command,
+            Some(
+                cli::Commands::Download {
+                    verify_only: false,
+                    ..
+                } | cli::Commands::DownloadOrg { .. }
+                    | cli::Commands::Pick { .. }
+            )
+        );
+        if options.auto_resume
+            && downloads_files
+            && !cli_args.dry_run
+            && !instance::is_daemon_client()
+            && !download::downloads_locked()
+        {
+            if let Err(e) = headless::auto_resume_downloads(
+                download_tx.clone(),
+                progress_tx.clone(),
+                download_queue.clone(),
+            )
+            .await
+            {
+                eprintln!("Warning: could not resume incomplete downloads: {}", e);
+            }
+        }
+
         // Execute command
-        let result = match cli_args.command {
+        let result = match command {
             Some(cli::Commands::Search {
                 query,
                 sort: _,
                 min_downloads,
                 min_likes,
+                min_params,
+                max_params,
+                no_gated,
+                no_private,
+                no_nsfw,
+                full_text,
+                repo_size,
+                page_size,
+                max_results,
+                cursor,
+                format,
             }) => {
+                let format = parse_record_format(format.as_deref(), records::SEARCH_FIELDS);
+                let configured = config::load_config().search_limits();
+                let limits = models::SearchLimits {
+                    page_size: page_size.map_or(configured.page_size, |n| n as usize),
+                    max_results: max_results.map_or(configured.max_results, |n| n as usize),
+                };
+                if let (Some(min), Some(max)) = (min_params, max_params) {
+                    if min > max {
+                        eprintln!("Error: --min-params is larger than --max-params");
+                        std::process::exit(headless::EXIT_INVALID_ARGS);
+                    }
+                }
                 headless::run_search(
                     &query,
                     None, // sort_field
                     min_downloads,
                     min_likes,
+                    models::ParamRange {
+                        min: min_params,
+                        max: max_params,
+                    },
+                    models::AccessFilter::from_flags(no_gated, no_private),
+                    models::ContentFilter::from_flag(no_nsfw),
+                    limits,
+                    full_text,
+                    repo_size,
+                    cursor.as_deref(),
+                    format.as_ref(),
                     cli_args.token.as_ref(),
                     &reporter,
                 )
@@ -182,21 +462,47 @@ async fn main() -> color_eyre::Result<()> {
                 model_id,
                 quantization,
                 all,
+                with_base,
+                preset,
                 output,
+                verify_only,
             }) => {
-                let output_dir = output.unwrap_or_else(|| {
-                    let options = config::load_config();
-                    options.default_directory
-                });
+                if verify_only && cli_args.skip_verify {
+                    eprintln!("Error: --verify-only cannot be used with --skip-verify");
+                    std::process::exit(headless::EXIT_INVALID_ARGS);
+                }
 
                 if cli_args.dry_run {
                     headless::run_download_dry_run(
                         &model_id,
                         quantization.as_deref(),
                         all,
+                        preset,
+                        with_base,
+                        output.as_deref(),
+                        cli_args.token,
+                        cli_args.accept_license,
+                        &reporter,
+                    )
+                    .await
+                } else if verify_only {
+                    // Verification finds the files through the registry, wherever
+                    // they were placed
+                    let output_dir =
+                        output.unwrap_or_else(|| config::load_config().default_directory);
+                    headless::run_verify_only(
+                        &model_id,
+                        quantization.as_deref(),
+                        all,
+                        preset,
+                        with_base,
                         &output_dir,
                         cli_args.token,
                         &reporter,
+                        verification_queue,
+                        verification_queue_size,
+                        verification_progress,
+                        shutdown_signal,
                     )
                     .await
                 } else {
@@ -204,8 +510,11 @@ async fn main() -> color_eyre::Result<()> {
                         &model_id,
                         quantization.as_deref(),
                         all,
-                        &output_dir,
+                        preset,
+                        with_base,
+                        output.as_deref(),
                         cli_args.token,
+                        cli_args.accept_license,
                         &reporter,
                         download_tx,
                         progress_tx,
@@ -218,8 +527,136 @@ async fn main() -> color_eyre::Result<()> {
                     .await
                 }
             }
-            Some(cli::Commands::List { model_id }) => {
-                headless::run_list(&model_id, cli_args.token.as_ref(), &reporter).await
+            Some(cli::Commands::DownloadOrg {
+                org,
+                include,
+                quant,
+                output,
+                yes,
+            }) => {
+                if include.is_empty() && quant.is_none() {
+                    eprintln!("Error: download-org needs --include and/or --quant");
+                    std::process::exit(headless::EXIT_INVALID_ARGS);
+                }
+                let output_dir = output.unwrap_or_else(|| config::load_config().default_directory);
+
+                headless::run_download_org(
+                    &org,
+                    &include,
+                    quant.as_deref(),
+                    &output_dir,
+                    yes,
+                    cli_args.dry_run,
+                    cli_args.token,
+                    &reporter,
+                    download_tx,
+                    download_queue,
+                    download_progress,
+                    verification_queue_size,
+                    verification_progress,
+                    shutdown_signal,
+                )
+                .await
+            }
+            Some(cli::Commands::Pick {
+                query,
+                picker,
+                output,
+            }) => {
+                headless::run_pick(
+                    &query,
+                    picker.as_deref(),
+                    output.as_deref(),
+                    cli_args.token,
+                    cli_args.accept_license,
+                    &reporter,
+                    download_tx,
+                    progress_tx,
+                    download_queue,
+                    download_progress,
+                    verification_queue_size,
+                    verification_progress,
+                    shutdown_signal,
+                )
+                .await
+            }
+            Some(cli::Commands::MyModels) => {
+                headless::run_my_models(cli_args.token.as_ref(), &reporter).await
+            }
+            Some(cli::Commands::Recent { days, format }) => {
+                let format = parse_record_format(format.as_deref(), records::SEARCH_FIELDS);
+                let days = days.unwrap_or_else(|| config::load_config().recent_gguf_days);
+                headless::run_recent(days, format.as_ref(), cli_args.token.as_ref(), &reporter)
+                    .await
+            }
+            Some(cli::Commands::List { model_id, format }) => {
+                let format = parse_record_format(format.as_deref(), records::LIST_FIELDS);
+                headless::run_list(
+                    &model_id,
+                    format.as_ref(),
+                    cli_args.token.as_ref(),
+                    &reporter,
+                )
+                .await
+            }
+            Some(cli::Commands::Cache {
+                action: cli::CacheAction::Clear,
+            }) => headless::run_cache_clear(&reporter),
+            Some(cli::Commands::Blobs { action }) => match action {
+                cli::BlobsAction::Status => headless::run_blobs_status(&reporter),
+                cli::BlobsAction::Gc => headless::run_blobs_gc(cli_args.dry_run, &reporter),
+            },
+            Some(cli::Commands::Delete { model_id, file }) => {
+                headless::run_delete(&model_id, file.as_deref(), cli_args.dry_run, &reporter)
+            }
+            Some(cli::Commands::Restore { id }) => headless::run_restore(id.as_deref(), &reporter),
+            Some(cli::Commands::Trash { action }) => match action {
+                cli::TrashAction::List => headless::run_trash_list(&reporter),
+                cli::TrashAction::Gc { all } => {
+                    headless::run_trash_gc(all, cli_args.dry_run, &reporter)
+                }
+            },
+            Some(cli::Commands::Evict {
+                keep_free,
+                dir,
+                yes,
+            }) => headless::run_evict(keep_free, dir.as_deref(), yes, cli_args.dry_run, &reporter),
+            Some(cli::Commands::Path { .. }) => {
+                unreachable!("path runs before the instance lock is claimed")
+            }
+            Some(cli::Commands::Stats { days }) => headless::run_stats(days, &reporter),
+            Some(cli::Commands::Bench { model, yes }) => {
+                headless::run_bench(model.as_deref(), yes, cli_args.token, &reporter).await
+            }
+            Some(cli::Commands::Watch { action }) => match action {
+                cli::WatchAction::Add { model_id } => {
+                    headless::run_watch_add(&model_id, cli_args.token.as_ref(), &reporter).await
+                }
+                cli::WatchAction::Remove { model_id } => {
+                    headless::run_watch_remove(&model_id, &reporter)
+                }
+                cli::WatchAction::List => headless::run_watch_list(&reporter),
+                cli::WatchAction::Check => {
+                    headless::run_watch_check(cli_args.token.as_ref(), &reporter).await
+                }
+            },
+            Some(cli::Commands::Ignore { action }) => match action {
+                cli::IgnoreAction::Add { target } => headless::run_ignore_add(&target, &reporter),
+                cli::IgnoreAction::Remove { target } => {
+                    headless::run_ignore_remove(&target, &reporter)
+                }
+                cli::IgnoreAction::List => headless::run_ignore_list(&reporter),
+            },
+            Some(cli::Commands::Adopt { dir, model, hash }) => {
+                headless::run_adopt(
+                    &dir,
+                    model.as_deref(),
+                    hash,
+                    cli_args.dry_run,
+                    cli_args.token,
+                    &reporter,
+                )
+                .await
             }
             Some(cli::Commands::Resume) => {
                 headless::run_resume(
@@ -234,12 +671,51 @@ async fn main() -> color_eyre::Result<()> {
                 )
                 .await
             }
+            Some(cli::Commands::Daemon { action }) => match action {
+                cli::DaemonAction::Start { .. } if instance::is_daemon_client() => {
+                    Err(headless::HeadlessError::ConfigError(
+                        "the download daemon is already running".to_string(),
+                    ))
+                }
+                cli::DaemonAction::Start { .. } => {
+                    headless::run_daemon(
+                        &reporter,
+                        headless::DaemonEngine {
+                            download_tx,
+                            download_rx,
+                            download_queue,
+                            download_progress,
+                            complete_downloads,
+                            verification_queue,
+                            verification_queue_size,
+                            verification_progress,
+                            progress_tx,
+                            log: daemon_log,
+                            shutdown_signal,
+                        },
+                    )
+                    .await
+                }
+                _ => unreachable!("daemon clients run before the instance lock is claimed"),
+            },
+            Some(cli::Commands::Completions { .. } | cli::Commands::Man { .. }) => {
+                unreachable!("completion scripts and man pages are printed before headless mode")
+            }
             None => {
                 eprintln!("Error: No command specified");
                 std::process::exit(headless::EXIT_INVALID_ARGS);
             }
         };
 
+        // process::exit skips destructors, so write out batched registry saves first
+        registry::flush_registry();
+        webhook::flush().await;
+
+        if result.is_ok() && downloads_files && instance::is_daemon_client() {
+            reporter
+                .report_info("Downloads handed to the daemon; follow them with `daemon attach`");
+        }
+
         match result {
             Ok(_) => std::process::exit(headless::EXIT_SUCCESS),
             Err(e) => {
@@ -256,8 +732,14 @@ async fn main() -> color_eyre::Result<()> {
     use std::io::stdout;
     execute!(stdout(), EnableMouseCapture)?;
 
+    let mut app = ui::App::new();
+    app.enable_screen_reader(cli_args.screen_reader, cli_args.screen_reader_log.clone());
+    if let Some(target) = cli_args.target.as_deref() {
+        app.open_model_reference(target);
+    }
+
     let terminal = ratatui::init();
-    let result = ui::App::new().run(terminal).await;
+    let result = app.run(terminal).await;
     ratatui::restore();
 
     // Disable mouse capture when exiting
@@ -266,3 +748,149 @@ async fn main() -> color_eyre::Result<()> {
 
     result
 }
+
+/// Apply `--ca-bundle` (or the `ca_bundle` option) and `--insecure` before
+/// any request is made, exiting with the invalid-arguments code on a bad bundle
+fn configure_tls(cli_args: &cli::Cli) {
+    let ca_bundle = cli_args.ca_bundle.clone().or_else(|| {
+        config::load_config()
+            .ca_bundle
+            .filter(|path| !path.is_empty())
+            .map(std::path::PathBuf::from)
+    });
+    if cli_args.insecure {
+        eprintln!(
+            "WARNING: --insecure turns off TLS certificate verification. Anyone on the \
+             network path can read and alter all traffic, including your Hugging Face \
+             token and the files being downloaded. Use --ca-bundle for a TLS-inspecting proxy."
+        );
+    }
+    if let Err(e) = http_client::configure_tls(ca_bundle.as_deref(), cli_args.insecure) {
+        eprintln!("Error: {}", e);
+        std::process::exit(headless::EXIT_INVALID_ARGS);
+    }
+}
+
+/// Build the headless progress reporter from the output flags
+fn headless_reporter(cli_args: &cli::Cli) -> headless::ProgressReporter {
+    let json_mode = cli_args.json;
+    let progress_mode = if cli_args.no_progress {
+        cli::ProgressMode::None
+    } else {
+        cli_args.progress.unwrap_or(if json_mode {
+            cli::ProgressMode::Json
+        } else {
+            cli::ProgressMode::Bar
+        })
+    };
+    headless::ProgressReporter::new(json_mode).with_progress(progress_mode, cli_args.progress_step)
+}
+
+/// Take the instance lock. When the download daemon holds it, this instance
+/// becomes its client, and headless commands that write more than downloads
+/// exit with an error; when another instance does, headless commands that
+/// only read run read-only, the others exit with an error, and the TUI asks
+/// whether to open read-only.
+async fn claim_instance(cli_args: &cli::Cli) -> Option<instance::InstanceLock> {
+    if cli_args.read_only {
+        instance::set_read_only(true);
+        return None;
+    }
+
+    let other = match instance::acquire() {
+        Ok(lock) => return lock,
+        Err(other) => other,
+    };
+
+    if daemon::is_running().await {
+        if cli_args.headless && needs_instance_lock(cli_args) && !only_downloads(cli_args) {
+            eprintln!(
+                "Error: the download daemon is running and owns the download registry. \
+                 Stop it first with `daemon stop`."
+            );
+            std::process::exit(headless::EXIT_ERROR);
+        }
+        instance::set_daemon_client(true);
+        return None;
+    }
+
+    if cli_args.headless && !needs_instance_lock(cli_args) {
+        instance::set_read_only(true);
+        return None;
+    }
+    if cli_args.headless {
+        eprintln!(
+            "Error: {} and owns the download registry and config. \
+             Close it, or pass --read-only to run without saving them.",
+            other
+        );
+        std::process::exit(headless::EXIT_ERROR);
+    }
+
+    eprint!(
+        "{}.\nOpen in read-only mode (no downloads, settings are not saved)? [y/N] ",
+        other
+    );
+    let mut answer = String::new();
+    let _ = std::io::stdin().read_line(&mut answer);
+    if !answer.trim().eq_ignore_ascii_case("y") {
+        std::process::exit(headless::EXIT_ERROR);
+    }
+    instance::set_read_only(true);
+    None
+}
+
+/// Whether the headless command may write what the running instance owns; a
+/// bare file URL downloads it, any other bare target only lists
+fn needs_instance_lock(cli_args: &cli::Cli) -> bool {
+    match &cli_args.command {
+        Some(command) => command.writes_state(cli_args.dry_run),
+        None => {
+            !cli_args.dry_run
+                && cli_args
+                    .target
+                    .as_deref()
+                    .and_then(api::parse_model_reference)
+                    .is_some_and(|r| r.file_path.is_some())
+        }
+    }
+}
+
+/// Whether the headless command only downloads, which the download daemon
+/// can take over; a bare target downloads or lists
+fn only_downloads(cli_args: &cli::Cli) -> bool {
+    match &cli_args.command {
+        Some(command) => command.only_downloads(),
+        None => true,
+    }
+}
+
+/// Print a man page, or write them all to `out_dir`
+fn run_man(command: Option<&str>, out_dir: Option<&std::path::Path>) {
+    let result = match out_dir {
+        Some(dir) => manpage::write_pages(dir)
+            .map(|written| {
+                for path in written {
+                    println!("{}", path.display());
+                }
+            })
+            .map_err(|e| format!("Failed to write man pages to {}: {}", dir.display(), e)),
+        None => manpage::print_page(command),
+    };
+    if let Err(e) = result {
+        eprintln!("Error: {}", e);
+        std::process::exit(headless::EXIT_INVALID_ARGS);
+    }
+}
+
+/// Parse `--format`, exiting with the invalid-arguments code on an unknown field
+fn parse_record_format(spec: Option<&str>, fields: &[&str]) -> Option<records::RecordFormat> {
+    let spec = spec?;
+    match records::RecordFormat::parse(spec, fields) {
+        Ok(format) => Some(format),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(headless::EXIT_INVALID_ARGS);
+        }
+    }
+}
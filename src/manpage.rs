@@ -0,0 +1,96 @@
+//! Man pages rendered from the clap definitions in [`crate::cli`]
+//!
+//! Pages are generated by `clap_mangen` from the same command tree the parser
+//! uses, so they can't drift from `--help`. The recipes a command shows under
+//! `help <command>` become the page's EXAMPLES section.
+
+use clap::{Command, CommandFactory};
+use clap_mangen::Man;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// Print the page of the whole program, or of `command` (e.g. "download",
+/// "watch check")
+pub fn print_page(command: Option<&str>) -> Result<(), String> {
+    let cmd = command_tree();
+    let mut page = &cmd;
+    for name in command.into_iter().flat_map(str::split_whitespace) {
+        page = page
+            .find_subcommand(name)
+            .filter(|sub| !sub.is_hide_set())
+            .ok_or_else(|| format!("no man page for '{}'", command.unwrap_or_default()))?;
+    }
+
+    let mut out = io::stdout().lock();
+    render(page, &mut out).map_err(|e| e.to_string())
+}
+
+/// Write a page for the program and each of its (sub)commands into `dir`,
+/// returning the files written
+pub fn write_pages(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    fn write(cmd: &Command, dir: &Path, written: &mut Vec<PathBuf>) -> io::Result<()> {
+        let path = dir.join(Man::new(cmd.clone()).get_filename());
+        let mut file = std::fs::File::create(&path)?;
+        render(cmd, &mut file)?;
+        written.push(path);
+        for sub in cmd.get_subcommands().filter(|sub| !sub.is_hide_set()) {
+            write(sub, dir, written)?;
+        }
+        Ok(())
+    }
+
+    std::fs::create_dir_all(dir)?;
+    let mut written = Vec::new();
+    write(&command_tree(), dir, &mut written)?;
+    Ok(written)
+}
+
+/// The parser's commands, built so subcommands carry their full names
+/// (`rust-hf-downloader-download`); `help` gets no page of its own
+fn command_tree() -> Command {
+    let mut cmd = crate::cli::Cli::command().disable_help_subcommand(true);
+    cmd.build();
+    cmd
+}
+
+/// clap_mangen's sections, with the recipes as an EXAMPLES section (before
+/// VERSION) instead of its generic EXTRA section
+fn render(cmd: &Command, out: &mut dyn Write) -> io::Result<()> {
+    let man = Man::new(cmd.clone());
+    man.render_title(out)?;
+    man.render_name_section(out)?;
+    man.render_synopsis_section(out)?;
+    man.render_description_section(out)?;
+    if cmd.get_arguments().any(|arg| !arg.is_hide_set()) {
+        man.render_options_section(out)?;
+    }
+    if cmd.get_subcommands().any(|sub| !sub.is_hide_set()) {
+        man.render_subcommands_section(out)?;
+    }
+    if let Some(examples) = cmd.get_after_long_help() {
+        render_examples(&examples.to_string(), out)?;
+    }
+    if cmd.get_version().is_some() {
+        man.render_version_section(out)?;
+    }
+    Ok(())
+}
+
+/// Recipes as literal lines, escaped so they can be copied from the page
+fn render_examples(examples: &str, out: &mut dyn Write) -> io::Result<()> {
+    let examples = examples.strip_prefix("Examples:\n").unwrap_or(examples);
+    writeln!(out, ".SH EXAMPLES")?;
+    writeln!(out, ".nf")?;
+    for line in examples.lines() {
+        let line = line
+            .replace('\\', "\\e")
+            .replace('-', "\\-")
+            .replace('\'', "\\(aq");
+        // A leading '.' would be read as a request
+        if line.starts_with('.') {
+            write!(out, "\\&")?;
+        }
+        writeln!(out, "{}", line)?;
+    }
+    writeln!(out, ".fi")
+}
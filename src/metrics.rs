@@ -0,0 +1,224 @@
+//! Prometheus metrics for the download daemon and long headless runs
+//!
+//! Counters are process-wide atomics the download and verification code
+//! updates as it goes; gauges (queue depth, active chunks, verification
+//! backlog) are read from the caller's shared state on every scrape. [`serve`]
+//! answers `GET /metrics` in the text exposition format.
+
+use crate::models::{DownloadProgress, QueueState};
+use std::fmt::Write as _;
+use std::io;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::sync::Mutex;
+
+/// Bytes written to downloaded files
+pub static DOWNLOADED_BYTES: AtomicU64 = AtomicU64::new(0);
+
+/// Files downloaded completely
+pub static COMPLETED_FILES: AtomicU64 = AtomicU64::new(0);
+
+/// What went wrong, the `type` label of `hf_downloader_errors_total`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    Timeout,
+    Connection,
+    Auth,
+    ClientStatus,
+    ServerStatus,
+    Io,
+    HashMismatch,
+    Other,
+}
+
+impl ErrorKind {
+    const ALL: [ErrorKind; 8] = [
+        ErrorKind::Timeout,
+        ErrorKind::Connection,
+        ErrorKind::Auth,
+        ErrorKind::ClientStatus,
+        ErrorKind::ServerStatus,
+        ErrorKind::Io,
+        ErrorKind::HashMismatch,
+        ErrorKind::Other,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ErrorKind::Timeout => "timeout",
+            ErrorKind::Connection => "connection",
+            ErrorKind::Auth => "auth",
+            ErrorKind::ClientStatus => "http_4xx",
+            ErrorKind::ServerStatus => "http_5xx",
+            ErrorKind::Io => "io",
+            ErrorKind::HashMismatch => "hash_mismatch",
+            ErrorKind::Other => "other",
+        }
+    }
+
+    /// Classify a download error
+    pub fn of(e: &(dyn std::error::Error + 'static)) -> Self {
+        if let Some(reqwest_err) = e.downcast_ref::<reqwest::Error>() {
+            return match reqwest_err.status() {
+                Some(reqwest::StatusCode::UNAUTHORIZED) => ErrorKind::Auth,
+                Some(status) if status.is_client_error() => ErrorKind::ClientStatus,
+                Some(status) if status.is_server_error() => ErrorKind::ServerStatus,
+                Some(_) => ErrorKind::Other,
+                None if reqwest_err.is_timeout() => ErrorKind::Timeout,
+                None => ErrorKind::Connection,
+            };
+        }
+        if e.downcast_ref::<io::Error>().is_some() {
+            return ErrorKind::Io;
+        }
+        ErrorKind::Other
+    }
+}
+
+static ERRORS: [AtomicU64; 8] = [
+    AtomicU64::new(0),
+    AtomicU64::new(0),
+    AtomicU64::new(0),
+    AtomicU64::new(0),
+    AtomicU64::new(0),
+    AtomicU64::new(0),
+    AtomicU64::new(0),
+    AtomicU64::new(0),
+];
+
+pub fn record_error(kind: ErrorKind) {
+    ERRORS[kind as usize].fetch_add(1, Ordering::Relaxed);
+}
+
+/// Shared state the gauges are read from
+#[derive(Clone)]
+pub struct MetricsSources {
+    pub download_queue: Arc<Mutex<QueueState>>,
+    pub download_progress: Arc<Mutex<Option<DownloadProgress>>>,
+    pub verification_queue_size: Arc<AtomicUsize>,
+}
+
+fn metric(out: &mut String, name: &str, kind: &str, help: &str, value: u64) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+    let _ = writeln!(out, "{} {}", name, value);
+}
+
+/// All metrics in the Prometheus text exposition format
+pub async fn render(sources: &MetricsSources) -> String {
+    let queue = sources.download_queue.lock().await.clone();
+    let (active_downloads, active_chunks) = match sources.download_progress.lock().await.as_ref() {
+        Some(progress) => (
+            1,
+            progress.chunks.iter().filter(|c| c.is_active).count() as u64,
+        ),
+        None => (0, 0),
+    };
+
+    let mut out = String::new();
+    metric(
+        &mut out,
+        "hf_downloader_downloaded_bytes_total",
+        "counter",
+        "Bytes written to downloaded files",
+        DOWNLOADED_BYTES.load(Ordering::Relaxed),
+    );
+    metric(
+        &mut out,
+        "hf_downloader_completed_files_total",
+        "counter",
+        "Files downloaded completely",
+        COMPLETED_FILES.load(Ordering::Relaxed),
+    );
+    metric(
+        &mut out,
+        "hf_downloader_active_downloads",
+        "gauge",
+        "Downloads in progress",
+        active_downloads,
+    );
+    metric(
+        &mut out,
+        "hf_downloader_active_chunks",
+        "gauge",
+        "Chunk requests in progress",
+        active_chunks,
+    );
+    metric(
+        &mut out,
+        "hf_downloader_queue_depth",
+        "gauge",
+        "Downloads queued",
+        queue.size as u64,
+    );
+    metric(
+        &mut out,
+        "hf_downloader_queue_bytes",
+        "gauge",
+        "Bytes of the queued downloads",
+        queue.bytes,
+    );
+    metric(
+        &mut out,
+        "hf_downloader_verification_backlog",
+        "gauge",
+        "Files queued for or being verified",
+        sources.verification_queue_size.load(Ordering::Relaxed) as u64,
+    );
+
+    let _ = writeln!(
+        out,
+        "# HELP hf_downloader_errors_total Failed requests and downloads by type"
+    );
+    let _ = writeln!(out, "# TYPE hf_downloader_errors_total counter");
+    for kind in ErrorKind::ALL {
+        let _ = writeln!(
+            out,
+            "hf_downloader_errors_total{{type=\"{}\"}} {}",
+            kind.label(),
+            ERRORS[kind as usize].load(Ordering::Relaxed)
+        );
+    }
+    out
+}
+
+/// Serve `GET /metrics` on `addr` until the process exits; binding errors
+/// are returned before anything is spawned
+pub async fn serve(addr: &str, sources: MetricsSources) -> io::Result<()> {
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    tokio::spawn(async move {
+        loop {
+            let Ok((mut stream, _)) = listener.accept().await else {
+                continue;
+            };
+            let sources = sources.clone();
+            tokio::spawn(async move {
+                // The request line is all that matters
+                let mut request = [0u8; 1024];
+                let read = tokio::time::timeout(
+                    std::time::Duration::from_secs(5),
+                    stream.read(&mut request),
+                )
+                .await;
+                let Ok(Ok(len)) = read else {
+                    return;
+                };
+                let request = String::from_utf8_lossy(&request[..len]);
+                let response = if request.starts_with("GET /metrics ") {
+                    let body = render(&sources).await;
+                    format!(
+                        "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        body.len(),
+                        body
+                    )
+                } else {
+                    "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                        .to_string()
+                };
+                let _ = stream.write_all(response.as_bytes()).await;
+            });
+        }
+    });
+    Ok(())
+}
@@ -1,6 +1,7 @@
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::sync::atomic::AtomicU64;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU64};
 use std::sync::Arc;
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -15,6 +16,260 @@ pub struct ModelInfo {
     pub tags: Vec<String>,
     #[serde(rename = "lastModified", default)]
     pub last_modified: Option<String>,
+    #[serde(rename = "createdAt", default)]
+    pub created_at: Option<String>,
+    /// Gated status: "auto", "manual", false, or true
+    #[serde(default)]
+    pub gated: serde_json::Value,
+    #[serde(default)]
+    pub private: bool,
+    /// Whether the file listing sent with `full=true` results has GGUF files
+    #[serde(
+        rename(deserialize = "siblings"),
+        default,
+        deserialize_with = "lists_gguf"
+    )]
+    pub has_gguf_files: bool,
+    /// Parameter counts, when the Hub includes them in the listing
+    #[serde(default, skip_serializing)]
+    pub safetensors: Option<SafetensorsInfo>,
+    /// Total size of the repository's files in bytes; search listings leave
+    /// it out, see [`crate::api::repo_sizes`]
+    #[serde(
+        rename = "usedStorage",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub used_storage: Option<u64>,
+    /// Commit of the main branch; sent for a single model, not in search listings
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha: Option<String>,
+}
+
+impl ModelInfo {
+    /// Quantization format guessed from the tags, repository name and, for
+    /// GGUF, the file names sent with search results
+    pub fn quant_format(&self) -> Option<QuantFormat> {
+        if self.has_gguf_files {
+            return Some(QuantFormat::Gguf);
+        }
+        QuantFormat::from_tags_and_name(&self.tags, &self.id)
+    }
+
+    pub fn is_gated(&self) -> bool {
+        is_gated(&self.gated)
+    }
+
+    /// Whether the repository is tagged as adult content
+    pub fn is_nsfw(&self) -> bool {
+        self.tags
+            .iter()
+            .any(|tag| NSFW_TAGS.iter().any(|nsfw| tag.eq_ignore_ascii_case(nsfw)))
+    }
+
+    /// License from the Hub's `license:` tag and gating
+    pub fn license_notice(&self) -> LicenseNotice {
+        LicenseNotice {
+            license: license_tag(&self.tags),
+            gated: self.is_gated(),
+        }
+    }
+
+    /// Model this repository was derived from, from the Hub's `base_model:`
+    /// tags (`base_model:quantized:org/name` and the like)
+    pub fn base_model(&self) -> Option<&str> {
+        base_model_tag(&self.tags)
+    }
+
+    /// Parameter count from the Hub, or from the name (`Llama-3.1-8B`, `Mixtral-8x7B`)
+    pub fn parameter_count(&self) -> Option<u64> {
+        self.safetensors
+            .as_ref()
+            .and_then(|s| s.total)
+            .or_else(|| params_from_name(&self.id))
+    }
+
+    /// Rough size class from the parameter count: XS (<4B), S (<15B),
+    /// M (<40B), L (<100B) or XL
+    pub fn size_class(&self) -> Option<&'static str> {
+        const B: u64 = 1_000_000_000;
+        self.parameter_count().map(|params| match params {
+            p if p < 4 * B => "XS",
+            p if p < 15 * B => "S",
+            p if p < 40 * B => "M",
+            p if p < 100 * B => "L",
+            _ => "XL",
+        })
+    }
+
+    /// First status bar line for the selected result, with the exact date
+    /// behind the list's "3 days ago"
+    pub fn selection_summary(&self) -> String {
+        let mut summary = format!(
+            "Selected: {} | URL: https://huggingface.co/{}",
+            self.id, self.id
+        );
+        if let Some(modified) = self
+            .last_modified
+            .as_deref()
+            .and_then(crate::utils::format_absolute_date)
+        {
+            summary.push_str(&format!(" | Modified: {}", modified));
+        }
+        summary
+    }
+}
+
+/// Tags that mark adult content; the Hub itself uses `not-for-all-audiences`
+const NSFW_TAGS: &[&str] = &["not-for-all-audiences", "nsfw"];
+
+/// Whether a Hub `gated` field ("auto", "manual", true or false) requires a token
+pub fn is_gated(gated: &serde_json::Value) -> bool {
+    match gated {
+        serde_json::Value::String(s) => s == "auto" || s == "manual",
+        serde_json::Value::Bool(gated) => *gated,
+        _ => false,
+    }
+}
+
+/// Direct base model named by the `base_model:` tags
+fn base_model_tag(tags: &[String]) -> Option<&str> {
+    let bases = tags.iter().filter_map(|t| t.strip_prefix("base_model:"));
+    // The plain tag names the direct base; relation tags repeat it
+    bases.clone().find(|base| !base.contains(':')).or_else(|| {
+        bases
+            .filter_map(|base| base.split_once(':').map(|(_, base)| base))
+            .next()
+    })
+}
+
+/// License id from a Hub `license:apache-2.0` tag
+fn license_tag(tags: &[String]) -> Option<String> {
+    tags.iter()
+        .find_map(|t| t.strip_prefix("license:"))
+        .map(str::to_string)
+}
+
+/// First parameter count written in a repository name, e.g. "8B", "1.5B",
+/// "270M" or "8x7B" (experts × size)
+fn params_from_name(model_id: &str) -> Option<u64> {
+    let name = model_id.rsplit('/').next().unwrap_or(model_id);
+    name.split(|c: char| !c.is_ascii_alphanumeric() && c != '.')
+        .find_map(|segment| {
+            let segment = segment.to_ascii_lowercase();
+            let (experts, size) = match segment.split_once('x') {
+                Some((experts, size)) => (experts.parse::<u64>().ok()?, size),
+                None => (1, segment.as_str()),
+            };
+            let scale = match size.chars().last()? {
+                'm' => 1e6,
+                'b' => 1e9,
+                't' => 1e12,
+                _ => return None,
+            };
+            let value: f64 = size[..size.len() - 1].parse().ok()?;
+            Some((experts as f64 * value * scale) as u64)
+        })
+}
+
+fn lists_gguf<'de, D: Deserializer<'de>>(deserializer: D) -> Result<bool, D::Error> {
+    #[derive(Deserialize)]
+    struct Sibling {
+        rfilename: String,
+    }
+    let siblings = Option::<Vec<Sibling>>::deserialize(deserializer)?.unwrap_or_default();
+    Ok(siblings
+        .iter()
+        .any(|f| f.rfilename.ends_with(".gguf") || f.rfilename.contains(".gguf.part")))
+}
+
+/// Weight format a repository is published in
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QuantFormat {
+    Gguf,
+    Exl2,
+    Exl3,
+    Awq,
+    Gptq,
+    /// Unquantized safetensors; the dtype ("bf16", "fp16", ...) when the Hub reports one
+    Safetensors(Option<String>),
+}
+
+impl QuantFormat {
+    /// Quantized formats, matched against tags and name segments in this order
+    const QUANTIZED: [(&'static str, QuantFormat); 5] = [
+        ("gguf", QuantFormat::Gguf),
+        ("exl2", QuantFormat::Exl2),
+        ("exl3", QuantFormat::Exl3),
+        ("awq", QuantFormat::Awq),
+        ("gptq", QuantFormat::Gptq),
+    ];
+
+    /// Classify from Hub tags and the repository name, e.g. "…-AWQ" or "…-exl2"
+    pub fn from_tags_and_name(tags: &[String], model_id: &str) -> Option<QuantFormat> {
+        let name = model_id
+            .rsplit('/')
+            .next()
+            .unwrap_or(model_id)
+            .to_lowercase();
+        let name_has = |key: &str| {
+            name.split(|c: char| !c.is_ascii_alphanumeric())
+                .any(|segment| segment == key)
+        };
+
+        Self::QUANTIZED
+            .iter()
+            .find(|(key, _)| tags.iter().any(|t| t.eq_ignore_ascii_case(key)) || name_has(key))
+            .map(|(_, format)| format.clone())
+            .or_else(|| {
+                tags.iter()
+                    .any(|t| t == "safetensors")
+                    .then_some(QuantFormat::Safetensors(None))
+            })
+    }
+
+    /// Short badge, e.g. "EXL2" or "safetensors-bf16"
+    pub fn label(&self) -> String {
+        match self {
+            QuantFormat::Gguf => "GGUF".to_string(),
+            QuantFormat::Exl2 => "EXL2".to_string(),
+            QuantFormat::Exl3 => "EXL3".to_string(),
+            QuantFormat::Awq => "AWQ".to_string(),
+            QuantFormat::Gptq => "GPTQ".to_string(),
+            QuantFormat::Safetensors(Some(dtype)) => format!("safetensors-{}", dtype),
+            QuantFormat::Safetensors(None) => "safetensors".to_string(),
+        }
+    }
+
+    /// Which files a working download of this format needs
+    pub fn guidance(&self) -> &'static str {
+        match self {
+            QuantFormat::Gguf => {
+                "one .gguf per quant (every part of split files); runs in llama.cpp, Ollama, \
+                 LM Studio"
+            }
+            QuantFormat::Exl2 => {
+                "config.json, tokenizer files and all .safetensors of one bpw (often one branch \
+                 per bpw); runs in ExLlamaV2 / TabbyAPI"
+            }
+            QuantFormat::Exl3 => {
+                "config.json, tokenizer files and all .safetensors of one bpw (often one branch \
+                 per bpw); runs in ExLlamaV3 / TabbyAPI"
+            }
+            QuantFormat::Awq => {
+                "config.json (with quantization_config), tokenizer files and all .safetensors; \
+                 runs in vLLM, TGI, AutoAWQ"
+            }
+            QuantFormat::Gptq => {
+                "config.json, quantize_config.json, tokenizer files and all .safetensors; \
+                 runs in vLLM, TGI, GPTQModel"
+            }
+            QuantFormat::Safetensors(_) => {
+                "config.json, tokenizer files and all .safetensors shards; .bin/.pth copies \
+                 are optional (Tab in the download dialog: weights only)"
+            }
+        }
+    }
 }
 
 /// Extended model metadata from /api/models/{model_id}
@@ -26,7 +281,11 @@ pub struct ModelMetadata {
     pub library_name: Option<String>,
     #[serde(default)]
     pub pipeline_tag: Option<String>,
-    #[serde(default)]
+    #[serde(rename = "lastModified", default)]
+    pub last_modified: Option<String>,
+    #[serde(rename = "createdAt", default)]
+    pub created_at: Option<String>,
+    #[serde(rename = "cardData", default)]
     pub card_data: Option<ModelCardData>,
     #[serde(default)]
     pub siblings: Vec<RepoFile>, // All files in the repo
@@ -35,21 +294,404 @@ pub struct ModelMetadata {
     /// Gated status: "auto", "manual", false, or true
     #[serde(default)]
     pub gated: serde_json::Value,
+    /// Parameter counts computed by the Hub from safetensors headers
+    #[serde(default)]
+    pub safetensors: Option<SafetensorsInfo>,
+    /// Parameter count and header info computed by the Hub for GGUF repos
+    #[serde(default)]
+    pub gguf: Option<GgufInfo>,
+    /// Finetune lineage: base model, its base model, ... (filled by `api::fetch_base_model_chain`
+    /// where it is shown)
+    #[serde(skip)]
+    pub base_model_chain: Vec<String>,
+    /// Sharded checkpoints listed by `*.index.json` files (filled by `api::fetch_model_metadata`)
+    #[serde(skip)]
+    pub weight_shards: Vec<WeightShards>,
+    /// Architecture summary from `config.json` of Standard repos (filled by `api::fetch_model_metadata`)
+    #[serde(skip)]
+    pub model_config: Option<ModelConfigSummary>,
+}
+
+/// The fields of a Transformers `config.json` checked before downloading
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ModelConfigSummary {
+    /// First of `architectures`, else `model_type`, e.g. "LlamaForCausalLM"
+    pub architecture: Option<String>,
+    pub hidden_size: Option<u64>,
+    pub num_layers: Option<u64>,
+    /// `max_position_embeddings` (or its older names)
+    pub context_length: Option<u64>,
+    /// `rope_scaling`, e.g. "yarn ×4 (from 32768)"
+    pub rope_scaling: Option<String>,
+}
+
+impl ModelConfigSummary {
+    /// Read the summary from a parsed `config.json`. Multimodal configs keep
+    /// the language model's fields in `text_config` (or `llm_config`), which
+    /// is used for whatever the top level lacks.
+    pub fn from_config(config: &serde_json::Value) -> Self {
+        let nested: Vec<&serde_json::Value> = ["text_config", "llm_config"]
+            .into_iter()
+            .filter_map(|key| config.get(key))
+            .collect();
+        let field = |keys: &[&str]| -> Option<&serde_json::Value> {
+            std::iter::once(config)
+                .chain(nested.iter().copied())
+                .flat_map(|section| keys.iter().filter_map(move |key| section.get(*key)))
+                .find(|value| !value.is_null())
+        };
+        let number = |keys: &[&str]| field(keys).and_then(|value| value.as_u64());
+
+        let architecture = config
+            .get("architectures")
+            .and_then(|list| list.get(0))
+            .and_then(|name| name.as_str())
+            .or_else(|| field(&["model_type"]).and_then(|value| value.as_str()))
+            .map(str::to_string);
+
+        Self {
+            architecture,
+            hidden_size: number(&["hidden_size", "n_embd", "d_model"]),
+            num_layers: number(&["num_hidden_layers", "n_layer", "num_layers"]),
+            context_length: number(&[
+                "max_position_embeddings",
+                "n_positions",
+                "max_sequence_length",
+                "seq_length",
+            ]),
+            rope_scaling: field(&["rope_scaling"]).and_then(describe_rope_scaling),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// "<type> ×<factor> (from <original context>)" for a `rope_scaling` object;
+/// parts the config leaves out are left out too
+fn describe_rope_scaling(scaling: &serde_json::Value) -> Option<String> {
+    let kind = scaling
+        .get("rope_type")
+        .or_else(|| scaling.get("type"))
+        .and_then(|value| value.as_str());
+    // "default" is no scaling (newer configs spell out the plain RoPE this way)
+    if kind == Some("default") {
+        return None;
+    }
+    let factor = scaling.get("factor").and_then(|value| value.as_f64());
+    let original = scaling
+        .get("original_max_position_embeddings")
+        .and_then(|value| value.as_u64());
+
+    let mut parts = Vec::new();
+    parts.push(kind.unwrap_or("scaled").to_string());
+    if let Some(factor) = factor {
+        parts.push(format!("×{}", factor));
+    }
+    if let Some(original) = original {
+        parts.push(format!("(from {})", original));
+    }
+    Some(parts.join(" "))
+}
+
+/// A sharded checkpoint, e.g. `model.safetensors.index.json` and the
+/// `model-0000N-of-00014.safetensors` files its `weight_map` points at
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WeightShards {
+    /// Repository path of the index file
+    pub index_file: String,
+    /// Weight format from the index name: "safetensors", "bin", "pth" or "pt"
+    pub format: String,
+    /// Repository paths of the shards, sorted
+    pub shards: Vec<String>,
+}
+
+impl ModelMetadata {
+    pub fn is_gated(&self) -> bool {
+        is_gated(&self.gated)
+    }
+
+    /// Total parameter count, if the Hub reports one
+    pub fn parameter_count(&self) -> Option<u64> {
+        self.safetensors
+            .as_ref()
+            .and_then(|s| s.total)
+            .or_else(|| self.gguf.as_ref().and_then(|g| g.total))
+    }
+
+    /// Quantization format from the tags, name and file listing
+    pub fn quant_format(&self) -> Option<QuantFormat> {
+        let mut paths = self.siblings.iter().map(|f| f.rfilename.as_str());
+
+        if paths
+            .clone()
+            .any(|path| path.ends_with(".gguf") || path.contains(".gguf.part"))
+        {
+            return Some(QuantFormat::Gguf);
+        }
+        match QuantFormat::from_tags_and_name(&self.tags, &self.model_id) {
+            Some(QuantFormat::Safetensors(_)) | None => {}
+            format => return format,
+        }
+        // AutoGPTQ writes its settings next to the weights
+        if paths
+            .clone()
+            .any(|path| path.ends_with("quantize_config.json"))
+        {
+            return Some(QuantFormat::Gptq);
+        }
+        paths
+            .any(|path| path.ends_with(".safetensors"))
+            .then(|| QuantFormat::Safetensors(self.dominant_dtype()))
+    }
+
+    /// Most common parameter dtype reported by the Hub, e.g. "bf16"
+    fn dominant_dtype(&self) -> Option<String> {
+        self.safetensors
+            .as_ref()?
+            .parameters
+            .iter()
+            .max_by_key(|(_, count)| **count)
+            .map(|(dtype, _)| match dtype.to_lowercase().as_str() {
+                "f16" => "fp16".to_string(),
+                "f32" => "fp32".to_string(),
+                other => other.to_string(),
+            })
+    }
+
+    /// License from the card, falling back to the Hub's `license:` tag
+    pub fn license(&self) -> Option<String> {
+        self.card_data
+            .as_ref()
+            .and_then(|card| card.license.clone())
+            .or_else(|| license_tag(&self.tags))
+    }
+
+    /// License and gating, as shown before a download
+    pub fn license_notice(&self) -> LicenseNotice {
+        LicenseNotice {
+            license: self.license(),
+            gated: self.is_gated(),
+        }
+    }
+
+    /// Direct base model from the card, falling back to the Hub's `base_model:` tags
+    pub fn base_model(&self) -> Option<String> {
+        self.card_data
+            .as_ref()
+            .and_then(|card| card.base_model.clone())
+            .or_else(|| base_model_tag(&self.tags).map(str::to_string))
+    }
+
+    /// Layout with its own pipeline preset, from `library_name` (or the
+    /// layout's marker file) and, for speech models, `pipeline_tag`
+    pub fn layout(&self) -> Option<RepoLayout> {
+        let has_file = |name: &str| self.siblings.iter().any(|f| f.rfilename == name);
+        let library = self.library_name.as_deref().unwrap_or("");
+        if library == "diffusers" || has_file("model_index.json") {
+            Some(RepoLayout::Diffusers)
+        } else if library == "sentence-transformers" || has_file("modules.json") {
+            Some(RepoLayout::SentenceTransformers)
+        } else if matches!(
+            self.pipeline_tag.as_deref(),
+            Some(
+                "automatic-speech-recognition"
+                    | "text-to-speech"
+                    | "text-to-audio"
+                    | "audio-to-audio"
+                    | "audio-classification"
+            )
+        ) {
+            Some(RepoLayout::Speech)
+        } else {
+            None
+        }
+    }
+
+    /// True for LoRA / PEFT adapter repositories
+    pub fn is_adapter(&self) -> bool {
+        self.library_name.as_deref() == Some("peft")
+            || self.tags.iter().any(|t| t == "peft" || t == "lora")
+    }
+
+    /// Base model an adapter applies to
+    /// Taken from the card's `base_model`, falling back to the Hub's `base_model:adapter:` tag
+    pub fn adapter_base_model(&self) -> Option<String> {
+        if !self.is_adapter() {
+            return None;
+        }
+        self.card_data
+            .as_ref()
+            .and_then(|card| card.base_model.clone())
+            .or_else(|| {
+                self.tags
+                    .iter()
+                    .find_map(|t| t.strip_prefix("base_model:adapter:"))
+                    .map(str::to_string)
+            })
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
-pub struct ModelCardData {
+pub struct SafetensorsInfo {
     #[serde(default)]
-    pub base_model: Option<String>,
+    pub total: Option<u64>,
+    /// Parameter count per dtype, e.g. {"BF16": 8030261248}
     #[serde(default)]
-    pub license: Option<String>,
+    pub parameters: HashMap<String, u64>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GgufInfo {
     #[serde(default)]
+    pub total: Option<u64>,
+}
+
+/// Model card front matter
+///
+/// Card fields are free-form YAML, so list fields also accept a single string
+/// (and `base_model` a list, keeping the first entry) instead of failing the
+/// whole metadata request.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ModelCardData {
+    #[serde(default, deserialize_with = "first_of_one_or_many")]
+    pub base_model: Option<String>,
+    #[serde(default, deserialize_with = "first_of_one_or_many")]
+    pub license: Option<String>,
+    #[serde(default, deserialize_with = "one_or_many")]
     pub language: Option<Vec<String>>,
-    #[serde(default)]
-    #[allow(dead_code)]
+    #[serde(default, deserialize_with = "one_or_many")]
     pub datasets: Option<Vec<String>>,
 }
 
+impl ModelCardData {
+    /// Whether any of the fields shown in the metadata pane is missing
+    pub fn is_sparse(&self) -> bool {
+        self.base_model.is_none()
+            || self.license.is_none()
+            || self.language.is_none()
+            || self.datasets.is_none()
+    }
+
+    /// Take the fields this card lacks from `other`
+    pub fn fill_missing(&mut self, other: ModelCardData) {
+        self.base_model = self.base_model.take().or(other.base_model);
+        self.license = self.license.take().or(other.license);
+        self.language = self.language.take().or(other.language);
+        self.datasets = self.datasets.take().or(other.datasets);
+    }
+
+    /// Read the card fields from the YAML front matter of a README
+    ///
+    /// Only what cards use for these fields is understood: `key: value`,
+    /// `key: [a, b]` and `key:` followed by `- item` lines. `None` when the
+    /// README has no front matter.
+    pub fn from_front_matter(readme: &str) -> Option<Self> {
+        let readme = readme.trim_start_matches('\u{feff}');
+        let mut lines = readme.lines();
+        if lines.next()?.trim_end() != "---" {
+            return None;
+        }
+
+        let mut fields: HashMap<String, Vec<String>> = HashMap::new();
+        let mut list_key: Option<String> = None;
+        let mut closed = false;
+        for line in lines {
+            if line.trim_end() == "---" {
+                closed = true;
+                break;
+            }
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            if line.starts_with(char::is_whitespace) || trimmed.starts_with('-') {
+                // Items of a block list; nested mappings are ignored
+                if let (Some(key), Some(item)) = (&list_key, trimmed.strip_prefix('-')) {
+                    let item = yaml_scalar(item);
+                    if !item.is_empty() && !item.contains(": ") {
+                        fields.entry(key.clone()).or_default().push(item);
+                    }
+                }
+                continue;
+            }
+
+            list_key = None;
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let key = key.trim().to_string();
+            let value = value.trim();
+            if value.is_empty() {
+                list_key = Some(key);
+            } else if let Some(inline) = value.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+                let items = inline
+                    .split(',')
+                    .map(yaml_scalar)
+                    .filter(|item| !item.is_empty())
+                    .collect();
+                fields.insert(key, items);
+            } else {
+                fields.insert(key, vec![yaml_scalar(value)]);
+            }
+        }
+        if !closed {
+            return None;
+        }
+
+        let mut take = |key: &str| fields.remove(key).filter(|values| !values.is_empty());
+        Some(ModelCardData {
+            base_model: take("base_model").and_then(|values| values.into_iter().next()),
+            license: take("license").and_then(|values| values.into_iter().next()),
+            language: take("language"),
+            datasets: take("datasets"),
+        })
+    }
+}
+
+/// A YAML scalar without its quotes and trailing comment
+fn yaml_scalar(value: &str) -> String {
+    let value = value.trim();
+    for quote in ['"', '\''] {
+        if let Some(inner) = value
+            .strip_prefix(quote)
+            .and_then(|v| v.split(quote).next())
+        {
+            return inner.to_string();
+        }
+    }
+    value
+        .split(" #")
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_string()
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum OneOrMany {
+    One(String),
+    Many(Vec<String>),
+    #[allow(dead_code)] // Any other YAML value is ignored
+    Other(serde_json::Value),
+}
+
+fn one_or_many<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Vec<String>>, D::Error> {
+    Ok(match Option::<OneOrMany>::deserialize(deserializer)? {
+        Some(OneOrMany::One(value)) => Some(vec![value]),
+        Some(OneOrMany::Many(values)) => Some(values),
+        Some(OneOrMany::Other(_)) | None => None,
+    })
+}
+
+fn first_of_one_or_many<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<String>, D::Error> {
+    Ok(one_or_many(deserializer)?.and_then(|values| values.into_iter().next()))
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct RepoFile {
     pub rfilename: String, // API uses 'rfilename' for relative path
@@ -58,6 +700,88 @@ pub struct RepoFile {
     #[serde(default)]
     #[allow(dead_code)]
     pub lfs: Option<LfsInfo>, // Reuse existing LfsInfo struct
+    /// Git blob id (SHA-1) from the tree API; the only checksum of non-LFS files
+    #[serde(default)]
+    pub oid: Option<String>,
+}
+
+impl RepoFile {
+    /// Checksum to verify the download against: the LFS SHA-256, or the git
+    /// blob SHA-1 for small files stored directly in git
+    pub fn expected_hash(&self) -> Option<ExpectedHash> {
+        ExpectedHash::of(self.lfs.as_ref(), self.oid.as_deref())
+    }
+}
+
+/// Checksum a download is verified against, tagged with its kind so a git
+/// blob id is never taken for a SHA-256 (or the other way round)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ExpectedHash {
+    /// LFS object id: SHA-256 of the content (64 hex digits)
+    Sha256(String),
+    /// Git blob id of a non-LFS file: SHA-1 of `blob <len>\0` + content (40 hex digits)
+    GitBlob(String),
+}
+
+impl ExpectedHash {
+    /// Hash of a file in a tree listing: the LFS SHA-256, or the git blob id
+    /// of a file stored directly in git
+    pub fn of(lfs: Option<&LfsInfo>, git_oid: Option<&str>) -> Option<Self> {
+        match (lfs, git_oid) {
+            (Some(lfs), _) => Some(Self::Sha256(lfs.oid.clone())),
+            (None, Some(oid)) => Some(Self::GitBlob(oid.to_string())),
+            (None, None) => None,
+        }
+    }
+
+    /// From the registry's `expected_sha256` and `expected_git_blob` fields
+    pub fn from_fields(sha256: Option<&String>, git_blob: Option<&String>) -> Option<Self> {
+        sha256
+            .map(|hash| Self::Sha256(hash.clone()))
+            .or_else(|| git_blob.map(|hash| Self::GitBlob(hash.clone())))
+    }
+
+    /// The registry's `expected_sha256` and `expected_git_blob` fields
+    pub fn fields(hash: Option<&Self>) -> (Option<String>, Option<String>) {
+        (
+            hash.and_then(Self::sha256).map(str::to_string),
+            hash.and_then(Self::git_blob).map(str::to_string),
+        )
+    }
+
+    /// The hex digits, whatever the kind
+    pub fn hex(&self) -> &str {
+        match self {
+            Self::Sha256(hash) | Self::GitBlob(hash) => hash,
+        }
+    }
+
+    /// The SHA-256, if this is one
+    pub fn sha256(&self) -> Option<&str> {
+        match self {
+            Self::Sha256(hash) => Some(hash),
+            Self::GitBlob(_) => None,
+        }
+    }
+
+    /// The git blob id, if this is one
+    pub fn git_blob(&self) -> Option<&str> {
+        match self {
+            Self::GitBlob(hash) => Some(hash),
+            Self::Sha256(_) => None,
+        }
+    }
+}
+
+/// A model (and optionally a single file) referenced by a pasted
+/// huggingface.co URL or a bare `author/model` ID
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModelReference {
+    pub model_id: String,
+    /// Branch, tag or commit from `blob/`, `resolve/` or `tree/` URLs
+    pub revision: Option<String>,
+    /// Repository-relative file path from `blob/` or `resolve/` URLs
+    pub file_path: Option<String>,
 }
 
 /// Tree node for hierarchical file display
@@ -70,6 +794,8 @@ pub struct FileTreeNode {
     pub children: Vec<FileTreeNode>,
     pub expanded: bool,
     pub depth: usize,
+    /// Number of shards when this is the virtual group of a sharded checkpoint
+    pub shard_count: Option<usize>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -87,6 +813,9 @@ pub struct ModelFile {
     pub path: String,
     #[serde(default)]
     pub size: u64,
+    /// Git object id (changes whenever the file content changes)
+    #[serde(default)]
+    pub oid: Option<String>,
     #[serde(default)]
     pub lfs: Option<LfsInfo>,
 }
@@ -96,7 +825,9 @@ pub struct QuantizationInfo {
     pub quant_type: String,
     pub filename: String,
     pub size: u64,
-    pub sha256: Option<String>,
+    pub expected_hash: Option<ExpectedHash>,
+    /// Branch the file is published on; `None` for main
+    pub revision: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -104,9 +835,419 @@ pub struct QuantizationGroup {
     pub quant_type: String,
     pub files: Vec<QuantizationInfo>, // All files in this quantization type
     pub total_size: u64,
+    /// Header summary of the group's first GGUF file, filled in the
+    /// background after the list loads (`api::fetch_gguf_header_summary`)
+    pub header: Option<GgufHeaderSummary>,
+}
+
+/// Architecture and context settings from a GGUF header
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GgufHeaderSummary {
+    /// `general.architecture`, e.g. "llama"
+    pub architecture: Option<String>,
+    /// `<architecture>.context_length`
+    pub context_length: Option<u64>,
+    /// `<architecture>.rope.scaling.*`, e.g. "yarn ×4 (from 32768)"
+    pub rope_scaling: Option<String>,
 }
 
+/// Which files of a Standard repository a download takes (`Tab` in the
+/// download popup, `download --preset` in headless mode)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RepoPreset {
+    /// Every file in the repository
+    #[default]
+    All,
+    /// The preferred weights plus configs and tokenizers, without duplicate
+    /// weight formats or exports for other runtimes
+    Weights,
+    /// Only what transformers loads: configs, tokenizer files, weights (with
+    /// their shard index) and remote code
+    Essential,
+    /// One runtime's export directory plus the root configs and tokenizer
+    Runtime(Runtime),
+    /// What the library of the repository's layout loads, e.g. the component
+    /// folders of a diffusers pipeline in one precision variant
+    Pipeline(RepoLayout, WeightVariant),
+}
+
+impl RepoPreset {
+    /// Every preset, in `--preset` order
+    pub fn all() -> Vec<Self> {
+        let mut presets = vec![RepoPreset::All, RepoPreset::Weights, RepoPreset::Essential];
+        presets.extend(
+            WeightVariant::ALL
+                .into_iter()
+                .map(|variant| RepoPreset::Pipeline(RepoLayout::Diffusers, variant)),
+        );
+        presets.push(RepoPreset::Pipeline(
+            RepoLayout::SentenceTransformers,
+            WeightVariant::Default,
+        ));
+        presets.push(RepoPreset::Pipeline(
+            RepoLayout::Speech,
+            WeightVariant::Default,
+        ));
+        presets.extend(Runtime::ALL.into_iter().map(RepoPreset::Runtime));
+        presets
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            RepoPreset::All => "all",
+            RepoPreset::Weights => "weights",
+            RepoPreset::Essential => "essential",
+            RepoPreset::Runtime(runtime) => runtime.name(),
+            RepoPreset::Pipeline(RepoLayout::Diffusers, WeightVariant::Default) => "diffusers",
+            RepoPreset::Pipeline(RepoLayout::Diffusers, WeightVariant::Fp16) => "diffusers-fp16",
+            RepoPreset::Pipeline(RepoLayout::Diffusers, WeightVariant::Bf16) => "diffusers-bf16",
+            RepoPreset::Pipeline(layout, _) => layout.name(),
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        RepoPreset::all()
+            .into_iter()
+            .find(|preset| preset.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Description in the download popup, e.g. "weights only"
+    pub fn label(&self) -> String {
+        match self {
+            RepoPreset::All => "all files".to_string(),
+            RepoPreset::Weights => "weights only".to_string(),
+            RepoPreset::Essential => "essential only".to_string(),
+            RepoPreset::Runtime(runtime) => format!("{} export only", runtime.label()),
+            RepoPreset::Pipeline(layout, variant) => match variant.suffix() {
+                Some(suffix) => format!("{} ({})", layout.label(), suffix),
+                None => layout.label().to_string(),
+            },
+        }
+    }
+}
+
+/// Library layout of a repository that has its own pipeline preset (see
+/// `ModelMetadata::layout`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RepoLayout {
+    /// diffusers: `model_index.json` and a folder per component (unet, vae, ...)
+    Diffusers,
+    /// sentence-transformers: `modules.json` and numbered module folders
+    SentenceTransformers,
+    /// Speech recognition and synthesis models
+    Speech,
+}
+
+impl RepoLayout {
+    pub fn name(&self) -> &'static str {
+        match self {
+            RepoLayout::Diffusers => "diffusers",
+            RepoLayout::SentenceTransformers => "sentence-transformers",
+            RepoLayout::Speech => "speech",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            RepoLayout::Diffusers => "diffusers pipeline",
+            RepoLayout::SentenceTransformers => "sentence-transformers model",
+            RepoLayout::Speech => "speech model",
+        }
+    }
+}
+
+/// Precision variant of diffusers component weights, the `fp16` of
+/// `diffusion_pytorch_model.fp16.safetensors`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WeightVariant {
+    /// Weights without a variant suffix (usually fp32)
+    Default,
+    Fp16,
+    Bf16,
+}
+
+impl WeightVariant {
+    pub const ALL: [WeightVariant; 3] = [
+        WeightVariant::Default,
+        WeightVariant::Fp16,
+        WeightVariant::Bf16,
+    ];
+
+    pub fn suffix(&self) -> Option<&'static str> {
+        match self {
+            WeightVariant::Default => None,
+            WeightVariant::Fp16 => Some("fp16"),
+            WeightVariant::Bf16 => Some("bf16"),
+        }
+    }
+}
+
+/// Runtime an export directory of a repository (`onnx/`, `coreml/`, ...) serves
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Runtime {
+    Onnx,
+    OpenVino,
+    CoreMl,
+    TensorRt,
+    TfLite,
+}
+
+impl Runtime {
+    pub const ALL: [Runtime; 5] = [
+        Runtime::Onnx,
+        Runtime::OpenVino,
+        Runtime::CoreMl,
+        Runtime::TensorRt,
+        Runtime::TfLite,
+    ];
+
+    /// Name for `--preset`
+    pub fn name(&self) -> &'static str {
+        match self {
+            Runtime::Onnx => "onnx",
+            Runtime::OpenVino => "openvino",
+            Runtime::CoreMl => "coreml",
+            Runtime::TensorRt => "tensorrt",
+            Runtime::TfLite => "tflite",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Runtime::Onnx => "ONNX",
+            Runtime::OpenVino => "OpenVINO",
+            Runtime::CoreMl => "Core ML",
+            Runtime::TensorRt => "TensorRT",
+            Runtime::TfLite => "TFLite",
+        }
+    }
+
+    /// The runtime of an export directory name (case-insensitive)
+    pub fn from_dir(dir: &str) -> Option<Self> {
+        let dir = dir.to_ascii_lowercase();
+        Runtime::ALL.into_iter().find(|runtime| match runtime {
+            Runtime::TensorRt => matches!(dir.as_str(), "tensorrt" | "tensorrt_llm" | "trt"),
+            _ => dir == runtime.name(),
+        })
+    }
+}
+
+/// What the download path popup is about to queue
+#[derive(Debug, Clone, Default)]
+pub struct DownloadSummary {
+    /// Quantization type(s), file name or repository being downloaded
+    pub label: String,
+    pub files: usize,
+    pub bytes: u64,
+    /// Free space at the destination, refreshed as the path is edited
+    pub free_space: Option<u64>,
+    /// Quota of the destination directory, refreshed with `free_space`
+    pub quota: Option<crate::quota::QuotaUsage>,
+    /// Download root the placement rules pick for this download
+    pub placement: Option<crate::placement::Placement>,
+    /// Recent average download speed (MB/s) used for the ETA
+    pub speed_mbps: Option<f64>,
+    /// (part, total) when the selected file is one part of a split set
+    pub part_of: Option<(u32, u32)>,
+    /// Preset of a repository download, when the repository has presets
+    /// other than "all"
+    pub preset: Option<RepoPreset>,
+    /// Preset `Tab` switches to
+    pub next_preset: RepoPreset,
+    /// Files the preset leaves out
+    pub skipped_files: usize,
+    /// License of the model being downloaded, when its metadata is loaded
+    pub license: Option<LicenseNotice>,
+    /// Queue position of the new downloads (Ctrl+P in the popup)
+    pub priority: DownloadPriority,
+    /// Whether to convert a repository download to GGUF afterwards (Ctrl+G
+    /// in the popup); `None` for single files and quantizations
+    pub convert_to_gguf: Option<bool>,
+}
+
+/// A model's license, surfaced in the download popup and headless plans
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LicenseNotice {
+    /// License id from the card, e.g. "apache-2.0" or "llama3.1"
+    pub license: Option<String>,
+    /// The Hub makes users accept the terms before downloading
+    pub gated: bool,
+}
+
+/// Licenses that allow use, modification and redistribution with no more
+/// than attribution
+const PERMISSIVE_LICENSES: &[&str] = &[
+    "apache-2.0",
+    "mit",
+    "bsd",
+    "bsd-2-clause",
+    "bsd-3-clause",
+    "bsd-3-clause-clear",
+    "bsl-1.0",
+    "isc",
+    "zlib",
+    "unlicense",
+    "cc0-1.0",
+    "cc-by-2.0",
+    "cc-by-2.5",
+    "cc-by-3.0",
+    "cc-by-4.0",
+    "odc-by",
+    "pddl",
+    "ecl-2.0",
+    "afl-3.0",
+    "postgresql",
+];
+
+impl LicenseNotice {
+    /// A permissive license on an ungated repository; anything else
+    /// (including no license at all) may restrict how the weights are used
+    pub fn is_permissive(&self) -> bool {
+        !self.gated
+            && self
+                .license
+                .as_deref()
+                .is_some_and(|license| PERMISSIVE_LICENSES.contains(&license))
+    }
+
+    /// What the terms ask of the user, e.g. "requires acceptance"
+    pub fn terms(&self) -> &'static str {
+        let Some(license) = self.license.as_deref() else {
+            return if self.gated {
+                "requires acceptance"
+            } else {
+                "no license declared"
+            };
+        };
+        if self.gated {
+            "requires acceptance"
+        } else if PERMISSIVE_LICENSES.contains(&license) {
+            "permissive"
+        } else if license.contains("-nc") || license.contains("noncommercial") {
+            "non-commercial"
+        } else if license.contains("gpl") || license.contains("-sa") {
+            "copyleft"
+        } else {
+            // Model-specific licenses (llama3.1, gemma, openrail, other, ...)
+            "requires acceptance"
+        }
+    }
+}
+
+impl std::fmt::Display for LicenseNotice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} — {}",
+            self.license.as_deref().unwrap_or("unknown"),
+            self.terms()
+        )
+    }
+}
+
+/// One file of a dry-run download plan
 #[derive(Debug, Clone)]
+pub struct PlannedDownload {
+    pub local_path: PathBuf,
+    pub size: u64,
+    /// A file already exists at `local_path`
+    pub exists: bool,
+}
+
+/// Chat template(s) and special tokens a model expects its prompts in
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PromptFormat {
+    /// File the format was read from (a GGUF or `tokenizer_config.json`)
+    pub source: String,
+    /// (name, Jinja template); the unnamed template is called "default"
+    pub chat_templates: Vec<(String, String)>,
+    /// (role, token text), e.g. ("bos", "<|begin_of_text|>")
+    pub special_tokens: Vec<(String, String)>,
+}
+
+/// The prompt-related fields of a Transformers `tokenizer_config.json`
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TokenizerConfig {
+    #[serde(default)]
+    pub chat_template: Option<ChatTemplates>,
+    #[serde(default, deserialize_with = "token_content")]
+    pub bos_token: Option<String>,
+    #[serde(default, deserialize_with = "token_content")]
+    pub eos_token: Option<String>,
+    #[serde(default, deserialize_with = "token_content")]
+    pub unk_token: Option<String>,
+    #[serde(default, deserialize_with = "token_content")]
+    pub sep_token: Option<String>,
+    #[serde(default, deserialize_with = "token_content")]
+    pub pad_token: Option<String>,
+    #[serde(default, deserialize_with = "token_content")]
+    pub mask_token: Option<String>,
+}
+
+/// A single chat template, or a list of named ones
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum ChatTemplates {
+    Single(String),
+    Named(Vec<NamedChatTemplate>),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct NamedChatTemplate {
+    pub name: String,
+    pub template: String,
+}
+
+impl TokenizerConfig {
+    /// `chat_template` is the template from a separate `chat_template.jinja`,
+    /// used when the config itself has none
+    pub fn prompt_format(&self, source: &str, chat_template: Option<String>) -> PromptFormat {
+        let chat_templates = match (&self.chat_template, chat_template) {
+            (Some(ChatTemplates::Single(template)), _) => {
+                vec![("default".to_string(), template.clone())]
+            }
+            (Some(ChatTemplates::Named(named)), _) => named
+                .iter()
+                .map(|t| (t.name.clone(), t.template.clone()))
+                .collect(),
+            (None, Some(template)) => vec![("default".to_string(), template)],
+            (None, None) => Vec::new(),
+        };
+
+        let special_tokens = [
+            ("bos", &self.bos_token),
+            ("eos", &self.eos_token),
+            ("unk", &self.unk_token),
+            ("sep", &self.sep_token),
+            ("pad", &self.pad_token),
+            ("mask", &self.mask_token),
+        ]
+        .into_iter()
+        .filter_map(|(role, token)| Some((role.to_string(), token.clone()?)))
+        .collect();
+
+        PromptFormat {
+            source: source.to_string(),
+            chat_templates,
+            special_tokens,
+        }
+    }
+}
+
+/// Special tokens are either a string or an `AddedToken` object with `content`
+fn token_content<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    let token = match Option::<serde_json::Value>::deserialize(deserializer)? {
+        Some(serde_json::Value::String(token)) => Some(token),
+        Some(serde_json::Value::Object(token)) => token
+            .get("content")
+            .and_then(|c| c.as_str())
+            .map(str::to_string),
+        _ => None,
+    };
+    Ok(token)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChunkProgress {
     pub chunk_id: usize,
     #[allow(dead_code)]
@@ -119,7 +1260,7 @@ pub struct ChunkProgress {
     pub is_active: bool,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[allow(dead_code)]
 pub struct DownloadProgress {
     pub model_id: String,
@@ -127,15 +1268,48 @@ pub struct DownloadProgress {
     pub downloaded: u64,
     pub total: u64,
     pub speed_mbps: f64,
+    /// Exponentially smoothed `speed_mbps` the ETAs go by (missing from
+    /// daemons older than the ETA)
+    #[serde(default)]
+    pub avg_speed_mbps: f64,
     pub chunks: Vec<ChunkProgress>,
     pub verifying: bool,
 }
 
+/// Weight of the newest speed sample in `DownloadProgress::avg_speed_mbps`;
+/// with samples every 200 ms, the ETA follows speed changes over a few seconds
+const SPEED_SMOOTHING: f64 = 0.1;
+
+impl DownloadProgress {
+    /// Set the current speed and fold it into the smoothed speed
+    pub fn record_speed(&mut self, speed_mbps: f64) {
+        self.speed_mbps = speed_mbps;
+        self.avg_speed_mbps = if self.avg_speed_mbps > 0.0 {
+            SPEED_SMOOTHING * speed_mbps + (1.0 - SPEED_SMOOTHING) * self.avg_speed_mbps
+        } else {
+            speed_mbps
+        };
+    }
+
+    /// Seconds until the rest of this file and `queued_bytes` more are
+    /// downloaded at the smoothed speed; `None` before there is a speed
+    pub fn eta_secs(&self, queued_bytes: u64) -> Option<u64> {
+        if self.avg_speed_mbps <= 0.0 {
+            return None;
+        }
+        let remaining = self.total.saturating_sub(self.downloaded) + queued_bytes;
+        Some((remaining as f64 / (self.avg_speed_mbps * 1_048_576.0)).ceil() as u64)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum DownloadStatus {
     Incomplete,
     Complete,
     HashMismatch,
+    /// Was complete, but the file was moved, deleted or resized outside the
+    /// tool (see `registry::check_complete_files`)
+    Changed,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -149,11 +1323,203 @@ pub struct DownloadMetadata {
     pub status: DownloadStatus,
     #[serde(default)]
     pub expected_sha256: Option<String>,
+    /// Git blob SHA-1 of a small non-LFS file, which has no SHA-256
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected_git_blob: Option<String>,
+    /// Commit of the repository the file was downloaded from (the revision
+    /// resolved when the download started), so a copy can be reproduced
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+}
+
+/// A download still waiting in the TUI queue when it was closed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueuedDownload {
+    pub model_id: String,
+    pub filename: String,
+    /// Directory the file's path is relative to
+    pub base_path: String,
+    #[serde(default)]
+    pub expected_sha256: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected_git_blob: Option<String>,
+    #[serde(default)]
+    pub total_size: u64,
+    /// Branch to download from; `None` for main
+    #[serde(default)]
+    pub revision: Option<String>,
+    #[serde(default, skip_serializing_if = "DownloadPriority::is_normal")]
+    pub priority: DownloadPriority,
+}
+
+impl DownloadMetadata {
+    pub fn expected_hash(&self) -> Option<ExpectedHash> {
+        ExpectedHash::from_fields(
+            self.expected_sha256.as_ref(),
+            self.expected_git_blob.as_ref(),
+        )
+    }
+}
+
+impl QueuedDownload {
+    pub fn expected_hash(&self) -> Option<ExpectedHash> {
+        ExpectedHash::from_fields(
+            self.expected_sha256.as_ref(),
+            self.expected_git_blob.as_ref(),
+        )
+    }
+}
+
+/// Where a download is placed in the TUI queue: ahead of every lower
+/// priority download that has not started yet. This only reorders the
+/// pending queue; a download already running is never paused or preempted.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Default,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum DownloadPriority {
+    High,
+    #[default]
+    Normal,
+    Low,
+}
+
+impl DownloadPriority {
+    pub fn is_normal(&self) -> bool {
+        *self == DownloadPriority::Normal
+    }
+
+    /// Next priority for the download popup's Ctrl+P: normal, high, low
+    pub fn cycle(&self) -> Self {
+        match self {
+            DownloadPriority::Normal => DownloadPriority::High,
+            DownloadPriority::High => DownloadPriority::Low,
+            DownloadPriority::Low => DownloadPriority::Normal,
+        }
+    }
+
+    pub fn raise(&self) -> Self {
+        match self {
+            DownloadPriority::Low => DownloadPriority::Normal,
+            _ => DownloadPriority::High,
+        }
+    }
+
+    pub fn lower(&self) -> Self {
+        match self {
+            DownloadPriority::High => DownloadPriority::Normal,
+            _ => DownloadPriority::Low,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            DownloadPriority::High => "high",
+            DownloadPriority::Normal => "normal",
+            DownloadPriority::Low => "low",
+        }
+    }
+}
+
+/// What a download does when the registry already holds a complete file
+/// with the same content hash, e.g. from a mirror of the same repository
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum IdenticalFiles {
+    /// Fetch it again
+    Download = 0,
+    /// Hardlink the existing file (copied when it is on another filesystem)
+    #[default]
+    Link = 1,
+    /// Copy the existing file
+    Copy = 2,
+}
+
+impl IdenticalFiles {
+    pub fn from_u8(value: u8) -> Self {
+        match value {
+            0 => Self::Download,
+            2 => Self::Copy,
+            _ => Self::Link,
+        }
+    }
+
+    /// Next mode for the Options popup: link, copy, download
+    pub fn cycle(self, forward: bool) -> Self {
+        match (self, forward) {
+            (Self::Link, true) | (Self::Download, false) => Self::Copy,
+            (Self::Copy, true) | (Self::Link, false) => Self::Download,
+            (Self::Download, true) | (Self::Copy, false) => Self::Link,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Download => "Download again",
+            Self::Link => "Hardlink (copy across filesystems)",
+            Self::Copy => "Copy",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct DownloadRegistry {
     pub downloads: Vec<DownloadMetadata>,
+    /// Downloads not yet started when the TUI quit, in queue order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub queue: Vec<QueuedDownload>,
+    /// User notes and tags, keyed by model ID
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub model_notes: BTreeMap<String, ModelNote>,
+}
+
+/// Free-text note and custom tags attached to a model
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModelNote {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub note: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+impl ModelNote {
+    pub fn is_empty(&self) -> bool {
+        self.note.is_empty() && self.tags.is_empty()
+    }
+}
+
+/// A user or organization and every model they publish
+#[derive(Debug, Clone, Default)]
+pub struct AuthorProfile {
+    pub name: String,
+    /// Display name from the profile overview
+    pub fullname: Option<String>,
+    pub is_org: Option<bool>,
+    pub followers: Option<u64>,
+    /// Most recently modified first
+    pub models: Vec<ModelInfo>,
+}
+
+impl AuthorProfile {
+    pub fn total_likes(&self) -> u64 {
+        self.models.iter().map(|m| m.likes).sum()
+    }
+
+    pub fn total_downloads(&self) -> u64 {
+        self.models.iter().map(|m| m.downloads).sum()
+    }
+
+    /// The `count` most recently created models (by `createdAt`, falling
+    /// back to the last modification)
+    pub fn recent_uploads(&self, count: usize) -> Vec<&ModelInfo> {
+        let mut models: Vec<&ModelInfo> = self.models.iter().collect();
+        models.sort_by(|a, b| {
+            let date = |m: &ModelInfo| m.created_at.clone().or_else(|| m.last_modified.clone());
+            date(b).cmp(&date(a))
+        });
+        models.truncate(count);
+        models
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -164,6 +1530,18 @@ pub enum PopupMode {
     Options,
     AuthError { model_url: String },
     SearchPopup,
+    OpenModel,
+    QuantDiff,
+    CommandPalette,
+    WatchUpdates,
+    IgnoreList,
+    Stats,
+    ModelNotes,
+    DownloadPlan,
+    PromptFormat,
+    AuthorProfile,
+    DownloadQueue,
+    QuotaCleanup,
 }
 
 /// Filter presets for quick filter combinations
@@ -175,6 +1553,25 @@ pub enum FilterPreset {
     Recent,
 }
 
+impl FilterPreset {
+    /// In the order of their number keys (1-4)
+    pub const ALL: [FilterPreset; 4] = [
+        FilterPreset::NoFilters,
+        FilterPreset::Popular,
+        FilterPreset::HighlyRated,
+        FilterPreset::Recent,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            FilterPreset::NoFilters => "No Filters",
+            FilterPreset::Popular => "Popular",
+            FilterPreset::HighlyRated => "Highly Rated",
+            FilterPreset::Recent => "Recent",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum InputMode {
     Normal,
@@ -200,8 +1597,210 @@ pub enum SortDirection {
     Descending,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// How many results a search requests from the Hub, before the client-side
+/// filters narrow them down
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SearchLimits {
+    /// `limit` of each API request
+    pub page_size: usize,
+    /// Results fetched in total, following the next pages as needed
+    pub max_results: usize,
+}
+
+impl SearchLimits {
+    /// Largest `limit` the Hub accepts
+    pub const MAX_PAGE_SIZE: usize = 1000;
+    pub const MAX_RESULTS: usize = 5000;
+}
+
+impl Default for SearchLimits {
+    fn default() -> Self {
+        Self {
+            page_size: 100,
+            max_results: 100,
+        }
+    }
+}
+
+/// Approximate model size filter: a parameter count range with inclusive,
+/// optional bounds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct ParamRange {
+    pub min: Option<u64>,
+    pub max: Option<u64>,
+}
+
+impl ParamRange {
+    /// Steps of the filter bar's Size field: any, ≤8B, 8B–34B, 34B–70B, ≥70B
+    pub const STEPS: [ParamRange; 5] = {
+        const B: u64 = 1_000_000_000;
+        [
+            ParamRange {
+                min: None,
+                max: None,
+            },
+            ParamRange {
+                min: None,
+                max: Some(8 * B),
+            },
+            ParamRange {
+                min: Some(8 * B),
+                max: Some(34 * B),
+            },
+            ParamRange {
+                min: Some(34 * B),
+                max: Some(70 * B),
+            },
+            ParamRange {
+                min: Some(70 * B),
+                max: None,
+            },
+        ]
+    };
+
+    pub fn is_any(&self) -> bool {
+        self.min.is_none() && self.max.is_none()
+    }
+
+    /// Whether a model with `params` parameters is in range
+    ///
+    /// Counts are compared as displayed (0.1B / 1M precision), so an 8.03B
+    /// Llama passes `max 8B`. Models without a known count only pass an
+    /// unbounded range.
+    pub fn contains(&self, params: Option<u64>) -> bool {
+        if self.is_any() {
+            return true;
+        }
+        let Some(params) = params else {
+            return false;
+        };
+        let step = if params >= 1_000_000_000 {
+            100_000_000
+        } else {
+            1_000_000
+        };
+        let params = (params + step / 2) / step * step;
+        let above_min = match self.min {
+            Some(min) => params >= min,
+            None => true,
+        };
+        let below_max = match self.max {
+            Some(max) => params <= max,
+            None => true,
+        };
+        above_min && below_max
+    }
+
+    /// Short label, e.g. "Any", "≤8B", "8B–34B" or "≥70B"
+    pub fn label(&self) -> String {
+        // "8.0B" -> "8B"
+        let short = |n: u64| crate::utils::format_params(n).replace(".0", "");
+        match (self.min, self.max) {
+            (None, None) => "Any".to_string(),
+            (None, Some(max)) => format!("≤{}", short(max)),
+            (Some(min), None) => format!("≥{}", short(min)),
+            (Some(min), Some(max)) => format!("{}–{}", short(min), short(max)),
+        }
+    }
+}
+
+/// Which gated and private repositories a search keeps, judged from the
+/// `gated` and `private` fields of the results
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+pub enum AccessFilter {
+    #[default]
+    All,
+    /// No gated repositories
+    Ungated,
+    /// No private repositories
+    Public,
+    /// Neither gated nor private
+    Open,
+}
+
+impl AccessFilter {
+    /// Steps of the filter bar's Access field
+    pub const STEPS: [AccessFilter; 4] = [
+        AccessFilter::All,
+        AccessFilter::Ungated,
+        AccessFilter::Public,
+        AccessFilter::Open,
+    ];
+
+    /// The filter for headless `--no-gated` / `--no-private`
+    pub fn from_flags(no_gated: bool, no_private: bool) -> Self {
+        match (no_gated, no_private) {
+            (false, false) => AccessFilter::All,
+            (true, false) => AccessFilter::Ungated,
+            (false, true) => AccessFilter::Public,
+            (true, true) => AccessFilter::Open,
+        }
+    }
+
+    pub fn is_any(&self) -> bool {
+        *self == AccessFilter::All
+    }
+
+    pub fn allows(&self, model: &ModelInfo) -> bool {
+        let hides_gated = matches!(self, AccessFilter::Ungated | AccessFilter::Open);
+        let hides_private = matches!(self, AccessFilter::Public | AccessFilter::Open);
+        let hidden = (hides_gated && model.is_gated()) || (hides_private && model.private);
+        !hidden
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            AccessFilter::All => "All",
+            AccessFilter::Ungated => "Ungated",
+            AccessFilter::Public => "Public",
+            AccessFilter::Open => "Open",
+        }
+    }
+}
+
+/// Whether a search keeps repositories tagged as adult content (see
+/// [`ModelInfo::is_nsfw`])
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+pub enum ContentFilter {
+    #[default]
+    All,
+    /// No repositories tagged `not-for-all-audiences` or similar
+    Safe,
+}
+
+impl ContentFilter {
+    /// Steps of the filter bar's Content field
+    pub const STEPS: [ContentFilter; 2] = [ContentFilter::All, ContentFilter::Safe];
+
+    /// The filter for headless `--no-nsfw`
+    pub fn from_flag(no_nsfw: bool) -> Self {
+        if no_nsfw {
+            ContentFilter::Safe
+        } else {
+            ContentFilter::All
+        }
+    }
+
+    pub fn is_any(&self) -> bool {
+        *self == ContentFilter::All
+    }
+
+    pub fn allows(&self, model: &ModelInfo) -> bool {
+        self.is_any() || !model.is_nsfw()
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ContentFilter::All => "All",
+            ContentFilter::Safe => "SFW",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub enum FocusedPane {
+    #[default]
     Models,
     QuantizationGroups,
     QuantizationFiles,
@@ -253,6 +1852,7 @@ pub type CompleteDownloads = HashMap<String, DownloadMetadata>;
 pub type MetadataCache = HashMap<String, ModelMetadata>;
 pub type FileTreeCache = HashMap<String, FileTreeNode>;
 pub type SearchCache = HashMap<SearchKey, Vec<ModelInfo>>;
+pub type RelatedCache = HashMap<String, Vec<ModelInfo>>;
 
 /// Search cache key that includes all filter parameters
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
@@ -262,6 +1862,12 @@ pub struct SearchKey {
     pub sort_direction: SortDirection,
     pub min_downloads: u64,
     pub min_likes: u64,
+    pub params: ParamRange,
+    pub access: AccessFilter,
+    pub content: ContentFilter,
+    /// Matched against model card text instead of repository names
+    pub full_text: bool,
+    pub limits: SearchLimits,
 }
 
 /// Unified API cache container for all cached data
@@ -271,6 +1877,7 @@ pub struct ApiCache {
     pub quantizations: QuantizationCache,
     pub file_trees: FileTreeCache,
     pub searches: SearchCache,
+    pub related: RelatedCache,
 }
 
 /// Progress tracking for an active verification operation
@@ -280,18 +1887,23 @@ pub struct ApiCache {
 /// This avoids lock contention while multiple files are verified concurrently.
 #[derive(Debug, Clone)]
 pub struct VerificationProgress {
+    pub model_id: String,
     pub filename: String,
+    pub local_path: String,
     pub verified_bytes: Arc<AtomicU64>,
     pub total_bytes: u64,
     pub speed_mbps: f64,
+    /// Set to stop hashing this file (skip verification)
+    pub skipped: Arc<AtomicBool>,
 }
 
 /// Item in the verification queue
 #[derive(Debug, Clone)]
 pub struct VerificationQueueItem {
+    pub model_id: String,
     pub filename: String,
     pub local_path: String,
-    pub expected_sha256: String,
+    pub expected_hash: ExpectedHash,
     pub total_size: u64,
     #[allow(dead_code)]
     pub is_manual: bool, // True if triggered by 'v' key, false if automatic
@@ -302,12 +1914,65 @@ fn default_rate_limit_mbps() -> f64 {
     50.0
 }
 
+fn default_single_request_max_size() -> u64 {
+    10 * 1024 * 1024
+}
+
+// Defaults for the on-disk API cache
+fn default_true() -> bool {
+    true
+}
+
+fn default_trash_retention_days() -> u64 {
+    7
+}
+
+fn default_api_cache_ttl_minutes() -> u64 {
+    30
+}
+
+fn default_api_cache_max_size_mb() -> u64 {
+    200
+}
+
+fn default_search_page_size() -> usize {
+    SearchLimits::default().page_size
+}
+
+fn default_search_max_results() -> usize {
+    SearchLimits::default().max_results
+}
+
+fn default_recent_gguf_days() -> u64 {
+    7
+}
+
+fn default_auto_refresh_interval_minutes() -> u64 {
+    10
+}
+
+fn default_convert_quant_type() -> String {
+    "Q4_K_M".to_string()
+}
+
+fn default_convert_python() -> String {
+    "python3".to_string()
+}
+
 /// Application options/settings
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppOptions {
     // General
     pub default_directory: String,
     pub hf_token: Option<String>,
+    /// User-Agent for all requests instead of `rust-hf-downloader/<version>`;
+    /// empty sends none (config file only)
+    #[serde(default)]
+    pub user_agent: Option<String>,
+    /// PEM bundle of extra root CAs to trust, e.g. for a TLS-inspecting
+    /// proxy (config file only, read at startup)
+    #[serde(default)]
+    pub ca_bundle: Option<String>,
 
     // Download Settings
     pub concurrent_threads: usize,
@@ -318,6 +1983,10 @@ pub struct AppOptions {
     pub download_timeout_secs: u64,
     pub retry_delay_secs: u64,
     pub progress_update_interval_ms: u64,
+    /// Files up to this size are fetched with one plain GET instead of
+    /// ranged chunks; 0 always chunks (config file only)
+    #[serde(default = "default_single_request_max_size")]
+    pub single_request_max_size: u64,
 
     // Rate Limiting
     #[serde(default)]
@@ -330,6 +1999,116 @@ pub struct AppOptions {
     pub concurrent_verifications: usize,
     pub verification_buffer_size: usize,
     pub verification_update_interval: usize,
+    #[serde(default)]
+    pub low_priority_verification: bool,
+
+    // API Cache
+    #[serde(default = "default_true")]
+    pub api_cache_enabled: bool,
+    #[serde(default = "default_api_cache_ttl_minutes")]
+    pub api_cache_ttl_minutes: u64,
+    #[serde(default = "default_api_cache_max_size_mb")]
+    pub api_cache_max_size_mb: u64,
+
+    // Search Refresh
+    #[serde(default)]
+    pub auto_refresh_enabled: bool,
+    #[serde(default = "default_auto_refresh_interval_minutes")]
+    pub auto_refresh_interval_minutes: u64,
+
+    // Session
+    #[serde(default)]
+    pub restore_session: bool,
+
+    // Storage
+    #[serde(default)]
+    pub quant_subdirectory: bool,
+
+    // Licenses
+    /// Headless downloads of models without a permissive license fail
+    /// unless `--accept-license` is passed
+    #[serde(default)]
+    pub require_license_confirmation: bool,
+
+    // New GGUF Feed
+    /// Days of uploads shown by the recent GGUF feed (`N` / `recent`)
+    #[serde(default = "default_recent_gguf_days")]
+    pub recent_gguf_days: u64,
+
+    // Search Results
+    /// `limit` of each search request (see [`SearchLimits`])
+    #[serde(default = "default_search_page_size")]
+    pub search_page_size: usize,
+    /// Raw results fetched per search, before client-side filters
+    #[serde(default = "default_search_max_results")]
+    pub search_max_results: usize,
+    /// Look up the total repository size of each result (one request each)
+    #[serde(default)]
+    pub show_repo_sizes: bool,
+
+    // Startup
+    /// Re-queue incomplete downloads at startup instead of asking; headless
+    /// download commands pick them up as well
+    #[serde(default)]
+    pub auto_resume: bool,
+
+    // Network
+    /// One connection, small chunks and long timeouts for Tor/VPN tunnels
+    /// (see `DownloadConfig::apply_conservative_networking`)
+    #[serde(default)]
+    pub conservative_networking: bool,
+    /// Tune the connections per file to the throughput they bring, starting
+    /// from `concurrent_threads` (see `DownloadConfig::adaptive_threads`)
+    #[serde(default)]
+    pub adaptive_threads: bool,
+    /// Ceiling on the download connections open at once across all files,
+    /// 0 for none (see `DownloadConfig::max_connections`)
+    #[serde(default)]
+    pub max_connections: usize,
+
+    // Archives
+    /// Unpack downloaded .zip/.tar(.gz) files into their directory
+    #[serde(default)]
+    pub extract_archives: bool,
+
+    // Deduplication
+    /// Files whose content the registry already holds from another download
+    /// are linked or copied instead of fetched (see `download::reuse_identical_file`)
+    #[serde(default)]
+    pub identical_files: IdenticalFiles,
+    /// Keep file contents in `~/models/.blobs/<sha256>` and symlinks in the
+    /// model directories (see `crate::blobstore`)
+    #[serde(default)]
+    pub blob_store: bool,
+
+    // Trash
+    /// Days deleted files stay in the trash before they are purged;
+    /// 0 deletes right away (see `crate::trash`)
+    #[serde(default = "default_trash_retention_days")]
+    pub trash_retention_days: u64,
+
+    // GGUF Conversion (see `crate::convert`)
+    /// llama.cpp's convert_hf_to_gguf.py, enables conversion when set
+    #[serde(default)]
+    pub convert_script: Option<String>,
+    /// llama-quantize; without it conversions stop at F16
+    #[serde(default)]
+    pub quantize_binary: Option<String>,
+    #[serde(default = "default_convert_quant_type")]
+    pub convert_quant_type: String,
+    /// Interpreter for the conversion script (config file only)
+    #[serde(default = "default_convert_python")]
+    pub convert_python: String,
+
+    // Display
+    /// Draw borders, trees and badges with ASCII characters only
+    #[serde(default)]
+    pub ascii_only: bool,
+
+    // Download Lock
+    /// Ask before every download (see `download::set_downloads_locked`)
+    #[serde(default)]
+    pub no_download: bool,
 
     // UI State (not serialized)
     #[serde(skip)]
@@ -338,6 +2117,9 @@ pub struct AppOptions {
     pub editing_directory: bool,
     #[serde(skip)]
     pub editing_token: bool,
+    /// Editing the conversion tool path of the selected field
+    #[serde(skip)]
+    pub editing_tool_path: bool,
 
     // Filter & Sort Settings (NEW)
     #[serde(default)]
@@ -348,6 +2130,109 @@ pub struct AppOptions {
     pub default_min_downloads: u64,
     #[serde(default)]
     pub default_min_likes: u64,
+    #[serde(default)]
+    pub default_params: ParamRange,
+    #[serde(default)]
+    pub default_access: AccessFilter,
+    /// Hide repositories tagged as adult content from searches by default
+    #[serde(default)]
+    pub default_content: ContentFilter,
+
+    // Accessibility (config file or command line only)
+    /// Screen-reader friendly TUI: no box drawing, selection markers beside
+    /// colors, and the focused item described on the top line
+    #[serde(default)]
+    pub screen_reader: bool,
+    /// File the TUI appends focus and status changes to, one plain line each
+    #[serde(default)]
+    pub screen_reader_log: Option<String>,
+
+    // Notifications
+    /// Webhooks called on download events (config file only)
+    #[serde(default)]
+    pub webhooks: Vec<WebhookConfig>,
+    /// Size limits of download directories (config file only, see `crate::quota`)
+    #[serde(default)]
+    pub quotas: Vec<QuotaConfig>,
+    /// Rules picking the download root of each download (config file only,
+    /// see `crate::placement`)
+    #[serde(default)]
+    pub placement: Vec<PlacementRule>,
+}
+
+/// Events a webhook can be notified of
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookEvent {
+    DownloadComplete,
+    VerificationFailed,
+    QueueDrained,
+}
+
+impl WebhookEvent {
+    pub fn name(self) -> &'static str {
+        match self {
+            WebhookEvent::DownloadComplete => "download_complete",
+            WebhookEvent::VerificationFailed => "verification_failed",
+            WebhookEvent::QueueDrained => "queue_drained",
+        }
+    }
+}
+
+/// A `[[quotas]]` entry of the config file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuotaConfig {
+    /// Directory the quota covers, with everything below it (`~/` is the
+    /// home directory)
+    pub directory: String,
+    pub max_size_gb: u64,
+}
+
+/// A `[[placement]]` rule of the config file; a rule without conditions
+/// matches every download
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlacementRule {
+    /// Download root the matching downloads go to (`~/` is the home directory)
+    pub directory: String,
+    /// Only downloads of at most this many GB in total
+    #[serde(default)]
+    pub max_size_gb: Option<u64>,
+    /// Only downloads of at least this many GB in total
+    #[serde(default)]
+    pub min_size_gb: Option<u64>,
+    /// Only models whose id matches this wildcard pattern, e.g. "unsloth/*"
+    #[serde(default)]
+    pub model: Option<String>,
+}
+
+/// A `[[webhooks]]` entry of the config file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookConfig {
+    pub url: String,
+    /// Request body with `{event}`, `{model_id}`, `{filename}`, `{path}`,
+    /// `{size}` and `{message}` placeholders; a JSON object of all of them
+    /// when unset
+    #[serde(default)]
+    pub template: Option<String>,
+    /// Events to send; all of them when empty
+    #[serde(default)]
+    pub events: Vec<WebhookEvent>,
+    #[serde(default = "default_webhook_content_type")]
+    pub content_type: String,
+}
+
+fn default_webhook_content_type() -> String {
+    "application/json".to_string()
+}
+
+impl AppOptions {
+    /// Search limits from the options, kept within what the Hub accepts
+    pub fn search_limits(&self) -> SearchLimits {
+        SearchLimits {
+            page_size: self.search_page_size.clamp(1, SearchLimits::MAX_PAGE_SIZE),
+            max_results: self.search_max_results.clamp(1, SearchLimits::MAX_RESULTS),
+        }
+    }
 }
 
 impl Default for AppOptions {
@@ -357,6 +2242,8 @@ impl Default for AppOptions {
         Self {
             default_directory: format!("{}/models", home),
             hf_token,
+            user_agent: None,
+            ca_bundle: None,
             concurrent_threads: 8,
             num_chunks: 20,
             min_chunk_size: 5 * 1024 * 1024,
@@ -365,20 +2252,57 @@ impl Default for AppOptions {
             download_timeout_secs: 300,
             retry_delay_secs: 1,
             progress_update_interval_ms: 200,
+            single_request_max_size: default_single_request_max_size(),
             download_rate_limit_enabled: false,
             download_rate_limit_mbps: 50.0,
             verification_on_completion: true,
             concurrent_verifications: 2,
             verification_buffer_size: 128 * 1024,
             verification_update_interval: 100,
+            low_priority_verification: false,
+            api_cache_enabled: true,
+            api_cache_ttl_minutes: 30,
+            api_cache_max_size_mb: 200,
+            auto_refresh_enabled: false,
+            auto_refresh_interval_minutes: 10,
+            restore_session: false,
+            quant_subdirectory: false,
+            require_license_confirmation: false,
+            recent_gguf_days: default_recent_gguf_days(),
+            search_page_size: default_search_page_size(),
+            search_max_results: default_search_max_results(),
+            show_repo_sizes: false,
+            auto_resume: false,
+            conservative_networking: false,
+            adaptive_threads: false,
+            max_connections: 0,
+            extract_archives: false,
+            identical_files: IdenticalFiles::default(),
+            blob_store: false,
+            trash_retention_days: 7,
+            convert_script: None,
+            quantize_binary: None,
+            convert_quant_type: default_convert_quant_type(),
+            convert_python: default_convert_python(),
+            ascii_only: false,
+            no_download: false,
             selected_field: 0,
             editing_directory: false,
             editing_token: false,
+            editing_tool_path: false,
             // Filter & Sort defaults
             default_sort_field: SortField::Downloads,
             default_sort_direction: SortDirection::Descending,
             default_min_downloads: 0,
             default_min_likes: 0,
+            default_params: ParamRange::default(),
+            default_access: AccessFilter::default(),
+            default_content: ContentFilter::default(),
+            screen_reader: false,
+            screen_reader_log: None,
+            webhooks: Vec::new(),
+            quotas: Vec::new(),
+            placement: Vec::new(),
         }
     }
 }
@@ -0,0 +1,127 @@
+//! Multi-part model files on disk
+//!
+//! Large GGUFs are published either as `gguf-split` shards
+//! (`model-Q6_K-00001-of-00009.gguf`) or as raw byte splits
+//! (`model.Q6_K.gguf.part1of2`). After downloading, [`part_sets`] checks which
+//! parts of each set are actually present next to each other, so missing parts
+//! can be reported and complete sets merged into a single usable file.
+
+use crate::api::{get_multipart_base_name, parse_multipart_filename};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// One split model file and the parts of it found on disk
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartSet {
+    /// Directory holding the parts
+    pub dir: PathBuf,
+    /// Name of the merged file, e.g. `model-Q6_K.gguf`
+    pub base_name: String,
+    /// Parts found on disk, indexed by part number - 1
+    pub parts: Vec<Option<PathBuf>>,
+}
+
+impl PartSet {
+    pub fn total(&self) -> usize {
+        self.parts.len()
+    }
+
+    pub fn present(&self) -> usize {
+        self.parts.iter().filter(|p| p.is_some()).count()
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.present() == self.total()
+    }
+
+    /// Part numbers (1-based) not found on disk
+    pub fn missing(&self) -> Vec<usize> {
+        self.parts
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| p.is_none())
+            .map(|(idx, _)| idx + 1)
+            .collect()
+    }
+
+    /// Shell command that merges a complete set into `base_name`
+    ///
+    /// `gguf-split` shards are merged with llama.cpp's `llama-gguf-split`
+    /// (given the first shard, it finds the rest); raw `.partNofM` splits are
+    /// plain byte ranges and are concatenated.
+    pub fn merge_command(&self) -> Option<String> {
+        if !self.is_complete() {
+            return None;
+        }
+        let parts: Vec<&Path> = self.parts.iter().flatten().map(PathBuf::as_path).collect();
+        let output = self.dir.join(&self.base_name);
+        let is_gguf_split = parts[0]
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.ends_with(".gguf"));
+
+        Some(if is_gguf_split {
+            format!(
+                "llama-gguf-split --merge {} {}",
+                shell_quote(parts[0]),
+                shell_quote(&output)
+            )
+        } else {
+            let inputs: Vec<String> = parts.iter().map(|p| shell_quote(p)).collect();
+            format!("cat {} > {}", inputs.join(" "), shell_quote(&output))
+        })
+    }
+}
+
+/// Part sets that the given files belong to, checked against the directory
+/// each one lives in. Files that aren't part of a split are ignored.
+pub fn part_sets(paths: &[PathBuf]) -> Vec<PartSet> {
+    // (directory, merged name) -> number of parts
+    let mut wanted: BTreeMap<(PathBuf, String), u32> = BTreeMap::new();
+    for path in paths {
+        let (Some(dir), Some(name)) = (path.parent(), path.file_name().and_then(|n| n.to_str()))
+        else {
+            continue;
+        };
+        if let Some((_, total)) = parse_multipart_filename(name) {
+            wanted.insert((dir.to_path_buf(), get_multipart_base_name(name)), total);
+        }
+    }
+
+    wanted
+        .into_iter()
+        .map(|((dir, base_name), total)| {
+            let mut parts = vec![None; total as usize];
+            for entry in std::fs::read_dir(&dir).into_iter().flatten().flatten() {
+                let Some(name) = entry.file_name().to_str().map(str::to_string) else {
+                    continue;
+                };
+                if name.ends_with(".incomplete") || get_multipart_base_name(&name) != base_name {
+                    continue;
+                }
+                if let Some((part, of)) = parse_multipart_filename(&name) {
+                    if of == total && part >= 1 {
+                        parts[part as usize - 1] = Some(entry.path());
+                    }
+                }
+            }
+            PartSet {
+                dir,
+                base_name,
+                parts,
+            }
+        })
+        .collect()
+}
+
+fn shell_quote(path: &Path) -> String {
+    let path = path.to_string_lossy();
+    if path
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || "/._-+:".contains(c))
+    {
+        path.into_owned()
+    } else {
+        format!("'{}'", path.replace('\'', r"'\''"))
+    }
+}
@@ -0,0 +1,183 @@
+//! Placement of downloads across download roots (`[[placement]]` in the config)
+//!
+//! With several download roots, say a fast NVMe drive for the quantizations
+//! that get loaded and a large disk as an archive, rules pick the root each
+//! download goes to from its total size and model id. Rules are tried in
+//! order and the first match wins; a rule without conditions catches the
+//! rest. The TUI fills the download popup's path from the rules when the
+//! popup opens (Ctrl+R switches to another root), and the headless `download`
+//! and `pick` commands use them when no `--output` is given. When no rule
+//! matches, the default directory is used as before.
+
+use crate::models::PlacementRule;
+use crate::utils::glob_match;
+use serde::Serialize;
+use std::sync::RwLock;
+
+const GB: u64 = 1024 * 1024 * 1024;
+
+static RULES: RwLock<Vec<PlacementRule>> = RwLock::new(Vec::new());
+
+/// Apply the placement rules (from `AppOptions`)
+pub fn configure(rules: Vec<PlacementRule>) {
+    if let Ok(mut configured) = RULES.write() {
+        *configured = rules;
+    }
+}
+
+/// Where the rules put a download
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Placement {
+    /// Download root, with `~/` expanded
+    pub directory: String,
+    /// Number of the rule that matched, counting from 1 in config order
+    pub rule: usize,
+    /// The rule's conditions, e.g. "up to 20 GB"
+    pub reason: String,
+}
+
+impl PlacementRule {
+    /// Whether a download of `bytes` in total from `model_id` matches
+    pub fn matches(&self, model_id: &str, bytes: u64) -> bool {
+        self.max_size_gb
+            .map_or(true, |max| bytes <= max.saturating_mul(GB))
+            && self
+                .min_size_gb
+                .map_or(true, |min| bytes >= min.saturating_mul(GB))
+            && self
+                .model
+                .as_deref()
+                .map_or(true, |pattern| glob_match(pattern, model_id))
+    }
+
+    /// The rule's conditions in words
+    pub fn describe(&self) -> String {
+        let mut conditions = Vec::new();
+        if let Some(pattern) = &self.model {
+            conditions.push(format!("models {}", pattern));
+        }
+        if let Some(min) = self.min_size_gb {
+            conditions.push(format!("at least {} GB", min));
+        }
+        if let Some(max) = self.max_size_gb {
+            conditions.push(format!("up to {} GB", max));
+        }
+        if conditions.is_empty() {
+            "everything else".to_string()
+        } else {
+            conditions.join(", ")
+        }
+    }
+}
+
+/// The download root of the first rule matching a download of `bytes` in
+/// total from `model_id`
+pub fn place(model_id: &str, bytes: u64) -> Option<Placement> {
+    let rules = RULES.read().ok()?;
+    let (index, rule) = rules
+        .iter()
+        .enumerate()
+        .find(|(_, rule)| rule.matches(model_id, bytes))?;
+    Some(Placement {
+        directory: crate::utils::expand_home(&rule.directory)
+            .to_string_lossy()
+            .into_owned(),
+        rule: index + 1,
+        reason: rule.describe(),
+    })
+}
+
+/// Every configured download root, in rule order without repeats
+pub fn roots() -> Vec<String> {
+    let Ok(rules) = RULES.read() else {
+        return Vec::new();
+    };
+    let mut roots: Vec<String> = Vec::new();
+    for rule in rules.iter() {
+        let root = crate::utils::expand_home(&rule.directory)
+            .to_string_lossy()
+            .into_owned();
+        if !roots.contains(&root) {
+            roots.push(root);
+        }
+    }
+    roots
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(
+        directory: &str,
+        model: Option<&str>,
+        min_size_gb: Option<u64>,
+        max_size_gb: Option<u64>,
+    ) -> PlacementRule {
+        PlacementRule {
+            directory: directory.to_string(),
+            max_size_gb,
+            min_size_gb,
+            model: model.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_matches() {
+        let small = rule("/fast", None, None, Some(20));
+        assert!(small.matches("a/b", 0));
+        assert!(small.matches("a/b", 20 * GB));
+        assert!(!small.matches("a/b", 20 * GB + 1));
+
+        let large = rule("/archive", None, Some(20), None);
+        assert!(!large.matches("a/b", 20 * GB - 1));
+        assert!(large.matches("a/b", 20 * GB));
+        assert!(large.matches("a/b", u64::MAX));
+
+        let models = rule("/unsloth", Some("unsloth/*"), Some(1), Some(10));
+        assert!(models.matches("unsloth/Qwen3-8B-GGUF", 5 * GB));
+        assert!(!models.matches("bartowski/Qwen3-8B-GGUF", 5 * GB));
+        assert!(!models.matches("unsloth/Qwen3-8B-GGUF", 11 * GB));
+
+        assert!(rule("/rest", None, None, None).matches("a/b", u64::MAX));
+    }
+
+    #[test]
+    fn test_matches_huge_limits() {
+        // `u64::MAX` GB would overflow in bytes
+        assert!(rule("/any", None, None, Some(u64::MAX)).matches("a/b", u64::MAX));
+        assert!(!rule("/none", None, Some(u64::MAX), None).matches("a/b", u64::MAX - 1));
+    }
+
+    #[test]
+    fn test_place_first_match_wins() {
+        configure(vec![
+            rule("/unsloth", Some("unsloth/*"), None, None),
+            rule("/fast", None, None, Some(20)),
+            rule("/fast", None, None, Some(50)),
+            rule("/archive", None, None, None),
+        ]);
+
+        let placed = place("unsloth/Qwen3-235B-GGUF", 100 * GB).unwrap();
+        assert_eq!((placed.directory.as_str(), placed.rule), ("/unsloth", 1));
+        assert_eq!(placed.reason, "models unsloth/*");
+
+        let placed = place("bartowski/Qwen3-8B-GGUF", 5 * GB).unwrap();
+        assert_eq!((placed.directory.as_str(), placed.rule), ("/fast", 2));
+        assert_eq!(placed.reason, "up to 20 GB");
+
+        let placed = place("bartowski/Qwen3-32B-GGUF", 30 * GB).unwrap();
+        assert_eq!((placed.directory.as_str(), placed.rule), ("/fast", 3));
+
+        let placed = place("bartowski/Qwen3-235B-GGUF", 100 * GB).unwrap();
+        assert_eq!((placed.directory.as_str(), placed.rule), ("/archive", 4));
+        assert_eq!(placed.reason, "everything else");
+
+        assert_eq!(roots(), ["/unsloth", "/fast", "/archive"]);
+
+        configure(vec![rule("/fast", None, None, Some(20))]);
+        assert_eq!(place("a/b", 30 * GB), None);
+        configure(Vec::new());
+        assert_eq!(place("a/b", 0), None);
+    }
+}
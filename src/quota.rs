@@ -0,0 +1,307 @@
+//! Size quotas of download directories (`[[quotas]]` in the config)
+//!
+//! A quota caps what a directory and everything below it may hold on disk.
+//! Before a download is queued its size is added to the directory's current
+//! usage; when that goes over the quota, the TUI opens a cleanup popup and
+//! the headless `download` commands print a warning, both suggesting
+//! downloaded models to delete (to the trash, see `crate::trash`).
+//! Downloads are never refused over a quota.
+
+use crate::models::{DownloadRegistry, DownloadStatus, QuotaConfig};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+static QUOTAS: RwLock<Vec<QuotaConfig>> = RwLock::new(Vec::new());
+
+/// Apply the quotas (from `AppOptions`)
+pub fn configure(quotas: Vec<QuotaConfig>) {
+    if let Ok(mut configured) = QUOTAS.write() {
+        *configured = quotas;
+    }
+}
+
+/// A quota and what its directory holds now
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct QuotaUsage {
+    pub directory: PathBuf,
+    pub max_bytes: u64,
+    pub used_bytes: u64,
+}
+
+impl QuotaUsage {
+    /// Bytes over the quota once `incoming` more are stored (0 if it fits)
+    pub fn excess(&self, incoming: u64) -> u64 {
+        (self.used_bytes + incoming).saturating_sub(self.max_bytes)
+    }
+}
+
+/// The quota covering `path` (of the innermost quota directory holding it),
+/// with the directory's current size, measured on a blocking thread
+pub async fn usage(path: &Path) -> Option<QuotaUsage> {
+    let (directory, max_size_gb) = quota_of(path)?;
+    let used_bytes = {
+        let directory = directory.clone();
+        tokio::task::spawn_blocking(move || dir_size(&directory))
+            .await
+            .unwrap_or(0)
+    };
+    Some(QuotaUsage {
+        directory,
+        max_bytes: max_size_gb.saturating_mul(1024 * 1024 * 1024),
+        used_bytes,
+    })
+}
+
+/// The innermost quota directory holding `path`, with its quota in GB
+fn quota_of(path: &Path) -> Option<(PathBuf, u64)> {
+    let path = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        std::env::current_dir().map_or_else(|_| path.to_path_buf(), |cwd| cwd.join(path))
+    };
+    let quotas = QUOTAS.read().ok()?;
+    quotas
+        .iter()
+        .map(|q| (crate::utils::expand_home(&q.directory), q.max_size_gb))
+        .filter(|(dir, _)| path.starts_with(dir))
+        .max_by_key(|(dir, _)| dir.components().count())
+}
+
+/// Bytes of the regular files below `dir`; symlinks (blob-store links) are
+/// not followed, and trash directories do not count, so deleting frees quota
+fn dir_size(dir: &Path) -> u64 {
+    let mut total = 0;
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        if dir
+            .file_name()
+            .is_some_and(|name| name == crate::trash::TRASH_DIR)
+        {
+            continue;
+        }
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let Ok(meta) = entry.metadata() else {
+                continue;
+            };
+            if meta.is_dir() {
+                pending.push(entry.path());
+            } else if meta.is_file() {
+                total += meta.len();
+            }
+        }
+    }
+    total
+}
+
+/// A downloaded model suggested for deletion
+#[derive(Debug, Clone, Serialize)]
+pub struct CleanupCandidate {
+    pub model_id: String,
+    pub files: usize,
+    pub bytes: u64,
+    /// Unix time its newest file was written
+    pub modified: u64,
+    /// A file failed verification or has no checksum to verify against
+    pub unverified: bool,
+}
+
+impl CleanupCandidate {
+    /// Whole days since its newest file was written
+    pub fn age_days(&self) -> u64 {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        now.saturating_sub(self.modified) / (24 * 60 * 60)
+    }
+}
+
+/// Models with downloaded files under `directory`, best to delete first:
+/// unverified ones, then the largest, then the oldest
+pub fn cleanup_candidates(registry: &DownloadRegistry, directory: &Path) -> Vec<CleanupCandidate> {
+    let mut models: HashMap<&str, CleanupCandidate> = HashMap::new();
+    for download in &registry.downloads {
+        let path = Path::new(&download.local_path);
+        if !path.starts_with(directory)
+            || !matches!(
+                download.status,
+                DownloadStatus::Complete | DownloadStatus::HashMismatch
+            )
+        {
+            continue;
+        }
+        let Ok(meta) = fs::metadata(path) else {
+            continue;
+        };
+        let modified = meta
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map_or(0, |d| d.as_secs());
+
+        let candidate = models
+            .entry(&download.model_id)
+            .or_insert_with(|| CleanupCandidate {
+                model_id: download.model_id.clone(),
+                files: 0,
+                bytes: 0,
+                modified: 0,
+                unverified: false,
+            });
+        candidate.files += 1;
+        candidate.bytes += meta.len();
+        candidate.modified = candidate.modified.max(modified);
+        candidate.unverified |=
+            download.status == DownloadStatus::HashMismatch || download.expected_hash().is_none();
+    }
+
+    let mut candidates: Vec<CleanupCandidate> = models.into_values().collect();
+    candidates.sort_by(|a, b| {
+        b.unverified
+            .cmp(&a.unverified)
+            .then(b.bytes.cmp(&a.bytes))
+            .then(a.modified.cmp(&b.modified))
+    });
+    candidates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::DownloadMetadata;
+    use std::time::Duration;
+
+    const GB: u64 = 1024 * 1024 * 1024;
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "hf-downloader-quota-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn write(path: &Path, len: usize) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, vec![0; len]).unwrap();
+    }
+
+    #[test]
+    fn test_excess() {
+        let usage = QuotaUsage {
+            directory: PathBuf::from("/models"),
+            max_bytes: 10 * GB,
+            used_bytes: 6 * GB,
+        };
+        assert_eq!(usage.excess(0), 0);
+        assert_eq!(usage.excess(4 * GB), 0);
+        assert_eq!(usage.excess(5 * GB), GB);
+        let full = QuotaUsage {
+            used_bytes: 12 * GB,
+            ..usage
+        };
+        assert_eq!(full.excess(0), 2 * GB);
+    }
+
+    #[test]
+    fn test_dir_size_skips_trash() {
+        let dir = scratch_dir("size");
+        write(&dir.join("author/model/model.gguf"), 100);
+        write(&dir.join("author/model/config.json"), 10);
+        write(&dir.join(".trash/1-0/file"), 1000);
+        write(&dir.join("author/.trash/1-0/file"), 1000);
+        assert_eq!(dir_size(&dir), 110);
+        assert_eq!(dir_size(&dir.join("missing")), 0);
+    }
+
+    #[tokio::test]
+    async fn test_usage_innermost_directory() {
+        let dir = scratch_dir("usage");
+        write(&dir.join("fast/author/model/model.gguf"), 100);
+        write(&dir.join("other.bin"), 50);
+        configure(vec![
+            QuotaConfig {
+                directory: dir.join("fast").to_string_lossy().into_owned(),
+                max_size_gb: 1,
+            },
+            QuotaConfig {
+                directory: dir.to_string_lossy().into_owned(),
+                max_size_gb: u64::MAX,
+            },
+        ]);
+
+        let inner = usage(&dir.join("fast/author")).await.unwrap();
+        assert_eq!(inner.directory, dir.join("fast"));
+        assert_eq!((inner.max_bytes, inner.used_bytes), (GB, 100));
+
+        let outer = usage(&dir.join("slow")).await.unwrap();
+        assert_eq!(outer.directory, dir);
+        assert_eq!((outer.max_bytes, outer.used_bytes), (u64::MAX, 150));
+
+        assert_eq!(usage(Path::new("/elsewhere")).await, None);
+        configure(Vec::new());
+    }
+
+    #[test]
+    fn test_cleanup_candidates_order() {
+        let dir = scratch_dir("cleanup");
+        let download = |model_id: &str, len: usize, age_days: u64, sha256: Option<&str>| {
+            let path = dir.join(model_id).join("model.gguf");
+            write(&path, len);
+            let modified = SystemTime::now() - Duration::from_secs(age_days * 24 * 60 * 60);
+            fs::File::options()
+                .write(true)
+                .open(&path)
+                .unwrap()
+                .set_modified(modified)
+                .unwrap();
+            DownloadMetadata {
+                model_id: model_id.to_string(),
+                filename: "model.gguf".to_string(),
+                url: String::new(),
+                local_path: path.to_string_lossy().into_owned(),
+                total_size: len as u64,
+                downloaded_size: len as u64,
+                status: DownloadStatus::Complete,
+                expected_sha256: sha256.map(str::to_string),
+                expected_git_blob: None,
+                commit: None,
+            }
+        };
+        let sha = Some("a");
+        let mut mismatch = download("a/mismatch", 10, 0, sha);
+        mismatch.status = DownloadStatus::HashMismatch;
+        let mut incomplete = download("a/incomplete", 1000, 0, sha);
+        incomplete.status = DownloadStatus::Incomplete;
+        let registry = DownloadRegistry {
+            downloads: vec![
+                download("a/new", 100, 1, sha),
+                download("a/old", 100, 30, sha),
+                download("a/large", 500, 0, sha),
+                download("a/unchecked", 20, 0, None),
+                mismatch,
+                incomplete,
+            ],
+            ..Default::default()
+        };
+
+        let candidates = cleanup_candidates(&registry, &dir);
+        let order: Vec<&str> = candidates.iter().map(|c| c.model_id.as_str()).collect();
+        assert_eq!(
+            order,
+            ["a/unchecked", "a/mismatch", "a/large", "a/old", "a/new"]
+        );
+        assert_eq!(candidates[3].age_days(), 30);
+        assert_eq!(cleanup_candidates(&registry, &dir.join("a/large")).len(), 1);
+    }
+}
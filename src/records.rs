@@ -0,0 +1,169 @@
+//! Flat output records for scripting (`search --format`, `list --format`)
+//!
+//! Each command turns its results into [`Record`]s with a fixed set of
+//! fields, which are printed as CSV or TSV (with a header row), as a JSON
+//! array of objects, or through a template such as `'{id}\t{downloads}'`
+//! with one line per record, ready for fzf, awk or a spreadsheet.
+
+/// One output row: field name and value pairs, in column order
+pub type Record = Vec<(&'static str, String)>;
+
+/// Fields of `search` records
+pub const SEARCH_FIELDS: &[&str] = &[
+    "id",
+    "author",
+    "downloads",
+    "likes",
+    "modified",
+    "gated",
+    "private",
+    "gguf",
+    "format",
+    "params",
+    "size_class",
+    "repo_size",
+    "repo_size_bytes",
+];
+
+/// Fields of `list` records (one per file)
+pub const LIST_FIELDS: &[&str] = &[
+    "id",
+    "quant",
+    "revision",
+    "path",
+    "size",
+    "size_bytes",
+    "sha256",
+];
+
+/// How records are printed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecordFormat {
+    Json,
+    Csv,
+    Tsv,
+    /// `{field}` placeholders; `\t`, `\n` and `\\` are unescaped
+    Template(String),
+}
+
+impl RecordFormat {
+    /// Parse `--format`: `json`, `csv`, `tsv` or a template using `fields`
+    pub fn parse(spec: &str, fields: &[&str]) -> Result<Self, String> {
+        match spec {
+            "json" => return Ok(Self::Json),
+            "csv" => return Ok(Self::Csv),
+            "tsv" => return Ok(Self::Tsv),
+            _ => {}
+        }
+
+        let template = unescape(spec);
+        for placeholder in placeholders(&template) {
+            if !fields.contains(&placeholder) {
+                return Err(format!(
+                    "unknown field '{{{}}}' in --format (available: {})",
+                    placeholder,
+                    fields.join(", ")
+                ));
+            }
+        }
+        Ok(Self::Template(template))
+    }
+
+    /// Render `records` (whose columns are `fields`) to a printable string
+    pub fn render(&self, fields: &[&str], records: &[Record]) -> String {
+        match self {
+            Self::Json => {
+                let rows: Vec<serde_json::Map<String, serde_json::Value>> = records
+                    .iter()
+                    .map(|record| {
+                        record
+                            .iter()
+                            .map(|(key, value)| (key.to_string(), value.clone().into()))
+                            .collect()
+                    })
+                    .collect();
+                let mut out = serde_json::to_string_pretty(&rows).unwrap_or_default();
+                out.push('\n');
+                out
+            }
+            Self::Csv => delimited(fields, records, ',', csv_field),
+            Self::Tsv => delimited(fields, records, '\t', |value| {
+                value.replace(['\t', '\n', '\r'], " ")
+            }),
+            Self::Template(template) => records
+                .iter()
+                .map(|record| {
+                    let mut line = template.clone();
+                    for (key, value) in record {
+                        line = line.replace(&format!("{{{}}}", key), value);
+                    }
+                    line.push('\n');
+                    line
+                })
+                .collect(),
+        }
+    }
+}
+
+fn delimited(
+    fields: &[&str],
+    records: &[Record],
+    separator: char,
+    escape: impl Fn(&str) -> String,
+) -> String {
+    let separator = separator.to_string();
+    let mut out = fields.join(&separator);
+    out.push('\n');
+    for record in records {
+        let values: Vec<String> = record.iter().map(|(_, value)| escape(value)).collect();
+        out.push_str(&values.join(&separator));
+        out.push('\n');
+    }
+    out
+}
+
+/// Quote a CSV field when it contains a separator, quote or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Turn the `\t`, `\n` and `\\` a shell passes through literally into characters
+fn unescape(spec: &str) -> String {
+    let mut out = String::with_capacity(spec.len());
+    let mut chars = spec.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('t') => out.push('\t'),
+            Some('n') => out.push('\n'),
+            Some('\\') => out.push('\\'),
+            Some(other) => {
+                out.push('\\');
+                out.push(other);
+            }
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+/// Names inside `{...}` in a template
+fn placeholders(template: &str) -> Vec<&str> {
+    let mut names = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let Some(len) = rest[start + 1..].find('}') else {
+            break;
+        };
+        names.push(&rest[start + 1..start + 1 + len]);
+        rest = &rest[start + 2 + len..];
+    }
+    names
+}
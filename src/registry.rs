@@ -1,14 +1,55 @@
-use crate::models::{DownloadRegistry, DownloadStatus};
+use crate::models::{DownloadRegistry, DownloadStatus, ModelNote};
+use std::collections::BTreeMap;
 use std::fs;
 use std::io::Write;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+/// How often deferred registry writes reach the disk
+pub const FLUSH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// Whether [`save_registry`] only updates the in-memory copy (see
+/// [`start_deferred_writes`]) instead of rewriting the file
+static DEFERRED_WRITES: AtomicBool = AtomicBool::new(false);
+
+/// In-memory registry while deferred writes are on, and whether it has
+/// changes not yet written to disk
+static CACHED_REGISTRY: Mutex<Option<(DownloadRegistry, bool)>> = Mutex::new(None);
 
 pub fn get_registry_path() -> PathBuf {
     let home = std::env::var("HOME").unwrap_or_else(|_| "/tmp".to_string());
     PathBuf::from(format!("{}/models/hf-downloads.toml", home))
 }
 
+/// Point `HOME` (and with it the registry, trash and config) at an empty
+/// directory for a test; other tests calling this wait for the guard
+#[cfg(test)]
+pub(crate) fn scratch_home(name: &str) -> (std::sync::MutexGuard<'static, ()>, PathBuf) {
+    static HOME: Mutex<()> = Mutex::new(());
+    let guard = HOME.lock().unwrap_or_else(|e| e.into_inner());
+    let home = std::env::temp_dir().join(format!("hf-downloader-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&home);
+    fs::create_dir_all(home.join("models")).unwrap();
+    std::env::set_var("HOME", &home);
+    (guard, home)
+}
+
 pub fn load_registry() -> DownloadRegistry {
+    if !DEFERRED_WRITES.load(Ordering::Relaxed) {
+        return read_registry();
+    }
+
+    let Ok(mut cached) = CACHED_REGISTRY.lock() else {
+        return read_registry();
+    };
+    cached
+        .get_or_insert_with(|| (read_registry(), false))
+        .0
+        .clone()
+}
+
+fn read_registry() -> DownloadRegistry {
     let path = get_registry_path();
     if !path.exists() {
         return DownloadRegistry::default();
@@ -20,16 +61,78 @@ pub fn load_registry() -> DownloadRegistry {
     }
 }
 
+/// Save the registry: right away, or at the next flush once deferred
+/// writes are on
 pub fn save_registry(registry: &DownloadRegistry) {
+    if DEFERRED_WRITES.load(Ordering::Relaxed) {
+        if let Ok(mut cached) = CACHED_REGISTRY.lock() {
+            *cached = Some((registry.clone(), true));
+            return;
+        }
+    }
+    write_registry(registry);
+}
+
+/// Write deferred registry changes to disk, if there are any
+pub fn flush_registry() {
+    // Held while writing, so flushes never overtake each other
+    let Ok(mut cached) = CACHED_REGISTRY.lock() else {
+        return;
+    };
+    if let Some((registry, dirty)) = cached.as_mut() {
+        if *dirty {
+            write_registry(registry);
+            *dirty = false;
+        }
+    }
+}
+
+/// Drop the in-memory copy so the next load reads the file again (a daemon
+/// client taking over from a stopped daemon, which wrote it meanwhile)
+pub fn forget_cached_registry() {
+    if let Ok(mut cached) = CACHED_REGISTRY.lock() {
+        *cached = None;
+    }
+}
+
+/// Batch registry saves during a session: they update an in-memory copy
+/// that a background task writes out every [`FLUSH_INTERVAL`] when changed,
+/// so a long queue run does not rewrite the file on every event. Callers
+/// must [`flush_registry`] before exiting.
+pub fn start_deferred_writes() {
+    if DEFERRED_WRITES.swap(true, Ordering::Relaxed) {
+        return;
+    }
+    tokio::spawn(async {
+        let mut interval = tokio::time::interval(FLUSH_INTERVAL);
+        loop {
+            interval.tick().await;
+            let _ = tokio::task::spawn_blocking(flush_registry).await;
+        }
+    });
+}
+
+/// Rewrite the registry file: written to a temporary file first and renamed
+/// over it, so an interrupted write never leaves a truncated registry
+fn write_registry(registry: &DownloadRegistry) {
+    if crate::instance::is_read_only() || crate::instance::is_daemon_client() {
+        return;
+    }
+
     let path = get_registry_path();
     if let Some(parent) = path.parent() {
         let _ = fs::create_dir_all(parent);
     }
 
     if let Ok(toml_string) = toml::to_string_pretty(registry) {
-        if let Ok(mut file) = fs::File::create(&path) {
-            let _ = file.write_all(toml_string.as_bytes());
-        }
+        crate::instance::with_file_lock(&path, || {
+            let tmp_path = path.with_extension("toml.tmp");
+            let written = fs::File::create(&tmp_path)
+                .and_then(|mut file| file.write_all(toml_string.as_bytes()));
+            if written.is_ok() {
+                let _ = fs::rename(&tmp_path, &path);
+            }
+        });
     }
 }
 
@@ -40,7 +143,10 @@ pub fn get_incomplete_downloads(
         .downloads
         .iter()
         .filter(|d| {
-            d.status == DownloadStatus::Incomplete || d.status == DownloadStatus::HashMismatch
+            matches!(
+                d.status,
+                DownloadStatus::Incomplete | DownloadStatus::HashMismatch | DownloadStatus::Changed
+            )
         })
         .cloned()
         .collect()
@@ -56,3 +162,108 @@ pub fn get_complete_downloads(
         .map(|d| (d.filename.clone(), d.clone()))
         .collect()
 }
+
+/// Stat every file listed as complete and flag the ones moved, deleted or
+/// resized outside the tool as `Changed`, so they are offered for
+/// re-download or removal instead of counted as downloaded; a changed file
+/// that is back at its size is complete again. Saves the registry when a
+/// status moved and returns the number of changed entries.
+pub fn check_complete_files(registry: &mut DownloadRegistry) -> usize {
+    let mut updated = false;
+    for entry in registry
+        .downloads
+        .iter_mut()
+        .filter(|d| matches!(d.status, DownloadStatus::Complete | DownloadStatus::Changed))
+    {
+        let intact = fs::metadata(&entry.local_path).is_ok_and(|meta| {
+            meta.is_file() && (entry.total_size == 0 || meta.len() == entry.total_size)
+        });
+        let status = if intact {
+            DownloadStatus::Complete
+        } else {
+            DownloadStatus::Changed
+        };
+        if entry.status != status {
+            entry.status = status;
+            updated = true;
+        }
+    }
+    if updated {
+        save_registry(registry);
+    }
+    registry
+        .downloads
+        .iter()
+        .filter(|d| d.status == DownloadStatus::Changed)
+        .count()
+}
+
+/// Commit of the latest complete download of each model from its main
+/// branch, for the models that have one recorded
+pub fn downloaded_commits(
+    registry: &DownloadRegistry,
+) -> std::collections::HashMap<String, String> {
+    registry
+        .downloads
+        .iter()
+        .filter(|d| {
+            d.status == DownloadStatus::Complete && crate::download::url_revision(&d.url).is_none()
+        })
+        .filter_map(|d| Some((d.model_id.clone(), d.commit.clone()?)))
+        .collect()
+}
+
+/// Set (or clear, if empty) the note and tags of a model
+pub fn set_model_note(registry: &mut DownloadRegistry, model_id: &str, note: ModelNote) {
+    if note.is_empty() {
+        registry.model_notes.remove(model_id);
+    } else {
+        registry.model_notes.insert(model_id.to_string(), note);
+    }
+}
+
+/// Parse a comma or whitespace separated tag list, dropping `#` prefixes and duplicates
+pub fn parse_tags(input: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in input
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .map(|t| t.trim_start_matches('#'))
+        .filter(|t| !t.is_empty())
+    {
+        if !tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+            tags.push(tag.to_string());
+        }
+    }
+    tags
+}
+
+/// Models whose tags or note fuzzy-match `query`, best matches first
+///
+/// Tag matches rank above note matches; ties are ordered by model ID.
+pub fn search_model_notes<'a>(
+    notes: &'a BTreeMap<String, ModelNote>,
+    query: &str,
+) -> Vec<(&'a String, &'a ModelNote)> {
+    let query = query.trim().to_lowercase();
+    let mut scored: Vec<(u8, &String, &ModelNote)> = notes
+        .iter()
+        .filter_map(|(model_id, note)| {
+            let tag_score = note
+                .tags
+                .iter()
+                .filter_map(|t| crate::utils::fuzzy_score(&query, &t.to_lowercase()))
+                .min();
+            let note_score = crate::utils::fuzzy_score(&query, &note.note.to_lowercase())
+                .map(|score| score + 3);
+            let score = tag_score.into_iter().chain(note_score).min()?;
+            Some((score, model_id, note))
+        })
+        .collect();
+
+    // BTreeMap iteration is already sorted by model ID, and the sort is stable
+    scored.sort_by_key(|(score, _, _)| *score);
+    scored
+        .into_iter()
+        .map(|(_, model_id, note)| (model_id, note))
+        .collect()
+}
@@ -0,0 +1,639 @@
+//! Trash for deleted downloads
+//!
+//! Files deleted by the tool (the `delete` command, incomplete downloads
+//! dropped from the resume popup, complete files changed on disk before
+//! they are downloaded again) are moved to the trash instead of being
+//! removed: `~/models/.trash/<id>/entry.json` records where a file was and
+//! its registry entry, and the file goes to `.trash/<id>/` in its download
+//! root, so deleting from a root on another disk is a rename there rather
+//! than a copy to the home filesystem. `restore` puts a file back and
+//! registers it again; entries older than the retention period
+//! (`trash_retention_days`) are purged at startup and by `trash gc`. A
+//! retention of 0 days deletes files right away, as before.
+
+use crate::models::{DownloadMetadata, DownloadRegistry};
+use crate::registry;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const DAY_SECS: u64 = 24 * 60 * 60;
+
+/// Days a deleted file is kept (from `AppOptions.trash_retention_days`)
+static RETENTION_DAYS: AtomicU64 = AtomicU64::new(7);
+
+/// Apply the retention period (from `AppOptions`)
+pub fn configure(retention_days: u64) {
+    RETENTION_DAYS.store(retention_days, Ordering::Relaxed);
+}
+
+pub fn retention_days() -> u64 {
+    RETENTION_DAYS.load(Ordering::Relaxed)
+}
+
+/// Name of the trash directories
+pub const TRASH_DIR: &str = ".trash";
+
+/// `~/models/.trash`, next to the registry: every entry, and the files
+/// deleted from outside the download roots
+pub fn trash_dir() -> PathBuf {
+    registry::get_registry_path()
+        .parent()
+        .map(|dir| dir.join(TRASH_DIR))
+        .unwrap_or_else(|| PathBuf::from(TRASH_DIR))
+}
+
+/// The trash a deleted file is kept in: the one of its download root (from
+/// its registry entry, else the innermost configured root holding it), on
+/// the same filesystem; `~/models/.trash` for files outside the roots
+fn files_trash_dir(path: &Path, download: Option<&DownloadMetadata>) -> PathBuf {
+    let registered = download.and_then(|d| {
+        let local_path = Path::new(&d.local_path);
+        let model_dir = local_path.ancestors().nth(d.filename.split('/').count())?;
+        if !local_path.ends_with(&d.filename) || !model_dir.ends_with(&d.model_id) {
+            return None;
+        }
+        model_dir.ancestors().nth(d.model_id.split('/').count())
+    });
+    let mut roots = vec![crate::utils::expand_home(
+        &crate::config::load_config().default_directory,
+    )];
+    roots.extend(crate::placement::roots().into_iter().map(PathBuf::from));
+    let configured = roots
+        .iter()
+        .filter(|root| path.starts_with(root))
+        .max_by_key(|root| root.components().count());
+    match registered
+        .filter(|root| path.starts_with(root))
+        .or(configured.map(PathBuf::as_path))
+    {
+        Some(root) => root.join(TRASH_DIR),
+        None => trash_dir(),
+    }
+}
+
+/// A deleted file waiting in the trash
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashEntry {
+    pub id: String,
+    /// Where the file was
+    pub path: PathBuf,
+    pub size: u64,
+    /// Unix time of the deletion
+    pub deleted_at: u64,
+    /// Registry entry the file belonged to, registered again on restore
+    #[serde(default)]
+    pub download: Option<DownloadMetadata>,
+    /// The file, when it is kept in the trash of its download root rather
+    /// than next to `entry.json`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<PathBuf>,
+}
+
+impl TrashEntry {
+    /// The file itself, inside the trash
+    pub fn file_path(&self) -> PathBuf {
+        self.file
+            .clone()
+            .unwrap_or_else(|| trash_dir().join(&self.id).join("file"))
+    }
+
+    /// Remove the entry and its file from the trash
+    fn remove(&self) -> io::Result<()> {
+        if let Some(dir) = self.file.as_deref().and_then(Path::parent) {
+            match fs::remove_dir_all(dir) {
+                Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e),
+                _ => {}
+            }
+        }
+        fs::remove_dir_all(trash_dir().join(&self.id))
+    }
+
+    /// Whole days since the deletion
+    pub fn age_days(&self) -> u64 {
+        now_secs().saturating_sub(self.deleted_at) / DAY_SECS
+    }
+
+    /// Whether the retention period has passed
+    pub fn is_expired(&self) -> bool {
+        now_secs().saturating_sub(self.deleted_at) >= retention_days() * DAY_SECS
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Move a file by renaming it; a blob-store symlink that cannot be renamed
+/// is recreated. Files are never copied: deleting a model should not take
+/// minutes and fill another disk
+fn move_file(from: &Path, to: &Path) -> io::Result<()> {
+    let Err(e) = fs::rename(from, to) else {
+        return Ok(());
+    };
+    if !fs::symlink_metadata(from)?.file_type().is_symlink() {
+        return Err(e);
+    }
+    crate::blobstore::symlink(&fs::read_link(from)?, to)?;
+    fs::remove_file(from)
+}
+
+/// Delete a file by moving it to the trash (or remove it when the retention
+/// is 0 days); `download` is the registry entry to restore with it. Returns
+/// the new trash entry; a file that is already gone is not an error
+pub fn move_to_trash(
+    path: &Path,
+    download: Option<&DownloadMetadata>,
+) -> io::Result<Option<TrashEntry>> {
+    let meta = match fs::symlink_metadata(path) {
+        Ok(meta) => meta,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e),
+    };
+    if retention_days() == 0 {
+        return fs::remove_file(path).map(|()| None);
+    }
+
+    // The first free `<time>-<n>` directory is the entry's id
+    let deleted_at = now_secs();
+    let dir = trash_dir();
+    fs::create_dir_all(&dir)?;
+    let mut n = 0;
+    let id = loop {
+        let id = format!("{}-{}", deleted_at, n);
+        match fs::create_dir(dir.join(&id)) {
+            Ok(()) => break id,
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => n += 1,
+            Err(e) => return Err(e),
+        }
+    };
+
+    let files_dir = files_trash_dir(path, download);
+    let entry = TrashEntry {
+        file: (files_dir != dir).then(|| files_dir.join(&id).join("file")),
+        id,
+        path: path.to_path_buf(),
+        size: fs::metadata(path).map(|m| m.len()).unwrap_or(meta.len()),
+        deleted_at,
+        download: download.cloned(),
+    };
+    let result = (|| {
+        if let Some(parent) = entry.file.as_deref().and_then(Path::parent) {
+            fs::create_dir_all(parent)?;
+        }
+        move_file(path, &entry.file_path())?;
+        let json = serde_json::to_string_pretty(&entry).map_err(io::Error::other)?;
+        fs::write(dir.join(&entry.id).join("entry.json"), json)
+    })();
+    match result {
+        Ok(()) => Ok(Some(entry)),
+        Err(e) => {
+            // The entry could not be written after the file was moved
+            if fs::symlink_metadata(path).is_err() {
+                let _ = move_file(&entry.file_path(), path);
+            }
+            let _ = entry.remove();
+            Err(e)
+        }
+    }
+}
+
+/// How [`delete_downloads`] gets rid of files
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeleteMode {
+    /// Only count what would go
+    DryRun,
+    Trash,
+    /// Remove for good, e.g. to free disk space the trash would still hold
+    Permanent,
+}
+
+/// What [`delete_downloads`] removed
+#[derive(Debug, Default)]
+pub struct Deleted {
+    pub downloads: Vec<DownloadMetadata>,
+    /// Space released once the files are gone for good (see [`Released`])
+    pub bytes: u64,
+    pub errors: Vec<String>,
+}
+
+/// Delete the files of the registry entries `filter` picks, with what is
+/// left of them as `.incomplete`, and drop the entries. When a file of an
+/// entry cannot be deleted, the ones already moved to the trash are put
+/// back and the entry stays
+pub fn delete_downloads(
+    registry: &mut DownloadRegistry,
+    filter: impl Fn(&DownloadMetadata) -> bool,
+    mode: DeleteMode,
+) -> Deleted {
+    delete_downloads_with(registry, filter, mode, |path, download| match mode {
+        DeleteMode::Permanent => fs::remove_file(path).map(|()| None),
+        _ => move_to_trash(path, Some(download)),
+    })
+}
+
+/// [`delete_downloads`], deleting each file with `delete`
+fn delete_downloads_with(
+    registry: &mut DownloadRegistry,
+    filter: impl Fn(&DownloadMetadata) -> bool,
+    mode: DeleteMode,
+    mut delete: impl FnMut(&Path, &DownloadMetadata) -> io::Result<Option<TrashEntry>>,
+) -> Deleted {
+    let dry_run = mode == DeleteMode::DryRun;
+    let mut deleted = Deleted::default();
+    let mut released = Released::new(registry);
+    let mut kept = Vec::new();
+    for download in std::mem::take(&mut registry.downloads) {
+        if !filter(&download) {
+            kept.push(download);
+            continue;
+        }
+        let paths = [
+            PathBuf::from(&download.local_path),
+            PathBuf::from(format!("{}.incomplete", download.local_path)),
+        ];
+        // The files gone, with their trash entries
+        let mut gone: Vec<(Held, Option<TrashEntry>)> = Vec::new();
+        let mut error = None;
+        for path in paths.iter().filter(|p| p.exists()) {
+            let held = Held::of(path);
+            if dry_run {
+                gone.push((held, None));
+                continue;
+            }
+            match delete(path, &download) {
+                Ok(entry) => gone.push((held, entry)),
+                Err(e) => {
+                    error = Some(format!("{}: {}", path.display(), e));
+                    break;
+                }
+            }
+        }
+        if let Some(error) = error {
+            deleted.errors.push(error);
+            // Put back what went to the trash
+            gone.retain(|(_, entry)| {
+                let Some(entry) = entry else {
+                    return true;
+                };
+                match put_back(entry) {
+                    Ok(()) => false,
+                    Err(e) => {
+                        deleted
+                            .errors
+                            .push(format!("{}: {}", entry.path.display(), e));
+                        true
+                    }
+                }
+            });
+            // Only files removed for good (or stuck in the trash, which
+            // restores the entry with them) make it go
+            if gone.is_empty() {
+                kept.push(download);
+                continue;
+            }
+        }
+        for (held, _) in gone {
+            released.release(held);
+        }
+        if dry_run {
+            kept.push(download.clone());
+        }
+        deleted.downloads.push(download);
+    }
+    registry.downloads = kept;
+
+    // Blobs whose last link went; a link in the trash still needs its blob
+    if mode == DeleteMode::Permanent || (mode == DeleteMode::Trash && retention_days() == 0) {
+        for blob in &released.blobs {
+            let _ = fs::remove_file(blob);
+        }
+    }
+    deleted.bytes = released.bytes;
+    deleted
+}
+
+/// What a file is, for counting the space deleting it releases
+enum Held {
+    /// A link into the blob store
+    Blob(PathBuf),
+    /// One of several hard links to the same data
+    HardLink {
+        inode: (u64, u64),
+        links: u64,
+        len: u64,
+    },
+    File(u64),
+    Missing,
+}
+
+impl Held {
+    /// Look at `path` (before it is deleted)
+    fn of(path: &Path) -> Held {
+        let Ok(meta) = fs::symlink_metadata(path) else {
+            return Held::Missing;
+        };
+        if meta.file_type().is_symlink() {
+            return crate::blobstore::stored_blob(path).map_or(Held::Missing, Held::Blob);
+        }
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+            if meta.nlink() > 1 {
+                return Held::HardLink {
+                    inode: (meta.dev(), meta.ino()),
+                    links: meta.nlink(),
+                    len: meta.len(),
+                };
+            }
+        }
+        Held::File(meta.len())
+    }
+}
+
+/// The disk space deleting files releases: a file's bytes once its last
+/// hard link goes, a stored blob's once no registered or trashed download
+/// links to it any more, nothing for the links themselves
+#[derive(Debug, Clone, Default)]
+pub struct Released {
+    blob_links: HashMap<PathBuf, usize>,
+    inode_links: HashMap<(u64, u64), u64>,
+    /// Blobs left without links, for the caller to delete
+    pub blobs: Vec<PathBuf>,
+    pub bytes: u64,
+}
+
+impl Released {
+    /// Nothing deleted yet from the downloads of `registry`
+    pub fn new(registry: &DownloadRegistry) -> Self {
+        Self {
+            blob_links: crate::blobstore::reference_counts(registry),
+            ..Self::default()
+        }
+    }
+
+    /// Count `path` as deleted; call it before the file goes
+    pub fn add(&mut self, path: &Path) {
+        self.release(Held::of(path));
+    }
+
+    fn release(&mut self, held: Held) {
+        match held {
+            Held::Blob(blob) => {
+                let Some(links) = self.blob_links.get_mut(&blob) else {
+                    return;
+                };
+                *links = links.saturating_sub(1);
+                if *links == 0 {
+                    self.bytes += fs::metadata(&blob).map_or(0, |m| m.len());
+                    self.blobs.push(blob);
+                }
+            }
+            Held::HardLink { inode, links, len } => {
+                let deleted = self.inode_links.entry(inode).or_insert(0);
+                *deleted += 1;
+                if *deleted == links {
+                    self.bytes += len;
+                }
+            }
+            Held::File(len) => self.bytes += len,
+            Held::Missing => {}
+        }
+    }
+}
+
+/// Everything in the trash, oldest first (unreadable entries are skipped)
+pub fn entries() -> Vec<TrashEntry> {
+    let Ok(dirs) = fs::read_dir(trash_dir()) else {
+        return Vec::new();
+    };
+    let mut entries: Vec<TrashEntry> = dirs
+        .flatten()
+        .filter_map(|d| {
+            let json = fs::read_to_string(d.path().join("entry.json")).ok()?;
+            serde_json::from_str(&json).ok()
+        })
+        .collect();
+    // Ids share the second of their deletion; the counter after it orders them
+    entries.sort_by_key(|e| {
+        let n = e.id.rsplit('-').next().and_then(|n| n.parse::<u64>().ok());
+        (e.deleted_at, n)
+    });
+    entries
+}
+
+/// Put a deleted file back where it was and register it again; with no id,
+/// the most recently deleted file. Refuses to overwrite a file that has
+/// taken its place
+pub fn restore(id: Option<&str>) -> io::Result<TrashEntry> {
+    let entries = entries();
+    let entry = match id {
+        Some(id) => entries.into_iter().find(|e| e.id == id),
+        None => entries.into_iter().last(),
+    }
+    .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no such file in the trash"))?;
+
+    if fs::symlink_metadata(&entry.path).is_ok() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", entry.path.display()),
+        ));
+    }
+    put_back(&entry)?;
+
+    if let Some(download) = &entry.download {
+        let mut registry = registry::load_registry();
+        registry.downloads.retain(|d| d.url != download.url);
+        registry.downloads.push(download.clone());
+        registry::save_registry(&registry);
+    }
+    Ok(entry)
+}
+
+/// Move an entry's file back where it was and drop the entry
+fn put_back(entry: &TrashEntry) -> io::Result<()> {
+    if let Some(parent) = entry.path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let file = entry.file_path();
+    if let Err(e) = move_file(&file, &entry.path) {
+        // Older versions kept every file in `~/models/.trash`, which may be
+        // on another filesystem than the one it came from
+        if entry.file.is_some() || fs::symlink_metadata(&file)?.file_type().is_symlink() {
+            return Err(e);
+        }
+        fs::copy(&file, &entry.path)?;
+    }
+    entry.remove()
+}
+
+/// Permanently delete the expired entries, or all of them (or only count
+/// them with `dry_run`), with the blobs only they still linked to; returns
+/// their number and the space released
+pub fn purge(all: bool, dry_run: bool) -> (usize, u64) {
+    let mut purged = 0;
+    let mut released = Released::new(&registry::load_registry());
+    for entry in entries() {
+        if !all && !entry.is_expired() {
+            continue;
+        }
+        let held = Held::of(&entry.file_path());
+        if dry_run || entry.remove().is_ok() {
+            purged += 1;
+            released.release(held);
+        }
+    }
+    if !dry_run {
+        for blob in &released.blobs {
+            let _ = fs::remove_file(blob);
+        }
+    }
+    (purged, released.bytes)
+}
+
+/// Purge what has outlived the retention period (at startup)
+pub fn purge_expired() {
+    purge(false, false);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::DownloadStatus;
+
+    /// A complete download of `filename` from author/model in `root`, with
+    /// `content` on disk
+    fn download(root: &Path, filename: &str, content: &[u8]) -> DownloadMetadata {
+        let path = root.join("author/model").join(filename);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, content).unwrap();
+        DownloadMetadata {
+            model_id: "author/model".to_string(),
+            filename: filename.to_string(),
+            url: format!(
+                "https://huggingface.co/author/model/resolve/main/{}",
+                filename
+            ),
+            local_path: path.to_string_lossy().into_owned(),
+            total_size: content.len() as u64,
+            downloaded_size: content.len() as u64,
+            status: DownloadStatus::Complete,
+            expected_sha256: None,
+            expected_git_blob: None,
+            commit: None,
+        }
+    }
+
+    #[test]
+    fn test_delete_restore_purge() {
+        let (_home, home) = registry::scratch_home("trash");
+        // A download root outside the default directory, as with placement rules
+        let root = home.join("disk");
+        let file = download(&root, "model.gguf", &[0; 100]);
+
+        // A second file linking to a blob in the root's store
+        let blob = crate::blobstore::blob_path(&root, &"a".repeat(64));
+        fs::create_dir_all(blob.parent().unwrap()).unwrap();
+        fs::write(&blob, [0; 40]).unwrap();
+        let linked = download(&root, "linked.gguf", b"");
+        fs::remove_file(&linked.local_path).unwrap();
+        crate::blobstore::symlink(&blob, Path::new(&linked.local_path)).unwrap();
+
+        let mut registry = DownloadRegistry {
+            downloads: vec![file.clone(), linked.clone()],
+            ..Default::default()
+        };
+        registry::save_registry(&registry);
+
+        let dry_run = delete_downloads(&mut registry, |_| true, DeleteMode::DryRun);
+        assert_eq!((dry_run.downloads.len(), dry_run.bytes), (2, 140));
+        assert_eq!(registry.downloads.len(), 2);
+
+        let deleted = delete_downloads(&mut registry, |_| true, DeleteMode::Trash);
+        assert!(deleted.errors.is_empty(), "{:?}", deleted.errors);
+        assert_eq!((deleted.downloads.len(), deleted.bytes), (2, 140));
+        assert!(registry.downloads.is_empty());
+        registry::save_registry(&registry);
+
+        // Kept in the root's trash; the blob stays for the trashed link
+        let trashed = entries();
+        assert_eq!(trashed.len(), 2);
+        for entry in &trashed {
+            assert!(entry.file_path().starts_with(root.join(TRASH_DIR)));
+            assert!(fs::symlink_metadata(entry.file_path()).is_ok());
+            assert!(fs::symlink_metadata(&entry.path).is_err());
+        }
+        assert!(blob.exists());
+
+        let model = trashed
+            .iter()
+            .find(|e| e.path == Path::new(&file.local_path))
+            .unwrap();
+        let restored = restore(Some(&model.id)).unwrap();
+        assert_eq!(fs::read(&file.local_path).unwrap(), [0; 100]);
+        assert!(!restored.file_path().exists());
+        let registered = registry::load_registry();
+        assert_eq!(registered.downloads.len(), 1);
+        assert_eq!(registered.downloads[0].local_path, file.local_path);
+
+        // Purging the link frees its blob; the restored file is not touched
+        assert_eq!(purge(true, true), (1, 40));
+        assert!(blob.exists());
+        assert_eq!(purge(true, false), (1, 40));
+        assert!(entries().is_empty());
+        assert!(!blob.exists());
+        assert_eq!(fs::read_dir(root.join(TRASH_DIR)).unwrap().count(), 0);
+        assert!(Path::new(&file.local_path).exists());
+    }
+
+    #[test]
+    fn test_delete_partial_failure() {
+        let (_home, home) = registry::scratch_home("trash-partial");
+        let root = home.join("disk");
+        let file = download(&root, "model.gguf", &[0; 100]);
+        let incomplete = PathBuf::from(format!("{}.incomplete", file.local_path));
+        fs::write(&incomplete, [0; 10]).unwrap();
+        let mut registry = DownloadRegistry {
+            downloads: vec![file.clone()],
+            ..Default::default()
+        };
+
+        // The file goes to the trash, its `.incomplete` cannot be moved
+        let fail_incomplete = |path: &Path, download: &DownloadMetadata| {
+            if path == incomplete {
+                return Err(io::Error::other("busy"));
+            }
+            move_to_trash(path, Some(download))
+        };
+        let deleted =
+            delete_downloads_with(&mut registry, |_| true, DeleteMode::Trash, fail_incomplete);
+        assert_eq!(deleted.errors.len(), 1);
+        assert!(deleted.downloads.is_empty());
+        assert_eq!(deleted.bytes, 0);
+        // Put back, and still registered
+        assert_eq!(registry.downloads.len(), 1);
+        assert_eq!(fs::read(&file.local_path).unwrap(), [0; 100]);
+        assert!(incomplete.exists());
+        assert!(entries().is_empty());
+
+        // Removed for good it cannot be put back, so the entry goes
+        let remove_file = |path: &Path, _: &DownloadMetadata| {
+            if path == incomplete {
+                return Err(io::Error::other("busy"));
+            }
+            fs::remove_file(path).map(|()| None)
+        };
+        let deleted =
+            delete_downloads_with(&mut registry, |_| true, DeleteMode::Permanent, remove_file);
+        assert_eq!(deleted.errors.len(), 1);
+        assert_eq!((deleted.downloads.len(), deleted.bytes), (1, 100));
+        assert!(registry.downloads.is_empty());
+    }
+}
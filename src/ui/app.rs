@@ -1,9 +1,18 @@
 // Declare submodules
+mod accessibility;
+pub mod actions;
+mod conversion;
 mod downloads;
 mod events;
+mod ignorelist;
 mod models;
+mod notes;
+mod placement;
+mod quota;
+mod session;
 mod state;
 mod verification;
+mod watchlist;
 
 // Re-export App struct
 pub use state::App;
@@ -13,9 +22,13 @@ use crate::models::PopupMode;
 use color_eyre::Result;
 use crossterm::event::{Event, KeyEventKind};
 use futures::{FutureExt, StreamExt};
+use ratatui::layout::Rect;
 use ratatui::{DefaultTerminal, Frame};
 use std::sync::atomic::Ordering;
 
+/// Longest gap between the two clicks of a double-click
+const DOUBLE_CLICK_INTERVAL: std::time::Duration = std::time::Duration::from_millis(400);
+
 impl App {
     /// Main application run loop
     pub async fn run(mut self, mut terminal: DefaultTerminal) -> Result<()> {
@@ -24,11 +37,35 @@ impl App {
         // Initialize global download config from options
         self.sync_options_to_config();
 
+        // Batch registry saves; flushed below on exit
+        crate::registry::start_deferred_writes();
+
+        // Deleted files past the trash retention period go for good (left to
+        // the instance that owns them when this one does not)
+        if !crate::instance::is_read_only() && !crate::instance::is_daemon_client() {
+            crate::trash::purge_expired();
+        }
+
         // Scan for incomplete downloads on startup
         self.scan_incomplete_downloads().await;
 
         // Set initial status for empty screen
-        *self.status.write() = "Welcome! Press '/' to search for models".to_string();
+        *self.status.write() = if crate::instance::is_daemon_client() {
+            "Connected to the download daemon: downloads keep running after you quit".to_string()
+        } else if crate::instance::is_read_only() {
+            "Read-only mode: another instance is running, downloads and settings are not saved"
+                .to_string()
+        } else {
+            "Welcome! Press '/' to search for models".to_string()
+        };
+
+        // Report upstream changes to watched models
+        self.spawn_watch_check();
+
+        // Pick up where the last session left off (unless a model was given on the command line)
+        if self.options.restore_session && !self.needs_search_models {
+            self.restore_session();
+        }
         terminal.draw(|frame| self.draw(frame))?;
 
         // Spawn verification worker
@@ -37,6 +74,7 @@ impl App {
         let verification_queue_size = self.verification_queue_size.clone();
         let status_tx_verify = self.status_tx.clone();
         let download_registry = self.download_registry.clone();
+        let download_queue = self.download_queue.clone();
 
         tokio::spawn(async move {
             crate::verification::verification_worker(
@@ -45,51 +83,102 @@ impl App {
                 verification_queue_size,
                 status_tx_verify,
                 download_registry,
+                download_queue,
             )
             .await;
         });
 
-        // Spawn download manager task
-        let download_rx = self.download_rx.clone();
-        let download_progress = self.download_progress.clone();
-        let download_queue = self.download_queue.clone();
-        let status_tx = self.status_tx.clone();
-        let complete_downloads = self.complete_downloads.clone();
-        let verification_queue = self.verification_queue.clone();
-        let verification_queue_size = self.verification_queue_size.clone();
-        tokio::spawn(async move {
-            loop {
-                // Lock only when receiving, release immediately after
-                // This prevents deadlock by not holding download_rx while acquiring other locks
-                let (model_id, filename, path, sha256, hf_token, total_size) = {
-                    let mut rx = download_rx.lock().await;
-                    match rx.recv().await {
-                        Some(msg) => msg,
-                        None => break, // Channel closed
-                    }
-                };
+        // Spawn download manager task; with a daemon running it does the
+        // downloading and this TUI only hands downloads over and shows progress
+        let daemon_client = crate::instance::is_daemon_client();
+        if daemon_client {
+            tokio::spawn(crate::daemon::mirror_status(
+                self.download_progress.clone(),
+                self.download_queue.clone(),
+                self.verification_progress.clone(),
+                self.verification_queue_size.clone(),
+                self.status_tx.clone(),
+            ));
+        }
+        {
+            let download_rx = self.download_rx.clone();
+            let download_tx = self.download_tx.clone();
+            let download_progress = self.download_progress.clone();
+            let download_queue = self.download_queue.clone();
+            let held_download = self.held_download.clone();
+            let status_tx = self.status_tx.clone();
+            let complete_downloads = self.complete_downloads.clone();
+            let verification_queue = self.verification_queue.clone();
+            let verification_queue_size = self.verification_queue_size.clone();
+            tokio::spawn(async move {
+                // Returns only when the daemon has stopped and this TUI
+                // downloads the rest itself
+                if daemon_client {
+                    crate::daemon::forward_downloads(
+                        download_rx.clone(),
+                        download_tx.clone(),
+                        download_queue.clone(),
+                        status_tx.clone(),
+                    )
+                    .await;
+                }
 
-                // download_rx lock is now released before we acquire other locks
-                // Decrement queue size and bytes when we start processing
-                {
-                    let mut queue = download_queue.lock().await;
-                    queue.remove(1, total_size);
+                // The download that ended a small-file batch, next in line
+                let mut held = std::collections::VecDeque::new();
+                loop {
+                    // Lock only when receiving, release immediately after
+                    // This prevents deadlock by not holding download_rx while acquiring other locks
+                    let Some(batch) =
+                        crate::download::next_download_batch(&download_rx, &mut held, |message| {
+                            message.5
+                        })
+                        .await
+                    else {
+                        break; // Channel closed
+                    };
+
+                    // download_rx lock is now released before we acquire other locks
+                    // Decrement queue size and bytes when we start processing
+                    {
+                        let mut queue = download_queue.lock().await;
+                        queue.remove(batch.len(), batch.iter().map(|message| message.5).sum());
+                    }
+                    // Saved on quit ahead of the downloads still in the channel
+                    if let Ok(mut shared) = held_download.lock() {
+                        *shared = held.iter().cloned().collect();
+                    }
+                    let mut params: Vec<_> = batch
+                        .into_iter()
+                        .map(
+                            |(model_id, filename, path, hash, hf_token, total_size, revision)| {
+                                crate::download::DownloadParams {
+                                    model_id,
+                                    filename,
+                                    base_path: path,
+                                    progress: download_progress.clone(),
+                                    status_tx: status_tx.clone(),
+                                    complete_downloads: complete_downloads.clone(),
+                                    expected_hash: hash,
+                                    verification_queue: verification_queue.clone(),
+                                    verification_queue_size: verification_queue_size.clone(),
+                                    hf_token,
+                                    revision,
+                                    total_size,
+                                }
+                            },
+                        )
+                        .collect();
+                    if params.len() == 1 {
+                        start_download(params.remove(0)).await;
+                    } else {
+                        crate::download::start_small_downloads(params).await;
+                    }
+                    if download_queue.lock().await.is_empty() {
+                        crate::webhook::queue_drained(&status_tx);
+                    }
                 }
-                start_download(crate::download::DownloadParams {
-                    model_id,
-                    filename,
-                    base_path: path,
-                    progress: download_progress.clone(),
-                    status_tx: status_tx.clone(),
-                    complete_downloads: complete_downloads.clone(),
-                    expected_sha256: sha256,
-                    verification_queue: verification_queue.clone(),
-                    verification_queue_size: verification_queue_size.clone(),
-                    hf_token,
-                })
-                .await;
-            }
-        });
+            });
+        }
 
         while self.running {
             terminal.draw(|frame| self.draw(frame))?;
@@ -100,6 +189,24 @@ impl App {
                 self.search_models().await;
             }
 
+            // Measure the quota directory of the download popup's path
+            if self.needs_quota_refresh {
+                self.needs_quota_refresh = false;
+                self.refresh_download_quota().await;
+            }
+
+            // Periodic background refresh of the current search
+            if self.options.auto_refresh_enabled
+                && self.popup_mode == PopupMode::None
+                && self.last_search_key.is_some()
+                && self.last_refresh_time.elapsed()
+                    >= std::time::Duration::from_secs(
+                        self.options.auto_refresh_interval_minutes * 60,
+                    )
+            {
+                self.refresh_search().await;
+            }
+
             // Check if we need to load quantizations after UI render
             if self.needs_load_quantizations {
                 self.needs_load_quantizations = false;
@@ -107,7 +214,56 @@ impl App {
                 self.prefetch_adjacent_models();
             }
 
+            // Finish opening a file URL once the search and model details have loaded
+            if self.pending_open_file.is_some()
+                && !*self.loading.read()
+                && (self.models.read().is_empty() || !*self.loading_quants.read())
+            {
+                self.select_pending_open_file();
+            }
+
+            // Restore the saved session's selection once its search (then model details) loaded
+            if self.pending_session.is_some()
+                && !self.needs_search_models
+                && !self.needs_load_quantizations
+                && !*self.loading.read()
+                && !*self.loading_quants.read()
+            {
+                self.restore_session_selection();
+            }
+
+            // Put prioritized downloads ahead; keep the queue popup's list current
+            if self.needs_sort_download_queue || self.popup_mode == PopupMode::DownloadQueue {
+                self.sort_download_queue();
+            }
+
+            // Once queued downloads finish, check split GGUFs arrived complete
+            if !self.pending_part_paths.is_empty() && self.cached_download_queue.size == 0 {
+                self.check_pending_part_sets();
+            }
+
+            // ...and convert repositories queued for GGUF conversion
+            if self
+                .conversion_jobs
+                .read()
+                .iter()
+                .any(|job| job.state == crate::convert::ConversionState::Waiting)
+            {
+                self.start_pending_conversions();
+            }
+
             self.handle_crossterm_events().await?;
+
+            // A lone `g` or digit that timed out runs as its own key
+            self.flush_pending_keys().await;
+        }
+
+        // Keep downloads that never started for the resume popup next time
+        self.save_pending_queue().await;
+        crate::registry::flush_registry();
+
+        if self.options.restore_session {
+            session::save_session(&self.capture_session());
         }
         Ok(())
     }
@@ -120,6 +276,8 @@ impl App {
         let quantizations = self.quantizations.read().clone();
         let model_metadata = self.model_metadata.read().clone();
         let file_tree = self.file_tree.read().clone();
+        let related_models = self.related_models.read().clone();
+        let watched_model_ids = self.watched_models.read().clone();
 
         // For tokio Mutex, use try_lock() to avoid blocking/deadlock
         // Fall back to cached values if lock is held by another task
@@ -155,14 +313,26 @@ impl App {
                 model_metadata: &model_metadata,
                 file_tree: &file_tree,
                 file_tree_state: &mut self.file_tree_state,
+                related_models: &related_models,
+                related_state: &mut self.related_state,
                 sort_field: self.sort_field,
                 sort_direction: self.sort_direction,
                 filter_min_downloads: self.filter_min_downloads,
                 filter_min_likes: self.filter_min_likes,
+                filter_params: self.filter_params,
+                filter_access: self.filter_access,
+                filter_content: self.filter_content,
+                search_full_text: self.search_full_text,
                 focused_filter_field: self.focused_filter_field,
                 panel_areas: &mut self.panel_areas,
                 hovered_panel: &self.hovered_panel,
                 filter_areas: &mut self.filter_areas,
+                preset_areas: &mut self.preset_areas,
+                new_model_ids: &self.new_model_ids,
+                recent_gguf_days: self.recent_gguf_days,
+                watched_model_ids: &watched_model_ids,
+                model_notes: &self.model_notes,
+                marked_quants: &self.marked_quants,
             },
         );
 
@@ -213,13 +383,107 @@ impl App {
         // Render popups (must be last to appear on top)
         match self.popup_mode {
             PopupMode::SearchPopup => {
-                crate::ui::render::render_search_popup(frame, &self.input);
+                crate::ui::render::render_search_popup(frame, &self.input, self.search_full_text);
+            }
+            PopupMode::OpenModel => {
+                crate::ui::render::render_open_model_popup(frame, &self.open_input);
+            }
+            PopupMode::ModelNotes => {
+                if let Some(ref model_id) = self.note_model_id {
+                    crate::ui::render::render_notes_popup(
+                        frame,
+                        model_id,
+                        &self.note_tags_input,
+                        &self.note_text_input,
+                        self.note_field,
+                    );
+                }
+            }
+            PopupMode::CommandPalette => {
+                crate::ui::render::render_command_palette(
+                    frame,
+                    &self.palette_input,
+                    &actions::filter_actions(self.palette_input.value()),
+                    self.palette_selected,
+                );
+            }
+            PopupMode::QuantDiff => {
+                if let Some((ref left, ref right)) = self.quant_diff {
+                    crate::ui::render::render_quant_diff_popup(frame, left, right);
+                }
+            }
+            PopupMode::WatchUpdates => {
+                crate::ui::render::render_watch_updates_popup(
+                    frame,
+                    &self.watch_updates.read(),
+                    self.watch_updates_scroll,
+                );
+            }
+            PopupMode::IgnoreList => {
+                crate::ui::render::render_ignore_list_popup(
+                    frame,
+                    &self.ignore_list_view,
+                    self.ignore_list_selected,
+                );
+            }
+            PopupMode::DownloadPlan => {
+                crate::ui::render::render_download_plan_popup(
+                    frame,
+                    &self.download_plan,
+                    self.download_plan_scroll,
+                );
+            }
+            PopupMode::PromptFormat => {
+                if let Some(ref format) = self.prompt_format {
+                    crate::ui::render::render_prompt_format_popup(
+                        frame,
+                        format,
+                        self.prompt_format_scroll,
+                    );
+                }
+            }
+            PopupMode::Stats => {
+                if let Some(ref stats) = self.history_stats {
+                    crate::ui::render::render_stats_popup(frame, stats);
+                }
+            }
+            PopupMode::AuthorProfile => {
+                if let Some(ref profile) = self.author_profile {
+                    crate::ui::render::render_author_profile_popup(frame, profile);
+                }
+            }
+            PopupMode::DownloadQueue => {
+                crate::ui::render::render_download_queue_popup(
+                    frame,
+                    &self.download_queue_view,
+                    self.download_queue_selected,
+                    &self.conversion_jobs.read(),
+                );
+            }
+            PopupMode::QuotaCleanup => {
+                if let Some(summary) = self.download_summary.as_ref() {
+                    crate::ui::render::render_quota_cleanup_popup(
+                        frame,
+                        summary,
+                        &self.quota_candidates,
+                        self.quota_selected,
+                    );
+                }
             }
             PopupMode::ResumeDownload => {
-                crate::ui::render::render_resume_popup(frame, &self.incomplete_downloads);
+                crate::ui::render::render_resume_popup(
+                    frame,
+                    &self.incomplete_downloads,
+                    &self.queued_downloads,
+                );
             }
             PopupMode::DownloadPath => {
-                crate::ui::render::render_download_path_popup(frame, &self.download_path_input);
+                crate::ui::render::render_download_path_popup(
+                    frame,
+                    &self.download_path_input,
+                    self.download_summary.as_ref(),
+                    self.downloads_locked(),
+                );
             }
             PopupMode::Options => {
                 crate::ui::render::render_options_popup(
@@ -227,6 +491,7 @@ impl App {
                     &self.options,
                     &self.options_directory_input,
                     &self.options_token_input,
+                    &self.options_tool_path_input,
                 );
             }
             PopupMode::AuthError { ref model_url } => {
@@ -239,6 +504,8 @@ impl App {
             }
             PopupMode::None => {}
         }
+
+        self.finish_accessible_frame(frame);
     }
 
     /// Handle mouse click events immediately (synchronous)
@@ -258,13 +525,68 @@ impl App {
             }
         }
 
+        if let Some(preset) = self
+            .preset_areas
+            .iter()
+            .find(|(_, area)| area.contains(pos))
+            .map(|(preset, _)| *preset)
+        {
+            self.select_filter_preset(preset);
+            return;
+        }
+
         // Check if click is within any panel area
-        for (pane, area) in &self.panel_areas {
-            if area.contains(pos) {
-                // Use focus_pane() to also select first item if needed
-                self.focus_pane(*pane);
-                return;
+        if let Some((pane, area)) = self
+            .panel_areas
+            .iter()
+            .find(|(_, area)| area.contains(pos))
+            .copied()
+        {
+            // Use focus_pane() to also select first item if needed
+            self.focus_pane(pane);
+            self.handle_list_click(pane, area, row);
+        }
+    }
+
+    /// Select the list item under a click; a second click on the same quant
+    /// or file opens the download popup
+    fn handle_list_click(&mut self, pane: crate::models::FocusedPane, area: Rect, row: u16) {
+        use crate::models::FocusedPane;
+
+        // Rows inside the borders (and below the results' column headers),
+        // counted from the list's scroll offset
+        let first_row = area.y + if pane == FocusedPane::Models { 2 } else { 1 };
+        if row < first_row || row + 1 >= area.bottom() {
+            return;
+        }
+        let offset = match pane {
+            FocusedPane::Models => self.list_state.offset(),
+            FocusedPane::QuantizationGroups => self.quant_list_state.offset(),
+            FocusedPane::QuantizationFiles => self.quant_file_list_state.offset(),
+            FocusedPane::FileTree => self.file_tree_state.offset(),
+            // Related models sit below the metadata text, not at the top
+            FocusedPane::ModelMetadata => return,
+        };
+        let index = offset + (row - first_row) as usize;
+        if index >= self.focused_list_len() {
+            return;
+        }
+        self.select_in_focused_pane(index);
+
+        let now = std::time::Instant::now();
+        let double_click = self.last_click.is_some_and(|(at, last_pane, last_index)| {
+            last_pane == pane && last_index == index && now - at <= DOUBLE_CLICK_INTERVAL
+        });
+        if double_click {
+            self.last_click = None;
+            if matches!(
+                pane,
+                FocusedPane::QuantizationGroups | FocusedPane::QuantizationFiles
+            ) {
+                self.trigger_download();
             }
+        } else {
+            self.last_click = Some((now, pane, index));
         }
     }
 
@@ -312,6 +634,36 @@ impl App {
                     crate::utils::format_number(self.filter_min_likes)
                 );
             }
+            3 => {
+                // Size: cycle through any, ≤8B, 8B–34B, 34B–70B, ≥70B
+                let steps = crate::models::ParamRange::STEPS;
+                let current_idx = steps
+                    .iter()
+                    .position(|&x| x == self.filter_params)
+                    .unwrap_or(0);
+                let new_idx = (current_idx + 1) % steps.len();
+                self.filter_params = steps[new_idx];
+                *self.status.write() = format!("Size: {}", self.filter_params.label());
+            }
+            4 => {
+                // Access: cycle through all, ungated, public, open
+                let steps = crate::models::AccessFilter::STEPS;
+                let current_idx = steps
+                    .iter()
+                    .position(|&x| x == self.filter_access)
+                    .unwrap_or(0);
+                let new_idx = (current_idx + 1) % steps.len();
+                self.filter_access = steps[new_idx];
+                *self.status.write() = format!("Access: {}", self.filter_access.label());
+            }
+            5 => {
+                // Content: toggle between all and safe
+                self.filter_content = match self.filter_content {
+                    crate::models::ContentFilter::All => crate::models::ContentFilter::Safe,
+                    crate::models::ContentFilter::Safe => crate::models::ContentFilter::All,
+                };
+                *self.status.write() = format!("Content: {}", self.filter_content.label());
+            }
             _ => {}
         }
 
@@ -365,7 +717,11 @@ impl App {
                 }
             }
             crate::models::FocusedPane::ModelMetadata => {
-                // Metadata pane has no scrollable list
+                if scroll_up {
+                    self.previous_related();
+                } else {
+                    self.next_related();
+                }
             }
             crate::models::FocusedPane::FileTree => {
                 if scroll_up {
@@ -446,6 +802,52 @@ impl App {
                     crate::utils::format_number(self.filter_min_likes)
                 );
             }
+            3 => {
+                // Size: cycle through steps
+                let steps = crate::models::ParamRange::STEPS;
+                let current_idx = steps
+                    .iter()
+                    .position(|&x| x == self.filter_params)
+                    .unwrap_or(0);
+                let new_idx = if scroll_up {
+                    if current_idx == 0 {
+                        steps.len() - 1
+                    } else {
+                        current_idx - 1
+                    }
+                } else {
+                    (current_idx + 1) % steps.len()
+                };
+                self.filter_params = steps[new_idx];
+                *self.status.write() = format!("Size: {}", self.filter_params.label());
+            }
+            4 => {
+                // Access: cycle through steps
+                let steps = crate::models::AccessFilter::STEPS;
+                let current_idx = steps
+                    .iter()
+                    .position(|&x| x == self.filter_access)
+                    .unwrap_or(0);
+                let new_idx = if scroll_up {
+                    if current_idx == 0 {
+                        steps.len() - 1
+                    } else {
+                        current_idx - 1
+                    }
+                } else {
+                    (current_idx + 1) % steps.len()
+                };
+                self.filter_access = steps[new_idx];
+                *self.status.write() = format!("Access: {}", self.filter_access.label());
+            }
+            5 => {
+                // Content: two steps, so either direction toggles
+                self.filter_content = match self.filter_content {
+                    crate::models::ContentFilter::All => crate::models::ContentFilter::Safe,
+                    crate::models::ContentFilter::Safe => crate::models::ContentFilter::All,
+                };
+                *self.status.write() = format!("Content: {}", self.filter_content.label());
+            }
             _ => {}
         }
 
@@ -506,10 +908,8 @@ impl App {
             maybe_event = self.event_stream.next().fuse() => {
                 if let Some(Ok(event)) = maybe_event {
                     match event {
-                        Event::Key(key) => {
-                            if key.kind == KeyEventKind::Press {
-                                self.on_key_event(key).await;
-                            }
+                        Event::Key(key) if key.kind == KeyEventKind::Press => {
+                            self.on_key_event(key).await;
                         }
                         Event::Mouse(mouse_event) => {
                             match mouse_event.kind {
@@ -549,10 +949,8 @@ impl App {
             match futures::poll!(self.event_stream.next()) {
                 std::task::Poll::Ready(Some(Ok(event))) => {
                     match event {
-                        Event::Key(key) => {
-                            if key.kind == KeyEventKind::Press {
-                                self.on_key_event(key).await;
-                            }
+                        Event::Key(key) if key.kind == KeyEventKind::Press => {
+                            self.on_key_event(key).await;
                         }
                         Event::Mouse(mouse_event) => {
                             match mouse_event.kind {
@@ -0,0 +1,309 @@
+//! Screen-reader support (`screen_reader` option / `--screen-reader`)
+//!
+//! Every frame gets a one-line description of what has focus, drawn on the
+//! top row in screen-reader mode. With a log file set, changes of that line
+//! and of the status message are appended to it as plain text, so a speech
+//! tool following the file reads them without scraping the screen.
+
+use super::state::App;
+use crate::models::*;
+use crate::utils::{format_number, format_size};
+use ratatui::Frame;
+use std::io::Write;
+use std::path::PathBuf;
+
+/// "`what`, 3 of 12: `item`"
+fn position(what: &str, selected: Option<usize>, len: usize, item: String) -> String {
+    match selected {
+        Some(idx) if idx < len => format!("{}, {} of {}: {}", what, idx + 1, len, item),
+        _ if len == 0 => format!("{}, empty", what),
+        _ => format!("{}, {} items, none selected", what, len),
+    }
+}
+
+impl App {
+    /// Turn on screen-reader output from `--screen-reader` or the option, and
+    /// open the state log (`--screen-reader-log` before the option)
+    pub fn enable_screen_reader(&mut self, forced: bool, log: Option<PathBuf>) {
+        let enabled = forced || self.options.screen_reader;
+        crate::ui::render::set_screen_reader(enabled);
+
+        let Some(path) = log.or_else(|| {
+            self.options
+                .screen_reader_log
+                .as_deref()
+                .filter(|path| !path.is_empty())
+                .map(PathBuf::from)
+        }) else {
+            return;
+        };
+        match std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+        {
+            Ok(file) => self.screen_reader_log = Some(file),
+            Err(e) => {
+                *self.error.write() = Some(format!(
+                    "Failed to open screen reader log {}: {}",
+                    path.display(),
+                    e
+                ));
+            }
+        }
+    }
+
+    /// Describe the focused pane or popup and its selected item in one line
+    pub fn describe_focus(&self) -> String {
+        match &self.popup_mode {
+            PopupMode::None => self.describe_pane(),
+            PopupMode::SearchPopup => {
+                let scope = if self.search_full_text {
+                    "card text"
+                } else {
+                    "model names"
+                };
+                format!("Search {}: {}", scope, self.input.value())
+            }
+            PopupMode::OpenModel => format!("Open model ID or URL: {}", self.open_input.value()),
+            PopupMode::CommandPalette => {
+                let matches = super::actions::filter_actions(self.palette_input.value());
+                let item = matches
+                    .get(self.palette_selected)
+                    .map(|entry| entry.name.to_string())
+                    .unwrap_or_default();
+                position(
+                    &format!("Command palette \"{}\"", self.palette_input.value()),
+                    Some(self.palette_selected),
+                    matches.len(),
+                    item,
+                )
+            }
+            PopupMode::IgnoreList => {
+                let item = self
+                    .ignore_list_view
+                    .get(self.ignore_list_selected)
+                    .map(|entry| entry.label())
+                    .unwrap_or_default();
+                position(
+                    "Hidden models and authors",
+                    Some(self.ignore_list_selected),
+                    self.ignore_list_view.len(),
+                    item,
+                )
+            }
+            PopupMode::QuotaCleanup => {
+                let item = self
+                    .quota_candidates
+                    .get(self.quota_selected)
+                    .map(|c| format!("{}, {}", c.model_id, format_size(c.bytes)))
+                    .unwrap_or_default();
+                position(
+                    "Over quota, models to delete",
+                    Some(self.quota_selected),
+                    self.quota_candidates.len(),
+                    item,
+                )
+            }
+            PopupMode::DownloadQueue => {
+                let item = self
+                    .download_queue_view
+                    .get(self.download_queue_selected)
+                    .map(|d| {
+                        format!(
+                            "{} from {}, {}",
+                            d.filename,
+                            d.model_id,
+                            format_size(d.total_size)
+                        )
+                    })
+                    .unwrap_or_default();
+                position(
+                    "Download queue",
+                    Some(self.download_queue_selected),
+                    self.download_queue_view.len(),
+                    item,
+                )
+            }
+            PopupMode::DownloadPath if self.downloads_locked() => format!(
+                "Download to: {}, downloads locked, Ctrl+Y to download anyway",
+                self.download_path_input.value()
+            ),
+            PopupMode::DownloadPath => format!("Download to: {}", self.download_path_input.value()),
+            PopupMode::ModelNotes => {
+                let model_id = self.note_model_id.as_deref().unwrap_or("");
+                let field = if self.note_field == 0 { "tags" } else { "note" };
+                format!("Notes for {}, editing {}", model_id, field)
+            }
+            PopupMode::Options => format!("Options, field {}", self.options.selected_field + 1),
+            PopupMode::AuthError { model_url } => {
+                format!("Authentication required for {}", model_url)
+            }
+            PopupMode::ResumeDownload => "Resume incomplete downloads? (y/n)".to_string(),
+            PopupMode::QuantDiff => "Quantization comparison".to_string(),
+            PopupMode::WatchUpdates => "Watched models updated".to_string(),
+            PopupMode::Stats => "Download statistics".to_string(),
+            PopupMode::DownloadPlan => "Download plan".to_string(),
+            PopupMode::PromptFormat => "Prompt format".to_string(),
+            PopupMode::AuthorProfile => "Author profile".to_string(),
+        }
+    }
+
+    /// The focused pane and its selected item
+    fn describe_pane(&self) -> String {
+        match self.focused_pane {
+            FocusedPane::Models => {
+                let models = self.models.read();
+                let selected = self.list_state.selected();
+                let item = selected
+                    .and_then(|idx| models.get(idx))
+                    .map(|model| self.describe_model(model))
+                    .unwrap_or_default();
+                let what = if *self.loading.read() {
+                    "Results, loading"
+                } else {
+                    "Results"
+                };
+                position(what, selected, models.len(), item)
+            }
+            FocusedPane::QuantizationGroups => {
+                let quantizations = self.quantizations.read();
+                let selected = self.quant_list_state.selected();
+                let item = selected
+                    .and_then(|idx| quantizations.get(idx))
+                    .map(|group| {
+                        let mut item = format!(
+                            "{}, {}, {} file{}",
+                            group.quant_type,
+                            format_size(group.total_size),
+                            group.files.len(),
+                            if group.files.len() == 1 { "" } else { "s" }
+                        );
+                        if self.marked_quants.contains(&group.quant_type) {
+                            item.push_str(", marked");
+                        }
+                        item
+                    })
+                    .unwrap_or_default();
+                position("Quantizations", selected, quantizations.len(), item)
+            }
+            FocusedPane::QuantizationFiles => {
+                let quantizations = self.quantizations.read();
+                let files = self
+                    .quant_list_state
+                    .selected()
+                    .and_then(|idx| quantizations.get(idx))
+                    .map(|group| group.files.as_slice())
+                    .unwrap_or_default();
+                let selected = self.quant_file_list_state.selected();
+                let item = selected
+                    .and_then(|idx| files.get(idx))
+                    .map(|file| format!("{}, {}", file.filename, format_size(file.size)))
+                    .unwrap_or_default();
+                position("Files", selected, files.len(), item)
+            }
+            FocusedPane::ModelMetadata => {
+                let related = self.related_models.read();
+                let selected = self.related_state.selected();
+                let item = selected
+                    .and_then(|idx| related.get(idx))
+                    .map(|model| self.describe_model(model))
+                    .unwrap_or_default();
+                position("Related models", selected, related.len(), item)
+            }
+            FocusedPane::FileTree => {
+                let tree = self.file_tree.read();
+                let flat = tree
+                    .as_ref()
+                    .map(crate::ui::render::flatten_tree_for_navigation)
+                    .unwrap_or_default();
+                let selected = self.file_tree_state.selected();
+                let item = selected
+                    .and_then(|idx| flat.get(idx))
+                    .map(|node| {
+                        if node.is_dir {
+                            let state = if node.expanded {
+                                "expanded"
+                            } else {
+                                "collapsed"
+                            };
+                            match crate::api::runtime_export(&node.path) {
+                                Some(runtime) if !node.path.contains('/') => format!(
+                                    "folder {}, {} export, {}",
+                                    node.path,
+                                    runtime.label(),
+                                    state
+                                ),
+                                _ => format!("folder {}, {}", node.path, state),
+                            }
+                        } else {
+                            match node.size {
+                                Some(size) => format!("{}, {}", node.path, format_size(size)),
+                                None => node.path.clone(),
+                            }
+                        }
+                    })
+                    .unwrap_or_default();
+                position("File tree", selected, flat.len(), item)
+            }
+        }
+    }
+
+    /// A result row in words, with the markers the list shows as symbols
+    fn describe_model(&self, model: &ModelInfo) -> String {
+        let mut item = format!(
+            "{}, {} downloads, {} likes",
+            model.id,
+            format_number(model.downloads),
+            format_number(model.likes)
+        );
+        let markers = [
+            (self.new_model_ids.contains(&model.id), "new"),
+            (self.watched_models.read().contains(&model.id), "watched"),
+            (model.is_gated(), "gated"),
+            (model.private, "private"),
+            (model.quant_format() == Some(QuantFormat::Gguf), "GGUF"),
+        ];
+        for (_, marker) in markers.iter().filter(|(set, _)| *set) {
+            item.push_str(", ");
+            item.push_str(marker);
+        }
+        item
+    }
+
+    /// Screen-reader pass at the end of a frame: the focus line on top (in
+    /// screen-reader mode) and new focus/status lines in the log
+    pub fn finish_accessible_frame(&mut self, frame: &mut Frame) {
+        let screen_reader = crate::ui::render::screen_reader();
+        if !screen_reader && self.screen_reader_log.is_none() {
+            return;
+        }
+        let focus = self.describe_focus();
+        if screen_reader {
+            crate::ui::render::render_screen_reader_overlay(frame, &focus);
+        }
+
+        let status = match self.error.read().as_ref() {
+            Some(error) => format!("Error: {}", error),
+            None => self.status.read().clone(),
+        };
+        let Some(log) = self.screen_reader_log.as_mut() else {
+            return;
+        };
+        let mut lines = String::new();
+        if status != self.last_announced_status && !status.is_empty() {
+            lines.push_str(&status);
+            lines.push('\n');
+        }
+        if focus != self.last_announced_focus {
+            lines.push_str(&focus);
+            lines.push('\n');
+        }
+        self.last_announced_status = status;
+        self.last_announced_focus = focus;
+        if !lines.is_empty() && log.write_all(lines.as_bytes()).is_err() {
+            // A reader that went away shouldn't take every later frame down
+            self.screen_reader_log = None;
+        }
+    }
+}
@@ -0,0 +1,692 @@
+//! Action table shared by the keybindings and the ':' command palette
+//!
+//! Every user-triggerable action is listed once in [`ACTIONS`] with its key
+//! bindings and a human-readable name. Normal-mode key presses are resolved
+//! through this table (first matching, currently available entry wins, so
+//! order matters just like match arms), and the command palette lists the
+//! same entries, so a new feature only needs to be added here to get both.
+
+use super::state::App;
+use crate::models::*;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// Every action that can be bound to a key or run from the command palette
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Quit,
+    Search,
+    OpenModel,
+    MyModels,
+    AuthorProfile,
+    RecentGguf,
+    CommandPalette,
+    Download,
+    DownloadPlan,
+    DownloadQueue,
+    AdoptLocalModels,
+    OpenBaseModel,
+    DownloadWithBase,
+    CompareQuantization,
+    ToggleQuantMark,
+    ToggleWatch,
+    WatchUpdates,
+    HideModel,
+    HideAuthor,
+    IgnoreList,
+    EditNotes,
+    PromptFormat,
+    Stats,
+    Verify,
+    SkipVerification,
+    CycleVerificationSchedule,
+    Options,
+    SaveFilters,
+    CycleSort,
+    ToggleSortDirection,
+    CycleFilterField,
+    IncreaseFilter,
+    DecreaseFilter,
+    Refresh,
+    ResetFilters,
+    PresetNoFilters,
+    PresetPopular,
+    PresetHighlyRated,
+    PresetRecent,
+    ToggleFocus,
+    QuantPaneLeft,
+    QuantPaneRight,
+    MoveDown,
+    MoveUp,
+    PageDown,
+    PageUp,
+    GoTop,
+    GoBottom,
+    Activate,
+}
+
+/// A key that triggers an action; `modifiers: None` matches any modifiers
+pub struct Binding {
+    pub modifiers: Option<KeyModifiers>,
+    pub code: KeyCode,
+}
+
+impl Binding {
+    const fn key(code: KeyCode) -> Self {
+        Self {
+            modifiers: None,
+            code,
+        }
+    }
+
+    const fn with(modifiers: KeyModifiers, code: KeyCode) -> Self {
+        Self {
+            modifiers: Some(modifiers),
+            code,
+        }
+    }
+
+    fn matches(&self, key: &KeyEvent) -> bool {
+        self.code == key.code && (self.modifiers.is_none() || self.modifiers == Some(key.modifiers))
+    }
+}
+
+/// One row of the action table
+pub struct ActionEntry {
+    pub action: Action,
+    pub name: &'static str,
+    /// Key hint shown in the command palette
+    pub keys: &'static str,
+    pub bindings: &'static [Binding],
+    /// Navigation primitives are bound to keys but hidden from the palette
+    pub in_palette: bool,
+}
+
+const fn entry(
+    action: Action,
+    name: &'static str,
+    keys: &'static str,
+    bindings: &'static [Binding],
+) -> ActionEntry {
+    ActionEntry {
+        action,
+        name,
+        keys,
+        bindings,
+        in_palette: true,
+    }
+}
+
+const fn hidden(action: Action, keys: &'static str, bindings: &'static [Binding]) -> ActionEntry {
+    ActionEntry {
+        action,
+        name: "",
+        keys,
+        bindings,
+        in_palette: false,
+    }
+}
+
+use KeyCode::Char;
+
+pub const ACTIONS: &[ActionEntry] = &[
+    entry(
+        Action::Quit,
+        "Quit",
+        "q / Ctrl+C",
+        &[
+            Binding::key(Char('q')),
+            Binding::with(KeyModifiers::CONTROL, Char('c')),
+            Binding::with(KeyModifiers::CONTROL, Char('C')),
+        ],
+    ),
+    // Before 'd' (download), whose binding accepts any modifiers
+    hidden(
+        Action::PageDown,
+        "Ctrl+D / PgDn",
+        &[
+            Binding::with(KeyModifiers::CONTROL, Char('d')),
+            Binding::key(KeyCode::PageDown),
+        ],
+    ),
+    hidden(
+        Action::PageUp,
+        "Ctrl+U / PgUp",
+        &[
+            Binding::with(KeyModifiers::CONTROL, Char('u')),
+            Binding::key(KeyCode::PageUp),
+        ],
+    ),
+    entry(Action::Search, "Search models", "/", &[Binding::key(Char('/'))]),
+    entry(
+        Action::OpenModel,
+        "Open model ID or URL",
+        "g",
+        &[Binding::key(Char('g'))],
+    ),
+    entry(
+        Action::MyModels,
+        "My models (including private)",
+        "m",
+        &[Binding::key(Char('m'))],
+    ),
+    entry(
+        Action::AuthorProfile,
+        "Author profile of the selected model",
+        "A",
+        &[Binding::with(KeyModifiers::SHIFT, Char('A'))],
+    ),
+    entry(
+        Action::RecentGguf,
+        "New GGUF uploads (grouped by base model)",
+        "N",
+        &[Binding::key(Char('N'))],
+    ),
+    hidden(Action::CommandPalette, ":", &[Binding::key(Char(':'))]),
+    entry(
+        Action::Download,
+        "Download selection",
+        "d",
+        &[Binding::key(Char('d'))],
+    ),
+    entry(
+        Action::DownloadPlan,
+        "Show download plan (dry run)",
+        "D",
+        &[Binding::with(KeyModifiers::SHIFT, Char('D'))],
+    ),
+    entry(
+        Action::DownloadQueue,
+        "Download queue (priorities and order)",
+        "Q",
+        &[Binding::with(KeyModifiers::SHIFT, Char('Q'))],
+    ),
+    entry(
+        Action::AdoptLocalModels,
+        "Adopt models other tools put in the download directory",
+        "",
+        &[],
+    ),
+    entry(
+        Action::OpenBaseModel,
+        "Open adapter base model",
+        "b",
+        &[Binding::key(Char('b'))],
+    ),
+    entry(
+        Action::DownloadWithBase,
+        "Download adapter with base model",
+        "B",
+        &[Binding::with(KeyModifiers::SHIFT, Char('B'))],
+    ),
+    entry(
+        Action::CompareQuantization,
+        "Compare quantizations",
+        "c",
+        &[Binding::key(Char('c'))],
+    ),
+    entry(
+        Action::ToggleQuantMark,
+        "Mark quantization for combined download",
+        "Space",
+        &[Binding::key(Char(' '))],
+    ),
+    entry(
+        Action::ToggleWatch,
+        "Watch / unwatch model",
+        "w",
+        &[Binding::key(Char('w'))],
+    ),
+    entry(
+        Action::WatchUpdates,
+        "Show watched model updates",
+        "W",
+        &[Binding::with(KeyModifiers::SHIFT, Char('W'))],
+    ),
+    entry(
+        Action::HideModel,
+        "Hide model from searches",
+        "i",
+        &[Binding::key(Char('i'))],
+    ),
+    entry(
+        Action::HideAuthor,
+        "Hide every model of the author from searches",
+        "I",
+        &[Binding::with(KeyModifiers::SHIFT, Char('I'))],
+    ),
+    entry(
+        Action::IgnoreList,
+        "Hidden models and authors (unhide)",
+        "",
+        &[],
+    ),
+    entry(
+        Action::EditNotes,
+        "Edit model notes and tags",
+        "n",
+        &[Binding::key(Char('n'))],
+    ),
+    entry(
+        Action::PromptFormat,
+        "Show chat template and special tokens",
+        "t",
+        &[Binding::key(Char('t'))],
+    ),
+    entry(
+        Action::Stats,
+        "Download history statistics",
+        "H",
+        &[Binding::with(KeyModifiers::SHIFT, Char('H'))],
+    ),
+    entry(
+        Action::Verify,
+        "Verify downloaded file (SHA256)",
+        "v",
+        &[Binding::key(Char('v'))],
+    ),
+    entry(
+        Action::SkipVerification,
+        "Skip verification of selected file",
+        "x",
+        &[Binding::key(Char('x'))],
+    ),
+    entry(
+        Action::CycleVerificationSchedule,
+        "Cycle verification schedule (now / deprioritized / after downloads)",
+        "V",
+        &[Binding::with(KeyModifiers::SHIFT, Char('V'))],
+    ),
+    entry(Action::Options, "Open options", "o", &[Binding::key(Char('o'))]),
+    entry(
+        Action::SaveFilters,
+        "Save filters as defaults",
+        "Ctrl+S",
+        &[
+            Binding::with(KeyModifiers::CONTROL, Char('s')),
+            Binding::with(KeyModifiers::CONTROL, Char('S')),
+        ],
+    ),
+    entry(
+        Action::CycleSort,
+        "Cycle sort field",
+        "s",
+        &[Binding::key(Char('s'))],
+    ),
+    entry(
+        Action::ToggleSortDirection,
+        "Toggle sort direction",
+        "S",
+        &[Binding::with(KeyModifiers::SHIFT, Char('S'))],
+    ),
+    entry(
+        Action::CycleFilterField,
+        "Cycle focused filter",
+        "f",
+        &[Binding::key(Char('f'))],
+    ),
+    entry(
+        Action::IncreaseFilter,
+        "Increase focused filter",
+        "+",
+        &[Binding::key(Char('+'))],
+    ),
+    entry(
+        Action::DecreaseFilter,
+        "Decrease focused filter",
+        "-",
+        &[Binding::key(Char('-')), Binding::key(Char('_'))],
+    ),
+    entry(
+        Action::Refresh,
+        "Refresh search results",
+        "F5",
+        &[Binding::key(KeyCode::F(5))],
+    ),
+    entry(
+        Action::ResetFilters,
+        "Reset filters",
+        "r",
+        &[Binding::key(Char('r'))],
+    ),
+    entry(
+        Action::PresetNoFilters,
+        "Filter preset: No filters",
+        "1",
+        &[Binding::key(Char('1'))],
+    ),
+    entry(
+        Action::PresetPopular,
+        "Filter preset: Popular",
+        "2",
+        &[Binding::key(Char('2'))],
+    ),
+    entry(
+        Action::PresetHighlyRated,
+        "Filter preset: Highly rated",
+        "3",
+        &[Binding::key(Char('3'))],
+    ),
+    entry(
+        Action::PresetRecent,
+        "Filter preset: Recent",
+        "4",
+        &[Binding::key(Char('4'))],
+    ),
+    entry(
+        Action::ToggleFocus,
+        "Switch pane focus",
+        "Tab",
+        &[Binding::key(KeyCode::Tab)],
+    ),
+    hidden(Action::QuantPaneLeft, "←", &[Binding::key(KeyCode::Left)]),
+    hidden(Action::QuantPaneRight, "→", &[Binding::key(KeyCode::Right)]),
+    hidden(
+        Action::MoveDown,
+        "j / ↓",
+        &[Binding::key(KeyCode::Down), Binding::key(Char('j'))],
+    ),
+    hidden(
+        Action::MoveUp,
+        "k / ↑",
+        &[Binding::key(KeyCode::Up), Binding::key(Char('k'))],
+    ),
+    // `gg` is a key sequence handled in events.rs
+    hidden(Action::GoTop, "gg / Home", &[Binding::key(KeyCode::Home)]),
+    hidden(
+        Action::GoBottom,
+        "G / End",
+        &[
+            Binding::with(KeyModifiers::SHIFT, Char('G')),
+            Binding::key(KeyCode::End),
+        ],
+    ),
+    hidden(Action::Activate, "Enter", &[Binding::key(KeyCode::Enter)]),
+];
+
+/// Palette entries matching `query`, best matches first
+///
+/// Matching is fuzzy: the query characters must appear in order in the
+/// action name. Prefix matches rank above substring matches, which rank
+/// above scattered (subsequence) matches; ties keep table order.
+pub fn filter_actions(query: &str) -> Vec<&'static ActionEntry> {
+    let query = query.trim().to_lowercase();
+    let mut scored: Vec<(u8, usize, &'static ActionEntry)> = ACTIONS
+        .iter()
+        .enumerate()
+        .filter(|(_, e)| e.in_palette)
+        .filter_map(|(idx, e)| {
+            crate::utils::fuzzy_score(&query, &e.name.to_lowercase()).map(|score| (score, idx, e))
+        })
+        .collect();
+
+    scored.sort_by_key(|(score, idx, _)| (*score, *idx));
+    scored.into_iter().map(|(_, _, e)| e).collect()
+}
+
+impl App {
+    /// Resolve a Normal-mode key press through the action table
+    pub fn action_for_key(&self, key: &KeyEvent) -> Option<Action> {
+        ACTIONS
+            .iter()
+            .find(|e| e.bindings.iter().any(|b| b.matches(key)) && self.is_action_available(e.action))
+            .map(|e| e.action)
+    }
+
+    /// Whether an action applies in the current focus / selection
+    pub fn is_action_available(&self, action: Action) -> bool {
+        let in_quant_panes = self.focused_pane == FocusedPane::QuantizationGroups
+            || self.focused_pane == FocusedPane::QuantizationFiles;
+
+        match action {
+            // Allow download from Models pane (for non-GGUF), the file tree, QuantizationGroups, or QuantizationFiles
+            Action::Download => {
+                self.focused_pane == FocusedPane::Models
+                    || self.focused_pane == FocusedPane::FileTree
+                    || in_quant_panes
+            }
+            Action::DownloadPlan | Action::PromptFormat => {
+                self.focused_pane == FocusedPane::Models || in_quant_panes
+            }
+            Action::OpenBaseModel | Action::DownloadWithBase => {
+                self.adapter_base_model().is_some()
+            }
+            Action::AuthorProfile => self.list_state.selected().is_some(),
+            Action::HideModel | Action::HideAuthor => {
+                self.focused_pane == FocusedPane::Models && self.list_state.selected().is_some()
+            }
+            Action::CompareQuantization | Action::Verify => in_quant_panes,
+            Action::ToggleQuantMark => self.focused_pane == FocusedPane::QuantizationGroups,
+            Action::SkipVerification => {
+                self.verification_queue_size
+                    .load(std::sync::atomic::Ordering::Relaxed)
+                    > 0
+                    || !self.cached_verification_progress.is_empty()
+            }
+            // Filter adjustment only in Models pane to avoid conflicts
+            Action::IncreaseFilter | Action::DecreaseFilter => {
+                self.focused_pane == FocusedPane::Models
+            }
+            Action::QuantPaneLeft => self.focused_pane == FocusedPane::QuantizationFiles,
+            Action::QuantPaneRight => self.focused_pane == FocusedPane::QuantizationGroups,
+            _ => true,
+        }
+    }
+
+    /// Run an action (from a key binding or the command palette)
+    pub async fn perform_action(&mut self, action: Action) {
+        match action {
+            Action::Quit => self.quit(),
+            Action::Search => {
+                // Open search popup instead of inline editing
+                self.popup_mode = PopupMode::SearchPopup;
+                self.input.reset(); // Clear previous search
+                *self.status.write() = "Search Models".to_string();
+            }
+            Action::OpenModel => {
+                // Open a model or file by pasted URL / model ID
+                self.popup_mode = PopupMode::OpenModel;
+                self.open_input.reset();
+                *self.status.write() = "Open Model or URL".to_string();
+            }
+            Action::MyModels => self.show_my_models().await,
+            Action::AuthorProfile => self.show_author_profile().await,
+            Action::RecentGguf => self.show_recent_gguf().await,
+            Action::CommandPalette => {
+                self.popup_mode = PopupMode::CommandPalette;
+                self.palette_input.reset();
+                self.palette_selected = 0;
+            }
+            Action::Download => {
+                self.trigger_download();
+            }
+            Action::DownloadPlan => self.show_download_plan(),
+            Action::DownloadQueue => self.show_download_queue(),
+            Action::AdoptLocalModels => self.adopt_local_models(),
+            Action::OpenBaseModel => {
+                // Jump to the base model of a LoRA/PEFT adapter
+                if let Some(base) = self.adapter_base_model() {
+                    self.open_model_reference(&base);
+                }
+            }
+            Action::DownloadWithBase => {
+                // Download adapter and its base model together
+                self.download_with_base = true;
+                self.focused_pane = FocusedPane::Models;
+                self.trigger_download();
+            }
+            Action::CompareQuantization => {
+                self.compare_selected_quantization();
+            }
+            Action::ToggleQuantMark => self.toggle_quant_mark(),
+            Action::ToggleWatch => self.toggle_watch_selected(),
+            Action::WatchUpdates => self.show_watch_updates(),
+            Action::HideModel => self.hide_selected(false),
+            Action::HideAuthor => self.hide_selected(true),
+            Action::IgnoreList => self.show_ignore_list(),
+            Action::EditNotes => self.open_notes_popup(),
+            Action::PromptFormat => self.show_prompt_format().await,
+            Action::Stats => {
+                self.history_stats = Some(crate::history::compute_stats(
+                    &crate::history::load_history(),
+                    5,
+                ));
+                self.popup_mode = PopupMode::Stats;
+            }
+            Action::Verify => {
+                self.verify_downloaded_file().await;
+            }
+            Action::SkipVerification => self.skip_verification().await,
+            Action::CycleVerificationSchedule => self.cycle_verification_schedule(),
+            Action::Options => {
+                self.popup_mode = PopupMode::Options;
+            }
+            Action::SaveFilters => {
+                // Save current filter settings as defaults
+                self.save_filter_settings();
+            }
+            Action::CycleSort => {
+                // Cycle sort field: Downloads → Likes → Modified → Name → Downloads
+                self.sort_field = match self.sort_field {
+                    SortField::Downloads => SortField::Likes,
+                    SortField::Likes => SortField::Modified,
+                    SortField::Modified => SortField::Name,
+                    SortField::Name => SortField::Downloads,
+                };
+
+                // Re-fetch with new sort
+                self.clear_search_results();
+                self.needs_search_models = true;
+
+                *self.status.write() = format!("Sort by: {:?}", self.sort_field);
+            }
+            Action::ToggleSortDirection => {
+                self.sort_direction = match self.sort_direction {
+                    SortDirection::Ascending => SortDirection::Descending,
+                    SortDirection::Descending => SortDirection::Ascending,
+                };
+
+                // Re-fetch with new direction
+                self.clear_search_results();
+                self.needs_search_models = true;
+
+                let arrow = match self.sort_direction {
+                    SortDirection::Ascending => "▲",
+                    SortDirection::Descending => "▼",
+                };
+                *self.status.write() =
+                    format!("Sort direction: {:?} {}", self.sort_direction, arrow);
+            }
+            Action::CycleFilterField => {
+                self.focused_filter_field = (self.focused_filter_field + 1) % 6;
+                let field_name = match self.focused_filter_field {
+                    0 => "Sort",
+                    1 => "Min Downloads",
+                    2 => "Min Likes",
+                    3 => "Size",
+                    4 => "Access",
+                    5 => "Content",
+                    _ => unreachable!(),
+                };
+                *self.status.write() = format!("Focused filter: {}", field_name);
+            }
+            Action::IncreaseFilter => self.modify_focused_filter(1),
+            Action::DecreaseFilter => self.modify_focused_filter(-1),
+            Action::Refresh => {
+                // Re-run the current search, highlighting newly published models
+                self.refresh_search().await;
+            }
+            Action::ResetFilters => {
+                self.sort_field = SortField::default();
+                self.sort_direction = SortDirection::default();
+                self.filter_min_downloads = 0;
+                self.filter_min_likes = 0;
+                self.filter_params = ParamRange::default();
+                self.filter_access = AccessFilter::default();
+                // Hiding adult content is a setting rather than a narrowing
+                self.filter_content = self.options.default_content;
+                self.focused_filter_field = 0;
+
+                // Re-fetch with reset filters
+                self.clear_search_results();
+                self.needs_search_models = true;
+
+                *self.status.write() = "Filters reset to defaults".to_string();
+            }
+            Action::PresetNoFilters => self.select_filter_preset(FilterPreset::NoFilters),
+            Action::PresetPopular => self.select_filter_preset(FilterPreset::Popular),
+            Action::PresetHighlyRated => self.select_filter_preset(FilterPreset::HighlyRated),
+            Action::PresetRecent => self.select_filter_preset(FilterPreset::Recent),
+            Action::ToggleFocus => self.toggle_focus(),
+            Action::QuantPaneLeft | Action::QuantPaneRight => self.toggle_quant_subfocus(),
+            Action::MoveDown => match self.focused_pane {
+                FocusedPane::Models => {
+                    self.next();
+                    // Clear details immediately to show selection change
+                    self.clear_model_details();
+                    // Set flag to load on next iteration (allows UI to render first)
+                    self.needs_load_quantizations = true;
+                }
+                FocusedPane::QuantizationGroups => self.next_quant(),
+                FocusedPane::QuantizationFiles => self.next_file(),
+                FocusedPane::ModelMetadata => self.next_related(),
+                FocusedPane::FileTree => self.next_file_tree_item(),
+            },
+            Action::MoveUp => match self.focused_pane {
+                FocusedPane::Models => {
+                    self.previous();
+                    // Clear details immediately to show selection change
+                    self.clear_model_details();
+                    // Set flag to load on next iteration (allows UI to render first)
+                    self.needs_load_quantizations = true;
+                }
+                FocusedPane::QuantizationGroups => self.previous_quant(),
+                FocusedPane::QuantizationFiles => self.previous_file(),
+                FocusedPane::ModelMetadata => self.previous_related(),
+                FocusedPane::FileTree => self.previous_file_tree_item(),
+            },
+            Action::PageDown => self.move_selection_by(self.half_page() as isize),
+            Action::PageUp => self.move_selection_by(-(self.half_page() as isize)),
+            Action::GoTop => self.select_in_focused_pane(0),
+            Action::GoBottom => self.select_in_focused_pane(usize::MAX),
+            Action::Activate => match self.focused_pane {
+                FocusedPane::Models => {
+                    // Show model details first
+                    self.show_model_details().await;
+                    // Switch focus to the appropriate pane based on display mode
+                    // (toggle_focus already handles skipping ModelMetadata in Standard mode)
+                    self.toggle_focus();
+                }
+                FocusedPane::QuantizationGroups => self.show_quantization_details().await,
+                FocusedPane::QuantizationFiles => self.show_file_details().await,
+                FocusedPane::ModelMetadata => self.open_related_model(),
+                FocusedPane::FileTree => self.toggle_file_tree_expansion(),
+            },
+        }
+    }
+
+    /// Run an action with a Vim-style count prefix (`5j`, `3 Ctrl+D`, `20G`)
+    /// Counted moves stop at the ends of the list instead of wrapping; actions
+    /// that are not movements ignore the count and run once.
+    pub async fn perform_action_with_count(&mut self, action: Action, count: usize) {
+        let count = count.max(1);
+        match action {
+            Action::MoveDown => self.move_selection_by(count as isize),
+            Action::MoveUp => self.move_selection_by(-(count as isize)),
+            Action::PageDown => self.move_selection_by((self.half_page() * count) as isize),
+            Action::PageUp => self.move_selection_by(-((self.half_page() * count) as isize)),
+            // `[count]G` jumps to row `count` (1-based), like `[count]gg`
+            Action::GoTop | Action::GoBottom => self.select_in_focused_pane(count - 1),
+            _ => self.perform_action(action).await,
+        }
+    }
+
+    /// Apply a filter preset unless it is already active (number keys and
+    /// clicks on the filter bar's presets)
+    pub fn select_filter_preset(&mut self, preset: FilterPreset) {
+        if self.would_change_settings(preset) {
+            self.apply_filter_preset(preset);
+        } else {
+            *self.status.write() = format!("Already using {} preset", preset.label());
+        }
+    }
+}
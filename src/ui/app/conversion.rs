@@ -0,0 +1,158 @@
+use super::state::App;
+use crate::convert::{ConversionJob, ConversionState, ConvertTools};
+use crate::models::*;
+use std::path::PathBuf;
+
+/// How long downloads must stay idle before waiting conversions start, so
+/// a download that is just being picked up isn't mistaken for a failure
+const IDLE_BEFORE_CONVERSION: std::time::Duration = std::time::Duration::from_secs(2);
+
+impl App {
+    /// The configured llama.cpp tools, if the conversion script is set
+    fn convert_tools(&self) -> Option<ConvertTools> {
+        let convert_script = self
+            .options
+            .convert_script
+            .as_deref()
+            .filter(|path| !path.is_empty())?;
+        Some(ConvertTools {
+            python: self.options.convert_python.clone(),
+            convert_script: PathBuf::from(convert_script),
+            quantize_binary: self
+                .options
+                .quantize_binary
+                .as_deref()
+                .filter(|path| !path.is_empty())
+                .map(PathBuf::from),
+            quant_type: self.options.convert_quant_type.clone(),
+        })
+    }
+
+    /// Ctrl+G in the download popup: convert the repository to GGUF after
+    /// downloading it
+    pub fn toggle_gguf_conversion(&mut self) {
+        let configured = self.convert_tools().is_some();
+        let Some(summary) = self.download_summary.as_mut() else {
+            return;
+        };
+        let Some(convert) = summary.convert_to_gguf else {
+            return;
+        };
+        if !convert && !configured {
+            *self.status.write() =
+                "Set the path of convert_hf_to_gguf.py under GGUF Conversion in Options ('o')"
+                    .to_string();
+            return;
+        }
+        summary.convert_to_gguf = Some(!convert);
+    }
+
+    /// Track a conversion of `model_id` that starts once its downloads finish
+    /// (one job per model directory)
+    pub fn queue_conversion(&mut self, model_id: &str, model_root: PathBuf) {
+        let mut jobs = self.conversion_jobs.write();
+        if jobs
+            .iter()
+            .any(|job| job.model_root == model_root && !job.state.is_finished())
+        {
+            return;
+        }
+        jobs.retain(|job| job.model_root != model_root);
+        jobs.push(ConversionJob {
+            model_id: model_id.to_string(),
+            model_root,
+            state: ConversionState::Waiting,
+        });
+    }
+
+    /// Start waiting conversions once the download queue has been idle for a
+    /// moment; repositories with files that did not download fail instead
+    pub fn start_pending_conversions(&mut self) {
+        let idle = self.cached_download_queue.size == 0 && self.cached_download_progress.is_none();
+        if !idle {
+            self.downloads_idle_since = None;
+            return;
+        }
+        let idle_since = *self
+            .downloads_idle_since
+            .get_or_insert_with(std::time::Instant::now);
+        if idle_since.elapsed() < IDLE_BEFORE_CONVERSION {
+            return;
+        }
+
+        let Some(tools) = self.convert_tools() else {
+            return;
+        };
+        let registry = crate::registry::load_registry();
+        let mut jobs = self.conversion_jobs.write();
+        for job in jobs.iter_mut() {
+            if job.state != ConversionState::Waiting {
+                continue;
+            }
+            let missing = registry
+                .downloads
+                .iter()
+                .filter(|d| {
+                    d.model_id == job.model_id
+                        && std::path::Path::new(&d.local_path).starts_with(&job.model_root)
+                        && d.status != DownloadStatus::Complete
+                })
+                .count();
+            if missing > 0 {
+                let reason = format!("{} file(s) did not download", missing);
+                let _ = self.status_tx.send(format!(
+                    "Error: Not converting {} to GGUF: {}",
+                    job.model_id, reason
+                ));
+                job.state = ConversionState::Failed(reason);
+                continue;
+            }
+
+            job.state = ConversionState::Converting;
+            let _ = self
+                .status_tx
+                .send(format!("Converting {} to GGUF...", job.model_id));
+            let jobs_handle = self.conversion_jobs.clone();
+            let status_tx = self.status_tx.clone();
+            let tools = tools.clone();
+            let model_id = job.model_id.clone();
+            let model_root = job.model_root.clone();
+            tokio::spawn(async move {
+                let set_state = |state: ConversionState| {
+                    if let Some(job) = jobs_handle
+                        .write()
+                        .iter_mut()
+                        .find(|job| job.model_root == model_root)
+                    {
+                        job.state = state;
+                    }
+                };
+                let result =
+                    crate::convert::convert_to_gguf(&tools, &model_id, &model_root, |state| {
+                        if state == ConversionState::Quantizing {
+                            let _ = status_tx.send(format!(
+                                "Quantizing {} to {}...",
+                                model_id, tools.quant_type
+                            ));
+                        }
+                        set_state(state);
+                    })
+                    .await;
+                match result {
+                    Ok(path) => {
+                        let _ =
+                            status_tx.send(format!("Converted {} to {}", model_id, path.display()));
+                        set_state(ConversionState::Done(path));
+                    }
+                    Err(e) => {
+                        let _ = status_tx.send(format!(
+                            "Error: Converting {} to GGUF failed: {}",
+                            model_id, e
+                        ));
+                        set_state(ConversionState::Failed(e));
+                    }
+                }
+            });
+        }
+    }
+}
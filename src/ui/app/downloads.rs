@@ -1,17 +1,33 @@
-use super::state::App;
-use crate::api::fetch_multipart_sha256s;
-use crate::download::validate_and_sanitize_path;
+use super::state::{App, DownloadMessage, DownloadPriorities};
+use crate::api::{fetch_model_metadata, fetch_multipart_sha256s};
+use crate::download::{file_url, quant_file_subdir, url_revision, validate_and_sanitize_path};
 use crate::models::*;
 use crate::registry;
 use std::collections::HashMap;
 use std::path::PathBuf;
 use tui_input::Input;
 
+/// Registry URL of a queued download
+fn queued_url(queued: &QueuedDownload) -> String {
+    file_url(
+        &queued.model_id,
+        queued.revision.as_deref(),
+        &queued.filename,
+    )
+}
+
 impl App {
     /// Scan registry for incomplete downloads and show resume popup if found
+    /// (or resume them right away with the `auto_resume` option)
     pub async fn scan_incomplete_downloads(&mut self) {
-        // Load registry from disk
-        let registry = registry::load_registry();
+        // Load registry from disk; complete files moved or resized since are
+        // flagged so they are offered again instead of shown as downloaded
+        let mut registry = registry::load_registry();
+        let changed = registry::check_complete_files(&mut registry);
+
+        // Downloads the last session queued but never started; they stay on
+        // disk until `save_pending_queue` writes the queue again
+        let queue = std::mem::take(&mut registry.queue);
 
         // Update the app's registry
         {
@@ -19,8 +35,26 @@ impl App {
             *reg = registry.clone();
         }
 
+        // Queued files were registered as incomplete when queued: list them
+        // once, as queued, unless something else has finished them since
+        let queued_urls: Vec<String> = queue.iter().map(queued_url).collect();
+        self.queued_downloads = queue
+            .into_iter()
+            .zip(&queued_urls)
+            .filter(|(_, url)| {
+                !registry
+                    .downloads
+                    .iter()
+                    .any(|d| &d.url == *url && d.status == DownloadStatus::Complete)
+            })
+            .map(|(queued, _)| queued)
+            .collect();
+
         // Find incomplete downloads
-        self.incomplete_downloads = registry::get_incomplete_downloads(&registry);
+        self.incomplete_downloads = registry::get_incomplete_downloads(&registry)
+            .into_iter()
+            .filter(|d| !queued_urls.contains(&d.url))
+            .collect();
 
         // Load complete downloads into memory
         let complete_map = registry::get_complete_downloads(&registry);
@@ -30,36 +64,281 @@ impl App {
             *complete = complete_map;
         }
 
-        // Show popup if incomplete downloads found
-        if !self.incomplete_downloads.is_empty() {
-            self.popup_mode = PopupMode::ResumeDownload;
-            *self.status.write() = format!(
+        if self.incomplete_downloads.is_empty() && self.queued_downloads.is_empty() {
+            return;
+        }
+
+        // The other instance (or the daemon, which resumes them itself) owns these
+        if crate::instance::is_read_only() || crate::instance::is_daemon_client() {
+            return;
+        }
+
+        // Re-queue without asking when the auto_resume option is set (files
+        // changed on disk still ask); the status line is reset after the
+        // scan, so report it in the log too
+        if self.options.auto_resume && !self.downloads_locked() && changed == 0 {
+            self.resume_incomplete_downloads().await;
+            let _ = self.status_tx.send(format!("Auto-resume: {}", self.status.read()));
+            return;
+        }
+
+        self.popup_mode = PopupMode::ResumeDownload;
+        *self.status.write() = if self.queued_downloads.is_empty() {
+            format!(
                 "Found {} incomplete download(s)",
                 self.incomplete_downloads.len()
+            )
+        } else {
+            format!(
+                "Found {} incomplete and {} queued download(s)",
+                self.incomplete_downloads.len(),
+                self.queued_downloads.len()
+            )
+        };
+        if changed > 0 {
+            self.status.write().push_str(&format!(
+                ", {} of them changed on disk since they completed",
+                changed
+            ));
+        }
+    }
+
+    /// Scan the download directory in the background for models other tools
+    /// put there and add the files that match their repository to the
+    /// registry, so they are marked as downloaded
+    pub fn adopt_local_models(&mut self) {
+        if self.downloads_disabled() {
+            return;
+        }
+
+        let root = PathBuf::from(&self.options.default_directory);
+        *self.status.write() = format!("Looking for models to adopt in {}...", root.display());
+        let download_registry = self.download_registry.clone();
+        let complete_downloads = self.complete_downloads.clone();
+        let status_tx = self.status_tx.clone();
+        let token = self.options.hf_token.clone();
+        tokio::spawn(async move {
+            let mut folders = Vec::new();
+            for folder in crate::adopt::find_folders(&root, None) {
+                folders.push(crate::adopt::resolve(&folder, false, token.as_ref()).await);
+            }
+
+            let mut registry = registry::load_registry();
+            let added = crate::adopt::register(&mut registry, &mut folders);
+            if added > 0 {
+                registry::save_registry(&registry);
+                *complete_downloads.lock().await = registry::get_complete_downloads(&registry);
+                *download_registry.lock().await = registry;
+            }
+            let unknown = folders.iter().filter(|f| f.model_id.is_none()).count();
+            let mut message = format!(
+                "Adopted {} file(s) from {} model folder(s)",
+                added,
+                folders.len() - unknown
             );
+            if unknown > 0 {
+                message.push_str(&format!(
+                    ", {} folder(s) matched no repository (try `adopt --model`)",
+                    unknown
+                ));
+            }
+            let _ = status_tx.send(message);
+        });
+    }
+
+    /// In read-only mode (another instance owns the registry) downloads could
+    /// not be recorded, so they are refused with a status message
+    fn downloads_disabled(&self) -> bool {
+        if !crate::instance::is_read_only() {
+            return false;
+        }
+        *self.status.write() =
+            "Read-only mode: downloads are disabled while another instance is running".to_string();
+        true
+    }
+
+    /// Download lock (`no_download` option or `--no-download`): the download
+    /// popup needs Ctrl+Y instead of Enter and nothing is resumed unasked
+    pub fn downloads_locked(&self) -> bool {
+        self.options.no_download || crate::download::downloads_locked()
+    }
+
+    /// Persist the downloads still waiting in the queue, plus queued ones the
+    /// resume popup was skipped for, so the next session offers them again
+    pub async fn save_pending_queue(&mut self) {
+        let mut queue = std::mem::take(&mut self.queued_downloads);
+        if let Ok(held) = self.held_download.lock() {
+            queue.extend(held.iter().map(|message| self.queued_download(message)));
+        }
+
+        // The download manager holds the receiver only while waiting on an
+        // empty channel, so a failed lock means nothing is pending
+        if let Ok(mut rx) = self.download_rx.try_lock() {
+            while let Ok(message) = rx.try_recv() {
+                queue.push(self.queued_download(&message));
+            }
+        }
+
+        // Reload: running downloads have updated the registry on disk
+        let mut registry = registry::load_registry();
+        if registry.queue != queue {
+            registry.queue = queue;
+            registry::save_registry(&registry);
+        }
+    }
+
+    /// A pending download as listed in the queue popup and saved on quit
+    fn queued_download(&self, message: &DownloadMessage) -> QueuedDownload {
+        let (model_id, filename, base_path, expected_hash, _, total_size, revision) = message;
+        let (expected_sha256, expected_git_blob) = ExpectedHash::fields(expected_hash.as_ref());
+        QueuedDownload {
+            model_id: model_id.clone(),
+            filename: filename.clone(),
+            base_path: base_path.to_string_lossy().to_string(),
+            expected_sha256,
+            expected_git_blob,
+            total_size: *total_size,
+            revision: revision.clone(),
+            priority: self.pending_priority(model_id, filename),
+        }
+    }
+
+    fn pending_priority(&self, model_id: &str, filename: &str) -> DownloadPriority {
+        priority_of(&self.download_priorities, model_id, filename)
+    }
+
+    /// Record the priority of a download just sent to the queue
+    fn set_pending_priority(&mut self, model_id: &str, filename: &str, priority: DownloadPriority) {
+        set_priority(&mut self.download_priorities, model_id, filename, priority);
+    }
+
+    /// Edit the downloads waiting in the channel: they are drained in queue
+    /// order and sent again afterwards. The download manager holds the
+    /// receiver only while waiting on an empty channel, so `None` (locked)
+    /// means nothing is pending.
+    fn edit_pending_downloads<T>(
+        &mut self,
+        edit: impl FnOnce(&mut Self, &mut Vec<DownloadMessage>) -> T,
+    ) -> Option<T> {
+        let download_rx = self.download_rx.clone();
+        let mut rx = download_rx.try_lock().ok()?;
+        let mut pending = Vec::new();
+        while let Ok(message) = rx.try_recv() {
+            pending.push(message);
+        }
+
+        let result = edit(self, &mut pending);
+        for message in pending {
+            let _ = self.download_tx.send(message);
+        }
+        Some(result)
+    }
+
+    /// Order the pending downloads by priority (keeping their order within
+    /// each priority) and refresh the queue popup's list
+    pub fn sort_download_queue(&mut self) {
+        let view = self.edit_pending_downloads(|app, pending| {
+            app.download_priorities
+                .retain(|(model_id, filename), _| {
+                    pending.iter().any(|m| &m.0 == model_id && &m.1 == filename)
+                });
+            sort_pending(pending, &app.download_priorities);
+            pending
+                .iter()
+                .map(|m| app.queued_download(m))
+                .collect::<Vec<_>>()
+        });
+
+        // Retried from the run loop while the receiver is busy and something is queued
+        if view.is_some() || self.cached_download_queue.size == 0 {
+            self.needs_sort_download_queue = false;
+        }
+        self.download_queue_view = view.unwrap_or_default();
+        self.download_queue_selected = self
+            .download_queue_selected
+            .min(self.download_queue_view.len().saturating_sub(1));
+    }
+
+    /// Open the queue popup ('Q') listing the downloads not yet started
+    pub fn show_download_queue(&mut self) {
+        self.download_queue_selected = 0;
+        self.sort_download_queue();
+        self.popup_mode = PopupMode::DownloadQueue;
+    }
+
+    /// Move the selected pending download up (negative) or down the queue;
+    /// moving past a download of another priority takes on that priority
+    pub fn move_queued_download(&mut self, delta: isize) {
+        let Some(selected) = self.download_queue_view.get(self.download_queue_selected) else {
+            return;
+        };
+        let key = (selected.model_id.clone(), selected.filename.clone());
+
+        let moved = self
+            .edit_pending_downloads(|app, pending| {
+                let from = pending.iter().position(|m| m.0 == key.0 && m.1 == key.1)?;
+                move_pending(pending, &mut app.download_priorities, from, delta)
+            })
+            .flatten();
+
+        self.sort_download_queue();
+        if let Some(to) = moved {
+            self.download_queue_selected = to.min(self.download_queue_view.len().saturating_sub(1));
+        }
+    }
+
+    /// Raise or lower the priority of the selected pending download, which
+    /// moves it in the queue but does not interrupt the running download
+    pub fn change_queued_priority(&mut self, raise: bool) {
+        let Some(selected) = self.download_queue_view.get(self.download_queue_selected) else {
+            return;
+        };
+        let (model_id, filename) = (selected.model_id.clone(), selected.filename.clone());
+        let current = self.pending_priority(&model_id, &filename);
+        let priority = if raise { current.raise() } else { current.lower() };
+        self.set_pending_priority(&model_id, &filename, priority);
+
+        self.sort_download_queue();
+        if let Some(idx) = self
+            .download_queue_view
+            .iter()
+            .position(|q| q.model_id == model_id && q.filename == filename)
+        {
+            self.download_queue_selected = idx;
+        }
+        *self.status.write() = format!("{} queue priority: {}", filename, priority.label());
+    }
+
+    /// Ctrl+P in the download popup: cycle the priority of the new downloads
+    pub fn cycle_download_priority(&mut self) {
+        if let Some(summary) = self.download_summary.as_mut() {
+            summary.priority = summary.priority.cycle();
         }
     }
 
     /// Initiate download flow - show download path popup
     pub fn trigger_download(&mut self) {
+        if self.downloads_disabled() {
+            return;
+        }
+
         // Check which pane is focused to determine what to download
         match self.focused_pane {
-            FocusedPane::Models => {
-                // Download entire model repository (non-GGUF models in Standard mode)
-                if *self.display_mode.read() == crate::models::ModelDisplayMode::Standard {
-                    let metadata = futures::executor::block_on(async {
-                        self.model_metadata.read().clone()
-                    });
-
-                    if let Some(meta) = metadata {
-                        let file_count = meta.siblings.len();
-                        self.download_path_input =
-                            Input::default().with_value(self.options.default_directory.clone());
-                        self.popup_mode = PopupMode::DownloadPath;
-                        *self.status.write() =
-                            format!("Download all {} files from repository", file_count);
-                    }
-                }
+            // Download entire model repository (non-GGUF models in Standard mode)
+            FocusedPane::Models
+                if *self.display_mode.read() == crate::models::ModelDisplayMode::Standard =>
+            {
+                self.open_repository_download(RepoPreset::All);
+            }
+            // Download the repository, or only the runtime export (onnx/,
+            // coreml/, ...) the selected entry is in
+            FocusedPane::FileTree
+                if *self.display_mode.read() == crate::models::ModelDisplayMode::Standard =>
+            {
+                let preset = self
+                    .selected_tree_runtime()
+                    .map_or(RepoPreset::All, RepoPreset::Runtime);
+                self.open_repository_download(preset);
             }
             FocusedPane::QuantizationGroups => {
                 // Download entire quantization group
@@ -67,38 +346,360 @@ impl App {
                     self.quantizations.read().clone()
                 });
 
-                if let Some(selected) = self.quant_list_state.selected() {
-                    if selected < quantizations.len() {
-                        // Update download path input with current default directory
-                        self.download_path_input =
-                            Input::default().with_value(self.options.default_directory.clone());
-                        self.popup_mode = PopupMode::DownloadPath;
-                        *self.status.write() = format!(
-                            "Download all {} files in quantization group",
-                            quantizations[selected].files.len()
-                        );
-                    }
+                // Groups marked with Space take precedence over the highlighted one
+                let marked = self.marked_quant_groups();
+                let groups: Vec<&QuantizationGroup> = if !marked.is_empty() {
+                    marked.iter().collect()
+                } else {
+                    self.quant_list_state
+                        .selected()
+                        .and_then(|selected| quantizations.get(selected))
+                        .into_iter()
+                        .collect()
+                };
+
+                if !groups.is_empty() {
+                    let files: usize = groups.iter().map(|g| g.files.len()).sum();
+                    self.download_summary = Some(DownloadSummary {
+                        label: groups
+                            .iter()
+                            .map(|g| g.quant_type.as_str())
+                            .collect::<Vec<_>>()
+                            .join(" + "),
+                        files,
+                        bytes: groups.iter().map(|g| g.total_size).sum(),
+                        ..Default::default()
+                    });
+                    // Update download path input with current default directory
+                    self.download_path_input =
+                        Input::default().with_value(self.options.default_directory.clone());
+                    self.popup_mode = PopupMode::DownloadPath;
+                    *self.status.write() = if groups.len() > 1 {
+                        format!(
+                            "Download {} marked quantizations ({} files)",
+                            groups.len(),
+                            files
+                        )
+                    } else {
+                        format!("Download all {} files in quantization group", files)
+                    };
                 }
             }
             FocusedPane::QuantizationFiles => {
-                // Download specific file only
-                if let Some(_group_idx) = self.quant_list_state.selected() {
-                    if let Some(_file_idx) = self.quant_file_list_state.selected() {
+                // Download the selected file, or every part of its split set
+                if let Some(group_idx) = self.quant_list_state.selected() {
+                    if let Some(file_idx) = self.quant_file_list_state.selected() {
+                        let part = self
+                            .quantizations
+                            .read()
+                            .get(group_idx)
+                            .and_then(|g| g.files.get(file_idx))
+                            .and_then(|file| crate::api::parse_multipart_filename(&file.filename));
+                        // A lone part is unusable, so default to the whole set
+                        self.download_all_parts = part.is_some();
+                        self.download_summary = self.selected_file_summary();
                         self.download_path_input =
                             Input::default().with_value(self.options.default_directory.clone());
                         self.popup_mode = PopupMode::DownloadPath;
-                        *self.status.write() = "Download single selected file".to_string();
+                        *self.status.write() = match part {
+                            Some((part, total)) => format!(
+                                "This is part {} of {} - downloading all parts (Tab: only this part)",
+                                part, total
+                            ),
+                            None => "Download single selected file".to_string(),
+                        };
                     }
                 }
             }
             _ => {}
         }
+
+        if self.popup_mode == PopupMode::DownloadPath {
+            let license = self.selected_license_notice();
+            if let Some(summary) = self.download_summary.as_mut() {
+                summary.license = license;
+                summary.speed_mbps = crate::history::recent_speed();
+            }
+            // Sets the path and refreshes the free space shown for it
+            self.place_download();
+        }
+    }
+
+    /// Open the download popup for the selected repository with `preset`
+    fn open_repository_download(&mut self, preset: RepoPreset) {
+        let metadata = futures::executor::block_on(async { self.model_metadata.read().clone() });
+        let Some(meta) = metadata else {
+            return;
+        };
+
+        let file_count = meta.siblings.len();
+        self.download_preset = preset;
+        let summary = repository_summary(&meta, preset);
+        self.download_path_input =
+            Input::default().with_value(self.options.default_directory.clone());
+        self.popup_mode = PopupMode::DownloadPath;
+        *self.status.write() = match (preset, meta.adapter_base_model()) {
+            (RepoPreset::Runtime(runtime), _) => format!(
+                "Download only the {} export ({} of {} files)",
+                runtime.label(),
+                summary.files,
+                file_count
+            ),
+            (_, Some(base)) if self.download_with_base => format!(
+                "Download all {} adapter files plus base model {}",
+                file_count, base
+            ),
+            _ => format!("Download all {} files from repository", file_count),
+        };
+        let others: Vec<String> = crate::api::repo_presets(&meta)
+            .into_iter()
+            .filter(|other| *other != preset)
+            .map(|other| other.label())
+            .collect();
+        if !others.is_empty() {
+            self.status
+                .write()
+                .push_str(&format!(" (Tab: {})", others.join(", ")));
+        }
+        self.download_summary = Some(summary);
+    }
+
+    /// Runtime of the export directory the selected file tree entry is in
+    fn selected_tree_runtime(&self) -> Option<Runtime> {
+        let tree = self.file_tree.read();
+        let flat = crate::ui::render::flatten_tree_for_navigation(tree.as_ref()?);
+        let node = flat.get(self.file_tree_state.selected()?)?;
+        crate::api::runtime_export(&node.path)
+    }
+
+    /// Tab in the download popup: switch between one part and the whole split
+    /// set, or cycle a repository download through its presets
+    pub fn toggle_download_scope(&mut self) {
+        let Some(summary) = self.download_summary.as_ref() else {
+            return;
+        };
+        let free_space = summary.free_space;
+        let quota = summary.quota.clone();
+        let placement = summary.placement.clone();
+        let speed_mbps = summary.speed_mbps;
+        let license = summary.license.clone();
+        let priority = summary.priority;
+        let convert_to_gguf = summary.convert_to_gguf;
+
+        let toggled = if summary.part_of.is_some() {
+            self.download_all_parts = !self.download_all_parts;
+            self.selected_file_summary()
+        } else if summary.preset.is_some() {
+            self.download_preset = summary.next_preset;
+            self.model_metadata
+                .read()
+                .as_ref()
+                .map(|meta| repository_summary(meta, self.download_preset))
+        } else {
+            return;
+        };
+        self.download_summary = toggled.map(|summary| DownloadSummary {
+            free_space,
+            quota,
+            placement,
+            speed_mbps,
+            license,
+            priority,
+            convert_to_gguf: convert_to_gguf.or(summary.convert_to_gguf),
+            ..summary
+        });
+        // The size changed, so the placement rules may pick another root
+        self.place_download();
+    }
+
+    /// License of the selected model: from the card when its metadata is
+    /// loaded (Standard mode), otherwise from the search result's tags
+    fn selected_license_notice(&self) -> Option<LicenseNotice> {
+        if let Some(meta) = self.model_metadata.read().as_ref() {
+            return Some(meta.license_notice());
+        }
+        let selected = self.list_state.selected()?;
+        self.models
+            .read()
+            .get(selected)
+            .map(ModelInfo::license_notice)
+    }
+
+    /// Summary for the file highlighted in the Files pane, covering its whole
+    /// split set when `download_all_parts` is on
+    fn selected_file_summary(&self) -> Option<DownloadSummary> {
+        let group_idx = self.quant_list_state.selected()?;
+        let file_idx = self.quant_file_list_state.selected()?;
+        let quantizations = self.quantizations.read();
+        let group = quantizations.get(group_idx)?;
+        let file = group.files.get(file_idx)?;
+        let part_of = crate::api::parse_multipart_filename(&file.filename);
+
+        Some(match part_of {
+            Some(_) if self.download_all_parts => {
+                let parts = part_set_files(group, file);
+                DownloadSummary {
+                    label: crate::api::get_multipart_base_name(&file.filename),
+                    files: parts.len(),
+                    bytes: parts.iter().map(|f| f.size).sum(),
+                    part_of,
+                    ..Default::default()
+                }
+            }
+            _ => DownloadSummary {
+                label: file.filename.clone(),
+                files: 1,
+                bytes: file.size,
+                part_of,
+                ..Default::default()
+            },
+        })
+    }
+
+    /// Re-read the free space at the path typed into the download popup;
+    /// its quota usage follows on the next loop iteration
+    pub fn refresh_download_free_space(&mut self) {
+        let path = PathBuf::from(self.download_path_input.value());
+        if let Some(summary) = self.download_summary.as_mut() {
+            summary.free_space = crate::utils::available_space(&path);
+            self.needs_quota_refresh = true;
+        }
+    }
+
+    /// Measure the quota directory of the path in the download popup
+    pub async fn refresh_download_quota(&mut self) {
+        let path = PathBuf::from(self.download_path_input.value());
+        let quota = crate::quota::usage(&path).await;
+        if let Some(summary) = self.download_summary.as_mut() {
+            summary.quota = quota;
+        }
+    }
+
+    /// Quantization files to download for the current focus: the selected file,
+    /// every marked group, or the highlighted group
+    fn quant_files_for_download(&self, group: &QuantizationGroup) -> Vec<QuantizationInfo> {
+        match self.focused_pane {
+            FocusedPane::QuantizationFiles => {
+                // Download the selected file, or all parts of its split set
+                match self
+                    .quant_file_list_state
+                    .selected()
+                    .and_then(|file_idx| group.files.get(file_idx))
+                {
+                    Some(file) if self.download_all_parts => part_set_files(group, file),
+                    Some(file) => vec![file.clone()],
+                    None => vec![],
+                }
+            }
+            _ if !self.marked_quants.is_empty() => {
+                // Download every marked group in one go
+                self.marked_quant_groups()
+                    .into_iter()
+                    .flat_map(|g| g.files)
+                    .collect()
+            }
+            _ => {
+                // Download all files in the group (default behavior)
+                group.files.clone()
+            }
+        }
+    }
+
+    /// Dry run ('D'): list the files the current selection would download,
+    /// with their target paths under the default directory, without queueing
+    pub fn show_download_plan(&mut self) {
+        if self.downloads_disabled() {
+            return;
+        }
+
+        let base_path = self.options.default_directory.clone();
+        let Some(model_id) = self
+            .list_state
+            .selected()
+            .and_then(|idx| self.models.read().get(idx).map(|m| m.id.clone()))
+        else {
+            *self.error.write() = Some("No model selected".to_string());
+            return;
+        };
+
+        // (remote filename, local filename relative to the model directory, size)
+        let files: Vec<(String, String, u64)> = match self.focused_pane {
+            FocusedPane::Models
+                if *self.display_mode.read() == crate::models::ModelDisplayMode::Standard =>
+            {
+                let metadata = self.model_metadata.read().clone();
+                metadata
+                    .map(|meta| {
+                        meta.siblings
+                            .iter()
+                            .filter(|f| f.size.is_some() && !f.rfilename.ends_with('/'))
+                            .map(|f| {
+                                (
+                                    f.rfilename.clone(),
+                                    f.rfilename.clone(),
+                                    f.size.unwrap_or(0),
+                                )
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default()
+            }
+            FocusedPane::QuantizationGroups | FocusedPane::QuantizationFiles => {
+                // Plan what 'd' queues by default: whole split sets
+                self.download_all_parts = true;
+                let group = self
+                    .quant_list_state
+                    .selected()
+                    .and_then(|idx| self.quantizations.read().get(idx).cloned());
+                group
+                    .map(|group| {
+                        self.quant_files_for_download(&group)
+                            .into_iter()
+                            .map(|f| {
+                                let local =
+                                    match quant_file_subdir(&f, self.options.quant_subdirectory) {
+                                        Some(subdir) => format!("{}/{}", subdir, f.filename),
+                                        None => f.filename.clone(),
+                                    };
+                                (f.filename, local, f.size)
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default()
+            }
+            _ => Vec::new(),
+        };
+
+        if files.is_empty() {
+            *self.status.write() =
+                "Nothing to plan - select a quantization, file or non-GGUF model".to_string();
+            return;
+        }
+
+        let mut plan = Vec::with_capacity(files.len());
+        for (filename, local_filename, size) in files {
+            match validate_and_sanitize_path(&base_path, &model_id, &local_filename) {
+                Ok(local_path) => plan.push(PlannedDownload {
+                    exists: local_path.exists(),
+                    local_path,
+                    size,
+                }),
+                Err(e) => {
+                    *self.error.write() = Some(format!("Invalid filename '{}': {}", filename, e));
+                    return;
+                }
+            }
+        }
+
+        self.download_plan = plan;
+        self.download_plan_scroll = 0;
+        self.popup_mode = PopupMode::DownloadPlan;
     }
 
     /// Complete download with validation - create metadata and queue download
     pub async fn confirm_download(&mut self) {
         // Check if we're downloading a full repository (non-GGUF model)
-        if self.focused_pane == FocusedPane::Models
+        if matches!(self.focused_pane, FocusedPane::Models | FocusedPane::FileTree)
             && *self.display_mode.read() == crate::models::ModelDisplayMode::Standard
         {
             self.confirm_repository_download().await;
@@ -116,24 +717,11 @@ impl App {
                 let model = &models[model_idx];
                 let group = &quant_groups[quant_idx];
 
-                // Determine which files to download based on focus
-                let files_to_download: Vec<QuantizationInfo> = match self.focused_pane {
-                    FocusedPane::QuantizationFiles => {
-                        // Download only the selected file
-                        if let Some(file_idx) = self.quant_file_list_state.selected() {
-                            if file_idx < group.files.len() {
-                                vec![group.files[file_idx].clone()]
-                            } else {
-                                vec![]
-                            }
-                        } else {
-                            vec![]
-                        }
-                    }
-                    _ => {
-                        // Download all files in the group (default behavior)
-                        group.files.clone()
-                    }
+                let files_to_download = self.quant_files_for_download(group);
+                let marked_count = if self.focused_pane == FocusedPane::QuantizationGroups {
+                    self.marked_quants.len()
+                } else {
+                    0
                 };
 
                 if files_to_download.is_empty() {
@@ -185,7 +773,7 @@ impl App {
                         }
                     }
                 } else {
-                    HashMap::new() // Single file uses quant.sha256 directly
+                    HashMap::new() // Single file uses quant.expected_hash directly
                 };
 
                 // Load registry and add metadata entries for all files
@@ -194,10 +782,22 @@ impl App {
                     reg.clone()
                 };
 
+                // Root-level quant files optionally go into a per-quant subfolder,
+                // files from other branches into a folder named after the branch
+                let subdirs: Vec<Option<String>> = files_to_download
+                    .iter()
+                    .map(|f| quant_file_subdir(f, self.options.quant_subdirectory))
+                    .collect();
+
                 for (idx, filename) in filenames_to_download.iter().enumerate() {
+                    let local_filename = match &subdirs[idx] {
+                        Some(subdir) => format!("{}/{}", subdir, filename),
+                        None => filename.clone(),
+                    };
+
                     // Validate each filename before processing
                     let validated_path =
-                        match validate_and_sanitize_path(&base_path, &model.id, filename) {
+                        match validate_and_sanitize_path(&base_path, &model.id, &local_filename) {
                             Ok(path) => path,
                             Err(e) => {
                                 *self.error.write() =
@@ -206,23 +806,34 @@ impl App {
                             }
                         };
 
-                    let url = format!(
-                        "https://huggingface.co/{}/resolve/main/{}",
-                        model.id, filename
+                    let url = file_url(
+                        &model.id,
+                        files_to_download[idx].revision.as_deref(),
+                        filename,
                     );
                     let local_path_str = validated_path.to_string_lossy().to_string();
 
+                    // Split GGUFs are checked for missing parts once the queue drains
+                    if crate::api::parse_multipart_filename(filename).is_some() {
+                        self.pending_part_paths.push(validated_path.clone());
+                    }
+
                     // Only add if not already in registry
                     if !registry.downloads.iter().any(|d| d.url == url) {
-                        // Get SHA256 from the corresponding QuantizationInfo
-                        let expected_sha256 = if idx < files_to_download.len() {
-                            files_to_download[idx].sha256.clone()
+                        // Get the hash from the corresponding QuantizationInfo
+                        let expected_hash = if idx < files_to_download.len() {
+                            files_to_download[idx].expected_hash.clone()
                         } else if num_files == 1 {
-                            files_to_download[0].sha256.clone()
+                            files_to_download[0].expected_hash.clone()
                         } else {
                             // Look up hash for this specific part from fetched map
-                            sha256_map.get(filename).and_then(|h| h.clone())
+                            sha256_map
+                                .get(filename)
+                                .and_then(|h| h.clone())
+                                .map(ExpectedHash::Sha256)
                         };
+                        let (expected_sha256, expected_git_blob) =
+                            ExpectedHash::fields(expected_hash.as_ref());
 
                         registry.downloads.push(DownloadMetadata {
                             model_id: model.id.clone(),
@@ -233,6 +844,8 @@ impl App {
                             downloaded_size: 0,
                             status: DownloadStatus::Incomplete,
                             expected_sha256,
+                            expected_git_blob,
+                            commit: None,
                         });
                     }
                 }
@@ -256,13 +869,17 @@ impl App {
                 // Send all download requests
                 let mut success_count = 0;
                 let hf_token = self.options.hf_token.clone();
+                let priority = self.download_summary.as_ref().map(|s| s.priority).unwrap_or_default();
                 for (idx, filename) in filenames_to_download.iter().enumerate() {
-                    // Get SHA256 from the corresponding QuantizationInfo
-                    let sha256 = if idx < files_to_download.len() {
-                        files_to_download[idx].sha256.clone()
+                    // Get the hash from the corresponding QuantizationInfo
+                    let expected_hash = if idx < files_to_download.len() {
+                        files_to_download[idx].expected_hash.clone()
                     } else {
                         // Fallback: look up hash from fetched map
-                        sha256_map.get(filename).and_then(|h| h.clone())
+                        sha256_map
+                            .get(filename)
+                            .and_then(|h| h.clone())
+                            .map(ExpectedHash::Sha256)
                     };
 
                     // Get file size from the corresponding QuantizationInfo
@@ -272,24 +889,40 @@ impl App {
                         0 // Fallback for safety
                     };
 
+                    let file_base = match &subdirs[idx] {
+                        Some(subdir) => model_path.join(subdir),
+                        None => model_path.clone(),
+                    };
+
                     if self
                         .download_tx
                         .send((
                             model.id.clone(),
                             filename.clone(),
-                            model_path.clone(),
-                            sha256,
+                            file_base,
+                            expected_hash,
                             hf_token.clone(),
                             file_size,
+                            files_to_download[idx].revision.clone(),
                         ))
                         .is_ok()
                     {
+                        self.set_pending_priority(&model.id, filename, priority);
                         success_count += 1;
                     }
                 }
+                self.needs_sort_download_queue = true;
 
                 if success_count > 0 {
-                    if num_files > 1 {
+                    if marked_count > 1 {
+                        *self.status.write() = format!(
+                            "Queued {} quantizations ({} files) to {}",
+                            marked_count,
+                            num_files,
+                            model_path.display()
+                        );
+                        self.marked_quants.clear();
+                    } else if num_files > 1 {
                         *self.status.write() = format!(
                             "Queued {} parts of {} to {}",
                             num_files,
@@ -325,53 +958,100 @@ impl App {
 
     /// Resume all incomplete downloads from registry
     pub async fn resume_incomplete_downloads(&mut self) {
+        if self.downloads_disabled() {
+            return;
+        }
+
         let count = self.incomplete_downloads.len();
         let hf_token = self.options.hf_token.clone();
         let default_dir = self.options.default_directory.clone();
         let mut total_bytes: u64 = 0;
 
         for metadata in &self.incomplete_downloads {
-            // Calculate model_path as base/author/model_name (without file's subdirectory)
-            // The filename may contain subdirectories (e.g., "Q4_1/model.gguf")
-            // which will be appended during download
-            let model_parts: Vec<&str> = metadata.model_id.split('/').collect();
-            let base_path = if model_parts.len() == 2 {
-                PathBuf::from(&default_dir)
-                    .join(model_parts[0])
-                    .join(model_parts[1])
-            } else {
-                // Fallback to deriving from local_path if model_id format is unexpected
-                PathBuf::from(&metadata.local_path)
-                    .parent()
+            // Derive the download base by stripping the filename (which may contain
+            // subdirectories, e.g. "Q4_1/model.gguf") from the recorded local path, so
+            // a custom directory or per-quant subfolder chosen at queue time is kept
+            let local_path = PathBuf::from(&metadata.local_path);
+            let depth = std::path::Path::new(&metadata.filename)
+                .components()
+                .count();
+            let base_path = if local_path.ends_with(&metadata.filename) {
+                local_path
+                    .ancestors()
+                    .nth(depth)
                     .map(|p| p.to_path_buf())
                     .unwrap_or_else(|| PathBuf::from(&default_dir))
+            } else {
+                // Fallback to base/author/model_name if the path doesn't match the filename
+                let model_parts: Vec<&str> = metadata.model_id.split('/').collect();
+                if model_parts.len() == 2 {
+                    PathBuf::from(&default_dir)
+                        .join(model_parts[0])
+                        .join(model_parts[1])
+                } else {
+                    PathBuf::from(&default_dir)
+                }
             };
 
             total_bytes += metadata.total_size;
 
+            crate::download::discard_changed_file(metadata);
             let _ = self.download_tx.send((
                 metadata.model_id.clone(),
                 metadata.filename.clone(),
                 base_path,
-                metadata.expected_sha256.clone(),
+                metadata.expected_hash(),
                 hf_token.clone(),
                 metadata.total_size,
+                url_revision(&metadata.url),
             ));
         }
 
+        // Then what was still queued, in its original order and directories
+        for queued in &self.queued_downloads {
+            total_bytes += queued.total_size;
+
+            let _ = self.download_tx.send((
+                queued.model_id.clone(),
+                queued.filename.clone(),
+                PathBuf::from(&queued.base_path),
+                queued.expected_hash(),
+                hf_token.clone(),
+                queued.total_size,
+                queued.revision.clone(),
+            ));
+            if !queued.priority.is_normal() {
+                self.download_priorities.insert(
+                    (queued.model_id.clone(), queued.filename.clone()),
+                    queued.priority,
+                );
+            }
+        }
+        let queued_count = self.queued_downloads.len();
+        self.needs_sort_download_queue = true;
+
         // Update queue size and bytes
         {
             let mut queue = self.download_queue.lock().await;
-            queue.add(count, total_bytes);
+            queue.add(count + queued_count, total_bytes);
         }
 
-        *self.status.write() = format!("Resuming {} incomplete download(s)", count);
+        *self.status.write() = if queued_count == 0 {
+            format!("Resuming {} incomplete download(s)", count)
+        } else {
+            format!(
+                "Resuming {} incomplete and {} queued download(s)",
+                count, queued_count
+            )
+        };
         self.incomplete_downloads.clear();
+        self.queued_downloads.clear();
     }
 
     /// Delete incomplete files and remove from registry
     pub async fn delete_incomplete_downloads(&mut self) {
         let mut deleted = 0;
+        let mut forgotten = 0;
         let mut errors = Vec::new();
 
         // Load registry
@@ -381,11 +1061,19 @@ impl App {
         };
 
         for metadata in &self.incomplete_downloads {
-            // Try to delete the actual .incomplete file
+            // A file changed on disk is only forgotten: what is left is the user's
+            if metadata.status == DownloadStatus::Changed {
+                registry.downloads.retain(|d| d.url != metadata.url);
+                forgotten += 1;
+                continue;
+            }
+
+            // Move the actual .incomplete file to the trash, so `restore`
+            // can bring it back and resume it
             let file_path = PathBuf::from(&metadata.local_path);
             let incomplete_path = PathBuf::from(format!("{}.incomplete", file_path.display()));
 
-            match tokio::fs::remove_file(&incomplete_path).await {
+            match crate::trash::move_to_trash(&incomplete_path, Some(metadata)) {
                 Ok(_) => deleted += 1,
                 Err(e) => {
                     errors.push(format!("{}: {}", metadata.filename, e));
@@ -396,6 +1084,16 @@ impl App {
             registry.downloads.retain(|d| d.url != metadata.url);
         }
 
+        // Queued downloads never started, so there are no files to delete
+        let dropped = self.queued_downloads.len();
+        for queued in &self.queued_downloads {
+            let url = queued_url(queued);
+            registry
+                .downloads
+                .retain(|d| d.url != url || d.status == DownloadStatus::Complete);
+        }
+        self.queued_downloads.clear();
+
         // Save updated registry
         registry::save_registry(&registry);
         {
@@ -403,12 +1101,30 @@ impl App {
             *reg = registry;
         }
 
-        if errors.is_empty() {
-            *self.status.write() = format!("Deleted {} incomplete file(s)", deleted);
+        let mut dropped_text = if dropped > 0 {
+            format!(", dropped {} queued download(s)", dropped)
+        } else {
+            String::new()
+        };
+        if forgotten > 0 {
+            dropped_text.push_str(&format!(
+                ", removed {} changed download(s) from the registry",
+                forgotten
+            ));
+        }
+        if errors.is_empty() && crate::trash::retention_days() > 0 {
+            *self.status.write() = format!(
+                "Moved {} incomplete file(s) to the trash{} (`restore` brings them back)",
+                deleted, dropped_text
+            );
+        } else if errors.is_empty() {
+            *self.status.write() =
+                format!("Deleted {} incomplete file(s){}", deleted, dropped_text);
         } else {
             *self.status.write() = format!(
-                "Deleted {} file(s), {} error(s): {}",
+                "Deleted {} file(s){}, {} error(s): {}",
                 deleted,
+                dropped_text,
                 errors.len(),
                 errors.join(", ")
             );
@@ -418,6 +1134,13 @@ impl App {
 
     /// Download entire repository (non-GGUF models)
     pub async fn confirm_repository_download(&mut self) {
+        let with_base = std::mem::take(&mut self.download_with_base);
+        let preset = std::mem::take(&mut self.download_preset);
+        let convert_to_gguf = self
+            .download_summary
+            .as_ref()
+            .and_then(|summary| summary.convert_to_gguf)
+            .unwrap_or(false);
         let models = self.models.read().clone();
         let metadata = self.model_metadata.read().clone();
 
@@ -428,143 +1151,384 @@ impl App {
                 let model = &models[model_idx];
                 let base_path = self.download_path_input.value().to_string();
 
-                // Filter out directories - only download files
-                let files_to_download: Vec<_> = meta
-                    .siblings
-                    .iter()
-                    .filter(|f| {
-                        // Skip if it's likely a directory (no size or ends with /)
-                        f.size.is_some() && !f.rfilename.ends_with('/')
-                    })
-                    .collect();
-
-                if files_to_download.is_empty() {
-                    *self.error.write() =
-                        Some("No files to download in this repository".to_string());
+                let Some((queued, model_root)) = self
+                    .queue_repository_files(&model.id, &meta, &base_path, preset)
+                    .await
+                else {
                     return;
-                }
-
-                let num_files = files_to_download.len();
-
-                // Load registry
-                let mut registry = {
-                    let reg = self.download_registry.lock().await;
-                    reg.clone()
                 };
 
-                // Add metadata entries for all files
-                for file in &files_to_download {
-                    let filename = &file.rfilename;
+                *self.status.write() = format!(
+                    "Queued {} files from {} to {}",
+                    queued,
+                    model.id,
+                    model_root.display()
+                );
+                if convert_to_gguf {
+                    self.queue_conversion(&model.id, model_root.clone());
+                    self.status
+                        .write()
+                        .push_str(" (converted to GGUF once downloaded)");
+                }
 
-                    // Validate path
-                    let validated_path =
-                        match validate_and_sanitize_path(&base_path, &model.id, filename) {
-                            Ok(path) => path,
-                            Err(e) => {
-                                *self.error.write() =
-                                    Some(format!("Invalid filename '{}': {}", filename, e));
-                                continue;
+                // Adapter + base: queue the base repository next to the adapter
+                if let Some(base) = meta.adapter_base_model().filter(|_| with_base) {
+                    let token = self.options.hf_token.clone();
+                    match fetch_model_metadata(&base, token.as_ref()).await {
+                        Ok(base_meta) => {
+                            if let Some((base_queued, _)) = self
+                                .queue_repository_files(&base, &base_meta, &base_path, preset)
+                                .await
+                            {
+                                *self.status.write() = format!(
+                                    "Queued {} adapter files from {} and {} files from base model {}",
+                                    queued, model.id, base_queued, base
+                                );
                             }
-                        };
-
-                    let url = format!(
-                        "https://huggingface.co/{}/resolve/main/{}",
-                        model.id, filename
-                    );
-                    let local_path_str = validated_path.to_string_lossy().to_string();
-
-                    // Only add if not already in registry
-                    if !registry.downloads.iter().any(|d| d.url == url) {
-                        // Extract SHA256 from LFS info if available
-                        let expected_sha256 = file.lfs.as_ref().map(|lfs| lfs.oid.clone());
-
-                        registry.downloads.push(DownloadMetadata {
-                            model_id: model.id.clone(),
-                            filename: filename.clone(),
-                            url: url.clone(),
-                            local_path: local_path_str,
-                            total_size: file.size.unwrap_or(0),
-                            downloaded_size: 0,
-                            status: DownloadStatus::Incomplete,
-                            expected_sha256,
-                        });
+                        }
+                        Err(e) => {
+                            *self.error.write() =
+                                Some(format!("Failed to fetch base model {}: {}", base, e));
+                        }
                     }
                 }
+            }
+        }
+    }
 
-                // Save registry with all new entries
-                registry::save_registry(&registry);
-                {
-                    let mut reg = self.download_registry.lock().await;
-                    *reg = registry;
-                }
+    /// Register and queue every file of a repository (or just its weights,
+    /// configs and tokenizers) under base/author/model
+    /// Returns the number of files queued and the model root directory
+    async fn queue_repository_files(
+        &mut self,
+        model_id: &str,
+        meta: &ModelMetadata,
+        base_path: &str,
+        preset: RepoPreset,
+    ) -> Option<(usize, PathBuf)> {
+        let files = crate::api::preset_files(meta, preset);
+
+        // Filter out directories - only download files
+        let files_to_download: Vec<_> = files
+            .into_iter()
+            .filter(|f| {
+                // Skip if it's likely a directory (no size or ends with /)
+                f.size.is_some() && !f.rfilename.ends_with('/')
+            })
+            .collect();
+
+        if files_to_download.is_empty() {
+            *self.error.write() = Some(format!("No files to download in {}", model_id));
+            return None;
+        }
 
-                // Calculate total bytes for all files
-                let total_queued_bytes: u64 = files_to_download.iter().filter_map(|f| f.size).sum();
+        let num_files = files_to_download.len();
 
-                // Increment queue size and bytes
-                {
-                    let mut queue = self.download_queue.lock().await;
-                    queue.add(num_files, total_queued_bytes);
+        // Load registry
+        let mut registry = {
+            let reg = self.download_registry.lock().await;
+            reg.clone()
+        };
+
+        // Add metadata entries for all files
+        for file in &files_to_download {
+            let filename = &file.rfilename;
+
+            // Validate path
+            let validated_path = match validate_and_sanitize_path(base_path, model_id, filename) {
+                Ok(path) => path,
+                Err(e) => {
+                    *self.error.write() = Some(format!("Invalid filename '{}': {}", filename, e));
+                    continue;
                 }
+            };
 
-                // Calculate the model root directory (base/author/model_name)
-                // This is where all files will be organized with their subdirectory structure
-                let model_parts: Vec<&str> = model.id.split('/').collect();
-                let model_root = if model_parts.len() == 2 {
-                    PathBuf::from(&base_path)
-                        .join(model_parts[0])
-                        .join(model_parts[1])
-                } else {
-                    PathBuf::from(&base_path)
-                };
+            let url = file_url(model_id, None, filename);
+            let local_path_str = validated_path.to_string_lossy().to_string();
+
+            // Only add if not already in registry
+            if !registry.downloads.iter().any(|d| d.url == url) {
+                // LFS SHA256, or the git blob id for small non-LFS files
+                let (expected_sha256, expected_git_blob) =
+                    ExpectedHash::fields(file.expected_hash().as_ref());
+
+                registry.downloads.push(DownloadMetadata {
+                    model_id: model_id.to_string(),
+                    filename: filename.clone(),
+                    url: url.clone(),
+                    local_path: local_path_str,
+                    total_size: file.size.unwrap_or(0),
+                    downloaded_size: 0,
+                    status: DownloadStatus::Incomplete,
+                    expected_sha256,
+                    expected_git_blob,
+                    commit: None,
+                });
+            }
+        }
 
-                // Send all download requests - each file will preserve its subdirectory structure
-                let mut success_count = 0;
-                let hf_token = self.options.hf_token.clone();
-                for file in &files_to_download {
-                    let sha256 = file.lfs.as_ref().map(|lfs| lfs.oid.clone());
-                    let file_size = file.size.unwrap_or(0);
+        // Save registry with all new entries
+        registry::save_registry(&registry);
+        {
+            let mut reg = self.download_registry.lock().await;
+            *reg = registry;
+        }
 
-                    if self
-                        .download_tx
-                        .send((
-                            model.id.clone(),
-                            file.rfilename.clone(),
-                            model_root.clone(),
-                            sha256,
-                            hf_token.clone(),
-                            file_size,
-                        ))
-                        .is_ok()
-                    {
-                        success_count += 1;
-                    }
-                }
+        // Calculate total bytes for all files
+        let total_queued_bytes: u64 = files_to_download.iter().filter_map(|f| f.size).sum();
 
-                if success_count > 0 {
-                    *self.status.write() = format!(
-                        "Queued {} files from {} to {}",
-                        success_count,
-                        model.id,
-                        model_root.display()
-                    );
-                } else {
-                    *self.error.write() = Some("Failed to start downloads".to_string());
-                }
+        // Increment queue size and bytes
+        {
+            let mut queue = self.download_queue.lock().await;
+            queue.add(num_files, total_queued_bytes);
+        }
 
-                // Adjust queue size and bytes if some sends failed
-                if success_count < num_files {
-                    let failed_count = num_files - success_count;
-                    let failed_bytes: u64 = files_to_download
-                        .iter()
-                        .skip(success_count)
-                        .filter_map(|f| f.size)
-                        .sum();
+        // Calculate the model root directory (base/author/model_name)
+        // This is where all files will be organized with their subdirectory structure
+        let model_parts: Vec<&str> = model_id.split('/').collect();
+        let model_root = if model_parts.len() == 2 {
+            PathBuf::from(base_path)
+                .join(model_parts[0])
+                .join(model_parts[1])
+        } else {
+            PathBuf::from(base_path)
+        };
 
-                    let mut queue = self.download_queue.lock().await;
-                    queue.remove(failed_count, failed_bytes);
-                }
+        // Send all download requests - each file will preserve its subdirectory structure
+        let mut success_count = 0;
+        let hf_token = self.options.hf_token.clone();
+        let priority = self.download_summary.as_ref().map(|s| s.priority).unwrap_or_default();
+        for file in &files_to_download {
+            let expected_hash = file.expected_hash();
+            let file_size = file.size.unwrap_or(0);
+
+            if self
+                .download_tx
+                .send((
+                    model_id.to_string(),
+                    file.rfilename.clone(),
+                    model_root.clone(),
+                    expected_hash,
+                    hf_token.clone(),
+                    file_size,
+                    None,
+                ))
+                .is_ok()
+            {
+                self.set_pending_priority(model_id, &file.rfilename, priority);
+                success_count += 1;
             }
         }
+        self.needs_sort_download_queue = true;
+
+        // Adjust queue size and bytes if some sends failed
+        if success_count < num_files {
+            let failed_count = num_files - success_count;
+            let failed_bytes: u64 = files_to_download
+                .iter()
+                .skip(success_count)
+                .filter_map(|f| f.size)
+                .sum();
+
+            let mut queue = self.download_queue.lock().await;
+            queue.remove(failed_count, failed_bytes);
+        }
+
+        if success_count == 0 {
+            *self.error.write() = Some("Failed to start downloads".to_string());
+            return None;
+        }
+
+        Some((success_count, model_root))
+    }
+
+    /// Check split GGUFs queued earlier now that the download queue is empty:
+    /// warn about missing parts, or offer the merge command for complete sets
+    pub fn check_pending_part_sets(&mut self) {
+        let paths = std::mem::take(&mut self.pending_part_paths);
+        let sets = crate::multipart::part_sets(&paths);
+
+        let incomplete: Vec<String> = sets
+            .iter()
+            .filter(|set| !set.is_complete())
+            .map(|set| {
+                let missing: Vec<String> = set.missing().iter().map(|n| n.to_string()).collect();
+                format!(
+                    "{} is missing part(s) {} of {}",
+                    set.base_name,
+                    missing.join(", "),
+                    set.total()
+                )
+            })
+            .collect();
+
+        if !incomplete.is_empty() {
+            *self.error.write() =
+                Some(format!("Incomplete split model: {}", incomplete.join("; ")));
+        } else if let Some(command) = sets
+            .iter()
+            .filter_map(|set| set.merge_command())
+            .next_back()
+        {
+            *self.status.write() = format!("All parts downloaded. Merge with: {}", command);
+        }
+    }
+}
+
+/// Every part of the split set `file` belongs to (ordered by part number), or
+/// just `file` when it isn't split
+fn part_set_files(group: &QuantizationGroup, file: &QuantizationInfo) -> Vec<QuantizationInfo> {
+    let Some((_, total)) = crate::api::parse_multipart_filename(&file.filename) else {
+        return vec![file.clone()];
+    };
+    let base_name = crate::api::get_multipart_base_name(&file.filename);
+
+    let mut parts: Vec<(u32, QuantizationInfo)> = group
+        .files
+        .iter()
+        .filter_map(|f| {
+            let (part, of) = crate::api::parse_multipart_filename(&f.filename)?;
+            (of == total && crate::api::get_multipart_base_name(&f.filename) == base_name)
+                .then(|| (part, f.clone()))
+        })
+        .collect();
+    parts.sort_by_key(|(part, _)| *part);
+    parts.into_iter().map(|(_, f)| f).collect()
+}
+
+/// Summary of a repository download with the files of `preset`
+fn repository_summary(meta: &ModelMetadata, preset: RepoPreset) -> DownloadSummary {
+    let presets = crate::api::repo_presets(meta);
+    let next_preset = presets
+        .iter()
+        .position(|other| *other == preset)
+        .map_or(RepoPreset::All, |idx| presets[(idx + 1) % presets.len()]);
+    let files = crate::api::preset_files(meta, preset);
+
+    DownloadSummary {
+        label: meta.model_id.clone(),
+        files: files.len(),
+        bytes: files.iter().filter_map(|f| f.size).sum(),
+        preset: (presets.len() > 1 || preset != RepoPreset::All).then_some(preset),
+        next_preset,
+        skipped_files: meta.siblings.len() - files.len(),
+        convert_to_gguf: Some(false),
+        ..Default::default()
+    }
+}
+
+/// Priority of a pending download; downloads not listed are normal
+fn priority_of(
+    priorities: &DownloadPriorities,
+    model_id: &str,
+    filename: &str,
+) -> DownloadPriority {
+    priorities
+        .get(&(model_id.to_string(), filename.to_string()))
+        .copied()
+        .unwrap_or_default()
+}
+
+fn set_priority(
+    priorities: &mut DownloadPriorities,
+    model_id: &str,
+    filename: &str,
+    priority: DownloadPriority,
+) {
+    let key = (model_id.to_string(), filename.to_string());
+    if priority.is_normal() {
+        priorities.remove(&key);
+    } else {
+        priorities.insert(key, priority);
+    }
+}
+
+/// Order pending downloads by priority, keeping their order within each
+fn sort_pending(pending: &mut [DownloadMessage], priorities: &DownloadPriorities) {
+    pending.sort_by_key(|m| priority_of(priorities, &m.0, &m.1));
+}
+
+/// Swap the pending download at `from` with the one `delta` places away,
+/// giving it that download's priority so sorting keeps it there. Returns
+/// its new position.
+fn move_pending(
+    pending: &mut [DownloadMessage],
+    priorities: &mut DownloadPriorities,
+    from: usize,
+    delta: isize,
+) -> Option<usize> {
+    let to = from
+        .checked_add_signed(delta)
+        .filter(|to| *to < pending.len())?;
+    let priority = priority_of(priorities, &pending[to].0, &pending[to].1);
+    let (model_id, filename) = (pending[from].0.clone(), pending[from].1.clone());
+    set_priority(priorities, &model_id, &filename, priority);
+    pending.swap(from, to);
+    Some(to)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(filename: &str) -> DownloadMessage {
+        (
+            "author/model".to_string(),
+            filename.to_string(),
+            PathBuf::from("models"),
+            None,
+            None,
+            1,
+            None,
+        )
+    }
+
+    fn order(pending: &[DownloadMessage]) -> Vec<&str> {
+        pending.iter().map(|m| m.1.as_str()).collect()
+    }
+
+    #[test]
+    fn test_reorder_pending_downloads() {
+        let mut pending: Vec<_> = ["a", "b", "c", "d"].into_iter().map(message).collect();
+        let mut priorities = DownloadPriorities::new();
+        set_priority(&mut priorities, "author/model", "c", DownloadPriority::High);
+        set_priority(&mut priorities, "author/model", "a", DownloadPriority::Low);
+
+        // Stable within each priority
+        sort_pending(&mut pending, &priorities);
+        assert_eq!(order(&pending), ["c", "b", "d", "a"]);
+
+        // Moving within a priority swaps places
+        assert_eq!(move_pending(&mut pending, &mut priorities, 1, 1), Some(2));
+        sort_pending(&mut pending, &priorities);
+        assert_eq!(order(&pending), ["c", "d", "b", "a"]);
+
+        // Moving past another priority takes it on, so sorting keeps it there
+        assert_eq!(move_pending(&mut pending, &mut priorities, 1, -1), Some(0));
+        assert_eq!(
+            priority_of(&priorities, "author/model", "d"),
+            DownloadPriority::High
+        );
+        assert_eq!(move_pending(&mut pending, &mut priorities, 2, 1), Some(3));
+        assert_eq!(
+            priority_of(&priorities, "author/model", "b"),
+            DownloadPriority::Low
+        );
+        sort_pending(&mut pending, &priorities);
+        assert_eq!(order(&pending), ["d", "c", "a", "b"]);
+
+        // Off either end is refused
+        assert_eq!(move_pending(&mut pending, &mut priorities, 0, -1), None);
+        assert_eq!(move_pending(&mut pending, &mut priorities, 3, 1), None);
+        assert_eq!(order(&pending), ["d", "c", "a", "b"]);
+
+        // Normal priority is not stored
+        set_priority(
+            &mut priorities,
+            "author/model",
+            "d",
+            DownloadPriority::Normal,
+        );
+        assert!(!priorities.contains_key(&("author/model".to_string(), "d".to_string())));
     }
 }
@@ -3,6 +3,9 @@ use crate::models::*;
 use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
 use tui_input::backend::crossterm::EventHandler;
 
+/// How long a `g` or count prefix waits for the rest of its key sequence
+const KEY_SEQUENCE_TIMEOUT: std::time::Duration = std::time::Duration::from_millis(500);
+
 impl App {
     /// Main keyboard event dispatcher
     pub async fn on_key_event(&mut self, key: KeyEvent) {
@@ -12,6 +15,150 @@ impl App {
         if self.popup_mode == PopupMode::SearchPopup {
             self.handle_search_popup_input(key).await;
             return;
+        } else if self.popup_mode == PopupMode::OpenModel {
+            self.handle_open_popup_input(key);
+            return;
+        } else if self.popup_mode == PopupMode::ModelNotes {
+            self.handle_notes_popup_input(key).await;
+            return;
+        } else if self.popup_mode == PopupMode::CommandPalette {
+            self.handle_command_palette_input(key).await;
+            return;
+        } else if self.popup_mode == PopupMode::QuantDiff {
+            if matches!(
+                key.code,
+                KeyCode::Esc | KeyCode::Enter | KeyCode::Char('c') | KeyCode::Char('q')
+            ) {
+                self.popup_mode = PopupMode::None;
+                self.quant_diff = None;
+            }
+            return;
+        } else if self.popup_mode == PopupMode::WatchUpdates {
+            match key.code {
+                KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') | KeyCode::Char('W') => {
+                    self.popup_mode = PopupMode::None;
+                }
+                KeyCode::Down | KeyCode::Char('j') => {
+                    self.watch_updates_scroll = self.watch_updates_scroll.saturating_add(1);
+                }
+                KeyCode::Up | KeyCode::Char('k') => {
+                    self.watch_updates_scroll = self.watch_updates_scroll.saturating_sub(1);
+                }
+                _ => {}
+            }
+            return;
+        } else if self.popup_mode == PopupMode::IgnoreList {
+            match key.code {
+                KeyCode::Esc | KeyCode::Char('q') => self.close_ignore_list(),
+                KeyCode::Down | KeyCode::Char('j') => {
+                    self.ignore_list_selected = (self.ignore_list_selected + 1)
+                        .min(self.ignore_list_view.len().saturating_sub(1));
+                }
+                KeyCode::Up | KeyCode::Char('k') => {
+                    self.ignore_list_selected = self.ignore_list_selected.saturating_sub(1);
+                }
+                KeyCode::Enter | KeyCode::Delete | KeyCode::Char('d') => {
+                    self.unhide_selected_entry();
+                    if self.ignore_list_view.is_empty() {
+                        self.close_ignore_list();
+                    }
+                }
+                _ => {}
+            }
+            return;
+        } else if self.popup_mode == PopupMode::QuotaCleanup {
+            match key.code {
+                KeyCode::Esc | KeyCode::Char('q') => self.close_quota_cleanup(),
+                KeyCode::Down | KeyCode::Char('j') => {
+                    self.quota_selected = (self.quota_selected + 1)
+                        .min(self.quota_candidates.len().saturating_sub(1));
+                }
+                KeyCode::Up | KeyCode::Char('k') => {
+                    self.quota_selected = self.quota_selected.saturating_sub(1);
+                }
+                KeyCode::Delete | KeyCode::Char('d') => {
+                    self.delete_selected_candidate().await;
+                }
+                KeyCode::Enter => {
+                    self.quota_candidates.clear();
+                    self.confirm_download().await;
+                    self.popup_mode = PopupMode::None;
+                }
+                _ => {}
+            }
+            return;
+        } else if self.popup_mode == PopupMode::DownloadPlan {
+            match key.code {
+                KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') | KeyCode::Char('D') => {
+                    self.popup_mode = PopupMode::None;
+                }
+                KeyCode::Down | KeyCode::Char('j') => {
+                    self.download_plan_scroll = self.download_plan_scroll.saturating_add(1);
+                }
+                KeyCode::Up | KeyCode::Char('k') => {
+                    self.download_plan_scroll = self.download_plan_scroll.saturating_sub(1);
+                }
+                _ => {}
+            }
+            return;
+        } else if self.popup_mode == PopupMode::PromptFormat {
+            match key.code {
+                KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') | KeyCode::Char('t') => {
+                    self.popup_mode = PopupMode::None;
+                    self.prompt_format = None;
+                }
+                KeyCode::Down | KeyCode::Char('j') => {
+                    self.prompt_format_scroll = self.prompt_format_scroll.saturating_add(1);
+                }
+                KeyCode::Up | KeyCode::Char('k') => {
+                    self.prompt_format_scroll = self.prompt_format_scroll.saturating_sub(1);
+                }
+                KeyCode::PageDown => {
+                    self.prompt_format_scroll = self.prompt_format_scroll.saturating_add(10);
+                }
+                KeyCode::PageUp => {
+                    self.prompt_format_scroll = self.prompt_format_scroll.saturating_sub(10);
+                }
+                _ => {}
+            }
+            return;
+        } else if self.popup_mode == PopupMode::AuthorProfile {
+            match key.code {
+                KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('A') => {
+                    self.popup_mode = PopupMode::None;
+                    self.author_profile = None;
+                }
+                KeyCode::Enter => self.show_author_models(),
+                _ => {}
+            }
+            return;
+        } else if self.popup_mode == PopupMode::DownloadQueue {
+            match key.code {
+                KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') | KeyCode::Char('Q') => {
+                    self.popup_mode = PopupMode::None;
+                }
+                KeyCode::Down | KeyCode::Char('j') => {
+                    self.download_queue_selected = (self.download_queue_selected + 1)
+                        .min(self.download_queue_view.len().saturating_sub(1));
+                }
+                KeyCode::Up | KeyCode::Char('k') => {
+                    self.download_queue_selected = self.download_queue_selected.saturating_sub(1);
+                }
+                KeyCode::Char('J') => self.move_queued_download(1),
+                KeyCode::Char('K') => self.move_queued_download(-1),
+                KeyCode::Char('+') | KeyCode::Char('=') => self.change_queued_priority(true),
+                KeyCode::Char('-') => self.change_queued_priority(false),
+                _ => {}
+            }
+            return;
+        } else if self.popup_mode == PopupMode::Stats {
+            if matches!(
+                key.code,
+                KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') | KeyCode::Char('H')
+            ) {
+                self.popup_mode = PopupMode::None;
+            }
+            return;
         } else if self.popup_mode == PopupMode::Options {
             self.handle_options_popup_input(key).await;
             return;
@@ -33,228 +180,117 @@ impl App {
     }
 
     /// Handle keyboard input in Normal mode
+    /// Keys are resolved through the shared action table (see `actions.rs`),
+    /// after Vim-style sequences: a count prefix (`5j`, `20G`) and `gg`
     async fn handle_normal_mode_input(&mut self, key: KeyEvent) {
-        match (key.modifiers, key.code) {
-            (_, KeyCode::Char('q'))
-            | (KeyModifiers::CONTROL, KeyCode::Char('c') | KeyCode::Char('C')) => self.quit(),
-            (_, KeyCode::Char('/')) => {
-                // Open search popup instead of inline editing
-                self.popup_mode = PopupMode::SearchPopup;
-                self.input.reset(); // Clear previous search
-                *self.status.write() = "Search Models".to_string();
-            }
-            (_, KeyCode::Char('d')) => {
-                // Allow download from Models pane (for non-GGUF), QuantizationGroups, or QuantizationFiles
-                if self.focused_pane == FocusedPane::Models
-                    || self.focused_pane == FocusedPane::QuantizationGroups
-                    || self.focused_pane == FocusedPane::QuantizationFiles
-                {
-                    self.trigger_download();
-                }
-            }
-            (_, KeyCode::Char('v')) => {
-                if self.focused_pane == FocusedPane::QuantizationGroups
-                    || self.focused_pane == FocusedPane::QuantizationFiles
-                {
-                    self.verify_downloaded_file().await;
+        let plain = key.modifiers == KeyModifiers::NONE;
+        let pending_g = self
+            .pending_keys
+            .last()
+            .is_some_and(|k| k.code == KeyCode::Char('g'));
+
+        if plain && !pending_g {
+            let starts_sequence = match key.code {
+                // A leading 0 is not a count
+                KeyCode::Char(c) if c.is_ascii_digit() => c != '0' || !self.pending_keys.is_empty(),
+                KeyCode::Char('g') => true,
+                _ => false,
+            };
+            if starts_sequence {
+                if self.pending_keys.is_empty() {
+                    self.pending_keys_since = std::time::Instant::now();
                 }
+                self.pending_keys.push(key);
+                return;
             }
-            (_, KeyCode::Char('o')) => {
-                self.popup_mode = PopupMode::Options;
-            }
-            (KeyModifiers::CONTROL, KeyCode::Char('s') | KeyCode::Char('S')) => {
-                // Save current filter settings as defaults
-                self.save_filter_settings();
-            }
-            (_, KeyCode::Char('s')) => {
-                // Cycle sort field: Downloads → Likes → Modified → Name → Downloads
-                self.sort_field = match self.sort_field {
-                    crate::models::SortField::Downloads => crate::models::SortField::Likes,
-                    crate::models::SortField::Likes => crate::models::SortField::Modified,
-                    crate::models::SortField::Modified => crate::models::SortField::Name,
-                    crate::models::SortField::Name => crate::models::SortField::Downloads,
-                };
+        }
 
-                // Re-fetch with new sort
-                self.clear_search_results();
-                self.needs_search_models = true;
+        // Any other key completes (or abandons) the pending sequence
+        let count = self.take_pending_count();
+        if pending_g && plain && key.code == KeyCode::Char('g') {
+            // `gg` / `[count]gg`
+            self.select_in_focused_pane(count.map_or(0, |n| n.saturating_sub(1)));
+            return;
+        }
 
-                *self.status.write() = format!("Sort by: {:?}", self.sort_field);
+        if let Some(action) = self.action_for_key(&key) {
+            match count {
+                Some(count) => self.perform_action_with_count(action, count).await,
+                None => self.perform_action(action).await,
             }
-            (KeyModifiers::SHIFT, KeyCode::Char('S')) => {
-                // Toggle sort direction
-                self.sort_direction = match self.sort_direction {
-                    crate::models::SortDirection::Ascending => {
-                        crate::models::SortDirection::Descending
-                    }
-                    crate::models::SortDirection::Descending => {
-                        crate::models::SortDirection::Ascending
-                    }
-                };
-
-                // Re-fetch with new direction
-                self.clear_search_results();
-                self.needs_search_models = true;
+        }
+    }
 
-                let arrow = match self.sort_direction {
-                    crate::models::SortDirection::Ascending => "▲",
-                    crate::models::SortDirection::Descending => "▼",
-                };
-                *self.status.write() =
-                    format!("Sort direction: {:?} {}", self.sort_direction, arrow);
-            }
-            (_, KeyCode::Char('f')) => {
-                // Cycle focused filter field
-                self.focused_filter_field = (self.focused_filter_field + 1) % 3;
-                let field_name = match self.focused_filter_field {
-                    0 => "Sort",
-                    1 => "Min Downloads",
-                    2 => "Min Likes",
-                    _ => unreachable!(),
-                };
-                *self.status.write() = format!("Focused filter: {}", field_name);
-            }
-            (_, KeyCode::Char('+')) if self.focused_pane == FocusedPane::Models => {
-                // Increment focused filter (only in Models pane to avoid conflicts)
-                self.modify_focused_filter(1);
-            }
-            (_, KeyCode::Char('-') | KeyCode::Char('_'))
-                if self.focused_pane == FocusedPane::Models =>
-            {
-                // Decrement focused filter (only in Models pane to avoid conflicts)
-                self.modify_focused_filter(-1);
-            }
-            (_, KeyCode::Char('r')) => {
-                // Reset all filters to defaults
-                self.sort_field = crate::models::SortField::default();
-                self.sort_direction = crate::models::SortDirection::default();
-                self.filter_min_downloads = 0;
-                self.filter_min_likes = 0;
-                self.focused_filter_field = 0;
+    /// Clear the pending key sequence, returning its count prefix
+    fn take_pending_count(&mut self) -> Option<usize> {
+        let keys = std::mem::take(&mut self.pending_keys);
+        let digits: String = keys
+            .iter()
+            .filter_map(|k| match k.code {
+                KeyCode::Char(c) if c.is_ascii_digit() => Some(c),
+                _ => None,
+            })
+            .collect();
+        // Absurdly long counts saturate; they only ever get clamped to the list length
+        (!digits.is_empty()).then(|| digits.parse().unwrap_or(usize::MAX))
+    }
 
-                // Re-fetch with reset filters
-                self.clear_search_results();
-                self.needs_search_models = true;
+    /// Resolve a key sequence nobody completed
+    /// A lone prefix key (`g`, or a digit bound to a filter preset) falls back
+    /// to its own binding after `KEY_SEQUENCE_TIMEOUT`; longer sequences are dropped.
+    pub async fn flush_pending_keys(&mut self) {
+        if self.pending_keys.is_empty()
+            || self.pending_keys_since.elapsed() < KEY_SEQUENCE_TIMEOUT
+        {
+            return;
+        }
 
-                *self.status.write() = "Filters reset to defaults".to_string();
-            }
-            (_, KeyCode::Char('1')) => {
-                // Preset 1: No Filters (default)
-                if self.would_change_settings(FilterPreset::NoFilters) {
-                    self.apply_filter_preset(FilterPreset::NoFilters);
-                } else {
-                    *self.status.write() = "Already using No Filters preset".to_string();
-                }
-            }
-            (_, KeyCode::Char('2')) => {
-                // Preset 2: Popular (10k+ downloads, 100+ likes)
-                if self.would_change_settings(FilterPreset::Popular) {
-                    self.apply_filter_preset(FilterPreset::Popular);
-                } else {
-                    *self.status.write() = "Already using Popular preset".to_string();
-                }
+        let keys = std::mem::take(&mut self.pending_keys);
+        if self.popup_mode != PopupMode::None {
+            return;
+        }
+        if let [key] = keys.as_slice() {
+            if let Some(action) = self.action_for_key(key) {
+                self.perform_action(action).await;
             }
-            (_, KeyCode::Char('3')) => {
-                // Preset 3: Highly Rated (1k+ likes, sort by likes)
-                if self.would_change_settings(FilterPreset::HighlyRated) {
-                    self.apply_filter_preset(FilterPreset::HighlyRated);
-                } else {
-                    *self.status.write() = "Already using Highly Rated preset".to_string();
-                }
+        }
+    }
+
+    /// Handle keyboard input in the ':' command palette
+    async fn handle_command_palette_input(&mut self, key: KeyEvent) {
+        let matches = super::actions::filter_actions(self.palette_input.value());
+
+        match (key.modifiers, key.code) {
+            (_, KeyCode::Esc) => {
+                self.popup_mode = PopupMode::None;
             }
-            (_, KeyCode::Char('4')) => {
-                // Preset 4: Recent (sort by modified)
-                if self.would_change_settings(FilterPreset::Recent) {
-                    self.apply_filter_preset(FilterPreset::Recent);
+            (_, KeyCode::Enter) => {
+                self.popup_mode = PopupMode::None;
+                let Some(entry) = matches.get(self.palette_selected) else {
+                    return;
+                };
+                if self.is_action_available(entry.action) {
+                    self.perform_action(entry.action).await;
                 } else {
-                    *self.status.write() = "Already using Recent preset".to_string();
-                }
-            }
-            (_, KeyCode::Tab) => {
-                self.toggle_focus();
-            }
-            (_, KeyCode::Left) => {
-                // Left arrow: switch from QuantizationFiles to QuantizationGroups
-                if self.focused_pane == FocusedPane::QuantizationFiles {
-                    self.toggle_quant_subfocus();
-                }
-            }
-            (_, KeyCode::Right) => {
-                // Right arrow: switch from QuantizationGroups to QuantizationFiles
-                if self.focused_pane == FocusedPane::QuantizationGroups {
-                    self.toggle_quant_subfocus();
+                    *self.error.write() =
+                        Some(format!("'{}' is not available here", entry.name));
                 }
             }
-            (_, KeyCode::Down | KeyCode::Char('j')) => {
-                match self.focused_pane {
-                    FocusedPane::Models => {
-                        self.next();
-                        // Clear details immediately to show selection change
-                        self.clear_model_details();
-                        // Set flag to load on next iteration (allows UI to render first)
-                        self.needs_load_quantizations = true;
-                    }
-                    FocusedPane::QuantizationGroups => {
-                        self.next_quant();
-                    }
-                    FocusedPane::QuantizationFiles => {
-                        self.next_file();
-                    }
-                    FocusedPane::ModelMetadata => {
-                        // No navigation in metadata pane (read-only text)
-                    }
-                    FocusedPane::FileTree => {
-                        self.next_file_tree_item();
-                    }
+            (_, KeyCode::Down) | (KeyModifiers::CONTROL, KeyCode::Char('n')) => {
+                if !matches.is_empty() {
+                    self.palette_selected = (self.palette_selected + 1) % matches.len();
                 }
             }
-            (_, KeyCode::Up | KeyCode::Char('k')) => {
-                match self.focused_pane {
-                    FocusedPane::Models => {
-                        self.previous();
-                        // Clear details immediately to show selection change
-                        self.clear_model_details();
-                        // Set flag to load on next iteration (allows UI to render first)
-                        self.needs_load_quantizations = true;
-                    }
-                    FocusedPane::QuantizationGroups => {
-                        self.previous_quant();
-                    }
-                    FocusedPane::QuantizationFiles => {
-                        self.previous_file();
-                    }
-                    FocusedPane::ModelMetadata => {
-                        // No navigation in metadata pane (read-only text)
-                    }
-                    FocusedPane::FileTree => {
-                        self.previous_file_tree_item();
-                    }
+            (_, KeyCode::Up) | (KeyModifiers::CONTROL, KeyCode::Char('p')) => {
+                if !matches.is_empty() {
+                    self.palette_selected =
+                        (self.palette_selected + matches.len() - 1) % matches.len();
                 }
             }
-            (_, KeyCode::Enter) => {
-                match self.focused_pane {
-                    FocusedPane::Models => {
-                        // Show model details first
-                        self.show_model_details().await;
-                        // Switch focus to the appropriate pane based on display mode
-                        // (toggle_focus already handles skipping ModelMetadata in Standard mode)
-                        self.toggle_focus();
-                    }
-                    FocusedPane::QuantizationGroups => {
-                        self.show_quantization_details().await;
-                    }
-                    FocusedPane::QuantizationFiles => {
-                        self.show_file_details().await;
-                    }
-                    FocusedPane::ModelMetadata => {
-                        // No action on Enter in metadata pane
-                    }
-                    FocusedPane::FileTree => {
-                        self.toggle_file_tree_expansion();
-                    }
-                }
+            _ => {
+                self.palette_input.handle_event(&Event::Key(key));
+                self.palette_selected = 0;
             }
-            _ => {}
         }
     }
 
@@ -272,6 +308,10 @@ impl App {
                 self.popup_mode = PopupMode::None;
                 self.input_mode = InputMode::Normal;
             }
+            KeyCode::Tab => {
+                // Match model card text instead of repository names
+                self.search_full_text = !self.search_full_text;
+            }
             KeyCode::Char(c) => {
                 self.input.handle(tui_input::InputRequest::InsertChar(c));
             }
@@ -297,6 +337,48 @@ impl App {
         }
     }
 
+    /// Handle keyboard input in Open popup (model ID or huggingface.co URL)
+    fn handle_open_popup_input(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Enter => {
+                self.popup_mode = PopupMode::None;
+                let target = self.open_input.value().to_string();
+                self.open_model_reference(&target);
+            }
+            KeyCode::Esc => {
+                self.popup_mode = PopupMode::None;
+            }
+            _ => {
+                self.open_input.handle_event(&Event::Key(key));
+            }
+        }
+    }
+
+    /// Handle keyboard input in the model notes popup (tags and note fields)
+    async fn handle_notes_popup_input(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Enter => {
+                self.popup_mode = PopupMode::None;
+                self.save_model_note().await;
+            }
+            KeyCode::Esc => {
+                self.popup_mode = PopupMode::None;
+                self.note_model_id = None;
+            }
+            KeyCode::Tab | KeyCode::BackTab | KeyCode::Up | KeyCode::Down => {
+                self.note_field = 1 - self.note_field;
+            }
+            _ => {
+                let input = if self.note_field == 0 {
+                    &mut self.note_tags_input
+                } else {
+                    &mut self.note_text_input
+                };
+                input.handle_event(&Event::Key(key));
+            }
+        }
+    }
+
     /// Handle keyboard input in Editing mode
     async fn handle_editing_mode_input(&mut self, key: KeyEvent) {
         match key.code {
@@ -366,21 +448,45 @@ impl App {
                     self.options_directory_input.handle_event(&Event::Key(key));
                 }
             }
+        } else if self.options.editing_tool_path {
+            match key.code {
+                KeyCode::Enter => {
+                    // Save the edited tool path (empty clears it)
+                    let path = self.options_tool_path_input.value().trim().to_string();
+                    let path = (!path.is_empty()).then_some(path);
+                    if self.options.selected_field == 34 {
+                        self.options.convert_script = path;
+                    } else {
+                        self.options.quantize_binary = path;
+                    }
+                    self.options.editing_tool_path = false;
+
+                    // Save to disk
+                    if let Err(e) = crate::config::save_config(&self.options) {
+                        *self.status.write() = format!("Failed to save config: {}", e);
+                    }
+                }
+                KeyCode::Esc => {
+                    // Cancel editing
+                    self.options.editing_tool_path = false;
+                }
+                _ => {
+                    self.options_tool_path_input.handle_event(&Event::Key(key));
+                }
+            }
         } else {
             // Normal navigation mode
             match key.code {
                 KeyCode::Esc => {
                     self.popup_mode = PopupMode::None;
                 }
-                KeyCode::Up | KeyCode::Char('k') => {
-                    if self.options.selected_field > 0 {
-                        self.options.selected_field -= 1;
-                    }
+                KeyCode::Up | KeyCode::Char('k') if self.options.selected_field > 0 => {
+                    self.options.selected_field -= 1;
                 }
-                KeyCode::Down | KeyCode::Char('j') => {
-                    if self.options.selected_field < 15 {
-                        self.options.selected_field += 1;
-                    }
+                KeyCode::Down | KeyCode::Char('j')
+                    if self.options.selected_field + 1 < crate::ui::render::OPTIONS_FIELD_COUNT =>
+                {
+                    self.options.selected_field += 1;
                 }
                 KeyCode::Char('+') | KeyCode::Right => {
                     self.modify_option(1);
@@ -398,6 +504,15 @@ impl App {
                         self.options.editing_token = true;
                         self.options_token_input = tui_input::Input::default()
                             .with_value(self.options.hf_token.as_deref().unwrap_or("").to_string());
+                    } else if matches!(self.options.selected_field, 34 | 35) {
+                        let path = if self.options.selected_field == 34 {
+                            &self.options.convert_script
+                        } else {
+                            &self.options.quantize_binary
+                        };
+                        self.options.editing_tool_path = true;
+                        self.options_tool_path_input = tui_input::Input::default()
+                            .with_value(path.as_deref().unwrap_or("").to_string());
                     }
                 }
                 _ => {}
@@ -428,16 +543,47 @@ impl App {
     /// Handle keyboard input in Download Path popup
     async fn handle_download_path_popup_input(&mut self, key: KeyEvent) {
         match key.code {
+            KeyCode::Enter if self.downloads_locked() => {
+                *self.status.write() =
+                    "Downloads are locked: press Ctrl+Y to download anyway".to_string();
+            }
+            KeyCode::Enter if self.download_over_quota() => {
+                self.open_quota_cleanup().await;
+            }
             KeyCode::Enter => {
                 self.confirm_download().await;
                 self.popup_mode = PopupMode::None;
             }
+            KeyCode::Char('y') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.confirm_download().await;
+                self.popup_mode = PopupMode::None;
+            }
             KeyCode::Esc => {
                 self.popup_mode = PopupMode::None;
+                self.download_with_base = false;
+                self.download_preset = RepoPreset::All;
                 *self.status.write() = "Download cancelled".to_string();
             }
+            KeyCode::Tab => {
+                self.toggle_download_scope();
+            }
+            KeyCode::Char('p') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.cycle_download_priority();
+            }
+            KeyCode::Char('g') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.toggle_gguf_conversion();
+            }
+            KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.cycle_download_root();
+            }
             _ => {
-                self.download_path_input.handle_event(&Event::Key(key));
+                if self
+                    .download_path_input
+                    .handle_event(&Event::Key(key))
+                    .is_some_and(|changed| changed.value)
+                {
+                    self.refresh_download_free_space();
+                }
             }
         }
     }
@@ -498,6 +644,78 @@ impl App {
         self.list_state.select(Some(i));
     }
 
+    /// Number of rows in the focused list pane
+    pub fn focused_list_len(&self) -> usize {
+        match self.focused_pane {
+            FocusedPane::Models => self.models.read().len(),
+            FocusedPane::QuantizationGroups => self.quantizations.read().len(),
+            FocusedPane::QuantizationFiles => self
+                .quant_list_state
+                .selected()
+                .and_then(|group| self.quantizations.read().get(group).map(|q| q.files.len()))
+                .unwrap_or(0),
+            FocusedPane::ModelMetadata => self.related_models.read().len(),
+            FocusedPane::FileTree => self
+                .file_tree
+                .read()
+                .as_ref()
+                .map(|tree| crate::ui::render::flatten_tree_for_navigation(tree).len())
+                .unwrap_or(0),
+        }
+    }
+
+    /// Selected row in the focused list pane
+    fn focused_selection(&self) -> Option<usize> {
+        match self.focused_pane {
+            FocusedPane::Models => self.list_state.selected(),
+            FocusedPane::QuantizationGroups => self.quant_list_state.selected(),
+            FocusedPane::QuantizationFiles => self.quant_file_list_state.selected(),
+            FocusedPane::ModelMetadata => self.related_state.selected(),
+            FocusedPane::FileTree => self.file_tree_state.selected(),
+        }
+    }
+
+    /// Select row `index` in the focused list pane, clamped to the last row
+    pub fn select_in_focused_pane(&mut self, index: usize) {
+        let len = self.focused_list_len();
+        if len == 0 {
+            return;
+        }
+        let index = index.min(len - 1);
+
+        match self.focused_pane {
+            FocusedPane::Models => {
+                if self.list_state.selected() == Some(index) {
+                    return;
+                }
+                self.list_state.select(Some(index));
+                // Same deferred load as single-step navigation
+                self.clear_model_details();
+                self.needs_load_quantizations = true;
+            }
+            FocusedPane::QuantizationGroups => self.quant_list_state.select(Some(index)),
+            FocusedPane::QuantizationFiles => self.quant_file_list_state.select(Some(index)),
+            FocusedPane::ModelMetadata => self.related_state.select(Some(index)),
+            FocusedPane::FileTree => self.file_tree_state.select(Some(index)),
+        }
+    }
+
+    /// Move the selection in the focused list pane by `delta` rows, stopping at the ends
+    pub fn move_selection_by(&mut self, delta: isize) {
+        let current = self.focused_selection().unwrap_or(0);
+        self.select_in_focused_pane(current.saturating_add_signed(delta));
+    }
+
+    /// Half the visible height of the focused pane, the Ctrl+D / Ctrl+U step
+    pub fn half_page(&self) -> usize {
+        self.panel_areas
+            .iter()
+            .find(|(pane, _)| *pane == self.focused_pane)
+            .map(|(_, area)| area.height.saturating_sub(2) as usize / 2) // minus borders
+            .unwrap_or(0)
+            .max(1)
+    }
+
     /// Focus a specific pane and select first item if needed
     /// This is the core logic used by both toggle_focus() and mouse clicks
     pub fn focus_pane(&mut self, pane: FocusedPane) {
@@ -536,7 +754,11 @@ impl App {
                 }
             }
             FocusedPane::ModelMetadata => {
-                // Metadata pane has no selection state
+                // Related models - select first if available and none selected
+                if !self.related_models.read().is_empty() && self.related_state.selected().is_none()
+                {
+                    self.related_state.select(Some(0));
+                }
             }
             FocusedPane::FileTree => {
                 // File tree - select first if available and none selected
@@ -571,9 +793,12 @@ impl App {
                 }
             }
             ModelDisplayMode::Standard => {
-                // Standard mode: cycle Models → FileTree → Models (skip ModelMetadata - no actions)
+                // Standard mode: cycle Models → FileTree → Models, through
+                // ModelMetadata only when it lists related models
+                let has_related = !self.related_models.read().is_empty();
                 match self.focused_pane {
                     FocusedPane::Models => FocusedPane::FileTree,
+                    FocusedPane::FileTree if has_related => FocusedPane::ModelMetadata,
                     FocusedPane::FileTree => FocusedPane::Models,
                     // Fallback for QuantizationGroups/Files/ModelMetadata (shouldn't happen in Standard mode)
                     _ => FocusedPane::Models,
@@ -708,6 +933,32 @@ impl App {
         }
     }
 
+    /// Navigate to next related model in the metadata pane
+    pub fn next_related(&mut self) {
+        let len = self.related_models.read().len();
+        if len == 0 {
+            return;
+        }
+        let i = match self.related_state.selected() {
+            Some(i) if i < len - 1 => i + 1,
+            _ => 0,
+        };
+        self.related_state.select(Some(i));
+    }
+
+    /// Navigate to previous related model in the metadata pane
+    pub fn previous_related(&mut self) {
+        let len = self.related_models.read().len();
+        if len == 0 {
+            return;
+        }
+        let i = match self.related_state.selected() {
+            Some(i) if i > 0 => i - 1,
+            _ => len - 1,
+        };
+        self.related_state.select(Some(i));
+    }
+
     /// Modify focused filter field value
     pub fn modify_focused_filter(&mut self, delta: i32) {
         match self.focused_filter_field {
@@ -760,6 +1011,48 @@ impl App {
                 };
                 self.filter_min_likes = steps[new_idx];
             }
+            3 => {
+                // Size: any, ≤8B, 8B–34B, 34B–70B, ≥70B
+                let steps = ParamRange::STEPS;
+                let current_idx = steps
+                    .iter()
+                    .position(|&x| x == self.filter_params)
+                    .unwrap_or(0);
+                let new_idx = if delta > 0 {
+                    (current_idx + 1).min(steps.len() - 1)
+                } else {
+                    current_idx.saturating_sub(1)
+                };
+                self.filter_params = steps[new_idx];
+            }
+            4 => {
+                // Access: all, ungated, public, open
+                let steps = AccessFilter::STEPS;
+                let current_idx = steps
+                    .iter()
+                    .position(|&x| x == self.filter_access)
+                    .unwrap_or(0);
+                let new_idx = if delta > 0 {
+                    (current_idx + 1).min(steps.len() - 1)
+                } else {
+                    current_idx.saturating_sub(1)
+                };
+                self.filter_access = steps[new_idx];
+            }
+            5 => {
+                // Content: all, safe
+                let steps = ContentFilter::STEPS;
+                let current_idx = steps
+                    .iter()
+                    .position(|&x| x == self.filter_content)
+                    .unwrap_or(0);
+                let new_idx = if delta > 0 {
+                    (current_idx + 1).min(steps.len() - 1)
+                } else {
+                    current_idx.saturating_sub(1)
+                };
+                self.filter_content = steps[new_idx];
+            }
             _ => {}
         }
 
@@ -770,7 +1063,7 @@ impl App {
 
     /// Check if applying a preset would change the current settings
     /// Returns true if the preset settings differ from current settings
-    fn would_change_settings(&self, preset: crate::models::FilterPreset) -> bool {
+    pub fn would_change_settings(&self, preset: crate::models::FilterPreset) -> bool {
         use crate::models::FilterPreset;
 
         let (target_sort_field, target_sort_direction, target_min_downloads, target_min_likes) =
@@ -789,6 +1082,7 @@ impl App {
             || self.sort_direction != target_sort_direction
             || self.filter_min_downloads != target_min_downloads
             || self.filter_min_likes != target_min_likes
+            || !self.filter_params.is_any()
     }
 
     /// Apply a filter preset
@@ -802,6 +1096,7 @@ impl App {
                 self.sort_direction = SortDirection::Descending;
                 self.filter_min_downloads = 0;
                 self.filter_min_likes = 0;
+                self.filter_params = ParamRange::default();
                 *self.status.write() = "Preset: No Filters".to_string();
             }
             FilterPreset::Popular => {
@@ -810,6 +1105,7 @@ impl App {
                 self.sort_direction = SortDirection::Descending;
                 self.filter_min_downloads = 10_000;
                 self.filter_min_likes = 100;
+                self.filter_params = ParamRange::default();
                 *self.status.write() =
                     "Preset: Popular (10k+ downloads, 100+ likes)".to_string();
             }
@@ -819,6 +1115,7 @@ impl App {
                 self.sort_direction = SortDirection::Descending;
                 self.filter_min_downloads = 0;
                 self.filter_min_likes = 1_000;
+                self.filter_params = ParamRange::default();
                 *self.status.write() = "Preset: Highly Rated (1k+ likes)".to_string();
             }
             FilterPreset::Recent => {
@@ -827,6 +1124,7 @@ impl App {
                 self.sort_direction = SortDirection::Descending;
                 self.filter_min_downloads = 0;
                 self.filter_min_likes = 0;
+                self.filter_params = ParamRange::default();
                 *self.status.write() = "Preset: Recent".to_string();
             }
         }
@@ -842,6 +1140,9 @@ impl App {
         self.options.default_sort_direction = self.sort_direction;
         self.options.default_min_downloads = self.filter_min_downloads;
         self.options.default_min_likes = self.filter_min_likes;
+        self.options.default_params = self.filter_params;
+        self.options.default_access = self.filter_access;
+        self.options.default_content = self.filter_content;
 
         if let Err(e) = crate::config::save_config(&self.options) {
             *self.status.write() = format!("Failed to save filter settings: {}", e);
@@ -935,6 +1236,127 @@ impl App {
                     .clamp(50, 500) as usize;
                 self.options.verification_update_interval = new;
             }
+            16 => {
+                // low_priority_verification - toggle with +/-
+                self.options.low_priority_verification = !self.options.low_priority_verification;
+            }
+            17 => {
+                // api_cache_enabled - toggle with +/-
+                self.options.api_cache_enabled = !self.options.api_cache_enabled;
+            }
+            18 => {
+                // api_cache_ttl_minutes (1-1440, step 5)
+                let new = (self.options.api_cache_ttl_minutes as i64 + delta as i64 * 5)
+                    .clamp(1, 1440) as u64;
+                self.options.api_cache_ttl_minutes = new;
+            }
+            19 => {
+                // api_cache_max_size_mb (10-2000, step 10)
+                let new = (self.options.api_cache_max_size_mb as i64 + delta as i64 * 10)
+                    .clamp(10, 2000) as u64;
+                self.options.api_cache_max_size_mb = new;
+            }
+            20 => {
+                // auto_refresh_enabled - toggle with +/-
+                self.options.auto_refresh_enabled = !self.options.auto_refresh_enabled;
+                self.last_refresh_time = std::time::Instant::now();
+            }
+            21 => {
+                // auto_refresh_interval_minutes (1-120, step 1)
+                let new = (self.options.auto_refresh_interval_minutes as i64 + delta as i64)
+                    .clamp(1, 120) as u64;
+                self.options.auto_refresh_interval_minutes = new;
+            }
+            22 => {
+                // restore_session - toggle with +/-
+                self.options.restore_session = !self.options.restore_session;
+            }
+            23 => {
+                // quant_subdirectory - toggle with +/-
+                self.options.quant_subdirectory = !self.options.quant_subdirectory;
+            }
+            24 => {
+                // require_license_confirmation - toggle with +/-
+                self.options.require_license_confirmation =
+                    !self.options.require_license_confirmation;
+            }
+            25 => {
+                // recent_gguf_days (1-30, step 1)
+                let new = (self.options.recent_gguf_days as i64 + delta as i64).clamp(1, 30) as u64;
+                self.options.recent_gguf_days = new;
+            }
+            26 => {
+                // search_page_size (10-1000, step 10)
+                let new = (self.options.search_page_size as i64 + delta as i64 * 10).clamp(10, 1000)
+                    as usize;
+                self.options.search_page_size = new;
+            }
+            27 => {
+                // search_max_results (50-5000, step 50)
+                let new = (self.options.search_max_results as i64 + delta as i64 * 50)
+                    .clamp(50, 5000) as usize;
+                self.options.search_max_results = new;
+            }
+            28 => {
+                // show_repo_sizes - toggle with +/-, looking up the listed models when enabled
+                self.options.show_repo_sizes = !self.options.show_repo_sizes;
+                self.spawn_load_repo_sizes();
+            }
+            29 => {
+                // auto_resume - toggle with +/-
+                self.options.auto_resume = !self.options.auto_resume;
+            }
+            30 => {
+                // conservative_networking - toggle with +/-; overrides the download settings
+                self.options.conservative_networking = !self.options.conservative_networking;
+            }
+            31 => {
+                // adaptive_threads - toggle with +/-
+                self.options.adaptive_threads = !self.options.adaptive_threads;
+            }
+            32 => {
+                // max_connections (0 = unlimited, up to 128)
+                let new = (self.options.max_connections as i32 + delta).clamp(0, 128) as usize;
+                self.options.max_connections = new;
+            }
+            33 => {
+                // extract_archives - toggle with +/-
+                self.options.extract_archives = !self.options.extract_archives;
+            }
+            34 => {} // convert_script - use Enter to edit
+            35 => {} // quantize_binary - use Enter to edit
+            36 => {
+                // convert_quant_type - cycle through convert::QUANT_TYPES
+                let types = crate::convert::QUANT_TYPES;
+                let current = types
+                    .iter()
+                    .position(|t| *t == self.options.convert_quant_type)
+                    .unwrap_or(0) as i32;
+                let next = (current + delta).rem_euclid(types.len() as i32) as usize;
+                self.options.convert_quant_type = types[next].to_string();
+            }
+            37 => {
+                // ascii_only - toggle with +/-
+                self.options.ascii_only = !self.options.ascii_only;
+            }
+            38 => {
+                // no_download - toggle with +/- (--no-download keeps the lock on)
+                self.options.no_download = !self.options.no_download;
+            }
+            39 => {
+                // identical_files - cycle link / copy / download with +/-
+                self.options.identical_files = self.options.identical_files.cycle(delta > 0);
+            }
+            40 => {
+                // blob_store - toggle with +/-; only affects new downloads
+                self.options.blob_store = !self.options.blob_store;
+            }
+            41 => {
+                // trash_retention_days (0-365, step 1)
+                let new =
+                    (self.options.trash_retention_days as i64 + delta as i64).clamp(0, 365) as u64;
+                self.options.trash_retention_days = new;
+            }
             _ => {}
         }
 
@@ -0,0 +1,112 @@
+use super::state::App;
+use crate::ignorelist::{self, IgnoredEntry};
+use crate::models::*;
+
+impl App {
+    /// Hide the selected model (or with `author`, everything by its author)
+    /// from this and every later search
+    pub fn hide_selected(&mut self, author: bool) {
+        let Some(model_id) = self
+            .list_state
+            .selected()
+            .and_then(|idx| self.models.read().get(idx).map(|m| m.id.clone()))
+        else {
+            *self.error.write() = Some("No model selected".to_string());
+            return;
+        };
+        let entry = if author {
+            IgnoredEntry::Author(ignorelist::author_of(&model_id).to_string())
+        } else {
+            IgnoredEntry::Model(model_id)
+        };
+
+        let label = entry.label();
+        let mut hidden = ignorelist::IgnoreList::default();
+        hidden.add(entry.clone());
+        match ignorelist::ignore(entry) {
+            Ok(_) => {
+                let before = self.models.read().len();
+                self.models.write().retain(|m| !hidden.hides(&m.id));
+                let removed = before - self.models.read().len();
+                // Cached searches would bring the model back otherwise
+                for results in self.api_cache.write().searches.values_mut() {
+                    results.retain(|m| !hidden.hides(&m.id));
+                }
+                self.reselect_after_hiding();
+                *self.status.write() = format!(
+                    "Hid {} ({} result{}) - open the hidden list from ':' to show it again",
+                    label,
+                    removed,
+                    if removed == 1 { "" } else { "s" }
+                );
+            }
+            Err(e) => {
+                *self.error.write() = Some(format!("Failed to update ignore list: {}", e));
+            }
+        }
+    }
+
+    /// Keep the selection on the row that moved into the hidden model's place
+    fn reselect_after_hiding(&mut self) {
+        let len = self.models.read().len();
+        self.clear_model_details();
+        if len == 0 {
+            self.list_state.select(None);
+            return;
+        }
+        let idx = self.list_state.selected().unwrap_or(0).min(len - 1);
+        self.list_state.select(Some(idx));
+        self.needs_load_quantizations = true;
+    }
+
+    /// Open the list of hidden models and authors
+    pub fn show_ignore_list(&mut self) {
+        let entries = ignorelist::load_ignore_list().entries();
+        if entries.is_empty() {
+            *self.status.write() =
+                "Nothing is hidden - press i to hide a model, I to hide its author".to_string();
+            return;
+        }
+        self.ignore_list_view = entries;
+        self.ignore_list_selected = 0;
+        self.ignore_list_changed = false;
+        self.popup_mode = PopupMode::IgnoreList;
+    }
+
+    /// Show the selected entry of the hidden list in searches again
+    pub fn unhide_selected_entry(&mut self) {
+        let Some(entry) = self
+            .ignore_list_view
+            .get(self.ignore_list_selected)
+            .cloned()
+        else {
+            return;
+        };
+        match ignorelist::unignore(&entry) {
+            Ok(_) => {
+                self.ignore_list_view.remove(self.ignore_list_selected);
+                self.ignore_list_selected = self
+                    .ignore_list_selected
+                    .min(self.ignore_list_view.len().saturating_sub(1));
+                self.ignore_list_changed = true;
+                *self.status.write() = format!("{} is no longer hidden", entry.label());
+            }
+            Err(e) => {
+                *self.error.write() = Some(format!("Failed to update ignore list: {}", e));
+            }
+        }
+    }
+
+    /// Close the hidden list
+    pub fn close_ignore_list(&mut self) {
+        self.popup_mode = PopupMode::None;
+        self.ignore_list_view.clear();
+        if self.ignore_list_changed {
+            self.ignore_list_changed = false;
+            // Cached results were filtered with the old list
+            self.api_cache.write().searches.clear();
+            *self.status.write() =
+                "Unhidden models show up from the next search (F5 refreshes)".to_string();
+        }
+    }
+}
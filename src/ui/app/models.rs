@@ -1,6 +1,20 @@
 use super::state::App;
-use crate::api::{build_file_tree, fetch_model_files, fetch_model_metadata, has_gguf_files};
-use crate::models::ModelDisplayMode;
+use crate::api::{
+    build_file_tree, fetch_author_models, fetch_author_profile, fetch_base_model_chain,
+    fetch_branch_groups, fetch_gguf_header_summary, fetch_gguf_prompt_format, fetch_model_files,
+    fetch_model_metadata, fetch_recent_gguf_models, fetch_related_models,
+    fetch_tokenizer_prompt_format, fetch_username, group_by_base_model, has_gguf_files,
+    main_branch_group, ApiError,
+};
+use crate::models::{
+    ApiCache, FileTreeNode, FocusedPane, ModelDisplayMode, PopupMode, QuantizationGroup,
+    QuantizationInfo,
+};
+use futures::StreamExt;
+use parking_lot::RwLock;
+use std::collections::HashSet;
+use std::sync::Arc;
+use tokio_util::sync::CancellationToken;
 
 impl App {
     /// Execute search query and load results
@@ -11,6 +25,14 @@ impl App {
             return;
         }
 
+        self.recent_gguf_days = None;
+
+        // `#query` searches local tags and notes instead of the Hub
+        if let Some(local_query) = query.strip_prefix('#') {
+            self.search_local_notes(local_query);
+            return;
+        }
+
         *self.loading.write() = true;
         *self.error.write() = None;
 
@@ -20,6 +42,16 @@ impl App {
         let sort_direction = self.sort_direction;
         let min_downloads = self.filter_min_downloads;
         let min_likes = self.filter_min_likes;
+        let params = self.filter_params;
+        let access = self.filter_access;
+        let content = self.filter_content;
+        let full_text = self.search_full_text;
+        let limits = self.options.search_limits();
+        let filtered = min_downloads > 0
+            || min_likes > 0
+            || !params.is_any()
+            || !access.is_any()
+            || !content.is_any();
 
         // Create search key for caching
         let search_key = crate::models::SearchKey {
@@ -28,8 +60,18 @@ impl App {
             sort_direction,
             min_downloads,
             min_likes,
+            params,
+            access,
+            content,
+            full_text,
+            limits,
         };
 
+        // A new search starts a fresh refresh cycle
+        self.last_search_key = Some(search_key.clone());
+        self.new_model_ids.clear();
+        self.last_refresh_time = std::time::Instant::now();
+
         // Step 1: Check cache with read lock (fast path)
         let cached_results = {
             let cache = self.api_cache.read();
@@ -57,8 +99,8 @@ impl App {
             *self.loading.write() = false;
             self.list_state.select(Some(0));
 
-            let filter_status = if min_downloads > 0 || min_likes > 0 {
-                " (cached, filtered from 100)".to_string()
+            let filter_status = if filtered {
+                " (cached, filtered)".to_string()
             } else if has_exact_match {
                 " (cached, exact match)".to_string()
             } else {
@@ -71,20 +113,41 @@ impl App {
 
             if has_results {
                 self.needs_load_quantizations = true;
+                self.spawn_load_repo_sizes();
             }
             return;
         }
 
         // Step 2: Fetch from API (if not cached)
-        let results = crate::api::fetch_models_filtered(
-            &query,
-            sort_field,
-            sort_direction,
-            min_downloads,
-            min_likes,
-            token,
-        )
-        .await;
+        let results = if full_text {
+            crate::api::fetch_models_full_text(
+                &query,
+                sort_field,
+                sort_direction,
+                min_downloads,
+                min_likes,
+                params,
+                access,
+                content,
+                token,
+                true,
+            )
+            .await
+        } else {
+            crate::api::fetch_models_filtered(
+                &query,
+                sort_field,
+                sort_direction,
+                min_downloads,
+                min_likes,
+                params,
+                access,
+                content,
+                limits,
+                token,
+            )
+            .await
+        };
 
         match results {
             Ok(results) => {
@@ -122,8 +185,8 @@ impl App {
                 *self.loading.write() = false;
                 self.list_state.select(Some(0));
 
-                let filter_status = if min_downloads > 0 || min_likes > 0 {
-                    " (filtered from 100)".to_string()
+                let filter_status = if filtered {
+                    " (filtered)".to_string()
                 } else if has_exact_match {
                     " (exact match)".to_string()
                 } else {
@@ -136,6 +199,7 @@ impl App {
 
                 if has_results {
                     self.needs_load_quantizations = true;
+                    self.spawn_load_repo_sizes();
                 }
             }
             Err(e) => {
@@ -146,16 +210,288 @@ impl App {
         }
     }
 
+    /// List the models of the token's owner, including private ones
+    pub async fn show_my_models(&mut self) {
+        let Some(token) = self.options.hf_token.clone() else {
+            *self.error.write() =
+                Some("My models needs a HuggingFace token - set one in Options (o)".to_string());
+            return;
+        };
+
+        *self.error.write() = None;
+        let result = async {
+            let user = fetch_username(Some(&token)).await?;
+            let models = fetch_author_models(&user, Some(&token)).await?;
+            Ok::<_, ApiError>((user, models))
+        }
+        .await;
+
+        let (user, results) = match result {
+            Ok(found) => found,
+            Err(e) => {
+                *self.error.write() = Some(format!("Failed to list your models: {}", e));
+                return;
+            }
+        };
+
+        // Not a search: F5 has nothing to re-run
+        self.last_search_key = None;
+        self.new_model_ids.clear();
+        self.recent_gguf_days = None;
+
+        let private = results.iter().filter(|m| m.private).count();
+        *self.status.write() = format!(
+            "{} model{} by {} ({} private)",
+            results.len(),
+            if results.len() == 1 { "" } else { "s" },
+            user,
+            private
+        );
+        let has_results = !results.is_empty();
+        *self.models.write() = results;
+        self.list_state.select(if has_results { Some(0) } else { None });
+        self.focused_pane = FocusedPane::Models;
+        self.needs_load_quantizations = has_results;
+    }
+
+    /// Open the profile popup of the selected model's author ('A')
+    pub async fn show_author_profile(&mut self) {
+        let author = {
+            let models = self.models.read();
+            let Some(model) = self.list_state.selected().and_then(|i| models.get(i)) else {
+                return;
+            };
+            model.author.clone().or_else(|| {
+                model
+                    .id
+                    .split_once('/')
+                    .map(|(author, _)| author.to_string())
+            })
+        };
+        let Some(author) = author else {
+            *self.error.write() = Some("This model has no author".to_string());
+            return;
+        };
+
+        *self.error.write() = None;
+        match fetch_author_profile(&author, self.options.hf_token.as_ref()).await {
+            Ok(profile) => {
+                self.author_profile = Some(profile);
+                self.popup_mode = PopupMode::AuthorProfile;
+            }
+            Err(e) => {
+                *self.error.write() = Some(format!("Failed to load profile of {}: {}", author, e));
+            }
+        }
+    }
+
+    /// Replace the results with every model of the profile popup's author (Enter)
+    pub fn show_author_models(&mut self) {
+        self.popup_mode = PopupMode::None;
+        let Some(profile) = self.author_profile.take() else {
+            return;
+        };
+
+        // Not a search: F5 has nothing to re-run
+        self.last_search_key = None;
+        self.new_model_ids.clear();
+        self.recent_gguf_days = None;
+
+        let results = profile.models;
+        *self.status.write() = format!(
+            "{} model{} by {}",
+            results.len(),
+            if results.len() == 1 { "" } else { "s" },
+            profile.name
+        );
+        let has_results = !results.is_empty();
+        *self.models.write() = results;
+        self.list_state.select(if has_results { Some(0) } else { None });
+        self.focused_pane = FocusedPane::Models;
+        self.needs_load_quantizations = has_results;
+    }
+
+    /// Show the GGUF repositories created in the last `recent_gguf_days`
+    /// days, newest first and grouped by base model ('N')
+    pub async fn show_recent_gguf(&mut self) {
+        let days = self.options.recent_gguf_days;
+        *self.error.write() = None;
+
+        let results = match fetch_recent_gguf_models(days, self.options.hf_token.as_ref()).await {
+            Ok(results) => results,
+            Err(e) => {
+                *self.error.write() = Some(format!("Failed to list new GGUF models: {}", e));
+                return;
+            }
+        };
+        let groups = group_by_base_model(results);
+        let group_count = groups.len();
+        let results: Vec<_> = groups.into_iter().flat_map(|(_, models)| models).collect();
+
+        // Not a search: F5 has nothing to re-run
+        self.last_search_key = None;
+        self.new_model_ids.clear();
+        self.recent_gguf_days = Some(days);
+
+        *self.status.write() = format!(
+            "{} new GGUF repo{} in the last {} day{} ({} base model{})",
+            results.len(),
+            if results.len() == 1 { "" } else { "s" },
+            days,
+            if days == 1 { "" } else { "s" },
+            group_count,
+            if group_count == 1 { "" } else { "s" }
+        );
+        let has_results = !results.is_empty();
+        *self.models.write() = results;
+        self.list_state.select(if has_results { Some(0) } else { None });
+        self.focused_pane = FocusedPane::Models;
+        self.needs_load_quantizations = has_results;
+    }
+
+    /// Re-run the current search against the API and highlight models that
+    /// were not in the previous results (F5 / auto-refresh)
+    pub async fn refresh_search(&mut self) {
+        self.last_refresh_time = std::time::Instant::now();
+
+        let Some(search_key) = self.last_search_key.clone() else {
+            *self.status.write() = "Nothing to refresh - search for models first".to_string();
+            return;
+        };
+
+        let results = if search_key.full_text {
+            crate::api::fetch_models_full_text(
+                &search_key.query,
+                search_key.sort_field,
+                search_key.sort_direction,
+                search_key.min_downloads,
+                search_key.min_likes,
+                search_key.params,
+                search_key.access,
+                search_key.content,
+                self.options.hf_token.as_ref(),
+                false,
+            )
+            .await
+        } else {
+            crate::api::refresh_models_filtered(
+                &search_key.query,
+                search_key.sort_field,
+                search_key.sort_direction,
+                search_key.min_downloads,
+                search_key.min_likes,
+                search_key.params,
+                search_key.access,
+                search_key.content,
+                search_key.limits,
+                self.options.hf_token.as_ref(),
+            )
+            .await
+        };
+
+        let mut results = match results {
+            Ok(results) => results,
+            Err(e) => {
+                *self.status.write() = format!("Refresh failed: {}", e);
+                return;
+            }
+        };
+
+        // Same exact-match narrowing as search_models
+        if search_key.query.contains('/') {
+            let query = search_key.query.to_lowercase();
+            if let Some(idx) = results.iter().position(|m| m.id.to_lowercase() == query) {
+                results = vec![results.swap_remove(idx)];
+            }
+        }
+
+        // Diff against what is currently shown, keeping the selected model selected
+        let (new_ids, selected_id) = {
+            let models = self.models.read();
+            let previous: HashSet<&str> = models.iter().map(|m| m.id.as_str()).collect();
+            let new_ids: HashSet<String> = results
+                .iter()
+                .filter(|m| !previous.contains(m.id.as_str()))
+                .map(|m| m.id.clone())
+                .collect();
+            let selected_id = self
+                .list_state
+                .selected()
+                .and_then(|i| models.get(i))
+                .map(|m| m.id.clone());
+            (new_ids, selected_id)
+        };
+
+        let new_selection = selected_id
+            .as_ref()
+            .and_then(|id| results.iter().position(|m| &m.id == id));
+
+        self.api_cache
+            .write()
+            .searches
+            .insert(search_key, results.clone());
+        let has_results = !results.is_empty();
+        *self.models.write() = results;
+
+        match new_selection {
+            Some(idx) => self.list_state.select(Some(idx)),
+            None => {
+                // Previously selected model is gone; reload details for the new first entry
+                self.list_state.select(Some(0));
+                self.needs_load_quantizations = has_results;
+            }
+        }
+
+        *self.status.write() = if new_ids.is_empty() {
+            "Refreshed: no new models since last refresh".to_string()
+        } else {
+            format!("Refreshed: {} new model(s) since last refresh", new_ids.len())
+        };
+        self.new_model_ids = new_ids;
+        self.spawn_load_repo_sizes();
+    }
+
+    /// Look up the repository size of each listed model in the background
+    /// (`show_repo_sizes` option), filling them in as they arrive
+    pub fn spawn_load_repo_sizes(&mut self) {
+        use futures::StreamExt;
+
+        // Lookups for the previous listing are no longer needed
+        self.load_sizes_cancel.cancel();
+        self.load_sizes_cancel = CancellationToken::new();
+        if !self.options.show_repo_sizes {
+            return;
+        }
+        let ids: Vec<String> = self
+            .models
+            .read()
+            .iter()
+            .filter(|m| m.used_storage.is_none())
+            .map(|m| m.id.clone())
+            .collect();
+        if ids.is_empty() {
+            return;
+        }
+        let cancel = self.load_sizes_cancel.clone();
+        let models = self.models.clone();
+        let token = self.options.hf_token.clone();
+
+        tokio::spawn(async move {
+            let mut sizes = std::pin::pin!(crate::api::repo_sizes(ids, token.as_ref()));
+            while let Some(Some((id, size))) = cancel.run_until_cancelled(sizes.next()).await {
+                if let Some(model) = models.write().iter_mut().find(|m| m.id == id) {
+                    model.used_storage = size;
+                }
+            }
+        });
+    }
+
     /// Display detailed model information in status bar
     pub async fn show_model_details(&mut self) {
         let models = self.models.read();
         if let Some(selected) = self.list_state.selected() {
             if selected < models.len() {
-                let model = &models[selected];
-                *self.selection_info.write() = format!(
-                    "Selected: {} | URL: https://huggingface.co/{}",
-                    model.id, model.id
-                );
+                *self.selection_info.write() = models[selected].selection_summary();
             }
         }
     }
@@ -178,6 +514,145 @@ impl App {
         }
     }
 
+    /// Mark the selected quantization group for comparison, or compare it with
+    /// the previously marked one ('c' in the quantization panes)
+    pub fn compare_selected_quantization(&mut self) {
+        let Some(selected) = self.quant_list_state.selected() else {
+            return;
+        };
+        let quantizations = self.quantizations.read().clone();
+        let Some(group) = quantizations.get(selected) else {
+            return;
+        };
+
+        let marked = self
+            .compare_quant
+            .take()
+            .and_then(|quant_type| quantizations.iter().find(|g| g.quant_type == quant_type));
+
+        match marked {
+            Some(first) if first.quant_type != group.quant_type => {
+                self.quant_diff = Some((first.clone(), group.clone()));
+                self.popup_mode = crate::models::PopupMode::QuantDiff;
+            }
+            _ => {
+                self.compare_quant = Some(group.quant_type.clone());
+                *self.status.write() = format!(
+                    "Marked {} for comparison - select another quantization and press 'c'",
+                    group.quant_type
+                );
+            }
+        }
+    }
+
+    /// Show the chat template and special tokens of the selected model ('t')
+    ///
+    /// GGUF models are read from the metadata at the start of the selected
+    /// quantization's first file (just that prefix is downloaded), other
+    /// repositories from `tokenizer_config.json`.
+    pub async fn show_prompt_format(&mut self) {
+        let Some(model_id) = self
+            .list_state
+            .selected()
+            .and_then(|idx| self.models.read().get(idx).map(|m| m.id.clone()))
+        else {
+            *self.error.write() = Some("No model selected".to_string());
+            return;
+        };
+        let token = self.options.hf_token.clone();
+
+        let result = if *self.display_mode.read() == ModelDisplayMode::Gguf {
+            // The selected quantization, or the first one from the Models pane
+            let file = {
+                let quantizations = self.quantizations.read();
+                let group = self
+                    .quant_list_state
+                    .selected()
+                    .and_then(|idx| quantizations.get(idx))
+                    .or_else(|| quantizations.first());
+                // Split files keep the metadata in the first part
+                group.and_then(|g| {
+                    g.files
+                        .iter()
+                        .min_by(|a, b| a.filename.cmp(&b.filename))
+                        .cloned()
+                })
+            };
+            let Some(file) = file else {
+                *self.error.write() =
+                    Some("No GGUF file to read the chat template from".to_string());
+                return;
+            };
+            *self.status.write() = format!("Reading GGUF metadata of {}...", file.filename);
+            fetch_gguf_prompt_format(
+                &model_id,
+                file.revision.as_deref(),
+                &file.filename,
+                token.as_ref(),
+            )
+            .await
+        } else {
+            fetch_tokenizer_prompt_format(&model_id, token.as_ref()).await
+        };
+
+        match result {
+            Ok(format) => {
+                *self.status.write() = format!("Prompt format of {}", model_id);
+                self.prompt_format = Some(format);
+                self.prompt_format_scroll = 0;
+                self.popup_mode = PopupMode::PromptFormat;
+            }
+            Err(e) => {
+                *self.error.write() = Some(format!("Failed to read the prompt format: {}", e));
+            }
+        }
+    }
+
+    /// Mark or unmark the selected quantization group for a combined download
+    /// (Space in the Quantization Types pane)
+    pub fn toggle_quant_mark(&mut self) {
+        let Some(selected) = self.quant_list_state.selected() else {
+            return;
+        };
+        let Some(quant_type) = self
+            .quantizations
+            .read()
+            .get(selected)
+            .map(|g| g.quant_type.clone())
+        else {
+            return;
+        };
+
+        if !self.marked_quants.remove(&quant_type) {
+            self.marked_quants.insert(quant_type);
+        }
+
+        let marked = self.marked_quant_groups();
+        *self.status.write() = if marked.is_empty() {
+            "No quantizations marked".to_string()
+        } else {
+            format!(
+                "Marked {} ({}) - press 'd' to download together",
+                marked
+                    .iter()
+                    .map(|g| g.quant_type.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                crate::utils::format_size(marked.iter().map(|g| g.total_size).sum())
+            )
+        };
+    }
+
+    /// Marked quantization groups, in list order
+    pub fn marked_quant_groups(&self) -> Vec<crate::models::QuantizationGroup> {
+        self.quantizations
+            .read()
+            .iter()
+            .filter(|g| self.marked_quants.contains(&g.quant_type))
+            .cloned()
+            .collect()
+    }
+
     pub async fn show_file_details(&mut self) {
         if let Some(group_idx) = self.quant_list_state.selected() {
             if let Some(file_idx) = self.quant_file_list_state.selected() {
@@ -215,16 +690,26 @@ impl App {
 
         // Immediate UI feedback (synchronous)
         *self.loading_quants.write() = true;
+        self.related_models.write().clear();
+        self.related_state.select(None);
+
+        // Abort the load for the previously selected model so its results
+        // can never overwrite the panes of the model selected now
+        self.load_quants_cancel.cancel();
+        self.load_quants_cancel = CancellationToken::new();
+        let cancel = self.load_quants_cancel.clone();
 
         // Clone Arcs for background task
         let quantizations = self.quantizations.clone();
         let api_cache = self.api_cache.clone();
         let model_metadata = self.model_metadata.clone();
         let file_tree = self.file_tree.clone();
+        let related_models = self.related_models.clone();
         let loading_quants = self.loading_quants.clone();
         let error = self.error.clone();
         let display_mode = self.display_mode.clone();
         let token = self.options.hf_token.clone();
+        let status_tx = self.status_tx.clone();
 
         // Spawn background task (non-blocking)
         tokio::spawn(async move {
@@ -238,7 +723,13 @@ impl App {
                 meta // Use cached metadata
             } else {
                 // Fetch and cache metadata
-                match fetch_model_metadata(&model_id, token.as_ref()).await {
+                let Some(result) = cancel
+                    .run_until_cancelled(fetch_model_metadata(&model_id, token.as_ref()))
+                    .await
+                else {
+                    return; // Selection moved on
+                };
+                match result {
                     Ok(meta) => {
                         let mut cache = api_cache.write();
                         cache.metadata.insert(model_id.clone(), meta.clone());
@@ -246,6 +737,10 @@ impl App {
                     }
                     Err(e) => {
                         *loading_quants.write() = false;
+                        if e.is_auth_error() {
+                            // Gated/private model: offer the token popup
+                            let _ = status_tx.send(format!("AUTH_ERROR:{}", model_id));
+                        }
                         *error.write() =
                             Some(format!("Failed to fetch model metadata: {}", e));
 
@@ -259,6 +754,10 @@ impl App {
                 }
             };
 
+            if cancel.is_cancelled() {
+                return;
+            }
+
             // Now process based on metadata
             if true {
                 // Placeholder to keep structure
@@ -273,17 +772,31 @@ impl App {
                     };
 
                     if let Some(cached_groups) = cached_result {
-                        let mut quants_lock = quantizations.write();
-                        *quants_lock = cached_groups;
+                        *quantizations.write() = cached_groups;
                         *loading_quants.write() = false;
 
                         // Reset file tree state
                         *model_metadata.write() = None;
                         *file_tree.write() = None;
+
+                        cancel
+                            .run_until_cancelled(load_gguf_headers(
+                                &model_id,
+                                &quantizations,
+                                &api_cache,
+                                token.as_ref(),
+                            ))
+                            .await;
                         return;
                     }
 
-                    match fetch_model_files(&model_id, token.as_ref()).await {
+                    let Some(result) = cancel
+                        .run_until_cancelled(fetch_model_files(&model_id, token.as_ref()))
+                        .await
+                    else {
+                        return; // Selection moved on
+                    };
+                    match result {
                         Ok(quants) => {
                             // Double-check and cache using Entry API
                             let quants_to_store = {
@@ -297,13 +810,21 @@ impl App {
                                 }
                             };
 
-                            let mut quants_lock = quantizations.write();
-                            *quants_lock = quants_to_store;
+                            *quantizations.write() = quants_to_store;
                             *loading_quants.write() = false;
 
                             // Reset file tree state
                             *model_metadata.write() = None;
                             *file_tree.write() = None;
+
+                            cancel
+                                .run_until_cancelled(load_gguf_headers(
+                                    &model_id,
+                                    &quantizations,
+                                    &api_cache,
+                                    token.as_ref(),
+                                ))
+                                .await;
                         }
                         Err(_) => {
                             *loading_quants.write() = false;
@@ -312,13 +833,48 @@ impl App {
                         }
                     }
                 } else {
+                    // Quants kept on branches (typical for EXL2) are listed like
+                    // GGUF quants, next to a group with the files of main
+                    let cached_groups = {
+                        let cache = api_cache.read();
+                        cache.quantizations.get(&model_id).cloned()
+                    };
+                    let branch_groups = match cached_groups {
+                        Some(groups) => groups,
+                        None => {
+                            let Some(branches) = cancel
+                                .run_until_cancelled(fetch_branch_groups(&model_id, token.as_ref()))
+                                .await
+                            else {
+                                return; // Selection moved on
+                            };
+                            let groups: Vec<_> = if branches.is_empty() {
+                                Vec::new()
+                            } else {
+                                std::iter::once(main_branch_group(&metadata))
+                                    .chain(branches)
+                                    .collect()
+                            };
+                            let mut cache = api_cache.write();
+                            cache.quantizations.insert(model_id.clone(), groups.clone());
+                            groups
+                        }
+                    };
+
+                    if !branch_groups.is_empty() {
+                        *display_mode.write() = ModelDisplayMode::Gguf;
+                        *quantizations.write() = branch_groups;
+                        *loading_quants.write() = false;
+                        *model_metadata.write() = None;
+                        *file_tree.write() = None;
+                        return;
+                    }
+
                     // Standard mode: show metadata + file tree
                     *display_mode.write() = ModelDisplayMode::Standard;
 
                     // Clear quantizations
-                    let mut quants_lock = quantizations.write();
-                    quants_lock.clear();
-                    drop(quants_lock);
+                    quantizations.write().clear();
 
                     // Check file tree cache with read lock
                     let cached_tree = {
@@ -330,7 +886,8 @@ impl App {
                         tree // Use cached tree
                     } else {
                         // Build tree
-                        let tree = build_file_tree(metadata.siblings.clone());
+                        let tree =
+                            build_file_tree(metadata.siblings.clone(), &metadata.weight_shards);
 
                         // Double-check and cache using Entry API
                         let tree_to_store = {
@@ -352,11 +909,172 @@ impl App {
                     *file_tree.write() = Some(tree_to_store);
 
                     *loading_quants.write() = false;
+
+                    // Related models for the metadata pane (best effort)
+                    let cached_related = {
+                        let cache = api_cache.read();
+                        cache.related.get(&model_id).cloned()
+                    };
+                    let related = match cached_related {
+                        Some(related) => related,
+                        None => {
+                            let Some(Ok(related)) = cancel
+                                .run_until_cancelled(fetch_related_models(
+                                    &metadata,
+                                    token.as_ref(),
+                                ))
+                                .await
+                            else {
+                                return;
+                            };
+                            let mut cache = api_cache.write();
+                            cache.related.insert(model_id.clone(), related.clone());
+                            related
+                        }
+                    };
+                    *related_models.write() = related;
+
+                    // Finetune lineage for the metadata pane, a request per
+                    // link, so only once the rest is shown
+                    let has_base = metadata
+                        .card_data
+                        .as_ref()
+                        .is_some_and(|card| card.base_model.is_some());
+                    if has_base && metadata.base_model_chain.is_empty() {
+                        let Some(chain) = cancel
+                            .run_until_cancelled(fetch_base_model_chain(&metadata, token.as_ref()))
+                            .await
+                        else {
+                            return;
+                        };
+                        if let Some(cached) = api_cache.write().metadata.get_mut(&model_id) {
+                            cached.base_model_chain = chain.clone();
+                        }
+                        if let Some(shown) = model_metadata.write().as_mut() {
+                            shown.base_model_chain = chain;
+                        }
+                    }
                 }
             }
         });
     }
 
+    /// Open the related model selected in the metadata pane (Enter)
+    pub fn open_related_model(&mut self) {
+        let selected = self
+            .related_state
+            .selected()
+            .and_then(|i| self.related_models.read().get(i).map(|m| m.id.clone()));
+        if let Some(model_id) = selected {
+            self.open_model_reference(&model_id);
+        }
+    }
+
+    /// Jump to a model given as a bare ID or huggingface.co URL (Open popup / CLI argument)
+    /// File URLs additionally select the file once the model has loaded
+    pub fn open_model_reference(&mut self, target: &str) {
+        let Some(reference) = crate::api::parse_model_reference(target) else {
+            *self.error.write() = Some(format!(
+                "Not a model ID or huggingface.co model URL: {}",
+                target.trim()
+            ));
+            return;
+        };
+
+        // An exact author/model query narrows the results to that model
+        self.input = tui_input::Input::default().with_value(reference.model_id.clone());
+        self.focused_pane = FocusedPane::Models;
+        self.clear_search_results();
+        self.needs_search_models = true;
+
+        *self.status.write() = format!("Opening {}...", reference.model_id);
+        self.pending_open_file = reference.file_path.is_some().then_some(reference);
+    }
+
+    /// Base model of the selected repository, if it is a LoRA/PEFT adapter
+    pub fn adapter_base_model(&self) -> Option<String> {
+        if *self.display_mode.read() != ModelDisplayMode::Standard {
+            return None;
+        }
+        self.model_metadata
+            .read()
+            .as_ref()
+            .and_then(|meta| meta.adapter_base_model())
+    }
+
+    /// Select the file of an opened file URL once its model has finished loading
+    /// GGUF files open the download popup; other files are highlighted in the file tree
+    pub fn select_pending_open_file(&mut self) {
+        let Some(reference) = self.pending_open_file.take() else {
+            return;
+        };
+        let Some(file_path) = reference.file_path else {
+            return;
+        };
+
+        let selected_id = self
+            .list_state
+            .selected()
+            .and_then(|idx| self.models.read().get(idx).map(|m| m.id.clone()));
+        if !selected_id.is_some_and(|id| id.eq_ignore_ascii_case(&reference.model_id)) {
+            *self.error.write() = Some(format!("Model '{}' not found", reference.model_id));
+            return;
+        }
+
+        let display_mode = *self.display_mode.read();
+        match display_mode {
+            ModelDisplayMode::Gguf => {
+                let position = self
+                    .quantizations
+                    .read()
+                    .iter()
+                    .enumerate()
+                    .find_map(|(group_idx, group)| {
+                        group
+                            .files
+                            .iter()
+                            .position(|f| f.filename == file_path)
+                            .map(|file_idx| (group_idx, file_idx))
+                    });
+
+                let Some((group_idx, file_idx)) = position else {
+                    *self.error.write() = Some(format!(
+                        "'{}' is not a GGUF file of {}",
+                        file_path, reference.model_id
+                    ));
+                    return;
+                };
+
+                self.quant_list_state.select(Some(group_idx));
+                self.quant_file_list_state.select(Some(file_idx));
+                self.focused_pane = FocusedPane::QuantizationFiles;
+                self.trigger_download();
+                *self.status.write() = format!("Download {} from {}", file_path, reference.model_id);
+            }
+            ModelDisplayMode::Standard => {
+                let mut tree = self.file_tree.read().clone();
+                let Some(tree) = tree.as_mut() else {
+                    return;
+                };
+
+                expand_to_path(tree, &file_path);
+                let flat = crate::ui::render::flatten_tree_for_navigation(tree);
+                let Some(idx) = flat.iter().position(|node| node.path == file_path) else {
+                    *self.error.write() = Some(format!(
+                        "File '{}' not found in {}",
+                        file_path, reference.model_id
+                    ));
+                    return;
+                };
+
+                *self.file_tree.write() = Some(tree.clone());
+                self.file_tree_state.select(Some(idx));
+                self.focused_pane = FocusedPane::FileTree;
+                *self.status.write() = format!("Opened {} in {}", file_path, reference.model_id);
+            }
+        }
+    }
+
     /// Clear model details immediately (for instant UI feedback during navigation)
     pub fn clear_model_details(&mut self) {
         // Clear quantizations (GGUF mode)
@@ -368,7 +1086,12 @@ impl App {
         futures::executor::block_on(async {
             *self.model_metadata.write() = None;
             *self.file_tree.write() = None;
+            self.related_models.write().clear();
         });
+        self.related_state.select(None);
+
+        // Marks refer to the previous model's quantizations
+        self.marked_quants.clear();
 
         // Set loading state
         *self.loading_quants.write() = true;
@@ -377,6 +1100,8 @@ impl App {
 
     /// Clear search results immediately (for instant UI feedback during search)
     pub fn clear_search_results(&mut self) {
+        self.compare_quant = None;
+
         // Clear models list
         futures::executor::block_on(async {
             self.models.write().clear();
@@ -392,7 +1117,7 @@ impl App {
 
     /// Pre-emptively load adjacent models into cache (1 before, 1 after current selection)
     /// Loads metadata, quantizations (GGUF), and file trees (Standard) with debouncing
-    pub fn prefetch_adjacent_models(&self) {
+    pub fn prefetch_adjacent_models(&mut self) {
         const PREFETCH_DEBOUNCE_MS: u128 = 1000; // Wait 1000ms before prefetching
 
         // Check debounce
@@ -442,11 +1167,16 @@ impl App {
             return;
         }
 
+        // Only the latest prefetch is useful; abort older ones still in flight
+        self.prefetch_cancel.cancel();
+        self.prefetch_cancel = CancellationToken::new();
+        let cancel = self.prefetch_cancel.clone();
+
         // Clone Arcs for background task
         let api_cache = self.api_cache.clone();
         let token = self.options.hf_token.clone();
 
-        // Spawn background prefetch task (fire-and-forget)
+        // Spawn background prefetch task (cancelled by the next prefetch)
         tokio::spawn(async move {
             for model_id in model_ids {
                 // Check metadata cache with read lock
@@ -459,7 +1189,13 @@ impl App {
                     meta // Use cached
                 } else {
                     // Fetch and cache metadata with double-check using Entry API
-                    let meta_to_store = match fetch_model_metadata(&model_id, token.as_ref()).await {
+                    let Some(result) = cancel
+                        .run_until_cancelled(fetch_model_metadata(&model_id, token.as_ref()))
+                        .await
+                    else {
+                        return;
+                    };
+                    let meta_to_store = match result {
                         Ok(meta) => {
                             let mut cache = api_cache.write();
                             match cache.metadata.entry(model_id.clone()) {
@@ -485,7 +1221,13 @@ impl App {
 
                     if !quants_cached {
                         // Fetch and cache quantizations with double-check using Entry API
-                        if let Ok(quants) = fetch_model_files(&model_id, token.as_ref()).await {
+                        let Some(result) = cancel
+                            .run_until_cancelled(fetch_model_files(&model_id, token.as_ref()))
+                            .await
+                        else {
+                            return;
+                        };
+                        if let Ok(quants) = result {
                             let mut cache = api_cache.write();
                             if matches!(cache.quantizations.entry(model_id.clone()), std::collections::hash_map::Entry::Vacant(_)) {
                                 cache.quantizations.insert(model_id.clone(), quants);
@@ -501,7 +1243,8 @@ impl App {
 
                     if !tree_cached {
                         // Build and cache file tree with double-check using Entry API
-                        let tree = build_file_tree(metadata.siblings.clone());
+                        let tree =
+                            build_file_tree(metadata.siblings.clone(), &metadata.weight_shards);
                         let mut cache = api_cache.write();
                         if matches!(cache.file_trees.entry(model_id.clone()), std::collections::hash_map::Entry::Vacant(_)) {
                             cache.file_trees.insert(model_id.clone(), tree);
@@ -512,3 +1255,79 @@ impl App {
         });
     }
 }
+
+/// Header reads running at once while filling in the quant list
+const HEADER_FETCH_CONCURRENCY: usize = 4;
+
+/// Read the header of each quant's first GGUF file and fill in the groups'
+/// architecture and context columns, keeping the cached list in step
+///
+/// Best-effort: a quant whose header cannot be read keeps empty columns.
+async fn load_gguf_headers(
+    model_id: &str,
+    quantizations: &Arc<RwLock<Vec<QuantizationGroup>>>,
+    api_cache: &Arc<RwLock<ApiCache>>,
+    token: Option<&String>,
+) {
+    let pending: Vec<(String, QuantizationInfo)> = quantizations
+        .read()
+        .iter()
+        .filter(|group| group.header.is_none())
+        .filter_map(|group| {
+            let first = group.files.first()?;
+            first
+                .filename
+                .ends_with(".gguf")
+                .then(|| (group.quant_type.clone(), first.clone()))
+        })
+        .collect();
+
+    let mut headers = futures::stream::iter(pending)
+        .map(|(quant_type, file)| async move {
+            let header = fetch_gguf_header_summary(
+                model_id,
+                file.revision.as_deref(),
+                &file.filename,
+                token,
+            )
+            .await;
+            (quant_type, header)
+        })
+        .buffer_unordered(HEADER_FETCH_CONCURRENCY);
+
+    while let Some((quant_type, header)) = headers.next().await {
+        let Ok(header) = header else {
+            continue;
+        };
+        let set_header = |groups: &mut Vec<QuantizationGroup>| {
+            if let Some(group) = groups.iter_mut().find(|g| g.quant_type == quant_type) {
+                group.header = Some(header.clone());
+            }
+        };
+        set_header(&mut quantizations.write());
+        if let Some(groups) = api_cache.write().quantizations.get_mut(model_id) {
+            set_header(groups);
+        }
+    }
+}
+
+/// Expand every directory on the way to `target_path` so it shows up in the tree view
+fn expand_to_path(node: &mut FileTreeNode, target_path: &str) -> bool {
+    for child in &mut node.children {
+        if child.path == target_path {
+            return true;
+        }
+
+        // Shard groups are virtual, so their path is no prefix of the shards'
+        let is_ancestor = child.shard_count.is_some()
+            || (child.is_dir
+                && target_path
+                    .strip_prefix(child.path.as_str())
+                    .is_some_and(|rest| rest.starts_with('/')));
+        if is_ancestor && expand_to_path(child, target_path) {
+            child.expanded = true;
+            return true;
+        }
+    }
+    false
+}
@@ -0,0 +1,90 @@
+use super::state::App;
+use crate::models::*;
+use crate::registry;
+use tui_input::Input;
+
+impl App {
+    /// Open the notes popup for the selected model, prefilled with its current note
+    pub fn open_notes_popup(&mut self) {
+        let Some(model_id) = self
+            .list_state
+            .selected()
+            .and_then(|idx| self.models.read().get(idx).map(|m| m.id.clone()))
+        else {
+            *self.error.write() = Some("No model selected".to_string());
+            return;
+        };
+
+        let current = self.model_notes.get(&model_id).cloned().unwrap_or_default();
+        self.note_tags_input = Input::default().with_value(current.tags.join(", "));
+        self.note_text_input = Input::default().with_value(current.note);
+        self.note_field = 0;
+        self.note_model_id = Some(model_id);
+        self.popup_mode = PopupMode::ModelNotes;
+    }
+
+    /// Save the notes popup to the registry
+    pub async fn save_model_note(&mut self) {
+        let Some(model_id) = self.note_model_id.take() else {
+            return;
+        };
+
+        let note = ModelNote {
+            note: self.note_text_input.value().trim().to_string(),
+            tags: registry::parse_tags(self.note_tags_input.value()),
+        };
+
+        // Update the registry on disk and the in-memory copy (which the
+        // verifier saves back), leaving download entries untouched
+        let mut reg = self.download_registry.lock().await;
+        let mut registry = registry::load_registry();
+        registry::set_model_note(&mut registry, &model_id, note);
+        registry::save_registry(&registry);
+        reg.model_notes = registry.model_notes.clone();
+        drop(reg);
+
+        *self.status.write() = if registry.model_notes.contains_key(&model_id) {
+            format!("Saved notes for {}", model_id)
+        } else {
+            format!("Cleared notes for {}", model_id)
+        };
+        self.model_notes = registry.model_notes;
+    }
+
+    /// Show models whose local tags or notes match `query` (search for `#query`)
+    pub fn search_local_notes(&mut self, query: &str) {
+        let results: Vec<ModelInfo> = registry::search_model_notes(&self.model_notes, query)
+            .into_iter()
+            .map(|(model_id, _)| ModelInfo {
+                id: model_id.clone(),
+                author: model_id.split('/').next().map(str::to_string),
+                downloads: 0,
+                likes: 0,
+                tags: Vec::new(),
+                last_modified: None,
+                created_at: None,
+                used_storage: None,
+                gated: serde_json::Value::Null,
+                private: false,
+                has_gguf_files: false,
+                safetensors: None,
+                sha: None,
+            })
+            .collect();
+
+        // Local results can't be refreshed against the API
+        self.last_search_key = None;
+        self.new_model_ids.clear();
+        self.recent_gguf_days = None;
+
+        let has_results = !results.is_empty();
+        *self.status.write() = format!(
+            "Found {} model{} with matching local tags or notes",
+            results.len(),
+            if results.len() == 1 { "" } else { "s" }
+        );
+        *self.models.write() = results;
+        self.list_state.select(if has_results { Some(0) } else { None });
+        self.needs_load_quantizations = has_results;
+    }
+}
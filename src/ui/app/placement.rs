@@ -0,0 +1,57 @@
+use super::state::App;
+use tui_input::Input;
+
+impl App {
+    /// Apply the placement rules to the download in the popup; the path
+    /// follows them unless it was edited away from where they last put it
+    pub fn place_download(&mut self) {
+        let follow = !self.download_path_overridden();
+        let model_id = self
+            .list_state
+            .selected()
+            .and_then(|selected| Some(self.models.read().get(selected)?.id.clone()))
+            .unwrap_or_default();
+        let Some(summary) = self.download_summary.as_mut() else {
+            return;
+        };
+        summary.placement = crate::placement::place(&model_id, summary.bytes);
+        if follow {
+            let directory = summary.placement.as_ref().map_or_else(
+                || self.options.default_directory.clone(),
+                |p| p.directory.clone(),
+            );
+            self.download_path_input = Input::default().with_value(directory);
+            self.refresh_download_free_space();
+        }
+    }
+
+    /// Whether the path in the download popup differs from where the
+    /// placement rules (or, without a match, the default directory) put it
+    pub fn download_path_overridden(&self) -> bool {
+        let placed = self
+            .download_summary
+            .as_ref()
+            .and_then(|s| s.placement.as_ref())
+            .map_or(self.options.default_directory.as_str(), |p| {
+                p.directory.as_str()
+            });
+        self.download_path_input.value() != placed
+    }
+
+    /// Ctrl+R in the download popup: switch the path to the next download
+    /// root (the default directory, then each placement rule's directory)
+    pub fn cycle_download_root(&mut self) {
+        let mut roots = vec![self.options.default_directory.clone()];
+        for root in crate::placement::roots() {
+            if !roots.contains(&root) {
+                roots.push(root);
+            }
+        }
+        let next = roots
+            .iter()
+            .position(|root| root == self.download_path_input.value())
+            .map_or(0, |i| (i + 1) % roots.len());
+        self.download_path_input = Input::default().with_value(roots[next].clone());
+        self.refresh_download_free_space();
+    }
+}
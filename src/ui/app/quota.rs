@@ -0,0 +1,79 @@
+use super::state::App;
+use crate::models::*;
+use crate::registry;
+use std::path::Path;
+
+impl App {
+    /// Whether the download in the download popup would go over the quota
+    /// of its directory
+    pub fn download_over_quota(&self) -> bool {
+        self.download_summary
+            .as_ref()
+            .and_then(|s| Some(s.quota.as_ref()?.excess(s.bytes)))
+            .is_some_and(|excess| excess > 0)
+    }
+
+    /// Enter in the download popup over quota: list the downloaded models in
+    /// the quota's directory that could make room
+    pub async fn open_quota_cleanup(&mut self) {
+        let Some(quota) = self.download_summary.as_ref().and_then(|s| s.quota.clone()) else {
+            return;
+        };
+        let registry = self.download_registry.lock().await.clone();
+        self.quota_candidates = crate::quota::cleanup_candidates(&registry, &quota.directory);
+        self.quota_selected = 0;
+        self.popup_mode = PopupMode::QuotaCleanup;
+    }
+
+    /// Move the selected model's files under the quota directory to the trash
+    pub async fn delete_selected_candidate(&mut self) {
+        let Some(candidate) = self.quota_candidates.get(self.quota_selected).cloned() else {
+            return;
+        };
+        let Some(directory) = self
+            .download_summary
+            .as_ref()
+            .and_then(|s| Some(s.quota.as_ref()?.directory.clone()))
+        else {
+            return;
+        };
+
+        let registry = {
+            let mut reg = self.download_registry.lock().await;
+            let deleted = crate::trash::delete_downloads(
+                &mut reg,
+                |d| {
+                    d.model_id == candidate.model_id
+                        && Path::new(&d.local_path).starts_with(&directory)
+                },
+                crate::trash::DeleteMode::Trash,
+            );
+            registry::save_registry(&reg);
+            *self.status.write() = match deleted.errors.first() {
+                Some(error) => format!("Failed to delete {}: {}", candidate.model_id, error),
+                None => format!(
+                    "Moved {} file(s) of {} ({}) to the trash",
+                    deleted.downloads.len(),
+                    candidate.model_id,
+                    crate::utils::format_size(deleted.bytes)
+                ),
+            };
+            reg.clone()
+        };
+
+        // The [downloaded] marks and the quota line follow the deletion
+        *self.complete_downloads.lock().await = registry::get_complete_downloads(&registry);
+        self.refresh_download_free_space();
+        self.refresh_download_quota().await;
+        self.quota_candidates = crate::quota::cleanup_candidates(&registry, &directory);
+        self.quota_selected = self
+            .quota_selected
+            .min(self.quota_candidates.len().saturating_sub(1));
+    }
+
+    /// Back to the download popup
+    pub fn close_quota_cleanup(&mut self) {
+        self.quota_candidates.clear();
+        self.popup_mode = PopupMode::DownloadPath;
+    }
+}
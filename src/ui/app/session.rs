@@ -0,0 +1,215 @@
+//! Opt-in persistence of the browsing session (`restore_session` option)
+//!
+//! On quit the search query, filters, selected model, focused pane and list
+//! positions are written to `~/.config/jreb/session.toml`. The next launch
+//! re-runs the search and, once results have loaded, restores the selection
+//! in two steps: first the model (which triggers its details load), then the
+//! quantization / file tree positions and pane focus.
+
+use super::state::App;
+use crate::models::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+
+/// Saved browsing position
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UiSession {
+    pub query: String,
+    pub sort_field: SortField,
+    pub sort_direction: SortDirection,
+    pub min_downloads: u64,
+    pub min_likes: u64,
+    pub params: ParamRange,
+    pub access: AccessFilter,
+    pub content: ContentFilter,
+    pub full_text: bool,
+    pub selected_model: Option<String>,
+    pub models_offset: usize,
+    pub focused_pane: FocusedPane,
+    pub quant_selected: Option<usize>,
+    pub quant_file_selected: Option<usize>,
+    pub file_tree_selected: Option<usize>,
+    pub file_tree_offset: usize,
+    pub expanded_dirs: Vec<String>,
+    /// Set once the model selection has been restored (second step pending)
+    #[serde(skip)]
+    pub model_restored: bool,
+}
+
+pub fn get_session_path() -> PathBuf {
+    let home = std::env::var("HOME").unwrap_or_else(|_| "/tmp".to_string());
+    PathBuf::from(format!("{}/.config/jreb/session.toml", home))
+}
+
+/// Load the saved session, if any
+pub fn load_session() -> Option<UiSession> {
+    let contents = fs::read_to_string(get_session_path()).ok()?;
+    toml::from_str(&contents).ok()
+}
+
+/// Save the session; failures are ignored (the session is best-effort)
+pub fn save_session(session: &UiSession) {
+    let path = get_session_path();
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    if let Ok(toml_string) = toml::to_string_pretty(session) {
+        let _ = fs::write(&path, toml_string);
+    }
+}
+
+impl App {
+    /// Snapshot the current browsing position
+    pub fn capture_session(&self) -> UiSession {
+        let selected_model = self
+            .list_state
+            .selected()
+            .and_then(|idx| self.models.read().get(idx).map(|m| m.id.clone()));
+
+        let mut expanded_dirs = Vec::new();
+        if let Some(tree) = self.file_tree.read().as_ref() {
+            collect_expanded_dirs(tree, &mut expanded_dirs);
+        }
+
+        UiSession {
+            query: self.input.value().to_string(),
+            sort_field: self.sort_field,
+            sort_direction: self.sort_direction,
+            min_downloads: self.filter_min_downloads,
+            min_likes: self.filter_min_likes,
+            params: self.filter_params,
+            access: self.filter_access,
+            content: self.filter_content,
+            full_text: self.search_full_text,
+            selected_model,
+            models_offset: self.list_state.offset(),
+            focused_pane: self.focused_pane,
+            quant_selected: self.quant_list_state.selected(),
+            quant_file_selected: self.quant_file_list_state.selected(),
+            file_tree_selected: self.file_tree_state.selected(),
+            file_tree_offset: self.file_tree_state.offset(),
+            expanded_dirs,
+            model_restored: false,
+        }
+    }
+
+    /// Re-run the saved session's search; the selection is restored by
+    /// `restore_session_selection` once results arrive
+    pub fn restore_session(&mut self) {
+        let Some(session) = load_session() else {
+            return;
+        };
+        if session.query.is_empty() {
+            return;
+        }
+
+        self.input = tui_input::Input::default().with_value(session.query.clone());
+        self.sort_field = session.sort_field;
+        self.sort_direction = session.sort_direction;
+        self.filter_min_downloads = session.min_downloads;
+        self.filter_min_likes = session.min_likes;
+        self.filter_params = session.params;
+        self.filter_access = session.access;
+        self.filter_content = session.content;
+        self.search_full_text = session.full_text;
+        self.needs_search_models = true;
+
+        *self.status.write() = format!("Restoring session: {}", session.query);
+        self.pending_session = Some(session);
+    }
+
+    /// Restore the saved selection (run loop, after the search has loaded)
+    pub fn restore_session_selection(&mut self) {
+        let Some(mut session) = self.pending_session.take() else {
+            return;
+        };
+
+        if !session.model_restored {
+            let models_len = self.models.read().len();
+            if models_len == 0 {
+                return;
+            }
+
+            let position = session.selected_model.as_ref().and_then(|id| {
+                self.models.read().iter().position(|m| &m.id == id)
+            });
+            let Some(idx) = position else {
+                // Model no longer in the results: keep the search, drop the rest
+                return;
+            };
+
+            *self.list_state.offset_mut() = session.models_offset.min(idx);
+            if self.list_state.selected() != Some(idx) {
+                self.list_state.select(Some(idx));
+                self.clear_model_details();
+                self.needs_load_quantizations = true;
+            }
+            session.model_restored = true;
+            self.pending_session = Some(session);
+            return;
+        }
+
+        // Details of the restored model have loaded: restore pane positions
+        match *self.display_mode.read() {
+            ModelDisplayMode::Gguf => {
+                let quants = self.quantizations.read();
+                if let Some(group) = session.quant_selected.filter(|&g| g < quants.len()) {
+                    self.quant_list_state.select(Some(group));
+                    let files_len = quants[group].files.len();
+                    self.quant_file_list_state
+                        .select(session.quant_file_selected.filter(|&f| f < files_len));
+                }
+            }
+            ModelDisplayMode::Standard => {
+                let mut tree = self.file_tree.read().clone();
+                if let Some(tree) = tree.as_mut() {
+                    let expanded: HashSet<&str> =
+                        session.expanded_dirs.iter().map(String::as_str).collect();
+                    set_expanded_dirs(tree, &expanded);
+                    let items_len = crate::ui::render::flatten_tree_for_navigation(tree).len();
+                    *self.file_tree.write() = Some(tree.clone());
+
+                    if let Some(idx) = session.file_tree_selected.filter(|&i| i < items_len) {
+                        *self.file_tree_state.offset_mut() = session.file_tree_offset.min(idx);
+                        self.file_tree_state.select(Some(idx));
+                    }
+                }
+            }
+        }
+
+        // Only restore focus to a pane that exists in the current display mode
+        let pane_valid = match *self.display_mode.read() {
+            ModelDisplayMode::Gguf => matches!(
+                session.focused_pane,
+                FocusedPane::QuantizationGroups | FocusedPane::QuantizationFiles
+            ),
+            ModelDisplayMode::Standard => session.focused_pane == FocusedPane::FileTree,
+        };
+        if pane_valid {
+            self.focused_pane = session.focused_pane;
+        }
+
+        *self.status.write() = format!("Restored session: {}", session.query);
+    }
+}
+
+fn collect_expanded_dirs(node: &FileTreeNode, out: &mut Vec<String>) {
+    for child in &node.children {
+        if child.is_dir && child.expanded {
+            out.push(child.path.clone());
+            collect_expanded_dirs(child, out);
+        }
+    }
+}
+
+fn set_expanded_dirs(node: &mut FileTreeNode, expanded: &HashSet<&str>) {
+    for child in &mut node.children {
+        if child.is_dir && expanded.contains(child.path.as_str()) {
+            child.expanded = true;
+            set_expanded_dirs(child, expanded);
+        }
+    }
+}
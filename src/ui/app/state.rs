@@ -1,18 +1,30 @@
 use crate::models::*;
-use crossterm::event::EventStream;
+use crossterm::event::{EventStream, KeyEvent};
 use ratatui::layout::Rect;
 use ratatui::widgets::ListState;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::PathBuf;
 use std::sync::atomic::AtomicUsize;
 use std::sync::Arc;
 use parking_lot::RwLock;
 use tokio::sync::{mpsc, Mutex};
+use tokio_util::sync::CancellationToken;
 use tui_input::Input;
 
 /// Type alias for download message tuple
-/// Tuple: (model_id, filename, path, sha256, hf_token, total_size)
-pub type DownloadMessage = (String, String, PathBuf, Option<String>, Option<String>, u64);
+/// Tuple: (model_id, filename, path, expected hash, hf_token, total_size, revision)
+pub type DownloadMessage = (
+    String,
+    String,
+    PathBuf,
+    Option<ExpectedHash>,
+    Option<String>,
+    u64,
+    Option<String>,
+);
+
+/// Priorities of queued (model_id, filename)s not at normal priority
+pub type DownloadPriorities = HashMap<(String, String), DownloadPriority>;
 
 /// Type alias for download receiver to reduce complexity
 pub type DownloadReceiver = Arc<Mutex<mpsc::UnboundedReceiver<DownloadMessage>>>;
@@ -24,6 +36,9 @@ pub struct App {
     pub event_stream: EventStream,
     pub input: Input,
     pub input_mode: InputMode,
+    pub open_input: Input, // Model ID / URL typed into the Open popup
+    pub palette_input: Input, // Query typed into the ':' command palette
+    pub palette_selected: usize, // Highlighted row among the palette matches
     pub focused_pane: FocusedPane,
     pub models: Arc<RwLock<Vec<ModelInfo>>>,
     pub list_state: ListState,
@@ -34,15 +49,33 @@ pub struct App {
     pub selection_info: Arc<RwLock<String>>, // Model selection info (name + URL)
     pub quantizations: Arc<RwLock<Vec<QuantizationGroup>>>,
     pub quant_file_list_state: ListState,
+    pub compare_quant: Option<String>, // Quant type marked with 'c' for the diff view
+    pub marked_quants: HashSet<String>, // Quant types marked with Space for a combined download
+    pub download_summary: Option<DownloadSummary>, // Shown in the download path popup
+    pub download_plan: Vec<PlannedDownload>, // Dry-run plan shown with 'D'
+    pub download_plan_scroll: u16,
+    pub pending_part_paths: Vec<PathBuf>, // Split GGUF parts checked once the queue drains
+    pub conversion_jobs: Arc<RwLock<Vec<crate::convert::ConversionJob>>>, // Repos to convert to GGUF, listed in the queue popup
+    pub downloads_idle_since: Option<std::time::Instant>, // When the queue last went idle (conversions wait for it)
+    pub quant_diff: Option<(QuantizationGroup, QuantizationGroup)>, // Pair shown in the diff popup
     pub loading_quants: Arc<RwLock<bool>>,
     pub api_cache: Arc<RwLock<crate::models::ApiCache>>,
     pub popup_mode: PopupMode,
     pub download_path_input: Input,
+    pub download_with_base: bool, // Also queue the base model of an adapter repo
+    pub download_all_parts: bool, // Queue every part of the selected split file
+    pub download_preset: RepoPreset, // Files of a repository download (Tab in the popup)
     pub download_progress: Arc<Mutex<Option<DownloadProgress>>>,
     pub download_tx: mpsc::UnboundedSender<DownloadMessage>,
     pub download_rx: DownloadReceiver,
     pub download_queue: Arc<Mutex<crate::models::QueueState>>, // Combined queue state to reduce lock complexity
+    pub held_download: Arc<std::sync::Mutex<Vec<DownloadMessage>>>, // Next download, held by the manager after a small-file batch
     pub incomplete_downloads: Vec<DownloadMetadata>,
+    pub queued_downloads: Vec<QueuedDownload>, // Left in the queue by the last session
+    pub download_priorities: HashMap<(String, String), DownloadPriority>, // Queued (model_id, filename)s not at normal priority
+    pub needs_sort_download_queue: bool, // Re-order the channel once its receiver is free
+    pub download_queue_view: Vec<QueuedDownload>, // Pending downloads listed in the queue popup ('Q')
+    pub download_queue_selected: usize,
     pub status_rx: Arc<Mutex<mpsc::UnboundedReceiver<String>>>,
     pub status_tx: mpsc::UnboundedSender<String>,
     pub download_registry: Arc<Mutex<DownloadRegistry>>,
@@ -53,28 +86,84 @@ pub struct App {
     pub options: crate::models::AppOptions,
     pub options_directory_input: Input,
     pub options_token_input: Input,
+    pub options_tool_path_input: Input, // Conversion tool path being edited in Options
     // Non-GGUF model support
     pub model_metadata: Arc<RwLock<Option<ModelMetadata>>>,
     pub file_tree: Arc<RwLock<Option<FileTreeNode>>>,
     pub file_tree_state: ListState,
+    // Related models listed in the metadata pane (Enter opens one)
+    pub related_models: Arc<RwLock<Vec<ModelInfo>>>,
+    pub related_state: ListState,
     pub display_mode: Arc<RwLock<crate::models::ModelDisplayMode>>,
     // Flags to trigger deferred loading on next loop iteration
     pub needs_load_quantizations: bool,
     pub needs_search_models: bool,
+    pub needs_quota_refresh: bool,
     // Prefetch debounce timer
     pub last_prefetch_time: Arc<Mutex<std::time::Instant>>,
+    // Cancellation for in-flight background loads (replaced on each new load)
+    pub load_quants_cancel: CancellationToken,
+    pub prefetch_cancel: CancellationToken,
+    pub load_sizes_cancel: CancellationToken,
     // Filter & Sort state
     pub sort_field: crate::models::SortField,
     pub sort_direction: crate::models::SortDirection,
     pub filter_min_downloads: u64,
     pub filter_min_likes: u64,
-    pub focused_filter_field: usize, // 0=sort, 1=downloads, 2=likes
+    pub filter_params: crate::models::ParamRange, // Size filter
+    pub filter_access: crate::models::AccessFilter, // Gated/private filter
+    pub filter_content: crate::models::ContentFilter, // Adult content filter
+    pub search_full_text: bool,                   // Card text search (Tab in search popup)
+    pub focused_filter_field: usize, // 0=sort, 1=downloads, 2=likes, 3=size, 4=access, 5=content
+    // Search refresh state
+    pub last_search_key: Option<SearchKey>, // Parameters of the search currently shown
+    pub last_refresh_time: std::time::Instant,
+    pub new_model_ids: HashSet<String>, // Models that appeared in the last refresh
+    pub recent_gguf_days: Option<u64>,  // Days covered while Results shows the GGUF feed ('N')
+    // File from an opened URL, selected once its model has loaded
+    pub pending_open_file: Option<ModelReference>,
+    // Watchlist: watched model IDs and updates found by the startup check
+    pub watched_models: Arc<RwLock<HashSet<String>>>,
+    pub watch_updates: Arc<RwLock<Vec<crate::watchlist::WatchUpdate>>>,
+    pub watch_updates_scroll: u16,
+    // Hidden models and authors popup: entries, selection, and whether any was unhidden
+    pub ignore_list_view: Vec<crate::ignorelist::IgnoredEntry>,
+    pub ignore_list_selected: usize,
+    pub ignore_list_changed: bool,
+    // Quota cleanup popup: models suggested for deletion and the selection
+    pub quota_candidates: Vec<crate::quota::CleanupCandidate>,
+    pub quota_selected: usize,
+    // Screen-reader log (see accessibility.rs) and the lines last written to it
+    pub screen_reader_log: Option<std::fs::File>,
+    pub last_announced_focus: String,
+    pub last_announced_status: String,
+    // Download history summary shown in the Stats popup (loaded when opened)
+    pub history_stats: Option<crate::history::HistoryStats>,
+    // Author profile popup ('A')
+    pub author_profile: Option<AuthorProfile>,
+    // Chat template / special tokens popup ('t')
+    pub prompt_format: Option<PromptFormat>,
+    pub prompt_format_scroll: u16,
+    // User notes and tags (mirrors the registry's model_notes for rendering)
+    pub model_notes: BTreeMap<String, ModelNote>,
+    // Notes popup: model being edited, its inputs and the focused field (0 = tags, 1 = note)
+    pub note_model_id: Option<String>,
+    pub note_tags_input: Input,
+    pub note_text_input: Input,
+    pub note_field: usize,
+    // Saved session being restored on startup (see session.rs)
+    pub pending_session: Option<super::session::UiSession>,
+    // Vim-style key sequence in progress (count prefix digits and/or `g`)
+    pub pending_keys: Vec<KeyEvent>,
+    pub pending_keys_since: std::time::Instant,
     // Mouse interaction state
     pub mouse_position: Option<(u16, u16)>, // Current mouse position (x, y)
     pub panel_areas: Vec<(FocusedPane, Rect)>, // Store panel areas for click/hover detection
     pub hovered_panel: Option<FocusedPane>, // Currently hovered panel for visual feedback
     pub last_mouse_event_time: std::time::Instant, // Track time of last processed mouse event
-    pub filter_areas: Vec<(usize, Rect)>, // Store filter field areas (0=sort, 1=downloads, 2=likes)
+    pub filter_areas: Vec<(usize, Rect)>, // Store filter field areas (0=sort, 1=downloads, 2=likes, 3=size)
+    pub preset_areas: Vec<(crate::models::FilterPreset, Rect)>, // Filter preset click areas
+    pub last_click: Option<(std::time::Instant, FocusedPane, usize)>, // Last list row clicked, for double-clicks
     // Cached values for non-blocking render (used when tokio Mutex is locked)
     pub cached_complete_downloads: CompleteDownloads,
     pub cached_download_progress: Option<DownloadProgress>,
@@ -109,6 +198,9 @@ impl App {
         let default_sort_direction = options.default_sort_direction;
         let default_min_downloads = options.default_min_downloads;
         let default_min_likes = options.default_min_likes;
+        let default_params = options.default_params;
+        let default_access = options.default_access;
+        let default_content = options.default_content;
 
         let mut download_path_input = Input::default();
         download_path_input = download_path_input.with_value(options.default_directory.clone());
@@ -120,6 +212,9 @@ impl App {
             event_stream: EventStream::default(),
             input: Input::default(),
             input_mode: InputMode::Normal, // Start in normal mode
+            open_input: Input::default(),
+            palette_input: Input::default(),
+            palette_selected: 0,
             focused_pane: FocusedPane::Models,
             models: Arc::new(RwLock::new(Vec::new())),
             list_state,
@@ -132,15 +227,33 @@ impl App {
             selection_info: Arc::new(RwLock::new(String::new())),
             quantizations: Arc::new(RwLock::new(Vec::new())),
             quant_file_list_state,
+            compare_quant: None,
+            marked_quants: HashSet::new(),
+            download_summary: None,
+            download_plan: Vec::new(),
+            download_plan_scroll: 0,
+            pending_part_paths: Vec::new(),
+            conversion_jobs: Arc::new(RwLock::new(Vec::new())),
+            downloads_idle_since: None,
+            quant_diff: None,
             loading_quants: Arc::new(RwLock::new(false)),
             api_cache: Arc::new(RwLock::new(crate::models::ApiCache::default())),
             popup_mode: PopupMode::None,
             download_path_input,
+            download_with_base: false,
+            download_all_parts: false,
+            download_preset: RepoPreset::All,
             download_progress: Arc::new(Mutex::new(None)),
             download_tx,
             download_rx: Arc::new(Mutex::new(download_rx)),
             download_queue: Arc::new(Mutex::new(crate::models::QueueState::new(0, 0))),
+            held_download: Arc::new(std::sync::Mutex::new(Vec::new())),
             incomplete_downloads: Vec::new(),
+            queued_downloads: Vec::new(),
+            download_priorities: HashMap::new(),
+            needs_sort_download_queue: false,
+            download_queue_view: Vec::new(),
+            download_queue_selected: 0,
             status_rx: Arc::new(Mutex::new(status_rx)),
             status_tx,
             download_registry: Arc::new(Mutex::new(DownloadRegistry::default())),
@@ -151,25 +264,72 @@ impl App {
             options,
             options_directory_input: Input::default(),
             options_token_input: Input::default(),
+            options_tool_path_input: Input::default(),
             // Non-GGUF model support
             model_metadata: Arc::new(RwLock::new(None)),
             file_tree: Arc::new(RwLock::new(None)),
             file_tree_state,
+            related_models: Arc::new(RwLock::new(Vec::new())),
+            related_state: ListState::default(),
             display_mode: Arc::new(RwLock::new(crate::models::ModelDisplayMode::Gguf)),
             needs_load_quantizations: false,
             needs_search_models: false,
+            needs_quota_refresh: false,
             last_prefetch_time: Arc::new(Mutex::new(std::time::Instant::now())),
+            load_quants_cancel: CancellationToken::new(),
+            prefetch_cancel: CancellationToken::new(),
+            load_sizes_cancel: CancellationToken::new(),
             sort_field: default_sort_field,
             sort_direction: default_sort_direction,
             filter_min_downloads: default_min_downloads,
             filter_min_likes: default_min_likes,
+            filter_params: default_params,
+            filter_access: default_access,
+            filter_content: default_content,
+            search_full_text: false,
             focused_filter_field: 0,
+            last_search_key: None,
+            recent_gguf_days: None,
+            last_refresh_time: std::time::Instant::now(),
+            new_model_ids: HashSet::new(),
+            pending_open_file: None,
+            watched_models: Arc::new(RwLock::new(
+                crate::watchlist::load_watchlist()
+                    .models
+                    .into_iter()
+                    .map(|m| m.model_id)
+                    .collect(),
+            )),
+            watch_updates: Arc::new(RwLock::new(Vec::new())),
+            watch_updates_scroll: 0,
+            ignore_list_view: Vec::new(),
+            ignore_list_selected: 0,
+            ignore_list_changed: false,
+            quota_candidates: Vec::new(),
+            quota_selected: 0,
+            screen_reader_log: None,
+            last_announced_focus: String::new(),
+            last_announced_status: String::new(),
+            history_stats: None,
+            author_profile: None,
+            prompt_format: None,
+            prompt_format_scroll: 0,
+            model_notes: crate::registry::load_registry().model_notes,
+            note_model_id: None,
+            note_tags_input: Input::default(),
+            note_text_input: Input::default(),
+            note_field: 0,
+            pending_session: None,
+            pending_keys: Vec::new(),
+            pending_keys_since: std::time::Instant::now(),
             // Mouse interaction state
             mouse_position: None,
             panel_areas: Vec::new(),
             hovered_panel: None,
             last_mouse_event_time: std::time::Instant::now(),
             filter_areas: Vec::new(),
+            preset_areas: Vec::new(),
+            last_click: None,
             // Cached values for non-blocking render
             cached_complete_downloads: HashMap::new(),
             cached_download_progress: None,
@@ -210,6 +370,28 @@ impl App {
         crate::download::DOWNLOAD_CONFIG
             .progress_update_interval_ms
             .store(self.options.progress_update_interval_ms, Ordering::Relaxed);
+        crate::download::DOWNLOAD_CONFIG
+            .single_request_max_size
+            .store(self.options.single_request_max_size, Ordering::Relaxed);
+        crate::download::DOWNLOAD_CONFIG
+            .adaptive_threads
+            .store(self.options.adaptive_threads, Ordering::Relaxed);
+        crate::download::DOWNLOAD_CONFIG
+            .max_connections
+            .store(self.options.max_connections, Ordering::Relaxed);
+        crate::download::DOWNLOAD_CONFIG
+            .conservative_networking
+            .store(false, Ordering::Relaxed);
+        if self.options.conservative_networking {
+            crate::download::DOWNLOAD_CONFIG.apply_conservative_networking();
+        }
+        crate::download::DOWNLOAD_CONFIG
+            .extract_archives
+            .store(self.options.extract_archives, Ordering::Relaxed);
+        crate::download::DOWNLOAD_CONFIG.set_identical_files(self.options.identical_files);
+        crate::download::DOWNLOAD_CONFIG
+            .blob_store
+            .store(self.options.blob_store, Ordering::Relaxed);
 
         // Rate limiting config
         let rate_limit_enabled = self.options.download_rate_limit_enabled;
@@ -237,6 +419,25 @@ impl App {
         crate::verification::VERIFICATION_CONFIG
             .update_interval_iterations
             .store(self.options.verification_update_interval, Ordering::Relaxed);
+        crate::verification::VERIFICATION_CONFIG
+            .low_priority
+            .store(self.options.low_priority_verification, Ordering::Relaxed);
+
+        // API cache config
+        crate::cache::configure(
+            self.options.api_cache_enabled,
+            self.options.api_cache_ttl_minutes,
+            self.options.api_cache_max_size_mb,
+        );
+
+        crate::http_client::set_user_agent(self.options.user_agent.clone());
+        crate::webhook::configure(self.options.webhooks.clone());
+        crate::trash::configure(self.options.trash_retention_days);
+        crate::quota::configure(self.options.quotas.clone());
+        crate::placement::configure(self.options.placement.clone());
+
+        // Glyph set of the UI
+        crate::ui::render::set_ascii_only(self.options.ascii_only);
     }
 
     /// Terminate application
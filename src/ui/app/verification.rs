@@ -34,8 +34,8 @@ impl App {
                 };
 
                 // Check if we have expected hash
-                let expected_hash = match &metadata.expected_sha256 {
-                    Some(hash) => hash.clone(),
+                let expected_hash = match metadata.expected_hash() {
+                    Some(hash) => hash,
                     None => {
                         *self.status.write() = format!(
                             "No SHA256 hash available for {}, cannot verify",
@@ -66,9 +66,10 @@ impl App {
 
                 // Queue verification item (ALWAYS queue, ignoring ENABLE_DOWNLOAD_VERIFICATION)
                 let item = VerificationQueueItem {
+                    model_id: metadata.model_id.clone(),
                     filename: quant.filename.clone(),
                     local_path: local_path.to_string_lossy().to_string(),
-                    expected_sha256: expected_hash,
+                    expected_hash,
                     total_size: file_size,
                     is_manual: true, // Mark as manual
                 };
@@ -85,4 +86,79 @@ impl App {
             }
         }
     }
+
+    /// Skip verification of the highlighted quantization's files, or of the
+    /// oldest running verification if the highlighted files aren't queued
+    pub async fn skip_verification(&mut self) {
+        let mut targets: Vec<String> = Vec::new();
+        if let Some(group) = self
+            .quant_list_state
+            .selected()
+            .and_then(|idx| self.quantizations.read().get(idx).cloned())
+        {
+            let complete_downloads = self.complete_downloads.lock().await;
+            targets.extend(
+                group
+                    .files
+                    .iter()
+                    .filter_map(|f| complete_downloads.get(&f.filename))
+                    .map(|m| m.local_path.clone()),
+            );
+        }
+
+        let mut skipped = Vec::new();
+        for local_path in &targets {
+            if crate::verification::skip_verification(
+                &self.verification_queue,
+                &self.verification_queue_size,
+                &self.verification_progress,
+                local_path,
+            )
+            .await
+            {
+                skipped.push(local_path.clone());
+            }
+        }
+
+        if skipped.is_empty() {
+            let oldest = self
+                .verification_progress
+                .lock()
+                .await
+                .first()
+                .map(|p| p.local_path.clone());
+            if let Some(local_path) = oldest {
+                crate::verification::skip_verification(
+                    &self.verification_queue,
+                    &self.verification_queue_size,
+                    &self.verification_progress,
+                    &local_path,
+                )
+                .await;
+                skipped.push(local_path);
+            }
+        }
+
+        *self.status.write() = match skipped.as_slice() {
+            [] => "Nothing is being verified".to_string(),
+            [one] => format!("Skipping verification of {}", file_name(one)),
+            many => format!("Skipping verification of {} files", many.len()),
+        };
+    }
+
+    /// Cycle when verification runs relative to active downloads
+    pub fn cycle_verification_schedule(&mut self) {
+        let config = &crate::verification::VERIFICATION_CONFIG;
+        let schedule = config.schedule().next();
+        config.set_schedule(schedule);
+        *self.status.write() = format!("Verify {}", schedule.label());
+    }
+}
+
+/// Last path component, for status messages
+fn file_name(local_path: &str) -> &str {
+    std::path::Path::new(local_path)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or(local_path)
 }
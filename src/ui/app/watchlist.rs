@@ -0,0 +1,90 @@
+use super::state::App;
+use crate::models::*;
+use crate::watchlist;
+
+impl App {
+    /// Add the selected model to the watchlist, or remove it if already watched
+    pub fn toggle_watch_selected(&mut self) {
+        let Some(model_id) = self
+            .list_state
+            .selected()
+            .and_then(|idx| self.models.read().get(idx).map(|m| m.id.clone()))
+        else {
+            *self.error.write() = Some("No model selected".to_string());
+            return;
+        };
+
+        if self.watched_models.read().contains(&model_id) {
+            match watchlist::unwatch(&model_id) {
+                Ok(_) => {
+                    self.watched_models.write().remove(&model_id);
+                    *self.status.write() = format!("Stopped watching {}", model_id);
+                }
+                Err(e) => {
+                    *self.error.write() = Some(format!("Failed to update watchlist: {}", e));
+                }
+            }
+            return;
+        }
+
+        // Snapshot the current files in the background; changes are reported from here on
+        *self.status.write() = format!("Adding {} to watchlist...", model_id);
+        let watched_models = self.watched_models.clone();
+        let status_tx = self.status_tx.clone();
+        let token = self.options.hf_token.clone();
+        tokio::spawn(async move {
+            let message = match watchlist::snapshot(&model_id, token.as_ref()).await {
+                Ok(watched) => {
+                    let files = watched.files.len();
+                    match watchlist::watch(watched) {
+                        Ok(()) => {
+                            watched_models.write().insert(model_id.clone());
+                            format!("Watching {} ({} files)", model_id, files)
+                        }
+                        Err(e) => format!("Failed to update watchlist: {}", e),
+                    }
+                }
+                Err(e) => format!("Failed to watch {}: {}", model_id, e),
+            };
+            let _ = status_tx.send(message);
+        });
+    }
+
+    /// Check watched models for updates in the background (on startup)
+    pub fn spawn_watch_check(&self) {
+        if self.watched_models.read().is_empty() || crate::cache::is_offline() {
+            return;
+        }
+
+        let watch_updates = self.watch_updates.clone();
+        let status_tx = self.status_tx.clone();
+        let token = self.options.hf_token.clone();
+        tokio::spawn(async move {
+            let result = watchlist::check(token.as_ref()).await;
+            if result.updates.is_empty() {
+                return;
+            }
+            let count = result.updates.len();
+            *watch_updates.write() = result.updates;
+            let _ = status_tx.send(format!(
+                "{} watched model{} updated - press W to see what changed",
+                count,
+                if count == 1 { "" } else { "s" }
+            ));
+        });
+    }
+
+    /// Open the list of watched models updated since the last check
+    pub fn show_watch_updates(&mut self) {
+        if self.watch_updates.read().is_empty() {
+            *self.status.write() = if self.watched_models.read().is_empty() {
+                "Watchlist is empty - press w on a model to watch it".to_string()
+            } else {
+                "No watched model updates".to_string()
+            };
+            return;
+        }
+        self.watch_updates_scroll = 0;
+        self.popup_mode = PopupMode::WatchUpdates;
+    }
+}
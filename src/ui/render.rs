@@ -1,19 +1,286 @@
 use crate::models::{
-    DownloadProgress, FileTreeNode, FocusedPane, InputMode, ModelDisplayMode, ModelInfo,
-    ModelMetadata, QuantizationGroup, QuantizationInfo, VerificationProgress,
+    DownloadPriority, DownloadProgress, FileTreeNode, FocusedPane, InputMode, ModelDisplayMode, ModelInfo,
+    DownloadSummary, ModelConfigSummary, ModelMetadata, ModelNote, QuantFormat, QuantizationGroup, QuantizationInfo,
+    RepoPreset, VerificationProgress,
 };
 use crate::utils::{format_number, format_size};
 use ratatui::{
-    layout::{Constraint, Direction, Layout, Rect},
+    layout::{Constraint, Direction, Layout, Margin, Rect},
     style::{Color, Modifier, Style},
+    symbols,
     text::{Line, Span},
-    widgets::{Block, Borders, Clear, Gauge, List, ListItem, ListState, Paragraph, Wrap},
+    widgets::{
+        Block, Borders, Clear, Gauge, HighlightSpacing, List, ListItem, ListState, Paragraph,
+        Scrollbar, ScrollbarOrientation, ScrollbarState, Wrap,
+    },
     Frame,
 };
-use std::collections::HashMap;
-use std::sync::atomic::Ordering;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
 use tui_input::Input;
 
+/// Screen-reader friendly output (`screen_reader` option / `--screen-reader`)
+static SCREEN_READER: AtomicBool = AtomicBool::new(false);
+
+/// ASCII replacements for every symbol the UI draws (`ascii_only` option)
+static ASCII_ONLY: AtomicBool = AtomicBool::new(false);
+
+/// Symbols drawn by the UI, swapped as a set on terminals without good
+/// Unicode support
+pub struct Glyphs {
+    pub border: symbols::border::Set,
+    /// Section rule of the options popup
+    pub rule: char,
+    pub expanded: &'static str,
+    pub collapsed: &'static str,
+    pub downloads: &'static str,
+    pub likes: &'static str,
+    pub watched: &'static str,
+    pub gated: &'static str,
+    pub private: &'static str,
+    pub gguf: &'static str,
+    pub repo_size: &'static str,
+    pub note: &'static str,
+    /// Marked quantization group
+    pub marked: &'static str,
+    /// Later repository of a base model group in the GGUF feed
+    pub group_child: &'static str,
+    pub derived_from: &'static str,
+    pub derives: &'static str,
+    pub bullet: &'static str,
+    pub masked: &'static str,
+    pub up_down: &'static str,
+    pub left_right: &'static str,
+    pub ascending: &'static str,
+    pub descending: &'static str,
+    pub scrollbar: symbols::scrollbar::Set,
+}
+
+const UNICODE_GLYPHS: Glyphs = Glyphs {
+    border: symbols::border::PLAIN,
+    rule: '─',
+    expanded: "▾ ",
+    collapsed: "▸ ",
+    downloads: "↓",
+    likes: "♥",
+    watched: "★ ",
+    gated: "🔒",
+    private: "🔐",
+    gguf: "🧩 ",
+    repo_size: "💾",
+    note: " ✎",
+    marked: "● ",
+    group_child: "  └ ",
+    derived_from: " ← ",
+    derives: " → ",
+    bullet: "  • ",
+    masked: "•",
+    up_down: "↑/↓",
+    left_right: "←/→",
+    ascending: "▲",
+    descending: "▼",
+    scrollbar: symbols::scrollbar::VERTICAL,
+};
+
+const ASCII_GLYPHS: Glyphs = Glyphs {
+    border: symbols::border::Set {
+        top_left: "+",
+        top_right: "+",
+        bottom_left: "+",
+        bottom_right: "+",
+        vertical_left: "|",
+        vertical_right: "|",
+        horizontal_top: "-",
+        horizontal_bottom: "-",
+    },
+    rule: '-',
+    expanded: "v ",
+    collapsed: "> ",
+    downloads: "dl:",
+    likes: "likes:",
+    watched: "* ",
+    gated: "[gated]",
+    private: "[private]",
+    gguf: "[GGUF] ",
+    repo_size: "disk:",
+    note: " (note)",
+    marked: "x ",
+    group_child: "  `- ",
+    derived_from: " <- ",
+    derives: " -> ",
+    bullet: "  - ",
+    masked: "*",
+    up_down: "Up/Down",
+    left_right: "Left/Right",
+    ascending: "^",
+    descending: "v",
+    scrollbar: symbols::scrollbar::Set {
+        track: "|",
+        thumb: "#",
+        begin: "^",
+        end: "v",
+    },
+};
+
+pub fn set_ascii_only(enabled: bool) {
+    ASCII_ONLY.store(enabled, Ordering::Relaxed);
+}
+
+/// The glyph set for the current `ascii_only` setting
+pub fn glyphs() -> &'static Glyphs {
+    if ASCII_ONLY.load(Ordering::Relaxed) {
+        &ASCII_GLYPHS
+    } else {
+        &UNICODE_GLYPHS
+    }
+}
+
+/// Scrollbar for a list of `len` rows, following the list's scroll offset;
+/// with `bordered` it is drawn over the right border of `area`, otherwise in
+/// its last column. Nothing is drawn while every row fits.
+fn render_list_scrollbar(
+    frame: &mut Frame,
+    area: Rect,
+    bordered: bool,
+    len: usize,
+    state: &ListState,
+) {
+    // Only text is left for screen readers
+    if screen_reader() {
+        return;
+    }
+    let track = if bordered {
+        area.inner(Margin {
+            vertical: 1,
+            horizontal: 0,
+        })
+    } else {
+        area
+    };
+    let visible = track.height as usize;
+    if visible == 0 || len <= visible {
+        return;
+    }
+    // One position per possible top row, so the thumb reaches the end of the
+    // track once the last row is shown
+    let mut scrollbar_state = ScrollbarState::new(len - visible + 1)
+        .viewport_content_length(visible)
+        .position(state.offset());
+    let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+        .symbols(glyphs().scrollbar.clone())
+        .begin_symbol(None)
+        .end_symbol(None);
+    frame.render_stateful_widget(scrollbar, track, &mut scrollbar_state);
+}
+
+/// Widths of the results' right-aligned columns, spacing included
+const DOWNLOADS_COLUMN: usize = 13;
+const LIKES_COLUMN: usize = 9;
+const MODIFIED_COLUMN: usize = 15;
+const SIZE_COLUMN: usize = 6;
+
+/// Cut or pad `spans` to exactly `width` columns; a cut ends in an ellipsis
+fn fit_spans(spans: Vec<Span<'_>>, width: usize) -> Vec<Span<'_>> {
+    let ellipsis = display_text("…");
+    let ellipsis_width = Span::raw(ellipsis.as_str()).width();
+    let mut fitted = Vec::new();
+    let mut used = 0;
+    for span in spans {
+        let span_width = span.width();
+        if used + span_width <= width {
+            used += span_width;
+            fitted.push(span);
+            continue;
+        }
+        let room = width.saturating_sub(used + ellipsis_width);
+        let mut cut = String::new();
+        let mut cut_width = 0;
+        for c in span.content.chars() {
+            let char_width = Span::raw(c.to_string()).width();
+            if cut_width + char_width > room {
+                break;
+            }
+            cut.push(c);
+            cut_width += char_width;
+        }
+        cut.push_str(&ellipsis);
+        used += cut_width + ellipsis_width;
+        fitted.push(Span::styled(cut, span.style));
+        break;
+    }
+    if used < width {
+        fitted.push(Span::raw(" ".repeat(width - used)));
+    }
+    fitted
+}
+
+/// Text from outside this module (filter labels like "≤8B", status
+/// messages, key hints) spelled in ASCII when `ascii_only` is set
+fn display_text(text: &str) -> String {
+    if !ASCII_ONLY.load(Ordering::Relaxed) {
+        return text.to_string();
+    }
+    text.replace('≤', "<=")
+        .replace('≥', ">=")
+        .replace(['–', '—'], "-")
+        .replace('…', "...")
+        .replace('×', "x")
+        .replace('✓', "OK")
+        .replace('✗', "FAILED")
+        .replace('▲', "^")
+        .replace('▼', "v")
+        .replace('↑', "Up")
+        .replace('↓', "Down")
+        .replace('←', "Left")
+        .replace('→', "Right")
+}
+
+/// A block drawn with the current glyph set's borders
+fn new_block() -> Block<'static> {
+    Block::default().border_set(glyphs().border)
+}
+
+pub fn set_screen_reader(enabled: bool) {
+    SCREEN_READER.store(enabled, Ordering::Relaxed);
+}
+
+pub fn screen_reader() -> bool {
+    SCREEN_READER.load(Ordering::Relaxed)
+}
+
+/// Row marker of popup lists, whose selection is otherwise shown by color only
+fn selection_symbol() -> &'static str {
+    if screen_reader() {
+        "> "
+    } else {
+        ""
+    }
+}
+
+/// Final pass of a screen-reader frame: box-drawing and block characters
+/// (borders, tree lines, gauge fill) become blanks so only text is read, and
+/// the top row states what has focus
+pub fn render_screen_reader_overlay(frame: &mut Frame, focus: &str) {
+    let area = frame.area();
+    for cell in frame.buffer_mut().content.iter_mut() {
+        let is_drawing = cell
+            .symbol()
+            .chars()
+            .next()
+            .is_some_and(|c| ('\u{2500}'..='\u{259F}').contains(&c));
+        if is_drawing {
+            cell.set_symbol(" ");
+        }
+    }
+
+    let top_row = Rect { height: 1, ..area };
+    frame.render_widget(Clear, top_row);
+    frame.render_widget(
+        Paragraph::new(focus).style(Style::default().add_modifier(Modifier::BOLD)),
+        top_row,
+    );
+}
+
 /// Parameters for rendering the UI
 pub struct RenderParams<'a> {
     pub input: &'a Input,
@@ -35,17 +302,31 @@ pub struct RenderParams<'a> {
     pub model_metadata: &'a Option<ModelMetadata>,
     pub file_tree: &'a Option<FileTreeNode>,
     pub file_tree_state: &'a mut ListState,
+    pub related_models: &'a [ModelInfo],
+    pub related_state: &'a mut ListState,
     // Filter & Sort
     pub sort_field: crate::models::SortField,
     pub sort_direction: crate::models::SortDirection,
     pub filter_min_downloads: u64,
     pub filter_min_likes: u64,
+    pub filter_params: crate::models::ParamRange,
+    pub filter_access: crate::models::AccessFilter,
+    pub filter_content: crate::models::ContentFilter,
+    pub search_full_text: bool,
     pub focused_filter_field: usize,
     // Mouse panel areas (for click/hover detection on panels)
     pub panel_areas: &'a mut Vec<(FocusedPane, Rect)>,
     pub hovered_panel: &'a Option<FocusedPane>,
     // Filter toolbar click areas
     pub filter_areas: &'a mut Vec<(usize, Rect)>,
+    pub preset_areas: &'a mut Vec<(crate::models::FilterPreset, Rect)>,
+    // Models that appeared since the previous search refresh
+    pub new_model_ids: &'a HashSet<String>,
+    // Days covered when the results are the new GGUF feed, grouped by base model
+    pub recent_gguf_days: Option<u64>,
+    pub watched_model_ids: &'a HashSet<String>,
+    pub model_notes: &'a BTreeMap<String, ModelNote>,
+    pub marked_quants: &'a HashSet<String>,
 }
 
 pub fn render_ui(frame: &mut Frame, params: RenderParams) {
@@ -68,19 +349,32 @@ pub fn render_ui(frame: &mut Frame, params: RenderParams) {
         model_metadata,
         file_tree,
         file_tree_state,
+        related_models,
+        related_state,
         sort_field,
         sort_direction,
         filter_min_downloads,
         filter_min_likes,
+        filter_params,
+        filter_access,
+        filter_content,
+        search_full_text,
         focused_filter_field,
         panel_areas,
         hovered_panel,
         filter_areas,
+        preset_areas,
+        new_model_ids,
+        recent_gguf_days,
+        watched_model_ids,
+        model_notes,
+        marked_quants,
     } = params;
 
     // Clear previous panel and filter areas
     panel_areas.clear();
     filter_areas.clear();
+    preset_areas.clear();
 
     let chunks = Layout::default()
         .direction(Direction::Vertical)
@@ -100,8 +394,12 @@ pub fn render_ui(frame: &mut Frame, params: RenderParams) {
         sort_direction,
         filter_min_downloads,
         filter_min_likes,
+        filter_params,
+        filter_access,
+        filter_content,
         focused_filter_field,
         filter_areas,
+        preset_areas,
     );
 
     // Helper to determine border style based on focus and hover state
@@ -115,7 +413,12 @@ pub fn render_ui(frame: &mut Frame, params: RenderParams) {
         }
     };
 
-    // Results list (chunks[1])
+    // Results list (chunks[1]): row number, model name and badges, then the
+    // right-aligned columns, under a header row that stays in place
+    let name_width = (chunks[1].width as usize)
+        .saturating_sub(2 + 3 + 5) // borders, highlight symbol, row number
+        .saturating_sub(DOWNLOADS_COLUMN + LIKES_COLUMN + MODIFIED_COLUMN + SIZE_COLUMN)
+        .max(20);
     let items: Vec<ListItem> = models
         .iter()
         .enumerate()
@@ -144,71 +447,245 @@ pub fn render_ui(frame: &mut Frame, params: RenderParams) {
                 )
             };
 
-            let last_modified_str = if let Some(ref modified) = model.last_modified {
-                if !modified.is_empty() {
-                    // Parse and format date in short format (YYYY-MM-DD)
-                    let date_part: &str = modified.split('T').next().unwrap_or("");
-                    if date_part.len() >= 10 {
-                        format!(" [{}]", &date_part[..10])
-                    } else {
-                        String::new()
-                    }
-                } else {
-                    String::new()
-                }
+            // "3 days ago"; the status line has the exact date of the selection
+            let last_modified_str = model
+                .last_modified
+                .as_deref()
+                .and_then(crate::utils::format_relative_date)
+                .unwrap_or_else(|| "-".to_string());
+
+            let new_badge = if new_model_ids.contains(&model.id) {
+                Span::styled(
+                    "NEW ",
+                    Style::default()
+                        .fg(Color::Green)
+                        .add_modifier(Modifier::BOLD),
+                )
             } else {
-                String::new()
+                Span::raw("")
             };
 
-            let content = Line::from(vec![
-                Span::styled(
-                    format!("{:3}. ", idx + 1),
-                    Style::default().fg(Color::DarkGray),
+            let watched_badge = if watched_model_ids.contains(&model.id) {
+                Span::styled(glyphs().watched, Style::default().fg(Color::Yellow))
+            } else {
+                Span::raw("")
+            };
+
+            // 🔒 gated / 🔐 private, 🧩 GGUF (other formats by name)
+            let mut access_badge = String::new();
+            if model.is_gated() {
+                access_badge.push_str(glyphs().gated);
+            }
+            if model.private {
+                access_badge.push_str(glyphs().private);
+            }
+            if !access_badge.is_empty() {
+                access_badge.push(' ');
+            }
+
+            let format_badge = match model.quant_format() {
+                Some(QuantFormat::Gguf) => Span::styled(
+                    glyphs().gguf,
+                    Style::default().fg(quant_format_color(&QuantFormat::Gguf)),
+                ),
+                Some(format) => Span::styled(
+                    format!("{} ", format.label()),
+                    Style::default().fg(quant_format_color(&format)),
                 ),
+                None => Span::raw(""),
+            };
+
+            // GGUF feed: indent the rest of a base model's group under its first repo
+            let group_of = |m: &ModelInfo| m.base_model().unwrap_or(&m.id).to_string();
+            let (group_prefix, base_str) = match recent_gguf_days {
+                Some(_) if idx > 0 && group_of(&models[idx - 1]) == group_of(model) => {
+                    (glyphs().group_child, String::new())
+                }
+                Some(_) => (
+                    "",
+                    model.base_model().map_or_else(String::new, |base| {
+                        format!("{}{}", glyphs().derived_from, base)
+                    }),
+                ),
+                None => ("", String::new()),
+            };
+
+            // Total repository size, once looked up (show_repo_sizes option)
+            let repo_size_str = model.used_storage.map_or_else(String::new, |bytes| {
+                format!(" {}{}", glyphs().repo_size, format_size(bytes))
+            });
+
+            // User tags (#tag) and a marker when a free-text note is attached
+            let user_note = model_notes.get(&model.id);
+            let note_str = user_note.map_or_else(String::new, |note| {
+                let mut s: String = note.tags.iter().map(|t| format!(" #{}", t)).collect();
+                if !note.note.is_empty() {
+                    s.push_str(glyphs().note);
+                }
+                s
+            });
+
+            let name = vec![
+                Span::styled(group_prefix, Style::default().fg(Color::DarkGray)),
+                new_badge,
+                watched_badge,
+                Span::styled(access_badge, Style::default().fg(Color::Red)),
+                format_badge,
                 Span::styled(
                     &model.id,
                     Style::default()
                         .fg(Color::Cyan)
                         .add_modifier(Modifier::BOLD),
                 ),
+                Span::styled(base_str, Style::default().fg(Color::DarkGray)),
+                Span::styled(repo_size_str, Style::default().fg(Color::Magenta)),
                 Span::raw(" by "),
                 Span::styled(author, Style::default().fg(Color::Green)),
-                Span::raw(format!(" ↓{} ♥{}", downloads, likes)),
-                Span::styled(last_modified_str, Style::default().fg(Color::Cyan)),
                 Span::styled(tags_str, Style::default().fg(Color::Yellow)),
+                Span::styled(note_str, Style::default().fg(Color::Magenta)),
+            ];
+
+            let mut spans = vec![Span::styled(
+                format!("{:3}. ", idx + 1),
+                Style::default().fg(Color::DarkGray),
+            )];
+            spans.extend(fit_spans(name, name_width));
+            spans.extend([
+                Span::raw(format!("{:>width$}", downloads, width = DOWNLOADS_COLUMN)),
+                Span::raw(format!("{:>width$}", likes, width = LIKES_COLUMN)),
+                Span::styled(
+                    format!("{:>width$}", last_modified_str, width = MODIFIED_COLUMN),
+                    Style::default().fg(Color::Cyan),
+                ),
+                Span::styled(
+                    format!(
+                        "{:>width$}",
+                        model.size_class().unwrap_or("-"),
+                        width = SIZE_COLUMN
+                    ),
+                    Style::default().fg(Color::Blue),
+                ),
             ]);
 
-            ListItem::new(content)
+            ListItem::new(Line::from(spans))
         })
         .collect();
 
-    let list_title = if loading {
-        "Results [Loading...]"
+    let mut list_title = if loading {
+        "Results [Loading...]".to_string()
     } else if models.is_empty() && !input.value().is_empty() {
-        "Results [No models found]"
+        "Results [No models found]".to_string()
     } else if models.is_empty() {
-        "Results [Enter a search query]"
+        "Results [Enter a search query]".to_string()
+    } else if !new_model_ids.is_empty() {
+        format!("Results [{} new since last refresh]", new_model_ids.len())
+    } else if let Some(days) = recent_gguf_days {
+        format!("Results [new GGUF, last {} days]", days)
+    } else {
+        "Results".to_string()
+    };
+    if search_full_text {
+        list_title.push_str(" [card text]");
+    }
+
+    let results_block = new_block()
+        .borders(Borders::ALL)
+        .title(list_title)
+        .border_style(get_border_style(FocusedPane::Models));
+    let results_inner = results_block.inner(chunks[1]);
+    frame.render_widget(results_block, chunks[1]);
+    let results_rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(0)])
+        .split(results_inner);
+
+    // Column headers; the sort column carries the direction arrow
+    let sort_arrow = match sort_direction {
+        crate::models::SortDirection::Ascending => glyphs().ascending,
+        crate::models::SortDirection::Descending => glyphs().descending,
+    };
+    let header = |text: String, field: crate::models::SortField| {
+        if field == sort_field {
+            format!("{} {}", text, sort_arrow)
+        } else {
+            text
+        }
+    };
+    // In ASCII the marks are spelled out ("dl:"), which the headers already are
+    let (downloads_mark, likes_mark) = if ASCII_ONLY.load(Ordering::Relaxed) {
+        ("", "")
     } else {
-        "Results"
+        (glyphs().downloads, glyphs().likes)
     };
+    let header_style = Style::default()
+        .fg(Color::DarkGray)
+        .add_modifier(Modifier::BOLD);
+    let mut header_spans = vec![Span::styled("     #  ", header_style)]; // highlight symbol, row number
+    header_spans.extend(fit_spans(
+        vec![Span::styled(
+            header("Model".to_string(), crate::models::SortField::Name),
+            header_style,
+        )],
+        name_width,
+    ));
+    header_spans.extend([
+        Span::styled(
+            format!(
+                "{:>width$}",
+                header(
+                    format!("{}Downloads", downloads_mark),
+                    crate::models::SortField::Downloads
+                ),
+                width = DOWNLOADS_COLUMN
+            ),
+            header_style,
+        ),
+        Span::styled(
+            format!(
+                "{:>width$}",
+                header(
+                    format!("{}Likes", likes_mark),
+                    crate::models::SortField::Likes
+                ),
+                width = LIKES_COLUMN
+            ),
+            header_style,
+        ),
+        Span::styled(
+            format!(
+                "{:>width$}",
+                header("Modified".to_string(), crate::models::SortField::Modified),
+                width = MODIFIED_COLUMN
+            ),
+            header_style,
+        ),
+        Span::styled(
+            format!("{:>width$}", "Size", width = SIZE_COLUMN),
+            header_style,
+        ),
+    ]);
+    frame.render_widget(Paragraph::new(Line::from(header_spans)), results_rows[0]);
 
     let list = List::new(items)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title(list_title)
-                .border_style(get_border_style(FocusedPane::Models)),
-        )
         .highlight_style(
             Style::default()
                 .bg(Color::DarkGray)
                 .add_modifier(Modifier::BOLD),
         )
-        .highlight_symbol(">> ");
+        .highlight_symbol(">> ")
+        // Keep the rows in line with the header while nothing is selected
+        .highlight_spacing(HighlightSpacing::Always);
 
     // Store panel area for click/hover detection
     panel_areas.push((FocusedPane::Models, chunks[1]));
-    frame.render_stateful_widget(list, chunks[1], list_state);
+    frame.render_stateful_widget(list, results_rows[1], list_state);
+    // The scrollbar runs beside the rows, below the header
+    let rows_with_border = Rect {
+        y: chunks[1].y + 1,
+        height: chunks[1].height.saturating_sub(1),
+        ..chunks[1]
+    };
+    render_list_scrollbar(frame, rows_with_border, true, models.len(), list_state);
 
     // Split bottom panel into left and right sections
     let bottom_panel_chunks = Layout::default()
@@ -232,6 +709,7 @@ pub fn render_ui(frame: &mut Frame, params: RenderParams) {
                     complete_downloads,
                     hovered_panel,
                     panel_areas,
+                    marked_quants,
                 },
             );
         }
@@ -243,6 +721,8 @@ pub fn render_ui(frame: &mut Frame, params: RenderParams) {
                     model_metadata,
                     file_tree,
                     file_tree_state,
+                    related_models,
+                    related_state,
                     loading: loading_quants,
                     input_mode,
                     focused_pane,
@@ -258,11 +738,7 @@ pub fn render_ui(frame: &mut Frame, params: RenderParams) {
         selection_info.to_string()
     } else if let Some(selected) = list_state.selected() {
         if selected < models.len() {
-            let model = &models[selected];
-            format!(
-                "Selected: {} | URL: https://huggingface.co/{}",
-                model.id, model.id
-            )
+            models[selected].selection_summary()
         } else {
             String::new()
         }
@@ -270,6 +746,18 @@ pub fn render_ui(frame: &mut Frame, params: RenderParams) {
         String::new()
     };
 
+    // Show the selected model's note, if any
+    let selected_note = list_state
+        .selected()
+        .and_then(|idx| models.get(idx))
+        .and_then(|model| model_notes.get(&model.id))
+        .filter(|note| !note.note.is_empty());
+    let line1 = match selected_note {
+        Some(note) if !line1.is_empty() => format!("{} | Note: {}", line1, note.note),
+        Some(note) => format!("Note: {}", note.note),
+        None => line1,
+    };
+
     // Check if any filters are non-default
     let has_filters = filter_min_downloads > 0
         || filter_min_likes > 0
@@ -277,9 +765,9 @@ pub fn render_ui(frame: &mut Frame, params: RenderParams) {
         || sort_direction != crate::models::SortDirection::Descending;
 
     let base_line2 = if let Some(err) = error {
-        format!("Error: {}", err)
+        format!("Error: {}", display_text(err))
     } else {
-        status.to_string()
+        display_text(status)
     };
 
     let line2 = if has_filters {
@@ -294,8 +782,33 @@ pub fn render_ui(frame: &mut Frame, params: RenderParams) {
         line2
     };
 
+    // Offline indicator: explicit --offline or cache fallback after a network failure
+    let mut status_title = vec![Span::raw("Status")];
+    if crate::cache::is_offline() {
+        status_title.push(Span::raw(" "));
+        status_title.push(Span::styled(
+            "[OFFLINE]",
+            Style::default().fg(Color::Yellow),
+        ));
+    } else if crate::cache::serving_from_cache() {
+        status_title.push(Span::raw(" "));
+        status_title.push(Span::styled(
+            "[NETWORK DOWN - CACHED]",
+            Style::default().fg(Color::Yellow),
+        ));
+    }
+    // Kept visible for the whole session, not just at startup
+    if crate::http_client::tls_insecure() {
+        status_title.push(Span::raw(" "));
+        status_title.push(Span::styled(
+            "[INSECURE TLS]",
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        ));
+    }
+    let status_title = Line::from(status_title);
+
     let status_widget = Paragraph::new(status_text)
-        .block(Block::default().borders(Borders::ALL).title("Status"))
+        .block(new_block().borders(Borders::ALL).title(status_title))
         .style(if error.is_some() {
             Style::default().fg(Color::Red)
         } else {
@@ -306,10 +819,49 @@ pub fn render_ui(frame: &mut Frame, params: RenderParams) {
     frame.render_widget(status_widget, chunks[3]);
 }
 
+/// Architecture, size and context length from a Standard repo's `config.json`
+fn model_config_lines(config: &ModelConfigSummary) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    if let Some(ref architecture) = config.architecture {
+        lines.push(Line::from(vec![
+            Span::styled("Architecture: ", Style::default().fg(Color::Yellow)),
+            Span::raw(architecture.clone()),
+        ]));
+    }
+    let shape: Vec<String> = [
+        config.hidden_size.map(|size| format!("hidden {}", size)),
+        config.num_layers.map(|layers| format!("{} layers", layers)),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    if !shape.is_empty() {
+        lines.push(Line::from(vec![
+            Span::styled("Shape: ", Style::default().fg(Color::Yellow)),
+            Span::raw(shape.join(", ")),
+        ]));
+    }
+    if let Some(context) = config.context_length {
+        lines.push(Line::from(vec![
+            Span::styled("Context: ", Style::default().fg(Color::Yellow)),
+            Span::raw(format!("{} tokens", context)),
+        ]));
+    }
+    if let Some(ref scaling) = config.rope_scaling {
+        lines.push(Line::from(vec![
+            Span::styled("RoPE scaling: ", Style::default().fg(Color::Yellow)),
+            Span::raw(scaling.clone()),
+        ]));
+    }
+    lines
+}
+
 struct StandardPanelContext<'a> {
     model_metadata: &'a Option<ModelMetadata>,
     file_tree: &'a Option<FileTreeNode>,
     file_tree_state: &'a mut ListState,
+    related_models: &'a [ModelInfo],
+    related_state: &'a mut ListState,
     loading: bool,
     input_mode: InputMode,
     focused_pane: FocusedPane,
@@ -326,6 +878,8 @@ fn render_standard_panels(
         model_metadata,
         file_tree,
         file_tree_state,
+        related_models,
+        related_state,
         loading,
         input_mode,
         focused_pane,
@@ -365,6 +919,20 @@ fn render_standard_panels(
             ]));
         }
 
+        if let Some(format) = metadata.quant_format() {
+            lines.push(Line::from(vec![
+                Span::styled("Format: ", Style::default().fg(Color::Yellow)),
+                Span::styled(
+                    format.label(),
+                    Style::default().fg(quant_format_color(&format)),
+                ),
+            ]));
+            lines.push(Line::from(vec![
+                Span::styled("Needs: ", Style::default().fg(Color::Yellow)),
+                Span::styled(format.guidance(), Style::default().fg(Color::DarkGray)),
+            ]));
+        }
+
         if let Some(ref pipeline) = metadata.pipeline_tag {
             lines.push(Line::from(vec![
                 Span::styled("Pipeline: ", Style::default().fg(Color::Yellow)),
@@ -372,8 +940,60 @@ fn render_standard_panels(
             ]));
         }
 
+        // "3 days ago (2024-03-09 14:03 UTC)"
+        let dates = [
+            ("Updated: ", metadata.last_modified.as_deref()),
+            ("Created: ", metadata.created_at.as_deref()),
+        ];
+        for (label, timestamp) in dates {
+            let Some(timestamp) = timestamp else {
+                continue;
+            };
+            let (Some(relative), Some(absolute)) = (
+                crate::utils::format_relative_date(timestamp),
+                crate::utils::format_absolute_date(timestamp),
+            ) else {
+                continue;
+            };
+            lines.push(Line::from(vec![
+                Span::styled(label, Style::default().fg(Color::Yellow)),
+                Span::raw(relative),
+                Span::styled(
+                    format!(" ({})", absolute),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]));
+        }
+
+        if let Some(base) = metadata.adapter_base_model() {
+            lines.push(Line::from(vec![
+                Span::styled("Adapter for: ", Style::default().fg(Color::Yellow)),
+                Span::styled(base, Style::default().fg(Color::Green)),
+                Span::styled(
+                    "  (b: open base, B: download both)",
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]));
+        }
+
+        if let Some(params) = metadata.parameter_count() {
+            lines.push(Line::from(vec![
+                Span::styled("Parameters: ", Style::default().fg(Color::Yellow)),
+                Span::raw(crate::utils::format_params(params)),
+            ]));
+        }
+
+        if let Some(ref config) = metadata.model_config {
+            lines.extend(model_config_lines(config));
+        }
+
         if let Some(ref card_data) = metadata.card_data {
-            if let Some(ref base) = card_data.base_model {
+            if !metadata.base_model_chain.is_empty() {
+                lines.push(Line::from(vec![
+                    Span::styled("Base Model: ", Style::default().fg(Color::Yellow)),
+                    Span::raw(metadata.base_model_chain.join(glyphs().derives)),
+                ]));
+            } else if let Some(ref base) = card_data.base_model {
                 lines.push(Line::from(vec![
                     Span::styled("Base Model: ", Style::default().fg(Color::Yellow)),
                     Span::raw(base),
@@ -391,6 +1011,12 @@ fn render_standard_panels(
                     Span::raw(languages.join(", ")),
                 ]));
             }
+            if let Some(ref datasets) = card_data.datasets {
+                lines.push(Line::from(vec![
+                    Span::styled("Datasets: ", Style::default().fg(Color::Yellow)),
+                    Span::raw(datasets.join(", ")),
+                ]));
+            }
         }
 
         let file_count = metadata.siblings.len();
@@ -423,7 +1049,7 @@ fn render_standard_panels(
 
     let metadata_widget = Paragraph::new(metadata_content)
         .block(
-            Block::default()
+            new_block()
                 .borders(Borders::ALL)
                 .title(meta_title)
                 .border_style(get_border_style(FocusedPane::ModelMetadata)),
@@ -432,7 +1058,58 @@ fn render_standard_panels(
 
     // Store panel area for click/hover detection
     panel_areas.push((FocusedPane::ModelMetadata, chunks[0]));
-    frame.render_widget(metadata_widget, chunks[0]);
+
+    // Related models below the metadata, selectable once the pane has focus
+    let metadata_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),
+            Constraint::Length(match related_models.len() {
+                0 => 0,
+                len => (len as u16 + 2).min(chunks[0].height / 2), // plus borders
+            }),
+        ])
+        .split(chunks[0]);
+    frame.render_widget(metadata_widget, metadata_chunks[0]);
+
+    if !related_models.is_empty() {
+        let items: Vec<ListItem> = related_models
+            .iter()
+            .map(|model| {
+                ListItem::new(Line::from(vec![
+                    Span::styled(&model.id, Style::default().fg(Color::Cyan)),
+                    Span::raw(format!(
+                        " {}{} {}{}",
+                        glyphs().downloads,
+                        format_number(model.downloads),
+                        glyphs().likes,
+                        format_number(model.likes)
+                    )),
+                ]))
+            })
+            .collect();
+        let related_list = List::new(items)
+            .block(
+                new_block()
+                    .borders(Borders::ALL)
+                    .title("Related (Enter: open)")
+                    .border_style(get_border_style(FocusedPane::ModelMetadata)),
+            )
+            .highlight_style(
+                Style::default()
+                    .bg(Color::DarkGray)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .highlight_symbol(">> ");
+        frame.render_stateful_widget(related_list, metadata_chunks[1], related_state);
+        render_list_scrollbar(
+            frame,
+            metadata_chunks[1],
+            true,
+            related_models.len(),
+            related_state,
+        );
+    }
 
     // Right side: File tree
     render_file_tree_panel(
@@ -447,6 +1124,16 @@ fn render_standard_panels(
     );
 }
 
+/// Badge color per weight format: GGUF, ExLlama, other GPU quants, unquantized
+fn quant_format_color(format: &QuantFormat) -> Color {
+    match format {
+        QuantFormat::Gguf => Color::Magenta,
+        QuantFormat::Exl2 | QuantFormat::Exl3 => Color::LightBlue,
+        QuantFormat::Awq | QuantFormat::Gptq => Color::LightGreen,
+        QuantFormat::Safetensors(_) => Color::Gray,
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 fn render_file_tree_panel(
     frame: &mut Frame,
@@ -481,9 +1168,9 @@ fn render_file_tree_panel(
                 let indent = "  ".repeat(node.depth);
                 let icon = if node.is_dir {
                     if node.expanded {
-                        "▾ "
+                        glyphs().expanded
                     } else {
-                        "▸ "
+                        glyphs().collapsed
                     }
                 } else {
                     "  "
@@ -494,7 +1181,20 @@ fn render_file_tree_panel(
                     Span::styled(icon, Style::default().fg(Color::Cyan)),
                 ];
 
-                if node.is_dir {
+                if let Some(shards) = node.shard_count {
+                    // Sharded checkpoint: one logical unit, e.g. "weights (14 shards, 138 GB)"
+                    spans.push(Span::styled(
+                        node.name.clone(),
+                        Style::default()
+                            .fg(Color::Magenta)
+                            .add_modifier(Modifier::BOLD),
+                    ));
+                    spans.push(Span::raw(format!(
+                        " ({} shards, {})",
+                        shards,
+                        format_size(node.size.unwrap_or(0))
+                    )));
+                } else if node.is_dir {
                     // Directory: show name, size, and file count
                     spans.push(Span::styled(
                         format!("{}/", node.name),
@@ -514,6 +1214,17 @@ fn render_file_tree_panel(
                         format!(" ({} files)", file_count),
                         Style::default().fg(Color::DarkGray),
                     ));
+
+                    // Export for another runtime ('d' downloads only that)
+                    let runtime = Some(&node.path)
+                        .filter(|path| !path.contains('/'))
+                        .and_then(|path| crate::api::runtime_export(path));
+                    if let Some(runtime) = runtime {
+                        spans.push(Span::styled(
+                            format!(" [{}]", runtime.label()),
+                            Style::default().fg(Color::Yellow),
+                        ));
+                    }
                 } else {
                     // File: show name and size
                     let size_str = node
@@ -531,9 +1242,10 @@ fn render_file_tree_panel(
         vec![]
     };
 
+    let tree_len = tree_items.len();
     let tree_list = List::new(tree_items)
         .block(
-            Block::default()
+            new_block()
                 .borders(Borders::ALL)
                 .title(tree_title)
                 .border_style(get_border_style(FocusedPane::FileTree)),
@@ -548,6 +1260,7 @@ fn render_file_tree_panel(
     // Store panel area for click/hover detection
     panel_areas.push((FocusedPane::FileTree, area));
     frame.render_stateful_widget(tree_list, area, file_tree_state);
+    render_list_scrollbar(frame, area, true, tree_len, file_tree_state);
 }
 
 /// Count total number of files within a node (recursive)
@@ -575,6 +1288,15 @@ fn flatten_tree_recursive(node: &FileTreeNode, result: &mut Vec<FileTreeNode>) {
     }
 }
 
+/// Context length as GGUF headers usually have it, e.g. 131072 → "128K"
+fn format_context(tokens: u64) -> String {
+    if tokens >= 1024 && tokens % 1024 == 0 {
+        format!("{}K", tokens / 1024)
+    } else {
+        tokens.to_string()
+    }
+}
+
 /// Public helper for flattening tree (used by events.rs for navigation)
 pub fn flatten_tree_for_navigation(node: &FileTreeNode) -> Vec<FileTreeNode> {
     flatten_tree(node)
@@ -590,6 +1312,7 @@ struct GgufPanelContext<'a> {
     complete_downloads: &'a HashMap<String, crate::models::DownloadMetadata>,
     hovered_panel: &'a Option<FocusedPane>,
     panel_areas: &'a mut Vec<(FocusedPane, Rect)>,
+    marked_quants: &'a HashSet<String>,
 }
 
 fn render_gguf_panels(frame: &mut Frame, chunks: std::rc::Rc<[Rect]>, ctx: GgufPanelContext) {
@@ -603,6 +1326,7 @@ fn render_gguf_panels(frame: &mut Frame, chunks: std::rc::Rc<[Rect]>, ctx: GgufP
         complete_downloads,
         hovered_panel,
         panel_areas,
+        marked_quants,
     } = ctx;
 
     // Helper to determine border style based on focus and hover state
@@ -617,20 +1341,40 @@ fn render_gguf_panels(frame: &mut Frame, chunks: std::rc::Rc<[Rect]>, ctx: GgufP
     };
     // Left side: Quantization types
     let quant_title = if loading_quants {
-        "Quantization Types [Loading...]"
+        "Quantization Types [Loading...]".to_string()
     } else if quantizations.is_empty() {
-        "Quantization Types [Select a model to view]"
+        "Quantization Types [Select a model to view]".to_string()
+    } else if !marked_quants.is_empty() {
+        format!("Quantization Types [{} marked]", marked_quants.len())
     } else {
-        "Quantization Types"
+        "Quantization Types".to_string()
     };
 
+    // Context and architecture columns appear once headers have been read
+    let show_header_columns = quantizations.iter().any(|group| group.header.is_some());
+
     let quant_items: Vec<ListItem> = quantizations
         .iter()
         .map(|group| {
             let size_str = format_size(group.total_size);
-            let is_downloaded = complete_downloads.contains_key(&group.files[0].filename);
+            let downloaded_parts = group
+                .files
+                .iter()
+                .filter(|f| complete_downloads.contains_key(&f.filename))
+                .count();
+            let is_downloaded = downloaded_parts == group.files.len();
+
+            // Mark column only appears while something is marked
+            let mark = if marked_quants.contains(&group.quant_type) {
+                Span::styled(glyphs().marked, Style::default().fg(Color::Magenta))
+            } else if !marked_quants.is_empty() {
+                Span::raw("  ")
+            } else {
+                Span::raw("")
+            };
 
             let mut spans = vec![
+                mark,
                 Span::raw(format!("{:>10}  ", size_str)),
                 Span::styled(
                     format!("{:<14} ", group.quant_type),
@@ -640,11 +1384,46 @@ fn render_gguf_panels(frame: &mut Frame, chunks: std::rc::Rc<[Rect]>, ctx: GgufP
                 ),
             ];
 
+            if show_header_columns {
+                let header = group.header.clone().unwrap_or_default();
+                let context = header
+                    .context_length
+                    .map(format_context)
+                    .unwrap_or_default();
+                if crate::api::has_unusual_context(quantizations, group) {
+                    // Different context length or RoPE scaling than the other quants
+                    spans.push(Span::styled(
+                        format!("{:>6}! ", context),
+                        Style::default().fg(Color::Yellow),
+                    ));
+                } else {
+                    spans.push(Span::styled(
+                        format!("{:>6}  ", context),
+                        Style::default().fg(Color::DarkGray),
+                    ));
+                }
+                spans.push(Span::styled(
+                    format!("{:<9} ", header.architecture.unwrap_or_default()),
+                    Style::default().fg(Color::DarkGray),
+                ));
+            }
+
+            // Published on another branch than main (e.g. one branch per EXL2 bpw)
+            if group.files.iter().any(|f| f.revision.is_some()) {
+                spans.push(Span::styled("[branch]", Style::default().fg(Color::Blue)));
+            }
+
             if is_downloaded {
                 spans.push(Span::styled(
                     " [downloaded]",
                     Style::default().fg(Color::Green),
                 ));
+            } else if downloaded_parts > 0 {
+                // Split model with only some parts downloaded
+                spans.push(Span::styled(
+                    format!(" [{}/{} parts]", downloaded_parts, group.files.len()),
+                    Style::default().fg(Color::Yellow),
+                ));
             } else {
                 let file_count = if group.files.len() > 1 {
                     format!(" ({} files)", group.files.len())
@@ -664,7 +1443,7 @@ fn render_gguf_panels(frame: &mut Frame, chunks: std::rc::Rc<[Rect]>, ctx: GgufP
 
     let quant_list = List::new(quant_items)
         .block(
-            Block::default()
+            new_block()
                 .borders(Borders::ALL)
                 .title(quant_title)
                 .border_style(get_border_style(FocusedPane::QuantizationGroups)),
@@ -679,6 +1458,13 @@ fn render_gguf_panels(frame: &mut Frame, chunks: std::rc::Rc<[Rect]>, ctx: GgufP
     // Store panel area for click/hover detection
     panel_areas.push((FocusedPane::QuantizationGroups, chunks[0]));
     frame.render_stateful_widget(quant_list, chunks[0], quant_list_state);
+    render_list_scrollbar(
+        frame,
+        chunks[0],
+        true,
+        quantizations.len(),
+        quant_list_state,
+    );
 
     // Right side: Files for selected quantization
     let selected_quant_idx = quant_list_state.selected();
@@ -692,10 +1478,32 @@ fn render_gguf_panels(frame: &mut Frame, chunks: std::rc::Rc<[Rect]>, ctx: GgufP
         Vec::new()
     };
 
+    let selected_header = selected_quant_idx
+        .and_then(|idx| quantizations.get(idx))
+        .and_then(|group| group.header.as_ref());
     let file_title = if files_for_selected.is_empty() {
-        "Files [Select a quantization type]"
+        "Files [Select a quantization type]".to_string()
+    } else if let Some(header) = selected_header {
+        let details: Vec<String> = [
+            header.architecture.clone(),
+            header
+                .context_length
+                .map(|context| format!("{} context", context)),
+            header
+                .rope_scaling
+                .as_ref()
+                .map(|scaling| format!("RoPE {}", scaling)),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        if details.is_empty() {
+            "Files".to_string()
+        } else {
+            format!("Files [{}]", details.join(", "))
+        }
     } else {
-        "Files"
+        "Files".to_string()
     };
 
     let file_items: Vec<ListItem> = files_for_selected
@@ -729,7 +1537,7 @@ fn render_gguf_panels(frame: &mut Frame, chunks: std::rc::Rc<[Rect]>, ctx: GgufP
 
     let file_list = List::new(file_items)
         .block(
-            Block::default()
+            new_block()
                 .borders(Borders::ALL)
                 .title(file_title)
                 .border_style(get_border_style(FocusedPane::QuantizationFiles)),
@@ -744,6 +1552,13 @@ fn render_gguf_panels(frame: &mut Frame, chunks: std::rc::Rc<[Rect]>, ctx: GgufP
     // Store panel area for click/hover detection
     panel_areas.push((FocusedPane::QuantizationFiles, chunks[1]));
     frame.render_stateful_widget(file_list, chunks[1], quant_file_list_state);
+    render_list_scrollbar(
+        frame,
+        chunks[1],
+        true,
+        files_for_selected.len(),
+        quant_file_list_state,
+    );
 }
 
 /// Format bytes as GB, rounding up. Returns empty string for 0 bytes.
@@ -759,29 +1574,17 @@ fn format_remaining_gb(bytes: u64) -> String {
     }
 }
 
-/// Calculate ETA in minutes based on remaining bytes and current speed.
-/// Returns None if speed is zero or negative (download stalled/starting).
+/// Format an ETA in whole minutes, rounded up.
 /// Shows "<1 minute" for very fast downloads.
-fn calculate_eta_minutes(remaining_bytes: u64, speed_mbps: f64) -> Option<String> {
-    if speed_mbps <= 0.0 {
-        return None;
-    }
-
-    // Convert speed from MB/s to bytes/s
-    let speed_bytes_per_sec = speed_mbps * 1_048_576.0;
-
-    // Calculate seconds remaining
-    let seconds_remaining = remaining_bytes as f64 / speed_bytes_per_sec;
-
-    // Convert to minutes, rounding UP
-    let minutes = (seconds_remaining / 60.0).ceil() as u64;
+fn format_eta_minutes(seconds_remaining: u64) -> String {
+    let minutes = seconds_remaining.div_ceil(60);
 
     if minutes == 0 {
-        Some("<1 minute".to_string())
+        "<1 minute".to_string()
     } else if minutes == 1 {
-        Some("1 minute".to_string())
+        "1 minute".to_string()
     } else {
-        Some(format!("{} minutes", minutes))
+        format!("{} minutes", minutes)
     }
 }
 
@@ -805,6 +1608,32 @@ pub fn render_progress_bars(
     }
 }
 
+/// "model: file" for a progress box title of `width` columns; the model ID
+/// loses its middle and the file name its start when they don't fit
+fn progress_title(model_id: &str, filename: &str, width: usize) -> String {
+    let model = crate::utils::truncate_middle(model_id, width / 2);
+    let room = if model.is_empty() {
+        width
+    } else {
+        width.saturating_sub(model.chars().count() + 2)
+    };
+    let file_chars = filename.chars().count();
+    let file = if file_chars > room {
+        let tail: String = filename
+            .chars()
+            .skip(file_chars - room.saturating_sub(3))
+            .collect();
+        format!("...{}", tail)
+    } else {
+        filename.to_string()
+    };
+    if model.is_empty() {
+        file
+    } else {
+        format!("{}: {}", model, file)
+    }
+}
+
 /// Render download progress bar in top-right corner
 fn render_download_progress(
     frame: &mut Frame,
@@ -844,8 +1673,8 @@ fn render_download_progress(
     let total_remaining = current_remaining + queue_bytes;
     let remaining_str = format_remaining_gb(total_remaining);
 
-    // Calculate ETA based on current speed and total remaining
-    let eta_str = calculate_eta_minutes(total_remaining, progress.speed_mbps);
+    // ETA of the whole queue at the smoothed speed (None while starting/stalled)
+    let eta_str = progress.eta_secs(queue_bytes).map(format_eta_minutes);
 
     // Title with queue info, remaining size, and ETA
     let title = match (queue_size > 0, !remaining_str.is_empty(), eta_str) {
@@ -879,7 +1708,11 @@ fn render_download_progress(
         _ => "Downloading".to_string(),
     };
 
-    // Label with speed and rate limit indicator
+    // Label with speed, rate limit indicator and this file's ETA
+    let file_eta = progress
+        .eta_secs(0)
+        .map(|secs| format!(" - {} left", crate::utils::format_duration(secs)))
+        .unwrap_or_default();
     let label = if progress.speed_mbps > 0.0 {
         use std::sync::atomic::Ordering;
         let rate_limited = crate::download::DOWNLOAD_CONFIG
@@ -891,14 +1724,17 @@ fn render_download_progress(
                 .load(Ordering::Relaxed);
             let limit_mbps = limit_bytes as f64 / 1_048_576.0;
             format!(
-                "{}% - {:.1}/{:.1} MB/s",
-                percentage, progress.speed_mbps, limit_mbps
+                "{}% - {:.1}/{:.1} MB/s{}",
+                percentage, progress.speed_mbps, limit_mbps, file_eta
             )
         } else {
-            format!("{}% - {:.2} MB/s", percentage, progress.speed_mbps)
+            format!(
+                "{}% - {:.2} MB/s{}",
+                percentage, progress.speed_mbps, file_eta
+            )
         }
     } else {
-        format!("{}%", percentage)
+        format!("{}%{}", percentage, file_eta)
     };
 
     // Overall progress gauge
@@ -909,8 +1745,19 @@ fn render_download_progress(
         height: 3,
     };
 
+    // Which repository's file, since many repositories name their files alike
+    let file_title = progress_title(
+        &progress.model_id,
+        &progress.filename,
+        overall_area.width.saturating_sub(2) as usize,
+    );
     let gauge = Gauge::default()
-        .block(Block::default().borders(Borders::ALL).title(title))
+        .block(
+            new_block()
+                .borders(Borders::ALL)
+                .title(title)
+                .title_bottom(file_title),
+        )
         .gauge_style(Style::default().fg(Color::Cyan).bg(Color::Black))
         .percent(percentage)
         .label(label);
@@ -926,9 +1773,7 @@ fn render_download_progress(
             height: num_active as u16 + 2,
         };
 
-        let chunks_block = Block::default()
-            .borders(Borders::ALL)
-            .title("Active Chunks");
+        let chunks_block = new_block().borders(Borders::ALL).title("Active Chunks");
 
         let inner_area = chunks_block.inner(chunks_area);
         frame.render_widget(chunks_block, chunks_area);
@@ -992,18 +1837,45 @@ fn render_verification_progress(
 
     frame.render_widget(Clear, area);
 
-    // Title with queue info
-    let title = if queue_size > 0 {
-        format!("Verifying ({} queued)", queue_size)
-    } else {
-        "Verifying".to_string()
-    };
-
-    // Main container block
-    let block = Block::default()
-        .borders(Borders::ALL)
-        .title(title)
-        .border_style(Style::default().fg(Color::Green));
+    // Title with combined progress of parallel verifications and queue info
+    let mut title = "Verifying".to_string();
+    if verifications.len() > 1 {
+        let verified: u64 = verifications
+            .iter()
+            .map(|v| v.verified_bytes.load(Ordering::Relaxed))
+            .sum();
+        let total: u64 = verifications.iter().map(|v| v.total_bytes).sum();
+        let speed: f64 = verifications.iter().map(|v| v.speed_mbps).sum();
+        let percent = if total > 0 {
+            (verified as f64 / total as f64 * 100.0) as u16
+        } else {
+            0
+        };
+        title.push_str(&format!(
+            " {} files {}% {:.1} MB/s",
+            verifications.len(),
+            percent,
+            speed
+        ));
+    }
+    if queue_size > 0 {
+        title.push_str(&format!(" ({} queued)", queue_size));
+    }
+    match crate::verification::VERIFICATION_CONFIG.schedule() {
+        crate::verification::VerificationSchedule::Immediate => {}
+        crate::verification::VerificationSchedule::Deprioritized => {
+            title.push_str(" [low priority]")
+        }
+        crate::verification::VerificationSchedule::AfterDownloads => {
+            title.push_str(" [after downloads]")
+        }
+    }
+
+    // Main container block
+    let block = new_block()
+        .borders(Borders::ALL)
+        .title(title)
+        .border_style(Style::default().fg(Color::Green));
 
     let inner = block.inner(area);
     frame.render_widget(block, area);
@@ -1030,17 +1902,16 @@ fn render_verification_progress(
             0
         };
 
-        // Truncate filename to fit (show end of filename)
-        let display_name = if ver.filename.len() > 35 {
-            format!("...{}", &ver.filename[ver.filename.len() - 32..])
-        } else {
-            ver.filename.clone()
-        };
+        let display_name = progress_title(
+            &ver.model_id,
+            &ver.filename,
+            ver_area.width.saturating_sub(2) as usize,
+        );
 
         let label = format!("{}%", percentage);
 
         let gauge = Gauge::default()
-            .block(Block::default().borders(Borders::ALL).title(display_name))
+            .block(new_block().borders(Borders::ALL).title(display_name))
             .gauge_style(Style::default().fg(Color::Green).bg(Color::Black))
             .percent(percentage)
             .label(label);
@@ -1052,10 +1923,13 @@ fn render_verification_progress(
 pub fn render_resume_popup(
     frame: &mut Frame,
     incomplete_downloads: &[crate::models::DownloadMetadata],
+    queued_downloads: &[crate::models::QueuedDownload],
 ) {
+    let total = incomplete_downloads.len() + queued_downloads.len();
+
     // Calculate centered popup area
     let popup_width = 70.min(frame.area().width.saturating_sub(4));
-    let popup_height = 10 + incomplete_downloads.len().min(5) as u16;
+    let popup_height = 10 + total.min(5) as u16;
     let popup_x = (frame.area().width.saturating_sub(popup_width)) / 2;
     let popup_y = (frame.area().height.saturating_sub(popup_height)) / 2;
 
@@ -1070,7 +1944,7 @@ pub fn render_resume_popup(
     frame.render_widget(Clear, popup_area);
 
     // Render popup background
-    let popup_block = Block::default()
+    let popup_block = new_block()
         .borders(Borders::ALL)
         .title("Resume Incomplete Downloads?")
         .style(Style::default().fg(Color::Yellow).bg(Color::Black));
@@ -1085,37 +1959,57 @@ pub fn render_resume_popup(
         height: 2,
     };
 
-    let message = Paragraph::new(format!(
-        "Found {} incomplete download(s):\n",
-        incomplete_downloads.len()
-    ))
-    .style(Style::default().fg(Color::White));
+    let message = if queued_downloads.is_empty() {
+        format!(
+            "Found {} incomplete download(s):\n",
+            incomplete_downloads.len()
+        )
+    } else {
+        format!(
+            "Found {} incomplete and {} queued download(s):\n",
+            incomplete_downloads.len(),
+            queued_downloads.len()
+        )
+    };
+    let message = Paragraph::new(message).style(Style::default().fg(Color::White));
 
     frame.render_widget(message, message_area);
 
-    // Render list of incomplete files (up to 5)
+    // Render list of incomplete, then queued files (up to 5)
     let list_area = Rect {
         x: popup_area.x + 2,
         y: popup_area.y + 3,
         width: popup_area.width.saturating_sub(4),
-        height: incomplete_downloads.len().min(5) as u16,
+        height: total.min(5) as u16,
     };
 
     let file_lines: Vec<Line> = incomplete_downloads
         .iter()
-        .take(5)
         .map(|metadata| {
             let progress_pct = if metadata.total_size > 0 {
                 (metadata.downloaded_size as f64 / metadata.total_size as f64 * 100.0) as u64
             } else {
                 0
             };
+            let state = if metadata.status == crate::models::DownloadStatus::Changed {
+                Span::styled(" (changed on disk)", Style::default().fg(Color::Red))
+            } else {
+                Span::raw(format!(" ({}%)", progress_pct))
+            };
             Line::from(vec![
-                Span::raw("  • "),
+                Span::raw(glyphs().bullet),
                 Span::styled(&metadata.filename, Style::default().fg(Color::Cyan)),
-                Span::raw(format!(" ({}%)", progress_pct)),
+                state,
             ])
         })
+        .chain(queued_downloads.iter().map(|queued| {
+            Line::from(vec![
+                Span::raw(glyphs().bullet),
+                Span::styled(&queued.filename, Style::default().fg(Color::Cyan)),
+                Span::styled(" (queued)", Style::default().fg(Color::DarkGray)),
+            ])
+        }))
+        .take(5)
         .collect();
 
     let files_widget = Paragraph::new(file_lines).style(Style::default().fg(Color::White));
@@ -1123,7 +2017,7 @@ pub fn render_resume_popup(
     frame.render_widget(files_widget, list_area);
 
     // Show "and X more..." if there are more than 5
-    if incomplete_downloads.len() > 5 {
+    if total > 5 {
         let more_area = Rect {
             x: popup_area.x + 2,
             y: list_area.y + list_area.height,
@@ -1132,7 +2026,7 @@ pub fn render_resume_popup(
         };
 
         let more_text =
-            Paragraph::new(format!("  ... and {} more", incomplete_downloads.len() - 5))
+            Paragraph::new(format!("  ... and {} more", total - 5))
                 .style(Style::default().fg(Color::DarkGray));
 
         frame.render_widget(more_text, more_area);
@@ -1174,9 +2068,9 @@ pub fn render_resume_popup(
 }
 
 /// Render search popup dialog
-pub fn render_search_popup(frame: &mut Frame, input: &Input) {
+pub fn render_search_popup(frame: &mut Frame, input: &Input, full_text: bool) {
     let popup_width = 60.min(frame.area().width.saturating_sub(4));
-    let popup_height = 8;
+    let popup_height = 9;
     let popup_x = (frame.area().width.saturating_sub(popup_width)) / 2;
     let popup_y = (frame.area().height.saturating_sub(popup_height)) / 2;
     let area = Rect {
@@ -1189,9 +2083,14 @@ pub fn render_search_popup(frame: &mut Frame, input: &Input) {
     // Clear the area
     frame.render_widget(Clear, area);
 
-    let block = Block::default()
+    let title = if full_text {
+        " Search HuggingFace Models [card text] "
+    } else {
+        " Search HuggingFace Models "
+    };
+    let block = new_block()
         .borders(Borders::ALL)
-        .title(" Search HuggingFace Models ")
+        .title(title)
         .style(Style::default().fg(Color::Cyan));
 
     let inner = block.inner(area);
@@ -1215,6 +2114,11 @@ pub fn render_search_popup(frame: &mut Frame, input: &Input) {
     let help = [
         "",
         "Enter search query and press Enter to search",
+        if full_text {
+            "Tab: match model names (now: model card text)"
+        } else {
+            "Tab: match model card text (now: model names)"
+        },
         "ESC: Cancel",
     ];
 
@@ -1230,78 +2134,1417 @@ pub fn render_search_popup(frame: &mut Frame, input: &Input) {
     }
 }
 
-pub fn render_download_path_popup(frame: &mut Frame, download_path_input: &Input) {
-    // Calculate centered popup area
-    let popup_width = 60.min(frame.area().width.saturating_sub(4));
-    let popup_height = 7;
+/// Render open-by-URL popup dialog
+pub fn render_notes_popup(
+    frame: &mut Frame,
+    model_id: &str,
+    tags_input: &Input,
+    note_input: &Input,
+    focused_field: usize,
+) {
+    let popup_width = 80.min(frame.area().width.saturating_sub(4));
+    let popup_height = 11;
     let popup_x = (frame.area().width.saturating_sub(popup_width)) / 2;
     let popup_y = (frame.area().height.saturating_sub(popup_height)) / 2;
+    let area = Rect {
+        x: popup_x,
+        y: popup_y,
+        width: popup_width,
+        height: popup_height,
+    };
 
-    let popup_area = Rect {
+    // Clear the area
+    frame.render_widget(Clear, area);
+
+    let block = new_block()
+        .borders(Borders::ALL)
+        .title(format!(" Notes: {} ", model_id))
+        .style(Style::default().fg(Color::Cyan));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    // Label + input per field; the focused one is highlighted and gets the cursor
+    let fields = [("Tags:", tags_input), ("Note:", note_input)];
+    for (i, (label, input)) in fields.iter().enumerate() {
+        let y = inner.y + 1 + 2 * i as u16;
+        let focused = i == focused_field;
+
+        let label_area = Rect {
+            x: inner.x + 2,
+            y,
+            width: 6,
+            height: 1,
+        };
+        let label_style = if focused {
+            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::White)
+        };
+        frame.render_widget(Paragraph::new(*label).style(label_style), label_area);
+
+        let input_area = Rect {
+            x: inner.x + 8,
+            y,
+            width: inner.width.saturating_sub(10),
+            height: 1,
+        };
+        let scroll = input.visual_scroll(input_area.width as usize);
+        let input_widget = Paragraph::new(input.value())
+            .style(Style::default().fg(if focused { Color::Yellow } else { Color::Gray }))
+            .scroll((0, scroll as u16));
+        frame.render_widget(input_widget, input_area);
+
+        if focused {
+            frame.set_cursor_position((
+                input_area.x + (input.visual_cursor().saturating_sub(scroll)) as u16,
+                input_area.y,
+            ));
+        }
+    }
+
+    // Help text
+    let help = [
+        "Tags are comma separated, e.g. coding, deprecated",
+        "Search #tag to list models by local tags and notes",
+        "Tab: Switch field   Enter: Save   ESC: Cancel",
+    ];
+
+    for (i, line) in help.iter().enumerate() {
+        let area = Rect {
+            x: inner.x + 2,
+            y: inner.y + 5 + i as u16,
+            width: inner.width - 4,
+            height: 1,
+        };
+        let widget = Paragraph::new(*line).style(Style::default().fg(Color::DarkGray));
+        frame.render_widget(widget, area);
+    }
+}
+
+pub fn render_open_model_popup(frame: &mut Frame, input: &Input) {
+    let popup_width = 80.min(frame.area().width.saturating_sub(4));
+    let popup_height = 9;
+    let popup_x = (frame.area().width.saturating_sub(popup_width)) / 2;
+    let popup_y = (frame.area().height.saturating_sub(popup_height)) / 2;
+    let area = Rect {
         x: popup_x,
         y: popup_y,
         width: popup_width,
         height: popup_height,
     };
 
-    // Clear the popup area first to remove any underlying content
-    frame.render_widget(Clear, popup_area);
+    // Clear the area
+    frame.render_widget(Clear, area);
+
+    let block = new_block()
+        .borders(Borders::ALL)
+        .title(" Open Model or URL ")
+        .style(Style::default().fg(Color::Cyan));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    // Input field (scrolls horizontally so long URLs stay editable)
+    let input_area = Rect {
+        x: inner.x + 2,
+        y: inner.y + 1,
+        width: inner.width - 4,
+        height: 1,
+    };
+
+    let scroll = input.visual_scroll(input_area.width as usize);
+    let input_widget = Paragraph::new(input.value())
+        .style(Style::default().fg(Color::Yellow))
+        .scroll((0, scroll as u16));
+    frame.render_widget(input_widget, input_area);
+
+    // Show cursor
+    frame.set_cursor_position((
+        input_area.x + (input.visual_cursor().saturating_sub(scroll)) as u16,
+        input_area.y,
+    ));
+
+    // Help text
+    let help = [
+        "",
+        "Paste author/model or a huggingface.co model, blob or resolve URL",
+        "File URLs select the file and open the download dialog",
+        "Enter: Open   ESC: Cancel",
+    ];
+
+    for (i, line) in help.iter().enumerate() {
+        let area = Rect {
+            x: inner.x + 2,
+            y: inner.y + 3 + i as u16,
+            width: inner.width - 4,
+            height: 1,
+        };
+        let widget = Paragraph::new(*line).style(Style::default().fg(Color::DarkGray));
+        frame.render_widget(widget, area);
+    }
+}
+
+/// Render the ':' command palette
+pub fn render_command_palette(
+    frame: &mut Frame,
+    input: &Input,
+    matches: &[&crate::ui::app::actions::ActionEntry],
+    selected: usize,
+) {
+    let popup_width = 64.min(frame.area().width.saturating_sub(4));
+    let popup_height = 20.min(frame.area().height.saturating_sub(2));
+    let area = Rect {
+        x: (frame.area().width.saturating_sub(popup_width)) / 2,
+        y: (frame.area().height.saturating_sub(popup_height)) / 3,
+        width: popup_width,
+        height: popup_height,
+    };
+
+    frame.render_widget(Clear, area);
+
+    let block = new_block()
+        .borders(Borders::ALL)
+        .title(" Command Palette ")
+        .style(Style::default().fg(Color::Cyan).bg(Color::Black));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2), // Query
+            Constraint::Min(1),    // Matches
+            Constraint::Length(1), // Help
+        ])
+        .split(inner);
+
+    let prompt = Paragraph::new(Line::from(vec![
+        Span::styled(": ", Style::default().fg(Color::Cyan)),
+        Span::styled(input.value(), Style::default().fg(Color::Yellow)),
+    ]));
+    frame.render_widget(prompt, rows[0]);
+    frame.set_cursor_position((rows[0].x + 2 + input.visual_cursor() as u16, rows[0].y));
+
+    let key_width = 12;
+    let items: Vec<ListItem> = if matches.is_empty() {
+        vec![ListItem::new(Span::styled(
+            "No matching actions",
+            Style::default().fg(Color::DarkGray),
+        ))]
+    } else {
+        matches
+            .iter()
+            .map(|entry| {
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        format!("{:<width$}", display_text(entry.keys), width = key_width),
+                        Style::default().fg(Color::Green),
+                    ),
+                    Span::styled(entry.name, Style::default().fg(Color::White)),
+                ]))
+            })
+            .collect()
+    };
+
+    let mut state = ListState::default();
+    if !matches.is_empty() {
+        state.select(Some(selected.min(matches.len() - 1)));
+    }
+    let list = List::new(items)
+        .highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol(selection_symbol());
+    frame.render_stateful_widget(list, rows[1], &mut state);
+    render_list_scrollbar(frame, rows[1], false, matches.len(), &state);
+
+    let help = Paragraph::new(format!(
+        "Type to filter  {}: Select  Enter: Run  Esc: Close",
+        glyphs().up_down
+    ))
+    .style(Style::default().fg(Color::DarkGray));
+    frame.render_widget(help, rows[2]);
+}
+
+/// Render the download history statistics popup
+pub fn render_stats_popup(frame: &mut Frame, stats: &crate::history::HistoryStats) {
+    const MAX_DAYS: usize = 7;
+
+    let popup_width = 70.min(frame.area().width.saturating_sub(4));
+    let popup_height = 24.min(frame.area().height.saturating_sub(2));
+    let area = Rect {
+        x: (frame.area().width.saturating_sub(popup_width)) / 2,
+        y: (frame.area().height.saturating_sub(popup_height)) / 2,
+        width: popup_width,
+        height: popup_height,
+    };
+
+    frame.render_widget(Clear, area);
+
+    let block = new_block()
+        .borders(Borders::ALL)
+        .title(" Download Statistics (Esc to close) ")
+        .style(Style::default().fg(Color::Cyan).bg(Color::Black));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let label = Style::default().fg(Color::Yellow);
+    let heading = Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD);
+    let dim = Style::default().fg(Color::DarkGray);
+
+    if stats.downloads == 0 {
+        frame.render_widget(
+            Paragraph::new("No downloads recorded yet.").style(dim),
+            inner,
+        );
+        return;
+    }
+
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled("Downloaded:      ", label),
+            Span::raw(format!(
+                "{} file(s), {}",
+                stats.downloads,
+                format_size(stats.total_bytes)
+            )),
+        ]),
+        Line::from(vec![
+            Span::styled("Average speed:   ", label),
+            Span::raw(format!("{:.1} MB/s", stats.avg_speed_mbps)),
+        ]),
+        Line::from(vec![
+            Span::styled("Per active day:  ", label),
+            Span::raw(format_size(stats.avg_bytes_per_active_day)),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled("Recent days", heading)),
+    ];
+    for day in stats.days.iter().take(MAX_DAYS) {
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {}", day.date), dim),
+            Span::raw(format!(
+                "  {:>4} file(s)  {:>10}  {:>7.1} MB/s",
+                day.downloads,
+                format_size(day.bytes),
+                day.avg_speed_mbps
+            )),
+        ]));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled("Biggest models", heading)));
+    for model in &stats.biggest_models {
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("  {:>10}  ", format_size(model.bytes)),
+                Style::default().fg(Color::Green),
+            ),
+            Span::raw(model.model_id.clone()),
+        ]));
+    }
+
+    frame.render_widget(Paragraph::new(lines), inner);
+}
+
+/// Render the author/organization profile ('A'): totals and recent uploads
+pub fn render_author_profile_popup(frame: &mut Frame, profile: &crate::models::AuthorProfile) {
+    const RECENT_UPLOADS: usize = 8;
+
+    let popup_width = 70.min(frame.area().width.saturating_sub(4));
+    let popup_height = 20.min(frame.area().height.saturating_sub(2));
+    let area = Rect {
+        x: (frame.area().width.saturating_sub(popup_width)) / 2,
+        y: (frame.area().height.saturating_sub(popup_height)) / 2,
+        width: popup_width,
+        height: popup_height,
+    };
+
+    frame.render_widget(Clear, area);
+
+    let block = new_block()
+        .borders(Borders::ALL)
+        .title(format!(" Author: {} (Esc to close) ", profile.name))
+        .style(Style::default().fg(Color::Cyan).bg(Color::Black));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let label = Style::default().fg(Color::Yellow);
+    let heading = Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD);
+    let dim = Style::default().fg(Color::DarkGray);
+
+    let mut lines = Vec::new();
+    if let Some(ref fullname) = profile.fullname {
+        lines.push(Line::from(vec![
+            Span::styled("Name:        ", label),
+            Span::raw(fullname.clone()),
+        ]));
+    }
+    if let Some(is_org) = profile.is_org {
+        lines.push(Line::from(vec![
+            Span::styled("Type:        ", label),
+            Span::raw(if is_org { "Organization" } else { "User" }),
+        ]));
+    }
+    if let Some(followers) = profile.followers {
+        lines.push(Line::from(vec![
+            Span::styled("Followers:   ", label),
+            Span::raw(format_number(followers)),
+        ]));
+    }
+    lines.push(Line::from(vec![
+        Span::styled("Models:      ", label),
+        Span::raw(format_number(profile.models.len() as u64)),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled("Total likes: ", label),
+        Span::raw(format_number(profile.total_likes())),
+        Span::styled(
+            format!(
+                "  ({}{} downloads)",
+                glyphs().downloads,
+                format_number(profile.total_downloads())
+            ),
+            dim,
+        ),
+    ]));
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled("Recent uploads", heading)));
+    let recent = profile.recent_uploads(RECENT_UPLOADS);
+    if recent.is_empty() {
+        lines.push(Line::from(Span::styled("  No public models", dim)));
+    }
+    for model in recent {
+        let date = model
+            .created_at
+            .as_deref()
+            .or(model.last_modified.as_deref())
+            .and_then(crate::utils::format_relative_date)
+            .unwrap_or_default();
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {:<13}  ", date), dim),
+            Span::raw(model.id.clone()),
+        ]));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "Enter: list all models in the Models pane",
+        dim,
+    )));
+
+    frame.render_widget(Paragraph::new(lines), inner);
+}
+
+/// Render the dry-run download plan ('D'): files, target paths and sizes
+pub fn render_download_plan_popup(
+    frame: &mut Frame,
+    plan: &[crate::models::PlannedDownload],
+    scroll: u16,
+) {
+    let popup_width = 100.min(frame.area().width.saturating_sub(4));
+    let popup_height = 24.min(frame.area().height.saturating_sub(2));
+    let area = Rect {
+        x: (frame.area().width.saturating_sub(popup_width)) / 2,
+        y: (frame.area().height.saturating_sub(popup_height)) / 2,
+        width: popup_width,
+        height: popup_height,
+    };
+
+    frame.render_widget(Clear, area);
+
+    let block = new_block()
+        .borders(Borders::ALL)
+        .title(" Download Plan (dry run - nothing is queued) ")
+        .style(Style::default().fg(Color::Cyan).bg(Color::Black));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2),
+            Constraint::Min(1),
+            Constraint::Length(1),
+        ])
+        .split(inner);
+
+    let total: u64 = plan.iter().map(|p| p.size).sum();
+    let existing = plan.iter().filter(|p| p.exists).count();
+    let mut summary = vec![
+        Span::styled("Files: ", Style::default().fg(Color::Yellow)),
+        Span::raw(format!("{}   ", plan.len())),
+        Span::styled("Total size: ", Style::default().fg(Color::Yellow)),
+        Span::raw(format_size(total)),
+    ];
+    if existing > 0 {
+        summary.push(Span::styled(
+            format!("   ({} already on disk)", existing),
+            Style::default().fg(Color::Green),
+        ));
+    }
+    frame.render_widget(Paragraph::new(Line::from(summary)), rows[0]);
+
+    let lines: Vec<Line> = plan
+        .iter()
+        .map(|p| {
+            let mut spans = vec![
+                Span::styled(
+                    format!("{:>10}  ", format_size(p.size)),
+                    Style::default().fg(Color::Green),
+                ),
+                Span::styled(
+                    p.local_path.display().to_string(),
+                    Style::default().fg(Color::White),
+                ),
+            ];
+            if p.exists {
+                spans.push(Span::styled(
+                    " [on disk]",
+                    Style::default().fg(Color::DarkGray),
+                ));
+            }
+            Line::from(spans)
+        })
+        .collect();
+
+    let max_scroll = (lines.len() as u16).saturating_sub(rows[1].height);
+    frame.render_widget(Paragraph::new(lines).scroll((scroll.min(max_scroll), 0)), rows[1]);
+
+    let help = Paragraph::new("Paths use the default directory  j/k: Scroll  Esc: Close")
+        .style(Style::default().fg(Color::DarkGray));
+    frame.render_widget(help, rows[2]);
+}
+
+/// Render the chat template(s) and special tokens of a model ('t')
+pub fn render_prompt_format_popup(
+    frame: &mut Frame,
+    format: &crate::models::PromptFormat,
+    scroll: u16,
+) {
+    let popup_width = 100.min(frame.area().width.saturating_sub(4));
+    let popup_height = 32.min(frame.area().height.saturating_sub(2));
+    let area = Rect {
+        x: (frame.area().width.saturating_sub(popup_width)) / 2,
+        y: (frame.area().height.saturating_sub(popup_height)) / 2,
+        width: popup_width,
+        height: popup_height,
+    };
+
+    frame.render_widget(Clear, area);
+
+    let block = new_block()
+        .borders(Borders::ALL)
+        .title(format!(" Prompt Format ({}) ", format.source))
+        .style(Style::default().fg(Color::Cyan).bg(Color::Black));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(inner);
+
+    let label = Style::default().fg(Color::Yellow);
+    let heading = Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD);
+    let dim = Style::default().fg(Color::DarkGray);
+
+    let mut lines = vec![Line::from(Span::styled("Special tokens", heading))];
+    if format.special_tokens.is_empty() {
+        lines.push(Line::from(Span::styled("  none declared", dim)));
+    }
+    for (role, token) in &format.special_tokens {
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {:<6}", role), label),
+            // Escaped so newline / control tokens stay visible
+            Span::styled(token.escape_debug().to_string(), Style::default().fg(Color::Green)),
+        ]));
+    }
+
+    if format.chat_templates.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled("Chat template", heading)));
+        lines.push(Line::from(Span::styled(
+            "  none - prompts are plain text (base model?)",
+            dim,
+        )));
+    }
+    // Templates are shown verbatim, wrapped by hand so scrolling reaches the end
+    let width = usize::from(rows[0].width.max(1));
+    for (name, template) in &format.chat_templates {
+        lines.push(Line::from(""));
+        let title = if name == "default" {
+            "Chat template".to_string()
+        } else {
+            format!("Chat template: {}", name)
+        };
+        lines.push(Line::from(Span::styled(title, heading)));
+        for line in template.lines() {
+            let chars: Vec<char> = line.replace('\t', "    ").chars().collect();
+            if chars.is_empty() {
+                lines.push(Line::from(""));
+            }
+            for chunk in chars.chunks(width) {
+                lines.push(Line::from(Span::styled(
+                    chunk.iter().collect::<String>(),
+                    Style::default().fg(Color::White),
+                )));
+            }
+        }
+    }
+
+    let max_scroll = (lines.len() as u16).saturating_sub(rows[0].height);
+    frame.render_widget(Paragraph::new(lines).scroll((scroll.min(max_scroll), 0)), rows[0]);
+
+    let help = Paragraph::new("j/k PgDn/PgUp: Scroll  Esc: Close").style(dim);
+    frame.render_widget(help, rows[1]);
+}
+
+/// Render the list of watched models updated since the last check
+pub fn render_watch_updates_popup(
+    frame: &mut Frame,
+    updates: &[crate::watchlist::WatchUpdate],
+    scroll: u16,
+) {
+    use crate::watchlist::FileChangeKind;
+
+    let popup_width = 90.min(frame.area().width.saturating_sub(4));
+    let popup_height = 24.min(frame.area().height.saturating_sub(2));
+    let area = Rect {
+        x: (frame.area().width.saturating_sub(popup_width)) / 2,
+        y: (frame.area().height.saturating_sub(popup_height)) / 2,
+        width: popup_width,
+        height: popup_height,
+    };
+
+    frame.render_widget(Clear, area);
+
+    let block = new_block()
+        .borders(Borders::ALL)
+        .title(format!(" {} Watched Model(s) Updated ", updates.len()))
+        .style(Style::default().fg(Color::Cyan).bg(Color::Black));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(inner);
+
+    let mut lines = Vec::new();
+    for update in updates {
+        if !lines.is_empty() {
+            lines.push(Line::from(""));
+        }
+        let modified = update
+            .last_modified
+            .as_deref()
+            .and_then(|m| {
+                Some(format!(
+                    "{} ({})",
+                    crate::utils::format_relative_date(m)?,
+                    crate::utils::format_absolute_date(m)?
+                ))
+            })
+            .unwrap_or_else(|| "unknown".to_string());
+        lines.push(Line::from(vec![
+            Span::styled(
+                update.model_id.clone(),
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!("  modified {}", modified),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]));
+        if update.changes.is_empty() {
+            lines.push(Line::from(Span::styled(
+                "  (no file changes)",
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+        for change in &update.changes {
+            let (marker, color) = match change.kind {
+                FileChangeKind::Added => ("+", Color::Green),
+                FileChangeKind::Removed => ("-", Color::Red),
+                FileChangeKind::Modified => ("~", Color::Yellow),
+            };
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {} ", marker), Style::default().fg(color)),
+                Span::styled(change.path.clone(), Style::default().fg(Color::White)),
+            ]));
+        }
+        if let Some(commit) = &update.downloaded_commit {
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "  Your download (commit {}): {} file(s) changed since",
+                    crate::utils::short_commit(commit),
+                    update.since_download.len()
+                ),
+                Style::default().fg(Color::Cyan),
+            )));
+            for change in &update.since_download {
+                let (marker, color) = match change.kind {
+                    FileChangeKind::Added => ("+", Color::Green),
+                    FileChangeKind::Removed => ("-", Color::Red),
+                    FileChangeKind::Modified => ("~", Color::Yellow),
+                };
+                lines.push(Line::from(vec![
+                    Span::styled(format!("    {} ", marker), Style::default().fg(color)),
+                    Span::styled(change.path.clone(), Style::default().fg(Color::White)),
+                ]));
+            }
+        }
+    }
+
+    let max_scroll = (lines.len() as u16).saturating_sub(rows[0].height);
+    frame.render_widget(Paragraph::new(lines).scroll((scroll.min(max_scroll), 0)), rows[0]);
+
+    let help =
+        Paragraph::new("j/k: Scroll  Esc: Close").style(Style::default().fg(Color::DarkGray));
+    frame.render_widget(help, rows[1]);
+}
+
+/// Render the hidden models and authors, with the selected one highlighted
+pub fn render_ignore_list_popup(
+    frame: &mut Frame,
+    entries: &[crate::ignorelist::IgnoredEntry],
+    selected: usize,
+) {
+    use crate::ignorelist::IgnoredEntry;
+
+    let popup_width = 70.min(frame.area().width.saturating_sub(4));
+    let popup_height = (entries.len() as u16 + 3)
+        .clamp(6, 24)
+        .min(frame.area().height.saturating_sub(2));
+    let area = Rect {
+        x: (frame.area().width.saturating_sub(popup_width)) / 2,
+        y: (frame.area().height.saturating_sub(popup_height)) / 2,
+        width: popup_width,
+        height: popup_height,
+    };
+
+    frame.render_widget(Clear, area);
+
+    let block = new_block()
+        .borders(Borders::ALL)
+        .title(format!(" {} Hidden from Searches ", entries.len()))
+        .style(Style::default().fg(Color::Cyan).bg(Color::Black));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(inner);
+
+    let items: Vec<ListItem> = entries
+        .iter()
+        .map(|entry| {
+            let kind = match entry {
+                IgnoredEntry::Model(_) => "model ",
+                IgnoredEntry::Author(_) => "author",
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("{}  ", kind), Style::default().fg(Color::DarkGray)),
+                Span::styled(entry.label(), Style::default().fg(Color::White)),
+            ]))
+        })
+        .collect();
+
+    let mut state = ListState::default();
+    if !entries.is_empty() {
+        state.select(Some(selected.min(entries.len() - 1)));
+    }
+    let list = List::new(items)
+        .highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol(selection_symbol());
+    frame.render_stateful_widget(list, rows[0], &mut state);
+    render_list_scrollbar(frame, rows[0], false, entries.len(), &state);
+
+    let help = Paragraph::new("j/k: Select  Enter/d: Unhide  Esc: Close")
+        .style(Style::default().fg(Color::DarkGray));
+    frame.render_widget(help, rows[1]);
+}
+
+/// Render side-by-side comparison of two quantization groups
+pub fn render_quant_diff_popup(frame: &mut Frame, left: &QuantizationGroup, right: &QuantizationGroup) {
+    const MAX_FILES: usize = 8;
+
+    let popup_width = 100.min(frame.area().width.saturating_sub(4));
+    let file_rows = left.files.len().max(right.files.len()).min(MAX_FILES) as u16;
+    let popup_height = (file_rows + 12).min(frame.area().height.saturating_sub(2));
+    let area = Rect {
+        x: (frame.area().width.saturating_sub(popup_width)) / 2,
+        y: (frame.area().height.saturating_sub(popup_height)) / 2,
+        width: popup_width,
+        height: popup_height,
+    };
+
+    frame.render_widget(Clear, area);
+
+    let block = new_block()
+        .borders(Borders::ALL)
+        .title(format!(" Compare {} vs {} ", left.quant_type, right.quant_type))
+        .style(Style::default().fg(Color::Cyan).bg(Color::Black));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(3),    // File columns
+            Constraint::Length(5), // Summary
+            Constraint::Length(1), // Help
+        ])
+        .split(inner);
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(rows[0]);
+
+    for (group, column) in [(left, columns[0]), (right, columns[1])] {
+        let mut lines = vec![Line::from(vec![
+            Span::styled(
+                group.quant_type.clone(),
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!(" ({} file{})", group.files.len(), if group.files.len() == 1 { "" } else { "s" }),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ])];
+        for file in group.files.iter().take(MAX_FILES) {
+            let name = file.filename.rsplit('/').next().unwrap_or(&file.filename);
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("{:>10} ", format_size(file.size)),
+                    Style::default().fg(Color::Green),
+                ),
+                Span::styled(name.to_string(), Style::default().fg(Color::White)),
+            ]));
+        }
+        if group.files.len() > MAX_FILES {
+            lines.push(Line::from(Span::styled(
+                format!("  ... and {} more", group.files.len() - MAX_FILES),
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+        frame.render_widget(
+            Paragraph::new(lines).block(new_block().borders(Borders::BOTTOM)),
+            column,
+        );
+    }
+
+    // Size delta relative to the group marked first
+    let delta = right.total_size as i128 - left.total_size as i128;
+    let sign = if delta >= 0 { "+" } else { "-" };
+    let delta_pct = if left.total_size > 0 {
+        delta as f64 / left.total_size as f64 * 100.0
+    } else {
+        0.0
+    };
+    let delta_color = if delta > 0 { Color::Red } else { Color::Green };
+
+    let label = |text: &'static str| Span::styled(text, Style::default().fg(Color::Yellow));
+    let summary = vec![
+        Line::from(vec![
+            label("Total size:    "),
+            Span::raw(format!(
+                "{}  vs  {}",
+                format_size(left.total_size),
+                format_size(right.total_size)
+            )),
+        ]),
+        Line::from(vec![
+            label("Difference:    "),
+            Span::styled(
+                format!(
+                    "{}{} ({}{:.1}%)",
+                    sign,
+                    format_size(delta.unsigned_abs() as u64),
+                    if delta_pct >= 0.0 { "+" } else { "" },
+                    delta_pct
+                ),
+                Style::default().fg(delta_color),
+            ),
+        ]),
+        Line::from(vec![
+            label("Quality tier:  "),
+            Span::raw(format!(
+                "{}  vs  {}",
+                crate::api::estimate_quality_tier(&left.quant_type),
+                crate::api::estimate_quality_tier(&right.quant_type)
+            )),
+        ]),
+        Line::from(vec![
+            label("Files:         "),
+            Span::raw(format!("{}  vs  {}", left.files.len(), right.files.len())),
+        ]),
+    ];
+    frame.render_widget(Paragraph::new(summary), rows[1]);
+
+    let help = Paragraph::new("Quality tiers are estimated from the quant name.  Esc/Enter: Close")
+        .style(Style::default().fg(Color::DarkGray));
+    frame.render_widget(help, rows[2]);
+}
+
+/// Remaining free space below which the confirmation popup warns
+const LOW_SPACE_BYTES: u64 = 5 * 1024 * 1024 * 1024;
+
+pub fn render_download_path_popup(
+    frame: &mut Frame,
+    download_path_input: &Input,
+    summary: Option<&DownloadSummary>,
+    locked: bool,
+) {
+    // Space check: None when unknown, otherwise (remaining after download, fits)
+    let space = summary.and_then(|s| {
+        s.free_space
+            .map(|free| (free.saturating_sub(s.bytes), free >= s.bytes))
+    });
+    // Bytes the download would put its directory over its quota
+    let over_quota = summary
+        .and_then(|s| Some(s.quota.as_ref()?.excess(s.bytes)))
+        .filter(|excess| *excess > 0);
+    let warning_color = match space {
+        Some((_, false)) => Some(Color::Red),
+        Some((remaining, true)) if remaining < LOW_SPACE_BYTES => Some(Color::Yellow),
+        _ if over_quota.is_some() => Some(Color::Yellow),
+        _ => None,
+    };
+
+    // Placement row only when the config has `[[placement]]` rules
+    let placement_rules = !crate::placement::roots().is_empty();
+
+    // Calculate centered popup area
+    let popup_width = 64.min(frame.area().width.saturating_sub(4));
+    let popup_height = match summary {
+        Some(s) if s.part_of.is_some() || s.preset.is_some() => 13,
+        Some(_) => 12,
+        None => 7,
+    } + summary.map_or(0, |s| {
+        u16::from(s.license.is_some())
+            + u16::from(s.convert_to_gguf.is_some())
+            + u16::from(s.quota.is_some())
+            + u16::from(placement_rules)
+    });
+    let popup_x = (frame.area().width.saturating_sub(popup_width)) / 2;
+    let popup_y = (frame.area().height.saturating_sub(popup_height)) / 2;
+
+    let popup_area = Rect {
+        x: popup_x,
+        y: popup_y,
+        width: popup_width,
+        height: popup_height,
+    };
+
+    // Clear the popup area first to remove any underlying content
+    frame.render_widget(Clear, popup_area);
+
+    // Render popup background (border follows the lock, then the space warning)
+    let border_color = if locked {
+        Color::Red
+    } else {
+        warning_color.unwrap_or(Color::White)
+    };
+    let popup_block = new_block()
+        .borders(Borders::ALL)
+        .title("Confirm Download")
+        .border_style(Style::default().fg(border_color))
+        .style(Style::default().fg(Color::White).bg(Color::Black));
+
+    frame.render_widget(popup_block, popup_area);
+
+    // Render input label
+    let label_area = Rect {
+        x: popup_area.x + 2,
+        y: popup_area.y + 1,
+        width: popup_area.width.saturating_sub(4),
+        height: 1,
+    };
+
+    let label = Paragraph::new("Download path:").style(Style::default().fg(Color::White));
+
+    frame.render_widget(label, label_area);
+
+    // Render input field
+    let input_area = Rect {
+        x: popup_area.x + 2,
+        y: popup_area.y + 2,
+        width: popup_area.width.saturating_sub(4),
+        height: 1,
+    };
+
+    let width = input_area.width.max(3) as usize;
+    let scroll = download_path_input.visual_scroll(width);
+
+    let input_widget = Paragraph::new(download_path_input.value())
+        .style(Style::default().fg(Color::Yellow))
+        .scroll((0, scroll as u16));
+
+    frame.render_widget(input_widget, input_area);
+
+    // Set cursor position
+    frame.set_cursor_position((
+        input_area.x + ((download_path_input.visual_cursor()).max(scroll) - scroll) as u16,
+        input_area.y,
+    ));
+
+    // Summary of what will be queued, e.g. "Q4_K_M + Q8_0: 2 files, 12.10 GB"
+    let mut next_y = popup_area.y + 4;
+    if let Some(summary) = summary {
+        let label_style = Style::default().fg(Color::DarkGray);
+        let files = if summary.files == 1 {
+            "1 file".to_string()
+        } else {
+            format!("{} files", summary.files)
+        };
+
+        let free_line = match (summary.free_space, space) {
+            (Some(free), Some((remaining, true))) => Span::styled(
+                format!(
+                    "{} left of {} free",
+                    format_size(remaining),
+                    format_size(free)
+                ),
+                Style::default().fg(warning_color.unwrap_or(Color::Green)),
+            ),
+            (Some(free), _) => Span::styled(
+                format!(
+                    "Not enough space: {} needed, {} free",
+                    format_size(summary.bytes),
+                    format_size(free)
+                ),
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            ),
+            (None, _) => Span::raw("unknown"),
+        };
+
+        let eta = match summary.speed_mbps.filter(|s| *s > 0.0) {
+            Some(speed) => crate::history::format_eta(summary.bytes, speed),
+            None => "unknown (no download history yet)".to_string(),
+        };
+
+        let lines = [
+            Line::from(vec![
+                Span::styled("Download:   ", label_style),
+                Span::styled(summary.label.clone(), Style::default().fg(Color::Cyan)),
+            ]),
+            Line::from(vec![
+                Span::styled("Size:       ", label_style),
+                Span::raw(format!("{} ({})", format_size(summary.bytes), files)),
+            ]),
+            Line::from(vec![Span::styled("Free after: ", label_style), free_line]),
+            Line::from(vec![Span::styled("ETA:        ", label_style), Span::raw(eta)]),
+            Line::from(vec![
+                Span::styled("Queue:      ", label_style),
+                Span::styled(
+                    format!("{} priority", summary.priority.label()),
+                    Style::default().fg(priority_color(summary.priority)),
+                ),
+                Span::styled(" [Ctrl+P: change]", label_style),
+            ]),
+        ];
+
+        // "Part 3 of 9" row for a single part of a split file
+        let parts_line = summary.part_of.map(|(part, total)| {
+            let text = if summary.files > 1 {
+                format!("part {} of {} - all parts [Tab: only this]", part, total)
+            } else {
+                format!("only part {} of {}, unusable alone [Tab: all]", part, total)
+            };
+            let color = if summary.files > 1 {
+                Color::Green
+            } else {
+                Color::Yellow
+            };
+            Line::from(vec![
+                Span::styled("Parts:      ", label_style),
+                Span::styled(text, Style::default().fg(color)),
+            ])
+        });
+
+        // Preset row for repositories with files a preset would skip
+        let preset_line = summary.preset.map(|preset| {
+            let skipped = summary.skipped_files;
+            let (text, color) = match preset {
+                RepoPreset::All => (
+                    format!("all files [Tab: {}]", summary.next_preset.label()),
+                    Color::White,
+                ),
+                _ => (
+                    format!(
+                        "{}, {} files skipped [Tab: {}]",
+                        preset.label(),
+                        skipped,
+                        summary.next_preset.label()
+                    ),
+                    Color::Green,
+                ),
+            };
+            Line::from(vec![
+                Span::styled("Files:      ", label_style),
+                Span::styled(text, Style::default().fg(color)),
+            ])
+        });
+
+        // "License: llama3.1 — requires acceptance", highlighted unless permissive
+        let license_line = summary.license.as_ref().map(|notice| {
+            let style = if notice.is_permissive() {
+                Style::default().fg(Color::Green)
+            } else {
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD)
+            };
+            Line::from(vec![
+                Span::styled("License:    ", label_style),
+                Span::styled(notice.to_string(), style),
+            ])
+        });
+
+        // "Convert" row for repositories, which llama.cpp can turn into a GGUF
+        let convert_line = summary.convert_to_gguf.map(|convert| {
+            let (text, color) = if convert {
+                ("to GGUF once downloaded [Ctrl+G: off]", Color::Green)
+            } else {
+                ("no [Ctrl+G: to GGUF with llama.cpp]", Color::White)
+            };
+            Line::from(vec![
+                Span::styled("Convert:    ", label_style),
+                Span::styled(text, Style::default().fg(color)),
+            ])
+        });
+
+        // "Quota" row when a configured quota covers the download path
+        let quota_line = summary.quota.as_ref().map(|quota| {
+            let span = match over_quota {
+                Some(excess) => Span::styled(
+                    format!(
+                        "{} over the {} limit of {}",
+                        format_size(excess),
+                        format_size(quota.max_bytes),
+                        quota.directory.display()
+                    ),
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                None => Span::styled(
+                    format!(
+                        "{} of {} used after download",
+                        format_size(quota.used_bytes + summary.bytes),
+                        format_size(quota.max_bytes)
+                    ),
+                    Style::default().fg(Color::Green),
+                ),
+            };
+            Line::from(vec![Span::styled("Quota:      ", label_style), span])
+        });
+
+        // "Placement" row: the rule that picked the path, or that it was edited
+        let placement_line = placement_rules.then(|| {
+            let span = match &summary.placement {
+                Some(p) if p.directory != download_path_input.value() => Span::styled(
+                    format!("edited (rule {} picks {})", p.rule, p.directory),
+                    Style::default().fg(Color::Yellow),
+                ),
+                Some(p) => Span::styled(
+                    format!("rule {} ({})", p.rule, p.reason),
+                    Style::default().fg(Color::Green),
+                ),
+                None => Span::raw("no rule matches, default directory"),
+            };
+            Line::from(vec![
+                Span::styled("Placement:  ", label_style),
+                span,
+                Span::styled(" [Ctrl+R: next root]", label_style),
+            ])
+        });
+
+        for line in license_line
+            .into_iter()
+            .chain(parts_line)
+            .chain(preset_line)
+            .chain(convert_line)
+            .chain(lines)
+            .chain(quota_line)
+            .chain(placement_line)
+        {
+            let area = Rect {
+                x: popup_area.x + 2,
+                y: next_y,
+                width: popup_area.width.saturating_sub(4),
+                height: 1,
+            };
+            frame.render_widget(Paragraph::new(line), area);
+            next_y += 1;
+        }
+        next_y += 1;
+    }
+
+    // Render instructions
+    let instructions_area = Rect {
+        x: popup_area.x + 2,
+        y: next_y,
+        width: popup_area.width.saturating_sub(4),
+        height: 1,
+    };
+
+    let instructions = if locked {
+        Paragraph::new("Downloads are locked: Ctrl+Y to download anyway, ESC to cancel")
+            .style(Style::default().fg(Color::Red).add_modifier(Modifier::BOLD))
+    } else if over_quota.is_some() {
+        Paragraph::new("Over quota: Enter to see what to delete, ESC to cancel")
+            .style(Style::default().fg(Color::Yellow))
+    } else {
+        Paragraph::new("Press Enter to confirm, ESC to cancel")
+            .style(Style::default().fg(Color::DarkGray))
+    };
+
+    frame.render_widget(instructions, instructions_area);
+}
+
+/// Render the models suggested for deletion when a download would go over
+/// its directory's quota (Enter in the download popup)
+pub fn render_quota_cleanup_popup(
+    frame: &mut Frame,
+    summary: &DownloadSummary,
+    candidates: &[crate::quota::CleanupCandidate],
+    selected: usize,
+) {
+    let popup_width = 80.min(frame.area().width.saturating_sub(4));
+    let popup_height = (candidates.len() as u16 + 5)
+        .clamp(8, 24)
+        .min(frame.area().height.saturating_sub(2));
+    let area = Rect {
+        x: (frame.area().width.saturating_sub(popup_width)) / 2,
+        y: (frame.area().height.saturating_sub(popup_height)) / 2,
+        width: popup_width,
+        height: popup_height,
+    };
+
+    frame.render_widget(Clear, area);
+
+    let block = new_block()
+        .borders(Borders::ALL)
+        .title(" Over Quota: Make Room ")
+        .border_style(Style::default().fg(Color::Yellow))
+        .style(Style::default().fg(Color::White).bg(Color::Black));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2),
+            Constraint::Min(1),
+            Constraint::Length(1),
+        ])
+        .split(inner);
+
+    // "12.4 GB over the 500 GB limit of /home/u/models" or that it fits now
+    let header = match summary.quota.as_ref() {
+        Some(quota) if quota.excess(summary.bytes) > 0 => Line::from(Span::styled(
+            format!(
+                "{} needed: {} over the {} limit of {}",
+                format_size(summary.bytes),
+                format_size(quota.excess(summary.bytes)),
+                format_size(quota.max_bytes),
+                quota.directory.display()
+            ),
+            Style::default().fg(Color::Yellow),
+        )),
+        Some(quota) => Line::from(Span::styled(
+            format!(
+                "The download fits now: {} of {} used after it",
+                format_size(quota.used_bytes + summary.bytes),
+                format_size(quota.max_bytes)
+            ),
+            Style::default().fg(Color::Green),
+        )),
+        None => Line::default(),
+    };
+    frame.render_widget(Paragraph::new(header), rows[0]);
+
+    let items: Vec<ListItem> = candidates
+        .iter()
+        .map(|c| {
+            let mut spans = vec![
+                Span::styled(
+                    format!("{:>10}  ", format_size(c.bytes)),
+                    Style::default().fg(Color::Cyan),
+                ),
+                Span::styled(c.model_id.clone(), Style::default().fg(Color::White)),
+                Span::styled(
+                    format!("  {} file(s), {} day(s) old", c.files, c.age_days()),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ];
+            if c.unverified {
+                spans.push(Span::styled(
+                    "  unverified",
+                    Style::default().fg(Color::Yellow),
+                ));
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+
+    let mut state = ListState::default();
+    if !candidates.is_empty() {
+        state.select(Some(selected.min(candidates.len() - 1)));
+    }
+    let list = List::new(items)
+        .highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol(selection_symbol());
+    frame.render_stateful_widget(list, rows[1], &mut state);
+    render_list_scrollbar(frame, rows[1], false, candidates.len(), &state);
+
+    let help = Paragraph::new("j/k: Select  d: Move to trash  Enter: Download  Esc: Back")
+        .style(Style::default().fg(Color::DarkGray));
+    frame.render_widget(help, rows[2]);
+}
+
+fn priority_color(priority: DownloadPriority) -> Color {
+    match priority {
+        DownloadPriority::High => Color::Green,
+        DownloadPriority::Normal => Color::White,
+        DownloadPriority::Low => Color::DarkGray,
+    }
+}
+
+/// Render the downloads waiting in the queue ('Q'), in the order they start
+pub fn render_download_queue_popup(
+    frame: &mut Frame,
+    queue: &[crate::models::QueuedDownload],
+    selected: usize,
+    conversions: &[crate::convert::ConversionJob],
+) {
+    let popup_width = 90.min(frame.area().width.saturating_sub(4));
+    let popup_height = 24.min(frame.area().height.saturating_sub(2));
+    let area = Rect {
+        x: (frame.area().width.saturating_sub(popup_width)) / 2,
+        y: (frame.area().height.saturating_sub(popup_height)) / 2,
+        width: popup_width,
+        height: popup_height,
+    };
+
+    frame.render_widget(Clear, area);
 
-    // Render popup background
-    let popup_block = Block::default()
+    let total: u64 = queue.iter().map(|q| q.total_size).sum();
+    let block = new_block()
         .borders(Borders::ALL)
-        .title("Download Model")
-        .style(Style::default().fg(Color::White).bg(Color::Black));
-
-    frame.render_widget(popup_block, popup_area);
+        .title(format!(
+            " Download Queue: {} waiting, {} ",
+            queue.len(),
+            format_size(total)
+        ))
+        .style(Style::default().fg(Color::Cyan).bg(Color::Black));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
 
-    // Render input label
-    let label_area = Rect {
-        x: popup_area.x + 2,
-        y: popup_area.y + 1,
-        width: popup_area.width.saturating_sub(4),
-        height: 1,
+    // Conversion jobs below the downloads, newest last
+    let conversion_rows = if conversions.is_empty() {
+        0
+    } else {
+        conversions.len().min(5) as u16 + 1
     };
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(1),
+            Constraint::Length(conversion_rows),
+            Constraint::Length(1),
+        ])
+        .split(inner);
 
-    let label = Paragraph::new("Download path:").style(Style::default().fg(Color::White));
-
-    frame.render_widget(label, label_area);
-
-    // Render input field
-    let input_area = Rect {
-        x: popup_area.x + 2,
-        y: popup_area.y + 2,
-        width: popup_area.width.saturating_sub(4),
-        height: 1,
+    let items: Vec<ListItem> = if queue.is_empty() {
+        vec![ListItem::new(Span::styled(
+            "No downloads waiting (the one in progress is not listed)",
+            Style::default().fg(Color::DarkGray),
+        ))]
+    } else {
+        queue
+            .iter()
+            .map(|queued| {
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        format!("{:<7}", queued.priority.label()),
+                        Style::default().fg(priority_color(queued.priority)),
+                    ),
+                    Span::styled(
+                        format!("{:>10}  ", format_size(queued.total_size)),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                    Span::styled(queued.filename.clone(), Style::default().fg(Color::White)),
+                    Span::styled(
+                        format!("  {}", queued.model_id),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                ]))
+            })
+            .collect()
     };
 
-    let width = input_area.width.max(3) as usize;
-    let scroll = download_path_input.visual_scroll(width);
-
-    let input_widget = Paragraph::new(download_path_input.value())
-        .style(Style::default().fg(Color::Yellow))
-        .scroll((0, scroll as u16));
-
-    frame.render_widget(input_widget, input_area);
-
-    // Set cursor position
-    frame.set_cursor_position((
-        input_area.x + ((download_path_input.visual_cursor()).max(scroll) - scroll) as u16,
-        input_area.y,
-    ));
-
-    // Render instructions
-    let instructions_area = Rect {
-        x: popup_area.x + 2,
-        y: popup_area.y + 4,
-        width: popup_area.width.saturating_sub(4),
-        height: 1,
-    };
+    let mut state = ListState::default();
+    if !queue.is_empty() {
+        state.select(Some(selected.min(queue.len() - 1)));
+    }
+    let list = List::new(items)
+        .highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol(selection_symbol());
+    frame.render_stateful_widget(list, rows[0], &mut state);
+    render_list_scrollbar(frame, rows[0], false, queue.len(), &state);
+
+    if !conversions.is_empty() {
+        use crate::convert::ConversionState;
+        let mut lines = vec![Line::from(Span::styled(
+            "GGUF conversions",
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        ))];
+        lines.extend(conversions.iter().rev().take(5).rev().map(|job| {
+            let (state, color) = match &job.state {
+                ConversionState::Waiting => ("waiting for downloads".to_string(), Color::DarkGray),
+                ConversionState::Converting => ("converting to F16".to_string(), Color::Yellow),
+                ConversionState::Quantizing => ("quantizing".to_string(), Color::Yellow),
+                ConversionState::Done(path) => (
+                    format!(
+                        "done: {}",
+                        path.file_name().unwrap_or_default().to_string_lossy()
+                    ),
+                    Color::Green,
+                ),
+                ConversionState::Failed(reason) => (format!("failed: {}", reason), Color::Red),
+            };
+            Line::from(vec![
+                Span::styled(format!("{:<24} ", state), Style::default().fg(color)),
+                Span::styled(job.model_id.clone(), Style::default().fg(Color::White)),
+            ])
+        }));
+        frame.render_widget(Paragraph::new(lines), rows[1]);
+    }
 
-    let instructions = Paragraph::new("Press Enter to confirm, ESC to cancel")
+    let help = Paragraph::new("j/k: Select  J/K: Move  +/-: Queue priority  Esc: Close")
         .style(Style::default().fg(Color::DarkGray));
-
-    frame.render_widget(instructions, instructions_area);
+    frame.render_widget(help, rows[2]);
 }
 
 pub fn render_auth_error_popup(frame: &mut Frame, model_url: &str, has_token: bool) {
@@ -1322,7 +3565,7 @@ pub fn render_auth_error_popup(frame: &mut Frame, model_url: &str, has_token: bo
     frame.render_widget(Clear, popup_area);
 
     // Render popup background
-    let popup_block = Block::default()
+    let popup_block = new_block()
         .borders(Borders::ALL)
         .title("Authentication Required")
         .style(Style::default().fg(Color::Yellow).bg(Color::Black));
@@ -1407,14 +3650,18 @@ pub fn render_auth_error_popup(frame: &mut Frame, model_url: &str, has_token: bo
     frame.render_widget(message, message_area);
 }
 
+/// Number of selectable fields in the options popup
+pub const OPTIONS_FIELD_COUNT: usize = 42;
+
 pub fn render_options_popup(
     frame: &mut Frame,
     options: &crate::models::AppOptions,
     directory_input: &tui_input::Input,
     token_input: &tui_input::Input,
+    tool_path_input: &tui_input::Input,
 ) {
     let popup_width = 64.min(frame.area().width.saturating_sub(4));
-    let popup_height = 31.min(frame.area().height.saturating_sub(4));
+    let popup_height = 40.min(frame.area().height.saturating_sub(4));
     let popup_area = Rect {
         x: (frame.area().width.saturating_sub(popup_width)) / 2,
         y: (frame.area().height.saturating_sub(popup_height)) / 2,
@@ -1424,7 +3671,7 @@ pub fn render_options_popup(
 
     frame.render_widget(Clear, popup_area);
 
-    let block = Block::default()
+    let block = new_block()
         .borders(Borders::ALL)
         .title("Options (ESC to close)")
         .border_style(Style::default().fg(Color::Yellow));
@@ -1432,7 +3679,7 @@ pub fn render_options_popup(
     let inner = block.inner(popup_area);
     frame.render_widget(block, popup_area);
 
-    // Render 14 fields with category headers
+    // Render all fields with category headers
     let fields = vec![
         // General (indices 0-1)
         (
@@ -1451,7 +3698,7 @@ pub fn render_options_popup(
                 if token.is_empty() {
                     "[Not set]".to_string()
                 } else {
-                    "•".repeat(token.len().min(20))
+                    glyphs().masked.repeat(token.len().min(20))
                 }
             } else {
                 "[Not set]".to_string()
@@ -1488,7 +3735,7 @@ pub fn render_options_popup(
             "Max Download Speed (MB/s):",
             format!("{:.1}", options.download_rate_limit_mbps),
         ),
-        // Verification (indices 12-15)
+        // Verification (indices 12-16)
         (
             "Enable Verification:",
             if options.verification_on_completion {
@@ -1509,6 +3756,197 @@ pub fn render_options_popup(
             "Verification Update Interval:",
             options.verification_update_interval.to_string(),
         ),
+        (
+            "Verification Priority:",
+            if options.low_priority_verification {
+                "Low (idle I/O)".to_string()
+            } else {
+                "Normal".to_string()
+            },
+        ),
+        // API Cache (indices 17-19)
+        (
+            "Disk Cache:",
+            if options.api_cache_enabled {
+                "Enabled".to_string()
+            } else {
+                "Disabled".to_string()
+            },
+        ),
+        (
+            "Cache TTL (min):",
+            options.api_cache_ttl_minutes.to_string(),
+        ),
+        (
+            "Max Cache Size (MB):",
+            options.api_cache_max_size_mb.to_string(),
+        ),
+        // Search Refresh (indices 20-21)
+        (
+            "Auto-Refresh:",
+            if options.auto_refresh_enabled {
+                "Enabled".to_string()
+            } else {
+                "Disabled".to_string()
+            },
+        ),
+        (
+            "Refresh Interval (min):",
+            options.auto_refresh_interval_minutes.to_string(),
+        ),
+        // Session (index 22)
+        (
+            "Restore Session:",
+            if options.restore_session {
+                "Enabled".to_string()
+            } else {
+                "Disabled".to_string()
+            },
+        ),
+        // Storage (index 23)
+        (
+            "Root-Level Quant Files:",
+            if options.quant_subdirectory {
+                "Per-quant subfolder (Q4_K_M/)".to_string()
+            } else {
+                "Flat (author/model/)".to_string()
+            },
+        ),
+        // Licenses (index 24)
+        (
+            "Require --accept-license:",
+            if options.require_license_confirmation {
+                "Non-permissive (headless)".to_string()
+            } else {
+                "Never".to_string()
+            },
+        ),
+        // New GGUF Feed (index 25)
+        ("Feed Window (days):", options.recent_gguf_days.to_string()),
+        // Search Results (indices 26-28)
+        ("Results per Page:", options.search_page_size.to_string()),
+        ("Max Results:", options.search_max_results.to_string()),
+        (
+            "Repo Sizes:",
+            if options.show_repo_sizes {
+                "Look up".to_string()
+            } else {
+                "Off".to_string()
+            },
+        ),
+        // Startup (index 29)
+        (
+            "Incomplete Downloads:",
+            if options.auto_resume {
+                "Resume automatically".to_string()
+            } else {
+                "Ask".to_string()
+            },
+        ),
+        // Network (indices 30-32)
+        (
+            "Conservative (Tor/VPN):",
+            if options.conservative_networking {
+                "On (1 connection, 4MB chunks)".to_string()
+            } else {
+                "Off".to_string()
+            },
+        ),
+        (
+            "Adaptive Threads:",
+            if options.conservative_networking {
+                "Off (conservative networking)".to_string()
+            } else if options.adaptive_threads {
+                format!(
+                    "On (1-{} per file, tuned to throughput)",
+                    crate::download::ADAPTIVE_MAX_THREADS
+                )
+            } else {
+                "Off (use Concurrent Threads)".to_string()
+            },
+        ),
+        (
+            "Max Connections:",
+            match options.max_connections {
+                0 => "Unlimited".to_string(),
+                n => format!("{} across all files", n),
+            },
+        ),
+        // Archives (index 33)
+        (
+            "Extract .zip/.tar.gz:",
+            if options.extract_archives {
+                "After download".to_string()
+            } else {
+                "Off".to_string()
+            },
+        ),
+        // GGUF Conversion (indices 34-36)
+        (
+            "convert_hf_to_gguf.py:",
+            if options.editing_tool_path && options.selected_field == 34 {
+                tool_path_input.value().to_string()
+            } else {
+                options
+                    .convert_script
+                    .clone()
+                    .unwrap_or_else(|| "[Not set]".to_string())
+            },
+        ),
+        (
+            "llama-quantize:",
+            if options.editing_tool_path && options.selected_field == 35 {
+                tool_path_input.value().to_string()
+            } else {
+                options
+                    .quantize_binary
+                    .clone()
+                    .unwrap_or_else(|| "[Not set] (keep F16)".to_string())
+            },
+        ),
+        ("Quantize To:", options.convert_quant_type.clone()),
+        // Display (index 37)
+        (
+            "ASCII Only:",
+            if options.ascii_only {
+                "On (no box drawing or symbols)".to_string()
+            } else {
+                "Off".to_string()
+            },
+        ),
+        // Download Lock (index 38)
+        (
+            "Lock Downloads:",
+            if crate::download::downloads_locked() {
+                "On (--no-download)".to_string()
+            } else if options.no_download {
+                "On (Ctrl+Y to confirm each download)".to_string()
+            } else {
+                "Off".to_string()
+            },
+        ),
+        // Deduplication (indices 39-40)
+        (
+            "Identical Files:",
+            options.identical_files.label().to_string(),
+        ),
+        (
+            "Blob Store:",
+            if options.blob_store {
+                "On (~/models/.blobs + symlinks)".to_string()
+            } else {
+                "Off".to_string()
+            },
+        ),
+        // Trash (index 41)
+        (
+            "Keep Deleted Files (days):",
+            if options.trash_retention_days == 0 {
+                "0 (delete right away)".to_string()
+            } else {
+                options.trash_retention_days.to_string()
+            },
+        ),
     ];
 
     // Render category headers
@@ -1517,75 +3955,114 @@ pub fn render_options_popup(
         (2, "Download"),
         (10, "Rate Limiting"),
         (12, "Verification"),
+        (17, "API Cache"),
+        (20, "Search Refresh"),
+        (22, "Session"),
+        (23, "Storage"),
+        (24, "Licenses"),
+        (25, "New GGUF Feed"),
+        (26, "Search Results"),
+        (29, "Startup"),
+        (30, "Network"),
+        (33, "Archives"),
+        (34, "GGUF Conversion"),
+        (37, "Display"),
+        (38, "Download Lock"),
+        (39, "Deduplication"),
+        (41, "Trash"),
     ];
 
-    let mut y_offset = 1u16;
-    let mut field_idx = 0;
-
+    // Lay out rows: spacer before each category (except first), header, fields
+    enum OptionRow<'a> {
+        Spacer,
+        Header(&'a str),
+        Field(usize),
+    }
+    let mut rows = Vec::new();
     for (cat_idx, (field_start, category_name)) in category_offsets.iter().enumerate() {
-        // Render category header
         if cat_idx > 0 {
-            y_offset += 1; // Add spacing before category (except first)
+            rows.push(OptionRow::Spacer);
         }
+        rows.push(OptionRow::Header(category_name));
+        let next_cat_start = category_offsets
+            .get(cat_idx + 1)
+            .map(|(s, _)| *s)
+            .unwrap_or(fields.len());
+        rows.extend((*field_start..next_cat_start).map(OptionRow::Field));
+    }
 
-        let separator = format!("─── {} ", category_name);
-        let full_width = inner.width.saturating_sub(4) as usize;
-        let separator = format!("{:─<width$}", separator, width = full_width);
+    // Scroll so the selected field stays visible above the help text
+    let visible_rows = inner.height.saturating_sub(6) as usize;
+    let selected_row = rows
+        .iter()
+        .position(|r| matches!(r, OptionRow::Field(i) if *i == options.selected_field))
+        .unwrap_or(0);
+    let scroll = (selected_row + 1).saturating_sub(visible_rows);
 
-        let header_area = Rect {
+    for (row_idx, row) in rows.iter().skip(scroll).take(visible_rows).enumerate() {
+        let area = Rect {
             x: inner.x + 2,
-            y: inner.y + y_offset,
+            y: inner.y + 1 + row_idx as u16,
             width: inner.width - 4,
             height: 1,
         };
 
-        let header_widget = Paragraph::new(separator).style(Style::default().fg(Color::DarkGray));
-        frame.render_widget(header_widget, header_area);
-
-        y_offset += 1;
-
-        // Render fields in this category
-        let next_cat_start = category_offsets
-            .get(cat_idx + 1)
-            .map(|(s, _)| *s)
-            .unwrap_or(fields.len());
-        for (label, value) in fields.iter().take(next_cat_start).skip(*field_start) {
-            let area = Rect {
-                x: inner.x + 2,
-                y: inner.y + y_offset,
-                width: inner.width - 4,
-                height: 1,
-            };
+        match row {
+            OptionRow::Spacer => {}
+            OptionRow::Header(category_name) => {
+                let rule = glyphs().rule.to_string();
+                let separator = format!("{} {} ", rule.repeat(3), category_name);
+                let full_width = inner.width.saturating_sub(4) as usize;
+                let padding = full_width.saturating_sub(separator.chars().count());
+                let separator = format!("{}{}", separator, rule.repeat(padding));
+                let header_widget =
+                    Paragraph::new(separator).style(Style::default().fg(Color::DarkGray));
+                frame.render_widget(header_widget, area);
+            }
+            OptionRow::Field(field_idx) => {
+                let field_idx = *field_idx;
+                let (label, value) = &fields[field_idx];
 
-            let style = if field_idx == options.selected_field {
-                Style::default()
-                    .fg(Color::Yellow)
-                    .add_modifier(Modifier::BOLD)
-            } else {
-                Style::default()
-            };
+                let style = if field_idx == options.selected_field {
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default()
+                };
 
-            let text = format!("{} {}", label, value);
-            let widget = Paragraph::new(text).style(style);
-            frame.render_widget(widget, area);
-
-            // Show cursor when editing directory or token
-            if options.editing_directory && field_idx == 0 {
-                let cursor_x =
-                    area.x + label.len() as u16 + 1 + directory_input.visual_cursor() as u16;
-                frame.set_cursor_position((cursor_x, area.y));
-            } else if options.editing_token && field_idx == 1 {
-                let cursor_x = area.x + label.len() as u16 + 1 + token_input.visual_cursor() as u16;
-                frame.set_cursor_position((cursor_x, area.y));
+                let text = format!("{} {}", label, value);
+                let widget = Paragraph::new(text).style(style);
+                frame.render_widget(widget, area);
+
+                // Show cursor when editing directory or token
+                if options.editing_directory && field_idx == 0 {
+                    let cursor_x =
+                        area.x + label.len() as u16 + 1 + directory_input.visual_cursor() as u16;
+                    frame.set_cursor_position((cursor_x, area.y));
+                } else if options.editing_token && field_idx == 1 {
+                    let cursor_x =
+                        area.x + label.len() as u16 + 1 + token_input.visual_cursor() as u16;
+                    frame.set_cursor_position((cursor_x, area.y));
+                } else if options.editing_tool_path && field_idx == options.selected_field {
+                    let cursor_x =
+                        area.x + label.len() as u16 + 1 + tool_path_input.visual_cursor() as u16;
+                    frame.set_cursor_position((cursor_x, area.y));
+                }
             }
-
-            y_offset += 1;
-            field_idx += 1;
         }
     }
 
     // Controls help (with empty line before)
     let help_y = inner.y + inner.height - 5;
+    let navigate_help = format!(
+        "j/k or {}: Navigate | Enter: Edit directory",
+        glyphs().up_down
+    );
+    let modify_help = format!(
+        "+/- or {}: Modify values & toggle verification",
+        glyphs().left_right
+    );
     let help = if options.editing_directory {
         vec![
             "",
@@ -1600,13 +4077,15 @@ pub fn render_options_popup(
             "Enter: Save | ESC: Cancel",
             "",
         ]
-    } else {
+    } else if options.editing_tool_path {
         vec![
             "",
-            "j/k or ↑/↓: Navigate | Enter: Edit directory",
-            "+/- or ←/→: Modify values & toggle verification",
-            "ESC: Close",
+            "Type to edit the tool path (or clear to remove)",
+            "Enter: Save | ESC: Cancel",
+            "",
         ]
+    } else {
+        vec!["", &navigate_help, &modify_help, "ESC: Close"]
     };
 
     for (i, line) in help.iter().enumerate() {
@@ -1630,14 +4109,18 @@ pub fn render_filter_toolbar(
     sort_direction: crate::models::SortDirection,
     min_downloads: u64,
     min_likes: u64,
+    params: crate::models::ParamRange,
+    access: crate::models::AccessFilter,
+    content: crate::models::ContentFilter,
     focused_field: usize,
     filter_areas: &mut Vec<(usize, Rect)>,
+    preset_areas: &mut Vec<(crate::models::FilterPreset, Rect)>,
 ) {
-    use crate::models::{SortDirection, SortField};
+    use crate::models::{FilterPreset, SortDirection, SortField};
 
-    let block = Block::default()
+    let block = new_block()
         .borders(Borders::ALL)
-        .title("Filters  [Click to cycle | 1-4: Presets | r: Reset | Ctrl+S: Save]")
+        .title("Filters  [Click to cycle | 1-4 or click: Presets | r: Reset | Ctrl+S: Save]")
         .style(Style::default().fg(Color::Cyan));
 
     let inner = block.inner(area);
@@ -1645,8 +4128,8 @@ pub fn render_filter_toolbar(
 
     // Sort arrow
     let sort_arrow = match sort_direction {
-        SortDirection::Ascending => "▲",
-        SortDirection::Descending => "▼",
+        SortDirection::Ascending => glyphs().ascending,
+        SortDirection::Descending => glyphs().descending,
     };
 
     // Sort name
@@ -1684,52 +4167,95 @@ pub fn render_filter_toolbar(
         Style::default().fg(Color::White)
     };
 
-    // Detect which preset is active (if any)
-    let preset_name = if sort_field == SortField::Modified
+    let size_style = if focused_field == 3 {
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
+    } else {
+        Style::default().fg(Color::White)
+    };
+
+    let access_style = if focused_field == 4 {
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
+    } else {
+        Style::default().fg(Color::White)
+    };
+
+    let content_style = if focused_field == 5 {
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
+    } else {
+        Style::default().fg(Color::White)
+    };
+
+    // Detect which preset is active (if any); presets leave the size filter off
+    let active_preset = if !params.is_any() {
+        None
+    } else if sort_field == SortField::Modified
         && sort_direction == SortDirection::Descending
         && min_downloads == 0
         && min_likes == 0
     {
-        Some("Recent")
+        Some(FilterPreset::Recent)
     } else if sort_field == SortField::Likes
         && sort_direction == SortDirection::Descending
         && min_downloads == 0
         && min_likes == 1_000
     {
-        Some("Highly Rated")
+        Some(FilterPreset::HighlyRated)
     } else if sort_field == SortField::Downloads
         && sort_direction == SortDirection::Descending
         && min_downloads == 10_000
         && min_likes == 100
     {
-        Some("Popular")
+        Some(FilterPreset::Popular)
     } else if sort_field == SortField::Downloads
         && sort_direction == SortDirection::Descending
         && min_downloads == 0
         && min_likes == 0
     {
-        Some("No Filters")
+        Some(FilterPreset::NoFilters)
     } else {
         None
     };
 
     // Calculate text segments for click detection
-    // Format: "Sort: {value}  |  Min Downloads: {value}  |  Min Likes: {value}"
-    let sort_label = "Sort: ";
+    // Format: "Sort: {value}  |  Min Downloads: {value}  |  Min Likes: {value}  |  Size: {value}  |  Access: {value}  |  Content: {value}"
+    // The focused field is marked by its color, and for screen readers by "> "
+    let label = |idx: usize, text: &str| {
+        if screen_reader() && focused_field == idx {
+            format!("> {}", text)
+        } else {
+            text.to_string()
+        }
+    };
+    let sort_label = label(0, "Sort: ");
     let sort_value = format!("{} {}", sort_name, sort_arrow);
     let separator1 = "  |  ";
-    let downloads_label = "Min Downloads: ";
+    let downloads_label = label(1, "Min Downloads: ");
     let downloads_value = crate::utils::format_number(min_downloads);
     let separator2 = "  |  ";
-    let likes_label = "Min Likes: ";
+    let likes_label = label(2, "Min Likes: ");
     let likes_value = crate::utils::format_number(min_likes);
+    let separator3 = "  |  ";
+    let size_label = label(3, "Size: ");
+    let size_value = display_text(&params.label());
+    let separator4 = "  |  ";
+    let access_label = label(4, "Access: ");
+    let access_value = access.label();
+    let separator5 = "  |  ";
+    let content_label = label(5, "Content: ");
+    let content_value = content.label();
 
     // Calculate x positions for each clickable area
     let mut x = inner.x;
 
-    // Sort area: includes label and value
+    // Sort area: includes label and value (the arrow is wider in bytes than on screen)
     let sort_start = x;
-    x += sort_label.len() as u16 + sort_value.len() as u16;
+    x += sort_label.len() as u16 + sort_value.chars().count() as u16;
     let sort_area = Rect {
         x: sort_start,
         y: inner.y,
@@ -1764,6 +4290,45 @@ pub fn render_filter_toolbar(
     };
     filter_areas.push((2, likes_area));
 
+    x += separator3.len() as u16;
+
+    // Size area: includes label and value ("≤8B" is wider in bytes than on screen)
+    let size_start = x;
+    x += size_label.len() as u16 + size_value.chars().count() as u16;
+    let size_area = Rect {
+        x: size_start,
+        y: inner.y,
+        width: x - size_start,
+        height: 1,
+    };
+    filter_areas.push((3, size_area));
+
+    x += separator4.len() as u16;
+
+    // Access area: includes label and value
+    let access_start = x;
+    x += access_label.len() as u16 + access_value.len() as u16;
+    let access_area = Rect {
+        x: access_start,
+        y: inner.y,
+        width: x - access_start,
+        height: 1,
+    };
+    filter_areas.push((4, access_area));
+
+    x += separator5.len() as u16;
+
+    // Content area: includes label and value
+    let content_start = x;
+    x += content_label.len() as u16 + content_value.len() as u16;
+    let content_area = Rect {
+        x: content_start,
+        y: inner.y,
+        width: x - content_start,
+        height: 1,
+    };
+    filter_areas.push((5, content_area));
+
     let mut line_parts = vec![
         Span::styled(sort_label, Style::default().fg(Color::DarkGray)),
         Span::styled(sort_value, sort_style),
@@ -1773,13 +4338,66 @@ pub fn render_filter_toolbar(
         Span::raw(separator2),
         Span::styled(likes_label, Style::default().fg(Color::DarkGray)),
         Span::styled(likes_value, likes_style),
+        Span::raw(separator3),
+        Span::styled(size_label, Style::default().fg(Color::DarkGray)),
+        Span::styled(size_value, size_style),
+        Span::raw(separator4),
+        Span::styled(access_label, Style::default().fg(Color::DarkGray)),
+        Span::styled(access_value, access_style),
+        Span::raw(separator5),
+        Span::styled(content_label, Style::default().fg(Color::DarkGray)),
+        Span::styled(content_value, content_style),
     ];
 
-    // Add preset indicator if a preset is active
-    if let Some(preset) = preset_name {
+    // Clickable presets on the bottom border, right-aligned, the active one
+    // highlighted: "[1 No Filters] [2 Popular] [3 Highly Rated] [4 Recent]"
+    let chips: Vec<String> = FilterPreset::ALL
+        .iter()
+        .enumerate()
+        .map(|(idx, preset)| format!("[{} {}]", idx + 1, preset.label()))
+        .collect();
+    let chips_width = chips.iter().map(|chip| chip.len() as u16 + 1).sum::<u16>() - 1;
+    let chips_fit = area.height >= 3 && chips_width + 4 <= area.width;
+
+    if chips_fit {
+        let mut x = area.right() - 2 - chips_width;
+        let y = area.bottom() - 1;
+        let mut chip_parts = Vec::new();
+        for (preset, chip) in FilterPreset::ALL.into_iter().zip(chips) {
+            let width = chip.len() as u16;
+            preset_areas.push((
+                preset,
+                Rect {
+                    x,
+                    y,
+                    width,
+                    height: 1,
+                },
+            ));
+            x += width + 1;
+            let style = if active_preset == Some(preset) {
+                Style::default()
+                    .fg(Color::Green)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::DarkGray)
+            };
+            chip_parts.push(Span::styled(chip, style));
+            chip_parts.push(Span::raw(" "));
+        }
+        chip_parts.pop();
+        let chips_area = Rect {
+            x: area.right() - 2 - chips_width,
+            y,
+            width: chips_width,
+            height: 1,
+        };
+        frame.render_widget(Paragraph::new(Line::from(chip_parts)), chips_area);
+    } else if let Some(preset) = active_preset {
+        // Too narrow for the presets: name the active one after the fields
         line_parts.push(Span::raw("  |  "));
         line_parts.push(Span::styled(
-            format!("[{}]", preset),
+            format!("[{}]", preset.label()),
             Style::default()
                 .fg(Color::Green)
                 .add_modifier(Modifier::BOLD),
@@ -8,6 +8,53 @@ pub fn format_number(n: u64) -> String {
     }
 }
 
+/// Format a parameter count the way model cards do (e.g. 494M, 8.0B, 70.6B)
+pub fn format_params(n: u64) -> String {
+    if n >= 1_000_000_000_000 {
+        format!("{:.1}T", n as f64 / 1_000_000_000_000.0)
+    } else if n >= 1_000_000_000 {
+        format!("{:.1}B", n as f64 / 1_000_000_000.0)
+    } else if n >= 1_000_000 {
+        format!("{:.0}M", n as f64 / 1_000_000.0)
+    } else {
+        format_number(n)
+    }
+}
+
+/// Parse a parameter count such as "8B", "1.5b", "500M", "1T" or "7000000"
+pub fn parse_params(text: &str) -> Option<u64> {
+    let text = text.trim();
+    let (number, scale) = match text.char_indices().last()? {
+        (i, 'k' | 'K') => (&text[..i], 1e3),
+        (i, 'm' | 'M') => (&text[..i], 1e6),
+        (i, 'b' | 'B') => (&text[..i], 1e9),
+        (i, 't' | 'T') => (&text[..i], 1e12),
+        _ => (text, 1.0),
+    };
+    let value: f64 = number.trim().parse().ok()?;
+    (value.is_finite() && value >= 0.0).then(|| (value * scale).round() as u64)
+}
+
+/// Parse a byte size such as "200GB", "1.5 TB", "500M" or "4096"; units
+/// are binary, like [`format_size`] (1 GB = 1024 MB)
+pub fn parse_size(text: &str) -> Option<u64> {
+    let text = text.trim();
+    let digits = text
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(text.len());
+    let (number, unit) = text.split_at(digits);
+    let scale: u64 = match unit.trim().to_ascii_lowercase().as_str() {
+        "" | "b" => 1,
+        "k" | "kb" | "kib" => 1 << 10,
+        "m" | "mb" | "mib" => 1 << 20,
+        "g" | "gb" | "gib" => 1 << 30,
+        "t" | "tb" | "tib" => 1 << 40,
+        _ => return None,
+    };
+    let value: f64 = number.parse().ok()?;
+    (value.is_finite() && value >= 0.0).then(|| (value * scale as f64).round() as u64)
+}
+
 pub fn format_size(bytes: u64) -> String {
     const GB: u64 = 1_073_741_824;
     const MB: u64 = 1_048_576;
@@ -23,3 +70,251 @@ pub fn format_size(bytes: u64) -> String {
         format!("{} B", bytes)
     }
 }
+
+/// Format a Unix timestamp (seconds) as a UTC calendar date, e.g. 2024-03-09
+pub fn format_unix_date(secs: u64) -> String {
+    // Civil-from-days conversion (proleptic Gregorian calendar)
+    let days = (secs / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// Parse a Hub timestamp such as `2024-03-09T14:03:27.000Z` into Unix
+/// seconds (UTC); fractional seconds and the zone suffix are ignored
+pub fn parse_iso_timestamp(text: &str) -> Option<u64> {
+    let (date, time) = text.split_once('T').unwrap_or((text, "00:00:00"));
+    let mut date_parts = date.splitn(3, '-').map(|part| part.parse::<i64>().ok());
+    let (year, month, day) = (
+        date_parts.next()??,
+        date_parts.next()??,
+        date_parts.next()??,
+    );
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+    let mut time_parts = time
+        .get(..8)
+        .unwrap_or(time)
+        .splitn(3, ':')
+        .map(|part| part.parse::<i64>().ok());
+    let hour = time_parts.next().flatten().unwrap_or(0);
+    let minute = time_parts.next().flatten().unwrap_or(0);
+    let second = time_parts.next().flatten().unwrap_or(0);
+
+    // Days-from-civil conversion, the inverse of format_unix_date
+    let year = year - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era * 146_097 + day_of_era - 719_468;
+    u64::try_from(days * 86_400 + hour * 3_600 + minute * 60 + second).ok()
+}
+
+/// How long ago `secs` seconds is, in words, e.g. "3 days ago"
+pub fn format_relative_time(secs: u64) -> String {
+    const MINUTE: u64 = 60;
+    const HOUR: u64 = 60 * MINUTE;
+    const DAY: u64 = 24 * HOUR;
+    let (count, unit) = match secs {
+        s if s < MINUTE => return "just now".to_string(),
+        s if s < HOUR => (s / MINUTE, "minute"),
+        s if s < DAY => (s / HOUR, "hour"),
+        s if s < 2 * DAY => return "yesterday".to_string(),
+        s if s < 14 * DAY => (s / DAY, "day"),
+        s if s < 60 * DAY => (s / (7 * DAY), "week"),
+        s if s < 365 * DAY => (s / (30 * DAY), "month"),
+        s => (s / (365 * DAY), "year"),
+    };
+    format!(
+        "{} {}{} ago",
+        count,
+        unit,
+        if count == 1 { "" } else { "s" }
+    )
+}
+
+/// A Hub timestamp relative to now ("3 days ago"); `None` if it doesn't parse
+pub fn format_relative_date(timestamp: &str) -> Option<String> {
+    let then = parse_iso_timestamp(timestamp)?;
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    Some(format_relative_time(now.saturating_sub(then)))
+}
+
+/// A Hub timestamp as an absolute UTC date and time, e.g. "2024-03-09 14:03 UTC"
+pub fn format_absolute_date(timestamp: &str) -> Option<String> {
+    let secs = parse_iso_timestamp(timestamp)?;
+    Some(format!(
+        "{} {:02}:{:02} UTC",
+        format_unix_date(secs),
+        secs % 86_400 / 3_600,
+        secs % 3_600 / 60
+    ))
+}
+
+/// Shorten `text` to `max_chars` characters by cutting out its middle,
+/// e.g. "bartowski/Qwen...-GGUF", so both the author and the suffix stay
+pub fn truncate_middle(text: &str, max_chars: usize) -> String {
+    let len = text.chars().count();
+    if len <= max_chars {
+        return text.to_string();
+    }
+    let keep = max_chars.saturating_sub(3);
+    let head: String = text.chars().take(keep - keep / 2).collect();
+    let tail: String = text.chars().skip(len - keep / 2).collect();
+    format!("{}...{}", head, tail)
+}
+
+/// Name of a file in progress displays: "`model_id`: `filename`", with the
+/// model ID shortened to `model_chars` characters; many repositories use
+/// the same file names (model-Q4_K_M.gguf), so the file alone is ambiguous
+pub fn progress_name(model_id: &str, filename: &str, model_chars: usize) -> String {
+    if model_id.is_empty() {
+        return filename.to_string();
+    }
+    format!("{}: {}", truncate_middle(model_id, model_chars), filename)
+}
+
+/// Fuzzy match rank of `query` in `text` (both expected lowercase)
+///
+/// 0 for a prefix match, 1 for a substring match and 2 when the query
+/// characters only appear in order (subsequence); `None` if they don't.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<u8> {
+    if query.is_empty() || text.starts_with(query) {
+        Some(0)
+    } else if text.contains(query) {
+        Some(1)
+    } else {
+        let mut haystack = text.chars();
+        query
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .all(|c| haystack.any(|h| h == c))
+            .then_some(2)
+    }
+}
+
+/// Shell-style wildcard match of a repository path: `*` matches any run of
+/// characters (including `/`, so `*.gguf` also matches files in subfolders)
+/// and `?` a single character
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    // Position after the last `*` and the text position it currently covers up to
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                p += 1;
+                backtrack = Some((p, t));
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                Some((star_p, star_t)) => {
+                    p = star_p;
+                    t = star_t + 1;
+                    backtrack = Some((star_p, star_t + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// A commit sha shortened the way git shows it (7 characters)
+pub fn short_commit(commit: &str) -> &str {
+    commit.get(..7).unwrap_or(commit)
+}
+
+/// Format a duration in seconds compactly, e.g. 45s, 12m 30s, 2h 05m
+pub fn format_duration(secs: u64) -> String {
+    if secs < 60 {
+        format!("{}s", secs)
+    } else if secs < 3_600 {
+        format!("{}m {:02}s", secs / 60, secs % 60)
+    } else {
+        format!("{}h {:02}m", secs / 3_600, (secs % 3_600) / 60)
+    }
+}
+
+/// A configured directory with `~/` expanded to the home directory
+pub fn expand_home(directory: &str) -> std::path::PathBuf {
+    match directory.strip_prefix("~/") {
+        Some(rest) => {
+            let home = std::env::var("HOME").unwrap_or_else(|_| "/tmp".to_string());
+            std::path::PathBuf::from(home).join(rest)
+        }
+        None => std::path::PathBuf::from(directory),
+    }
+}
+
+/// Free space (bytes available to the current user) on the filesystem holding
+/// `path`; a path that doesn't exist yet is resolved to its nearest existing parent
+#[cfg(unix)]
+pub fn available_space(path: &std::path::Path) -> Option<u64> {
+    use std::os::unix::ffi::OsStrExt;
+
+    let existing = path.ancestors().find(|p| p.exists())?;
+    let c_path = std::ffi::CString::new(existing.as_os_str().as_bytes()).ok()?;
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    // SAFETY: c_path is a valid NUL-terminated string and stat is a valid out pointer
+    if unsafe { libc::statvfs(c_path.as_ptr(), &mut stat) } != 0 {
+        return None;
+    }
+    #[allow(clippy::unnecessary_cast)] // field types differ between platforms
+    Some(stat.f_bavail as u64 * stat.f_frsize as u64)
+}
+
+#[cfg(not(unix))]
+pub fn available_space(_path: &std::path::Path) -> Option<u64> {
+    None
+}
+
+/// Run the calling thread at background priority: idle I/O class and
+/// lowest CPU priority on Linux, background QoS on macOS, a no-op elsewhere.
+/// Best effort; failures are ignored.
+#[cfg(target_os = "linux")]
+pub fn lower_thread_priority() {
+    // Linux applies both to the calling thread when `who` is 0
+    const IOPRIO_WHO_PROCESS: libc::c_long = 1;
+    const IOPRIO_CLASS_IDLE: libc::c_long = 3;
+    const IOPRIO_CLASS_SHIFT: libc::c_long = 13;
+    // SAFETY: plain syscalls without pointer arguments
+    unsafe {
+        libc::syscall(
+            libc::SYS_ioprio_set,
+            IOPRIO_WHO_PROCESS,
+            0 as libc::c_long,
+            IOPRIO_CLASS_IDLE << IOPRIO_CLASS_SHIFT,
+        );
+        libc::setpriority(libc::PRIO_PROCESS as _, 0, 19);
+    }
+}
+
+#[cfg(target_os = "macos")]
+pub fn lower_thread_priority() {
+    // SAFETY: only changes the QoS class of the calling thread
+    unsafe {
+        libc::pthread_set_qos_class_self_np(libc::qos_class_t::QOS_CLASS_BACKGROUND, 0);
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+pub fn lower_thread_priority() {}
@@ -1,11 +1,14 @@
 use crate::models::{
-    DownloadRegistry, DownloadStatus, VerificationProgress, VerificationQueueItem,
+    DownloadRegistry, DownloadStatus, ExpectedHash, QueueState, VerificationProgress,
+    VerificationQueueItem,
 };
+use sha1::Sha1;
+use sha2::digest::DynDigest;
 use sha2::{Digest, Sha256};
+use std::io::Read;
 use std::path::{Path, PathBuf};
-use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicU8, AtomicUsize, Ordering};
 use std::sync::Arc;
-use tokio::io::AsyncReadExt;
 use tokio::sync::{mpsc, Mutex, Semaphore};
 
 /// Global verification configuration (thread-safe, runtime-modifiable)
@@ -13,6 +16,10 @@ pub struct VerificationConfig {
     pub concurrent_verifications: AtomicUsize,
     pub buffer_size: AtomicUsize,
     pub update_interval_iterations: AtomicUsize,
+    /// Hash on a dedicated background-priority thread (see `lower_thread_priority`)
+    pub low_priority: AtomicBool,
+    /// [`VerificationSchedule`] as u8
+    schedule: AtomicU8,
 }
 
 impl VerificationConfig {
@@ -21,27 +28,118 @@ impl VerificationConfig {
             concurrent_verifications: AtomicUsize::new(2),
             buffer_size: AtomicUsize::new(128 * 1024),
             update_interval_iterations: AtomicUsize::new(100),
+            low_priority: AtomicBool::new(false),
+            schedule: AtomicU8::new(VerificationSchedule::Immediate as u8),
         }
     }
+
+    pub fn schedule(&self) -> VerificationSchedule {
+        VerificationSchedule::from_u8(self.schedule.load(Ordering::Relaxed))
+    }
+
+    pub fn set_schedule(&self, schedule: VerificationSchedule) {
+        self.schedule.store(schedule as u8, Ordering::Relaxed);
+    }
+}
+
+impl Default for VerificationConfig {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 pub static VERIFICATION_CONFIG: VerificationConfig = VerificationConfig::new();
 
+/// When queued verifications run relative to downloads
+///
+/// Hashing competes with downloads for disk I/O, so it can be throttled or
+/// held back until the download queue is empty.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VerificationSchedule {
+    /// Verify files as soon as they finish downloading
+    Immediate = 0,
+    /// Verify one file at a time while downloads are active
+    Deprioritized = 1,
+    /// Hold verification until the whole download queue has finished
+    AfterDownloads = 2,
+}
+
+impl VerificationSchedule {
+    fn from_u8(value: u8) -> Self {
+        match value {
+            1 => Self::Deprioritized,
+            2 => Self::AfterDownloads,
+            _ => Self::Immediate,
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            Self::Immediate => Self::Deprioritized,
+            Self::Deprioritized => Self::AfterDownloads,
+            Self::AfterDownloads => Self::Immediate,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Immediate => "immediately",
+            Self::Deprioritized => "one at a time while downloading",
+            Self::AfterDownloads => "after all downloads finish",
+        }
+    }
+
+    /// Files that may be hashed at once, given whether downloads are active
+    fn limit(self, downloads_active: bool) -> usize {
+        let configured = VERIFICATION_CONFIG
+            .concurrent_verifications
+            .load(Ordering::Relaxed)
+            .max(1);
+        match (self, downloads_active) {
+            (Self::Deprioritized, true) => 1,
+            (Self::AfterDownloads, true) => 0,
+            _ => configured,
+        }
+    }
+}
+
 /// Main verification worker that processes the verification queue
-/// Runs continuously in the background, processing items as they arrive
+/// Runs continuously in the background, hashing up to
+/// `concurrent_verifications` files at once (fewer while downloads in
+/// `download_queue` are pending, depending on the [`VerificationSchedule`]).
+/// Each file in flight gets its own entry in `verification_progress`.
 pub async fn verification_worker(
     verification_queue: Arc<Mutex<Vec<VerificationQueueItem>>>,
     verification_progress: Arc<Mutex<Vec<VerificationProgress>>>,
     verification_queue_size: Arc<AtomicUsize>,
     status_tx: mpsc::UnboundedSender<String>,
     download_registry: Arc<Mutex<DownloadRegistry>>,
+    download_queue: Arc<Mutex<QueueState>>,
 ) {
-    let max_concurrent = VERIFICATION_CONFIG
-        .concurrent_verifications
-        .load(Ordering::Relaxed);
-    let semaphore = Arc::new(Semaphore::new(max_concurrent));
+    let mut max_concurrent = 0;
+    let semaphore = Arc::new(Semaphore::new(0));
 
     loop {
+        // Follow the concurrency setting (Options popup) and the schedule
+        let downloads_active = download_queue.lock().await.size > 0;
+        let wanted = VERIFICATION_CONFIG.schedule().limit(downloads_active);
+        if wanted > max_concurrent {
+            semaphore.add_permits(wanted - max_concurrent);
+            max_concurrent = wanted;
+        } else if wanted < max_concurrent {
+            // Permits held by running tasks are returned later, so shrink gradually
+            max_concurrent -= semaphore.forget_permits(max_concurrent - wanted);
+        }
+
+        // Take a free slot before taking an item, so queued files stay
+        // counted in the queue size until a worker actually starts on them.
+        // Polling (rather than waiting) lets returned permits be forgotten
+        // when the limit drops.
+        let Ok(permit) = semaphore.clone().try_acquire_owned() else {
+            tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+            continue;
+        };
+
         // Check if there's work to do - remove item and decrement size atomically while holding lock
         let item = {
             let mut queue = verification_queue.lock().await;
@@ -56,7 +154,6 @@ pub async fn verification_worker(
         };
 
         if let Some(item) = item {
-            let permit = semaphore.clone().acquire_owned().await.unwrap();
             let verification_progress = verification_progress.clone();
             let status_tx = status_tx.clone();
             let download_registry = download_registry.clone();
@@ -67,6 +164,7 @@ pub async fn verification_worker(
             });
         } else {
             // No work, sleep briefly
+            drop(permit);
             tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
         }
     }
@@ -92,48 +190,78 @@ async fn verify_file(
 
     // Add to active verifications
     let verified_bytes = Arc::new(AtomicU64::new(0));
+    let skipped = Arc::new(AtomicBool::new(false));
     {
         let mut progress = verification_progress.lock().await;
         progress.push(VerificationProgress {
+            model_id: item.model_id.clone(),
             filename: item.filename.clone(),
+            local_path: item.local_path.clone(),
             verified_bytes: verified_bytes.clone(),
             total_bytes: item.total_size,
             speed_mbps: 0.0,
+            skipped: skipped.clone(),
         });
     }
 
     let _ = status_tx.send(format!("Verifying integrity of {}...", item.filename));
 
-    // Calculate hash with progress tracking (use filename as identifier)
-    match calculate_sha256_with_progress(
-        &local_path,
-        &verification_progress,
-        &item.filename,
+    // Calculate hash with progress tracking (the byte counter identifies the
+    // entry, since files with the same name may be verified side by side)
+    match calculate_hash_with_progress(
+        local_path,
+        HashKind::of(&item.expected_hash),
+        verification_progress.clone(),
+        verified_bytes.clone(),
+        skipped,
         item.total_size,
     )
     .await
     {
-        Ok(calculated_hash) => {
-            if calculated_hash == item.expected_sha256 {
+        Ok(None) => {
+            let _ = status_tx.send(format!("Skipped verification of {}", item.filename));
+        }
+        Ok(Some(calculated_hash)) => {
+            if calculated_hash == item.expected_hash.hex() {
                 let _ = status_tx.send(format!("✓ Hash verified for {}", item.filename));
+                if !item.is_manual {
+                    crate::archive::extract_if_enabled(&item.local_path, &status_tx).await;
+                }
             } else {
+                crate::metrics::record_error(crate::metrics::ErrorKind::HashMismatch);
+                crate::webhook::verification_failed(
+                    &item.model_id,
+                    &item.filename,
+                    &item.local_path,
+                    item.total_size,
+                    "hash mismatch",
+                    &status_tx,
+                );
                 let _ = status_tx.send(format!(
                     "✗ Hash mismatch for {}: expected {}..., got {}...",
                     item.filename,
-                    &item.expected_sha256[..16],
+                    &item.expected_hash.hex()[..16],
                     &calculated_hash[..16]
                 ));
 
-                // Update registry to HashMismatch
+                // A bad blob must not be linked again
+                crate::blobstore::discard_blob(Path::new(&item.local_path));
+
+                // Update registry to HashMismatch, both on disk and in memory
+                // (the in-memory copy may be stale or empty, so it isn't saved
+                // wholesale)
                 let mut registry = download_registry.lock().await;
-                if let Some(entry) = registry
-                    .downloads
-                    .iter_mut()
-                    .find(|d| d.local_path == item.local_path)
-                {
-                    entry.status = DownloadStatus::HashMismatch;
+                let mut on_disk = crate::registry::load_registry();
+                for reg in [&mut *registry, &mut on_disk] {
+                    if let Some(entry) = reg
+                        .downloads
+                        .iter_mut()
+                        .find(|d| d.local_path == item.local_path)
+                    {
+                        entry.status = DownloadStatus::HashMismatch;
+                    }
                 }
-                crate::registry::save_registry(&registry);
+                crate::registry::save_registry(&on_disk);
             }
         }
         Err(e) => {
@@ -141,25 +269,100 @@ async fn verify_file(
                 "Warning: Failed to verify {}: {}",
                 item.filename, e
             ));
+            crate::webhook::verification_failed(
+                &item.model_id,
+                &item.filename,
+                &item.local_path,
+                item.total_size,
+                &e.to_string(),
+                &status_tx,
+            );
         }
     }
 
     // Remove from active verifications
     {
         let mut progress = verification_progress.lock().await;
-        progress.retain(|p| p.filename != item.filename);
+        progress.retain(|p| !Arc::ptr_eq(&p.verified_bytes, &verified_bytes));
     }
 }
 
-/// Calculate SHA256 hash of a file with progress tracking
-async fn calculate_sha256_with_progress(
+/// Which checksum an expected hash is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HashKind {
+    /// LFS object id: SHA-256 of the content (64 hex digits)
+    Sha256,
+    /// Git blob id of a non-LFS file: SHA-1 of `blob <len>\0` + content (40 hex digits)
+    GitBlob,
+}
+
+impl HashKind {
+    fn of(expected: &ExpectedHash) -> Self {
+        match expected {
+            ExpectedHash::Sha256(_) => Self::Sha256,
+            ExpectedHash::GitBlob(_) => Self::GitBlob,
+        }
+    }
+}
+
+/// Calculate the SHA256 (or git blob SHA-1) of a file with progress tracking
+///
+/// Hashing runs on a blocking thread; with `low_priority` set it gets a
+/// dedicated thread at background I/O and CPU priority instead, so it doesn't
+/// starve downloads or other work on the machine.
+/// Returns `None` if `skipped` was set before hashing finished.
+async fn calculate_hash_with_progress(
+    file_path: PathBuf,
+    kind: HashKind,
+    verification_progress: Arc<Mutex<Vec<VerificationProgress>>>,
+    verified_bytes: Arc<AtomicU64>,
+    skipped: Arc<AtomicBool>,
+    total_size: u64,
+) -> Result<Option<String>, Box<dyn std::error::Error + Send + Sync>> {
+    let hash = move || {
+        hash_file(
+            &file_path,
+            kind,
+            &verification_progress,
+            &verified_bytes,
+            &skipped,
+            total_size,
+        )
+    };
+
+    let result = if VERIFICATION_CONFIG.low_priority.load(Ordering::Relaxed) {
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        std::thread::Builder::new()
+            .name("verify-low-priority".to_string())
+            .spawn(move || {
+                crate::utils::lower_thread_priority();
+                let _ = tx.send(hash());
+            })?;
+        rx.await?
+    } else {
+        tokio::task::spawn_blocking(hash).await?
+    };
+    Ok(result?)
+}
+
+/// Blocking hashing loop behind [`calculate_hash_with_progress`]
+fn hash_file(
     file_path: &Path,
-    verification_progress: &Arc<Mutex<Vec<VerificationProgress>>>,
-    filename: &str,
+    kind: HashKind,
+    verification_progress: &Mutex<Vec<VerificationProgress>>,
+    verified_bytes: &Arc<AtomicU64>,
+    skipped: &AtomicBool,
     total_size: u64,
-) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
-    let mut file = tokio::fs::File::open(file_path).await?;
-    let mut hasher = Sha256::new();
+) -> std::io::Result<Option<String>> {
+    let mut file = std::fs::File::open(file_path)?;
+    let mut hasher: Box<dyn DynDigest> = match kind {
+        HashKind::Sha256 => Box::new(Sha256::new()),
+        HashKind::GitBlob => {
+            let mut sha1 = Sha1::new();
+            Digest::update(&mut sha1, format!("blob {}\0", file.metadata()?.len()));
+            Box::new(sha1)
+        }
+    };
     let buffer_size = VERIFICATION_CONFIG.buffer_size.load(Ordering::Relaxed);
     let mut buffer = vec![0u8; buffer_size];
 
@@ -169,20 +372,12 @@ async fn calculate_sha256_with_progress(
     let mut last_update = start_time;
     let mut last_bytes = 0u64;
 
-    // Get the Arc<AtomicU64> reference for atomic updates
-    let verified_bytes = {
-        let progress = verification_progress.lock().await;
-        progress
-            .iter()
-            .find(|p| p.filename == filename)
-            .map(|p| p.verified_bytes.clone())
-            // NOTE: If progress entry is removed mid-verification (e.g., cancellation),
-            // verified_bytes becomes None. This is safe - we simply stop atomic updates.
-            // The verification will still complete and final progress will be cleared.
-    };
-
     loop {
-        let bytes_read = file.read(&mut buffer).await?;
+        if skipped.load(Ordering::Relaxed) {
+            return Ok(None);
+        }
+
+        let bytes_read = file.read(&mut buffer)?;
         if bytes_read == 0 {
             break;
         }
@@ -199,9 +394,7 @@ async fn calculate_sha256_with_progress(
         // is_multiple_of() not available in Rust 1.75.0 (Ubuntu 22.04)
         if iteration % (update_interval as u64) == 0 || bytes_verified >= total_size {
             // Atomic update for verified_bytes - NO LOCK NEEDED!
-            if let Some(ref vb) = verified_bytes {
-                vb.fetch_add(bytes_read as u64, Ordering::Relaxed);
-            }
+            verified_bytes.store(bytes_verified, Ordering::Relaxed);
 
             let now = std::time::Instant::now();
             let elapsed = now.duration_since(last_update).as_secs_f64();
@@ -210,9 +403,12 @@ async fn calculate_sha256_with_progress(
                 let bytes_since_last = bytes_verified - last_bytes;
                 let speed = (bytes_since_last as f64 / elapsed) / 1_048_576.0;
 
-                // Find and update progress by filename (not index)
-                let mut progress = verification_progress.lock().await;
-                if let Some(entry) = progress.iter_mut().find(|p| p.filename == filename) {
+                // Find and update this file's progress entry (not by index)
+                let mut progress = verification_progress.blocking_lock();
+                if let Some(entry) = progress
+                    .iter_mut()
+                    .find(|p| Arc::ptr_eq(&p.verified_bytes, verified_bytes))
+                {
                     entry.speed_mbps = speed;
                 }
 
@@ -223,12 +419,24 @@ async fn calculate_sha256_with_progress(
     }
 
     // Final progress update to ensure 100%
-    // If verified_bytes is Some, update atomically; otherwise entry was removed (cancellation)
-    if let Some(ref vb) = verified_bytes {
-        vb.store(total_size, Ordering::Relaxed);
-    }
+    verified_bytes.store(total_size, Ordering::Relaxed);
+
+    Ok(Some(hex::encode(hasher.finalize())))
+}
 
-    Ok(hex::encode(hasher.finalize()))
+/// Hash a file outside the verification queue (blocking, no progress
+/// entry) and compare it with an expected SHA-256 or git blob id
+pub fn file_matches_hash(file_path: &Path, expected: &ExpectedHash) -> std::io::Result<bool> {
+    let total_size = std::fs::metadata(file_path)?.len();
+    let hash = hash_file(
+        file_path,
+        HashKind::of(expected),
+        &Mutex::new(Vec::new()),
+        &Arc::new(AtomicU64::new(0)),
+        &AtomicBool::new(false),
+        total_size,
+    )?;
+    Ok(hash.as_deref() == Some(expected.hex()))
 }
 
 /// Queue a file for verification
@@ -242,3 +450,33 @@ pub async fn queue_verification(
 
     verification_queue_size.fetch_add(1, Ordering::Relaxed);
 }
+
+/// Skip verification of the file at `local_path`
+///
+/// Drops it from the queue, or stops hashing it if it is already being
+/// verified. Returns false if the file was neither queued nor in progress.
+pub async fn skip_verification(
+    verification_queue: &Mutex<Vec<VerificationQueueItem>>,
+    verification_queue_size: &AtomicUsize,
+    verification_progress: &Mutex<Vec<VerificationProgress>>,
+    local_path: &str,
+) -> bool {
+    let removed = {
+        let mut queue = verification_queue.lock().await;
+        let before = queue.len();
+        queue.retain(|item| item.local_path != local_path);
+        let removed = before - queue.len();
+        verification_queue_size.fetch_sub(removed, Ordering::Relaxed);
+        removed
+    };
+
+    let mut stopped = false;
+    for progress in verification_progress.lock().await.iter() {
+        if progress.local_path == local_path {
+            progress.skipped.store(true, Ordering::Relaxed);
+            stopped = true;
+        }
+    }
+
+    removed > 0 || stopped
+}
@@ -0,0 +1,299 @@
+//! Watchlist of repositories checked for upstream changes
+//!
+//! Each watched repository is stored in `~/.config/jreb/watchlist.toml` with
+//! the `lastModified` timestamp, commit and a snapshot of its files (path,
+//! size and git object id) from the last check. [`check`] compares every
+//! entry against the Hub and reports which files were added, removed or
+//! modified since, and since the commit the local copy was downloaded from
+//! when the registry records one.
+
+use crate::api::{self, ApiError};
+use crate::models::ModelFile;
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// Number of watched repositories checked at once
+const CHECK_CONCURRENCY: usize = 4;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Watchlist {
+    #[serde(default)]
+    pub models: Vec<WatchedModel>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchedModel {
+    pub model_id: String,
+    #[serde(default)]
+    pub last_modified: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+    #[serde(default)]
+    pub files: Vec<WatchedFile>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WatchedFile {
+    pub path: String,
+    pub size: u64,
+    #[serde(default)]
+    pub oid: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FileChangeKind {
+    Added,
+    Removed,
+    Modified,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FileChange {
+    pub path: String,
+    pub kind: FileChangeKind,
+}
+
+/// A watched repository whose `lastModified` moved since the last check
+#[derive(Debug, Clone, Serialize)]
+pub struct WatchUpdate {
+    pub model_id: String,
+    pub previous_modified: Option<String>,
+    pub last_modified: Option<String>,
+    /// Current commit of the main branch
+    pub commit: Option<String>,
+    pub changes: Vec<FileChange>,
+    /// Commit the local copy was downloaded from, when recorded and behind
+    pub downloaded_commit: Option<String>,
+    /// Files changed between `downloaded_commit` and `commit`
+    pub since_download: Vec<FileChange>,
+}
+
+/// Result of checking the whole watchlist
+#[derive(Debug, Default)]
+pub struct WatchCheck {
+    pub checked: usize,
+    pub updates: Vec<WatchUpdate>,
+    /// Repositories that could not be checked, with the error message
+    pub errors: Vec<(String, String)>,
+}
+
+impl Watchlist {
+    pub fn contains(&self, model_id: &str) -> bool {
+        self.models.iter().any(|m| m.model_id == model_id)
+    }
+
+    /// Remove a repository; returns false if it was not watched
+    pub fn remove(&mut self, model_id: &str) -> bool {
+        let before = self.models.len();
+        self.models.retain(|m| m.model_id != model_id);
+        self.models.len() != before
+    }
+}
+
+pub fn get_watchlist_path() -> PathBuf {
+    let home = std::env::var("HOME").unwrap_or_else(|_| "/tmp".to_string());
+    PathBuf::from(format!("{}/.config/jreb/watchlist.toml", home))
+}
+
+pub fn load_watchlist() -> Watchlist {
+    fs::read_to_string(get_watchlist_path())
+        .ok()
+        .and_then(|contents| toml::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_watchlist(watchlist: &Watchlist) -> std::io::Result<()> {
+    let path = get_watchlist_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let toml_string = toml::to_string_pretty(watchlist).map_err(std::io::Error::other)?;
+    fs::write(path, toml_string)
+}
+
+/// Fetch the current state of a repository for the watchlist
+pub async fn snapshot(model_id: &str, token: Option<&String>) -> Result<WatchedModel, ApiError> {
+    let info = api::fetch_model_info(model_id, token).await?;
+    let files = api::fetch_repo_files(&info.id, token).await?;
+    Ok(WatchedModel {
+        model_id: info.id,
+        last_modified: info.last_modified,
+        commit: info.sha,
+        files: files.into_iter().map(watched_file).collect(),
+    })
+}
+
+fn watched_file(file: ModelFile) -> WatchedFile {
+    WatchedFile {
+        path: file.path,
+        size: file.size,
+        oid: file.lfs.map(|lfs| lfs.oid).or(file.oid),
+    }
+}
+
+/// Store a snapshot in the watchlist (replacing any previous one)
+pub fn watch(watched: WatchedModel) -> std::io::Result<()> {
+    let mut watchlist = load_watchlist();
+    watchlist.remove(&watched.model_id);
+    watchlist.models.push(watched);
+    save_watchlist(&watchlist)
+}
+
+/// Stop watching a repository; returns false if it was not watched
+pub fn unwatch(model_id: &str) -> std::io::Result<bool> {
+    let mut watchlist = load_watchlist();
+    let removed = watchlist.remove(model_id);
+    if removed {
+        save_watchlist(&watchlist)?;
+    }
+    Ok(removed)
+}
+
+/// Files added, removed or modified between two snapshots, sorted by path
+pub fn diff_files(old: &[WatchedFile], new: &[WatchedFile]) -> Vec<FileChange> {
+    let old_by_path: HashMap<&str, &WatchedFile> =
+        old.iter().map(|f| (f.path.as_str(), f)).collect();
+    let new_by_path: HashMap<&str, &WatchedFile> =
+        new.iter().map(|f| (f.path.as_str(), f)).collect();
+
+    let mut changes: Vec<FileChange> = new
+        .iter()
+        .filter_map(|file| {
+            let kind = match old_by_path.get(file.path.as_str()) {
+                None => FileChangeKind::Added,
+                Some(previous) if *previous != file => FileChangeKind::Modified,
+                Some(_) => return None,
+            };
+            Some(FileChange {
+                path: file.path.clone(),
+                kind,
+            })
+        })
+        .chain(
+            old.iter()
+                .filter(|file| !new_by_path.contains_key(file.path.as_str()))
+                .map(|file| FileChange {
+                    path: file.path.clone(),
+                    kind: FileChangeKind::Removed,
+                }),
+        )
+        .collect();
+
+    changes.sort_by(|a, b| a.path.cmp(&b.path));
+    changes
+}
+
+/// Compare every watched repository against the Hub
+///
+/// Updated repositories get a fresh snapshot saved, so each change is
+/// reported once. The file listing is only fetched when `lastModified` moved.
+pub async fn check(token: Option<&String>) -> WatchCheck {
+    let watchlist = load_watchlist();
+    let downloaded = crate::registry::downloaded_commits(&crate::registry::load_registry());
+
+    let mut result = WatchCheck {
+        checked: watchlist.models.len(),
+        ..Default::default()
+    };
+
+    let results: Vec<(String, CheckOutcome)> = futures::stream::iter(watchlist.models)
+        .map(|watched| {
+            let downloaded = downloaded.get(&watched.model_id).cloned();
+            async move {
+                let outcome = check_model(&watched, downloaded, token).await;
+                (watched.model_id, outcome)
+            }
+        })
+        .buffer_unordered(CHECK_CONCURRENCY)
+        .collect()
+        .await;
+
+    let mut refreshed = Vec::new();
+    for (model_id, outcome) in results {
+        match outcome {
+            Ok(Some((watched, update))) => {
+                refreshed.push(watched);
+                result.updates.push(update);
+            }
+            Ok(None) => {}
+            Err(e) => result.errors.push((model_id, e.to_string())),
+        }
+    }
+    result.updates.sort_by(|a, b| a.model_id.cmp(&b.model_id));
+
+    if !refreshed.is_empty() {
+        // Re-read so entries added or removed during the check are kept
+        let mut watchlist = load_watchlist();
+        for watched in refreshed {
+            if let Some(entry) = watchlist
+                .models
+                .iter_mut()
+                .find(|m| m.model_id == watched.model_id)
+            {
+                *entry = watched;
+            }
+        }
+        if let Err(e) = save_watchlist(&watchlist) {
+            result
+                .errors
+                .push(("watchlist".to_string(), format!("Failed to save: {}", e)));
+        }
+    }
+
+    result
+}
+
+/// Refreshed snapshot and change report, or `None` if the repository is unchanged
+type CheckOutcome = Result<Option<(WatchedModel, WatchUpdate)>, ApiError>;
+
+async fn check_model(
+    watched: &WatchedModel,
+    downloaded_commit: Option<String>,
+    token: Option<&String>,
+) -> CheckOutcome {
+    let info = api::fetch_model_info(&watched.model_id, token).await?;
+    if info.last_modified == watched.last_modified {
+        return Ok(None);
+    }
+
+    let files: Vec<WatchedFile> = api::fetch_repo_files(&watched.model_id, token)
+        .await?
+        .into_iter()
+        .map(watched_file)
+        .collect();
+
+    // The local copy's exact files, when its commit is recorded and behind
+    // (best effort: a listing that can't be fetched leaves this out)
+    let downloaded_commit = downloaded_commit.filter(|commit| info.sha.as_ref() != Some(commit));
+    let since_download = match &downloaded_commit {
+        Some(commit) => match api::fetch_repo_files_at(&watched.model_id, commit, token).await {
+            Ok(old) => {
+                let old: Vec<WatchedFile> = old.into_iter().map(watched_file).collect();
+                diff_files(&old, &files)
+            }
+            Err(_) => Vec::new(),
+        },
+        None => Vec::new(),
+    };
+
+    let update = WatchUpdate {
+        model_id: watched.model_id.clone(),
+        previous_modified: watched.last_modified.clone(),
+        last_modified: info.last_modified.clone(),
+        commit: info.sha.clone(),
+        changes: diff_files(&watched.files, &files),
+        downloaded_commit,
+        since_download,
+    };
+    let refreshed = WatchedModel {
+        model_id: watched.model_id.clone(),
+        last_modified: info.last_modified,
+        commit: info.sha,
+        files,
+    };
+    Ok(Some((refreshed, update)))
+}
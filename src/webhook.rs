@@ -0,0 +1,276 @@
+//! Webhook notifications for automation
+//!
+//! `[[webhooks]]` entries of the config file are POSTed to when a download
+//! completes, a verification fails or the download queue drains, so long
+//! headless jobs can notify n8n, Gotify, Discord and the like. Deliveries run
+//! in the background; [`flush`] waits for them before a headless run exits.
+
+use crate::models::{WebhookConfig, WebhookEvent};
+use std::sync::{Mutex, RwLock};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+/// Timeout of one webhook request, and of [`flush`]
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+static WEBHOOKS: RwLock<Vec<WebhookConfig>> = RwLock::new(Vec::new());
+
+static PENDING: Mutex<Vec<JoinHandle<()>>> = Mutex::new(Vec::new());
+
+/// Set the webhooks to notify (from the config file)
+pub fn configure(webhooks: Vec<WebhookConfig>) {
+    if let Ok(mut configured) = WEBHOOKS.write() {
+        *configured = webhooks;
+    }
+}
+
+/// What a notification is about; fields that don't apply stay empty
+#[derive(Debug, Clone, Default)]
+pub struct WebhookDetails {
+    pub model_id: String,
+    pub filename: String,
+    pub path: String,
+    pub size: u64,
+    pub message: String,
+}
+
+/// Escape `text` for use inside a JSON string
+fn json_escape(text: &str) -> String {
+    let quoted = serde_json::to_string(text).unwrap_or_default();
+    quoted[1..quoted.len() - 1].to_string()
+}
+
+/// The request body for `webhook`
+pub fn render_payload(
+    webhook: &WebhookConfig,
+    event: WebhookEvent,
+    details: &WebhookDetails,
+) -> String {
+    match &webhook.template {
+        // Values are escaped for JSON, which every template of the common
+        // services (n8n, Gotify, Discord, ntfy) is
+        Some(template) => {
+            // One pass over the template, so placeholders inside the
+            // substituted values (a message quoting "{path}") stay as they are
+            let mut body = String::with_capacity(template.len());
+            let mut rest = template.as_str();
+            while let Some(start) = rest.find('{') {
+                body.push_str(&rest[..start]);
+                rest = &rest[start..];
+                let name = rest[1..].find('}').map(|end| &rest[1..end + 1]);
+                let value = match name {
+                    Some("event") => event.name().to_string(),
+                    Some("model_id") => json_escape(&details.model_id),
+                    Some("filename") => json_escape(&details.filename),
+                    Some("path") => json_escape(&details.path),
+                    Some("size") => details.size.to_string(),
+                    Some("message") => json_escape(&details.message),
+                    _ => {
+                        body.push('{');
+                        rest = &rest[1..];
+                        continue;
+                    }
+                };
+                body.push_str(&value);
+                rest = &rest[name.map_or(0, str::len) + 2..];
+            }
+            body.push_str(rest);
+            body
+        }
+        None => serde_json::json!({
+            "event": event.name(),
+            "model_id": details.model_id,
+            "filename": details.filename,
+            "path": details.path,
+            "size": details.size,
+            "message": details.message,
+        })
+        .to_string(),
+    }
+}
+
+/// Send `event` to every webhook subscribed to it, in the background.
+/// Failed deliveries are reported on `status_tx`.
+pub fn notify(
+    event: WebhookEvent,
+    details: WebhookDetails,
+    status_tx: &mpsc::UnboundedSender<String>,
+) {
+    let webhooks: Vec<WebhookConfig> = match WEBHOOKS.read() {
+        Ok(webhooks) => webhooks
+            .iter()
+            .filter(|webhook| webhook.events.is_empty() || webhook.events.contains(&event))
+            .cloned()
+            .collect(),
+        Err(_) => return,
+    };
+
+    for webhook in webhooks {
+        let body = render_payload(&webhook, event, &details);
+        let status_tx = status_tx.clone();
+        let handle = tokio::spawn(async move {
+            let mut request = crate::http_client::SHARED_CLIENT
+                .post(&webhook.url)
+                .timeout(DELIVERY_TIMEOUT)
+                .header(reqwest::header::CONTENT_TYPE, &webhook.content_type)
+                .body(body);
+            let user_agent = crate::http_client::user_agent();
+            if !user_agent.is_empty() {
+                request = request.header(reqwest::header::USER_AGENT, user_agent);
+            }
+            let host = reqwest::Url::parse(&webhook.url)
+                .ok()
+                .and_then(|url| url.host_str().map(str::to_string))
+                .unwrap_or_else(|| webhook.url.clone());
+            match request.send().await.and_then(|r| r.error_for_status()) {
+                Ok(_) => {}
+                Err(e) => {
+                    let reason = match e.status() {
+                        Some(status) => status.to_string(),
+                        None => e.without_url().to_string(),
+                    };
+                    let _ = status_tx.send(format!(
+                        "Warning: {} webhook to {} failed: {}",
+                        event.name(),
+                        host,
+                        reason
+                    ));
+                }
+            }
+        });
+        if let Ok(mut pending) = PENDING.lock() {
+            pending.retain(|handle| !handle.is_finished());
+            pending.push(handle);
+        }
+    }
+}
+
+/// Notify of a file downloaded completely
+pub fn download_complete(
+    model_id: &str,
+    filename: &str,
+    path: &str,
+    size: u64,
+    status_tx: &mpsc::UnboundedSender<String>,
+) {
+    notify(
+        WebhookEvent::DownloadComplete,
+        WebhookDetails {
+            model_id: model_id.to_string(),
+            filename: filename.to_string(),
+            path: path.to_string(),
+            size,
+            message: format!(
+                "Downloaded {}/{} ({})",
+                model_id,
+                filename,
+                crate::utils::format_size(size)
+            ),
+        },
+        status_tx,
+    );
+}
+
+/// Notify of a file that failed verification (`reason` says why)
+pub fn verification_failed(
+    model_id: &str,
+    filename: &str,
+    path: &str,
+    size: u64,
+    reason: &str,
+    status_tx: &mpsc::UnboundedSender<String>,
+) {
+    notify(
+        WebhookEvent::VerificationFailed,
+        WebhookDetails {
+            model_id: model_id.to_string(),
+            filename: filename.to_string(),
+            path: path.to_string(),
+            size,
+            message: format!(
+                "Verification of {}/{} failed: {}",
+                model_id, filename, reason
+            ),
+        },
+        status_tx,
+    );
+}
+
+/// Notify that the last queued download finished
+pub fn queue_drained(status_tx: &mpsc::UnboundedSender<String>) {
+    notify(
+        WebhookEvent::QueueDrained,
+        WebhookDetails {
+            message: "Download queue drained".to_string(),
+            ..Default::default()
+        },
+        status_tx,
+    );
+}
+
+/// Wait (up to the delivery timeout) for webhooks still being sent
+pub async fn flush() {
+    let pending = match PENDING.lock() {
+        Ok(mut pending) => std::mem::take(&mut *pending),
+        Err(_) => return,
+    };
+    let _ = tokio::time::timeout(DELIVERY_TIMEOUT, futures::future::join_all(pending)).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn webhook(template: Option<&str>) -> WebhookConfig {
+        WebhookConfig {
+            url: "http://localhost/hook".to_string(),
+            template: template.map(str::to_string),
+            events: Vec::new(),
+            content_type: "application/json".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_render_payload() {
+        let details = WebhookDetails {
+            model_id: "unsloth/{filename}".to_string(),
+            filename: "Qwen3-8B-Q4_K_M.gguf".to_string(),
+            path: "/models/{size}/\"a\"".to_string(),
+            size: 42,
+            message: "moved {path} to {message}".to_string(),
+        };
+
+        let template = r#"{"text": "{event}: {model_id} {filename} {path} {size} {message}"}"#;
+        assert_eq!(
+            render_payload(
+                &webhook(Some(template)),
+                WebhookEvent::DownloadComplete,
+                &details
+            ),
+            format!(
+                r#"{{"text": "{}: unsloth/{{filename}} Qwen3-8B-Q4_K_M.gguf /models/{{size}}/\"a\" 42 moved {{path}} to {{message}}"}}"#,
+                WebhookEvent::DownloadComplete.name()
+            )
+        );
+
+        // Unknown and unterminated placeholders are kept
+        assert_eq!(
+            render_payload(
+                &webhook(Some("{unknown} {{size}} {size")),
+                WebhookEvent::QueueDrained,
+                &details
+            ),
+            "{unknown} {42} {size"
+        );
+
+        let body: serde_json::Value = serde_json::from_str(&render_payload(
+            &webhook(None),
+            WebhookEvent::QueueDrained,
+            &details,
+        ))
+        .unwrap();
+        assert_eq!(body["message"], "moved {path} to {message}");
+        assert_eq!(body["size"], 42);
+    }
+}
@@ -0,0 +1,124 @@
+//! Headless commands run against a download daemon
+#![cfg(unix)]
+
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Output, Stdio};
+use std::time::{Duration, Instant};
+
+const BIN: &str = env!("CARGO_BIN_EXE_rust-hf-downloader");
+
+/// A daemon running with its own home directory; stopped when dropped
+struct Daemon {
+    home: PathBuf,
+    child: Child,
+}
+
+impl Daemon {
+    /// Start a daemon in `home` (see [`scratch_home`])
+    fn start(home: PathBuf) -> Daemon {
+        let child = command(&home, &["daemon", "start"])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap();
+
+        let socket = home.join("models/.hf-downloader.sock");
+        let started = Instant::now();
+        while std::os::unix::net::UnixStream::connect(&socket).is_err() {
+            assert!(
+                started.elapsed() < Duration::from_secs(10),
+                "the daemon did not start"
+            );
+            std::thread::sleep(Duration::from_millis(50));
+        }
+        Daemon { home, child }
+    }
+
+    fn run(&self, args: &[&str]) -> Output {
+        command(&self.home, args).output().unwrap()
+    }
+}
+
+impl Drop for Daemon {
+    fn drop(&mut self) {
+        let _ = self.run(&["daemon", "stop"]);
+        let started = Instant::now();
+        while matches!(self.child.try_wait(), Ok(None)) {
+            if started.elapsed() > Duration::from_secs(10) {
+                let _ = self.child.kill();
+                let _ = self.child.wait();
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(50));
+        }
+        let _ = std::fs::remove_dir_all(&self.home);
+    }
+}
+
+/// An empty home directory for one test
+fn scratch_home(name: &str) -> PathBuf {
+    let home = std::env::temp_dir().join(format!("{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&home);
+    std::fs::create_dir_all(home.join("models")).unwrap();
+    home
+}
+
+fn command(home: &Path, args: &[&str]) -> Command {
+    let mut command = Command::new(BIN);
+    command
+        .env("HOME", home)
+        .env_remove("HF_TOKEN")
+        .arg("--headless")
+        .arg("--offline")
+        .args(args);
+    command
+}
+
+#[test]
+fn test_write_commands_refused_while_daemon_runs() {
+    // A downloaded file in the registry, which `delete` must leave alone
+    let home = scratch_home("hf-downloader-daemon-write");
+    let model_dir = home.join("models/author/model");
+    std::fs::create_dir_all(&model_dir).unwrap();
+    let file = model_dir.join("model.gguf");
+    std::fs::write(&file, b"weights").unwrap();
+    let registry_path = home.join("models/hf-downloads.toml");
+    std::fs::write(
+        &registry_path,
+        format!(
+            r#"[[downloads]]
+model_id = "author/model"
+filename = "model.gguf"
+url = "https://huggingface.co/author/model/resolve/main/model.gguf"
+local_path = "{}"
+total_size = 7
+downloaded_size = 7
+status = "Complete"
+"#,
+            file.display()
+        ),
+    )
+    .unwrap();
+    let daemon = Daemon::start(home);
+    let registry = std::fs::read_to_string(&registry_path).unwrap();
+
+    for args in [
+        &["delete", "author/model"][..],
+        &["evict", "--keep-free", "1TB", "--yes"],
+        &["restore"],
+        &["adopt", "models"],
+        &["trash", "gc"],
+        &["blobs", "gc"],
+    ] {
+        let output = daemon.run(args);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert_eq!(output.status.code(), Some(1), "{:?}: {}", args, stderr);
+        assert!(stderr.contains("daemon stop"), "{:?}: {}", args, stderr);
+    }
+    assert!(file.exists());
+    assert_eq!(std::fs::read_to_string(&registry_path).unwrap(), registry);
+
+    // Read-only commands and dry runs still work
+    assert!(daemon.run(&["stats"]).status.success());
+    assert!(daemon.run(&["--dry-run", "trash", "gc"]).status.success());
+}